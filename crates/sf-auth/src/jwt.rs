@@ -7,6 +7,7 @@ use tracing::debug;
 
 use crate::credentials::SalesforceCredentials;
 use crate::error::{Error, ErrorKind, Result};
+use crate::oauth::post_form;
 
 /// JWT Bearer authentication for server-to-server integration.
 ///
@@ -104,12 +105,12 @@ impl JwtAuth {
         ];
         let body = serde_urlencoded::to_string(form_data)?;
 
-        let response = client
-            .post(format!("{}/services/oauth2/token", login_url))
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(body)
-            .send()
-            .await?;
+        let response = post_form(
+            &client,
+            format!("{}/services/oauth2/token", login_url),
+            body,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let error: OAuthErrorResponse = response.json().await?;
@@ -8,6 +8,7 @@
 //! Note: Device Code Flow has been intentionally excluded as it is being
 //! deprecated due to security concerns.
 
+use busbar_sf_client::trace;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 
@@ -127,13 +128,12 @@ impl OAuthClient {
 
         let body = serde_urlencoded::to_string(params)?;
 
-        let response = self
-            .http_client
-            .post(format!("{}/services/oauth2/token", login_url))
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(body)
-            .send()
-            .await?;
+        let response = post_form(
+            &self.http_client,
+            format!("{}/services/oauth2/token", login_url),
+            body,
+        )
+        .await?;
 
         self.handle_token_response(response).await
     }
@@ -149,13 +149,12 @@ impl OAuthClient {
         let form_data = [("access_token", token)];
         let body = serde_urlencoded::to_string(form_data)?;
 
-        let response = self
-            .http_client
-            .post(format!("{}/services/oauth2/tokeninfo", login_url))
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(body)
-            .send()
-            .await?;
+        let response = post_form(
+            &self.http_client,
+            format!("{}/services/oauth2/tokeninfo", login_url),
+            body,
+        )
+        .await?;
 
         if !response.status().is_success() {
             return Err(Error::new(ErrorKind::TokenInvalid(
@@ -206,13 +205,12 @@ impl OAuthClient {
         let form_data = [("token", token)];
         let body = serde_urlencoded::to_string(form_data)?;
 
-        let response = self
-            .http_client
-            .post(format!("{}/services/oauth2/revoke", login_url))
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(body)
-            .send()
-            .await?;
+        let response = post_form(
+            &self.http_client,
+            format!("{}/services/oauth2/revoke", login_url),
+            body,
+        )
+        .await?;
 
         if !response.status().is_success() {
             // Try to parse error response; Salesforce may return non-JSON (HTML, empty body)
@@ -320,13 +318,12 @@ impl WebFlowAuth {
 
         let body = serde_urlencoded::to_string(params)?;
 
-        let response = self
-            .http_client
-            .post(format!("{}/services/oauth2/token", login_url))
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(body)
-            .send()
-            .await?;
+        let response = post_form(
+            &self.http_client,
+            format!("{}/services/oauth2/token", login_url),
+            body,
+        )
+        .await?;
 
         if !response.status().is_success() {
             let error: OAuthErrorResponse = response.json().await?;
@@ -438,6 +435,29 @@ struct OAuthErrorResponse {
     error_description: String,
 }
 
+/// POST a form-encoded body to an OAuth endpoint.
+///
+/// The body carries credentials, so only the method, URL, status, and
+/// timing are traced.
+pub(crate) async fn post_form(
+    client: &reqwest::Client,
+    url: String,
+    body: String,
+) -> Result<reqwest::Response> {
+    let started = std::time::Instant::now();
+    let sent = client
+        .post(&url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(body)
+        .send()
+        .await;
+    match &sent {
+        Ok(response) => trace::trace_response("POST", &url, response, started.elapsed()),
+        Err(e) => trace::trace_failure("POST", &url, e, started.elapsed()),
+    }
+    Ok(sent?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
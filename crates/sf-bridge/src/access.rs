@@ -0,0 +1,247 @@
+//! Which objects a plugin's host calls touch, for
+//! [`BridgeRegistry::usage_report`](crate::BridgeRegistry::usage_report).
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// The objects and host functions used by every invocation of one plugin.
+#[derive(Debug, Default)]
+pub(crate) struct AccessLog {
+    inner: Mutex<AccessCounts>,
+}
+
+#[derive(Debug, Default)]
+struct AccessCounts {
+    /// object -> host function -> calls
+    objects: BTreeMap<String, BTreeMap<String, u64>>,
+    /// host function -> calls, for calls that name no object
+    other: BTreeMap<String, u64>,
+}
+
+/// The request fields that name the object a host call works on.
+///
+/// Every request struct that targets an object names it in one of these
+/// fields, so the object can be read without knowing the request type.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ObjectFields {
+    sobject: Option<String>,
+    sobject_type: Option<String>,
+    sobjects: Vec<String>,
+    soql: Option<String>,
+}
+
+impl AccessLog {
+    /// Record a call to `host_function` from the MessagePack request the
+    /// guest sent.
+    pub(crate) fn record(&self, host_function: &str, request: Option<&[u8]>) {
+        let objects = request.map(objects_in).unwrap_or_default();
+        let mut counts = self.inner.lock().unwrap();
+        if objects.is_empty() {
+            *counts.other.entry(host_function.to_string()).or_default() += 1;
+        }
+        for object in objects {
+            *counts
+                .objects
+                .entry(object)
+                .or_default()
+                .entry(host_function.to_string())
+                .or_default() += 1;
+        }
+    }
+
+    /// The recorded object accesses, and the calls that named no object.
+    pub(crate) fn snapshot(&self) -> (Vec<ObjectAccess>, BTreeMap<String, u64>) {
+        let counts = self.inner.lock().unwrap();
+        let objects = counts
+            .objects
+            .iter()
+            .map(|(object, operations)| ObjectAccess {
+                object: object.clone(),
+                operations: operations.clone(),
+            })
+            .collect();
+        (objects, counts.other.clone())
+    }
+}
+
+/// The objects named by a request, in the order they appear.
+fn objects_in(request: &[u8]) -> Vec<String> {
+    let Ok(fields) = rmp_serde::from_slice::<ObjectFields>(request) else {
+        return Vec::new();
+    };
+    let mut objects: Vec<String> = fields
+        .sobject
+        .into_iter()
+        .chain(fields.sobject_type)
+        .chain(fields.sobjects)
+        .chain(fields.soql.as_deref().and_then(soql_object))
+        .filter(|object| !object.is_empty())
+        .collect();
+    objects.dedup();
+    objects
+}
+
+/// The object a SOQL query selects from: the name after the first `FROM`
+/// outside a subquery.
+fn soql_object(soql: &str) -> Option<String> {
+    let mut depth = 0usize;
+    let mut after_from = false;
+    for token in soql
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .flat_map(|word| word.split_inclusive(['(', ')']))
+        .filter(|token| !token.is_empty())
+    {
+        let word = token.trim_end_matches(['(', ')']);
+        if after_from && depth == 0 && !word.is_empty() {
+            return Some(word.to_string());
+        }
+        after_from = depth == 0 && word.eq_ignore_ascii_case("FROM");
+        if token.ends_with('(') {
+            depth += 1;
+        } else if token.ends_with(')') {
+            depth = depth.saturating_sub(1);
+        }
+    }
+    None
+}
+
+/// Which plugins touch which objects, returned by
+/// [`BridgeRegistry::usage_report`](crate::BridgeRegistry::usage_report).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UsageReport {
+    /// One entry per registered plugin, sorted by name.
+    pub plugins: Vec<PluginUsage>,
+}
+
+impl UsageReport {
+    /// The plugins that have called a host function on `object`.
+    pub fn plugins_touching<'a>(&'a self, object: &'a str) -> impl Iterator<Item = &'a str> {
+        self.plugins
+            .iter()
+            .filter(move |plugin| plugin.objects.iter().any(|o| o.object == object))
+            .map(|plugin| plugin.name.as_str())
+    }
+}
+
+/// What one plugin has done and is allowed to do.
+///
+/// `objects` and `other_calls` are observed from the plugin's invocations
+/// since it was registered. The remaining fields are the bridge policies
+/// it runs under, which bound what it could reach beyond Salesforce.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PluginUsage {
+    /// Name the plugin was registered under.
+    pub name: String,
+    /// Objects the plugin's host calls named, sorted by object.
+    pub objects: Vec<ObjectAccess>,
+    /// Calls per host function for calls that named no object, such as
+    /// bulk job uploads or metadata deploys.
+    pub other_calls: BTreeMap<String, u64>,
+    /// Hosts the plugin may reach through `sf_http_request`.
+    pub http_hosts: Vec<String>,
+    /// Secrets the plugin may reference in `sf_http_request`.
+    pub secrets: Vec<String>,
+    /// Apex REST paths the plugin may call.
+    pub apex_rest_paths: Vec<String>,
+}
+
+/// The host functions a plugin has called on one object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ObjectAccess {
+    /// Object API name, as the guest wrote it.
+    pub object: String,
+    /// Calls per host function.
+    pub operations: BTreeMap<String, u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use busbar_sf_wasm_types::{
+        BulkCreateQueryJobRequest, CreateRequest, GetDeletedRequest, QueryRequest,
+    };
+
+    fn encode<T: Serialize>(request: &T) -> Vec<u8> {
+        rmp_serde::to_vec_named(request).unwrap()
+    }
+
+    #[test]
+    fn test_soql_object_skips_subqueries() {
+        assert_eq!(
+            soql_object("SELECT Id FROM Account").as_deref(),
+            Some("Account")
+        );
+        assert_eq!(
+            soql_object("SELECT Id, (SELECT Id FROM Contacts) FROM Account WHERE Name = 'x'")
+                .as_deref(),
+            Some("Account")
+        );
+        assert_eq!(
+            soql_object("select id from Opportunity where AccountId IN (SELECT Id FROM Account)")
+                .as_deref(),
+            Some("Opportunity")
+        );
+        assert_eq!(soql_object("SELECT Id"), None);
+    }
+
+    #[test]
+    fn test_record_groups_calls_by_object() {
+        let log = AccessLog::default();
+        let create = CreateRequest {
+            sobject: "Contact".to_string(),
+            record: serde_json::json!({"LastName": "Smith"}),
+        };
+        log.record("sf_create", Some(&encode(&create)));
+        log.record("sf_create", Some(&encode(&create)));
+        let query: QueryRequest = serde_json::from_value(
+            serde_json::json!({"soql": "SELECT Id FROM Contact WHERE Name = :name"}),
+        )
+        .unwrap();
+        log.record("sf_query", Some(&encode(&query)));
+        let job = BulkCreateQueryJobRequest {
+            soql: "SELECT Id FROM Lead".to_string(),
+            operation: Default::default(),
+            binds: Default::default(),
+        };
+        log.record("sf_bulk_create_query_job", Some(&encode(&job)));
+        let deleted = GetDeletedRequest {
+            sobject: "Lead".to_string(),
+            start: "2024-01-01T00:00:00Z".to_string(),
+            end: "2024-01-02T00:00:00Z".to_string(),
+        };
+        log.record("sf_get_deleted", Some(&encode(&deleted)));
+        log.record("sf_bulk_upload_job_data", Some(&encode(&"750000000000001")));
+        log.record("sf_limits", None);
+
+        let (objects, other) = log.snapshot();
+        assert_eq!(
+            objects,
+            vec![
+                ObjectAccess {
+                    object: "Contact".to_string(),
+                    operations: BTreeMap::from([
+                        ("sf_create".to_string(), 2),
+                        ("sf_query".to_string(), 1),
+                    ]),
+                },
+                ObjectAccess {
+                    object: "Lead".to_string(),
+                    operations: BTreeMap::from([
+                        ("sf_bulk_create_query_job".to_string(), 1),
+                        ("sf_get_deleted".to_string(), 1),
+                    ]),
+                },
+            ]
+        );
+        assert_eq!(
+            other,
+            BTreeMap::from([
+                ("sf_bulk_upload_job_data".to_string(), 1),
+                ("sf_limits".to_string(), 1),
+            ])
+        );
+    }
+}
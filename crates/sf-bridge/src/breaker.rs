@@ -0,0 +1,382 @@
+//! Circuit breakers that fail guest calls fast while Salesforce is down.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Salesforce API families, each with its own circuit breaker.
+///
+/// An outage of one API (a Metadata API incident, say) does not stop guests
+/// from using the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiFamily {
+    /// REST API, including composite, search, and Apex REST.
+    Rest,
+    /// Bulk API 2.0.
+    Bulk,
+    /// Tooling API.
+    Tooling,
+    /// Metadata API.
+    Metadata,
+}
+
+impl ApiFamily {
+    /// The family a host function belongs to, or `None` for functions that
+    /// don't call Salesforce (`sf_http_request`).
+    pub(crate) fn of(function: &str) -> Option<Self> {
+        if function == busbar_sf_wasm_types::host_fn_names::HTTP_REQUEST {
+            None
+        } else if function.starts_with("sf_bulk_") {
+            Some(ApiFamily::Bulk)
+        } else if function.starts_with("sf_tooling_") {
+            Some(ApiFamily::Tooling)
+        } else if function.starts_with("sf_metadata_") {
+            Some(ApiFamily::Metadata)
+        } else {
+            Some(ApiFamily::Rest)
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// State of one API family's circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through.
+    Closed,
+    /// Calls fail immediately with `SERVICE_UNAVAILABLE`.
+    Open,
+    /// The cooldown has passed and one probe call is being let through.
+    HalfOpen,
+}
+
+/// When circuit breakers open and how they recover.
+///
+/// A breaker opens after `failure_threshold` consecutive outage errors
+/// (timeouts, connection failures, 5xx responses, exhausted retries,
+/// maintenance windows) from its API family. Errors that show the org is
+/// up, like validation failures or missing records, reset the count.
+///
+/// While open, calls fail with `SERVICE_UNAVAILABLE` without reaching
+/// Salesforce. After `cooldown`, the next call is let through as a probe:
+/// if it succeeds the breaker closes, otherwise it opens for another
+/// cooldown. Only the probe decides; calls admitted before the breaker
+/// opened say nothing about recovery and are ignored when they finish.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bridge::{CircuitBreakerPolicy, SfBridge};
+/// use std::time::Duration;
+///
+/// let bridge = SfBridge::new(wasm_bytes, client)?.with_circuit_breaker(
+///     CircuitBreakerPolicy::new()
+///         .with_failure_threshold(3)
+///         .with_cooldown(Duration::from_secs(60)),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerPolicy {
+    pub(crate) failure_threshold: u32,
+    pub(crate) cooldown: Duration,
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitBreakerPolicy {
+    /// Open after 5 consecutive failures, probe again after 30 seconds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Never open. Every call goes through to Salesforce.
+    pub fn disabled() -> Self {
+        Self {
+            failure_threshold: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Consecutive outage errors that open a breaker. Zero disables it.
+    pub fn with_failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures;
+        self
+    }
+
+    /// How long a breaker stays open before letting a probe through.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Breaker {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    Probing { probe: u64 },
+}
+
+/// A call let through by [`CircuitBreakers::admit`], to be handed back to
+/// [`CircuitBreakers::record`] with its outcome.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Admission {
+    /// Set when this call is the half-open probe.
+    probe: Option<u64>,
+}
+
+/// Breakers for every API family, shared by all invocations of a bridge.
+#[derive(Debug)]
+pub(crate) struct CircuitBreakers {
+    policy: CircuitBreakerPolicy,
+    breakers: Mutex<[Breaker; 4]>,
+    next_probe: AtomicU64,
+}
+
+impl CircuitBreakers {
+    pub(crate) fn new(policy: CircuitBreakerPolicy) -> Self {
+        Self {
+            policy,
+            breakers: Mutex::new([Breaker::Closed { failures: 0 }; 4]),
+            next_probe: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether a call to `family` may go ahead, or `None` to refuse it.
+    /// Moves an open breaker whose cooldown has passed to half-open,
+    /// admitting this call as the probe.
+    pub(crate) fn admit(&self, family: ApiFamily) -> Option<Admission> {
+        if self.policy.failure_threshold == 0 {
+            return Some(Admission::default());
+        }
+        let mut breakers = self.breakers.lock().unwrap();
+        let breaker = &mut breakers[family.index()];
+        match *breaker {
+            Breaker::Closed { .. } => Some(Admission::default()),
+            Breaker::Open { until } if Instant::now() >= until => {
+                tracing::info!(?family, "Circuit half-open, probing Salesforce");
+                let probe = self.next_probe.fetch_add(1, Ordering::Relaxed);
+                *breaker = Breaker::Probing { probe };
+                Some(Admission { probe: Some(probe) })
+            }
+            Breaker::Open { .. } | Breaker::Probing { .. } => None,
+        }
+    }
+
+    /// Record the outcome of an admitted call. `outage` is true when the
+    /// error suggests the API itself is unavailable.
+    pub(crate) fn record(&self, family: ApiFamily, admission: Admission, outage: bool) {
+        if self.policy.failure_threshold == 0 {
+            return;
+        }
+        let mut breakers = self.breakers.lock().unwrap();
+        let breaker = &mut breakers[family.index()];
+        *breaker = match (*breaker, outage) {
+            // A call admitted before the breaker opened; only the probe
+            // decides whether Salesforce has recovered.
+            (probing @ Breaker::Probing { probe }, _) if admission.probe != Some(probe) => probing,
+            (Breaker::Probing { .. }, true) => self.open(family),
+            (Breaker::Closed { failures }, true) => {
+                let failures = failures + 1;
+                if failures >= self.policy.failure_threshold {
+                    self.open(family)
+                } else {
+                    Breaker::Closed { failures }
+                }
+            }
+            (Breaker::Probing { .. }, false) => {
+                tracing::info!(?family, "Circuit closed, Salesforce recovered");
+                Breaker::Closed { failures: 0 }
+            }
+            (Breaker::Closed { .. }, false) => Breaker::Closed { failures: 0 },
+            // A call admitted before the breaker opened; the open state stands.
+            (open @ Breaker::Open { .. }, _) => open,
+        };
+    }
+
+    pub(crate) fn state(&self, family: ApiFamily) -> CircuitState {
+        match self.breakers.lock().unwrap()[family.index()] {
+            Breaker::Closed { .. } => CircuitState::Closed,
+            Breaker::Open { .. } => CircuitState::Open,
+            Breaker::Probing { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    fn open(&self, family: ApiFamily) -> Breaker {
+        tracing::warn!(
+            ?family,
+            cooldown = ?self.policy.cooldown,
+            "Circuit opened after repeated Salesforce failures"
+        );
+        Breaker::Open {
+            until: Instant::now() + self.policy.cooldown,
+        }
+    }
+}
+
+/// Whether a bridge error code means the API is unavailable rather than
+/// that the request itself was rejected.
+pub(crate) fn is_outage(code: &str) -> bool {
+    matches!(
+        code,
+        "TIMEOUT"
+            | "CONNECTION_ERROR"
+            | "RETRIES_EXHAUSTED"
+            | "MAINTENANCE_WINDOW"
+            | "SERVER_UNAVAILABLE"
+            | "SERVICE_UNAVAILABLE"
+    ) || code
+        .strip_prefix("HTTP_")
+        .is_some_and(|status| status.starts_with('5'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_family_of_function() {
+        assert_eq!(ApiFamily::of("sf_query"), Some(ApiFamily::Rest));
+        assert_eq!(
+            ApiFamily::of("sf_bulk_get_ingest_job"),
+            Some(ApiFamily::Bulk)
+        );
+        assert_eq!(
+            ApiFamily::of("sf_metadata_deploy"),
+            Some(ApiFamily::Metadata)
+        );
+        assert_eq!(ApiFamily::of("sf_http_request"), None);
+    }
+
+    #[test]
+    fn test_is_outage() {
+        assert!(is_outage("TIMEOUT"));
+        assert!(is_outage("HTTP_503"));
+        assert!(!is_outage("HTTP_404"));
+        assert!(!is_outage("INVALID_FIELD"));
+    }
+
+    /// Admit a call to `family` and record its outcome.
+    fn call(breakers: &CircuitBreakers, family: ApiFamily, outage: bool) {
+        let admission = breakers.admit(family).expect("call should be admitted");
+        breakers.record(family, admission, outage);
+    }
+
+    #[test]
+    fn test_breaker_opens_and_recovers() {
+        let breakers = CircuitBreakers::new(
+            CircuitBreakerPolicy::new()
+                .with_failure_threshold(2)
+                .with_cooldown(Duration::ZERO),
+        );
+        let rest = ApiFamily::Rest;
+
+        call(&breakers, rest, true);
+        call(&breakers, rest, false);
+        call(&breakers, rest, true);
+        assert_eq!(breakers.state(rest), CircuitState::Closed);
+        call(&breakers, rest, true);
+        assert_eq!(breakers.state(rest), CircuitState::Open);
+        assert_eq!(breakers.state(ApiFamily::Bulk), CircuitState::Closed);
+
+        // Zero cooldown: the next call is the probe, and others wait on it.
+        let probe = breakers.admit(rest).unwrap();
+        assert_eq!(breakers.state(rest), CircuitState::HalfOpen);
+        assert!(breakers.admit(rest).is_none());
+        breakers.record(rest, probe, true);
+        assert_eq!(breakers.state(rest), CircuitState::Open);
+
+        call(&breakers, rest, false);
+        assert_eq!(breakers.state(rest), CircuitState::Closed);
+        assert!(breakers.admit(rest).is_some());
+    }
+
+    #[test]
+    fn test_only_the_probe_decides_recovery() {
+        let breakers = CircuitBreakers::new(
+            CircuitBreakerPolicy::new()
+                .with_failure_threshold(1)
+                .with_cooldown(Duration::ZERO),
+        );
+        let rest = ApiFamily::Rest;
+
+        // A slow call admitted while closed, then an outage opens the breaker.
+        let slow = breakers.admit(rest).unwrap();
+        call(&breakers, rest, true);
+        let probe = breakers.admit(rest).unwrap();
+        assert_eq!(breakers.state(rest), CircuitState::HalfOpen);
+
+        // The slow call finishing says nothing about recovery.
+        breakers.record(rest, slow, false);
+        assert_eq!(breakers.state(rest), CircuitState::HalfOpen);
+        breakers.record(rest, slow, true);
+        assert_eq!(breakers.state(rest), CircuitState::HalfOpen);
+
+        breakers.record(rest, probe, false);
+        assert_eq!(breakers.state(rest), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_open_breaker_refuses_until_cooldown() {
+        let breakers = CircuitBreakers::new(
+            CircuitBreakerPolicy::new()
+                .with_failure_threshold(1)
+                .with_cooldown(Duration::from_secs(3600)),
+        );
+        call(&breakers, ApiFamily::Tooling, true);
+        assert!(breakers.admit(ApiFamily::Tooling).is_none());
+        assert!(breakers.admit(ApiFamily::Rest).is_some());
+
+        let disabled = CircuitBreakers::new(CircuitBreakerPolicy::disabled());
+        call(&disabled, ApiFamily::Tooling, true);
+        assert!(disabled.admit(ApiFamily::Tooling).is_some());
+    }
+
+    #[cfg(feature = "bulk")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bulk_outages_open_breaker() {
+        use crate::host_functions::handle_bulk_get_ingest_job;
+        use busbar_sf_wasm_types::{BridgeResult, BulkJobIdRequest};
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        let client = busbar_sf_bulk::BulkApiClient::with_config(
+            server.uri(),
+            "token",
+            busbar_sf_client::ClientConfig::builder()
+                .without_retry()
+                .build(),
+        )
+        .unwrap();
+
+        let breakers = CircuitBreakers::new(CircuitBreakerPolicy::new().with_failure_threshold(3));
+        for _ in 0..3 {
+            let admission = breakers.admit(ApiFamily::Bulk).unwrap();
+            let request = BulkJobIdRequest {
+                job_id: "750000000000001".to_string(),
+            };
+            let BridgeResult::Err(e) = handle_bulk_get_ingest_job(&client, request).await else {
+                panic!("a 503 should fail");
+            };
+            assert_eq!(e.code, "HTTP_503");
+            breakers.record(ApiFamily::Bulk, admission, is_outage(&e.code));
+        }
+        assert_eq!(breakers.state(ApiFamily::Bulk), CircuitState::Open);
+        assert!(breakers.admit(ApiFamily::Bulk).is_none());
+    }
+}
@@ -45,18 +45,25 @@ impl HostCapability for SfBridge {
             #[cfg(feature = "rest")]
             rest_client: self.rest_client.clone(),
             #[cfg(feature = "bulk")]
-            bulk_client: self.bulk_client.clone(),
+            bulk_client: busbar_sf_bulk::BulkApiClient::from_client(
+                self.rest_client.inner().clone(),
+            ),
             #[cfg(feature = "tooling")]
-            tooling_client: self.tooling_client.clone(),
+            tooling_client: busbar_sf_tooling::ToolingClient::from_client(
+                self.rest_client.inner().clone(),
+            ),
             instance_url: self.instance_url.clone(),
             access_token: self.access_token.clone(),
             handle: self.handle.clone(),
+            usage: Default::default(),
         };
 
         let user_data = UserData::new(state);
 
         // Use the existing registration logic to wire up all host functions
-        let builder = registration::register_all(builder, &user_data);
+        let (builder, _) =
+            registration::register_all(registration::Registrar::new(builder), &user_data)
+                .finish();
 
         Ok(builder)
     }
@@ -83,6 +90,12 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "Fetch next page of query results",
             RiskClassification::ReadOnly,
         ),
+        op(
+            "query_cursor_next",
+            host_fn_names::QUERY_CURSOR_NEXT,
+            "Read query results held host-side behind a cursor",
+            RiskClassification::ReadOnly,
+        ),
         op(
             "create",
             host_fn_names::CREATE,
@@ -113,6 +126,18 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "Upsert a record by external ID",
             RiskClassification::WriteVisible,
         ),
+        op(
+            "get_by_external_id",
+            host_fn_names::GET_BY_EXTERNAL_ID,
+            "Get a record by external ID",
+            RiskClassification::ReadOnly,
+        ),
+        op(
+            "delete_by_external_id",
+            host_fn_names::DELETE_BY_EXTERNAL_ID,
+            "Delete a record by external ID",
+            RiskClassification::Destructive,
+        ),
         op(
             "describe_global",
             host_fn_names::DESCRIBE_GLOBAL,
@@ -155,6 +180,18 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "Get updated records in date range",
             RiskClassification::ReadOnly,
         ),
+        op(
+            "changes_since",
+            host_fn_names::CHANGES_SINCE,
+            "Get updated and deleted records since a watermark",
+            RiskClassification::ReadOnly,
+        ),
+        op(
+            "schema_snapshot",
+            host_fn_names::SCHEMA_SNAPSHOT,
+            "Get a compact schema digest of SObjects",
+            RiskClassification::ReadOnly,
+        ),
         // REST API: Composite
         op(
             "composite",
@@ -205,6 +242,19 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "Delete multiple records",
             RiskClassification::Destructive,
         ),
+        // REST API: Recycle Bin
+        op(
+            "empty_recycle_bin",
+            host_fn_names::EMPTY_RECYCLE_BIN,
+            "Permanently delete records from the Recycle Bin",
+            RiskClassification::Destructive,
+        ),
+        op(
+            "undelete",
+            host_fn_names::UNDELETE,
+            "Restore records from the Recycle Bin",
+            RiskClassification::WriteVisible,
+        ),
         // REST API: Process & Approvals
         op(
             "list_process_rules",
@@ -397,6 +447,36 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "List data categories",
             RiskClassification::ReadOnly,
         ),
+        op(
+            "knowledge_article",
+            host_fn_names::KNOWLEDGE_ARTICLE,
+            "Get a published knowledge article",
+            RiskClassification::ReadOnly,
+        ),
+        op(
+            "create_knowledge_draft",
+            host_fn_names::CREATE_KNOWLEDGE_DRAFT,
+            "Create a draft knowledge article",
+            RiskClassification::WriteVisible,
+        ),
+        op(
+            "publish_knowledge_articles",
+            host_fn_names::PUBLISH_KNOWLEDGE_ARTICLES,
+            "Publish draft knowledge articles",
+            RiskClassification::WriteVisible,
+        ),
+        op(
+            "archive_knowledge_articles",
+            host_fn_names::ARCHIVE_KNOWLEDGE_ARTICLES,
+            "Archive published knowledge articles",
+            RiskClassification::Destructive,
+        ),
+        op(
+            "knowledge_draft_from_online",
+            host_fn_names::KNOWLEDGE_DRAFT_FROM_ONLINE,
+            "Create a draft from a published knowledge article",
+            RiskClassification::WriteVisible,
+        ),
         // REST API: Standalone
         op(
             "tabs",
@@ -434,6 +514,12 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "Get platform event schema",
             RiskClassification::ReadOnly,
         ),
+        op(
+            "publish_event",
+            host_fn_names::PUBLISH_EVENT,
+            "Publish a platform event",
+            RiskClassification::WriteVisible,
+        ),
         op(
             "lightning_toggle_metrics",
             host_fn_names::LIGHTNING_TOGGLE_METRICS,
@@ -597,6 +683,18 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "List all bulk ingest jobs",
             RiskClassification::ReadOnly,
         ),
+        op(
+            "bulk_create_query_job",
+            host_fn_names::BULK_CREATE_QUERY_JOB,
+            "Create bulk query job",
+            RiskClassification::ReadOnly,
+        ),
+        op(
+            "bulk_get_query_job",
+            host_fn_names::BULK_GET_QUERY_JOB,
+            "Get bulk query job status",
+            RiskClassification::ReadOnly,
+        ),
         op(
             "bulk_abort_query_job",
             host_fn_names::BULK_ABORT_QUERY_JOB,
@@ -616,6 +714,12 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "Execute tooling query",
             RiskClassification::ReadOnly,
         ),
+        op(
+            "tooling_query_more",
+            host_fn_names::TOOLING_QUERY_MORE,
+            "Fetch next page of tooling query results",
+            RiskClassification::ReadOnly,
+        ),
         op(
             "tooling_execute_anonymous",
             host_fn_names::TOOLING_EXECUTE_ANONYMOUS,
@@ -628,18 +732,60 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "Get tooling record",
             RiskClassification::ReadOnly,
         ),
+        op(
+            "tooling_composite",
+            host_fn_names::TOOLING_COMPOSITE,
+            "Execute tooling composite subrequests",
+            RiskClassification::WriteVisible,
+        ),
         op(
             "tooling_create",
             host_fn_names::TOOLING_CREATE,
             "Create tooling record",
             RiskClassification::WriteVisible,
         ),
+        op(
+            "tooling_update",
+            host_fn_names::TOOLING_UPDATE,
+            "Update tooling record",
+            RiskClassification::WriteVisible,
+        ),
+        op(
+            "tooling_upsert",
+            host_fn_names::TOOLING_UPSERT,
+            "Upsert tooling record",
+            RiskClassification::WriteVisible,
+        ),
         op(
             "tooling_delete",
             host_fn_names::TOOLING_DELETE,
             "Delete tooling record",
             RiskClassification::Destructive,
         ),
+        op(
+            "tooling_async_apex_jobs",
+            host_fn_names::TOOLING_ASYNC_APEX_JOBS,
+            "List async Apex jobs",
+            RiskClassification::ReadOnly,
+        ),
+        op(
+            "tooling_abort_job",
+            host_fn_names::TOOLING_ABORT_JOB,
+            "Abort async Apex or scheduled job",
+            RiskClassification::Destructive,
+        ),
+        op(
+            "tooling_run_tests",
+            host_fn_names::TOOLING_RUN_TESTS,
+            "Run Apex tests asynchronously",
+            RiskClassification::WriteVisible,
+        ),
+        op(
+            "tooling_test_run_summary",
+            host_fn_names::TOOLING_TEST_RUN_SUMMARY,
+            "Get Apex test run results",
+            RiskClassification::ReadOnly,
+        ),
         // Metadata API
         op(
             "metadata_deploy",
@@ -677,6 +823,38 @@ fn create_operation_definitions() -> Vec<OperationDef> {
             "Describe metadata",
             RiskClassification::ReadOnly,
         ),
+        // Apex REST
+        op(
+            "apex_rest",
+            host_fn_names::APEX_REST,
+            "Call an allow-listed custom Apex REST endpoint",
+            RiskClassification::Destructive,
+        ),
+        // Outbound HTTP
+        op(
+            "http_request",
+            host_fn_names::HTTP_REQUEST,
+            "Call a third-party API with host-held secrets",
+            RiskClassification::WriteVisible,
+        ),
+        op(
+            "invocation_context",
+            host_fn_names::INVOCATION_CONTEXT,
+            "Get the org, user, and payload of the current invocation",
+            RiskClassification::ReadOnly,
+        ),
+        op(
+            "operation_status",
+            host_fn_names::OPERATION_STATUS,
+            "Check on a deploy, retrieve, or bulk job by handle",
+            RiskClassification::ReadOnly,
+        ),
+        op(
+            "capabilities",
+            host_fn_names::CAPABILITIES,
+            "List the host functions, policies, and limits available to the plugin",
+            RiskClassification::ReadOnly,
+        ),
     ]
 }
 
@@ -699,7 +877,7 @@ mod tests {
     #[test]
     fn test_operation_count() {
         let ops = create_operation_definitions();
-        assert_eq!(ops.len(), 98, "Expected 98 operations");
+        assert_eq!(ops.len(), 126, "Expected 126 operations");
     }
 
     #[test]
@@ -708,7 +886,7 @@ mod tests {
         // Since we can't easily construct a real SfBridge without auth,
         // we'll just test the standalone functions
         let ops = create_operation_definitions();
-        assert_eq!(ops.len(), 98);
+        assert_eq!(ops.len(), 126);
 
         // Verify all operations have non-empty names
         for op in &ops {
@@ -757,10 +935,10 @@ mod tests {
             "Should have destructive operations (got {destructive_count})"
         );
 
-        // Total should equal 98
+        // Total should equal 126
         assert_eq!(
             read_only_count + write_visible_count + destructive_count,
-            98
+            126
         );
 
         // Verify specific high-risk operations
@@ -0,0 +1,77 @@
+//! What a plugin can call and under which limits, for `sf_capabilities`.
+
+use busbar_sf_wasm_types::{
+    Capabilities, CapabilityLimits, CapabilityPolicies, HostFunctionDeprecation, ABI_VERSION,
+};
+
+use crate::BridgeState;
+
+/// Host functions slated for removal: name, bridge version that deprecated
+/// it, replacement, and why.
+///
+/// A deprecated function stays registered and keeps working until it is
+/// removed in a release that bumps [`ABI_VERSION`].
+const DEPRECATED: &[(&str, &str, Option<&str>, &str)] = &[];
+
+/// The capabilities of the plugin `state` belongs to.
+pub(crate) fn capabilities(state: &BridgeState) -> Capabilities {
+    let deprecated = DEPRECATED
+        .iter()
+        .filter(|(name, ..)| state.host_functions.contains(name))
+        .map(
+            |&(name, since, replacement, note)| HostFunctionDeprecation {
+                name: name.to_string(),
+                since: since.to_string(),
+                replacement: replacement.map(str::to_string),
+                note: note.to_string(),
+            },
+        )
+        .collect();
+
+    let mut http_hosts = state.http_policy.hosts.clone();
+    http_hosts.extend(state.secrets.hosts());
+    http_hosts.sort();
+    http_hosts.dedup();
+
+    #[cfg(feature = "rest")]
+    let (apex_rest_paths, bound_queries_only, query_memory_budget) = (
+        state.apex_rest_paths.to_vec(),
+        state.bound_queries_only,
+        state.query_spill.budget().map(|b| b as u64),
+    );
+    #[cfg(not(feature = "rest"))]
+    let (apex_rest_paths, bound_queries_only, query_memory_budget) = (Vec::new(), false, None);
+
+    let timeouts = &state.timeouts;
+    Capabilities {
+        abi_version: ABI_VERSION,
+        bridge_version: env!("CARGO_PKG_VERSION").to_string(),
+        host_functions: state
+            .host_functions
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        deprecated,
+        policies: CapabilityPolicies {
+            wasi: state.wasi,
+            http_hosts,
+            secrets: state.secrets.names(),
+            apex_rest_paths,
+            bound_queries_only,
+            locale: state.locale.clone(),
+        },
+        limits: CapabilityLimits {
+            host_call_timeout_ms: timeouts.default.as_millis() as u64,
+            long_running_timeout_ms: timeouts.long_running.as_millis() as u64,
+            function_timeouts_ms: timeouts
+                .functions
+                .iter()
+                .map(|(name, t)| (name.clone(), t.as_millis() as u64))
+                .collect(),
+            http_max_request_bytes: state.http_policy.max_request_bytes as u64,
+            http_max_response_bytes: state.http_policy.max_response_bytes as u64,
+            http_timeout_ms: state.http_policy.timeout.as_millis() as u64,
+            query_memory_budget,
+        },
+    }
+}
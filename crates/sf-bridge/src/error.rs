@@ -40,6 +40,10 @@ pub enum Error {
     #[error("task join error: {0}")]
     Join(#[from] tokio::task::JoinError),
 
+    /// The plugin module's signature is missing or not trusted.
+    #[error("plugin signature error: {0}")]
+    Signature(String),
+
     /// Configuration error.
     #[error("configuration error: {0}")]
     Config(String),
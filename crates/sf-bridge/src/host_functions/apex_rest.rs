@@ -0,0 +1,92 @@
+//! Apex REST host function handler.
+//!
+//! Guests may only reach the custom endpoints the host has allow-listed.
+use super::error::*;
+use busbar_sf_rest::{RequestMethod, SalesforceRestClient};
+use busbar_sf_wasm_types::*;
+
+/// Call an allow-listed Apex REST endpoint.
+pub(crate) async fn handle_apex_rest(
+    client: &SalesforceRestClient,
+    allowed_paths: &[String],
+    request: ApexRestRequest,
+) -> BridgeResult<serde_json::Value> {
+    if !apex_rest_path_allowed(allowed_paths, &request.path) {
+        return BridgeResult::err(
+            "PATH_NOT_ALLOWED",
+            format!("Apex REST path is not allowed: {}", request.path),
+        );
+    }
+    let method = match request.method.to_ascii_uppercase().as_str() {
+        "GET" => RequestMethod::Get,
+        "POST" => RequestMethod::Post,
+        "PUT" => RequestMethod::Put,
+        "PATCH" => RequestMethod::Patch,
+        "DELETE" => RequestMethod::Delete,
+        other => {
+            return BridgeResult::err(
+                "INVALID_METHOD",
+                format!("Unsupported HTTP method: {other}"),
+            )
+        }
+    };
+    let headers: Vec<(&str, &str)> = request
+        .headers
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
+
+    match client
+        .apex_rest(method, &request.path, request.body.as_ref(), &headers)
+        .await
+    {
+        Ok(value) => BridgeResult::ok(value),
+        Err(e) => {
+            let (code, message) = sanitize_rest_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Whether `path` matches one of the allow-list entries.
+///
+/// Entries match exactly (`Orders/v1`) or, when they end in `/*`, any path
+/// below them (`Orders/*`). The query string is ignored; dot segments
+/// never match.
+fn apex_rest_path_allowed(allowed_paths: &[String], path: &str) -> bool {
+    let route = path
+        .trim_start_matches('/')
+        .split('?')
+        .next()
+        .unwrap_or_default();
+    if route.split('/').any(|s| s == "." || s == "..") {
+        return false;
+    }
+    allowed_paths.iter().any(|entry| {
+        let entry = entry.trim_start_matches('/');
+        match entry.strip_suffix('*') {
+            Some(prefix) if prefix.ends_with('/') => {
+                route.len() > prefix.len() && route.starts_with(prefix)
+            }
+            _ => route == entry,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apex_rest_path_allowed() {
+        let allowed = vec!["Orders/*".to_string(), "/Ping".to_string()];
+        assert!(apex_rest_path_allowed(&allowed, "Orders/42"));
+        assert!(apex_rest_path_allowed(&allowed, "/Orders/42/lines?x=1"));
+        assert!(apex_rest_path_allowed(&allowed, "Ping?verbose=true"));
+        assert!(!apex_rest_path_allowed(&allowed, "Orders"));
+        assert!(!apex_rest_path_allowed(&allowed, "OrdersAdmin/1"));
+        assert!(!apex_rest_path_allowed(&allowed, "Orders/../Admin"));
+        assert!(!apex_rest_path_allowed(&allowed, "Ping/extra"));
+        assert!(!apex_rest_path_allowed(&[], "Ping"));
+    }
+}
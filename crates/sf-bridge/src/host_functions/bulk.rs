@@ -2,32 +2,20 @@
 use super::error::*;
 use busbar_sf_bulk::BulkApiClient;
 use busbar_sf_wasm_types::*;
+use futures::TryStreamExt;
 
 /// Create a bulk ingest job.
 pub(crate) async fn handle_bulk_create_ingest_job(
     client: &BulkApiClient,
     request: BulkCreateIngestJobRequest,
 ) -> BridgeResult<BulkJobResponse> {
-    let operation = match parse_bulk_operation(&request.operation) {
-        Ok(op) => op,
-        Err(msg) => return BridgeResult::err("INVALID_REQUEST", msg),
-    };
-    let column_delimiter = match parse_column_delimiter(&request.column_delimiter) {
-        Ok(d) => d,
-        Err(msg) => return BridgeResult::err("INVALID_REQUEST", msg),
-    };
-    let line_ending = match parse_line_ending(&request.line_ending) {
-        Ok(l) => l,
-        Err(msg) => return BridgeResult::err("INVALID_REQUEST", msg),
-    };
-
     let sf_request = busbar_sf_bulk::CreateIngestJobRequest {
         object: request.sobject,
-        operation,
+        operation: bulk_operation(request.operation),
         external_id_field_name: request.external_id_field,
         content_type: busbar_sf_bulk::ContentType::default(),
-        column_delimiter,
-        line_ending,
+        column_delimiter: column_delimiter(request.column_delimiter),
+        line_ending: line_ending(request.line_ending),
     };
 
     match client.create_ingest_job(sf_request).await {
@@ -138,15 +126,42 @@ pub(crate) async fn handle_bulk_delete_ingest_job(
     }
 }
 
-/// List all ingest jobs.
+/// List ingest jobs matching the request's filters, following
+/// `nextRecordsUrl` until every page has been read.
+///
+/// Guests built before the filters existed send no request, which lists
+/// every ingest job as before.
 pub(crate) async fn handle_bulk_get_all_ingest_jobs(
     client: &BulkApiClient,
+    request: Option<BulkGetAllIngestJobsRequest>,
 ) -> BridgeResult<BulkJobListResponse> {
-    match client.get_all_ingest_jobs().await {
-        Ok(list) => BridgeResult::ok(BulkJobListResponse {
-            records: list.records.into_iter().map(ingest_job_to_bridge).collect(),
-            done: list.done,
-            next_records_url: list.next_records_url,
+    let request = request.unwrap_or_default();
+    let mut filter = busbar_sf_bulk::IngestJobFilter::new();
+    if let Some(enabled) = request.is_pk_chunking_enabled {
+        filter = filter.with_pk_chunking_enabled(enabled);
+    }
+    if let Some(job_type) = request.job_type.as_deref() {
+        match parse_job_type(job_type) {
+            Ok(t) => filter = filter.with_job_type(t),
+            Err(msg) => return BridgeResult::err("INVALID_REQUEST", msg),
+        }
+    }
+    if let Some(mode) = request.concurrency_mode.as_deref() {
+        match parse_concurrency_mode(mode) {
+            Ok(m) => filter = filter.with_concurrency_mode(m),
+            Err(msg) => return BridgeResult::err("INVALID_REQUEST", msg),
+        }
+    }
+
+    match client
+        .get_ingest_jobs_stream(&filter)
+        .try_collect::<Vec<_>>()
+        .await
+    {
+        Ok(jobs) => BridgeResult::ok(BulkJobListResponse {
+            records: jobs.into_iter().map(ingest_job_to_bridge).collect(),
+            done: true,
+            next_records_url: None,
         }),
         Err(e) => {
             let (code, message) = sanitize_bulk_error(&e);
@@ -155,6 +170,47 @@ pub(crate) async fn handle_bulk_get_all_ingest_jobs(
     }
 }
 
+/// Create a bulk query job.
+///
+/// The SOQL goes through the same bind and bound-queries checks as
+/// `sf_query`.
+pub(crate) async fn handle_bulk_create_query_job(
+    client: &BulkApiClient,
+    bound_only: bool,
+    request: BulkCreateQueryJobRequest,
+) -> BridgeResult<BulkJobResponse> {
+    let operation = match request.operation {
+        QueryOperation::Query => busbar_sf_bulk::BulkOperation::Query,
+        QueryOperation::QueryAll => busbar_sf_bulk::BulkOperation::QueryAll,
+    };
+    let soql = match super::rest::bind_guest_soql(request.soql, &request.binds, bound_only) {
+        Ok(soql) => soql,
+        Err((code, message)) => return BridgeResult::err(code, message),
+    };
+
+    match client.create_query_job(&soql, operation).await {
+        Ok(job) => BridgeResult::ok(query_job_to_bridge(job)),
+        Err(e) => {
+            let (code, message) = sanitize_bulk_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Get the status of a bulk query job.
+pub(crate) async fn handle_bulk_get_query_job(
+    client: &BulkApiClient,
+    request: BulkJobIdRequest,
+) -> BridgeResult<BulkJobResponse> {
+    match client.get_query_job(&request.job_id).await {
+        Ok(job) => BridgeResult::ok(query_job_to_bridge(job)),
+        Err(e) => {
+            let (code, message) = sanitize_bulk_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
 /// Abort a bulk query job.
 pub(crate) async fn handle_bulk_abort_query_job(
     client: &BulkApiClient,
@@ -193,6 +249,86 @@ pub(crate) async fn handle_bulk_get_query_results(
     }
 }
 
+/// Status of an ingest job tracked as a long-running operation.
+pub(crate) async fn handle_bulk_operation_status(
+    client: &BulkApiClient,
+    handle: String,
+) -> BridgeResult<OperationStatus> {
+    match client.get_ingest_job(&handle).await {
+        Ok(job) => BridgeResult::ok(bulk_operation_status(
+            handle,
+            OperationKind::BulkIngest,
+            job.state,
+            job.number_records_processed,
+            job.error_message,
+        )),
+        Err(e) => {
+            let (code, message) = sanitize_bulk_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Status of a query job tracked as a long-running operation.
+pub(crate) async fn handle_bulk_query_operation_status(
+    client: &BulkApiClient,
+    handle: String,
+) -> BridgeResult<OperationStatus> {
+    match client.get_query_job(&handle).await {
+        Ok(job) => BridgeResult::ok(bulk_operation_status(
+            handle,
+            OperationKind::BulkQuery,
+            job.state,
+            job.number_records_processed,
+            job.error_message,
+        )),
+        Err(e) => {
+            let (code, message) = sanitize_bulk_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Status of a bulk job the bridge didn't see start. Ingest and query jobs
+/// share an ID prefix, so the job is looked up as an ingest job first and
+/// then as a query job.
+pub(crate) async fn handle_untracked_bulk_operation_status(
+    client: &BulkApiClient,
+    handle: String,
+) -> BridgeResult<OperationStatus> {
+    match handle_bulk_operation_status(client, handle.clone()).await {
+        BridgeResult::Err(_) => handle_bulk_query_operation_status(client, handle).await,
+        found => found,
+    }
+}
+
+fn bulk_operation_status(
+    handle: String,
+    kind: OperationKind,
+    state: busbar_sf_bulk::JobState,
+    processed: i64,
+    error_message: Option<String>,
+) -> OperationStatus {
+    use busbar_sf_bulk::JobState;
+
+    OperationStatus {
+        handle,
+        kind,
+        state: match state {
+            JobState::Open | JobState::UploadComplete | JobState::InProgress => {
+                OperationState::InProgress
+            }
+            JobState::JobComplete => OperationState::Succeeded,
+            JobState::Failed => OperationState::Failed,
+            JobState::Aborted => OperationState::Canceled,
+        },
+        status: format!("{:?}", state),
+        processed: Some(processed),
+        total: None,
+        error_message,
+    }
+}
+
 // =============================================================================
 // Utility functions
 // =============================================================================
@@ -208,6 +344,8 @@ fn ingest_job_to_bridge(job: busbar_sf_bulk::IngestJob) -> BulkJobResponse {
         created_date: job.created_date,
         system_modstamp: job.system_modstamp,
         error_message: job.error_message,
+        concurrency_mode: job.concurrency_mode,
+        job_type: job.job_type,
     }
 }
 
@@ -222,36 +360,101 @@ fn query_job_to_bridge(job: busbar_sf_bulk::QueryJob) -> BulkJobResponse {
         created_date: job.created_date,
         system_modstamp: job.system_modstamp,
         error_message: job.error_message,
+        concurrency_mode: None,
+        job_type: None,
+    }
+}
+
+fn bulk_operation(op: BulkOperation) -> busbar_sf_bulk::BulkOperation {
+    match op {
+        BulkOperation::Insert => busbar_sf_bulk::BulkOperation::Insert,
+        BulkOperation::Update => busbar_sf_bulk::BulkOperation::Update,
+        BulkOperation::Upsert => busbar_sf_bulk::BulkOperation::Upsert,
+        BulkOperation::Delete => busbar_sf_bulk::BulkOperation::Delete,
+        BulkOperation::HardDelete => busbar_sf_bulk::BulkOperation::HardDelete,
+    }
+}
+
+fn parse_job_type(s: &str) -> Result<busbar_sf_bulk::JobType, String> {
+    match s.to_lowercase().as_str() {
+        "v2ingest" => Ok(busbar_sf_bulk::JobType::V2Ingest),
+        "classic" => Ok(busbar_sf_bulk::JobType::Classic),
+        "bigobjectingest" => Ok(busbar_sf_bulk::JobType::BigObjectIngest),
+        _ => Err(format!("invalid job type: {s}")),
     }
 }
 
-fn parse_bulk_operation(s: &str) -> Result<busbar_sf_bulk::BulkOperation, String> {
+fn parse_concurrency_mode(s: &str) -> Result<busbar_sf_bulk::ConcurrencyMode, String> {
     match s.to_lowercase().as_str() {
-        "insert" => Ok(busbar_sf_bulk::BulkOperation::Insert),
-        "update" => Ok(busbar_sf_bulk::BulkOperation::Update),
-        "upsert" => Ok(busbar_sf_bulk::BulkOperation::Upsert),
-        "delete" => Ok(busbar_sf_bulk::BulkOperation::Delete),
-        "harddelete" => Ok(busbar_sf_bulk::BulkOperation::HardDelete),
-        _ => Err(format!("invalid bulk operation: {s}")),
+        "parallel" => Ok(busbar_sf_bulk::ConcurrencyMode::Parallel),
+        "serial" => Ok(busbar_sf_bulk::ConcurrencyMode::Serial),
+        _ => Err(format!("invalid concurrency mode: {s}")),
+    }
+}
+
+fn column_delimiter(d: ColumnDelimiter) -> busbar_sf_bulk::ColumnDelimiter {
+    match d {
+        ColumnDelimiter::Comma => busbar_sf_bulk::ColumnDelimiter::Comma,
+        ColumnDelimiter::Tab => busbar_sf_bulk::ColumnDelimiter::Tab,
+        ColumnDelimiter::Semicolon => busbar_sf_bulk::ColumnDelimiter::Semicolon,
+        ColumnDelimiter::Pipe => busbar_sf_bulk::ColumnDelimiter::Pipe,
+        ColumnDelimiter::Backquote => busbar_sf_bulk::ColumnDelimiter::Backquote,
+        ColumnDelimiter::Caret => busbar_sf_bulk::ColumnDelimiter::Caret,
     }
 }
 
-fn parse_column_delimiter(s: &str) -> Result<busbar_sf_bulk::ColumnDelimiter, String> {
-    match s {
-        "COMMA" => Ok(busbar_sf_bulk::ColumnDelimiter::Comma),
-        "TAB" => Ok(busbar_sf_bulk::ColumnDelimiter::Tab),
-        "SEMICOLON" => Ok(busbar_sf_bulk::ColumnDelimiter::Semicolon),
-        "PIPE" => Ok(busbar_sf_bulk::ColumnDelimiter::Pipe),
-        "BACKQUOTE" => Ok(busbar_sf_bulk::ColumnDelimiter::Backquote),
-        "CARET" => Ok(busbar_sf_bulk::ColumnDelimiter::Caret),
-        _ => Err(format!("invalid column delimiter: {s}")),
+fn line_ending(l: LineEnding) -> busbar_sf_bulk::LineEnding {
+    match l {
+        LineEnding::Lf => busbar_sf_bulk::LineEnding::Lf,
+        LineEnding::Crlf => busbar_sf_bulk::LineEnding::Crlf,
     }
 }
 
-fn parse_line_ending(s: &str) -> Result<busbar_sf_bulk::LineEnding, String> {
-    match s {
-        "LF" => Ok(busbar_sf_bulk::LineEnding::Lf),
-        "CRLF" => Ok(busbar_sf_bulk::LineEnding::Crlf),
-        _ => Err(format!("invalid line ending: {s}")),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_untracked_query_job_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/jobs/ingest/750xx0000000001AAA"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_json(serde_json::json!([{
+                    "errorCode": "NOT_FOUND",
+                    "message": "The requested resource does not exist"
+                }])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/jobs/query/750xx0000000001AAA"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "750xx0000000001AAA",
+                "operation": "query",
+                "state": "JobComplete",
+                "numberRecordsProcessed": 42
+            })))
+            .mount(&server)
+            .await;
+        let client = BulkApiClient::with_config(
+            server.uri(),
+            "token",
+            busbar_sf_client::ClientConfig::builder()
+                .without_retry()
+                .build(),
+        )
+        .unwrap();
+
+        let BridgeResult::Ok(status) =
+            handle_untracked_bulk_operation_status(&client, "750xx0000000001AAA".to_string()).await
+        else {
+            panic!("the query job should be found");
+        };
+        assert_eq!(status.kind, OperationKind::BulkQuery);
+        assert_eq!(status.state, OperationState::Succeeded);
+        assert_eq!(status.processed, Some(42));
     }
 }
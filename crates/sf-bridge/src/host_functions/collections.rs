@@ -76,6 +76,36 @@ pub(crate) async fn handle_delete_multiple(
     }
 }
 
+/// Permanently delete records from the Recycle Bin.
+pub(crate) async fn handle_empty_recycle_bin(
+    client: &SalesforceRestClient,
+    request: EmptyRecycleBinRequest,
+) -> BridgeResult<Vec<CollectionResult>> {
+    let ids: Vec<&str> = request.ids.iter().map(|s| s.as_str()).collect();
+    match client.empty_recycle_bin(&ids).await {
+        Ok(results) => BridgeResult::ok(collection_results_to_bridge(results)),
+        Err(e) => {
+            let (code, message) = sanitize_rest_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Restore deleted records from the Recycle Bin.
+pub(crate) async fn handle_undelete(
+    client: &SalesforceRestClient,
+    request: UndeleteRequest,
+) -> BridgeResult<Vec<CollectionResult>> {
+    let ids: Vec<&str> = request.ids.iter().map(|s| s.as_str()).collect();
+    match client.undelete(&ids).await {
+        Ok(results) => BridgeResult::ok(collection_results_to_bridge(results)),
+        Err(e) => {
+            let (code, message) = sanitize_rest_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
 // =============================================================================
 // Utility functions
 // =============================================================================
@@ -86,7 +116,7 @@ fn collection_results_to_bridge(
     results
         .into_iter()
         .map(|r| CollectionResult {
-            id: r.id,
+            id: r.id.map(String::from),
             success: r.success,
             errors: r
                 .errors
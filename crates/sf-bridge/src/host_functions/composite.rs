@@ -8,7 +8,22 @@ pub(crate) async fn handle_composite(
     client: &SalesforceRestClient,
     request: CompositeRequest,
 ) -> BridgeResult<CompositeResponse> {
-    let sf_request = busbar_sf_rest::CompositeRequest {
+    if let Some(err) = reject_auth_headers(request.subrequests.iter().map(|s| &s.http_headers)) {
+        return err;
+    }
+    match client.composite(&composite_request(request)).await {
+        Ok(result) => BridgeResult::ok(composite_response(result)),
+        Err(e) => {
+            let (code, message) = sanitize_rest_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// The client request for a guest's composite request. Shared with the
+/// Tooling API's composite endpoint, which takes the same shape.
+pub(super) fn composite_request(request: CompositeRequest) -> busbar_sf_rest::CompositeRequest {
+    busbar_sf_rest::CompositeRequest {
         all_or_none: request.all_or_none,
         collate_subrequests: false,
         subrequests: request
@@ -19,26 +34,23 @@ pub(crate) async fn handle_composite(
                 url: s.url,
                 reference_id: s.reference_id,
                 body: s.body,
+                http_headers: s.http_headers,
             })
             .collect(),
-    };
+    }
+}
 
-    match client.composite(&sf_request).await {
-        Ok(result) => BridgeResult::ok(CompositeResponse {
-            responses: result
-                .responses
-                .into_iter()
-                .map(|r| CompositeSubresponse {
-                    body: r.body,
-                    http_status_code: r.http_status_code,
-                    reference_id: r.reference_id,
-                })
-                .collect(),
-        }),
-        Err(e) => {
-            let (code, message) = sanitize_rest_error(&e);
-            BridgeResult::err(code, message)
-        }
+pub(super) fn composite_response(result: busbar_sf_rest::CompositeResponse) -> CompositeResponse {
+    CompositeResponse {
+        responses: result
+            .responses
+            .into_iter()
+            .map(|r| CompositeSubresponse {
+                body: r.body,
+                http_status_code: r.http_status_code,
+                reference_id: r.reference_id,
+            })
+            .collect(),
     }
 }
 
@@ -47,6 +59,9 @@ pub(crate) async fn handle_composite_batch(
     client: &SalesforceRestClient,
     request: CompositeBatchRequest,
 ) -> BridgeResult<CompositeBatchResponse> {
+    if let Some(err) = reject_auth_headers(request.subrequests.iter().map(|s| &s.http_headers)) {
+        return err;
+    }
     let sf_request = busbar_sf_rest::CompositeBatchRequest {
         halt_on_error: request.halt_on_error,
         batch_requests: request
@@ -58,6 +73,7 @@ pub(crate) async fn handle_composite_batch(
                 rich_input: s.rich_input,
                 binary_part_name: None,
                 binary_part_name_alias: None,
+                http_headers: s.http_headers,
             })
             .collect(),
     };
@@ -127,19 +143,82 @@ pub(crate) async fn handle_composite_tree(
     }
 }
 
-pub async fn handle_composite_graph(
-    rest: &busbar_sf_rest::SalesforceRestClient,
-    req: serde_json::Value,
-) -> BridgeResult<serde_json::Value> {
-    let sf_request: busbar_sf_rest::CompositeGraphRequest = match serde_json::from_value(req) {
-        Ok(r) => r,
-        Err(e) => return BridgeResult::err("INVALID_REQUEST", e.to_string()),
+/// Execute a composite graph API request.
+pub(crate) async fn handle_composite_graph(
+    client: &SalesforceRestClient,
+    request: CompositeGraphRequest,
+) -> BridgeResult<CompositeGraphResponse> {
+    if let Some(err) = reject_auth_headers(
+        request
+            .graphs
+            .iter()
+            .flat_map(|g| g.subrequests.iter().map(|s| &s.http_headers)),
+    ) {
+        return err;
+    }
+    let sf_request = busbar_sf_rest::CompositeGraphRequest {
+        graphs: request
+            .graphs
+            .into_iter()
+            .map(|g| busbar_sf_rest::GraphRequest {
+                graph_id: g.graph_id,
+                composite_request: g
+                    .subrequests
+                    .into_iter()
+                    .map(|s| busbar_sf_rest::CompositeSubrequest {
+                        method: s.method,
+                        url: s.url,
+                        reference_id: s.reference_id,
+                        body: s.body,
+                        http_headers: s.http_headers,
+                    })
+                    .collect(),
+            })
+            .collect(),
     };
-    match rest.composite_graph(&sf_request).await {
-        Ok(result) => BridgeResult::ok(serde_json::to_value(result).unwrap()),
+
+    match client.composite_graph(&sf_request).await {
+        Ok(result) => BridgeResult::ok(CompositeGraphResponse {
+            graphs: result
+                .graphs
+                .into_iter()
+                .map(|g| GraphResponse {
+                    graph_id: g.graph_id,
+                    is_successful: g.is_successful,
+                    responses: g
+                        .graph_response
+                        .responses
+                        .into_iter()
+                        .map(|r| CompositeSubresponse {
+                            body: r.body,
+                            http_status_code: r.http_status_code,
+                            reference_id: r.reference_id,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }),
         Err(e) => {
-            let (code, msg) = sanitize_rest_error(&e);
-            BridgeResult::err(code, msg)
+            let (code, message) = sanitize_rest_error(&e);
+            BridgeResult::err(code, message)
         }
     }
 }
+
+/// Subrequests run with the bridge's session; guests may not supply their
+/// own credentials in a subrequest header.
+pub(super) fn reject_auth_headers<'a, T>(
+    mut headers: impl Iterator<Item = &'a std::collections::BTreeMap<String, String>>,
+) -> Option<BridgeResult<T>> {
+    headers
+        .any(|h| {
+            h.keys()
+                .any(|name| name.eq_ignore_ascii_case("authorization"))
+        })
+        .then(|| {
+            BridgeResult::err(
+                "INVALID_REQUEST",
+                "subrequests may not set the Authorization header",
+            )
+        })
+}
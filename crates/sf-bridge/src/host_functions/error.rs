@@ -16,34 +16,11 @@ use busbar_sf_rest::ErrorKind as RestErrorKind;
 #[cfg(feature = "rest")]
 pub(crate) fn sanitize_rest_error(err: &busbar_sf_rest::Error) -> (String, String) {
     let code = match &err.kind {
-        RestErrorKind::Client(_msg) => {
-            // Check if the source is a client error with more specific kind
-            if let Some(source) = &err.source {
-                if let Some(client_err) = source.downcast_ref::<busbar_sf_client::Error>() {
-                    match &client_err.kind {
-                        ClientErrorKind::Http { status, .. } => format!("HTTP_{}", status),
-                        ClientErrorKind::RateLimited { .. } => "RATE_LIMITED".to_string(),
-                        ClientErrorKind::Authentication(_) => "AUTH_ERROR".to_string(),
-                        ClientErrorKind::Authorization(_) => "AUTHORIZATION_ERROR".to_string(),
-                        ClientErrorKind::NotFound(_) => "NOT_FOUND".to_string(),
-                        ClientErrorKind::PreconditionFailed(_) => "PRECONDITION_FAILED".to_string(),
-                        ClientErrorKind::Timeout => "TIMEOUT".to_string(),
-                        ClientErrorKind::Connection(_) => "CONNECTION_ERROR".to_string(),
-                        ClientErrorKind::Json(_) => "JSON_ERROR".to_string(),
-                        ClientErrorKind::InvalidUrl(_) => "INVALID_URL".to_string(),
-                        ClientErrorKind::Serialization(_) => "SERIALIZATION_ERROR".to_string(),
-                        ClientErrorKind::Config(_) => "CONFIG_ERROR".to_string(),
-                        ClientErrorKind::SalesforceApi { error_code, .. } => error_code.clone(),
-                        ClientErrorKind::RetriesExhausted { .. } => "RETRIES_EXHAUSTED".to_string(),
-                        ClientErrorKind::Other(_) => "CLIENT_ERROR".to_string(),
-                    }
-                } else {
-                    "CLIENT_ERROR".to_string()
-                }
-            } else {
-                "CLIENT_ERROR".to_string()
-            }
-        }
+        RestErrorKind::Client(_msg) => err
+            .source
+            .as_ref()
+            .and_then(|source| source.downcast_ref::<busbar_sf_client::Error>())
+            .map_or_else(|| "CLIENT_ERROR".to_string(), client_error_code),
         RestErrorKind::Auth(_) => "AUTH_ERROR".to_string(),
         RestErrorKind::Salesforce { error_code, .. } => error_code.clone(),
         RestErrorKind::Other(_) => "OTHER_ERROR".to_string(),
@@ -52,39 +29,64 @@ pub(crate) fn sanitize_rest_error(err: &busbar_sf_rest::Error) -> (String, Strin
     (code, err.to_string())
 }
 
+/// The code for a client error, by its specific kind.
+#[cfg(feature = "rest")]
+fn client_error_code(err: &busbar_sf_client::Error) -> String {
+    match &err.kind {
+        ClientErrorKind::Http { status, .. } => format!("HTTP_{}", status),
+        ClientErrorKind::RateLimited { .. } => "RATE_LIMITED".to_string(),
+        ClientErrorKind::Authentication(_) => "AUTH_ERROR".to_string(),
+        ClientErrorKind::Authorization(_) => "AUTHORIZATION_ERROR".to_string(),
+        ClientErrorKind::NotFound(_) => "NOT_FOUND".to_string(),
+        ClientErrorKind::PreconditionFailed(_) => "PRECONDITION_FAILED".to_string(),
+        ClientErrorKind::Timeout => "TIMEOUT".to_string(),
+        ClientErrorKind::Connection(_) => "CONNECTION_ERROR".to_string(),
+        ClientErrorKind::Json(_) => "JSON_ERROR".to_string(),
+        ClientErrorKind::InvalidUrl(_) => "INVALID_URL".to_string(),
+        ClientErrorKind::Serialization(_) => "SERIALIZATION_ERROR".to_string(),
+        ClientErrorKind::Config(_) => "CONFIG_ERROR".to_string(),
+        ClientErrorKind::SalesforceApi { error_code, .. } => error_code.clone(),
+        ClientErrorKind::ApiSoftLimit { .. } => "API_SOFT_LIMIT".to_string(),
+        ClientErrorKind::RetriesExhausted { .. } => "RETRIES_EXHAUSTED".to_string(),
+        ClientErrorKind::MaintenanceWindow { .. } => "MAINTENANCE_WINDOW".to_string(),
+        ClientErrorKind::Other(_) => "CLIENT_ERROR".to_string(),
+    }
+}
+
+/// Sanitize an error from one of the other API crates by its source: the
+/// REST or client error it wraps, if any, so outages get the same codes
+/// whichever API they hit.
+#[cfg(any(feature = "bulk", feature = "tooling", feature = "metadata"))]
+fn sanitize_wrapped_error(
+    source: Option<&(dyn std::error::Error + Send + Sync + 'static)>,
+    message: String,
+) -> Option<(String, String)> {
+    let source = source?;
+    if let Some(rest_err) = source.downcast_ref::<busbar_sf_rest::Error>() {
+        return Some(sanitize_rest_error(rest_err));
+    }
+    source
+        .downcast_ref::<busbar_sf_client::Error>()
+        .map(|client_err| (client_error_code(client_err), message))
+}
+
 /// Sanitize bulk API errors.
 #[cfg(feature = "bulk")]
 pub(crate) fn sanitize_bulk_error(err: &busbar_sf_bulk::Error) -> (String, String) {
-    // Bulk errors typically wrap client/rest errors, so try to extract those
-    if let Some(source) = &err.source {
-        if let Some(rest_err) = source.downcast_ref::<busbar_sf_rest::Error>() {
-            return sanitize_rest_error(rest_err);
-        }
-    }
-    // Fallback to generic bulk error code
-    ("BULK_ERROR".to_string(), err.to_string())
+    sanitize_wrapped_error(err.source.as_deref(), err.to_string())
+        .unwrap_or_else(|| ("BULK_ERROR".to_string(), err.to_string()))
 }
 
 /// Sanitize tooling API errors.
 #[cfg(feature = "tooling")]
 pub(crate) fn sanitize_tooling_error(err: &busbar_sf_tooling::Error) -> (String, String) {
-    // Tooling errors typically wrap client/rest errors
-    if let Some(source) = &err.source {
-        if let Some(rest_err) = source.downcast_ref::<busbar_sf_rest::Error>() {
-            return sanitize_rest_error(rest_err);
-        }
-    }
-    ("TOOLING_ERROR".to_string(), err.to_string())
+    sanitize_wrapped_error(err.source.as_deref(), err.to_string())
+        .unwrap_or_else(|| ("TOOLING_ERROR".to_string(), err.to_string()))
 }
 
 /// Sanitize metadata API errors.
 #[cfg(feature = "metadata")]
 pub(crate) fn sanitize_metadata_error(err: &busbar_sf_metadata::Error) -> (String, String) {
-    // Metadata errors wrap various error types
-    if let Some(source) = &err.source {
-        if let Some(rest_err) = source.downcast_ref::<busbar_sf_rest::Error>() {
-            return sanitize_rest_error(rest_err);
-        }
-    }
-    ("METADATA_ERROR".to_string(), err.to_string())
+    sanitize_wrapped_error(err.source.as_deref(), err.to_string())
+        .unwrap_or_else(|| ("METADATA_ERROR".to_string(), err.to_string()))
 }
@@ -0,0 +1,176 @@
+//! Outbound HTTP host function handler.
+//!
+//! Lets guests call third-party APIs with secrets that stay on the host.
+//! Requests may only go to hosts allowed by the [`HttpPolicy`] or bound to a
+//! registered secret, and are subject to the policy's size and time limits.
+use std::time::Instant;
+
+use crate::policy::HttpPolicy;
+use crate::secrets::SecretStore;
+use busbar_sf_wasm_types::*;
+
+/// Tracing target for the outbound request audit log.
+const AUDIT_TARGET: &str = "busbar_sf_bridge::audit";
+
+/// Send an HTTP request on behalf of the guest.
+pub(crate) async fn handle_http_request(
+    http: &reqwest::Client,
+    policy: &HttpPolicy,
+    secrets: &SecretStore,
+    request: OutboundHttpRequest,
+) -> BridgeResult<OutboundHttpResponse> {
+    let url = match reqwest::Url::parse(&request.url) {
+        Ok(url) if url.scheme() == "https" => url,
+        Ok(_) => return BridgeResult::err("INVALID_URL", "Only https:// URLs are allowed"),
+        Err(e) => return BridgeResult::err("INVALID_URL", e.to_string()),
+    };
+    let Some(host) = url.host_str().map(str::to_string) else {
+        return BridgeResult::err("INVALID_URL", "URL has no host");
+    };
+    let path = url.path().to_string();
+    if !policy.allows_host(&host) && !secrets.knows_host(&host) {
+        tracing::warn!(
+            target: AUDIT_TARGET,
+            method = %request.method,
+            host = %host,
+            path = %path,
+            "outbound request denied: host not allowed"
+        );
+        return BridgeResult::err(
+            "HOST_NOT_ALLOWED",
+            format!("Outbound requests to {host} are not allowed"),
+        );
+    }
+
+    let method = match request.method.to_ascii_uppercase().as_str() {
+        "GET" => reqwest::Method::GET,
+        "POST" => reqwest::Method::POST,
+        "PUT" => reqwest::Method::PUT,
+        "PATCH" => reqwest::Method::PATCH,
+        "DELETE" => reqwest::Method::DELETE,
+        "HEAD" => reqwest::Method::HEAD,
+        other => {
+            return BridgeResult::err(
+                "INVALID_METHOD",
+                format!("Unsupported HTTP method: {other}"),
+            )
+        }
+    };
+
+    let request_bytes = request.body.as_ref().map_or(0, String::len);
+    if request_bytes > policy.max_request_bytes {
+        tracing::warn!(
+            target: AUDIT_TARGET,
+            method = %method,
+            host = %host,
+            path = %path,
+            request_bytes,
+            "outbound request denied: body too large"
+        );
+        return BridgeResult::err(
+            "REQUEST_TOO_LARGE",
+            format!(
+                "Request body is {request_bytes} bytes; the limit is {}",
+                policy.max_request_bytes
+            ),
+        );
+    }
+
+    let started = Instant::now();
+    let audit_method = method.clone();
+    let mut builder = http.request(method, url).timeout(policy.timeout);
+    for (name, value) in &request.headers {
+        match secrets.resolve(value, &host) {
+            Ok(value) => builder = builder.header(name.as_str(), value),
+            Err(e) => return BridgeResult::err("SECRET_ERROR", e.to_string()),
+        }
+    }
+    if let Some(body) = request.body {
+        builder = builder.body(body);
+    }
+
+    let result = send(builder, policy, secrets).await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match &result {
+        BridgeResult::Ok(response) => tracing::info!(
+            target: AUDIT_TARGET,
+            method = %audit_method,
+            host = %host,
+            path = %path,
+            status = response.status,
+            request_bytes,
+            response_bytes = response.body.len(),
+            elapsed_ms,
+            "outbound request"
+        ),
+        BridgeResult::Err(e) => tracing::warn!(
+            target: AUDIT_TARGET,
+            method = %audit_method,
+            host = %host,
+            path = %path,
+            request_bytes,
+            elapsed_ms,
+            error = %e.code,
+            "outbound request failed"
+        ),
+    }
+    result
+}
+
+/// Send the request and read at most `policy.max_response_bytes` of body.
+async fn send(
+    builder: reqwest::RequestBuilder,
+    policy: &HttpPolicy,
+    secrets: &SecretStore,
+) -> BridgeResult<OutboundHttpResponse> {
+    let mut response = match builder.send().await {
+        Ok(response) => response,
+        Err(e) if e.is_timeout() => return BridgeResult::err("TIMEOUT", "Request timed out"),
+        Err(e) => return BridgeResult::err("HTTP_ERROR", secrets.redact(&e.to_string())),
+    };
+    let too_large = || {
+        BridgeResult::err(
+            "RESPONSE_TOO_LARGE",
+            format!(
+                "Response body exceeds the {} byte limit",
+                policy.max_response_bytes
+            ),
+        )
+    };
+    if response
+        .content_length()
+        .is_some_and(|len| len > policy.max_response_bytes as u64)
+    {
+        return too_large();
+    }
+
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            let value = value.to_str().ok()?;
+            Some((name.as_str().to_string(), secrets.redact(value)))
+        })
+        .collect();
+
+    let mut body = Vec::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                if body.len() + chunk.len() > policy.max_response_bytes {
+                    return too_large();
+                }
+                body.extend_from_slice(&chunk);
+            }
+            Ok(None) => break,
+            Err(e) if e.is_timeout() => return BridgeResult::err("TIMEOUT", "Request timed out"),
+            Err(e) => return BridgeResult::err("HTTP_ERROR", secrets.redact(&e.to_string())),
+        }
+    }
+    BridgeResult::ok(OutboundHttpResponse {
+        status,
+        headers,
+        body: secrets.redact(&String::from_utf8_lossy(&body)),
+    })
+}
@@ -58,3 +58,85 @@ pub async fn handle_data_categories(
         }
     }
 }
+
+pub async fn handle_knowledge_article(
+    rest: &busbar_sf_rest::SalesforceRestClient,
+    req: KnowledgeArticleRequest,
+) -> BridgeResult<serde_json::Value> {
+    match rest.knowledge_article(&req.article).await {
+        Ok(result) => BridgeResult::ok(serde_json::to_value(result).unwrap()),
+        Err(e) => {
+            let (code, msg) = sanitize_rest_error(&e);
+            BridgeResult::err(code, msg)
+        }
+    }
+}
+
+pub async fn handle_create_knowledge_draft(
+    rest: &busbar_sf_rest::SalesforceRestClient,
+    req: CreateKnowledgeDraftRequest,
+) -> BridgeResult<CreateResponse> {
+    let mut draft = busbar_sf_rest::KnowledgeArticleDraft::new(req.title, req.url_name);
+    draft.summary = req.summary;
+    draft.language = req.language;
+    draft.fields = req.fields;
+    match rest.create_knowledge_draft(&req.article_type, &draft).await {
+        Ok(id) => BridgeResult::ok(CreateResponse {
+            id: id.into(),
+            success: true,
+            errors: vec![],
+        }),
+        Err(e) => {
+            let (code, msg) = sanitize_rest_error(&e);
+            BridgeResult::err(code, msg)
+        }
+    }
+}
+
+pub async fn handle_publish_knowledge_articles(
+    rest: &busbar_sf_rest::SalesforceRestClient,
+    req: PublishKnowledgeArticlesRequest,
+) -> BridgeResult<()> {
+    let ids: Vec<&str> = req.version_ids.iter().map(String::as_str).collect();
+    match rest.publish_knowledge_articles(&ids, req.new_version).await {
+        Ok(()) => BridgeResult::ok(()),
+        Err(e) => {
+            let (code, msg) = sanitize_rest_error(&e);
+            BridgeResult::err(code, msg)
+        }
+    }
+}
+
+pub async fn handle_archive_knowledge_articles(
+    rest: &busbar_sf_rest::SalesforceRestClient,
+    req: ArchiveKnowledgeArticlesRequest,
+) -> BridgeResult<()> {
+    let ids: Vec<&str> = req.version_ids.iter().map(String::as_str).collect();
+    match rest.archive_knowledge_articles(&ids).await {
+        Ok(()) => BridgeResult::ok(()),
+        Err(e) => {
+            let (code, msg) = sanitize_rest_error(&e);
+            BridgeResult::err(code, msg)
+        }
+    }
+}
+
+pub async fn handle_knowledge_draft_from_online(
+    rest: &busbar_sf_rest::SalesforceRestClient,
+    req: KnowledgeDraftFromOnlineRequest,
+) -> BridgeResult<CreateResponse> {
+    match rest
+        .create_knowledge_draft_from_online(&req.knowledge_article_id, req.unpublish)
+        .await
+    {
+        Ok(id) => BridgeResult::ok(CreateResponse {
+            id,
+            success: true,
+            errors: vec![],
+        }),
+        Err(e) => {
+            let (code, msg) = sanitize_rest_error(&e);
+            BridgeResult::err(code, msg)
+        }
+    }
+}
@@ -25,6 +25,7 @@ pub(crate) async fn handle_metadata_deploy(
     let options = busbar_sf_metadata::DeployOptions {
         check_only: request.options.check_only,
         rollback_on_error: request.options.rollback_on_error,
+        purge_on_delete: request.options.purge_on_delete,
         test_level,
         run_tests: request.options.run_tests,
         ..Default::default()
@@ -49,6 +50,7 @@ pub(crate) async fn handle_metadata_check_deploy_status(
         .await
     {
         Ok(result) => BridgeResult::ok(MetadataDeployResult {
+            details: request.include_details.then(|| deploy_details(&result)),
             id: result.id,
             done: result.done,
             status: format!("{:?}", result.status),
@@ -117,6 +119,95 @@ pub(crate) async fn handle_metadata_check_retrieve_status(
             success: result.success,
             zip_base64: result.zip_file,
             error_message: result.error_message,
+            file_properties: result
+                .file_properties
+                .into_iter()
+                .map(|p| FileProperties {
+                    full_name: p.full_name,
+                    file_name: p.file_name,
+                    component_type: p.component_type,
+                    id: p.id,
+                    namespace_prefix: p.namespace_prefix,
+                    manageable_state: p.manageable_state,
+                    last_modified_date: non_empty(p.last_modified_date),
+                    last_modified_by_name: non_empty(p.last_modified_by_name),
+                })
+                .collect(),
+            messages: result
+                .messages
+                .into_iter()
+                .map(|m| RetrieveMessage {
+                    file_name: m.file_name,
+                    problem: m.problem,
+                })
+                .collect(),
+        }),
+        Err(e) => {
+            let (code, message) = sanitize_metadata_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Status of a deploy tracked as a long-running operation.
+pub(crate) async fn handle_deploy_operation_status(
+    client: &MetadataClient,
+    handle: String,
+) -> BridgeResult<OperationStatus> {
+    use busbar_sf_metadata::DeployStatus;
+
+    match client.check_deploy_status(&handle, false).await {
+        Ok(result) => BridgeResult::ok(OperationStatus {
+            handle,
+            kind: OperationKind::Deploy,
+            state: match result.status {
+                DeployStatus::Pending | DeployStatus::InProgress | DeployStatus::Canceling => {
+                    OperationState::InProgress
+                }
+                DeployStatus::Succeeded | DeployStatus::SucceededPartial => {
+                    OperationState::Succeeded
+                }
+                DeployStatus::Failed => OperationState::Failed,
+                DeployStatus::Canceled => OperationState::Canceled,
+            },
+            status: format!("{:?}", result.status),
+            processed: Some(result.number_components_deployed.into()),
+            total: Some(result.number_components_total.into()),
+            error_message: result.error_message,
+        }),
+        Err(e) => {
+            let (code, message) = sanitize_metadata_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Status of a retrieve tracked as a long-running operation.
+///
+/// The zip is left out; fetch it with `sf_metadata_check_retrieve_status`
+/// once the retrieve has succeeded.
+pub(crate) async fn handle_retrieve_operation_status(
+    client: &MetadataClient,
+    handle: String,
+) -> BridgeResult<OperationStatus> {
+    use busbar_sf_metadata::RetrieveStatus;
+
+    match client.check_retrieve_status(&handle, false).await {
+        Ok(result) => BridgeResult::ok(OperationStatus {
+            handle,
+            kind: OperationKind::Retrieve,
+            state: match result.status {
+                RetrieveStatus::Pending
+                | RetrieveStatus::InProgress
+                | RetrieveStatus::Canceling => OperationState::InProgress,
+                RetrieveStatus::Succeeded => OperationState::Succeeded,
+                RetrieveStatus::Failed => OperationState::Failed,
+                RetrieveStatus::Canceled => OperationState::Canceled,
+            },
+            status: format!("{:?}", result.status),
+            processed: None,
+            total: None,
+            error_message: result.error_message,
         }),
         Err(e) => {
             let (code, message) = sanitize_metadata_error(&e);
@@ -187,6 +278,51 @@ pub(crate) async fn handle_metadata_describe(
 // Utility functions
 // =============================================================================
 
+/// The failures and test results the guest sees with `include_details`.
+fn deploy_details(result: &busbar_sf_metadata::DeployResult) -> DeployDetails {
+    DeployDetails {
+        component_failures: result
+            .component_failures
+            .iter()
+            .map(|f| DeployComponentFailure {
+                component_type: f.component_type.clone(),
+                full_name: f.full_name.clone(),
+                file_name: f.file_name.clone(),
+                line_number: f.line_number,
+                column_number: f.column_number,
+                problem: f.problem.clone(),
+                problem_type: f.problem_type.clone(),
+            })
+            .collect(),
+        test_failures: result
+            .test_failures
+            .iter()
+            .map(|f| DeployTestFailure {
+                name: f.name.clone(),
+                method_name: f.method_name.clone(),
+                message: f.message.clone(),
+                stack_trace: f.stack_trace.clone(),
+                namespace: f.namespace.clone(),
+            })
+            .collect(),
+        code_coverage_warnings: result
+            .code_coverage_warnings
+            .iter()
+            .map(|w| CodeCoverageWarning {
+                name: w.name.clone(),
+                message: w.message.clone(),
+                namespace: w.namespace.clone(),
+            })
+            .collect(),
+    }
+}
+
+/// `None` for the empty strings the Metadata API sends for unset dates
+/// and names.
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
 fn parse_test_level(s: &str) -> Result<busbar_sf_metadata::TestLevel, String> {
     match s {
         "NoTestRun" => Ok(busbar_sf_metadata::TestLevel::NoTestRun),
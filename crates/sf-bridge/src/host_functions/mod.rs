@@ -6,7 +6,10 @@
 //! ABI boundary) is handled in the parent module.
 
 mod error;
+mod http;
 
+#[cfg(feature = "rest")]
+mod apex_rest;
 #[cfg(feature = "rest")]
 mod binary;
 #[cfg(feature = "rest")]
@@ -51,6 +54,8 @@ mod metadata;
 
 // Re-export handler functions based on features
 #[cfg(feature = "rest")]
+pub(crate) use apex_rest::*;
+#[cfg(feature = "rest")]
 pub(crate) use binary::*;
 #[cfg(feature = "rest")]
 pub(crate) use collections::*;
@@ -60,6 +65,7 @@ pub(crate) use composite::*;
 pub(crate) use consent::*;
 #[cfg(feature = "rest")]
 pub(crate) use embedded_service::*;
+pub(crate) use http::*;
 #[cfg(feature = "rest")]
 pub(crate) use knowledge::*;
 #[cfg(feature = "rest")]
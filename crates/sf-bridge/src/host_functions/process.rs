@@ -3,10 +3,19 @@ use super::error::*;
 use busbar_sf_rest::SalesforceRestClient;
 use busbar_sf_rest::{
     ApprovalActionType, ApprovalRequest as RestApprovalRequest,
-    ProcessRuleRequest as RestProcessRuleRequest,
+    ProcessRuleRequest as RestProcessRuleRequest, SalesforceId,
 };
 use busbar_sf_wasm_types::*;
 
+/// Parse record IDs sent by the guest.
+fn parse_ids(ids: &[String]) -> Option<Vec<SalesforceId>> {
+    ids.iter().map(|id| id.parse().ok()).collect()
+}
+
+fn invalid_id<T>() -> BridgeResult<T> {
+    BridgeResult::err("INVALID_ID", "Invalid Salesforce ID format")
+}
+
 /// List all process rules.
 pub(crate) async fn handle_list_process_rules(
     client: &SalesforceRestClient,
@@ -70,9 +79,10 @@ pub(crate) async fn handle_trigger_process_rules(
     client: &SalesforceRestClient,
     request: ProcessRuleRequest,
 ) -> BridgeResult<ProcessRuleResult> {
-    let rest_request = RestProcessRuleRequest {
-        context_ids: request.context_ids,
+    let Some(context_ids) = parse_ids(&request.context_ids) else {
+        return invalid_id();
     };
+    let rest_request = RestProcessRuleRequest { context_ids };
     match client.trigger_process_rules(&rest_request).await {
         Ok(result) => BridgeResult::ok(ProcessRuleResult {
             errors: result
@@ -137,12 +147,26 @@ pub(crate) async fn handle_submit_approval(
         _ => return BridgeResult::err("INVALID_ACTION_TYPE", "Invalid approval action type"),
     };
 
+    let Ok(context_id) = request.context_id.parse() else {
+        return invalid_id();
+    };
+    let context_actor_id = match request.context_actor_id.as_deref().map(str::parse) {
+        Some(Ok(id)) => Some(id),
+        Some(Err(_)) => return invalid_id(),
+        None => None,
+    };
+    let next_approver_ids = match request.next_approver_ids.as_deref().map(parse_ids) {
+        Some(Some(ids)) => Some(ids),
+        Some(None) => return invalid_id(),
+        None => None,
+    };
+
     let rest_request = RestApprovalRequest {
         action_type,
-        context_id: request.context_id,
-        context_actor_id: request.context_actor_id,
+        context_id,
+        context_actor_id,
         comments: request.comments,
-        next_approver_ids: request.next_approver_ids,
+        next_approver_ids,
         process_definition_name_or_id: request.process_definition_name_or_id,
         skip_entry_criteria: request.skip_entry_criteria,
     };
@@ -2,29 +2,41 @@
 //!
 //! Handles CRUD operations, queries, describe, and basic REST endpoints.
 use super::error::*;
-use busbar_sf_rest::SalesforceRestClient;
+use crate::spill::QuerySpill;
+use busbar_sf_rest::{SalesforceRestClient, SoqlValue};
 use busbar_sf_wasm_types::*;
 
 /// Execute a SOQL query.
+///
+/// Binds are filled in host-side. With `bound_only`, queries must take
+/// every string value through a bind.
 pub(crate) async fn handle_query(
     client: &SalesforceRestClient,
+    bound_only: bool,
+    spill: &QuerySpill,
     request: QueryRequest,
 ) -> BridgeResult<QueryResponse> {
+    let soql = match bind_guest_soql(request.soql, &request.binds, bound_only) {
+        Ok(soql) => soql,
+        Err((code, message)) => return BridgeResult::err(code, message),
+    };
+
     let result = if request.include_deleted {
         client
-            .query_all_including_deleted::<serde_json::Value>(&request.soql)
+            .query_all_including_deleted::<serde_json::Value>(&soql)
             .await
     } else {
-        client.query::<serde_json::Value>(&request.soql).await
+        client.query::<serde_json::Value>(&soql).await
     };
 
     match result {
-        Ok(qr) => BridgeResult::ok(QueryResponse {
+        Ok(qr) => BridgeResult::ok(spill.admit(QueryResponse {
             total_size: qr.total_size,
             done: qr.done,
             records: qr.records,
             next_records_url: qr.next_records_url,
-        }),
+            cursor: None,
+        })),
         Err(e) => {
             let (code, message) = sanitize_rest_error(&e);
             BridgeResult::err(code, message)
@@ -32,21 +44,71 @@ pub(crate) async fn handle_query(
     }
 }
 
+/// Fill a guest's SOQL template with its binds.
+///
+/// Without binds the SOQL is used as given, unless the bridge only accepts
+/// bound queries, in which case it is still checked for string literals.
+/// Returns the error code and message to give the guest on failure.
+pub(crate) fn bind_guest_soql(
+    soql: String,
+    binds: &std::collections::BTreeMap<String, SoqlBind>,
+    bound_only: bool,
+) -> Result<String, (String, String)> {
+    if binds.is_empty() && !bound_only {
+        return Ok(soql);
+    }
+    let binds = binds
+        .iter()
+        .map(|(name, bind)| Ok((name.clone(), soql_value(name, bind)?)))
+        .collect::<Result<_, String>>()
+        .map_err(|message| ("INVALID_BIND".to_string(), message))?;
+    busbar_sf_rest::bind_soql(&soql, &binds).map_err(|e| sanitize_rest_error(&e))
+}
+
+/// Convert a guest bind into the value sf-rest renders.
+fn soql_value(name: &str, bind: &SoqlBind) -> Result<SoqlValue, String> {
+    Ok(match bind {
+        SoqlBind::Null => SoqlValue::Null,
+        SoqlBind::Boolean(b) => SoqlValue::Boolean(*b),
+        SoqlBind::Integer(n) => SoqlValue::Integer(*n),
+        SoqlBind::Double(n) => SoqlValue::Double(*n),
+        SoqlBind::String(s) => SoqlValue::String(s.clone()),
+        SoqlBind::Id(id) => SoqlValue::Id(id.clone()),
+        SoqlBind::Date(date) => SoqlValue::Date(
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date for :{name}: {date}"))?,
+        ),
+        SoqlBind::DateTime(time) => SoqlValue::DateTime(
+            chrono::DateTime::parse_from_rfc3339(time)
+                .map_err(|_| format!("Invalid date/time for :{name}: {time}"))?
+                .with_timezone(&chrono::Utc),
+        ),
+        SoqlBind::List(values) => SoqlValue::List(
+            values
+                .iter()
+                .map(|v| soql_value(name, v))
+                .collect::<Result<_, _>>()?,
+        ),
+    })
+}
+
 /// Fetch the next page of query results.
 pub(crate) async fn handle_query_more(
     client: &SalesforceRestClient,
+    spill: &QuerySpill,
     request: QueryMoreRequest,
 ) -> BridgeResult<QueryResponse> {
     match client
         .query_more::<serde_json::Value>(&request.next_records_url)
         .await
     {
-        Ok(qr) => BridgeResult::ok(QueryResponse {
+        Ok(qr) => BridgeResult::ok(spill.admit(QueryResponse {
             total_size: qr.total_size,
             done: qr.done,
             records: qr.records,
             next_records_url: qr.next_records_url,
-        }),
+            cursor: None,
+        })),
         Err(e) => {
             let (code, message) = sanitize_rest_error(&e);
             BridgeResult::err(code, message)
@@ -54,6 +116,50 @@ pub(crate) async fn handle_query_more(
     }
 }
 
+/// Most records returned by one `sf_query_cursor_next` call.
+const MAX_CURSOR_RECORDS: u32 = 2000;
+
+/// Read the next records held behind a query cursor, fetching the next
+/// Salesforce page once the held ones run out.
+pub(crate) async fn handle_query_cursor_next(
+    client: &SalesforceRestClient,
+    spill: &QuerySpill,
+    request: QueryCursorRequest,
+) -> BridgeResult<QueryCursorPage> {
+    let Some(mut held) = spill.take(&request.cursor) else {
+        return BridgeResult::err("INVALID_CURSOR", "Unknown or exhausted query cursor");
+    };
+
+    if held.records.is_empty() {
+        if let Some(url) = held.next_records_url.take() {
+            match client.query_more::<serde_json::Value>(&url).await {
+                Ok(qr) => {
+                    held.records = qr.records.into();
+                    held.next_records_url = if qr.done { None } else { qr.next_records_url };
+                }
+                Err(e) => {
+                    // Keep the cursor so the guest can retry.
+                    held.next_records_url = Some(url);
+                    spill.put_back(request.cursor, held);
+                    let (code, message) = sanitize_rest_error(&e);
+                    return BridgeResult::err(code, message);
+                }
+            }
+        }
+    }
+
+    let max = request
+        .max_records
+        .unwrap_or(MAX_CURSOR_RECORDS)
+        .clamp(1, MAX_CURSOR_RECORDS) as usize;
+    let records: Vec<_> = held.records.drain(..max.min(held.records.len())).collect();
+    let done = held.is_exhausted();
+    if !done {
+        spill.put_back(request.cursor, held);
+    }
+    BridgeResult::ok(QueryCursorPage { records, done })
+}
+
 /// Create a new record.
 pub(crate) async fn handle_create(
     client: &SalesforceRestClient,
@@ -61,7 +167,7 @@ pub(crate) async fn handle_create(
 ) -> BridgeResult<CreateResponse> {
     match client.create(&request.sobject, &request.record).await {
         Ok(id) => BridgeResult::ok(CreateResponse {
-            id,
+            id: id.into(),
             success: true,
             errors: vec![],
         }),
@@ -100,10 +206,24 @@ pub(crate) async fn handle_update(
     client: &SalesforceRestClient,
     request: UpdateRequest,
 ) -> BridgeResult<()> {
-    match client
-        .update(&request.sobject, &request.id, &request.record)
-        .await
-    {
+    let result = match request.precondition {
+        Some(precondition) => {
+            client
+                .update_if_unchanged(
+                    &request.sobject,
+                    &request.id,
+                    &request.record,
+                    &rest_precondition(precondition),
+                )
+                .await
+        }
+        None => {
+            client
+                .update(&request.sobject, &request.id, &request.record)
+                .await
+        }
+    };
+    match result {
         Ok(()) => BridgeResult::ok(()),
         Err(e) => {
             let (code, message) = sanitize_rest_error(&e);
@@ -117,7 +237,70 @@ pub(crate) async fn handle_delete(
     client: &SalesforceRestClient,
     request: DeleteRequest,
 ) -> BridgeResult<()> {
-    match client.delete(&request.sobject, &request.id).await {
+    let result = match request.precondition {
+        Some(precondition) => {
+            client
+                .delete_if_unchanged(
+                    &request.sobject,
+                    &request.id,
+                    &rest_precondition(precondition),
+                )
+                .await
+        }
+        None => client.delete(&request.sobject, &request.id).await,
+    };
+    match result {
+        Ok(()) => BridgeResult::ok(()),
+        Err(e) => {
+            let (code, message) = sanitize_rest_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+fn rest_precondition(precondition: WritePrecondition) -> busbar_sf_rest::WritePrecondition {
+    match precondition {
+        WritePrecondition::IfMatch(etag) => busbar_sf_rest::WritePrecondition::IfMatch(etag),
+        WritePrecondition::IfUnmodifiedSince(timestamp) => {
+            busbar_sf_rest::WritePrecondition::IfUnmodifiedSince(timestamp)
+        }
+    }
+}
+
+/// Get a record by external ID.
+pub(crate) async fn handle_get_by_external_id(
+    client: &SalesforceRestClient,
+    request: ExternalIdRequest,
+) -> BridgeResult<serde_json::Value> {
+    match client
+        .get_by_external_id(
+            &request.sobject,
+            &request.external_id_field,
+            &request.external_id_value,
+        )
+        .await
+    {
+        Ok(record) => BridgeResult::ok(record),
+        Err(e) => {
+            let (code, message) = sanitize_rest_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Delete a record by external ID.
+pub(crate) async fn handle_delete_by_external_id(
+    client: &SalesforceRestClient,
+    request: ExternalIdRequest,
+) -> BridgeResult<()> {
+    match client
+        .delete_by_external_id(
+            &request.sobject,
+            &request.external_id_field,
+            &request.external_id_value,
+        )
+        .await
+    {
         Ok(()) => BridgeResult::ok(()),
         Err(e) => {
             let (code, message) = sanitize_rest_error(&e);
@@ -195,10 +378,21 @@ pub(crate) async fn handle_describe_sobject(
 }
 
 /// Execute a SOSL search.
+///
+/// Raw SOSL puts the search term in the query text, so it is refused when
+/// the bridge only accepts bound queries; `sf_parameterized_search` takes
+/// the term as data instead.
 pub(crate) async fn handle_search(
     client: &SalesforceRestClient,
+    bound_only: bool,
     request: SearchRequest,
 ) -> BridgeResult<SearchResponse> {
+    if bound_only {
+        return BridgeResult::err(
+            "BOUND_QUERY_REQUIRED",
+            "Raw SOSL is not allowed; use sf_parameterized_search",
+        );
+    }
     match client.search::<serde_json::Value>(&request.sosl).await {
         Ok(result) => BridgeResult::ok(SearchResponse {
             search_records: result.search_records,
@@ -245,13 +439,14 @@ pub(crate) async fn handle_versions(
     }
 }
 
-/// Get deleted records.
+/// Get deleted records, clamping and splitting the window as Salesforce
+/// requires.
 pub(crate) async fn handle_get_deleted(
     client: &SalesforceRestClient,
     request: GetDeletedRequest,
 ) -> BridgeResult<GetDeletedResult> {
     match client
-        .get_deleted(&request.sobject, &request.start, &request.end)
+        .get_deleted_window(&request.sobject, &request.start, &request.end)
         .await
     {
         Ok(result) => BridgeResult::ok(GetDeletedResult {
@@ -259,7 +454,7 @@ pub(crate) async fn handle_get_deleted(
                 .deleted_records
                 .into_iter()
                 .map(|r| DeletedRecord {
-                    id: r.id,
+                    id: r.id.into(),
                     deleted_date: r.deleted_date,
                 })
                 .collect(),
@@ -273,17 +468,18 @@ pub(crate) async fn handle_get_deleted(
     }
 }
 
-/// Get updated records.
+/// Get updated records, clamping and splitting the window as Salesforce
+/// requires.
 pub(crate) async fn handle_get_updated(
     client: &SalesforceRestClient,
     request: GetUpdatedRequest,
 ) -> BridgeResult<GetUpdatedResult> {
     match client
-        .get_updated(&request.sobject, &request.start, &request.end)
+        .get_updated_window(&request.sobject, &request.start, &request.end)
         .await
     {
         Ok(result) => BridgeResult::ok(GetUpdatedResult {
-            ids: result.ids,
+            ids: result.ids.into_iter().map(String::from).collect(),
             latest_date_covered: result.latest_date_covered,
         }),
         Err(e) => {
@@ -292,3 +488,172 @@ pub(crate) async fn handle_get_updated(
         }
     }
 }
+
+/// Get all updated and deleted records since a watermark.
+pub(crate) async fn handle_changes_since(
+    client: &SalesforceRestClient,
+    request: ChangesSinceRequest,
+) -> BridgeResult<ChangeJournal> {
+    match client
+        .changes_since(&request.sobject, &request.watermark)
+        .await
+    {
+        Ok(journal) => BridgeResult::ok(ChangeJournal {
+            changes: journal
+                .changes
+                .into_iter()
+                .map(|c| RecordChange {
+                    id: c.id.into(),
+                    kind: match c.kind {
+                        busbar_sf_rest::ChangeKind::Updated => ChangeKind::Updated,
+                        busbar_sf_rest::ChangeKind::Deleted => ChangeKind::Deleted,
+                    },
+                    deleted_date: c.deleted_date,
+                })
+                .collect(),
+            watermark: journal.watermark,
+        }),
+        Err(e) => {
+            let (code, message) = sanitize_rest_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Most objects a single schema snapshot may cover.
+const MAX_SNAPSHOT_OBJECTS: usize = 200;
+
+/// Build a compact schema snapshot of the requested SObjects.
+///
+/// Describes go through the client, so a [`DescribeCache`] attached to the
+/// bridge serves repeat snapshots without refetching unchanged objects.
+///
+/// [`DescribeCache`]: busbar_sf_rest::DescribeCache
+pub(crate) async fn handle_schema_snapshot(
+    client: &SalesforceRestClient,
+    request: SchemaSnapshotRequest,
+) -> BridgeResult<SchemaSnapshot> {
+    if request.sobjects.is_empty() {
+        return BridgeResult::err("INVALID_REQUEST", "No SObjects requested");
+    }
+    if request.sobjects.len() > MAX_SNAPSHOT_OBJECTS {
+        return BridgeResult::err(
+            "INVALID_REQUEST",
+            format!("At most {MAX_SNAPSHOT_OBJECTS} SObjects per snapshot"),
+        );
+    }
+
+    let mut objects: Vec<SchemaObject> = Vec::with_capacity(request.sobjects.len());
+    for sobject in &request.sobjects {
+        if objects.iter().any(|o| o.name.eq_ignore_ascii_case(sobject)) {
+            continue;
+        }
+        match client.describe_sobject(sobject).await {
+            Ok(describe) => objects.push(SchemaObject {
+                name: describe.name,
+                label: describe.label,
+                custom: describe.custom,
+                fields: describe
+                    .fields
+                    .into_iter()
+                    .map(|f| SchemaField {
+                        name: f.name,
+                        field_type: f.field_type,
+                        nillable: f.nillable,
+                        createable: f.createable,
+                        updateable: f.updateable,
+                        length: f.length.filter(|&len| len > 0),
+                        reference_to: f.reference_to.unwrap_or_default(),
+                    })
+                    .collect(),
+            }),
+            Err(e) => {
+                let (code, message) = sanitize_rest_error(&e);
+                return BridgeResult::err(code, message);
+            }
+        }
+    }
+
+    match schema_digest(&objects) {
+        Ok(digest) => BridgeResult::ok(SchemaSnapshot {
+            format_version: SchemaSnapshot::FORMAT_VERSION,
+            api_version: client.api_version().to_string(),
+            digest,
+            objects,
+        }),
+        Err(e) => BridgeResult::err("SERIALIZATION_ERROR", e.to_string()),
+    }
+}
+
+/// Hex SHA-256 of the objects' JSON form. Field order in the describe is
+/// stable, so unchanged schemas hash the same across calls.
+fn schema_digest(objects: &[SchemaObject]) -> Result<String, serde_json::Error> {
+    use sha2::{Digest, Sha256};
+    use std::fmt::Write;
+
+    let hash = Sha256::digest(serde_json::to_vec(objects)?);
+    Ok(hash.iter().fold(String::with_capacity(64), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soql_value_parses_dates() {
+        let bind = SoqlBind::List(vec![
+            SoqlBind::Date("2024-03-01".to_string()),
+            SoqlBind::DateTime("2024-03-01T10:00:00+02:00".to_string()),
+        ]);
+        let value = soql_value("when", &bind).unwrap();
+        let binds = [("when".to_string(), value)].into();
+        assert_eq!(
+            busbar_sf_rest::bind_soql("CreatedDate IN :when", &binds).unwrap(),
+            "CreatedDate IN (2024-03-01, 2024-03-01T08:00:00Z)"
+        );
+
+        let err = soql_value("d", &SoqlBind::Date("03/01/2024".to_string())).unwrap_err();
+        assert!(err.contains(":d"));
+    }
+
+    #[test]
+    fn test_schema_digest_is_stable() {
+        let objects = vec![SchemaObject {
+            name: "Account".to_string(),
+            label: "Account".to_string(),
+            custom: false,
+            fields: vec![SchemaField {
+                name: "Name".to_string(),
+                field_type: "string".to_string(),
+                nillable: false,
+                createable: true,
+                updateable: true,
+                length: Some(255),
+                reference_to: Vec::new(),
+            }],
+        }];
+        let digest = schema_digest(&objects).unwrap();
+        assert_eq!(digest.len(), 64);
+        assert_eq!(digest, schema_digest(&objects.clone()).unwrap());
+
+        let mut changed = objects;
+        changed[0].fields[0].length = Some(80);
+        assert_ne!(digest, schema_digest(&changed).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_bound_only_refuses_raw_sosl() {
+        let client =
+            SalesforceRestClient::new("https://example.my.salesforce.com", "token").unwrap();
+        let request = SearchRequest {
+            sosl: "FIND {Acme} IN NAME FIELDS RETURNING Account(Id)".to_string(),
+        };
+        let BridgeResult::Err(e) = handle_search(&client, true, request).await else {
+            panic!("raw SOSL should be refused");
+        };
+        assert_eq!(e.code, "BOUND_QUERY_REQUIRED");
+    }
+}
@@ -76,6 +76,32 @@ pub async fn handle_platform_event_schema(
     }
 }
 
+pub async fn handle_publish_event(
+    rest: &busbar_sf_rest::SalesforceRestClient,
+    req: PublishEventRequest,
+) -> BridgeResult<PublishEventResponse> {
+    match rest.publish_event(&req.event, &req.payload).await {
+        Ok(result) => BridgeResult::ok(PublishEventResponse {
+            success: result.success,
+            id: result.id.map(String::from),
+            event_uuid: result.event_uuid,
+            errors: result
+                .errors
+                .into_iter()
+                .map(|e| SalesforceApiError {
+                    status_code: e.status_code,
+                    message: e.message,
+                    fields: e.fields,
+                })
+                .collect(),
+        }),
+        Err(e) => {
+            let (code, msg) = sanitize_rest_error(&e);
+            BridgeResult::err(code, msg)
+        }
+    }
+}
+
 pub async fn handle_lightning_toggle_metrics(
     rest: &busbar_sf_rest::SalesforceRestClient,
 ) -> BridgeResult<serde_json::Value> {
@@ -4,16 +4,48 @@ use busbar_sf_tooling::ToolingClient;
 use busbar_sf_wasm_types::*;
 
 /// Execute a Tooling API SOQL query.
+///
+/// With `bound_only`, the SOQL may not contain string literals, as for
+/// `sf_query`.
 pub(crate) async fn handle_tooling_query(
     client: &ToolingClient,
+    bound_only: bool,
     request: ToolingQueryRequest,
 ) -> BridgeResult<QueryResponse> {
-    match client.query::<serde_json::Value>(&request.soql).await {
+    let soql = match super::rest::bind_guest_soql(request.soql, &Default::default(), bound_only) {
+        Ok(soql) => soql,
+        Err((code, message)) => return BridgeResult::err(code, message),
+    };
+    match client.query::<serde_json::Value>(&soql).await {
         Ok(qr) => BridgeResult::ok(QueryResponse {
             total_size: qr.total_size,
             done: qr.done,
             records: qr.records,
             next_records_url: qr.next_records_url,
+            cursor: None,
+        }),
+        Err(e) => {
+            let (code, message) = sanitize_tooling_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Fetch the next page of Tooling query results.
+pub(crate) async fn handle_tooling_query_more(
+    client: &ToolingClient,
+    request: ToolingQueryMoreRequest,
+) -> BridgeResult<QueryResponse> {
+    match client
+        .query_more::<serde_json::Value>(&request.next_records_url)
+        .await
+    {
+        Ok(qr) => BridgeResult::ok(QueryResponse {
+            total_size: qr.total_size,
+            done: qr.done,
+            records: qr.records,
+            next_records_url: qr.next_records_url,
+            cursor: None,
         }),
         Err(e) => {
             let (code, message) = sanitize_tooling_error(&e);
@@ -27,8 +59,19 @@ pub(crate) async fn handle_tooling_execute_anonymous(
     client: &ToolingClient,
     request: ExecuteAnonymousRequest,
 ) -> BridgeResult<ExecuteAnonymousResponse> {
-    match client.execute_anonymous(&request.apex_code).await {
-        Ok(result) => BridgeResult::ok(ExecuteAnonymousResponse {
+    let outcome = if request.capture_log {
+        client
+            .execute_anonymous_with_log(&request.apex_code)
+            .await
+            .map(|run| (run.result, run.log))
+    } else {
+        client
+            .execute_anonymous(&request.apex_code)
+            .await
+            .map(|result| (result, None))
+    };
+    match outcome {
+        Ok((result, debug_log)) => BridgeResult::ok(ExecuteAnonymousResponse {
             compiled: result.compiled,
             success: result.success,
             compile_problem: result.compile_problem,
@@ -36,6 +79,7 @@ pub(crate) async fn handle_tooling_execute_anonymous(
             exception_stack_trace: result.exception_stack_trace,
             line: result.line,
             column: result.column,
+            debug_log,
         }),
         Err(e) => {
             let (code, message) = sanitize_tooling_error(&e);
@@ -44,6 +88,32 @@ pub(crate) async fn handle_tooling_execute_anonymous(
     }
 }
 
+/// Execute a Tooling API composite request.
+///
+/// Subrequest URLs address Tooling resources, e.g.
+/// `/services/data/v62.0/tooling/sobjects/MetadataContainer`, and can refer
+/// to earlier results with `@{referenceId.id}`.
+pub(crate) async fn handle_tooling_composite(
+    client: &ToolingClient,
+    request: CompositeRequest,
+) -> BridgeResult<CompositeResponse> {
+    if let Some(err) =
+        super::composite::reject_auth_headers(request.subrequests.iter().map(|s| &s.http_headers))
+    {
+        return err;
+    }
+    match client
+        .composite(&super::composite::composite_request(request))
+        .await
+    {
+        Ok(result) => BridgeResult::ok(super::composite::composite_response(result)),
+        Err(e) => {
+            let (code, message) = sanitize_tooling_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
 /// Get a Tooling API record.
 pub(crate) async fn handle_tooling_get(
     client: &ToolingClient,
@@ -79,6 +149,58 @@ pub(crate) async fn handle_tooling_create(
     }
 }
 
+/// Update fields on a Tooling API record.
+pub(crate) async fn handle_tooling_update(
+    client: &ToolingClient,
+    request: ToolingUpdateRequest,
+) -> BridgeResult<()> {
+    match client
+        .update(&request.sobject, &request.id, &request.record)
+        .await
+    {
+        Ok(()) => BridgeResult::ok(()),
+        Err(e) => {
+            let (code, message) = sanitize_tooling_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Upsert a Tooling API record by an external ID field.
+pub(crate) async fn handle_tooling_upsert(
+    client: &ToolingClient,
+    request: ToolingUpsertRequest,
+) -> BridgeResult<UpsertResponse> {
+    match client
+        .upsert(
+            &request.sobject,
+            &request.external_id_field,
+            &request.external_id_value,
+            &request.record,
+        )
+        .await
+    {
+        Ok(result) => BridgeResult::ok(UpsertResponse {
+            id: result.id,
+            success: result.success,
+            created: result.created,
+            errors: result
+                .errors
+                .into_iter()
+                .map(|e| SalesforceApiError {
+                    status_code: e.status_code,
+                    message: e.message,
+                    fields: e.fields,
+                })
+                .collect(),
+        }),
+        Err(e) => {
+            let (code, message) = sanitize_tooling_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
 /// Delete a Tooling API record.
 pub(crate) async fn handle_tooling_delete(
     client: &ToolingClient,
@@ -92,3 +214,186 @@ pub(crate) async fn handle_tooling_delete(
         }
     }
 }
+
+/// List async Apex jobs.
+pub(crate) async fn handle_tooling_async_apex_jobs(
+    client: &ToolingClient,
+    request: AsyncApexJobsRequest,
+) -> BridgeResult<Vec<serde_json::Value>> {
+    let statuses = match parse_job_enums::<busbar_sf_tooling::AsyncApexJobStatus>(
+        request.statuses,
+        busbar_sf_tooling::AsyncApexJobStatus::Unknown,
+        "status",
+    ) {
+        Ok(statuses) => statuses,
+        Err(msg) => return BridgeResult::err("INVALID_REQUEST", msg),
+    };
+    let job_types = match parse_job_enums::<busbar_sf_tooling::AsyncApexJobType>(
+        request.job_types,
+        busbar_sf_tooling::AsyncApexJobType::Other,
+        "job type",
+    ) {
+        Ok(job_types) => job_types,
+        Err(msg) => return BridgeResult::err("INVALID_REQUEST", msg),
+    };
+
+    let mut filter = busbar_sf_tooling::AsyncApexJobFilter::new()
+        .statuses(statuses)
+        .job_types(job_types);
+    if let Some(apex_class) = request.apex_class {
+        filter = filter.apex_class(apex_class);
+    }
+    if let Some(minutes) = request.older_than_minutes {
+        filter = filter.older_than(std::time::Duration::from_secs(u64::from(minutes) * 60));
+    }
+    if let Some(limit) = request.limit {
+        filter = filter.limit(limit);
+    }
+
+    match client.async_apex_jobs(&filter).await {
+        Ok(jobs) => match jobs.iter().map(serde_json::to_value).collect() {
+            Ok(jobs) => BridgeResult::ok(jobs),
+            Err(e) => BridgeResult::err("SERIALIZATION_ERROR", e.to_string()),
+        },
+        Err(e) => {
+            let (code, message) = sanitize_tooling_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Abort an async Apex job or scheduled job.
+pub(crate) async fn handle_tooling_abort_job(
+    client: &ToolingClient,
+    request: AbortJobRequest,
+) -> BridgeResult<()> {
+    match client.abort_job(&request.id).await {
+        Ok(()) => BridgeResult::ok(()),
+        Err(e) => {
+            let (code, message) = sanitize_tooling_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Enqueue Apex tests with `runTestsAsynchronous`.
+pub(crate) async fn handle_tooling_run_tests(
+    client: &ToolingClient,
+    request: ToolingRunTestsRequest,
+) -> BridgeResult<ToolingRunTestsResponse> {
+    let joined = |values: Vec<String>| (!values.is_empty()).then(|| values.join(","));
+    let request = busbar_sf_tooling::RunTestsAsyncRequest {
+        class_ids: joined(request.class_ids),
+        class_names: joined(request.class_names),
+        suite_ids: joined(request.suite_ids),
+        suite_names: joined(request.suite_names),
+        max_failed_tests: request.max_failed_tests,
+        test_level: request.test_level,
+        skip_code_coverage: request.skip_code_coverage,
+    };
+    match client.run_tests_async(&request).await {
+        Ok(test_run_id) => BridgeResult::ok(ToolingRunTestsResponse { test_run_id }),
+        Err(e) => {
+            let (code, message) = sanitize_tooling_error(&e);
+            BridgeResult::err(code, message)
+        }
+    }
+}
+
+/// Get the class statuses and method results of an asynchronous test run.
+pub(crate) async fn handle_tooling_test_run_summary(
+    client: &ToolingClient,
+    request: TestRunSummaryRequest,
+) -> BridgeResult<TestRunSummary> {
+    let summary = match client.test_run_summary(&request.test_run_id).await {
+        Ok(summary) => summary,
+        Err(e) => {
+            let (code, message) = sanitize_tooling_error(&e);
+            return BridgeResult::err(code, message);
+        }
+    };
+    BridgeResult::ok(TestRunSummary {
+        test_run_id: summary.test_run_id.clone(),
+        complete: summary.is_complete(),
+        success: summary.is_success(),
+        passed: summary.passed() as u32,
+        failed: summary.failed() as u32,
+        classes: summary
+            .queue_items
+            .iter()
+            .map(|item| TestClassStatus {
+                class_name: item.apex_class.as_ref().and_then(|c| c.name.clone()),
+                status: item.status.as_str().to_string(),
+                extended_status: item.extended_status.clone(),
+            })
+            .collect(),
+        results: summary
+            .results
+            .iter()
+            .map(|result| TestMethodResult {
+                class_name: result.class_name().map(str::to_string),
+                method_name: result.method_name.clone(),
+                outcome: result.outcome.as_str().to_string(),
+                run_time_ms: result.run_time,
+                message: result.message.clone(),
+                stack_trace: result.stack_trace.clone(),
+            })
+            .collect(),
+    })
+}
+
+/// Parse picklist values into a known enum, rejecting values that would
+/// fall through to its catch-all variant.
+fn parse_job_enums<T>(values: Vec<String>, catch_all: T, what: &str) -> Result<Vec<T>, String>
+where
+    T: serde::de::DeserializeOwned + PartialEq,
+{
+    values
+        .into_iter()
+        .map(
+            |value| match serde_json::from_value::<T>(serde_json::Value::String(value.clone())) {
+                Ok(parsed) if parsed != catch_all => Ok(parsed),
+                _ => Err(format!("unknown async Apex job {what}: {value}")),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_bound_only_tooling_query() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 0,
+                "done": true,
+                "records": []
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = ToolingClient::new(server.uri(), "token").unwrap();
+
+        let literal = ToolingQueryRequest {
+            soql: "SELECT Id FROM ApexClass WHERE Name = 'Invoices'".to_string(),
+        };
+        let BridgeResult::Err(e) = handle_tooling_query(&client, true, literal).await else {
+            panic!("a string literal should be refused");
+        };
+        assert_eq!(e.code, "INLINE_LITERAL");
+
+        let plain = ToolingQueryRequest {
+            soql: "SELECT Id FROM ApexClass WHERE Status = null".to_string(),
+        };
+        assert!(matches!(
+            handle_tooling_query(&client, true, plain).await,
+            BridgeResult::Ok(_)
+        ));
+    }
+}
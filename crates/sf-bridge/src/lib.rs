@@ -40,10 +40,22 @@
 //! - **Credential isolation**: Access tokens live only in the host process.
 //!   They never appear in WASM linear memory.
 //! - **Sandboxed guests**: WASM modules cannot make raw HTTP calls, read
-//!   environment variables, or access the filesystem.
+//!   environment variables, or access the filesystem. WASI can be disabled,
+//!   or its clocks denied, with [`WasiPolicy`].
 //! - **Input validation**: All inputs from the guest are validated using
 //!   sf-client's security utilities (SOQL injection prevention, etc.)
 //!   before being forwarded to Salesforce.
+//! - **Third-party secrets**: API keys for other systems are registered
+//!   with a [`SecretStore`] and referenced by name in `sf_http_request`.
+//!   The host substitutes them, only for the hosts each secret is bound to.
+//! - **Signed plugins**: [`SfBridge::require_signature`] refuses modules
+//!   that are not signed with a trusted Ed25519 key.
+//! - **Outbound HTTP allow-list**: `sf_http_request` may only reach hosts
+//!   bound to a secret or allowed by the bridge's [`HttpPolicy`], which also
+//!   caps request and response sizes and duration. Every call is logged at
+//!   the `busbar_sf_bridge::audit` tracing target.
+//! - **Usage review**: [`BridgeRegistry::usage_report`] lists the objects
+//!   each registered plugin has touched and the policies it runs under.
 //!
 //! ## Concurrency
 //!
@@ -75,9 +87,22 @@
 //! }
 //! ```
 
+mod access;
+mod breaker;
+mod discovery;
 mod error;
 mod host_functions;
+mod localization;
+mod operations;
+mod policy;
 mod registration;
+mod registry;
+mod secrets;
+mod signing;
+#[cfg(feature = "rest")]
+mod spill;
+mod trace;
+mod usage;
 
 // `busbar` feature (HostCapability integration, crates/sf-bridge/src/capability.rs)
 // is temporarily disabled — see Cargo.toml. Re-enable by uncommenting this
@@ -85,8 +110,18 @@ mod registration;
 // #[cfg(feature = "busbar")]
 // mod capability;
 
+pub use access::{ObjectAccess, PluginUsage, UsageReport};
+pub use breaker::{ApiFamily, CircuitBreakerPolicy, CircuitState};
+pub use busbar_sf_wasm_types::{HostCallTrace, InvocationContext};
 pub use error::{Error, Result};
+pub use localization::ErrorCatalog;
+pub use policy::{HttpPolicy, TimeoutPolicy, TracePolicy, WasiPolicy};
+pub use registry::BridgeRegistry;
+pub use secrets::{Secret, SecretStore};
+pub use signing::{sign_plugin, sign_plugin_detached, SIGNATURE_SECTION};
+pub use usage::InvocationUsage;
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 #[cfg(feature = "bulk")]
@@ -98,8 +133,13 @@ use busbar_sf_rest::SalesforceRestClient;
 #[cfg(feature = "tooling")]
 use busbar_sf_tooling::ToolingClient;
 
+use access::AccessLog;
+use breaker::CircuitBreakers;
+use busbar_sf_wasm_types::BridgeResult;
 use extism::{Manifest, Plugin, PluginBuilder, UserData, Wasm};
+use trace::TraceRecorder;
 use tracing::instrument;
+use usage::UsageCounters;
 
 /// Shared state passed to all host functions via `UserData<BridgeState>`.
 ///
@@ -108,7 +148,7 @@ use tracing::instrument;
 ///
 /// The Mutex serializes access per-plugin instance. The tokio handle
 /// allows bridging from sync host function callbacks to async Salesforce
-/// API calls via `state.handle.block_on(...)`.
+/// API calls via `state.block_on(name, ...)`.
 pub(crate) struct BridgeState {
     #[cfg(feature = "rest")]
     pub(crate) rest_client: SalesforceRestClient,
@@ -119,13 +159,87 @@ pub(crate) struct BridgeState {
     pub(crate) instance_url: Arc<str>,
     pub(crate) access_token: Arc<str>,
     pub(crate) handle: tokio::runtime::Handle,
+    pub(crate) usage: Arc<UsageCounters>,
+    pub(crate) http_client: reqwest::Client,
+    pub(crate) http_policy: Arc<HttpPolicy>,
+    pub(crate) secrets: Arc<SecretStore>,
+    pub(crate) timeouts: Arc<TimeoutPolicy>,
+    pub(crate) breakers: Arc<CircuitBreakers>,
+    pub(crate) context: InvocationContext,
+    /// The objects every invocation of this plugin has touched.
+    pub(crate) access: Arc<AccessLog>,
+    /// Where host calls are recorded, when the invocation is traced.
+    pub(crate) trace: Option<Arc<TraceRecorder>>,
+    /// Host functions registered on the plugin, filled in by
+    /// [`create_plugin`].
+    pub(crate) host_functions: Vec<&'static str>,
+    pub(crate) wasi: bool,
+    pub(crate) operations: Arc<operations::OperationRegistry>,
+    pub(crate) error_catalog: Arc<ErrorCatalog>,
+    /// Locale error messages are translated into, if any.
+    pub(crate) locale: Option<String>,
+    #[cfg(feature = "rest")]
+    pub(crate) apex_rest_paths: Arc<Vec<String>>,
+    #[cfg(feature = "rest")]
+    pub(crate) bound_queries_only: bool,
+    #[cfg(feature = "rest")]
+    pub(crate) query_spill: spill::QuerySpill,
 }
 
 impl BridgeState {
+    /// Run a host function's future to completion on the bridge runtime,
+    /// giving up with a `TIMEOUT` error once the [`TimeoutPolicy`] limit
+    /// for `function` passes.
+    ///
+    /// Calls are refused with `SERVICE_UNAVAILABLE` while the circuit
+    /// breaker for the function's API family is open, and every outcome is
+    /// reported back to that breaker.
+    pub(crate) fn block_on<T>(
+        &self,
+        function: &str,
+        future: impl std::future::Future<Output = BridgeResult<T>>,
+    ) -> BridgeResult<T> {
+        let admission = match ApiFamily::of(function) {
+            Some(family) => match self.breakers.admit(family) {
+                Some(admission) => Some((family, admission)),
+                None => {
+                    return BridgeResult::err(
+                        "SERVICE_UNAVAILABLE",
+                        format!("{family:?} API calls are suspended after repeated failures"),
+                    );
+                }
+            },
+            None => None,
+        };
+
+        let limit = self.timeouts.timeout_for(function);
+        let result = match self.handle.block_on(tokio::time::timeout(limit, future)) {
+            Ok(result) => result,
+            Err(_) => {
+                tracing::warn!(function, ?limit, "Host function timed out");
+                BridgeResult::err(
+                    "TIMEOUT",
+                    format!("{function} did not complete within {limit:?}"),
+                )
+            }
+        };
+
+        if let Some((family, admission)) = admission {
+            let outage = matches!(&result, BridgeResult::Err(e) if breaker::is_outage(&e.code));
+            self.breakers.record(family, admission, outage);
+        }
+        result
+    }
+
     /// Construct a fresh MetadataClient. MetadataClient is not Clone,
     /// so we build one on-demand from stored credentials.
+    ///
+    /// Every metadata host function builds exactly one client and issues
+    /// one SOAP request with it, so this is also where those requests are
+    /// counted towards the invocation's usage.
     #[cfg(feature = "metadata")]
     pub(crate) fn metadata_client(&self) -> MetadataClient {
+        self.usage.record_soap_call();
         MetadataClient::from_parts(&*self.instance_url, &*self.access_token)
     }
 }
@@ -137,12 +251,25 @@ impl BridgeState {
 /// across tokio tasks.
 pub struct SfBridge {
     wasm_bytes: Arc<Vec<u8>>,
+    wasi_policy: WasiPolicy,
+    http_policy: Arc<HttpPolicy>,
+    secrets: Arc<SecretStore>,
+    timeouts: Arc<TimeoutPolicy>,
+    trace_policy: Arc<TracePolicy>,
+    breakers: Arc<CircuitBreakers>,
+    access: Arc<AccessLog>,
+    operations: Arc<operations::OperationRegistry>,
+    error_catalog: Arc<ErrorCatalog>,
+    locale: Option<String>,
+    http_client: reqwest::Client,
+    #[cfg(feature = "rest")]
+    apex_rest_paths: Arc<Vec<String>>,
+    #[cfg(feature = "rest")]
+    bound_queries_only: bool,
+    #[cfg(feature = "rest")]
+    query_memory_budget: Option<usize>,
     #[cfg(feature = "rest")]
     pub(crate) rest_client: SalesforceRestClient,
-    #[cfg(feature = "bulk")]
-    pub(crate) bulk_client: BulkApiClient,
-    #[cfg(feature = "tooling")]
-    pub(crate) tooling_client: ToolingClient,
     pub(crate) instance_url: Arc<str>,
     pub(crate) access_token: Arc<str>,
     pub(crate) handle: tokio::runtime::Handle,
@@ -174,25 +301,231 @@ impl SfBridge {
         let instance_url: Arc<str> = inner.instance_url().to_string().into();
         let access_token: Arc<str> = inner.access_token().to_string().into();
 
-        #[cfg(feature = "bulk")]
-        let bulk_client = BulkApiClient::from_client(inner.clone());
-        #[cfg(feature = "tooling")]
-        let tooling_client = ToolingClient::from_client(inner.clone());
+        let http_client = outbound_http_client(reqwest::Client::builder())?;
 
         Ok(Self {
             wasm_bytes: Arc::new(wasm_bytes),
+            wasi_policy: WasiPolicy::default(),
+            http_policy: Arc::new(HttpPolicy::default()),
+            secrets: Arc::new(SecretStore::default()),
+            timeouts: Arc::new(TimeoutPolicy::default()),
+            trace_policy: Arc::new(TracePolicy::default()),
+            breakers: Arc::new(CircuitBreakers::new(CircuitBreakerPolicy::default())),
+            access: Arc::default(),
+            operations: Arc::default(),
+            error_catalog: Arc::default(),
+            locale: None,
+            http_client,
+            #[cfg(feature = "rest")]
+            apex_rest_paths: Arc::new(Vec::new()),
+            #[cfg(feature = "rest")]
+            bound_queries_only: false,
+            #[cfg(feature = "rest")]
+            query_memory_budget: None,
             #[cfg(feature = "rest")]
             rest_client,
-            #[cfg(feature = "bulk")]
-            bulk_client,
-            #[cfg(feature = "tooling")]
-            tooling_client,
             instance_url,
             access_token,
             handle,
         })
     }
 
+    /// Set the WASI capabilities given to guest plugins.
+    ///
+    /// Applies to every subsequent [`SfBridge::call`].
+    pub fn with_wasi_policy(mut self, policy: WasiPolicy) -> Self {
+        self.wasi_policy = policy;
+        self
+    }
+
+    /// Require the module to carry an embedded signature from one of
+    /// `public_keys` (raw 32-byte Ed25519 keys).
+    ///
+    /// Modules are signed with [`sign_plugin`]. Returns
+    /// [`Error::Signature`] if the signature is missing or does not verify.
+    pub fn require_signature<I>(self, public_keys: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = [u8; 32]>,
+    {
+        let keys = signing::parse_keys(public_keys)?;
+        signing::verify_embedded(&self.wasm_bytes, &keys)?;
+        Ok(self)
+    }
+
+    /// Require a detached `signature` over the module bytes from one of
+    /// `public_keys` (raw 32-byte Ed25519 keys).
+    ///
+    /// Detached signatures are made with [`sign_plugin_detached`].
+    pub fn require_detached_signature<I>(self, signature: &[u8], public_keys: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = [u8; 32]>,
+    {
+        let keys = signing::parse_keys(public_keys)?;
+        signing::verify_detached(&self.wasm_bytes, signature, &keys)?;
+        Ok(self)
+    }
+
+    /// Set the hosts and limits for guest calls to `sf_http_request`.
+    ///
+    /// Applies to every subsequent [`SfBridge::call`].
+    pub fn with_http_policy(mut self, policy: HttpPolicy) -> Self {
+        self.http_policy = Arc::new(policy);
+        self
+    }
+
+    /// Tune the connection pool behind guest `sf_http_request` calls.
+    ///
+    /// Uses the pool, HTTP/2, keepalive, timeout, proxy, and TLS settings
+    /// from `config`.
+    /// Salesforce API calls go through the REST client the bridge was
+    /// created with, so configure that client with the same
+    /// [`ClientConfig`](busbar_sf_client::ClientConfig) to tune both.
+    #[cfg(feature = "rest")]
+    pub fn with_client_config(mut self, config: &busbar_sf_client::ClientConfig) -> Result<Self> {
+        let builder = config
+            .http_client_builder()
+            .map_err(|e| Error::Config(e.to_string()))?;
+        self.http_client = outbound_http_client(builder)?;
+        Ok(self)
+    }
+
+    /// Set the secrets guests may reference in `sf_http_request`.
+    ///
+    /// Hosts that any of these secrets is bound to are allowed for outbound
+    /// requests in addition to those in the [`HttpPolicy`].
+    pub fn with_secrets(mut self, secrets: SecretStore) -> Self {
+        self.secrets = Arc::new(secrets);
+        self
+    }
+
+    /// Set how long each host function may run before the guest gets a
+    /// `TIMEOUT` error.
+    ///
+    /// Applies to every subsequent [`SfBridge::call`].
+    pub fn with_timeout_policy(mut self, policy: TimeoutPolicy) -> Self {
+        self.timeouts = Arc::new(policy);
+        self
+    }
+
+    /// Set which fields [`call_with_trace`](Self::call_with_trace) redacts
+    /// in addition to secrets.
+    pub fn with_trace_policy(mut self, policy: TracePolicy) -> Self {
+        self.trace_policy = Arc::new(policy);
+        self
+    }
+
+    /// Set when calls to an unavailable Salesforce API start failing fast.
+    ///
+    /// Replaces the bridge's breakers, so any open circuit is closed again.
+    pub fn with_circuit_breaker(mut self, policy: CircuitBreakerPolicy) -> Self {
+        self.breakers = Arc::new(CircuitBreakers::new(policy));
+        self
+    }
+
+    /// Current state of the circuit breaker for `family`.
+    pub fn circuit_state(&self, family: ApiFamily) -> CircuitState {
+        self.breakers.state(family)
+    }
+
+    /// Allow guests to call these Apex REST endpoints via `sf_apex_rest`.
+    ///
+    /// Paths are relative to `/services/apexrest/`. An entry matches that
+    /// path exactly, or every path below it when it ends in `/*`
+    /// (`Orders/*`). With no entries, every Apex REST call is refused.
+    #[cfg(feature = "rest")]
+    pub fn with_apex_rest_paths<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.apex_rest_paths = Arc::new(paths.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Serve guest `sf_describe_sobject` calls from `cache`.
+    ///
+    /// Describes are shared across invocations (and with any other client
+    /// for the same user using the same cache), so repeated describes from
+    /// guests don't cost API calls while the cached copy is fresh.
+    #[cfg(feature = "rest")]
+    pub fn with_describe_cache(mut self, cache: busbar_sf_rest::DescribeCache) -> Self {
+        self.rest_client = self.rest_client.with_describe_cache(cache);
+        self
+    }
+
+    /// Refuse guest SOQL queries that contain string literals.
+    ///
+    /// Guests must then pass every string value as a bind on the
+    /// `QueryRequest` or bulk query job, which the host escapes, so values
+    /// taken from user input can't change the query's structure. Tooling
+    /// queries, which take no binds, are refused if they contain a string
+    /// literal. Raw SOSL through `sf_search` is refused outright; guests
+    /// search with `sf_parameterized_search` instead.
+    #[cfg(feature = "rest")]
+    pub fn require_bound_queries(mut self) -> Self {
+        self.bound_queries_only = true;
+        self
+    }
+
+    /// Cap the query result bytes returned to each invocation.
+    ///
+    /// Once `sf_query` and `sf_query_more` have returned `bytes` of records
+    /// to a guest, further records are held on the host and the response
+    /// carries a cursor. The guest reads them in bounded chunks with
+    /// `sf_query_cursor_next`, so a large result never has to fit in its
+    /// linear memory at once. Unlimited by default.
+    #[cfg(feature = "rest")]
+    pub fn with_query_memory_budget(mut self, bytes: usize) -> Self {
+        self.query_memory_budget = Some(bytes);
+        self
+    }
+
+    /// Translate the messages of errors returned to guests.
+    ///
+    /// Messages are translated into the invocation's
+    /// [`InvocationContext::locale`], or the bridge's
+    /// [`with_locale`](Self::with_locale) default when the context has
+    /// none. Error codes are never changed.
+    pub fn with_error_catalog(mut self, catalog: ErrorCatalog) -> Self {
+        self.error_catalog = Arc::new(catalog);
+        self
+    }
+
+    /// Set the locale for invocations whose context doesn't name one.
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// The catalog used to translate error messages, for hosts that also
+    /// want to translate errors a guest reports in its own output.
+    pub fn error_catalog(&self) -> &ErrorCatalog {
+        &self.error_catalog
+    }
+
+    /// What this plugin has touched so far and may reach, for
+    /// [`BridgeRegistry::usage_report`].
+    pub(crate) fn plugin_usage(&self, name: &str) -> PluginUsage {
+        let (objects, other_calls) = self.access.snapshot();
+        let mut http_hosts = self.http_policy.hosts.clone();
+        http_hosts.extend(self.secrets.hosts());
+        http_hosts.sort();
+        http_hosts.dedup();
+        #[cfg(feature = "rest")]
+        let apex_rest_paths = self.apex_rest_paths.to_vec();
+        #[cfg(not(feature = "rest"))]
+        let apex_rest_paths = Vec::new();
+
+        PluginUsage {
+            name: name.to_string(),
+            objects,
+            other_calls,
+            http_hosts,
+            secrets: self.secrets.names(),
+            apex_rest_paths,
+        }
+    }
+
     /// Call an exported function in the WASM guest.
     ///
     /// Each call creates a fresh plugin instance (cheap -- the module is
@@ -200,27 +533,150 @@ impl SfBridge {
     /// with the bridge's Salesforce clients.
     ///
     /// Safe to call concurrently from multiple tokio tasks.
-    #[instrument(skip(self, input), fields(function = %function))]
     pub async fn call(
         &self,
         function: &str,
         input: impl AsRef<[u8]> + Send + 'static,
     ) -> Result<Vec<u8>> {
-        let wasm_bytes = self.wasm_bytes.clone();
+        self.call_with_usage(function, input).await.0
+    }
+
+    /// Call an exported function and report what the invocation consumed.
+    ///
+    /// Behaves like [`SfBridge::call`], but also returns the Salesforce API
+    /// calls, bulk jobs, and bytes used by this invocation alone. The usage
+    /// is returned even when the call fails, so failed runs can still be
+    /// charged back.
+    pub async fn call_with_usage(
+        &self,
+        function: &str,
+        input: impl AsRef<[u8]> + Send + 'static,
+    ) -> (Result<Vec<u8>>, InvocationUsage) {
+        let context = InvocationContext::new(next_invocation_id());
+        self.call_with_context(function, input, context).await
+    }
+
+    /// Call an exported function with business context for the guest.
+    ///
+    /// Behaves like [`SfBridge::call_with_usage`]. The guest reads `context`
+    /// through `sf_invocation_context`, which is how hosts tell plugins which
+    /// org, user, and triggering data an invocation is for. Calls without a
+    /// context get one holding only a generated invocation ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use busbar_sf_bridge::InvocationContext;
+    ///
+    /// let context = InvocationContext::new(request_id)
+    ///     .with_org_id(org_id)
+    ///     .with_user_id(user_id)
+    ///     .with_payload(serde_json::json!({"opportunityId": opp_id}));
+    /// let (result, usage) = bridge.call_with_context("run", input, context).await;
+    /// ```
+    pub async fn call_with_context(
+        &self,
+        function: &str,
+        input: impl AsRef<[u8]> + Send + 'static,
+        context: InvocationContext,
+    ) -> (Result<Vec<u8>>, InvocationUsage) {
+        self.invoke(function, input, context, None).await
+    }
+
+    /// Call an exported function and record every host call it makes.
+    ///
+    /// Behaves like [`SfBridge::call`], and also returns the requests the
+    /// guest sent to host functions and the responses it got back, in
+    /// order. Secret values and the fields named in the bridge's
+    /// [`TracePolicy`] are redacted. The trace is returned even when the
+    /// call fails, which is usually when it is wanted: save it with
+    /// [`HostCallTrace::to_json`] and replay it with
+    /// [`TraceReplay`](busbar_sf_wasm_types::TraceReplay) to debug the guest
+    /// without access to the org.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let (result, trace) = bridge.call_with_trace("run", input).await;
+    /// if result.is_err() {
+    ///     std::fs::write("run-trace.json", trace.to_json())?;
+    /// }
+    /// ```
+    pub async fn call_with_trace(
+        &self,
+        function: &str,
+        input: impl AsRef<[u8]> + Send + 'static,
+    ) -> (Result<Vec<u8>>, HostCallTrace) {
+        let context = InvocationContext::new(next_invocation_id());
+        let invocation_id = context.invocation_id.clone();
+        let recorder = Arc::new(TraceRecorder::new(
+            Arc::clone(&self.trace_policy),
+            Arc::clone(&self.secrets),
+        ));
+        let (result, _) = self
+            .invoke(function, input, context, Some(Arc::clone(&recorder)))
+            .await;
+        (result, recorder.finish(function, &invocation_id))
+    }
+
+    #[instrument(
+        skip(self, input, context, trace),
+        fields(function = %function, invocation_id = %context.invocation_id)
+    )]
+    async fn invoke(
+        &self,
+        function: &str,
+        input: impl AsRef<[u8]> + Send + 'static,
+        context: InvocationContext,
+        trace: Option<Arc<TraceRecorder>>,
+    ) -> (Result<Vec<u8>>, InvocationUsage) {
+        let usage = Arc::new(UsageCounters::default());
+
+        // Give this invocation its own metered clients. They share the
+        // bridge's connection pool but record into `usage` only.
         #[cfg(feature = "rest")]
-        let rest_client = self.rest_client.clone();
+        let metered = self
+            .rest_client
+            .inner()
+            .clone()
+            .with_usage_tracker(usage.http_tracker());
         #[cfg(feature = "bulk")]
-        let bulk_client = self.bulk_client.clone();
+        let bulk_client = BulkApiClient::from_client(metered.clone());
         #[cfg(feature = "tooling")]
-        let tooling_client = self.tooling_client.clone();
+        let tooling_client = ToolingClient::from_client(metered.clone());
+        #[cfg(feature = "rest")]
+        let mut rest_client = SalesforceRestClient::from_client(metered);
+        #[cfg(feature = "rest")]
+        if let Some(cache) = self.rest_client.describe_cache() {
+            rest_client = rest_client.with_describe_cache(cache.clone());
+        }
+
+        let wasm_bytes = self.wasm_bytes.clone();
+        let wasi_policy = self.wasi_policy.clone();
         let instance_url = Arc::clone(&self.instance_url);
         let access_token = Arc::clone(&self.access_token);
         let handle = self.handle.clone();
         let function = function.to_string();
+        let counters = Arc::clone(&usage);
+        let http_client = self.http_client.clone();
+        let http_policy = Arc::clone(&self.http_policy);
+        let secrets = Arc::clone(&self.secrets);
+        let timeouts = Arc::clone(&self.timeouts);
+        let breakers = Arc::clone(&self.breakers);
+        let access = Arc::clone(&self.access);
+        let operations = Arc::clone(&self.operations);
+        let error_catalog = Arc::clone(&self.error_catalog);
+        let locale = context.locale.clone().or_else(|| self.locale.clone());
+        #[cfg(feature = "rest")]
+        let apex_rest_paths = Arc::clone(&self.apex_rest_paths);
+        #[cfg(feature = "rest")]
+        let bound_queries_only = self.bound_queries_only;
+        #[cfg(feature = "rest")]
+        let query_memory_budget = self.query_memory_budget;
 
         // Run the plugin on a blocking thread so host functions can
         // safely use Handle::block_on() for async Salesforce operations.
-        tokio::task::spawn_blocking(move || {
+        let result = tokio::task::spawn_blocking(move || {
             let state = BridgeState {
                 #[cfg(feature = "rest")]
                 rest_client,
@@ -231,26 +687,90 @@ impl SfBridge {
                 instance_url,
                 access_token,
                 handle,
+                usage: counters,
+                http_client,
+                http_policy,
+                secrets,
+                timeouts,
+                breakers,
+                context,
+                access,
+                trace,
+                host_functions: Vec::new(),
+                wasi: wasi_policy.enabled,
+                operations,
+                error_catalog,
+                locale,
+                #[cfg(feature = "rest")]
+                apex_rest_paths,
+                #[cfg(feature = "rest")]
+                bound_queries_only,
+                #[cfg(feature = "rest")]
+                query_spill: spill::QuerySpill::new(query_memory_budget),
             };
-            let mut plugin = create_plugin(&wasm_bytes, state)?;
+            let mut plugin = create_plugin(&wasm_bytes, &wasi_policy, state)?;
             let result = plugin.call::<&[u8], &[u8]>(&function, input.as_ref())?;
             Ok(result.to_vec())
         })
-        .await?
+        .await
+        .map_err(Error::from)
+        .and_then(|r| r);
+
+        let usage = usage.report();
+        tracing::debug!(
+            api_calls = usage.api_calls,
+            bulk_jobs = usage.bulk_jobs,
+            bytes_sent = usage.bytes_sent,
+            bytes_received = usage.bytes_received,
+            "Guest invocation finished"
+        );
+        (result, usage)
     }
 }
 
+/// Build the client for guest outbound HTTP.
+///
+/// Redirects are not followed: a redirect could carry substituted secrets
+/// to a host they are not bound to.
+#[cfg(feature = "rest")]
+fn outbound_http_client(builder: reqwest::ClientBuilder) -> Result<reqwest::Client> {
+    builder
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| Error::Config(format!("failed to build HTTP client: {e}")))
+}
+
+/// A process-unique invocation ID for calls made without a context.
+fn next_invocation_id() -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let started = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    format!("{started:x}-{:x}", NEXT.fetch_add(1, Ordering::Relaxed))
+}
+
 /// Create an Extism plugin with all enabled Salesforce host functions registered.
-fn create_plugin(wasm_bytes: &[u8], state: BridgeState) -> Result<Plugin> {
-    let manifest = Manifest::new([Wasm::data(wasm_bytes.to_vec())]);
+fn create_plugin(
+    wasm_bytes: &[u8],
+    wasi_policy: &WasiPolicy,
+    state: BridgeState,
+) -> Result<Plugin> {
+    let mut manifest = Manifest::new([Wasm::data(wasm_bytes.to_vec())]);
+    if wasi_policy.enabled {
+        for (host_dir, guest_dir) in &wasi_policy.dirs {
+            manifest = manifest.with_allowed_path(host_dir.clone(), guest_dir);
+        }
+    }
     let user_data = UserData::new(state);
 
-    let builder = PluginBuilder::new(manifest).with_wasi(true);
+    let builder = wasi_policy.restrict(PluginBuilder::new(manifest).with_wasi(wasi_policy.enabled));
 
     // Register all enabled host functions based on feature flags
-    let plugin = registration::register_all(builder, &user_data).build()?;
+    let (builder, host_functions) =
+        registration::register_all(registration::Registrar::new(builder), &user_data).finish();
+    user_data.get()?.lock().unwrap().host_functions = host_functions;
 
-    Ok(plugin)
+    Ok(builder.build()?)
 }
 
 /// Helper for host functions that take a request argument.
@@ -267,12 +787,16 @@ where
 {
     let state_arc = user_data.get()?;
     let state = state_arc.lock().unwrap();
+    state.usage.record_host_call();
 
     let input_bytes: Vec<u8> = plugin.memory_get_val(&inputs[0])?;
     let request: Req = rmp_serde::from_slice(&input_bytes)
         .map_err(|e| extism::Error::msg(format!("deserialize request: {e}")))?;
 
-    let result = handler(&state, request);
+    let mut result = handler(&state, request);
+    state
+        .error_catalog
+        .localize_result(state.locale.as_deref(), &mut result);
 
     let output_bytes = rmp_serde::to_vec_named(&result)
         .map_err(|e| extism::Error::msg(format!("serialize response: {e}")))?;
@@ -294,8 +818,12 @@ where
 {
     let state_arc = user_data.get()?;
     let state = state_arc.lock().unwrap();
+    state.usage.record_host_call();
 
-    let result = handler(&state);
+    let mut result = handler(&state);
+    state
+        .error_catalog
+        .localize_result(state.locale.as_deref(), &mut result);
 
     let output_bytes = rmp_serde::to_vec_named(&result)
         .map_err(|e| extism::Error::msg(format!("serialize response: {e}")))?;
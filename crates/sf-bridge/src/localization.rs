@@ -0,0 +1,160 @@
+//! Translated error messages for end users.
+//!
+//! Error codes returned to guests (`NOT_FOUND`, `TIMEOUT`,
+//! `INVALID_SESSION_ID`, ...) are a stable contract and are never
+//! translated. Their messages are written for developers, in English. An
+//! [`ErrorCatalog`] lets a host that shows those errors to end users swap
+//! the message for one in the user's language, keyed by code.
+
+use std::collections::HashMap;
+
+use busbar_sf_wasm_types::{BridgeError, BridgeResult};
+
+/// Error message templates by locale and error code.
+///
+/// A template may contain `{code}` and `{message}`, which are replaced by
+/// the error code and the original (English) message.
+///
+/// Locales are matched case-insensitively, with `_` and `-` treated alike.
+/// A lookup for `fr-CA` falls back to `fr`; a locale or code with no entry
+/// keeps the original message.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bridge::{ErrorCatalog, InvocationContext, SfBridge};
+///
+/// let catalog = ErrorCatalog::new()
+///     .with_message("fr", "NOT_FOUND", "L'enregistrement est introuvable.")
+///     .with_message("fr", "TIMEOUT", "Salesforce n'a pas répondu à temps ({code}).");
+///
+/// let bridge = SfBridge::new(wasm_bytes, rest_client)?
+///     .with_error_catalog(catalog)
+///     .with_locale("en");
+///
+/// // Per-invocation locale wins over the bridge default.
+/// let context = InvocationContext::new(request_id).with_locale("fr-CA");
+/// let (result, _) = bridge.call_with_context("run", input, context).await;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ErrorCatalog {
+    messages: HashMap<String, HashMap<String, String>>,
+}
+
+impl ErrorCatalog {
+    /// An empty catalog, which leaves every message untouched.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the template for `code` in `locale`.
+    pub fn with_message(
+        mut self,
+        locale: &str,
+        code: impl Into<String>,
+        template: impl Into<String>,
+    ) -> Self {
+        self.messages
+            .entry(normalize(locale))
+            .or_default()
+            .insert(code.into(), template.into());
+        self
+    }
+
+    /// Add templates for several codes in `locale`, e.g. from a JSON
+    /// object of code to template loaded from a translation file.
+    pub fn with_messages<I, C, T>(mut self, locale: &str, messages: I) -> Self
+    where
+        I: IntoIterator<Item = (C, T)>,
+        C: Into<String>,
+        T: Into<String>,
+    {
+        let entry = self.messages.entry(normalize(locale)).or_default();
+        for (code, template) in messages {
+            entry.insert(code.into(), template.into());
+        }
+        self
+    }
+
+    /// Whether the catalog has no translations.
+    pub fn is_empty(&self) -> bool {
+        self.messages.values().all(HashMap::is_empty)
+    }
+
+    /// The translated message for `code` in `locale`, if there is one.
+    pub fn message(&self, locale: &str, code: &str, original: &str) -> Option<String> {
+        let locale = normalize(locale);
+        let template = self
+            .messages
+            .get(&locale)
+            .and_then(|m| m.get(code))
+            .or_else(|| {
+                let (language, _) = locale.split_once('-')?;
+                self.messages.get(language)?.get(code)
+            })?;
+        Some(
+            template
+                .replace("{code}", code)
+                .replace("{message}", original),
+        )
+    }
+
+    /// `error` with its message translated into `locale`. The code and
+    /// fields are kept as they are.
+    pub fn localize(&self, locale: &str, error: &BridgeError) -> BridgeError {
+        let mut localized = error.clone();
+        if let Some(message) = self.message(locale, &error.code, &error.message) {
+            localized.message = message;
+        }
+        localized
+    }
+
+    /// Translate the error in a host function result in place.
+    pub(crate) fn localize_result<T>(&self, locale: Option<&str>, result: &mut BridgeResult<T>) {
+        let (Some(locale), BridgeResult::Err(error)) = (locale, result) else {
+            return;
+        };
+        if let Some(message) = self.message(locale, &error.code, &error.message) {
+            error.message = message;
+        }
+    }
+}
+
+fn normalize(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_fallback_and_placeholders() {
+        let catalog = ErrorCatalog::new()
+            .with_message("fr", "NOT_FOUND", "Introuvable ({code})")
+            .with_messages("fr_CA", [("TIMEOUT", "Délai dépassé : {message}")]);
+
+        let error = BridgeError {
+            code: "NOT_FOUND".to_string(),
+            message: "The requested resource does not exist".to_string(),
+            fields: vec!["Id".to_string()],
+        };
+        let localized = catalog.localize("fr-CA", &error);
+        assert_eq!(localized.code, "NOT_FOUND");
+        assert_eq!(localized.message, "Introuvable (NOT_FOUND)");
+        assert_eq!(localized.fields, error.fields);
+
+        assert_eq!(
+            catalog
+                .message("FR-ca", "TIMEOUT", "took too long")
+                .unwrap(),
+            "Délai dépassé : took too long"
+        );
+        assert!(catalog.message("fr", "TIMEOUT", "took too long").is_none());
+        assert_eq!(catalog.localize("de", &error).message, error.message);
+
+        let mut result: BridgeResult<()> = BridgeResult::err("NOT_FOUND", "missing");
+        catalog.localize_result(Some("fr"), &mut result);
+        assert!(matches!(result, BridgeResult::Err(e) if e.message == "Introuvable (NOT_FOUND)"));
+    }
+}
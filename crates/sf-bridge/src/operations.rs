@@ -0,0 +1,97 @@
+//! Long-running operations started by guests.
+//!
+//! Deploys, retrieves, and bulk jobs outlive the invocation that starts
+//! them. The bridge remembers what kind of operation each returned ID
+//! refers to, so a later invocation can check on it with
+//! `sf_operation_status` given nothing but the ID.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use busbar_sf_wasm_types::OperationKind;
+
+/// How long a handle is remembered after its operation started.
+const RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug)]
+struct Tracked {
+    kind: OperationKind,
+    started: Instant,
+}
+
+/// Operations started through the bridge, shared by all its invocations.
+#[derive(Debug, Default)]
+pub(crate) struct OperationRegistry {
+    operations: Mutex<HashMap<String, Tracked>>,
+}
+
+impl OperationRegistry {
+    /// Remember that `handle` is an operation of `kind`.
+    pub(crate) fn track(&self, handle: &str, kind: OperationKind) {
+        let mut operations = self.operations.lock().unwrap();
+        operations.retain(|_, op| op.started.elapsed() < RETENTION);
+        operations.insert(
+            handle.to_string(),
+            Tracked {
+                kind,
+                started: Instant::now(),
+            },
+        );
+    }
+
+    /// The kind of operation `handle` refers to.
+    ///
+    /// Deploys and retrieves the bridge didn't see start (for example,
+    /// because the host restarted since) are recognised by their Salesforce
+    /// ID prefix. Untracked bulk jobs are not: ingest and query jobs share
+    /// the prefix, so the caller looks them up instead (see
+    /// [`is_bulk_job_id`]).
+    pub(crate) fn kind_of(&self, handle: &str) -> Option<OperationKind> {
+        if let Some(op) = self.operations.lock().unwrap().get(handle) {
+            return Some(op.kind);
+        }
+        match handle.get(..3)? {
+            "0Af" => Some(OperationKind::Deploy),
+            "09S" => Some(OperationKind::Retrieve),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `handle` has the ID prefix of a bulk job, ingest or query.
+#[cfg(feature = "bulk")]
+pub(crate) fn is_bulk_job_id(handle: &str) -> bool {
+    handle.starts_with("750")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracked_and_inferred_kinds() {
+        let registry = OperationRegistry::default();
+        registry.track("custom-handle", OperationKind::Retrieve);
+        registry.track("750xx0000000002AAA", OperationKind::BulkQuery);
+
+        assert_eq!(
+            registry.kind_of("custom-handle"),
+            Some(OperationKind::Retrieve)
+        );
+        assert_eq!(
+            registry.kind_of("0Afxx0000000001AAA"),
+            Some(OperationKind::Deploy)
+        );
+        assert_eq!(
+            registry.kind_of("750xx0000000002AAA"),
+            Some(OperationKind::BulkQuery)
+        );
+        // An untracked bulk job could be either kind.
+        assert_eq!(registry.kind_of("750xx0000000001AAA"), None);
+        #[cfg(feature = "bulk")]
+        assert!(is_bulk_job_id("750xx0000000001AAA"));
+        assert_eq!(registry.kind_of("001xx0000000001AAA"), None);
+        assert_eq!(registry.kind_of("0A"), None);
+    }
+}
@@ -0,0 +1,417 @@
+//! Sandbox policies for guest plugins: WASI, outbound HTTP, host call
+//! timeouts, and what host call traces may contain.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use busbar_sf_wasm_types::host_fn_names;
+use extism::{PluginBuilder, UserData, Val, ValType};
+
+/// Module name of the WASI preview 1 imports.
+const WASI_MODULE: &str = "wasi_snapshot_preview1";
+
+/// WASI `errno` returned for clock reads a [`WasiPolicy`] denies (`ENOTSUP`).
+const WASI_ERRNO_NOTSUP: i32 = 58;
+
+/// Controls which WASI capabilities a guest plugin is given.
+///
+/// The default keeps the sandbox described in the crate docs: WASI is
+/// enabled so guests built for `wasm32-wasip1` can start, no host
+/// directories are preopened, and the clocks are available. Guests built
+/// for `wasm32-unknown-unknown` (the `sf-guest-sdk` default) do not need
+/// WASI at all and can run under [`WasiPolicy::disabled`].
+///
+/// Guests never see the host's environment variables: Extism starts WASI
+/// with an empty environment and has no way to pass selected variables in,
+/// so there is no allow-list. Hand configuration to a guest in its input.
+///
+/// [`WasiPolicy::without_clocks`] makes every WASI clock read fail. There
+/// is no monotonic-only mode: host functions cannot write guest memory, so
+/// the bridge can refuse a clock read but cannot answer one itself.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bridge::{SfBridge, WasiPolicy};
+///
+/// let bridge = SfBridge::new(wasm_bytes, client)?
+///     .with_wasi_policy(WasiPolicy::disabled());
+/// ```
+#[derive(Debug, Clone)]
+pub struct WasiPolicy {
+    pub(crate) enabled: bool,
+    pub(crate) dirs: Vec<(String, PathBuf)>,
+    pub(crate) clocks: bool,
+}
+
+impl Default for WasiPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dirs: Vec::new(),
+            clocks: true,
+        }
+    }
+}
+
+impl WasiPolicy {
+    /// WASI enabled with no filesystem access. This is the default.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// No WASI imports at all. Guests that import WASI functions will fail
+    /// to instantiate.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// Preopen `host_dir` inside the guest at `guest_dir`.
+    ///
+    /// The guest gets read and write access to everything under the
+    /// directory. Has no effect when WASI is disabled.
+    pub fn with_dir(mut self, host_dir: impl Into<String>, guest_dir: impl Into<PathBuf>) -> Self {
+        self.dirs.push((host_dir.into(), guest_dir.into()));
+        self
+    }
+
+    /// Fail every WASI clock read (`clock_time_get` and `clock_res_get`)
+    /// with `ENOTSUP`, so the guest cannot observe wall-clock or monotonic
+    /// time. Has no effect when WASI is disabled.
+    pub fn without_clocks(mut self) -> Self {
+        self.clocks = false;
+        self
+    }
+
+    /// Whether WASI imports are provided to the guest.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether the guest can read the WASI clocks.
+    pub fn allows_clocks(&self) -> bool {
+        self.enabled && self.clocks
+    }
+
+    /// WASI imports replaced with ones that fail, with their parameter
+    /// types. Each returns a single `errno`.
+    pub(crate) fn denied_imports(&self) -> Vec<(&'static str, Vec<ValType>)> {
+        if !self.enabled || self.clocks {
+            return Vec::new();
+        }
+        vec![
+            (
+                "clock_time_get",
+                vec![ValType::I32, ValType::I64, ValType::I32],
+            ),
+            ("clock_res_get", vec![ValType::I32, ValType::I32]),
+        ]
+    }
+
+    /// Shadow the denied WASI imports on `builder`. Extism links host
+    /// functions after WASI, so these replace the WASI implementations.
+    pub(crate) fn restrict<'a>(&self, builder: PluginBuilder<'a>) -> PluginBuilder<'a> {
+        self.denied_imports()
+            .into_iter()
+            .fold(builder, |builder, (name, params)| {
+                builder.with_function_in_namespace(
+                    WASI_MODULE,
+                    name,
+                    params,
+                    [ValType::I32],
+                    UserData::new(()),
+                    |_, _, outputs, _| {
+                        outputs[0] = Val::I32(WASI_ERRNO_NOTSUP);
+                        Ok(())
+                    },
+                )
+            })
+    }
+}
+
+/// Controls where and how much guests may call out through `sf_http_request`.
+///
+/// The default allows no hosts. Hosts bound to a registered
+/// [`Secret`](crate::Secret) are always allowed, since the secret already
+/// names where it may be sent.
+///
+/// Every outbound request, allowed or refused, is logged at the
+/// `busbar_sf_bridge::audit` tracing target with its method, host, path,
+/// status, sizes, and duration. Query strings and headers are not logged.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bridge::{HttpPolicy, SfBridge};
+/// use std::time::Duration;
+///
+/// let bridge = SfBridge::new(wasm_bytes, client)?.with_http_policy(
+///     HttpPolicy::new()
+///         .allow_host("api.example.com")
+///         .allow_host("*.geocoder.example")
+///         .with_timeout(Duration::from_secs(10)),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpPolicy {
+    pub(crate) hosts: Vec<String>,
+    pub(crate) max_request_bytes: usize,
+    pub(crate) max_response_bytes: usize,
+    pub(crate) timeout: Duration,
+}
+
+impl Default for HttpPolicy {
+    fn default() -> Self {
+        Self {
+            hosts: Vec::new(),
+            max_request_bytes: 1024 * 1024,
+            max_response_bytes: 5 * 1024 * 1024,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl HttpPolicy {
+    /// No allowed hosts, 1 MiB requests, 5 MiB responses, 30 second timeout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow requests to `host`.
+    ///
+    /// A leading `*.` matches any subdomain (`*.example.com` matches
+    /// `api.example.com` but not `example.com`). Matching is
+    /// case-insensitive.
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.hosts.push(host.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Largest request body a guest may send.
+    pub fn with_max_request_bytes(mut self, bytes: usize) -> Self {
+        self.max_request_bytes = bytes;
+        self
+    }
+
+    /// Largest response body returned to the guest; larger responses fail.
+    pub fn with_max_response_bytes(mut self, bytes: usize) -> Self {
+        self.max_response_bytes = bytes;
+        self
+    }
+
+    /// Total time allowed for each request, including reading the body.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Whether `host` is on the allow-list.
+    pub fn allows_host(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.hosts
+            .iter()
+            .any(|entry| match entry.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.len() > 1 && sub.ends_with('.')),
+                None => *entry == host,
+            })
+    }
+}
+
+/// Host functions that can legitimately run for minutes: deploys and
+/// retrieves, bulk uploads and result downloads, and anonymous Apex.
+const LONG_RUNNING: &[&str] = &[
+    host_fn_names::METADATA_DEPLOY,
+    host_fn_names::METADATA_RETRIEVE,
+    host_fn_names::BULK_UPLOAD_JOB_DATA,
+    host_fn_names::BULK_GET_JOB_RESULTS,
+    host_fn_names::BULK_GET_QUERY_RESULTS,
+    host_fn_names::TOOLING_EXECUTE_ANONYMOUS,
+];
+
+/// How long each host function may run before the guest gets a `TIMEOUT`
+/// error.
+///
+/// When the limit passes, the pending Salesforce call is dropped and the
+/// blocking thread returns to the guest instead of waiting on the org.
+/// Functions are either regular calls, which get the default limit, or
+/// known long-running ones (metadata deploy and retrieve, bulk uploads and
+/// result downloads, anonymous Apex). Individual functions can be given
+/// their own limit by name.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bridge::{SfBridge, TimeoutPolicy};
+/// use busbar_sf_wasm_types::host_fn_names;
+/// use std::time::Duration;
+///
+/// let bridge = SfBridge::new(wasm_bytes, client)?.with_timeout_policy(
+///     TimeoutPolicy::new()
+///         .with_default(Duration::from_secs(20))
+///         .with_function(host_fn_names::QUERY, Duration::from_secs(60)),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeoutPolicy {
+    pub(crate) default: Duration,
+    pub(crate) long_running: Duration,
+    pub(crate) functions: HashMap<String, Duration>,
+}
+
+impl Default for TimeoutPolicy {
+    fn default() -> Self {
+        Self {
+            default: Duration::from_secs(120),
+            long_running: Duration::from_secs(30 * 60),
+            functions: HashMap::new(),
+        }
+    }
+}
+
+impl TimeoutPolicy {
+    /// Two minutes per call, thirty minutes for long-running functions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit for every function without a more specific one.
+    pub fn with_default(mut self, timeout: Duration) -> Self {
+        self.default = timeout;
+        self
+    }
+
+    /// Limit for the long-running functions.
+    pub fn with_long_running(mut self, timeout: Duration) -> Self {
+        self.long_running = timeout;
+        self
+    }
+
+    /// Limit for one host function, by its
+    /// [`host_fn_names`](busbar_sf_wasm_types::host_fn_names) name.
+    pub fn with_function(mut self, function: impl Into<String>, timeout: Duration) -> Self {
+        self.functions.insert(function.into(), timeout);
+        self
+    }
+
+    /// The limit that applies to `function`.
+    pub fn timeout_for(&self, function: &str) -> Duration {
+        match self.functions.get(function) {
+            Some(timeout) => *timeout,
+            None if LONG_RUNNING.contains(&function) => self.long_running,
+            None => self.default,
+        }
+    }
+}
+
+/// What a host call trace leaves out.
+///
+/// Traces recorded with [`SfBridge::call_with_trace`](crate::SfBridge::call_with_trace)
+/// never contain the values of registered [`Secret`](crate::Secret)s. The
+/// policy adds record fields whose values are replaced as well, such as
+/// personal data that shouldn't leave the org in a bug report. Field names
+/// match JSON object keys at any depth, case-insensitively.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bridge::{SfBridge, TracePolicy};
+///
+/// let bridge = SfBridge::new(wasm_bytes, client)?
+///     .with_trace_policy(TracePolicy::new().redact_field("Email").redact_field("Phone"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TracePolicy {
+    pub(crate) redact_fields: Vec<String>,
+}
+
+impl TracePolicy {
+    /// Redact only secrets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also redact the values of fields named `field`.
+    pub fn redact_field(mut self, field: impl Into<String>) -> Self {
+        self.redact_fields.push(field.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Whether values of `field` are redacted.
+    pub fn redacts_field(&self, field: &str) -> bool {
+        let field = field.to_ascii_lowercase();
+        self.redact_fields.contains(&field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn denied_names(policy: &WasiPolicy) -> Vec<&'static str> {
+        policy
+            .denied_imports()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn test_wasi_policy_clocks() {
+        let policy = WasiPolicy::new();
+        assert!(policy.allows_clocks());
+        assert!(denied_names(&policy).is_empty());
+
+        let policy = WasiPolicy::new()
+            .with_dir("/tmp/in", "/in")
+            .without_clocks();
+        assert!(policy.is_enabled());
+        assert!(!policy.allows_clocks());
+        assert_eq!(denied_names(&policy), ["clock_time_get", "clock_res_get"]);
+
+        // Shadowing WASI imports would provide them to a guest that has no
+        // WASI at all.
+        let policy = WasiPolicy::disabled().without_clocks();
+        assert!(!policy.allows_clocks());
+        assert!(denied_names(&policy).is_empty());
+    }
+
+    #[test]
+    fn test_http_policy_host_matching() {
+        let policy = HttpPolicy::new()
+            .allow_host("api.example.com")
+            .allow_host("*.Geocoder.example");
+        assert!(policy.allows_host("API.example.com"));
+        assert!(!policy.allows_host("example.com"));
+        assert!(!policy.allows_host("evil-api.example.com"));
+        assert!(policy.allows_host("eu.geocoder.example"));
+        assert!(policy.allows_host("a.b.geocoder.example"));
+        assert!(!policy.allows_host("geocoder.example"));
+        assert!(!policy.allows_host("evilgeocoder.example"));
+        assert!(!HttpPolicy::default().allows_host("api.example.com"));
+    }
+
+    #[test]
+    fn test_timeout_policy_lookup() {
+        let policy = TimeoutPolicy::new()
+            .with_default(Duration::from_secs(5))
+            .with_function(host_fn_names::QUERY, Duration::from_secs(60));
+        assert_eq!(
+            policy.timeout_for(host_fn_names::QUERY),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            policy.timeout_for(host_fn_names::CREATE),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            policy.timeout_for(host_fn_names::METADATA_DEPLOY),
+            Duration::from_secs(30 * 60)
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Apex REST host function wrapper and registration.
+use super::{bridge_host_fn, BridgeState, Registrar};
+use crate::host_functions;
+use busbar_sf_wasm_types::host_fn_names;
+use extism::{UserData, ValType};
+
+fn host_fn_apex_rest(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::APEX_REST,
+            host_functions::handle_apex_rest(&s.rest_client, &s.apex_rest_paths, r),
+        )
+    })
+}
+
+pub(super) fn register<'a>(
+    builder: Registrar<'a>,
+    user_data: &UserData<BridgeState>,
+) -> Registrar<'a> {
+    builder.with_function(
+        host_fn_names::APEX_REST,
+        [ValType::I64],
+        [ValType::I64],
+        user_data.clone(),
+        host_fn_apex_rest,
+    )
+}
@@ -1,5 +1,5 @@
 //! Binary API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,8 +11,10 @@ fn host_fn_get_blob(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_blob(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::GET_BLOB,
+            host_functions::handle_get_blob(&s.rest_client, r),
+        )
     })
 }
 
@@ -23,8 +25,10 @@ fn host_fn_get_relationship(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_relationship(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::GET_RELATIONSHIP,
+            host_functions::handle_get_relationship(&s.rest_client, r),
+        )
     })
 }
 
@@ -35,19 +39,18 @@ fn host_fn_get_rich_text_image(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_rich_text_image(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::GET_RICH_TEXT_IMAGE,
+            host_functions::handle_get_rich_text_image(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all binary API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::GET_BLOB,
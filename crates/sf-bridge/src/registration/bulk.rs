@@ -1,7 +1,7 @@
 //! Bulk API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
-use busbar_sf_wasm_types::host_fn_names;
+use busbar_sf_wasm_types::{host_fn_names, BridgeResult, OperationKind};
 use extism::{UserData, ValType};
 
 fn host_fn_bulk_abort_ingest_job(
@@ -11,11 +11,10 @@ fn host_fn_bulk_abort_ingest_job(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_abort_ingest_job(
-                &s.bulk_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::BULK_ABORT_INGEST_JOB,
+            host_functions::handle_bulk_abort_ingest_job(&s.bulk_client, r),
+        )
     })
 }
 
@@ -26,11 +25,10 @@ fn host_fn_bulk_abort_query_job(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_abort_query_job(
-                &s.bulk_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::BULK_ABORT_QUERY_JOB,
+            host_functions::handle_bulk_abort_query_job(&s.bulk_client, r),
+        )
     })
 }
 
@@ -41,11 +39,10 @@ fn host_fn_bulk_close_ingest_job(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_close_ingest_job(
-                &s.bulk_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::BULK_CLOSE_INGEST_JOB,
+            host_functions::handle_bulk_close_ingest_job(&s.bulk_client, r),
+        )
     })
 }
 
@@ -56,11 +53,34 @@ fn host_fn_bulk_create_ingest_job(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_create_ingest_job(
-                &s.bulk_client,
-                r,
-            ))
+        let result = s.block_on(
+            host_fn_names::BULK_CREATE_INGEST_JOB,
+            host_functions::handle_bulk_create_ingest_job(&s.bulk_client, r),
+        );
+        if let BridgeResult::Ok(job) = &result {
+            s.usage.record_bulk_job();
+            s.operations.track(&job.id, OperationKind::BulkIngest);
+        }
+        result
+    })
+}
+
+fn host_fn_bulk_create_query_job(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        let result = s.block_on(
+            host_fn_names::BULK_CREATE_QUERY_JOB,
+            host_functions::handle_bulk_create_query_job(&s.bulk_client, s.bound_queries_only, r),
+        );
+        if let BridgeResult::Ok(job) = &result {
+            s.usage.record_bulk_job();
+            s.operations.track(&job.id, OperationKind::BulkQuery);
+        }
+        result
     })
 }
 
@@ -71,11 +91,10 @@ fn host_fn_bulk_delete_ingest_job(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_delete_ingest_job(
-                &s.bulk_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::BULK_DELETE_INGEST_JOB,
+            host_functions::handle_bulk_delete_ingest_job(&s.bulk_client, r),
+        )
     })
 }
 
@@ -85,11 +104,11 @@ fn host_fn_bulk_get_all_ingest_jobs(
     outputs: &mut [extism::Val],
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
-    bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_bulk_get_all_ingest_jobs(
-                &s.bulk_client,
-            ))
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::BULK_GET_ALL_INGEST_JOBS,
+            host_functions::handle_bulk_get_all_ingest_jobs(&s.bulk_client, r),
+        )
     })
 }
 
@@ -100,11 +119,10 @@ fn host_fn_bulk_get_ingest_job(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_get_ingest_job(
-                &s.bulk_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::BULK_GET_INGEST_JOB,
+            host_functions::handle_bulk_get_ingest_job(&s.bulk_client, r),
+        )
     })
 }
 
@@ -115,11 +133,24 @@ fn host_fn_bulk_get_job_results(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_get_job_results(
-                &s.bulk_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::BULK_GET_JOB_RESULTS,
+            host_functions::handle_bulk_get_job_results(&s.bulk_client, r),
+        )
+    })
+}
+
+fn host_fn_bulk_get_query_job(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::BULK_GET_QUERY_JOB,
+            host_functions::handle_bulk_get_query_job(&s.bulk_client, r),
+        )
     })
 }
 
@@ -130,11 +161,10 @@ fn host_fn_bulk_get_query_results(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_get_query_results(
-                &s.bulk_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::BULK_GET_QUERY_RESULTS,
+            host_functions::handle_bulk_get_query_results(&s.bulk_client, r),
+        )
     })
 }
 
@@ -145,19 +175,18 @@ fn host_fn_bulk_upload_job_data(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_bulk_upload_job_data(
-                &s.bulk_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::BULK_UPLOAD_JOB_DATA,
+            host_functions::handle_bulk_upload_job_data(&s.bulk_client, r),
+        )
     })
 }
 
 /// Register all bulk API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::BULK_ABORT_INGEST_JOB,
@@ -187,6 +216,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_bulk_create_ingest_job,
         )
+        .with_function(
+            host_fn_names::BULK_CREATE_QUERY_JOB,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_bulk_create_query_job,
+        )
         .with_function(
             host_fn_names::BULK_DELETE_INGEST_JOB,
             [ValType::I64],
@@ -215,6 +251,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_bulk_get_job_results,
         )
+        .with_function(
+            host_fn_names::BULK_GET_QUERY_JOB,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_bulk_get_query_job,
+        )
         .with_function(
             host_fn_names::BULK_GET_QUERY_RESULTS,
             [ValType::I64],
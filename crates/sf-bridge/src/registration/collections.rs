@@ -1,5 +1,5 @@
-//! Collections API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+//! Collections and Recycle Bin host function wrappers and registration.
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,8 +11,10 @@ fn host_fn_create_multiple(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_create_multiple(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::CREATE_MULTIPLE,
+            host_functions::handle_create_multiple(&s.rest_client, r),
+        )
     })
 }
 
@@ -23,8 +25,24 @@ fn host_fn_delete_multiple(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_delete_multiple(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::DELETE_MULTIPLE,
+            host_functions::handle_delete_multiple(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_empty_recycle_bin(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::EMPTY_RECYCLE_BIN,
+            host_functions::handle_empty_recycle_bin(&s.rest_client, r),
+        )
     })
 }
 
@@ -35,8 +53,24 @@ fn host_fn_get_multiple(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_multiple(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::GET_MULTIPLE,
+            host_functions::handle_get_multiple(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_undelete(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::UNDELETE,
+            host_functions::handle_undelete(&s.rest_client, r),
+        )
     })
 }
 
@@ -47,16 +81,18 @@ fn host_fn_update_multiple(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_update_multiple(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::UPDATE_MULTIPLE,
+            host_functions::handle_update_multiple(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all collections API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::CREATE_MULTIPLE,
@@ -72,6 +108,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_delete_multiple,
         )
+        .with_function(
+            host_fn_names::EMPTY_RECYCLE_BIN,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_empty_recycle_bin,
+        )
         .with_function(
             host_fn_names::GET_MULTIPLE,
             [ValType::I64],
@@ -86,4 +129,11 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_update_multiple,
         )
+        .with_function(
+            host_fn_names::UNDELETE,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_undelete,
+        )
 }
@@ -1,5 +1,5 @@
 //! Composite API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,8 +11,10 @@ fn host_fn_composite(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_composite(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::COMPOSITE,
+            host_functions::handle_composite(&s.rest_client, r),
+        )
     })
 }
 
@@ -23,8 +25,10 @@ fn host_fn_composite_batch(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_composite_batch(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::COMPOSITE_BATCH,
+            host_functions::handle_composite_batch(&s.rest_client, r),
+        )
     })
 }
 
@@ -35,8 +39,10 @@ fn host_fn_composite_graph(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_composite_graph(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::COMPOSITE_GRAPH,
+            host_functions::handle_composite_graph(&s.rest_client, r),
+        )
     })
 }
 
@@ -47,16 +53,18 @@ fn host_fn_composite_tree(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_composite_tree(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::COMPOSITE_TREE,
+            host_functions::handle_composite_tree(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all composite API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::COMPOSITE,
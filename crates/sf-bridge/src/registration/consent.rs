@@ -1,5 +1,5 @@
 //! Consent API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,8 +11,10 @@ fn host_fn_read_consent(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_read_consent(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::READ_CONSENT,
+            host_functions::handle_read_consent(&s.rest_client, r),
+        )
     })
 }
 
@@ -23,8 +25,10 @@ fn host_fn_read_multi_consent(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_read_multi_consent(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::READ_MULTI_CONSENT,
+            host_functions::handle_read_multi_consent(&s.rest_client, r),
+        )
     })
 }
 
@@ -35,16 +39,18 @@ fn host_fn_write_consent(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_write_consent(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::WRITE_CONSENT,
+            host_functions::handle_write_consent(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all consent API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::READ_CONSENT,
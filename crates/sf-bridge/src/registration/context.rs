@@ -0,0 +1,48 @@
+//! Invocation context and capability discovery host function wrappers and
+//! registration.
+use super::{bridge_host_fn_no_input, BridgeState, Registrar};
+use busbar_sf_wasm_types::{host_fn_names, BridgeResult};
+use extism::{UserData, ValType};
+
+fn host_fn_invocation_context(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
+        BridgeResult::ok(s.context.clone())
+    })
+}
+
+fn host_fn_capabilities(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
+        BridgeResult::ok(crate::discovery::capabilities(s))
+    })
+}
+
+pub(super) fn register<'a>(
+    builder: Registrar<'a>,
+    user_data: &UserData<BridgeState>,
+) -> Registrar<'a> {
+    builder
+        .with_function(
+            host_fn_names::INVOCATION_CONTEXT,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_invocation_context,
+        )
+        .with_function(
+            host_fn_names::CAPABILITIES,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_capabilities,
+        )
+}
@@ -1,5 +1,5 @@
 //! Embedded Service API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,19 +11,18 @@ fn host_fn_get_embedded_service_config(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_embedded_service_config(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::GET_EMBEDDED_SERVICE_CONFIG,
+            host_functions::handle_get_embedded_service_config(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all embedded_service API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder.with_function(
         host_fn_names::GET_EMBEDDED_SERVICE_CONFIG,
         [ValType::I64],
@@ -0,0 +1,32 @@
+//! Outbound HTTP host function wrapper and registration.
+use super::{bridge_host_fn, BridgeState, Registrar};
+use crate::host_functions;
+use busbar_sf_wasm_types::host_fn_names;
+use extism::{UserData, ValType};
+
+fn host_fn_http_request(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::HTTP_REQUEST,
+            host_functions::handle_http_request(&s.http_client, &s.http_policy, &s.secrets, r),
+        )
+    })
+}
+
+pub(super) fn register<'a>(
+    builder: Registrar<'a>,
+    user_data: &UserData<BridgeState>,
+) -> Registrar<'a> {
+    builder.with_function(
+        host_fn_names::HTTP_REQUEST,
+        [ValType::I64],
+        [ValType::I64],
+        user_data.clone(),
+        host_fn_http_request,
+    )
+}
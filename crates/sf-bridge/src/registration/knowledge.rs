@@ -1,9 +1,37 @@
 //! Knowledge API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
 
+fn host_fn_archive_knowledge_articles(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::ARCHIVE_KNOWLEDGE_ARTICLES,
+            host_functions::handle_archive_knowledge_articles(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_create_knowledge_draft(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::CREATE_KNOWLEDGE_DRAFT,
+            host_functions::handle_create_knowledge_draft(&s.rest_client, r),
+        )
+    })
+}
+
 fn host_fn_data_categories(
     plugin: &mut extism::CurrentPlugin,
     inputs: &[extism::Val],
@@ -11,8 +39,10 @@ fn host_fn_data_categories(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_data_categories(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::DATA_CATEGORIES,
+            host_functions::handle_data_categories(&s.rest_client, r),
+        )
     })
 }
 
@@ -23,11 +53,24 @@ fn host_fn_data_category_groups(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_data_category_groups(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::DATA_CATEGORY_GROUPS,
+            host_functions::handle_data_category_groups(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_knowledge_article(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::KNOWLEDGE_ARTICLE,
+            host_functions::handle_knowledge_article(&s.rest_client, r),
+        )
     })
 }
 
@@ -38,8 +81,24 @@ fn host_fn_knowledge_articles(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_knowledge_articles(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::KNOWLEDGE_ARTICLES,
+            host_functions::handle_knowledge_articles(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_knowledge_draft_from_online(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::KNOWLEDGE_DRAFT_FROM_ONLINE,
+            host_functions::handle_knowledge_draft_from_online(&s.rest_client, r),
+        )
     })
 }
 
@@ -50,17 +109,47 @@ fn host_fn_knowledge_settings(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_knowledge_settings(&s.rest_client))
+        s.block_on(
+            host_fn_names::KNOWLEDGE_SETTINGS,
+            host_functions::handle_knowledge_settings(&s.rest_client),
+        )
+    })
+}
+
+fn host_fn_publish_knowledge_articles(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::PUBLISH_KNOWLEDGE_ARTICLES,
+            host_functions::handle_publish_knowledge_articles(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all knowledge API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
+        .with_function(
+            host_fn_names::ARCHIVE_KNOWLEDGE_ARTICLES,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_archive_knowledge_articles,
+        )
+        .with_function(
+            host_fn_names::CREATE_KNOWLEDGE_DRAFT,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_create_knowledge_draft,
+        )
         .with_function(
             host_fn_names::DATA_CATEGORIES,
             [ValType::I64],
@@ -75,6 +164,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_data_category_groups,
         )
+        .with_function(
+            host_fn_names::KNOWLEDGE_ARTICLE,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_knowledge_article,
+        )
         .with_function(
             host_fn_names::KNOWLEDGE_ARTICLES,
             [ValType::I64],
@@ -82,6 +178,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_knowledge_articles,
         )
+        .with_function(
+            host_fn_names::KNOWLEDGE_DRAFT_FROM_ONLINE,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_knowledge_draft_from_online,
+        )
         .with_function(
             host_fn_names::KNOWLEDGE_SETTINGS,
             [ValType::I64],
@@ -89,4 +192,11 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_knowledge_settings,
         )
+        .with_function(
+            host_fn_names::PUBLISH_KNOWLEDGE_ARTICLES,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_publish_knowledge_articles,
+        )
 }
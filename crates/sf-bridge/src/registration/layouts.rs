@@ -1,5 +1,5 @@
 //! Layouts API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,11 +11,10 @@ fn host_fn_compact_layouts_multi(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_compact_layouts_multi(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::COMPACT_LAYOUTS_MULTI,
+            host_functions::handle_compact_layouts_multi(&s.rest_client, r),
+        )
     })
 }
 
@@ -26,11 +25,10 @@ fn host_fn_describe_approval_layouts(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_approval_layouts(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::DESCRIBE_APPROVAL_LAYOUTS,
+            host_functions::handle_describe_approval_layouts(&s.rest_client, r),
+        )
     })
 }
 
@@ -41,11 +39,10 @@ fn host_fn_describe_compact_layouts(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_compact_layouts(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::DESCRIBE_COMPACT_LAYOUTS,
+            host_functions::handle_describe_compact_layouts(&s.rest_client, r),
+        )
     })
 }
 
@@ -56,10 +53,10 @@ fn host_fn_describe_global_publisher_layouts(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_describe_global_publisher_layouts(
-                &s.rest_client,
-            ))
+        s.block_on(
+            host_fn_names::DESCRIBE_GLOBAL_PUBLISHER_LAYOUTS,
+            host_functions::handle_describe_global_publisher_layouts(&s.rest_client),
+        )
     })
 }
 
@@ -70,8 +67,10 @@ fn host_fn_describe_layouts(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_layouts(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::DESCRIBE_LAYOUTS,
+            host_functions::handle_describe_layouts(&s.rest_client, r),
+        )
     })
 }
 
@@ -82,19 +81,18 @@ fn host_fn_describe_named_layout(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_named_layout(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::DESCRIBE_NAMED_LAYOUT,
+            host_functions::handle_describe_named_layout(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all layouts API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::COMPACT_LAYOUTS_MULTI,
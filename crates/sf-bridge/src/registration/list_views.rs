@@ -1,5 +1,5 @@
 //! List Views API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,8 +11,10 @@ fn host_fn_describe_list_view(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_list_view(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::DESCRIBE_LIST_VIEW,
+            host_functions::handle_describe_list_view(&s.rest_client, r),
+        )
     })
 }
 
@@ -23,8 +25,10 @@ fn host_fn_execute_list_view(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_execute_list_view(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::EXECUTE_LIST_VIEW,
+            host_functions::handle_execute_list_view(&s.rest_client, r),
+        )
     })
 }
 
@@ -35,8 +39,10 @@ fn host_fn_get_list_view(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_list_view(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::GET_LIST_VIEW,
+            host_functions::handle_get_list_view(&s.rest_client, r),
+        )
     })
 }
 
@@ -47,16 +53,18 @@ fn host_fn_list_views(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_list_views(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::LIST_VIEWS,
+            host_functions::handle_list_views(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all list_views API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::DESCRIBE_LIST_VIEW,
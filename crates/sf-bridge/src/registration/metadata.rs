@@ -1,7 +1,7 @@
 //! Metadata API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState, Registrar};
 use crate::host_functions;
-use busbar_sf_wasm_types::host_fn_names;
+use busbar_sf_wasm_types::{host_fn_names, BridgeResult, OperationKind};
 use extism::{UserData, ValType};
 
 fn host_fn_metadata_check_deploy_status(
@@ -12,10 +12,10 @@ fn host_fn_metadata_check_deploy_status(
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s: &BridgeState, r| {
         let client = s.metadata_client();
-        s.handle
-            .block_on(host_functions::handle_metadata_check_deploy_status(
-                &client, r,
-            ))
+        s.block_on(
+            host_fn_names::METADATA_CHECK_DEPLOY_STATUS,
+            host_functions::handle_metadata_check_deploy_status(&client, r),
+        )
     })
 }
 
@@ -27,10 +27,10 @@ fn host_fn_metadata_check_retrieve_status(
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s: &BridgeState, r| {
         let client = s.metadata_client();
-        s.handle
-            .block_on(host_functions::handle_metadata_check_retrieve_status(
-                &client, r,
-            ))
+        s.block_on(
+            host_fn_names::METADATA_CHECK_RETRIEVE_STATUS,
+            host_functions::handle_metadata_check_retrieve_status(&client, r),
+        )
     })
 }
 
@@ -42,8 +42,15 @@ fn host_fn_metadata_deploy(
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s: &BridgeState, r| {
         let client = s.metadata_client();
-        s.handle
-            .block_on(host_functions::handle_metadata_deploy(&client, r))
+        let result = s.block_on(
+            host_fn_names::METADATA_DEPLOY,
+            host_functions::handle_metadata_deploy(&client, r),
+        );
+        if let BridgeResult::Ok(started) = &result {
+            s.operations
+                .track(&started.async_process_id, OperationKind::Deploy);
+        }
+        result
     })
 }
 
@@ -55,8 +62,10 @@ fn host_fn_metadata_describe(
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s: &BridgeState| {
         let client = s.metadata_client();
-        s.handle
-            .block_on(host_functions::handle_metadata_describe(&client))
+        s.block_on(
+            host_fn_names::METADATA_DESCRIBE,
+            host_functions::handle_metadata_describe(&client),
+        )
     })
 }
 
@@ -68,8 +77,10 @@ fn host_fn_metadata_list(
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s: &BridgeState, r| {
         let client = s.metadata_client();
-        s.handle
-            .block_on(host_functions::handle_metadata_list(&client, r))
+        s.block_on(
+            host_fn_names::METADATA_LIST,
+            host_functions::handle_metadata_list(&client, r),
+        )
     })
 }
 
@@ -81,16 +92,23 @@ fn host_fn_metadata_retrieve(
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s: &BridgeState, r| {
         let client = s.metadata_client();
-        s.handle
-            .block_on(host_functions::handle_metadata_retrieve(&client, r))
+        let result = s.block_on(
+            host_fn_names::METADATA_RETRIEVE,
+            host_functions::handle_metadata_retrieve(&client, r),
+        );
+        if let BridgeResult::Ok(started) = &result {
+            s.operations
+                .track(&started.async_process_id, OperationKind::Retrieve);
+        }
+        result
     })
 }
 
 /// Register all metadata API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::METADATA_CHECK_DEPLOY_STATUS,
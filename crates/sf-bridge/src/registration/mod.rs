@@ -6,8 +6,16 @@
 // Re-export helpers from parent module for use in submodules
 use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
 
-use extism::{PluginBuilder, UserData};
+use std::sync::Arc;
 
+use extism::{CurrentPlugin, PluginBuilder, UserData, Val, ValType};
+
+mod context;
+mod http;
+mod operations;
+
+#[cfg(feature = "rest")]
+mod apex_rest;
 #[cfg(feature = "rest")]
 mod binary;
 #[cfg(feature = "rest")]
@@ -48,11 +56,124 @@ mod tooling;
 #[cfg(feature = "metadata")]
 mod metadata;
 
+/// A plugin builder that remembers the names of the host functions added
+/// to it, so `sf_capabilities` reports exactly what was registered.
+pub(crate) struct Registrar<'a> {
+    builder: PluginBuilder<'a>,
+    names: Vec<&'static str>,
+}
+
+impl<'a> Registrar<'a> {
+    pub(crate) fn new(builder: PluginBuilder<'a>) -> Self {
+        Self {
+            builder,
+            names: Vec::new(),
+        }
+    }
+
+    /// Add a host function; see [`PluginBuilder::with_function`].
+    pub(crate) fn with_function<F>(
+        mut self,
+        name: &'static str,
+        args: impl IntoIterator<Item = ValType>,
+        returns: impl IntoIterator<Item = ValType>,
+        user_data: UserData<BridgeState>,
+        f: F,
+    ) -> Self
+    where
+        F: Fn(
+                &mut CurrentPlugin,
+                &[Val],
+                &mut [Val],
+                UserData<BridgeState>,
+            ) -> Result<(), extism::Error>
+            + Sync
+            + Send
+            + 'static,
+    {
+        self.builder = self
+            .builder
+            .with_function(name, args, returns, user_data, traced(name, f));
+        self.names.push(name);
+        self
+    }
+
+    /// The builder, and the names of the functions registered on it.
+    pub(crate) fn finish(self) -> (PluginBuilder<'a>, Vec<&'static str>) {
+        (self.builder, self.names)
+    }
+}
+
+/// Wrap a host function so the objects its calls name are logged for the
+/// plugin's usage report, and its calls are recorded when the invocation
+/// is traced.
+///
+/// Every host function takes its request in `inputs[0]` and returns its
+/// response in `outputs[0]`, so both are read from plugin memory here
+/// rather than in each handler.
+fn traced<F>(
+    name: &'static str,
+    f: F,
+) -> impl Fn(
+    &mut CurrentPlugin,
+    &[Val],
+    &mut [Val],
+    UserData<BridgeState>,
+) -> Result<(), extism::Error>
+       + Sync
+       + Send
+       + 'static
+where
+    F: Fn(
+            &mut CurrentPlugin,
+            &[Val],
+            &mut [Val],
+            UserData<BridgeState>,
+        ) -> Result<(), extism::Error>
+        + Sync
+        + Send
+        + 'static,
+{
+    move |plugin, inputs, outputs, user_data| {
+        let (access, trace) = {
+            let state = user_data.get()?;
+            let state = state.lock().unwrap();
+            (Arc::clone(&state.access), state.trace.clone())
+        };
+
+        let request: Option<Vec<u8>> = inputs
+            .first()
+            .and_then(|val| plugin.memory_get_val(val).ok());
+        access.record(name, request.as_deref());
+        let Some(trace) = trace else {
+            return f(plugin, inputs, outputs, user_data);
+        };
+
+        let started = std::time::Instant::now();
+        let result = f(plugin, inputs, outputs, user_data);
+        let elapsed = started.elapsed();
+        let response = match &result {
+            Ok(()) => outputs
+                .first()
+                .and_then(|val| plugin.memory_get_val::<Vec<u8>>(val).ok())
+                .ok_or_else(|| "no response was returned".to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        trace.record(
+            name,
+            request.as_deref(),
+            response.as_deref().map_err(Clone::clone),
+            elapsed,
+        );
+        result
+    }
+}
+
 /// Register all enabled host functions based on feature flags.
 pub(crate) fn register_all<'a>(
-    mut builder: PluginBuilder<'a>,
+    mut builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> PluginBuilder<'a> {
+) -> Registrar<'a> {
     #[cfg(feature = "rest")]
     {
         builder = rest::register(builder, user_data);
@@ -70,6 +191,7 @@ pub(crate) fn register_all<'a>(
         builder = binary::register(builder, user_data);
         builder = embedded_service::register(builder, user_data);
         builder = search::register(builder, user_data);
+        builder = apex_rest::register(builder, user_data);
     }
 
     #[cfg(feature = "bulk")]
@@ -87,5 +209,9 @@ pub(crate) fn register_all<'a>(
         builder = metadata::register(builder, user_data);
     }
 
+    builder = http::register(builder, user_data);
+    builder = context::register(builder, user_data);
+    builder = operations::register(builder, user_data);
+
     builder
 }
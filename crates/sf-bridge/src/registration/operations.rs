@@ -0,0 +1,88 @@
+//! Long-running operation host function wrapper and registration.
+use super::{bridge_host_fn, BridgeState, Registrar};
+#[cfg(any(feature = "bulk", feature = "metadata"))]
+use crate::host_functions;
+#[cfg(any(feature = "bulk", feature = "metadata"))]
+use busbar_sf_wasm_types::OperationKind;
+use busbar_sf_wasm_types::{host_fn_names, BridgeResult, OperationStatus, OperationStatusRequest};
+use extism::{UserData, ValType};
+
+fn host_fn_operation_status(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(
+        plugin,
+        inputs,
+        outputs,
+        user_data,
+        |s, r: OperationStatusRequest| -> BridgeResult<OperationStatus> {
+            let Some(kind) = s.operations.kind_of(&r.handle) else {
+                #[cfg(feature = "bulk")]
+                if crate::operations::is_bulk_job_id(&r.handle) {
+                    return s.block_on(
+                        host_fn_names::BULK_GET_INGEST_JOB,
+                        host_functions::handle_untracked_bulk_operation_status(
+                            &s.bulk_client,
+                            r.handle,
+                        ),
+                    );
+                }
+                return BridgeResult::err(
+                    "UNKNOWN_OPERATION",
+                    format!("no operation with handle {}", r.handle),
+                );
+            };
+            // Status checks run under the check function's own name so its
+            // API family's timeout and circuit breaker apply.
+            match kind {
+                #[cfg(feature = "metadata")]
+                OperationKind::Deploy => {
+                    let client = s.metadata_client();
+                    s.block_on(
+                        host_fn_names::METADATA_CHECK_DEPLOY_STATUS,
+                        host_functions::handle_deploy_operation_status(&client, r.handle),
+                    )
+                }
+                #[cfg(feature = "metadata")]
+                OperationKind::Retrieve => {
+                    let client = s.metadata_client();
+                    s.block_on(
+                        host_fn_names::METADATA_CHECK_RETRIEVE_STATUS,
+                        host_functions::handle_retrieve_operation_status(&client, r.handle),
+                    )
+                }
+                #[cfg(feature = "bulk")]
+                OperationKind::BulkIngest => s.block_on(
+                    host_fn_names::BULK_GET_INGEST_JOB,
+                    host_functions::handle_bulk_operation_status(&s.bulk_client, r.handle),
+                ),
+                #[cfg(feature = "bulk")]
+                OperationKind::BulkQuery => s.block_on(
+                    host_fn_names::BULK_GET_QUERY_JOB,
+                    host_functions::handle_bulk_query_operation_status(&s.bulk_client, r.handle),
+                ),
+                #[allow(unreachable_patterns)]
+                kind => BridgeResult::err(
+                    "UNSUPPORTED_OPERATION",
+                    format!("{kind:?} operations are not enabled in this bridge"),
+                ),
+            }
+        },
+    )
+}
+
+pub(super) fn register<'a>(
+    builder: Registrar<'a>,
+    user_data: &UserData<BridgeState>,
+) -> Registrar<'a> {
+    builder.with_function(
+        host_fn_names::OPERATION_STATUS,
+        [ValType::I64],
+        [ValType::I64],
+        user_data.clone(),
+        host_fn_operation_status,
+    )
+}
@@ -1,5 +1,5 @@
 //! Process & Approvals API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,10 +11,10 @@ fn host_fn_list_pending_approvals(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_list_pending_approvals(
-                &s.rest_client,
-            ))
+        s.block_on(
+            host_fn_names::LIST_PENDING_APPROVALS,
+            host_functions::handle_list_pending_approvals(&s.rest_client),
+        )
     })
 }
 
@@ -25,8 +25,10 @@ fn host_fn_list_process_rules(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_list_process_rules(&s.rest_client))
+        s.block_on(
+            host_fn_names::LIST_PROCESS_RULES,
+            host_functions::handle_list_process_rules(&s.rest_client),
+        )
     })
 }
 
@@ -37,11 +39,10 @@ fn host_fn_list_process_rules_for_sobject(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_list_process_rules_for_sobject(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::LIST_PROCESS_RULES_FOR_SOBJECT,
+            host_functions::handle_list_process_rules_for_sobject(&s.rest_client, r),
+        )
     })
 }
 
@@ -52,8 +53,10 @@ fn host_fn_submit_approval(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_submit_approval(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::SUBMIT_APPROVAL,
+            host_functions::handle_submit_approval(&s.rest_client, r),
+        )
     })
 }
 
@@ -64,19 +67,18 @@ fn host_fn_trigger_process_rules(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_trigger_process_rules(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::TRIGGER_PROCESS_RULES,
+            host_functions::handle_trigger_process_rules(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all process API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::LIST_PENDING_APPROVALS,
@@ -1,5 +1,5 @@
 //! Quick Actions API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,11 +11,10 @@ fn host_fn_describe_custom_action(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_custom_action(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::DESCRIBE_CUSTOM_ACTION,
+            host_functions::handle_describe_custom_action(&s.rest_client, r),
+        )
     })
 }
 
@@ -26,11 +25,10 @@ fn host_fn_describe_global_quick_action(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_global_quick_action(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::DESCRIBE_GLOBAL_QUICK_ACTION,
+            host_functions::handle_describe_global_quick_action(&s.rest_client, r),
+        )
     })
 }
 
@@ -41,11 +39,10 @@ fn host_fn_describe_quick_action(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_quick_action(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::DESCRIBE_QUICK_ACTION,
+            host_functions::handle_describe_quick_action(&s.rest_client, r),
+        )
     })
 }
 
@@ -56,11 +53,10 @@ fn host_fn_describe_standard_action(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_standard_action(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::DESCRIBE_STANDARD_ACTION,
+            host_functions::handle_describe_standard_action(&s.rest_client, r),
+        )
     })
 }
 
@@ -71,11 +67,10 @@ fn host_fn_invoke_custom_action(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_invoke_custom_action(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::INVOKE_CUSTOM_ACTION,
+            host_functions::handle_invoke_custom_action(&s.rest_client, r),
+        )
     })
 }
 
@@ -86,11 +81,10 @@ fn host_fn_invoke_quick_action(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_invoke_quick_action(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::INVOKE_QUICK_ACTION,
+            host_functions::handle_invoke_quick_action(&s.rest_client, r),
+        )
     })
 }
 
@@ -101,11 +95,10 @@ fn host_fn_invoke_standard_action(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_invoke_standard_action(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::INVOKE_STANDARD_ACTION,
+            host_functions::handle_invoke_standard_action(&s.rest_client, r),
+        )
     })
 }
 
@@ -116,10 +109,10 @@ fn host_fn_list_custom_action_types(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_list_custom_action_types(
-                &s.rest_client,
-            ))
+        s.block_on(
+            host_fn_names::LIST_CUSTOM_ACTION_TYPES,
+            host_functions::handle_list_custom_action_types(&s.rest_client),
+        )
     })
 }
 
@@ -130,11 +123,10 @@ fn host_fn_list_custom_actions(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_list_custom_actions(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::LIST_CUSTOM_ACTIONS,
+            host_functions::handle_list_custom_actions(&s.rest_client, r),
+        )
     })
 }
 
@@ -145,10 +137,10 @@ fn host_fn_list_global_quick_actions(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_list_global_quick_actions(
-                &s.rest_client,
-            ))
+        s.block_on(
+            host_fn_names::LIST_GLOBAL_QUICK_ACTIONS,
+            host_functions::handle_list_global_quick_actions(&s.rest_client),
+        )
     })
 }
 
@@ -159,8 +151,10 @@ fn host_fn_list_quick_actions(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_list_quick_actions(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::LIST_QUICK_ACTIONS,
+            host_functions::handle_list_quick_actions(&s.rest_client, r),
+        )
     })
 }
 
@@ -171,16 +165,18 @@ fn host_fn_list_standard_actions(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_list_standard_actions(&s.rest_client))
+        s.block_on(
+            host_fn_names::LIST_STANDARD_ACTIONS,
+            host_functions::handle_list_standard_actions(&s.rest_client),
+        )
     })
 }
 
 /// Register all quick_actions API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::DESCRIBE_CUSTOM_ACTION,
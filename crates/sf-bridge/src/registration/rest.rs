@@ -1,9 +1,23 @@
 //! REST API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
 
+fn host_fn_query_cursor_next(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::QUERY_CURSOR_NEXT,
+            host_functions::handle_query_cursor_next(&s.rest_client, &s.query_spill, r),
+        )
+    })
+}
+
 fn host_fn_create(
     plugin: &mut extism::CurrentPlugin,
     inputs: &[extism::Val],
@@ -11,8 +25,10 @@ fn host_fn_create(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_create(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::CREATE,
+            host_functions::handle_create(&s.rest_client, r),
+        )
     })
 }
 
@@ -23,8 +39,24 @@ fn host_fn_delete(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_delete(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::DELETE,
+            host_functions::handle_delete(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_delete_by_external_id(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::DELETE_BY_EXTERNAL_ID,
+            host_functions::handle_delete_by_external_id(&s.rest_client, r),
+        )
     })
 }
 
@@ -35,8 +67,10 @@ fn host_fn_describe_global(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_describe_global(&s.rest_client))
+        s.block_on(
+            host_fn_names::DESCRIBE_GLOBAL,
+            host_functions::handle_describe_global(&s.rest_client),
+        )
     })
 }
 
@@ -47,8 +81,10 @@ fn host_fn_describe_sobject(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_describe_sobject(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::DESCRIBE_SOBJECT,
+            host_functions::handle_describe_sobject(&s.rest_client, r),
+        )
     })
 }
 
@@ -59,8 +95,52 @@ fn host_fn_get(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::GET,
+            host_functions::handle_get(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_changes_since(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::CHANGES_SINCE,
+            host_functions::handle_changes_since(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_schema_snapshot(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::SCHEMA_SNAPSHOT,
+            host_functions::handle_schema_snapshot(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_get_by_external_id(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::GET_BY_EXTERNAL_ID,
+            host_functions::handle_get_by_external_id(&s.rest_client, r),
+        )
     })
 }
 
@@ -71,8 +151,10 @@ fn host_fn_get_deleted(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_deleted(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::GET_DELETED,
+            host_functions::handle_get_deleted(&s.rest_client, r),
+        )
     })
 }
 
@@ -83,8 +165,10 @@ fn host_fn_get_updated(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_updated(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::GET_UPDATED,
+            host_functions::handle_get_updated(&s.rest_client, r),
+        )
     })
 }
 
@@ -95,8 +179,10 @@ fn host_fn_limits(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_limits(&s.rest_client))
+        s.block_on(
+            host_fn_names::LIMITS,
+            host_functions::handle_limits(&s.rest_client),
+        )
     })
 }
 
@@ -107,8 +193,10 @@ fn host_fn_query(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_query(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::QUERY,
+            host_functions::handle_query(&s.rest_client, s.bound_queries_only, &s.query_spill, r),
+        )
     })
 }
 
@@ -119,8 +207,10 @@ fn host_fn_query_more(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_query_more(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::QUERY_MORE,
+            host_functions::handle_query_more(&s.rest_client, &s.query_spill, r),
+        )
     })
 }
 
@@ -131,8 +221,10 @@ fn host_fn_search(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_search(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::SEARCH,
+            host_functions::handle_search(&s.rest_client, s.bound_queries_only, r),
+        )
     })
 }
 
@@ -143,8 +235,10 @@ fn host_fn_update(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_update(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::UPDATE,
+            host_functions::handle_update(&s.rest_client, r),
+        )
     })
 }
 
@@ -155,8 +249,10 @@ fn host_fn_upsert(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_upsert(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::UPSERT,
+            host_functions::handle_upsert(&s.rest_client, r),
+        )
     })
 }
 
@@ -167,16 +263,18 @@ fn host_fn_versions(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_versions(&s.rest_client))
+        s.block_on(
+            host_fn_names::VERSIONS,
+            host_functions::handle_versions(&s.rest_client),
+        )
     })
 }
 
 /// Register all rest API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::CREATE,
@@ -192,6 +290,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_delete,
         )
+        .with_function(
+            host_fn_names::DELETE_BY_EXTERNAL_ID,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_delete_by_external_id,
+        )
         .with_function(
             host_fn_names::DESCRIBE_GLOBAL,
             [ValType::I64],
@@ -213,6 +318,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_get,
         )
+        .with_function(
+            host_fn_names::GET_BY_EXTERNAL_ID,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_get_by_external_id,
+        )
         .with_function(
             host_fn_names::GET_DELETED,
             [ValType::I64],
@@ -227,6 +339,20 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_get_updated,
         )
+        .with_function(
+            host_fn_names::CHANGES_SINCE,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_changes_since,
+        )
+        .with_function(
+            host_fn_names::SCHEMA_SNAPSHOT,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_schema_snapshot,
+        )
         .with_function(
             host_fn_names::LIMITS,
             [ValType::I64],
@@ -248,6 +374,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_query_more,
         )
+        .with_function(
+            host_fn_names::QUERY_CURSOR_NEXT,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_query_cursor_next,
+        )
         .with_function(
             host_fn_names::SEARCH,
             [ValType::I64],
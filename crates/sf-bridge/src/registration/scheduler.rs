@@ -1,5 +1,5 @@
 //! Scheduler API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,11 +11,10 @@ fn host_fn_appointment_candidates(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_appointment_candidates(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::APPOINTMENT_CANDIDATES,
+            host_functions::handle_appointment_candidates(&s.rest_client, r),
+        )
     })
 }
 
@@ -26,16 +25,18 @@ fn host_fn_appointment_slots(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_appointment_slots(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::APPOINTMENT_SLOTS,
+            host_functions::handle_appointment_slots(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all scheduler API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::APPOINTMENT_CANDIDATES,
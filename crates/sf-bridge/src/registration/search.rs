@@ -1,5 +1,5 @@
 //! Search API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,11 +11,10 @@ fn host_fn_parameterized_search(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_parameterized_search(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::PARAMETERIZED_SEARCH,
+            host_functions::handle_parameterized_search(&s.rest_client, r),
+        )
     })
 }
 
@@ -26,11 +25,10 @@ fn host_fn_search_result_layouts(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_search_result_layouts(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::SEARCH_RESULT_LAYOUTS,
+            host_functions::handle_search_result_layouts(&s.rest_client, r),
+        )
     })
 }
 
@@ -41,8 +39,10 @@ fn host_fn_search_scope_order(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_search_scope_order(&s.rest_client))
+        s.block_on(
+            host_fn_names::SEARCH_SCOPE_ORDER,
+            host_functions::handle_search_scope_order(&s.rest_client),
+        )
     })
 }
 
@@ -53,16 +53,18 @@ fn host_fn_search_suggestions(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_search_suggestions(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::SEARCH_SUGGESTIONS,
+            host_functions::handle_search_suggestions(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all search API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::PARAMETERIZED_SEARCH,
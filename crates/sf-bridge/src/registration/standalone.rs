@@ -1,5 +1,5 @@
 //! Standalone API host function wrappers and registration.
-use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState};
+use super::{bridge_host_fn, bridge_host_fn_no_input, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,8 +11,10 @@ fn host_fn_app_menu(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_app_menu(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::APP_MENU,
+            host_functions::handle_app_menu(&s.rest_client, r),
+        )
     })
 }
 
@@ -23,10 +25,10 @@ fn host_fn_lightning_toggle_metrics(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_lightning_toggle_metrics(
-                &s.rest_client,
-            ))
+        s.block_on(
+            host_fn_names::LIGHTNING_TOGGLE_METRICS,
+            host_functions::handle_lightning_toggle_metrics(&s.rest_client),
+        )
     })
 }
 
@@ -37,8 +39,10 @@ fn host_fn_lightning_usage(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_lightning_usage(&s.rest_client))
+        s.block_on(
+            host_fn_names::LIGHTNING_USAGE,
+            host_functions::handle_lightning_usage(&s.rest_client),
+        )
     })
 }
 
@@ -49,11 +53,24 @@ fn host_fn_platform_event_schema(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_platform_event_schema(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::PLATFORM_EVENT_SCHEMA,
+            host_functions::handle_platform_event_schema(&s.rest_client, r),
+        )
+    })
+}
+
+fn host_fn_publish_event(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::PUBLISH_EVENT,
+            host_functions::handle_publish_event(&s.rest_client, r),
+        )
     })
 }
 
@@ -64,8 +81,10 @@ fn host_fn_recent_items(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_recent_items(&s.rest_client))
+        s.block_on(
+            host_fn_names::RECENT_ITEMS,
+            host_functions::handle_recent_items(&s.rest_client),
+        )
     })
 }
 
@@ -76,8 +95,10 @@ fn host_fn_relevant_items(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_relevant_items(&s.rest_client))
+        s.block_on(
+            host_fn_names::RELEVANT_ITEMS,
+            host_functions::handle_relevant_items(&s.rest_client),
+        )
     })
 }
 
@@ -88,8 +109,10 @@ fn host_fn_tabs(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_tabs(&s.rest_client))
+        s.block_on(
+            host_fn_names::TABS,
+            host_functions::handle_tabs(&s.rest_client),
+        )
     })
 }
 
@@ -100,16 +123,18 @@ fn host_fn_theme(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn_no_input(plugin, inputs, outputs, user_data, |s| {
-        s.handle
-            .block_on(host_functions::handle_theme(&s.rest_client))
+        s.block_on(
+            host_fn_names::THEME,
+            host_functions::handle_theme(&s.rest_client),
+        )
     })
 }
 
 /// Register all standalone API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::APP_MENU,
@@ -139,6 +164,13 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_platform_event_schema,
         )
+        .with_function(
+            host_fn_names::PUBLISH_EVENT,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_publish_event,
+        )
         .with_function(
             host_fn_names::RECENT_ITEMS,
             [ValType::I64],
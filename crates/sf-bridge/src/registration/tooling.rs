@@ -1,9 +1,51 @@
 //! Tooling API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
 
+fn host_fn_tooling_abort_job(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::TOOLING_ABORT_JOB,
+            host_functions::handle_tooling_abort_job(&s.tooling_client, r),
+        )
+    })
+}
+
+fn host_fn_tooling_async_apex_jobs(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::TOOLING_ASYNC_APEX_JOBS,
+            host_functions::handle_tooling_async_apex_jobs(&s.tooling_client, r),
+        )
+    })
+}
+
+fn host_fn_tooling_composite(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::TOOLING_COMPOSITE,
+            host_functions::handle_tooling_composite(&s.tooling_client, r),
+        )
+    })
+}
+
 fn host_fn_tooling_create(
     plugin: &mut extism::CurrentPlugin,
     inputs: &[extism::Val],
@@ -11,8 +53,38 @@ fn host_fn_tooling_create(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_tooling_create(&s.tooling_client, r))
+        s.block_on(
+            host_fn_names::TOOLING_CREATE,
+            host_functions::handle_tooling_create(&s.tooling_client, r),
+        )
+    })
+}
+
+fn host_fn_tooling_update(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::TOOLING_UPDATE,
+            host_functions::handle_tooling_update(&s.tooling_client, r),
+        )
+    })
+}
+
+fn host_fn_tooling_upsert(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::TOOLING_UPSERT,
+            host_functions::handle_tooling_upsert(&s.tooling_client, r),
+        )
     })
 }
 
@@ -23,8 +95,10 @@ fn host_fn_tooling_delete(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_tooling_delete(&s.tooling_client, r))
+        s.block_on(
+            host_fn_names::TOOLING_DELETE,
+            host_functions::handle_tooling_delete(&s.tooling_client, r),
+        )
     })
 }
 
@@ -35,11 +109,10 @@ fn host_fn_tooling_execute_anonymous(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_tooling_execute_anonymous(
-                &s.tooling_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::TOOLING_EXECUTE_ANONYMOUS,
+            host_functions::handle_tooling_execute_anonymous(&s.tooling_client, r),
+        )
     })
 }
 
@@ -50,8 +123,10 @@ fn host_fn_tooling_get(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_tooling_get(&s.tooling_client, r))
+        s.block_on(
+            host_fn_names::TOOLING_GET,
+            host_functions::handle_tooling_get(&s.tooling_client, r),
+        )
     })
 }
 
@@ -62,17 +137,82 @@ fn host_fn_tooling_query(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_tooling_query(&s.tooling_client, r))
+        s.block_on(
+            host_fn_names::TOOLING_QUERY,
+            host_functions::handle_tooling_query(&s.tooling_client, s.bound_queries_only, r),
+        )
+    })
+}
+
+fn host_fn_tooling_query_more(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::TOOLING_QUERY_MORE,
+            host_functions::handle_tooling_query_more(&s.tooling_client, r),
+        )
+    })
+}
+
+fn host_fn_tooling_run_tests(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::TOOLING_RUN_TESTS,
+            host_functions::handle_tooling_run_tests(&s.tooling_client, r),
+        )
+    })
+}
+
+fn host_fn_tooling_test_run_summary(
+    plugin: &mut extism::CurrentPlugin,
+    inputs: &[extism::Val],
+    outputs: &mut [extism::Val],
+    user_data: UserData<BridgeState>,
+) -> std::result::Result<(), extism::Error> {
+    bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
+        s.block_on(
+            host_fn_names::TOOLING_TEST_RUN_SUMMARY,
+            host_functions::handle_tooling_test_run_summary(&s.tooling_client, r),
+        )
     })
 }
 
 /// Register all tooling API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
+        .with_function(
+            host_fn_names::TOOLING_ABORT_JOB,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_tooling_abort_job,
+        )
+        .with_function(
+            host_fn_names::TOOLING_ASYNC_APEX_JOBS,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_tooling_async_apex_jobs,
+        )
+        .with_function(
+            host_fn_names::TOOLING_COMPOSITE,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_tooling_composite,
+        )
         .with_function(
             host_fn_names::TOOLING_CREATE,
             [ValType::I64],
@@ -80,6 +220,20 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_tooling_create,
         )
+        .with_function(
+            host_fn_names::TOOLING_UPDATE,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_tooling_update,
+        )
+        .with_function(
+            host_fn_names::TOOLING_UPSERT,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_tooling_upsert,
+        )
         .with_function(
             host_fn_names::TOOLING_DELETE,
             [ValType::I64],
@@ -108,4 +262,25 @@ pub(super) fn register<'a>(
             user_data.clone(),
             host_fn_tooling_query,
         )
+        .with_function(
+            host_fn_names::TOOLING_QUERY_MORE,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_tooling_query_more,
+        )
+        .with_function(
+            host_fn_names::TOOLING_RUN_TESTS,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_tooling_run_tests,
+        )
+        .with_function(
+            host_fn_names::TOOLING_TEST_RUN_SUMMARY,
+            [ValType::I64],
+            [ValType::I64],
+            user_data.clone(),
+            host_fn_tooling_test_run_summary,
+        )
 }
@@ -1,5 +1,5 @@
 //! User password API host function wrappers and registration.
-use super::{bridge_host_fn, BridgeState};
+use super::{bridge_host_fn, BridgeState, Registrar};
 use crate::host_functions;
 use busbar_sf_wasm_types::host_fn_names;
 use extism::{UserData, ValType};
@@ -11,11 +11,10 @@ fn host_fn_get_user_password_status(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_get_user_password_status(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::GET_USER_PASSWORD_STATUS,
+            host_functions::handle_get_user_password_status(&s.rest_client, r),
+        )
     })
 }
 
@@ -26,11 +25,10 @@ fn host_fn_reset_user_password(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_reset_user_password(
-                &s.rest_client,
-                r,
-            ))
+        s.block_on(
+            host_fn_names::RESET_USER_PASSWORD,
+            host_functions::handle_reset_user_password(&s.rest_client, r),
+        )
     })
 }
 
@@ -41,16 +39,18 @@ fn host_fn_set_user_password(
     user_data: UserData<BridgeState>,
 ) -> std::result::Result<(), extism::Error> {
     bridge_host_fn(plugin, inputs, outputs, user_data, |s, r| {
-        s.handle
-            .block_on(host_functions::handle_set_user_password(&s.rest_client, r))
+        s.block_on(
+            host_fn_names::SET_USER_PASSWORD,
+            host_functions::handle_set_user_password(&s.rest_client, r),
+        )
     })
 }
 
 /// Register all user_password API host functions.
 pub(super) fn register<'a>(
-    builder: extism::PluginBuilder<'a>,
+    builder: Registrar<'a>,
     user_data: &UserData<BridgeState>,
-) -> extism::PluginBuilder<'a> {
+) -> Registrar<'a> {
     builder
         .with_function(
             host_fn_names::GET_USER_PASSWORD_STATUS,
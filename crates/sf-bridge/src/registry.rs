@@ -0,0 +1,156 @@
+//! A named set of plugins, for hosts that run more than one.
+
+use std::collections::BTreeMap;
+
+use crate::{Error, Result, SfBridge, UsageReport};
+
+/// The plugins a host runs, each under its own name.
+///
+/// Every plugin keeps its own [`SfBridge`] and so its own policies. The
+/// registry adds a name to call it by and a [`usage_report`] across all of
+/// them for security review.
+///
+/// [`usage_report`]: BridgeRegistry::usage_report
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bridge::{BridgeRegistry, SfBridge};
+///
+/// let registry = BridgeRegistry::new()
+///     .with_plugin("lead-scoring", SfBridge::new(scoring_wasm, client.clone())?)
+///     .with_plugin("renewals", SfBridge::new(renewals_wasm, client)?);
+///
+/// registry.call("lead-scoring", "run", input).await?;
+///
+/// for plugin in registry.usage_report().plugins {
+///     for access in plugin.objects {
+///         println!("{} -> {}: {:?}", plugin.name, access.object, access.operations);
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct BridgeRegistry {
+    plugins: BTreeMap<String, SfBridge>,
+}
+
+impl BridgeRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `bridge` under `name`, replacing any plugin already
+    /// registered under it.
+    pub fn with_plugin(mut self, name: impl Into<String>, bridge: SfBridge) -> Self {
+        self.register(name, bridge);
+        self
+    }
+
+    /// Register `bridge` under `name`, returning the plugin it replaced.
+    pub fn register(&mut self, name: impl Into<String>, bridge: SfBridge) -> Option<SfBridge> {
+        self.plugins.insert(name.into(), bridge)
+    }
+
+    /// The plugin registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&SfBridge> {
+        self.plugins.get(name)
+    }
+
+    /// Names of the registered plugins, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.plugins.keys().map(String::as_str)
+    }
+
+    /// Call `function` on the plugin registered under `plugin`; see
+    /// [`SfBridge::call`].
+    ///
+    /// Returns [`Error::Config`] if no plugin is registered under that name.
+    pub async fn call(
+        &self,
+        plugin: &str,
+        function: &str,
+        input: impl AsRef<[u8]> + Send + 'static,
+    ) -> Result<Vec<u8>> {
+        let bridge = self
+            .get(plugin)
+            .ok_or_else(|| Error::Config(format!("no plugin registered as {plugin:?}")))?;
+        bridge.call(function, input).await
+    }
+
+    /// Which objects each plugin has touched, with which host functions,
+    /// alongside the outbound hosts, secrets, and Apex REST paths its
+    /// policies allow.
+    ///
+    /// Object access is observed from every invocation of the plugin since
+    /// its bridge was created, however it was called. An object is
+    /// attributed to a call when the request names it directly or, for
+    /// SOQL, in its `FROM` clause; calls that name none (job uploads,
+    /// composite requests, deploys) are counted in
+    /// [`other_calls`](crate::PluginUsage::other_calls) so nothing a plugin
+    /// did is left out of the report.
+    pub fn usage_report(&self) -> UsageReport {
+        UsageReport {
+            plugins: self
+                .plugins
+                .iter()
+                .map(|(name, bridge)| bridge.plugin_usage(name))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rest"))]
+mod tests {
+    use super::*;
+    use crate::{HttpPolicy, Secret, SecretStore};
+    use busbar_sf_rest::SalesforceRestClient;
+
+    fn bridge() -> SfBridge {
+        let client =
+            SalesforceRestClient::new("https://example.my.salesforce.com", "token").unwrap();
+        SfBridge::new(Vec::new(), client).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_usage_report_lists_each_plugin_with_its_policies() {
+        let registry =
+            BridgeRegistry::new()
+                .with_plugin(
+                    "renewals",
+                    bridge()
+                        .with_http_policy(HttpPolicy::new().allow_host("api.example.com"))
+                        .with_secrets(SecretStore::new().with_secret(
+                            "stripe",
+                            Secret::new("sk_test").for_host("api.stripe.com"),
+                        ))
+                        .with_apex_rest_paths(["Renewals/*"]),
+                )
+                .with_plugin("lead-scoring", bridge());
+
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["lead-scoring", "renewals"]
+        );
+
+        let report = registry.usage_report();
+        assert_eq!(report.plugins.len(), 2);
+        assert_eq!(report.plugins[0].name, "lead-scoring");
+        assert!(report.plugins[0].objects.is_empty());
+        let renewals = &report.plugins[1];
+        assert_eq!(renewals.http_hosts, ["api.example.com", "api.stripe.com"]);
+        assert_eq!(renewals.secrets, ["stripe"]);
+        assert_eq!(renewals.apex_rest_paths, ["Renewals/*"]);
+        assert_eq!(report.plugins_touching("Account").count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_call_unknown_plugin_is_a_config_error() {
+        let registry = BridgeRegistry::new().with_plugin("renewals", bridge());
+        let err = registry
+            .call("missing", "run", Vec::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+    }
+}
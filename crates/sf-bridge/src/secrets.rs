@@ -0,0 +1,239 @@
+//! Host-held secrets for outbound calls to third-party APIs.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Placeholder prefix guests use to reference a secret in a header value.
+const PLACEHOLDER_OPEN: &str = "{{secret:";
+const PLACEHOLDER_CLOSE: &str = "}}";
+
+/// Replacement for secret values echoed back in a response.
+const REDACTED: &str = busbar_sf_wasm_types::TRACE_REDACTED;
+
+/// A secret value and the hosts it may be sent to.
+///
+/// The value is never shown by `Debug`.
+#[derive(Clone)]
+pub struct Secret {
+    value: String,
+    hosts: Vec<String>,
+}
+
+impl Secret {
+    /// A secret that is not yet allowed to be sent anywhere.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            hosts: Vec::new(),
+        }
+    }
+
+    /// Allow the secret to be sent to `host` (e.g. `api.clearbit.com`).
+    ///
+    /// Hosts are matched exactly and case-insensitively; subdomains are not
+    /// included.
+    pub fn for_host(mut self, host: impl Into<String>) -> Self {
+        self.hosts.push(host.into().to_ascii_lowercase());
+        self
+    }
+
+    fn allows(&self, host: &str) -> bool {
+        self.hosts.iter().any(|h| h.eq_ignore_ascii_case(host))
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Secret")
+            .field("value", &REDACTED)
+            .field("hosts", &self.hosts)
+            .finish()
+    }
+}
+
+/// Named secrets that guests can use in `sf_http_request` without seeing them.
+///
+/// Guests write `{{secret:NAME}}` in a header value and the bridge
+/// substitutes the value on the host side, after checking that the request
+/// goes to one of the secret's hosts. Secret values that show up in the
+/// response are redacted before it is handed back to the guest.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bridge::{Secret, SecretStore, SfBridge};
+///
+/// let secrets = SecretStore::new().with_secret(
+///     "clearbit",
+///     Secret::new(std::env::var("CLEARBIT_KEY")?).for_host("company.clearbit.com"),
+/// );
+/// let bridge = SfBridge::new(wasm_bytes, client)?.with_secrets(secrets);
+///
+/// // In the guest:
+/// // headers: {"Authorization": "Bearer {{secret:clearbit}}"}
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SecretStore {
+    secrets: HashMap<String, Secret>,
+}
+
+/// Why a secret reference could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SecretError {
+    /// No secret with this name is registered.
+    Unknown(String),
+    /// The secret exists but is not bound to the request's host.
+    HostNotAllowed { name: String, host: String },
+    /// A `{{secret:` placeholder without a closing `}}`.
+    Malformed,
+}
+
+impl fmt::Display for SecretError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretError::Unknown(name) => write!(f, "unknown secret '{name}'"),
+            SecretError::HostNotAllowed { name, host } => {
+                write!(f, "secret '{name}' may not be sent to {host}")
+            }
+            SecretError::Malformed => write!(f, "unterminated secret placeholder"),
+        }
+    }
+}
+
+impl SecretStore {
+    /// An empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a secret under `name`, replacing any previous one.
+    pub fn with_secret(mut self, name: impl Into<String>, secret: Secret) -> Self {
+        self.secrets.insert(name.into(), secret);
+        self
+    }
+
+    /// Whether no secrets are registered.
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    /// Names of the registered secrets, sorted.
+    pub(crate) fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.secrets.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Every host some secret is bound to, sorted and deduplicated.
+    pub(crate) fn hosts(&self) -> Vec<String> {
+        let mut hosts: Vec<String> = self
+            .secrets
+            .values()
+            .flat_map(|s| s.hosts.iter().cloned())
+            .collect();
+        hosts.sort();
+        hosts.dedup();
+        hosts
+    }
+
+    /// Whether any registered secret is bound to `host`.
+    pub(crate) fn knows_host(&self, host: &str) -> bool {
+        self.secrets.values().any(|s| s.allows(host))
+    }
+
+    /// Replace every `{{secret:NAME}}` in `value` for a request to `host`.
+    pub(crate) fn resolve(&self, value: &str, host: &str) -> Result<String, SecretError> {
+        let mut out = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find(PLACEHOLDER_OPEN) {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + PLACEHOLDER_OPEN.len()..];
+            let end = after
+                .find(PLACEHOLDER_CLOSE)
+                .ok_or(SecretError::Malformed)?;
+            let name = after[..end].trim();
+            let secret = self
+                .secrets
+                .get(name)
+                .ok_or_else(|| SecretError::Unknown(name.to_string()))?;
+            if !secret.allows(host) {
+                return Err(SecretError::HostNotAllowed {
+                    name: name.to_string(),
+                    host: host.to_string(),
+                });
+            }
+            out.push_str(&secret.value);
+            rest = &after[end + PLACEHOLDER_CLOSE.len()..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    /// Replace any secret values that appear in `text`.
+    pub(crate) fn redact(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for secret in self.secrets.values() {
+            if !secret.value.is_empty() && out.contains(&secret.value) {
+                out = out.replace(&secret.value, REDACTED);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> SecretStore {
+        SecretStore::new().with_secret(
+            "clearbit",
+            Secret::new("sk_live_123").for_host("company.clearbit.com"),
+        )
+    }
+
+    #[test]
+    fn test_resolve_placeholder() {
+        let resolved = store()
+            .resolve("Bearer {{secret:clearbit}}", "company.clearbit.com")
+            .unwrap();
+        assert_eq!(resolved, "Bearer sk_live_123");
+        assert_eq!(
+            store().resolve("no placeholders", "example.com").unwrap(),
+            "no placeholders"
+        );
+    }
+
+    #[test]
+    fn test_resolve_rejects_other_hosts() {
+        let err = store()
+            .resolve("Bearer {{secret:clearbit}}", "attacker.example.com")
+            .unwrap_err();
+        assert!(matches!(err, SecretError::HostNotAllowed { .. }));
+        assert!(!err.to_string().contains("sk_live_123"));
+    }
+
+    #[test]
+    fn test_resolve_unknown_and_malformed() {
+        assert_eq!(
+            store().resolve("{{secret:nope}}", "company.clearbit.com"),
+            Err(SecretError::Unknown("nope".to_string()))
+        );
+        assert_eq!(
+            store().resolve("{{secret:clearbit", "company.clearbit.com"),
+            Err(SecretError::Malformed)
+        );
+    }
+
+    #[test]
+    fn test_redact_and_debug() {
+        let store = store();
+        assert_eq!(
+            store.redact(r#"{"echo":"sk_live_123"}"#),
+            r#"{"echo":"[REDACTED]"}"#
+        );
+        assert!(!format!("{store:?}").contains("sk_live_123"));
+        assert!(store.knows_host("COMPANY.clearbit.com"));
+        assert!(!store.knows_host("clearbit.com"));
+    }
+}
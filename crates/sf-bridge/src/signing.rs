@@ -0,0 +1,191 @@
+//! Ed25519 signatures for WASM plugin modules.
+//!
+//! A signature covers the module bytes. It can travel separately (a
+//! detached 64-byte signature) or inside the module as a custom section
+//! named [`SIGNATURE_SECTION`]. An embedded signature covers the module
+//! with that section removed, so signing and embedding can happen after
+//! the build without invalidating the signature.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use crate::error::{Error, Result};
+
+/// Name of the custom section that holds an embedded signature.
+pub const SIGNATURE_SECTION: &str = "busbar.signature";
+
+const WASM_HEADER: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+/// Sign `wasm` and return the module with the signature embedded.
+///
+/// Any signature already embedded is replaced.
+pub fn sign_plugin(wasm: &[u8], secret_key: &[u8; 32]) -> Result<Vec<u8>> {
+    let (mut unsigned, _) = split_signature(wasm)?;
+    let signature = SigningKey::from_bytes(secret_key).sign(&unsigned);
+    let name = SIGNATURE_SECTION.as_bytes();
+    let mut payload = Vec::with_capacity(1 + name.len() + Signature::BYTE_SIZE);
+    write_leb128(&mut payload, name.len());
+    payload.extend_from_slice(name);
+    payload.extend_from_slice(&signature.to_bytes());
+    unsigned.push(0);
+    write_leb128(&mut unsigned, payload.len());
+    unsigned.extend_from_slice(&payload);
+    Ok(unsigned)
+}
+
+/// Sign `wasm` as-is and return a detached signature.
+pub fn sign_plugin_detached(wasm: &[u8], secret_key: &[u8; 32]) -> [u8; 64] {
+    SigningKey::from_bytes(secret_key).sign(wasm).to_bytes()
+}
+
+/// Check the signature embedded in `wasm` against `keys`.
+pub(crate) fn verify_embedded(wasm: &[u8], keys: &[VerifyingKey]) -> Result<()> {
+    let (unsigned, signature) = split_signature(wasm)?;
+    let signature =
+        signature.ok_or_else(|| Error::Signature("module has no embedded signature".into()))?;
+    verify(&unsigned, signature, keys)
+}
+
+/// Check a detached signature over `wasm` against `keys`.
+pub(crate) fn verify_detached(wasm: &[u8], signature: &[u8], keys: &[VerifyingKey]) -> Result<()> {
+    verify(wasm, signature, keys)
+}
+
+/// Parse raw Ed25519 public keys.
+pub(crate) fn parse_keys(keys: impl IntoIterator<Item = [u8; 32]>) -> Result<Vec<VerifyingKey>> {
+    let keys = keys
+        .into_iter()
+        .map(|key| {
+            VerifyingKey::from_bytes(&key)
+                .map_err(|_| Error::Signature("invalid Ed25519 public key".into()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if keys.is_empty() {
+        return Err(Error::Signature("no trusted public keys given".into()));
+    }
+    Ok(keys)
+}
+
+fn verify(message: &[u8], signature: &[u8], keys: &[VerifyingKey]) -> Result<()> {
+    let signature = Signature::from_slice(signature)
+        .map_err(|_| Error::Signature("signature must be 64 bytes".into()))?;
+    if keys
+        .iter()
+        .any(|key| key.verify(message, &signature).is_ok())
+    {
+        Ok(())
+    } else {
+        Err(Error::Signature(
+            "signature does not match any trusted key".into(),
+        ))
+    }
+}
+
+/// Split a module into its bytes without the signature section and the
+/// embedded signature, if any.
+fn split_signature(wasm: &[u8]) -> Result<(Vec<u8>, Option<&[u8]>)> {
+    let malformed = || Error::Signature("malformed WASM module".into());
+    if !wasm.starts_with(&WASM_HEADER) {
+        return Err(malformed());
+    }
+    let mut unsigned = WASM_HEADER.to_vec();
+    let mut signature = None;
+    let mut pos = WASM_HEADER.len();
+    while pos < wasm.len() {
+        let start = pos;
+        let id = wasm[pos];
+        let (size, len) = read_leb128(&wasm[pos + 1..]).ok_or_else(malformed)?;
+        let body_start = pos + 1 + len;
+        let end = body_start.checked_add(size).ok_or_else(malformed)?;
+        let body = wasm.get(body_start..end).ok_or_else(malformed)?;
+        pos = end;
+
+        if id == 0 {
+            let (name_len, len) = read_leb128(body).ok_or_else(malformed)?;
+            let name = body.get(len..len + name_len).ok_or_else(malformed)?;
+            if name == SIGNATURE_SECTION.as_bytes() {
+                if signature.is_some() {
+                    return Err(Error::Signature(
+                        "module has more than one signature section".into(),
+                    ));
+                }
+                signature = Some(&body[len + name_len..]);
+                continue;
+            }
+        }
+        unsigned.extend_from_slice(&wasm[start..end]);
+    }
+    Ok((unsigned, signature))
+}
+
+/// Read an unsigned LEB128 value, returning it and the bytes consumed.
+fn read_leb128(bytes: &[u8]) -> Option<(usize, usize)> {
+    let mut value: usize = 0;
+    for (i, byte) in bytes.iter().take(5).enumerate() {
+        value |= ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+fn write_leb128(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: [u8; 32] = [7; 32];
+    const OTHER_SECRET: [u8; 32] = [9; 32];
+
+    /// A minimal module: header plus an unrelated custom section.
+    fn module() -> Vec<u8> {
+        let mut wasm = WASM_HEADER.to_vec();
+        wasm.extend_from_slice(&[0, 5, 4, b'n', b'o', b't', b'e']);
+        wasm
+    }
+
+    fn public(secret: &[u8; 32]) -> Vec<VerifyingKey> {
+        vec![SigningKey::from_bytes(secret).verifying_key()]
+    }
+
+    #[test]
+    fn test_embedded_signature_round_trip() {
+        let signed = sign_plugin(&module(), &SECRET).unwrap();
+        assert!(signed.starts_with(&module()));
+        verify_embedded(&signed, &public(&SECRET)).unwrap();
+        assert!(verify_embedded(&signed, &public(&OTHER_SECRET)).is_err());
+
+        // Re-signing replaces the existing signature.
+        let resigned = sign_plugin(&signed, &OTHER_SECRET).unwrap();
+        assert_eq!(resigned.len(), signed.len());
+        verify_embedded(&resigned, &public(&OTHER_SECRET)).unwrap();
+    }
+
+    #[test]
+    fn test_embedded_signature_detects_tampering() {
+        let mut signed = sign_plugin(&module(), &SECRET).unwrap();
+        signed[12] ^= 1;
+        assert!(verify_embedded(&signed, &public(&SECRET)).is_err());
+        assert!(verify_embedded(&module(), &public(&SECRET)).is_err());
+        assert!(verify_embedded(b"not wasm", &public(&SECRET)).is_err());
+    }
+
+    #[test]
+    fn test_detached_signature() {
+        let signature = sign_plugin_detached(&module(), &SECRET);
+        verify_detached(&module(), &signature, &public(&SECRET)).unwrap();
+        assert!(verify_detached(&module(), &signature[..32], &public(&SECRET)).is_err());
+        assert!(parse_keys([]).is_err());
+    }
+}
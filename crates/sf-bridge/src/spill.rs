@@ -0,0 +1,162 @@
+//! Per-invocation cap on query results handed to the guest.
+//!
+//! Guests run in 32-bit WASM with at most 4GB of linear memory, and a guest
+//! that pages through a large query with `sf_query_more` keeps every page it
+//! was given. Once the records returned to an invocation reach the bridge's
+//! budget, further records stay on the host behind a cursor that the guest
+//! drains in bounded chunks with `sf_query_cursor_next`.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use busbar_sf_wasm_types::QueryResponse;
+
+/// Records held back from the guest, plus where to fetch more.
+#[derive(Debug, Default)]
+pub(crate) struct SpilledQuery {
+    pub(crate) records: VecDeque<serde_json::Value>,
+    pub(crate) next_records_url: Option<String>,
+}
+
+impl SpilledQuery {
+    /// Whether nothing is held and Salesforce has no more pages.
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.records.is_empty() && self.next_records_url.is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+struct SpillState {
+    delivered: usize,
+    next_id: u64,
+    cursors: HashMap<String, SpilledQuery>,
+}
+
+/// Query result accounting for one invocation.
+#[derive(Debug, Default)]
+pub(crate) struct QuerySpill {
+    budget: Option<usize>,
+    state: Mutex<SpillState>,
+}
+
+impl QuerySpill {
+    /// Track query results against `budget` bytes. `None` never spills.
+    pub(crate) fn new(budget: Option<usize>) -> Self {
+        Self {
+            budget,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Bytes of records returned before results spill, if capped.
+    pub(crate) fn budget(&self) -> Option<usize> {
+        self.budget
+    }
+
+    /// Charge a query page to the budget.
+    ///
+    /// Records that fit are returned as-is. The rest, and any later pages,
+    /// are held host-side and the response carries a cursor instead of a
+    /// `next_records_url`.
+    pub(crate) fn admit(&self, mut page: QueryResponse) -> QueryResponse {
+        let Some(budget) = self.budget else {
+            return page;
+        };
+        let mut state = self.state.lock().unwrap();
+
+        let mut kept = 0;
+        for record in &page.records {
+            let size = rmp_serde::to_vec_named(record).map_or(0, |bytes| bytes.len());
+            if state.delivered + size > budget {
+                break;
+            }
+            state.delivered += size;
+            kept += 1;
+        }
+        if kept == page.records.len() {
+            return page;
+        }
+
+        let held = SpilledQuery {
+            records: page.records.split_off(kept).into(),
+            next_records_url: if page.done {
+                None
+            } else {
+                page.next_records_url.take()
+            },
+        };
+        state.next_id += 1;
+        let cursor = format!("qc-{}", state.next_id);
+        tracing::debug!(
+            cursor = %cursor,
+            held = held.records.len(),
+            budget,
+            "Query memory budget reached, holding records host-side"
+        );
+        state.cursors.insert(cursor.clone(), held);
+
+        page.done = false;
+        page.cursor = Some(cursor);
+        page
+    }
+
+    /// Remove a cursor's held records so they can be read without holding
+    /// the lock across a Salesforce call.
+    pub(crate) fn take(&self, cursor: &str) -> Option<SpilledQuery> {
+        self.state.lock().unwrap().cursors.remove(cursor)
+    }
+
+    /// Return what is left of a cursor after [`take`](Self::take).
+    pub(crate) fn put_back(&self, cursor: String, held: SpilledQuery) {
+        self.state.lock().unwrap().cursors.insert(cursor, held);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(records: usize, next: Option<&str>) -> QueryResponse {
+        QueryResponse {
+            total_size: 10,
+            done: next.is_none(),
+            records: (0..records)
+                .map(|i| serde_json::json!({"Id": format!("001xx{i:013}")}))
+                .collect(),
+            next_records_url: next.map(str::to_string),
+            cursor: None,
+        }
+    }
+
+    #[test]
+    fn test_unbudgeted_pages_pass_through() {
+        let spill = QuerySpill::new(None);
+        let out = spill.admit(page(5, Some("/next")));
+        assert_eq!(out.records.len(), 5);
+        assert!(out.cursor.is_none());
+        assert_eq!(out.next_records_url.as_deref(), Some("/next"));
+    }
+
+    #[test]
+    fn test_over_budget_records_are_held() {
+        let record_size = rmp_serde::to_vec_named(&page(1, None).records[0])
+            .unwrap()
+            .len();
+        let spill = QuerySpill::new(Some(record_size * 3));
+
+        let first = spill.admit(page(2, Some("/p2")));
+        assert_eq!(first.records.len(), 2);
+        assert!(first.cursor.is_none());
+
+        let second = spill.admit(page(4, Some("/p3")));
+        assert_eq!(second.records.len(), 1);
+        assert!(!second.done);
+        assert!(second.next_records_url.is_none());
+
+        let cursor = second.cursor.unwrap();
+        let held = spill.take(&cursor).unwrap();
+        assert_eq!(held.records.len(), 3);
+        assert_eq!(held.next_records_url.as_deref(), Some("/p3"));
+        assert!(spill.take(&cursor).is_none());
+    }
+}
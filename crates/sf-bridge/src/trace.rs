@@ -0,0 +1,144 @@
+//! Recording of host function calls for [`SfBridge::call_with_trace`](crate::SfBridge::call_with_trace).
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use busbar_sf_wasm_types::{HostCallRecord, HostCallTrace, TRACE_REDACTED};
+use serde_json::Value;
+
+use crate::policy::TracePolicy;
+use crate::secrets::SecretStore;
+
+/// Collects the host calls of one invocation, redacted as they are
+/// recorded so unredacted payloads are never held longer than the call.
+pub(crate) struct TraceRecorder {
+    policy: Arc<TracePolicy>,
+    secrets: Arc<SecretStore>,
+    calls: Mutex<Vec<HostCallRecord>>,
+}
+
+impl TraceRecorder {
+    pub(crate) fn new(policy: Arc<TracePolicy>, secrets: Arc<SecretStore>) -> Self {
+        Self {
+            policy,
+            secrets,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Record one call from the raw MessagePack the guest sent and received.
+    pub(crate) fn record(
+        &self,
+        host_function: &str,
+        request: Option<&[u8]>,
+        response: Result<&[u8], String>,
+        duration: Duration,
+    ) {
+        let (response, trap) = match response {
+            Ok(bytes) => (self.decode(Some(bytes)), None),
+            Err(trap) => (Value::Null, Some(self.secrets.redact(&trap))),
+        };
+        let record = HostCallRecord {
+            host_function: host_function.to_string(),
+            request: self.decode(request),
+            response,
+            trap,
+            duration_ms: duration.as_millis().try_into().unwrap_or(u64::MAX),
+        };
+        self.calls.lock().unwrap().push(record);
+    }
+
+    /// The calls recorded so far, as a trace of the guest export `function`.
+    pub(crate) fn finish(&self, function: &str, invocation_id: &str) -> HostCallTrace {
+        let mut trace = HostCallTrace::new(function, invocation_id);
+        trace.calls = std::mem::take(&mut *self.calls.lock().unwrap());
+        trace
+    }
+
+    fn decode(&self, bytes: Option<&[u8]>) -> Value {
+        let Some(bytes) = bytes.filter(|b| !b.is_empty()) else {
+            return Value::Null;
+        };
+        let mut value = rmp_serde::from_slice(bytes).unwrap_or_else(|e| {
+            Value::String(format!(
+                "<undecodable payload of {} bytes: {e}>",
+                bytes.len()
+            ))
+        });
+        self.redact(&mut value);
+        value
+    }
+
+    fn redact(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.secrets.redact(s),
+            Value::Array(items) => items.iter_mut().for_each(|v| self.redact(v)),
+            Value::Object(map) => {
+                for (key, v) in map.iter_mut() {
+                    if self.policy.redacts_field(key) && !v.is_null() {
+                        *v = Value::String(TRACE_REDACTED.to_string());
+                    } else {
+                        self.redact(v);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::Secret;
+    use serde_json::json;
+
+    #[test]
+    fn test_recorder_redacts_secrets_and_fields() {
+        let recorder = TraceRecorder::new(
+            Arc::new(TracePolicy::new().redact_field("email")),
+            Arc::new(
+                SecretStore::new().with_secret("api", Secret::new("sk_live_123").for_host("x.io")),
+            ),
+        );
+        let request = rmp_serde::to_vec_named(&json!({
+            "sobject": "Contact",
+            "record": {"LastName": "Smith", "Email": "jo@example.com", "Phone": null}
+        }))
+        .unwrap();
+        let response =
+            rmp_serde::to_vec_named(&json!({"ok": {"body": "echo sk_live_123"}})).unwrap();
+        recorder.record(
+            "sf_create",
+            Some(&request),
+            Ok(&response),
+            Duration::from_millis(40),
+        );
+        recorder.record(
+            "sf_get",
+            None,
+            Err("guest trap near sk_live_123".to_string()),
+            Duration::ZERO,
+        );
+
+        let trace = recorder.finish("run", "inv-1");
+        assert_eq!(trace.function, "run");
+        assert_eq!(
+            trace.calls[0].request,
+            json!({
+                "sobject": "Contact",
+                "record": {"LastName": "Smith", "Email": "[REDACTED]", "Phone": null}
+            })
+        );
+        assert_eq!(
+            trace.calls[0].response,
+            json!({"ok": {"body": "echo [REDACTED]"}})
+        );
+        assert_eq!(trace.calls[0].duration_ms, 40);
+        assert!(trace.calls[1].request.is_null());
+        assert_eq!(
+            trace.calls[1].trap.as_deref(),
+            Some("guest trap near [REDACTED]")
+        );
+    }
+}
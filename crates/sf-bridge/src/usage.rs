@@ -0,0 +1,90 @@
+//! Per-invocation accounting of Salesforce resources consumed by a guest.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "rest")]
+use std::sync::Arc;
+
+#[cfg(feature = "rest")]
+use busbar_sf_client::UsageTracker;
+use serde::Serialize;
+
+/// Salesforce resources consumed by a single guest invocation.
+///
+/// Returned by [`SfBridge::call_with_usage`](crate::SfBridge::call_with_usage)
+/// for chargeback and budget enforcement.
+///
+/// REST, Bulk, and Tooling traffic is metered at the HTTP layer, so
+/// paginated queries and retried requests are counted per request. The
+/// Metadata API client does not share that HTTP layer; each metadata host
+/// function is counted as one API call and its bytes are not included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct InvocationUsage {
+    /// Host functions the guest called.
+    pub host_calls: u64,
+    /// HTTP requests sent to Salesforce.
+    pub api_calls: u64,
+    /// Bulk API jobs created.
+    pub bulk_jobs: u64,
+    /// Request body bytes sent to Salesforce.
+    pub bytes_sent: u64,
+    /// Response body bytes received from Salesforce.
+    pub bytes_received: u64,
+}
+
+/// Counters shared by every host function of one plugin instance.
+#[derive(Debug, Default)]
+pub(crate) struct UsageCounters {
+    #[cfg(feature = "rest")]
+    http: Arc<UsageTracker>,
+    host_calls: AtomicU64,
+    bulk_jobs: AtomicU64,
+    soap_calls: AtomicU64,
+}
+
+impl UsageCounters {
+    /// The HTTP tracker to attach to the invocation's Salesforce clients.
+    #[cfg(feature = "rest")]
+    pub(crate) fn http_tracker(&self) -> Arc<UsageTracker> {
+        Arc::clone(&self.http)
+    }
+
+    pub(crate) fn record_host_call(&self) {
+        self.host_calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg_attr(not(feature = "bulk"), allow(dead_code))]
+    pub(crate) fn record_bulk_job(&self) {
+        self.bulk_jobs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg_attr(not(feature = "metadata"), allow(dead_code))]
+    pub(crate) fn record_soap_call(&self) {
+        self.soap_calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn report(&self) -> InvocationUsage {
+        let (requests, bytes_sent, bytes_received) = self.http_totals();
+        InvocationUsage {
+            host_calls: self.host_calls.load(Ordering::Relaxed),
+            api_calls: requests + self.soap_calls.load(Ordering::Relaxed),
+            bulk_jobs: self.bulk_jobs.load(Ordering::Relaxed),
+            bytes_sent,
+            bytes_received,
+        }
+    }
+
+    #[cfg(feature = "rest")]
+    fn http_totals(&self) -> (u64, u64, u64) {
+        let snapshot = self.http.snapshot();
+        (
+            snapshot.requests,
+            snapshot.bytes_sent,
+            snapshot.bytes_received,
+        )
+    }
+
+    #[cfg(not(feature = "rest"))]
+    fn http_totals(&self) -> (u64, u64, u64) {
+        (0, 0, 0)
+    }
+}
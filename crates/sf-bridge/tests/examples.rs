@@ -0,0 +1,395 @@
+//! Host harnesses for the example plugins in `examples/`.
+//!
+//! Each test loads a compiled example and runs it against a mock org
+//! served by wiremock, so unlike `integration.rs` these need no
+//! credentials. They cover the bridge features the examples lean on:
+//! query memory budgets and cursors (dedupe-plugin), invocation payloads,
+//! the outbound HTTP policy and secrets (cdc-webhook-forwarder), and
+//! metadata, tooling, and capability discovery (deploy-gatekeeper).
+//!
+//! ## Building the Examples
+//!
+//! ```sh
+//! for example in dedupe-plugin cdc-webhook-forwarder deploy-gatekeeper; do
+//!     cargo build --manifest-path examples/$example/Cargo.toml \
+//!         --target wasm32-unknown-unknown --release
+//! done
+//! ```
+//!
+//! Tests for an example that hasn't been built print a warning and pass.
+
+use busbar_sf_bridge::{HttpPolicy, InvocationContext, Secret, SecretStore, SfBridge};
+use busbar_sf_rest::SalesforceRestClient;
+use wiremock::matchers::{method, path, path_regex, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Load a compiled example plugin, or `None` if it hasn't been built.
+fn load_example_wasm(example: &str) -> Option<Vec<u8>> {
+    let wasm_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!(
+        "../../examples/{example}/target/wasm32-unknown-unknown/release/{}.wasm",
+        example.replace('-', "_")
+    ));
+
+    if !wasm_path.exists() {
+        eprintln!(
+            "⚠️  Example plugin not found at: {}\n\
+             Build it with: cargo build --manifest-path examples/{example}/Cargo.toml \
+             --target wasm32-unknown-unknown --release",
+            wasm_path.display()
+        );
+        return None;
+    }
+
+    match std::fs::read(&wasm_path) {
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            eprintln!("⚠️  Failed to read example plugin: {}", e);
+            None
+        }
+    }
+}
+
+/// Create a bridge for `example` whose REST client points at `server`.
+fn example_bridge(example: &str, server: &MockServer) -> Option<SfBridge> {
+    let wasm_bytes = load_example_wasm(example)?;
+    let client =
+        SalesforceRestClient::new(server.uri(), "token").expect("Failed to create REST client");
+    Some(SfBridge::new(wasm_bytes, client).expect("Failed to create bridge"))
+}
+
+/// Call `function` with `context` and parse its JSON output.
+async fn call_example(
+    bridge: &SfBridge,
+    function: &str,
+    input: serde_json::Value,
+    context: InvocationContext,
+) -> serde_json::Value {
+    let input_bytes = serde_json::to_vec(&input).expect("Failed to serialize input");
+    let (result, _) = bridge
+        .call_with_context(function, input_bytes, context)
+        .await;
+    let output = result.unwrap_or_else(|e| panic!("Failed to call {function}: {e}"));
+    serde_json::from_slice(&output).expect("Failed to parse output")
+}
+
+// =============================================================================
+// dedupe-plugin
+// =============================================================================
+
+#[tokio::test]
+async fn test_dedupe_streams_past_query_budget() {
+    let server = MockServer::start().await;
+    let Some(bridge) = example_bridge("dedupe-plugin", &server) else {
+        return;
+    };
+
+    let contacts = [
+        ("003000000000001AAA", "ada@example.com"),
+        ("003000000000002AAA", "ADA@example.com "),
+        ("003000000000003AAA", "grace@example.com"),
+        ("003000000000004AAA", "linus@example.com"),
+        ("003000000000005AAA", "Linus@Example.com"),
+        ("003000000000006AAA", "linus@example.com"),
+    ];
+    Mock::given(method("GET"))
+        .and(path("/services/data/v62.0/query"))
+        .and(query_param(
+            "q",
+            "SELECT Id, Email FROM Contact WHERE Email != null ORDER BY Email, CreatedDate, Id",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "totalSize": contacts.len(),
+            "done": true,
+            "records": contacts
+                .iter()
+                .map(|(id, email)| serde_json::json!({"Id": id, "Email": email}))
+                .collect::<Vec<_>>()
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Room for about two records; the rest must come through the cursor.
+    let bridge = bridge.with_query_memory_budget(100);
+    let output = call_example(
+        &bridge,
+        "find_duplicates",
+        serde_json::json!({"sobject": "Contact", "match_field": "Email"}),
+        InvocationContext::new("dedupe-1"),
+    )
+    .await;
+
+    assert_eq!(output["scanned"], 6);
+    assert_eq!(output["streamed"], true);
+    assert_eq!(
+        output["groups"],
+        serde_json::json!([
+            {
+                "key": "ada@example.com",
+                "keep": "003000000000001AAA",
+                "duplicates": ["003000000000002AAA"]
+            },
+            {
+                "key": "linus@example.com",
+                "keep": "003000000000004AAA",
+                "duplicates": ["003000000000005AAA", "003000000000006AAA"]
+            }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_dedupe_rejects_injected_field() {
+    let server = MockServer::start().await;
+    let Some(bridge) = example_bridge("dedupe-plugin", &server) else {
+        return;
+    };
+
+    let input = serde_json::json!({"sobject": "Contact", "match_field": "Email FROM User --"});
+    let result = bridge
+        .call("find_duplicates", serde_json::to_vec(&input).unwrap())
+        .await;
+
+    assert!(result.is_err(), "Unsafe field names should be refused");
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+// =============================================================================
+// cdc-webhook-forwarder
+// =============================================================================
+
+/// An Account update as delivered on a change event channel.
+fn account_change_event() -> serde_json::Value {
+    serde_json::json!({
+        "ChangeEventHeader": {
+            "entityName": "Account",
+            "changeType": "UPDATE",
+            "recordIds": ["001000000000001AAA"],
+            "changedFields": ["Name", "LastModifiedDate"],
+            "commitTimestamp": 1760600000000_i64,
+            "transactionKey": "0002f3a4-0b1c-4d5e-8f90-aabbccddeeff"
+        },
+        "Name": "Acme (renamed)",
+        "LastModifiedDate": "2025-10-16T07:33:20.000Z",
+        "Industry": null
+    })
+}
+
+#[tokio::test]
+async fn test_cdc_forwarder_refuses_unlisted_webhook() {
+    let server = MockServer::start().await;
+    let Some(bridge) = example_bridge("cdc-webhook-forwarder", &server) else {
+        return;
+    };
+
+    let context = InvocationContext::new("cdc-1").with_payload(account_change_event());
+    let output = call_example(
+        &bridge,
+        "forward_change",
+        serde_json::json!({"webhook_url": "https://hooks.example.com/salesforce"}),
+        context,
+    )
+    .await;
+
+    assert_eq!(output["forwarded"], false);
+    assert_eq!(output["error"], "HOST_NOT_ALLOWED");
+}
+
+#[tokio::test]
+async fn test_cdc_forwarder_enforces_request_limit() {
+    let server = MockServer::start().await;
+    let Some(bridge) = example_bridge("cdc-webhook-forwarder", &server) else {
+        return;
+    };
+
+    // The secret's binding allows the host; the policy caps the body.
+    let bridge = bridge
+        .with_secrets(SecretStore::new().with_secret(
+            "webhook_token",
+            Secret::new("whk_live_123").for_host("hooks.example.com"),
+        ))
+        .with_http_policy(HttpPolicy::new().with_max_request_bytes(32));
+    let context = InvocationContext::new("cdc-2").with_payload(account_change_event());
+    let output = call_example(
+        &bridge,
+        "forward_change",
+        serde_json::json!({
+            "webhook_url": "https://hooks.example.com/salesforce",
+            "secret": "webhook_token"
+        }),
+        context,
+    )
+    .await;
+
+    assert_eq!(output["forwarded"], false);
+    assert_eq!(output["error"], "REQUEST_TOO_LARGE");
+}
+
+#[tokio::test]
+async fn test_cdc_forwarder_skips_unwatched_objects() {
+    let server = MockServer::start().await;
+    let Some(bridge) = example_bridge("cdc-webhook-forwarder", &server) else {
+        return;
+    };
+
+    let context = InvocationContext::new("cdc-3").with_payload(account_change_event());
+    let output = call_example(
+        &bridge,
+        "forward_change",
+        serde_json::json!({
+            "webhook_url": "https://hooks.example.com/salesforce",
+            "objects": ["Opportunity"]
+        }),
+        context,
+    )
+    .await;
+
+    assert_eq!(output["forwarded"], false);
+    assert_eq!(output["skipped"], "Account is not watched");
+    assert!(output.get("error").is_none());
+}
+
+// =============================================================================
+// deploy-gatekeeper
+// =============================================================================
+
+/// Mount a `checkDeployStatus` response with the given result fields.
+async fn mock_deploy_status(server: &MockServer, result: &str) {
+    let body = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<soapenv:Envelope xmlns:soapenv="http://schemas.xmlsoap.org/soap/envelope/" xmlns="http://soap.sforce.com/2006/04/metadata">
+  <soapenv:Body>
+    <checkDeployStatusResponse>
+      <result>
+        <id>0Af000000000001AAA</id>
+        {result}
+      </result>
+    </checkDeployStatusResponse>
+  </soapenv:Body>
+</soapenv:Envelope>"#
+    );
+    Mock::given(method("POST"))
+        .and(path("/services/Soap/m/62.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/xml"))
+        .mount(server)
+        .await;
+}
+
+/// Mount the org-wide coverage Tooling query.
+async fn mock_org_coverage(server: &MockServer, percent: u32) {
+    Mock::given(method("GET"))
+        .and(path_regex(".*/tooling/query/?"))
+        .and(query_param(
+            "q",
+            "SELECT PercentCovered FROM ApexOrgWideCoverage",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "totalSize": 1,
+            "done": true,
+            "records": [{"PercentCovered": percent}]
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_deploy_gatekeeper_approves_clean_validation() {
+    let server = MockServer::start().await;
+    let Some(bridge) = example_bridge("deploy-gatekeeper", &server) else {
+        return;
+    };
+    mock_deploy_status(
+        &server,
+        "<done>true</done>
+        <status>Succeeded</status>
+        <success>true</success>
+        <numberComponentsDeployed>12</numberComponentsDeployed>
+        <numberComponentErrors>0</numberComponentErrors>
+        <numberComponentsTotal>12</numberComponentsTotal>
+        <numberTestsCompleted>40</numberTestsCompleted>
+        <numberTestErrors>0</numberTestErrors>
+        <numberTestsTotal>40</numberTestsTotal>",
+    )
+    .await;
+    mock_org_coverage(&server, 82).await;
+
+    let output = call_example(
+        &bridge,
+        "gate_deploy",
+        serde_json::json!({"async_process_id": "0Af000000000001AAA", "min_coverage": 75.0}),
+        InvocationContext::new("gate-1"),
+    )
+    .await;
+
+    assert_eq!(output["verdict"], "approve");
+    assert_eq!(output["deploy_status"], "Succeeded");
+    assert_eq!(output["org_coverage"], 82.0);
+    assert_eq!(output["reasons"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn test_deploy_gatekeeper_rejects_failed_tests_and_low_coverage() {
+    let server = MockServer::start().await;
+    let Some(bridge) = example_bridge("deploy-gatekeeper", &server) else {
+        return;
+    };
+    mock_deploy_status(
+        &server,
+        "<done>true</done>
+        <status>Failed</status>
+        <success>false</success>
+        <errorMessage>Apex tests failed</errorMessage>
+        <numberComponentsDeployed>12</numberComponentsDeployed>
+        <numberComponentErrors>0</numberComponentErrors>
+        <numberComponentsTotal>12</numberComponentsTotal>
+        <numberTestsCompleted>38</numberTestsCompleted>
+        <numberTestErrors>2</numberTestErrors>
+        <numberTestsTotal>40</numberTestsTotal>",
+    )
+    .await;
+    mock_org_coverage(&server, 71).await;
+
+    let output = call_example(
+        &bridge,
+        "gate_deploy",
+        serde_json::json!({"async_process_id": "0Af000000000001AAA", "min_coverage": 75.0}),
+        InvocationContext::new("gate-2"),
+    )
+    .await;
+
+    assert_eq!(output["verdict"], "reject");
+    assert_eq!(
+        output["reasons"],
+        serde_json::json!([
+            "Apex tests failed",
+            "2 of 40 tests failed",
+            "org coverage 71% is below 75%"
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_deploy_gatekeeper_waits_for_running_deploy() {
+    let server = MockServer::start().await;
+    let Some(bridge) = example_bridge("deploy-gatekeeper", &server) else {
+        return;
+    };
+    mock_deploy_status(
+        &server,
+        "<done>false</done>
+        <status>InProgress</status>
+        <success>false</success>",
+    )
+    .await;
+
+    let output = call_example(
+        &bridge,
+        "gate_deploy",
+        serde_json::json!({"async_process_id": "0Af000000000001AAA", "min_coverage": 75.0}),
+        InvocationContext::new("gate-3"),
+    )
+    .await;
+
+    assert_eq!(output["verdict"], "pending");
+    assert_eq!(output["deploy_status"], "InProgress");
+}
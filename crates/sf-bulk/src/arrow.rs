@@ -0,0 +1,313 @@
+//! Arrow record batches and Parquet files from bulk query results.
+//!
+//! Bulk CSVs carry no types, so column types come from the queried
+//! object's describe: `int` becomes `Int64`, `currency` becomes `Float64`,
+//! `datetime` becomes a UTC millisecond timestamp, and so on. Columns the
+//! describe doesn't cover (relationship paths, aggregate aliases), and
+//! every column when no describe is given, are strings. Empty cells and
+//! values that don't parse as the column's type are null.
+
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Date32Array, Float64Array, Int64Array, RecordBatch, StringArray,
+    Time32MillisecondArray, TimestampMillisecondArray,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+use busbar_sf_rest::DescribeSObjectResult;
+use csv::StringRecord;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::results::QueryResultRow;
+
+/// Rows per record batch when writing Parquet, and a reasonable default
+/// for [`get_query_results_arrow`](crate::BulkApiClient::get_query_results_arrow).
+pub const DEFAULT_ARROW_BATCH_ROWS: usize = 8192;
+
+/// The Arrow schema for query results with these columns.
+///
+/// Every field is nullable.
+pub fn query_results_schema(
+    columns: &StringRecord,
+    describe: Option<&DescribeSObjectResult>,
+) -> Schema {
+    let fields: Vec<Field> = columns
+        .iter()
+        .map(|name| {
+            let data_type = describe
+                .and_then(|d| d.fields.iter().find(|f| f.name == name))
+                .map_or(DataType::Utf8, |f| data_type(&f.field_type));
+            Field::new(name, data_type, true)
+        })
+        .collect();
+    Schema::new(fields)
+}
+
+/// Convert `rows` into a record batch with `schema`.
+///
+/// Columns are matched to the schema's fields by name; a field the rows
+/// don't have is all null.
+pub fn rows_to_record_batch(schema: SchemaRef, rows: &[QueryResultRow]) -> Result<RecordBatch> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            // Every row of a result set shares the same header.
+            let index = rows
+                .first()
+                .and_then(|row| row.columns().iter().position(|c| c == field.name()));
+            let values = rows.iter().map(|row| {
+                index
+                    .and_then(|i| row.values().get(i))
+                    .filter(|value| !value.is_empty())
+            });
+            build_column(field.data_type(), values)
+        })
+        .collect();
+    RecordBatch::try_new(schema, columns).map_err(arrow_error)
+}
+
+/// The Arrow type for a describe field type.
+fn data_type(field_type: &str) -> DataType {
+    match field_type {
+        "boolean" => DataType::Boolean,
+        "int" => DataType::Int64,
+        "double" | "currency" | "percent" => DataType::Float64,
+        "date" => DataType::Date32,
+        "datetime" => DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+        "time" => DataType::Time32(TimeUnit::Millisecond),
+        _ => DataType::Utf8,
+    }
+}
+
+fn build_column<'a>(
+    data_type: &DataType,
+    values: impl Iterator<Item = Option<&'a str>>,
+) -> ArrayRef {
+    match data_type {
+        DataType::Boolean => Arc::new(
+            values
+                .map(|v| match v? {
+                    v if v.eq_ignore_ascii_case("true") => Some(true),
+                    v if v.eq_ignore_ascii_case("false") => Some(false),
+                    _ => None,
+                })
+                .collect::<BooleanArray>(),
+        ),
+        DataType::Int64 => Arc::new(
+            values
+                .map(|v| v?.parse::<i64>().ok())
+                .collect::<Int64Array>(),
+        ),
+        DataType::Float64 => Arc::new(
+            values
+                .map(|v| v?.parse::<f64>().ok())
+                .collect::<Float64Array>(),
+        ),
+        DataType::Date32 => {
+            let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+            Arc::new(
+                values
+                    .map(|v| {
+                        let date = chrono::NaiveDate::parse_from_str(v?, "%Y-%m-%d").ok()?;
+                        i32::try_from((date - epoch).num_days()).ok()
+                    })
+                    .collect::<Date32Array>(),
+            )
+        }
+        DataType::Timestamp(_, _) => Arc::new(
+            values
+                .map(|v| parse_datetime_millis(v?))
+                .collect::<TimestampMillisecondArray>()
+                .with_timezone("UTC"),
+        ),
+        DataType::Time32(_) => Arc::new(
+            values
+                .map(|v| {
+                    let v = v?;
+                    let time = chrono::NaiveTime::parse_from_str(
+                        v.strip_suffix('Z').unwrap_or(v),
+                        "%H:%M:%S%.f",
+                    )
+                    .ok()?;
+                    i32::try_from((time - chrono::NaiveTime::MIN).num_milliseconds()).ok()
+                })
+                .collect::<Time32MillisecondArray>(),
+        ),
+        _ => Arc::new(values.collect::<StringArray>()),
+    }
+}
+
+/// Bulk API CSVs use `2024-01-04T10:00:00.000Z`; the REST API's
+/// `2024-01-04T10:00:00.000+0000` is accepted too.
+fn parse_datetime_millis(s: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .or_else(|_| chrono::DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+/// Write `batches` to `writer` as a Parquet file, returning the rows
+/// written. With no batches the file has no columns.
+#[cfg(feature = "parquet")]
+pub(crate) async fn write_parquet<W>(
+    batches: impl futures::Stream<Item = Result<RecordBatch>>,
+    writer: W,
+) -> Result<usize>
+where
+    W: std::io::Write + Send,
+{
+    use futures::TryStreamExt;
+    use parquet::arrow::ArrowWriter;
+
+    futures::pin_mut!(batches);
+    let mut writer = Some(writer);
+    let mut parquet: Option<ArrowWriter<W>> = None;
+    let mut rows = 0;
+    while let Some(batch) = batches.try_next().await? {
+        let parquet = match &mut parquet {
+            Some(parquet) => parquet,
+            None => parquet.insert(
+                ArrowWriter::try_new(writer.take().expect("writer"), batch.schema(), None)
+                    .map_err(parquet_error)?,
+            ),
+        };
+        rows += batch.num_rows();
+        parquet.write(&batch).map_err(parquet_error)?;
+    }
+    let parquet = match parquet {
+        Some(parquet) => parquet,
+        None => ArrowWriter::try_new(
+            writer.take().expect("writer"),
+            Arc::new(Schema::empty()),
+            None,
+        )
+        .map_err(parquet_error)?,
+    };
+    parquet.close().map_err(parquet_error)?;
+    Ok(rows)
+}
+
+fn arrow_error(e: ArrowError) -> Error {
+    Error {
+        kind: ErrorKind::Other(format!("Arrow conversion failed: {}", e)),
+        source: Some(Box::new(e)),
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_error(e: parquet::errors::ParquetError) -> Error {
+    Error {
+        kind: ErrorKind::Other(format!("Parquet write failed: {}", e)),
+        source: Some(Box::new(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::RowDecoder;
+    use arrow_array::Array;
+
+    fn describe() -> DescribeSObjectResult {
+        serde_json::from_value(serde_json::json!({
+            "name": "Opportunity",
+            "label": "Opportunity",
+            "custom": false,
+            "fields": [
+                {"name": "Id", "label": "Id", "type": "id"},
+                {"name": "Amount", "label": "Amount", "type": "currency"},
+                {"name": "IsWon", "label": "Won", "type": "boolean"},
+                {"name": "CloseDate", "label": "Close Date", "type": "date"},
+                {"name": "LastModifiedDate", "label": "Modified", "type": "datetime"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn rows() -> Vec<QueryResultRow> {
+        let mut decoder = RowDecoder::default();
+        decoder.start_page();
+        decoder
+            .push(
+                b"Id,Amount,IsWon,CloseDate,LastModifiedDate,Account.Name\n\
+                  006A,1250.5,true,1970-01-03,1970-01-01T00:00:01.500Z,Acme\n\
+                  006B,,false,,,\n",
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rows_to_record_batch_types_columns_from_describe() {
+        let rows = rows();
+        let schema = Arc::new(query_results_schema(rows[0].columns(), Some(&describe())));
+        let batch = rows_to_record_batch(schema, &rows).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let schema = batch.schema();
+        let types: Vec<_> = schema.fields().iter().map(|f| f.data_type()).collect();
+        assert_eq!(types[0], &DataType::Utf8);
+        assert_eq!(types[1], &DataType::Float64);
+        assert_eq!(types[5], &DataType::Utf8);
+
+        let amount = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(amount.value(0), 1250.5);
+        assert!(amount.is_null(1));
+        let close = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap();
+        assert_eq!(close.value(0), 2);
+        let modified = batch
+            .column(4)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(modified.value(0), 1500);
+        assert_eq!(batch.column(5).null_count(), 1);
+    }
+
+    #[test]
+    fn test_schema_without_describe_is_all_strings() {
+        let rows = rows();
+        let schema = query_results_schema(rows[0].columns(), None);
+        assert!(schema
+            .fields()
+            .iter()
+            .all(|f| f.data_type() == &DataType::Utf8));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[tokio::test]
+    async fn test_write_parquet() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let rows = rows();
+        let schema = Arc::new(query_results_schema(rows[0].columns(), Some(&describe())));
+        let batch = rows_to_record_batch(schema, &rows).unwrap();
+
+        let mut file = Vec::new();
+        let written = write_parquet(futures::stream::iter([Ok(batch)]), &mut file)
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+
+        let reader = SerializedFileReader::new(bytes::Bytes::from(file)).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 2);
+        assert_eq!(metadata.schema_descr().num_columns(), 6);
+
+        let mut file = Vec::new();
+        let written = write_parquet(futures::stream::empty(), &mut file)
+            .await
+            .unwrap();
+        assert_eq!(written, 0);
+        let reader = SerializedFileReader::new(bytes::Bytes::from(file)).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 0);
+    }
+}
@@ -0,0 +1,297 @@
+//! External ID backfill.
+//!
+//! Upserts, sync, and parent lookups all key off an external ID field, so
+//! records created before that field existed need values assigned first.
+//! [`ExternalIdBackfill`] describes which records to fill and how to derive
+//! their values; [`BulkApiClient::backfill_external_ids`] runs it.
+//!
+//! Generated values are deterministic: the same record always gets the same
+//! value, so a backfill can be re-run (or run in parallel against a sandbox
+//! and production) without producing conflicting IDs.
+//!
+//! [`BulkApiClient::backfill_external_ids`]: crate::BulkApiClient::backfill_external_ids
+
+use busbar_sf_client::security::soql;
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::IngestJobResult;
+
+/// Separates hashed field values so `("ab", "c")` and `("a", "bc")` differ.
+const FIELD_SEPARATOR: u8 = 0x1f;
+
+/// How external ID values are derived from a record's source fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExternalIdStrategy {
+    /// Name-based UUID (RFC 9562 version 8, SHA-256), e.g.
+    /// `3f1c2a9e-7b4d-8e21-a0f3-5c6d7e8f9a0b`. Fits a 36-character text field.
+    #[default]
+    Uuid,
+    /// Lowercase hex SHA-256, truncated to `len` characters (at most 64).
+    Hash { len: usize },
+}
+
+/// Configuration for backfilling an external ID field.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bulk::{ExternalIdBackfill, ExternalIdStrategy};
+///
+/// let backfill = ExternalIdBackfill::new("Contact", "Legacy_Key__c")?
+///     .with_source_fields(&["Email", "Account.Name"])?
+///     .with_strategy(ExternalIdStrategy::Hash { len: 32 });
+/// let result = client.backfill_external_ids(&backfill).await?;
+/// println!("Backfilled {} contacts", result.records_found);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExternalIdBackfill {
+    pub(crate) sobject: String,
+    pub(crate) external_id_field: String,
+    pub(crate) source_fields: Vec<String>,
+    pub(crate) strategy: ExternalIdStrategy,
+}
+
+impl ExternalIdBackfill {
+    /// Backfill `external_id_field` on `sobject` records where it is null.
+    ///
+    /// Values are derived from the record `Id` unless
+    /// [`with_source_fields`](Self::with_source_fields) is used. IDs are
+    /// unique but differ between orgs; use natural-key fields when the same
+    /// record in two orgs should get the same external ID.
+    pub fn new(sobject: impl Into<String>, external_id_field: impl Into<String>) -> Result<Self> {
+        let sobject = sobject.into();
+        let external_id_field = external_id_field.into();
+        if !soql::is_safe_sobject_name(&sobject) {
+            return Err(Error::new(ErrorKind::Other(format!(
+                "Invalid SObject name: {}",
+                sobject
+            ))));
+        }
+        validate_field(&external_id_field)?;
+        Ok(Self {
+            sobject,
+            external_id_field,
+            source_fields: vec!["Id".to_string()],
+            strategy: ExternalIdStrategy::default(),
+        })
+    }
+
+    /// Derive values from these fields instead of the record `Id`.
+    ///
+    /// Relationship fields (`Account.Name`) are allowed. Field order matters.
+    pub fn with_source_fields(mut self, fields: &[&str]) -> Result<Self> {
+        if fields.is_empty() {
+            return Err(Error::new(ErrorKind::Other(
+                "At least one source field is required".to_string(),
+            )));
+        }
+        for field in fields {
+            validate_field(field)?;
+        }
+        self.source_fields = fields.iter().map(|f| f.to_string()).collect();
+        Ok(self)
+    }
+
+    /// Set how values are derived.
+    pub fn with_strategy(mut self, strategy: ExternalIdStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Generate the external ID for one record's source field values, in
+    /// the same order as the source fields.
+    pub fn generate(&self, values: &[&str]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.sobject.as_bytes());
+        for value in values {
+            hasher.update([FIELD_SEPARATOR]);
+            hasher.update(value.as_bytes());
+        }
+        let digest = hasher.finalize();
+
+        match self.strategy {
+            ExternalIdStrategy::Uuid => {
+                let mut b = [0u8; 16];
+                b.copy_from_slice(&digest[..16]);
+                b[6] = (b[6] & 0x0f) | 0x80; // version 8
+                b[8] = (b[8] & 0x3f) | 0x80; // RFC 9562 variant
+                let hex = to_hex(&b);
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[0..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..32]
+                )
+            }
+            ExternalIdStrategy::Hash { len } => {
+                let mut hex = to_hex(&digest);
+                hex.truncate(len);
+                hex
+            }
+        }
+    }
+
+    /// Fields to select when querying records that are missing an external ID.
+    #[cfg_attr(not(feature = "query-builder"), allow(dead_code))]
+    pub(crate) fn soql_fields(&self) -> Vec<String> {
+        let mut fields = vec!["Id".to_string()];
+        fields.extend(
+            self.source_fields
+                .iter()
+                .filter(|f| !f.eq_ignore_ascii_case("Id"))
+                .cloned(),
+        );
+        fields
+    }
+
+    /// Turn a Bulk query result CSV into an `Id,<external id field>` update CSV.
+    ///
+    /// Returns the CSV and the number of records in it.
+    #[cfg_attr(not(feature = "query-builder"), allow(dead_code))]
+    pub(crate) fn build_update_csv(&self, query_csv: &str) -> Result<(String, usize)> {
+        if query_csv.trim().is_empty() {
+            return Ok((String::new(), 0));
+        }
+        let mut reader = csv::Reader::from_reader(query_csv.as_bytes());
+        let headers = reader.headers()?.clone();
+        let find = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h == name)
+                .or_else(|| headers.iter().position(|h| h.eq_ignore_ascii_case(name)))
+                .ok_or_else(|| {
+                    Error::new(ErrorKind::Csv(format!(
+                        "Query results are missing column {}",
+                        name
+                    )))
+                })
+        };
+        let id_idx = find("Id")?;
+        let source_idx = self
+            .source_fields
+            .iter()
+            .map(|f| find(f))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(["Id", self.external_id_field.as_str()])?;
+        let mut count = 0;
+        for record in reader.records() {
+            let record = record?;
+            let values: Vec<&str> = source_idx
+                .iter()
+                .map(|&i| record.get(i).unwrap_or(""))
+                .collect();
+            let id = record.get(id_idx).unwrap_or("");
+            writer.write_record([id, self.generate(&values).as_str()])?;
+            count += 1;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| Error::new(ErrorKind::Csv(e.to_string())))?;
+        let csv =
+            String::from_utf8(bytes).map_err(|e| Error::new(ErrorKind::Csv(e.to_string())))?;
+        Ok((csv, count))
+    }
+}
+
+/// Outcome of [`BulkApiClient::backfill_external_ids`](crate::BulkApiClient::backfill_external_ids).
+#[derive(Debug, Clone)]
+pub struct BackfillResult {
+    /// Records found without an external ID.
+    pub records_found: usize,
+    /// The update job, or `None` if there was nothing to backfill.
+    pub ingest: Option<IngestJobResult>,
+}
+
+fn validate_field(field: &str) -> Result<()> {
+    let valid = !field.is_empty() && field.split('.').all(soql::is_safe_field_name);
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::Other(format!(
+            "Invalid field name: {}",
+            field
+        ))))
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_is_deterministic_v8() {
+        let backfill = ExternalIdBackfill::new("Account", "Ext_Id__c").unwrap();
+        let a = backfill.generate(&["001xx000003DGb2AAG"]);
+        let b = backfill.generate(&["001xx000003DGb2AAG"]);
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 36);
+        assert_eq!(&a[14..15], "8");
+        assert!(matches!(&a[19..20], "8" | "9" | "a" | "b"));
+        assert_ne!(a, backfill.generate(&["001xx000003DGb3AAG"]));
+    }
+
+    #[test]
+    fn test_field_boundaries_matter() {
+        let backfill = ExternalIdBackfill::new("Contact", "Key__c")
+            .unwrap()
+            .with_source_fields(&["FirstName", "LastName"])
+            .unwrap();
+        assert_ne!(
+            backfill.generate(&["ab", "c"]),
+            backfill.generate(&["a", "bc"])
+        );
+    }
+
+    #[test]
+    fn test_hash_strategy_truncates() {
+        let backfill = ExternalIdBackfill::new("Account", "Ext_Id__c")
+            .unwrap()
+            .with_strategy(ExternalIdStrategy::Hash { len: 20 });
+        let value = backfill.generate(&["x"]);
+        assert_eq!(value.len(), 20);
+        assert!(value.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_invalid_names_rejected() {
+        assert!(ExternalIdBackfill::new("Account; DELETE", "Ext__c").is_err());
+        assert!(ExternalIdBackfill::new("Account", "Ext__c = null OR Id").is_err());
+        assert!(ExternalIdBackfill::new("Account", "Ext__c")
+            .unwrap()
+            .with_source_fields(&[])
+            .is_err());
+    }
+
+    #[test]
+    fn test_build_update_csv() {
+        let backfill = ExternalIdBackfill::new("Contact", "Key__c")
+            .unwrap()
+            .with_source_fields(&["Email", "Account.Name"])
+            .unwrap();
+        assert_eq!(backfill.soql_fields(), vec!["Id", "Email", "Account.Name"]);
+
+        let query_csv = "\"Id\",\"Email\",\"Account.Name\"\n\
+                         \"003xx0000000001AAA\",\"a@example.com\",\"Acme\"\n\
+                         \"003xx0000000002AAA\",\"b@example.com\",\"\"\n";
+        let (csv, count) = backfill.build_update_csv(query_csv).unwrap();
+        assert_eq!(count, 2);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Id,Key__c");
+        assert_eq!(
+            lines[1],
+            format!(
+                "003xx0000000001AAA,{}",
+                backfill.generate(&["a@example.com", "Acme"])
+            )
+        );
+    }
+}
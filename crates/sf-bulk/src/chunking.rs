@@ -0,0 +1,221 @@
+//! Splitting ingest data that is too large for one job.
+//!
+//! Bulk API 2.0 accepts at most 150 MB of base64-encoded data per job,
+//! which Salesforce advises keeping to 100 MB of raw CSV.
+//! [`BulkApiClient::execute_ingest_chunked`] splits larger datasets into
+//! one job per chunk, runs them in parallel, and merges their results.
+//!
+//! [`BulkApiClient::execute_ingest_chunked`]: crate::BulkApiClient::execute_ingest_chunked
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::IngestJobResult;
+
+/// Raw CSV bytes per job that stay under the upload limit once encoded.
+pub const DEFAULT_MAX_CHUNK_BYTES: usize = 100 * 1024 * 1024;
+
+/// Jobs run at once by default.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// How ingest data is split into jobs.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bulk::{BulkOperation, CreateIngestJobRequest, IngestChunking};
+///
+/// let request = CreateIngestJobRequest::new("Contact", BulkOperation::Upsert)
+///     .with_external_id_field("Legacy_Key__c");
+/// let result = client
+///     .execute_ingest_chunked(request, &csv_data, IngestChunking::new().with_concurrency(2))
+///     .await?;
+/// println!("{} jobs, {} failed records", result.jobs.len(), result.records_failed());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IngestChunking {
+    pub(crate) max_chunk_bytes: usize,
+    pub(crate) concurrency: usize,
+}
+
+impl Default for IngestChunking {
+    fn default() -> Self {
+        Self {
+            max_chunk_bytes: DEFAULT_MAX_CHUNK_BYTES,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+impl IngestChunking {
+    /// Chunks of up to [`DEFAULT_MAX_CHUNK_BYTES`], four jobs at a time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap the CSV bytes uploaded to each job, header included.
+    pub fn with_max_chunk_bytes(mut self, bytes: usize) -> Self {
+        self.max_chunk_bytes = bytes;
+        self
+    }
+
+    /// Run at most `jobs` jobs at once. At least one always runs.
+    pub fn with_concurrency(mut self, jobs: usize) -> Self {
+        self.concurrency = jobs.max(1);
+        self
+    }
+}
+
+/// The merged outcome of a chunked ingest.
+#[derive(Debug, Clone)]
+pub struct ChunkedIngestResult {
+    /// Each chunk's job and results, in the order of the input data.
+    pub jobs: Vec<IngestJobResult>,
+    /// Successful records CSV across all jobs, with one header row.
+    pub successful_results: Option<String>,
+    /// Failed records CSV across all jobs, with one header row.
+    pub failed_results: Option<String>,
+}
+
+impl ChunkedIngestResult {
+    pub(crate) fn new(jobs: Vec<IngestJobResult>) -> Self {
+        let successful_results =
+            merge_csv(jobs.iter().filter_map(|j| j.successful_results.as_deref()));
+        let failed_results = merge_csv(jobs.iter().filter_map(|j| j.failed_results.as_deref()));
+        Self {
+            jobs,
+            successful_results,
+            failed_results,
+        }
+    }
+
+    /// Whether every job succeeded.
+    pub fn is_success(&self) -> bool {
+        self.jobs.iter().all(IngestJobResult::is_success)
+    }
+
+    /// Records processed across all jobs.
+    pub fn records_processed(&self) -> i64 {
+        self.jobs
+            .iter()
+            .map(|j| j.job.number_records_processed)
+            .sum()
+    }
+
+    /// Records that failed across all jobs.
+    pub fn records_failed(&self) -> i64 {
+        self.jobs.iter().map(|j| j.job.number_records_failed).sum()
+    }
+}
+
+/// Split `csv` into its header row and bodies of whole records that fit in
+/// `max_bytes` once the header is put in front of each. Records are never
+/// split, including quoted values with line breaks; a record that doesn't
+/// fit in a chunk on its own is an error.
+///
+/// Bodies borrow from `csv`, so a chunk is only copied when its job runs.
+pub(crate) fn split_csv(csv: &str, max_bytes: usize) -> Result<(&str, Vec<&str>)> {
+    let mut ends = record_ends(csv);
+    let Some(header_end) = ends.next() else {
+        return Ok(("", Vec::new()));
+    };
+    let header = &csv[..header_end];
+    let budget = max_bytes.saturating_sub(header.len());
+
+    let mut bodies = Vec::new();
+    let mut start = header_end;
+    let mut record_start = header_end;
+    for end in ends {
+        if end - record_start > budget {
+            return Err(Error::new(ErrorKind::Csv(format!(
+                "A record of {} bytes does not fit in a {max_bytes} byte chunk",
+                end - record_start
+            ))));
+        }
+        if end - start > budget {
+            bodies.push(&csv[start..record_start]);
+            start = record_start;
+        }
+        record_start = end;
+    }
+    if record_start > start {
+        bodies.push(&csv[start..record_start]);
+    }
+    Ok((header, bodies))
+}
+
+/// The byte offsets just past each record of `csv`, header included.
+fn record_ends(csv: &str) -> impl Iterator<Item = usize> + '_ {
+    let bytes = csv.as_bytes();
+    let mut in_quotes = false;
+    bytes
+        .iter()
+        .enumerate()
+        .filter_map(move |(i, &byte)| match byte {
+            b'"' => {
+                in_quotes = !in_quotes;
+                None
+            }
+            b'\n' if !in_quotes => Some(i + 1),
+            _ => None,
+        })
+        .chain((!bytes.is_empty() && !csv.ends_with('\n')).then_some(bytes.len()))
+}
+
+/// Concatenate result CSVs, keeping only the first one's header row.
+fn merge_csv<'a>(parts: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut merged: Option<String> = None;
+    for part in parts {
+        match &mut merged {
+            None => merged = Some(part.to_string()),
+            Some(merged) => {
+                let body = record_ends(part).next().map_or("", |end| &part[end..]);
+                if body.is_empty() {
+                    continue;
+                }
+                if !merged.is_empty() && !merged.ends_with('\n') {
+                    merged.push('\n');
+                }
+                merged.push_str(body);
+            }
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_csv_keeps_records_whole() {
+        let csv = "Name,Description\nA,\"one\ntwo\"\nB,x\nC,y\n";
+        let (header, bodies) = split_csv(csv, 30).unwrap();
+        assert_eq!(header, "Name,Description\n");
+        assert_eq!(bodies, ["A,\"one\ntwo\"\n", "B,x\nC,y\n"]);
+
+        let (_, bodies) = split_csv(csv, 1024).unwrap();
+        assert_eq!(bodies, ["A,\"one\ntwo\"\nB,x\nC,y\n"]);
+
+        let (_, bodies) = split_csv("Name\nA\nB", 7).unwrap();
+        assert_eq!(bodies, ["A\n", "B"]);
+
+        assert!(split_csv(csv, 20).is_err());
+        assert!(split_csv("", 10).unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn test_merge_csv_keeps_first_header() {
+        let merged = merge_csv(
+            [
+                "\"sf__Id\",\"Name\"\n\"001A\",\"A\"",
+                "\"sf__Id\",\"Name\"\n",
+                "\"sf__Id\",\"Name\"\n\"001B\",\"B\"\n",
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            merged.as_deref(),
+            Some("\"sf__Id\",\"Name\"\n\"001A\",\"A\"\n\"001B\",\"B\"\n")
+        );
+        assert_eq!(merge_csv(std::iter::empty()), None);
+    }
+}
@@ -3,14 +3,28 @@
 //! Provides a high-level interface to Salesforce Bulk API 2.0 for
 //! efficient large-scale data operations.
 
+use std::collections::VecDeque;
 use std::time::Duration;
+
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, Stream, StreamExt, TryStreamExt};
+use serde::Serialize;
 use tokio::time::sleep;
 use tracing::instrument;
 
-use busbar_sf_client::{ClientConfig, SalesforceClient};
+use busbar_sf_client::{ClientConfig, MaintenancePreflight, SalesforceClient};
 
+#[cfg(feature = "query-builder")]
+use crate::backfill::{BackfillResult, ExternalIdBackfill};
+use crate::chunking::{split_csv, ChunkedIngestResult, IngestChunking};
 use crate::error::{Error, ErrorKind, Result};
+use crate::mapping::FieldMapping;
+use crate::results::{QueryResultRow, RowDecoder};
+use crate::serialize::to_ingest_csv;
 use crate::types::*;
+use crate::upload::{is_transient, JobUpload, UploadProgress, UploadRecovery};
+#[cfg(feature = "query-builder")]
+use crate::validation::{CsvValidationReport, CsvValidator};
 
 /// Default polling interval for job status checks.
 const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
@@ -51,6 +65,8 @@ pub struct BulkApiClient {
     client: SalesforceClient,
     poll_interval: Duration,
     max_wait: Duration,
+    maintenance_preflight: Option<MaintenancePreflight>,
+    upload_recovery: Option<UploadRecovery>,
 }
 
 impl BulkApiClient {
@@ -61,6 +77,8 @@ impl BulkApiClient {
             client,
             poll_interval: DEFAULT_POLL_INTERVAL,
             max_wait: DEFAULT_MAX_WAIT,
+            maintenance_preflight: None,
+            upload_recovery: None,
         })
     }
 
@@ -75,6 +93,8 @@ impl BulkApiClient {
             client,
             poll_interval: DEFAULT_POLL_INTERVAL,
             max_wait: DEFAULT_MAX_WAIT,
+            maintenance_preflight: None,
+            upload_recovery: None,
         })
     }
 
@@ -84,6 +104,8 @@ impl BulkApiClient {
             client,
             poll_interval: DEFAULT_POLL_INTERVAL,
             max_wait: DEFAULT_MAX_WAIT,
+            maintenance_preflight: None,
+            upload_recovery: None,
         }
     }
 
@@ -120,6 +142,22 @@ impl BulkApiClient {
         self
     }
 
+    /// Check the Trust API for instance maintenance before each
+    /// [`execute_ingest`](Self::execute_ingest) load.
+    pub fn with_maintenance_preflight(mut self, preflight: MaintenancePreflight) -> Self {
+        self.maintenance_preflight = Some(preflight);
+        self
+    }
+
+    /// Retry failed uploads, and replace jobs whose uploads keep failing,
+    /// in the ingest operations that create their own jobs
+    /// ([`execute_ingest`](Self::execute_ingest) and the methods built on
+    /// it). See [`upload_ingest_data_resumable`](Self::upload_ingest_data_resumable).
+    pub fn with_upload_recovery(mut self, recovery: UploadRecovery) -> Self {
+        self.upload_recovery = Some(recovery);
+        self
+    }
+
     // =========================================================================
     // Ingest Job Operations
     // =========================================================================
@@ -151,6 +189,85 @@ impl BulkApiClient {
         Ok(())
     }
 
+    /// Create an ingest job and upload `csv_data` to it, recovering from
+    /// failed uploads as `recovery` allows.
+    ///
+    /// A failed upload is retried while the failure is transient (a
+    /// dropped connection, timeout, rate limit, or server error) and the
+    /// job is still open. Once a job's uploads are used up, or it has left
+    /// the Open state, it is aborted and the data goes to a new job created
+    /// from the same `request`. Any other failure aborts the job and is
+    /// returned, so no open job is left behind.
+    ///
+    /// On success the last job in the returned [`UploadProgress`] holds all
+    /// the data and is still open; close it with
+    /// [`close_ingest_job`](Self::close_ingest_job).
+    #[instrument(skip(self, request, csv_data, recovery), fields(sobject = %request.object, bytes = csv_data.len()))]
+    pub async fn upload_ingest_data_resumable(
+        &self,
+        request: &CreateIngestJobRequest,
+        csv_data: &str,
+        recovery: &UploadRecovery,
+    ) -> Result<UploadProgress> {
+        let mut progress = UploadProgress::new(csv_data.len());
+        loop {
+            let job = self.create_ingest_job(request.clone()).await?;
+            progress.jobs.push(JobUpload {
+                job_id: job.id,
+                bytes_sent: 0,
+                attempts: 0,
+                aborted: false,
+            });
+            let upload = progress.jobs.last_mut().expect("job was just added");
+
+            let (error, recreate) = loop {
+                upload.attempts += 1;
+                let error = match self.upload_job_data(&upload.job_id, csv_data).await {
+                    Ok(()) => {
+                        upload.bytes_sent = csv_data.len();
+                        return Ok(progress);
+                    }
+                    Err(e) => e,
+                };
+                // A job that failed or was aborted can't take data any more,
+                // whatever the upload error was.
+                let still_open = match self.get_ingest_job(&upload.job_id).await {
+                    Ok(job) => job.state == JobState::Open,
+                    Err(_) => true,
+                };
+                if !still_open {
+                    break (error, true);
+                }
+                if !is_transient(&error) {
+                    break (error, false);
+                }
+                if upload.attempts >= recovery.max_attempts {
+                    break (error, true);
+                }
+                tracing::warn!(
+                    job_id = %upload.job_id,
+                    attempt = upload.attempts,
+                    error = %error,
+                    "Job data upload failed, retrying"
+                );
+                sleep(recovery.delay(upload.attempts)).await;
+            };
+
+            // The job was never closed, so aborting it discards the data
+            // it may have received without processing any of it.
+            upload.aborted = self.abort_ingest_job(&upload.job_id).await.is_ok();
+            let recreates = progress.jobs.len() as u32 - 1;
+            if !recreate || recreates >= recovery.max_recreates {
+                return Err(error);
+            }
+            tracing::warn!(
+                job_id = %progress.jobs[recreates as usize].job_id,
+                error = %error,
+                "Job data upload failed, recreating job"
+            );
+        }
+    }
+
     /// Close an ingest job (mark as UploadComplete).
     #[instrument(skip(self))]
     pub async fn close_ingest_job(&self, job_id: &str) -> Result<IngestJob> {
@@ -319,11 +436,49 @@ impl BulkApiClient {
         Ok(jobs)
     }
 
+    /// Stream ingest jobs matching `filter`, following `nextRecordsUrl`
+    /// across pages.
+    ///
+    /// Pages are fetched as the stream is read. The stream ends after the
+    /// last page and stops at the first error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use busbar_sf_bulk::{ConcurrencyMode, IngestJobFilter};
+    /// use futures::TryStreamExt;
+    ///
+    /// let filter = IngestJobFilter::new().with_concurrency_mode(ConcurrencyMode::Serial);
+    /// let jobs: Vec<_> = client.get_ingest_jobs_stream(&filter).try_collect().await?;
+    /// ```
+    pub fn get_ingest_jobs_stream(
+        &self,
+        filter: &IngestJobFilter,
+    ) -> impl Stream<Item = Result<IngestJob>> + Send + '_ {
+        let query = filter.query_string();
+        let first = if query.is_empty() {
+            self.client.bulk_url("ingest")
+        } else {
+            format!("{}?{}", self.client.bulk_url("ingest"), query)
+        };
+
+        stream::try_unfold(Some(first), move |next| async move {
+            let Some(url) = next else {
+                return Ok(None);
+            };
+            let page: IngestJobList = self.client.get_json(&url).await?;
+            let next = page.next_records_url.filter(|_| !page.done);
+            Ok::<_, Error>(Some((stream::iter(page.records.into_iter().map(Ok)), next)))
+        })
+        .try_flatten()
+    }
+
     // =========================================================================
     // Query Job Operations - SECURED
     // =========================================================================
-    // All query operations now use QueryBuilder for automatic SOQL injection prevention.
-    // No raw SOQL methods are exposed in the public API.
+    // `execute_query` builds its SOQL with QueryBuilder for automatic SOQL
+    // injection prevention. `create_query_job` takes SOQL as given, for
+    // callers that run a job step by step.
 
     /// Execute a complete query operation with automatic SOQL injection prevention.
     ///
@@ -370,9 +525,7 @@ impl BulkApiClient {
             .map_err(|e| Error::new(ErrorKind::Api(format!("Failed to build query: {}", e))))?;
 
         // Create job
-        let request = CreateQueryJobRequest::new(soql);
-        let url = self.client.bulk_url("query");
-        let job: QueryJob = self.client.post_json(&url, &request).await?;
+        let job = self.create_query_job(&soql, BulkOperation::Query).await?;
 
         // Wait for completion
         let completed_job = self.wait_for_query_job_internal(&job.id).await?;
@@ -390,6 +543,137 @@ impl BulkApiClient {
         })
     }
 
+    /// Execute a query job and load the results into a Polars DataFrame.
+    ///
+    /// Column types come from a describe of the queried object. Fails if
+    /// the job doesn't complete successfully.
+    #[cfg(feature = "polars")]
+    #[instrument(skip(self, query_builder))]
+    pub async fn execute_query_dataframe<T>(
+        &self,
+        query_builder: busbar_sf_rest::QueryBuilder<T>,
+    ) -> Result<busbar_sf_rest::DataFrame>
+    where
+        T: serde::de::DeserializeOwned + Clone,
+    {
+        let sobject = query_builder.sobject().to_string();
+        let result = self.execute_query(query_builder).await?;
+        let Some(csv_data) = result.results else {
+            return Err(Error::new(ErrorKind::Job(format!(
+                "Query job {} did not complete: {:?}",
+                result.job.id, result.job.state
+            ))));
+        };
+        let describe = busbar_sf_rest::SalesforceRestClient::from_client(self.client.clone())
+            .describe_sobject(&sobject)
+            .await
+            .map_err(|e| {
+                Error::new(ErrorKind::Api(format!(
+                    "Failed to describe {}: {}",
+                    sobject, e
+                )))
+            })?;
+        crate::dataframe::csv_to_dataframe(&csv_data, Some(&describe))
+    }
+
+    /// Stream query job results as Arrow record batches of up to
+    /// `batch_rows` rows each.
+    ///
+    /// Rows are decoded as they download, as for
+    /// [`get_query_results_stream`](Self::get_query_results_stream), so only
+    /// one batch is held in memory at a time. Pass the queried object's
+    /// describe to get typed columns; see [`query_results_schema`]. A job
+    /// with no rows yields no batches.
+    ///
+    /// [`query_results_schema`]: crate::query_results_schema
+    #[cfg(feature = "arrow")]
+    pub fn get_query_results_arrow<'a>(
+        &'a self,
+        job_id: &'a str,
+        describe: Option<&'a busbar_sf_rest::DescribeSObjectResult>,
+        batch_rows: usize,
+    ) -> impl Stream<Item = Result<crate::RecordBatch>> + Send + 'a {
+        let mut schema = None;
+        self.get_query_results_stream(job_id)
+            .chunks(batch_rows.max(1))
+            .map(move |rows| {
+                let rows = rows.into_iter().collect::<Result<Vec<_>>>()?;
+                let schema = schema.get_or_insert_with(|| {
+                    std::sync::Arc::new(crate::query_results_schema(rows[0].columns(), describe))
+                });
+                crate::rows_to_record_batch(schema.clone(), &rows)
+            })
+    }
+
+    /// Write query job results to `writer` as a Parquet file, returning
+    /// the number of rows written.
+    ///
+    /// Column types come from `describe` as for
+    /// [`get_query_results_arrow`](Self::get_query_results_arrow). Results
+    /// are written a batch at a time as they download. A job with no rows
+    /// produces a file with no columns.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let describe = rest_client.describe_sobject("Opportunity").await?;
+    /// let file = std::fs::File::create("opportunities.parquet")?;
+    /// let rows = client
+    ///     .write_query_results_parquet(&job.id, Some(&describe), file)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "parquet")]
+    #[instrument(skip(self, describe, writer))]
+    pub async fn write_query_results_parquet<W>(
+        &self,
+        job_id: &str,
+        describe: Option<&busbar_sf_rest::DescribeSObjectResult>,
+        writer: W,
+    ) -> Result<usize>
+    where
+        W: std::io::Write + Send,
+    {
+        let batches =
+            self.get_query_results_arrow(job_id, describe, crate::DEFAULT_ARROW_BATCH_ROWS);
+        crate::arrow::write_parquet(batches, writer).await
+    }
+
+    /// Create a query job without waiting for it to finish.
+    ///
+    /// `operation` is [`BulkOperation::Query`], or [`BulkOperation::QueryAll`]
+    /// to include deleted and archived records. Poll the job with
+    /// [`get_query_job`](Self::get_query_job) and read its results with
+    /// [`get_query_results`](Self::get_query_results) once it is complete.
+    ///
+    /// # Security
+    ///
+    /// The SOQL is sent as given. Build it with `QueryBuilder`, or use
+    /// `execute_query`, when it contains user input.
+    #[instrument(skip(self, soql))]
+    pub async fn create_query_job(&self, soql: &str, operation: BulkOperation) -> Result<QueryJob> {
+        let request = match operation {
+            BulkOperation::Query => CreateQueryJobRequest::new(soql),
+            BulkOperation::QueryAll => CreateQueryJobRequest::new(soql).with_query_all(),
+            other => {
+                return Err(Error::new(ErrorKind::Job(format!(
+                    "{} is not a query operation",
+                    other.api_name()
+                ))))
+            }
+        };
+        let url = self.client.bulk_url("query");
+        let job: QueryJob = self.client.post_json(&url, &request).await?;
+        Ok(job)
+    }
+
+    /// Get the status of a query job.
+    #[instrument(skip(self))]
+    pub async fn get_query_job(&self, job_id: &str) -> Result<QueryJob> {
+        let url = format!("{}/{}", self.client.bulk_url("query"), job_id);
+        let job: QueryJob = self.client.get_json(&url).await?;
+        Ok(job)
+    }
+
     /// Abort a query job.
     ///
     /// This can be used with job IDs from `execute_query()`.
@@ -418,8 +702,7 @@ impl BulkApiClient {
         let start = std::time::Instant::now();
 
         loop {
-            let url = format!("{}/{}", self.client.bulk_url("query"), job_id);
-            let job: QueryJob = self.client.get_json(&url).await?;
+            let job = self.get_query_job(job_id).await?;
 
             if job.state.is_terminal() {
                 return Ok(job);
@@ -446,6 +729,25 @@ impl BulkApiClient {
         locator: Option<&str>,
         max_records: Option<usize>,
     ) -> Result<QueryResults> {
+        let (response, next_locator) = self
+            .query_results_page(job_id, locator, max_records)
+            .await?;
+        let csv_data = response.text().await?;
+
+        Ok(QueryResults {
+            csv_data,
+            locator: next_locator,
+        })
+    }
+
+    /// Request one page of query results, returning the response and the
+    /// locator of the next page.
+    async fn query_results_page(
+        &self,
+        job_id: &str,
+        locator: Option<&str>,
+        max_records: Option<usize>,
+    ) -> Result<(busbar_sf_client::Response, Option<String>)> {
         let mut url = format!("{}/{}/results", self.client.bulk_url("query"), job_id);
 
         let mut query_params = vec![];
@@ -476,11 +778,77 @@ impl BulkApiClient {
             .map(|s| s.to_string())
             .filter(|s| s != "null");
 
-        let csv_data = response.text().await?;
+        Ok((response, next_locator))
+    }
 
-        Ok(QueryResults {
-            csv_data,
-            locator: next_locator,
+    /// Stream query results row by row, following the `Sforce-Locator`
+    /// header across pages.
+    ///
+    /// Rows are decoded as each page downloads, so neither a page nor the
+    /// whole result set is held in memory. The stream ends after the last
+    /// page and stops at the first error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use futures::TryStreamExt;
+    ///
+    /// let rows = client.get_query_results_stream(&job.id);
+    /// futures::pin_mut!(rows);
+    /// while let Some(row) = rows.try_next().await? {
+    ///     println!("{}", row.get("Id").unwrap_or_default());
+    /// }
+    /// ```
+    pub fn get_query_results_stream<'a>(
+        &'a self,
+        job_id: &'a str,
+    ) -> impl Stream<Item = Result<QueryResultRow>> + Send + 'a {
+        struct State<'a> {
+            client: &'a BulkApiClient,
+            job_id: &'a str,
+            /// The locator of the page to fetch next: `Some(None)` for the
+            /// first page, `None` once the last page has been fetched.
+            next_page: Option<Option<String>>,
+            body: Option<BoxStream<'static, busbar_sf_client::Result<Bytes>>>,
+            decoder: RowDecoder,
+            rows: VecDeque<QueryResultRow>,
+        }
+
+        let state = State {
+            client: self,
+            job_id,
+            next_page: Some(None),
+            body: None,
+            decoder: RowDecoder::default(),
+            rows: VecDeque::new(),
+        };
+
+        stream::try_unfold(state, |mut state| async move {
+            loop {
+                if let Some(row) = state.rows.pop_front() {
+                    return Ok(Some((row, state)));
+                }
+                if let Some(body) = &mut state.body {
+                    match body.try_next().await? {
+                        Some(chunk) => state.rows.extend(state.decoder.push(&chunk)?),
+                        None => {
+                            state.body = None;
+                            state.rows.extend(state.decoder.finish_page()?);
+                        }
+                    }
+                    continue;
+                }
+                let Some(locator) = state.next_page.take() else {
+                    return Ok(None);
+                };
+                let (response, next_locator) = state
+                    .client
+                    .query_results_page(state.job_id, locator.as_deref(), None)
+                    .await?;
+                state.next_page = next_locator.map(Some);
+                state.body = Some(response.bytes_stream().boxed());
+                state.decoder.start_page();
+            }
         })
     }
 
@@ -721,26 +1089,132 @@ impl BulkApiClient {
         csv_data: &str,
         external_id_field: Option<&str>,
     ) -> Result<IngestJobResult> {
-        // Create job
+        if let Some(preflight) = &self.maintenance_preflight {
+            preflight.check().await?;
+        }
+
         let mut request = CreateIngestJobRequest::new(sobject, operation);
         if let Some(ext_id) = external_id_field {
             request = request.with_external_id_field(ext_id);
         }
 
-        let job = self.create_ingest_job(request).await?;
+        self.run_ingest_job(request, csv_data).await
+    }
 
-        // Upload data
-        self.upload_job_data(&job.id, csv_data).await?;
+    /// Insert typed records with a bulk job and wait for it to finish.
+    ///
+    /// Records are encoded with [`to_ingest_csv`], so there's no CSV to
+    /// build by hand; see the [`to_ingest_csv`] docs for how fields map to
+    /// columns. Use [`submit_ingest_records`](Self::submit_ingest_records)
+    /// for other operations or to return without waiting.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// #[derive(serde::Serialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct Account {
+    ///     name: String,
+    ///     industry: Option<String>,
+    /// }
+    ///
+    /// let result = client.bulk_insert_records("Account", &accounts).await?;
+    /// println!("Inserted {} accounts", result.job.number_records_processed);
+    /// ```
+    #[instrument(skip(self, records), fields(records = records.len()))]
+    pub async fn bulk_insert_records<T: Serialize>(
+        &self,
+        sobject: &str,
+        records: &[T],
+    ) -> Result<IngestJobResult> {
+        let csv_data = to_ingest_csv(records)?;
+        self.execute_ingest(sobject, BulkOperation::Insert, &csv_data, None)
+            .await
+    }
+
+    /// Create an ingest job for typed records, upload them, and close the
+    /// job without waiting for Salesforce to process it.
+    ///
+    /// Returns the closed job; follow it with
+    /// [`wait_for_ingest_job`](Self::wait_for_ingest_job) or
+    /// [`get_ingest_job`](Self::get_ingest_job).
+    #[instrument(skip(self, request, records), fields(sobject = %request.object, records = records.len()))]
+    pub async fn submit_ingest_records<T: Serialize>(
+        &self,
+        request: CreateIngestJobRequest,
+        records: &[T],
+    ) -> Result<IngestJob> {
+        if let Some(preflight) = &self.maintenance_preflight {
+            preflight.check().await?;
+        }
+
+        let csv_data = to_ingest_csv(records)?;
+        let job_id = self.create_and_upload(request, &csv_data).await?;
+        self.close_ingest_job(&job_id).await
+    }
+
+    /// Execute an ingest operation too large for one job.
+    ///
+    /// Splits `csv_data` into chunks of whole records, each under the
+    /// [`IngestChunking`] size cap with the header row repeated, and runs
+    /// one job per chunk (created, uploaded, closed, and awaited) with at
+    /// most the configured number in flight. Data that fits in one chunk
+    /// runs as a single job.
+    ///
+    /// Each job's results are kept in [`ChunkedIngestResult::jobs`], and
+    /// their successful and failed records are merged into one CSV each.
+    /// Stops at the first job that can't be run; jobs already created are
+    /// left to finish on their own.
+    #[instrument(skip(self, request, csv_data), fields(sobject = %request.object))]
+    pub async fn execute_ingest_chunked(
+        &self,
+        request: CreateIngestJobRequest,
+        csv_data: &str,
+        chunking: IngestChunking,
+    ) -> Result<ChunkedIngestResult> {
+        if let Some(preflight) = &self.maintenance_preflight {
+            preflight.check().await?;
+        }
+
+        let (header, bodies) = split_csv(csv_data, chunking.max_chunk_bytes)?;
+        tracing::debug!(jobs = bodies.len(), "Split ingest data into chunks");
+
+        let jobs = stream::iter(bodies)
+            .map(|body| {
+                let request = request.clone();
+                async move {
+                    let mut chunk = String::with_capacity(header.len() + body.len());
+                    chunk.push_str(header);
+                    chunk.push_str(body);
+                    self.run_ingest_job(request, &chunk).await
+                }
+            })
+            .buffered(chunking.concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(ChunkedIngestResult::new(jobs))
+    }
+
+    /// Create a job, upload `csv_data` to it, close it, wait for it to
+    /// finish, and fetch its results.
+    async fn run_ingest_job(
+        &self,
+        request: CreateIngestJobRequest,
+        csv_data: &str,
+    ) -> Result<IngestJobResult> {
+        // Create job and upload data
+        let job_id = self.create_and_upload(request, csv_data).await?;
 
         // Close job
-        self.close_ingest_job(&job.id).await?;
+        self.close_ingest_job(&job_id).await?;
 
         // Wait for completion
-        let completed_job = self.wait_for_ingest_job(&job.id).await?;
+        let completed_job = self.wait_for_ingest_job(&job_id).await?;
 
         // Get results
-        let successful_results = self.get_successful_results(&job.id).await.ok();
-        let failed_results = self.get_failed_results(&job.id).await.ok();
+        let successful_results = self.get_successful_results(&job_id).await.ok();
+        let failed_results = self.get_failed_results(&job_id).await.ok();
 
         Ok(IngestJobResult {
             job: completed_job,
@@ -748,6 +1222,118 @@ impl BulkApiClient {
             failed_results,
         })
     }
+
+    /// Create a job and upload `csv_data` to it, with the configured upload
+    /// recovery if any, returning the ID of the job holding the data.
+    async fn create_and_upload(
+        &self,
+        request: CreateIngestJobRequest,
+        csv_data: &str,
+    ) -> Result<String> {
+        if let Some(recovery) = &self.upload_recovery {
+            let progress = self
+                .upload_ingest_data_resumable(&request, csv_data, recovery)
+                .await?;
+            let job = progress.jobs.into_iter().last().expect("upload completed");
+            return Ok(job.job_id);
+        }
+
+        let job = self.create_ingest_job(request).await?;
+        self.upload_job_data(&job.id, csv_data).await?;
+        Ok(job.id)
+    }
+
+    /// Assign deterministic external IDs to records that don't have one.
+    ///
+    /// Queries `sobject` records where the external ID field is null, derives
+    /// a value for each from its source fields, and writes them back with a
+    /// bulk update. Records whose update fails are listed in
+    /// `ingest.failed_results`; re-running the backfill picks them up again.
+    #[cfg(feature = "query-builder")]
+    #[instrument(skip(self, backfill), fields(sobject = %backfill.sobject))]
+    pub async fn backfill_external_ids(
+        &self,
+        backfill: &ExternalIdBackfill,
+    ) -> Result<BackfillResult> {
+        let query = busbar_sf_rest::QueryBuilder::<serde_json::Value>::new(&backfill.sobject)
+            .map_err(|e| Error::new(ErrorKind::Api(format!("Failed to build query: {}", e))))?
+            .select(&backfill.soql_fields())
+            .where_raw(format!("{} = null", backfill.external_id_field));
+        let result = self.execute_query(query).await?;
+        if !result.job.state.is_success() {
+            return Err(Error::new(ErrorKind::Job(format!(
+                "Backfill query job {} did not complete: {:?}",
+                result.job.id, result.job.state
+            ))));
+        }
+
+        let (csv_data, records_found) =
+            backfill.build_update_csv(result.results.as_deref().unwrap_or(""))?;
+        if records_found == 0 {
+            return Ok(BackfillResult {
+                records_found,
+                ingest: None,
+            });
+        }
+
+        let ingest = self
+            .execute_ingest(&backfill.sobject, BulkOperation::Update, &csv_data, None)
+            .await?;
+        Ok(BackfillResult {
+            records_found,
+            ingest: Some(ingest),
+        })
+    }
+
+    /// Check an ingest CSV against `sobject`'s describe without creating a
+    /// job.
+    ///
+    /// Describes the object (one REST call) and runs a [`CsvValidator`]
+    /// over the CSV. Use the validator directly to reuse a describe across
+    /// files.
+    #[cfg(feature = "query-builder")]
+    #[instrument(skip(self, csv_data))]
+    pub async fn validate_ingest_csv(
+        &self,
+        sobject: &str,
+        operation: BulkOperation,
+        csv_data: &str,
+        external_id_field: Option<&str>,
+    ) -> Result<CsvValidationReport> {
+        let describe = busbar_sf_rest::SalesforceRestClient::from_client(self.client.clone())
+            .describe_sobject(sobject)
+            .await
+            .map_err(|e| {
+                Error::new(ErrorKind::Api(format!(
+                    "Failed to describe {}: {}",
+                    sobject, e
+                )))
+            })?;
+        let mut validator = CsvValidator::new(&describe, operation);
+        if let Some(field) = external_id_field {
+            validator = validator.with_external_id_field(field);
+        }
+        validator.validate(csv_data)
+    }
+
+    /// Execute a complete ingest operation, remapping the CSV with a Data
+    /// Loader `.sdl` mapping first.
+    ///
+    /// See [`FieldMapping::apply`] for how columns are renamed, dropped, and
+    /// filled with constants.
+    #[instrument(skip(self, csv_data, mapping))]
+    pub async fn execute_ingest_with_mapping(
+        &self,
+        sobject: &str,
+        operation: BulkOperation,
+        csv_data: &str,
+        mapping: &FieldMapping,
+        external_id_field: Option<&str>,
+    ) -> Result<IngestJobResult> {
+        let mapped = mapping.apply(csv_data)?;
+        self.execute_ingest(sobject, operation, &mapped, external_id_field)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -843,6 +1429,360 @@ mod tests {
         assert!(batch.next_records_url.is_none());
     }
 
+    #[tokio::test]
+    async fn test_create_and_get_query_job_wiremock() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/jobs/query"))
+            .and(body_partial_json(serde_json::json!({
+                "query": "SELECT Id FROM Account",
+                "operation": "queryAll"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "750xx000000003",
+                "state": "UploadComplete",
+                "operation": "queryAll",
+                "object": "Account"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/jobs/query/750xx000000003"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "750xx000000003",
+                "state": "JobComplete",
+                "operation": "queryAll",
+                "numberRecordsProcessed": 42
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = BulkApiClient::new(mock_server.uri(), "test-token").unwrap();
+
+        let job = client
+            .create_query_job("SELECT Id FROM Account", BulkOperation::QueryAll)
+            .await
+            .unwrap();
+        assert_eq!(job.state, JobState::UploadComplete);
+
+        let job = client.get_query_job(&job.id).await.unwrap();
+        assert!(job.state.is_success());
+        assert_eq!(job.number_records_processed, 42);
+
+        let err = client
+            .create_query_job("SELECT Id FROM Account", BulkOperation::Insert)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::Job(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_query_results_stream_follows_locator() {
+        use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let results_path = "/services/data/v62.0/jobs/query/750xx000000004/results";
+
+        Mock::given(method("GET"))
+            .and(path(results_path))
+            .and(query_param_is_missing("locator"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Sforce-Locator", "MTAwMDA")
+                    .set_body_string("\"Id\",\"Name\"\n\"001A\",\"Acme\"\n\"001B\",\"Globex\"\n"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(results_path))
+            .and(query_param("locator", "MTAwMDA"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Sforce-Locator", "null")
+                    .set_body_string("\"Id\",\"Name\"\n\"001C\",\"Initech\"\n"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = BulkApiClient::new(mock_server.uri(), "test-token").unwrap();
+        let rows: Vec<QueryResultRow> = client
+            .get_query_results_stream("750xx000000004")
+            .try_collect()
+            .await
+            .unwrap();
+
+        let names: Vec<_> = rows.iter().map(|r| r.get("Name").unwrap()).collect();
+        assert_eq!(names, ["Acme", "Globex", "Initech"]);
+        assert_eq!(rows[2].get("Id"), Some("001C"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_ingest_data_resumable_recreates_job() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let job = |id: &str, state: &str| {
+            serde_json::json!({
+                "id": id,
+                "state": state,
+                "object": "Account",
+                "operation": "insert"
+            })
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/jobs/ingest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(job("750A", "Open")))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/jobs/ingest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(job("750B", "Open")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/services/data/v62.0/jobs/ingest/750A/batches"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/jobs/ingest/750A"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(job("750A", "Open")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/services/data/v62.0/jobs/ingest/750A"))
+            .and(body_json(serde_json::json!({"state": "Aborted"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(job("750A", "Aborted")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/services/data/v62.0/jobs/ingest/750B/batches"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = BulkApiClient::with_config(
+            mock_server.uri(),
+            "test-token",
+            ClientConfig::builder().without_retry().build(),
+        )
+        .unwrap();
+        let recovery = UploadRecovery::new()
+            .with_max_attempts(2)
+            .with_retry_delay(Duration::ZERO);
+        let progress = client
+            .upload_ingest_data_resumable(
+                &CreateIngestJobRequest::new("Account", BulkOperation::Insert),
+                "Name\nAcme\n",
+                &recovery,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(progress.job_id(), Some("750B"));
+        assert_eq!(progress.aborted_jobs().collect::<Vec<_>>(), ["750A"]);
+        assert_eq!(progress.jobs[0].attempts, 2);
+        assert_eq!(progress.jobs[0].bytes_sent, 0);
+        assert_eq!(progress.jobs[1].bytes_sent, progress.bytes_total);
+    }
+
+    #[tokio::test]
+    async fn test_execute_ingest_chunked_wiremock() {
+        use wiremock::matchers::{body_string, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let job = serde_json::json!({
+            "id": "750xx000000005",
+            "state": "JobComplete",
+            "object": "Account",
+            "operation": "insert",
+            "numberRecordsProcessed": 1
+        });
+
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/jobs/ingest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        for chunk in ["Name\nAcme\n", "Name\nGlobex\n"] {
+            Mock::given(method("PUT"))
+                .and(path(
+                    "/services/data/v62.0/jobs/ingest/750xx000000005/batches",
+                ))
+                .and(body_string(chunk))
+                .respond_with(ResponseTemplate::new(201))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(method("PATCH"))
+            .and(path("/services/data/v62.0/jobs/ingest/750xx000000005"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/jobs/ingest/750xx000000005"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/services/data/v62.0/jobs/ingest/750xx000000005/successfulResults",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("\"sf__Id\",\"sf__Created\",Name\n\"001A\",\"true\",Acme\n"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = BulkApiClient::new(mock_server.uri(), "test-token").unwrap();
+        let result = client
+            .execute_ingest_chunked(
+                CreateIngestJobRequest::new("Account", BulkOperation::Insert),
+                "Name\nAcme\nGlobex\n",
+                IngestChunking::new().with_max_chunk_bytes(12),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.jobs.len(), 2);
+        assert!(result.is_success());
+        assert_eq!(result.records_processed(), 2);
+        assert_eq!(
+            result.successful_results.as_deref(),
+            Some(
+                "\"sf__Id\",\"sf__Created\",Name\n\"001A\",\"true\",Acme\n\"001A\",\"true\",Acme\n"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_ingest_records_wiremock() {
+        use wiremock::matchers::{body_partial_json, body_string, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let job = serde_json::json!({
+            "id": "750xx000000006",
+            "state": "UploadComplete",
+            "object": "Account",
+            "operation": "upsert"
+        });
+
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/jobs/ingest"))
+            .and(body_partial_json(serde_json::json!({
+                "object": "Account",
+                "operation": "upsert",
+                "externalIdFieldName": "External_Id__c"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path(
+                "/services/data/v62.0/jobs/ingest/750xx000000006/batches",
+            ))
+            .and(body_string("External_Id__c,Name\nA-1,Acme\n"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/services/data/v62.0/jobs/ingest/750xx000000006"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&job))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = BulkApiClient::new(mock_server.uri(), "test-token").unwrap();
+        let job = client
+            .submit_ingest_records(
+                CreateIngestJobRequest::new("Account", BulkOperation::Upsert)
+                    .with_external_id_field("External_Id__c"),
+                &[serde_json::json!({"Name": "Acme", "External_Id__c": "A-1"})],
+            )
+            .await
+            .unwrap();
+        assert_eq!(job.state, JobState::UploadComplete);
+    }
+
+    #[tokio::test]
+    async fn test_get_ingest_jobs_stream_follows_next_records_url() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let job = |id: &str| {
+            serde_json::json!({
+                "id": id,
+                "state": "JobComplete",
+                "object": "Account",
+                "operation": "insert",
+                "concurrencyMode": "Serial",
+                "jobType": "Classic"
+            })
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/jobs/ingest"))
+            .and(query_param("concurrencyMode", "Serial"))
+            .and(query_param("jobType", "Classic"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "records": [job("750A"), job("750B")],
+                "done": false,
+                "nextRecordsUrl": "/services/data/v62.0/jobs/ingest?queryLocator=01gA"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/jobs/ingest"))
+            .and(query_param("queryLocator", "01gA"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "records": [job("750C")],
+                "done": true,
+                "nextRecordsUrl": null
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = BulkApiClient::new(mock_server.uri(), "test-token").unwrap();
+        let filter = IngestJobFilter::new()
+            .with_job_type(JobType::Classic)
+            .with_concurrency_mode(ConcurrencyMode::Serial);
+        let jobs: Vec<IngestJob> = client
+            .get_ingest_jobs_stream(&filter)
+            .try_collect()
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = jobs.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, ["750A", "750B", "750C"]);
+        assert_eq!(jobs[0].job_type.as_deref(), Some("Classic"));
+    }
+
     #[tokio::test]
     async fn test_get_parallel_query_results_with_max_records() {
         use wiremock::matchers::{method, path_regex, query_param};
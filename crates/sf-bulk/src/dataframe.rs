@@ -0,0 +1,81 @@
+//! Polars DataFrames from Bulk API query results.
+
+use busbar_sf_rest::{records_to_dataframe, DataFrame, DescribeSObjectResult};
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Convert a Bulk API query result CSV into a DataFrame.
+///
+/// Bulk CSVs carry no types, so pass the queried object's describe to get
+/// typed columns; without it every column is a string unless its values
+/// look like dates or datetimes. Empty cells are null.
+pub fn csv_to_dataframe(
+    csv_data: &str,
+    describe: Option<&DescribeSObjectResult>,
+) -> Result<DataFrame> {
+    let mut reader = csv::ReaderBuilder::new().from_reader(csv_data.as_bytes());
+    let headers = reader.headers()?.clone();
+    let records = reader
+        .records()
+        .map(|record| {
+            let record = record?;
+            let row: serde_json::Map<String, serde_json::Value> = headers
+                .iter()
+                .zip(record.iter())
+                .map(|(header, value)| {
+                    let value = if value.is_empty() {
+                        serde_json::Value::Null
+                    } else {
+                        serde_json::Value::String(value.to_string())
+                    };
+                    (header.to_string(), value)
+                })
+                .collect();
+            Ok(serde_json::Value::Object(row))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    records_to_dataframe(&records, describe).map_err(|e| Error::new(ErrorKind::Csv(e.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(describe: Option<&DescribeSObjectResult>) -> DataFrame {
+        let csv = "Id,Name,NumberOfEmployees,Owner.Name\n\
+                   001xx000003DGb2AAG,Acme,120,Ada\n\
+                   001xx000003DGb3AAG,Globex,,Grace\n";
+        csv_to_dataframe(csv, describe).unwrap()
+    }
+
+    #[test]
+    fn test_csv_to_dataframe() {
+        use polars::prelude::DataType;
+
+        let describe: DescribeSObjectResult = serde_json::from_value(serde_json::json!({
+            "name": "Account",
+            "label": "Account",
+            "custom": false,
+            "fields": [
+                {"name": "Id", "label": "Id", "type": "id"},
+                {"name": "Name", "label": "Name", "type": "string"},
+                {"name": "NumberOfEmployees", "label": "Employees", "type": "int"}
+            ]
+        }))
+        .unwrap();
+
+        let df = frame(Some(&describe));
+        assert_eq!(df.height(), 2);
+        let employees = df.column("NumberOfEmployees").unwrap();
+        assert_eq!(employees.dtype(), &DataType::Int64);
+        assert_eq!(employees.null_count(), 1);
+        assert_eq!(df.column("Owner.Name").unwrap().dtype(), &DataType::String);
+
+        let untyped = frame(None);
+        assert_eq!(
+            untyped.column("NumberOfEmployees").unwrap().dtype(),
+            &DataType::String
+        );
+    }
+}
@@ -26,6 +26,8 @@ pub enum ErrorKind {
     Job(String),
     #[error("CSV error: {0}")]
     Csv(String),
+    #[error("Mapping error: {0}")]
+    Mapping(String),
     #[error("Upload error: {0}")]
     Upload(String),
     #[error("API error: {0}")]
@@ -5,10 +5,18 @@
 //! ## Features
 //!
 //! - **Ingest Jobs** - Insert, Update, Upsert, Delete, Hard Delete
-//! - **Query Jobs** - Query and QueryAll for large datasets with automatic SOQL injection prevention
-//! - **Job Management** - Create, monitor, abort, and delete jobs
-//! - **CSV Support** - Native CSV data handling
-//! - **Automatic Pagination** - Handle large result sets automatically
+//! - **Query Jobs** - Query and QueryAll for large datasets, end to end with automatic SOQL injection prevention or step by step
+//! - **Job Management** - Create, monitor, abort, and delete jobs; list ingest jobs by type and mode
+//! - **CSV Support** - Native CSV data handling, or typed records encoded with serde
+//! - **Chunked Ingest** - Split datasets over the per-job upload limit into parallel jobs
+//! - **Upload Recovery** - Retry failed uploads and replace jobs that can't take their data
+//! - **Data Loader Mappings** - Reuse `.sdl` field mapping files for ingest
+//! - **External ID Backfill** - Assign deterministic external IDs to existing records
+//! - **CSV Validation** - Check headers and values against describe metadata before uploading
+//! - **Bulk API 1.0** - XML and CSV batches, serial jobs, and PK-chunked extracts ([`v1`])
+//! - **Polars DataFrames** - Typed DataFrames from query results (`polars` feature)
+//! - **Arrow and Parquet** - Typed record batches streamed from query results (`arrow` feature), written to Parquet files (`parquet` feature)
+//! - **Automatic Pagination** - Handle large result sets automatically, or stream them row by row
 //! - **Security by Default** - QueryBuilder integration prevents SOQL injection
 //!
 //! ## Example - Safe Bulk Query
@@ -61,13 +69,40 @@
 //! }
 //! ```
 
+#[cfg(feature = "arrow")]
+mod arrow;
+mod backfill;
+mod chunking;
 mod client;
+#[cfg(feature = "polars")]
+mod dataframe;
 mod error;
+mod mapping;
+mod results;
+mod serialize;
 mod types;
+mod upload;
+pub mod v1;
+#[cfg(feature = "query-builder")]
+mod validation;
 
+#[cfg(feature = "arrow")]
+pub use arrow::{query_results_schema, rows_to_record_batch, DEFAULT_ARROW_BATCH_ROWS};
+#[cfg(feature = "arrow")]
+pub use arrow_array::RecordBatch;
+pub use backfill::{BackfillResult, ExternalIdBackfill, ExternalIdStrategy};
+pub use chunking::{ChunkedIngestResult, IngestChunking, DEFAULT_MAX_CHUNK_BYTES};
 pub use client::BulkApiClient;
+#[cfg(feature = "polars")]
+pub use dataframe::csv_to_dataframe;
 pub use error::{Error, ErrorKind, Result};
+pub use mapping::{FieldMapping, MappingEntry, MappingSource};
+pub use results::QueryResultRow;
+pub use serialize::to_ingest_csv;
 pub use types::*;
+pub use upload::{JobUpload, UploadProgress, UploadRecovery};
+#[cfg(feature = "query-builder")]
+pub use validation::{CsvIssue, CsvIssueKind, CsvValidationReport, CsvValidator};
 
 // Re-export QueryBuilder when the feature is enabled for convenient access
 #[cfg(feature = "query-builder")]
@@ -0,0 +1,361 @@
+//! Data Loader (`.sdl`) field mapping files.
+//!
+//! Data Loader stores column-to-field mappings as Java properties files:
+//!
+//! ```text
+//! #Mapping values
+//! ACCOUNT_NAME=Name
+//! PARENT_EXT_ID=Parent\:External_Id__c
+//! "Customer"=Type
+//! ```
+//!
+//! Each key is a CSV column (or a quoted constant) and each value is one or
+//! more comma-separated Salesforce fields. [`FieldMapping::apply`] rewrites a
+//! CSV so its header row matches what Bulk API 2.0 expects.
+
+use std::path::Path;
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Where the value for a mapped field comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappingSource {
+    /// A column in the source CSV, matched case-insensitively.
+    Column(String),
+    /// The same literal value for every row.
+    Constant(String),
+}
+
+/// One line of a mapping file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappingEntry {
+    pub source: MappingSource,
+    /// Salesforce fields in Data Loader notation (`Relationship:ExternalIdField`
+    /// for lookups). Empty when the column is deliberately unmapped.
+    pub fields: Vec<String>,
+}
+
+/// A Data Loader-compatible column-to-field mapping.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bulk::{BulkOperation, FieldMapping};
+///
+/// let mapping = FieldMapping::from_file("accountUpsert.sdl")?;
+/// let result = client
+///     .execute_ingest_with_mapping(
+///         "Account",
+///         BulkOperation::Upsert,
+///         &csv_data,
+///         &mapping,
+///         Some("External_Id__c"),
+///     )
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldMapping {
+    entries: Vec<MappingEntry>,
+}
+
+impl FieldMapping {
+    /// Create an empty mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map a CSV column to a Salesforce field.
+    pub fn with_column(mut self, column: impl Into<String>, field: impl Into<String>) -> Self {
+        self.entries.push(MappingEntry {
+            source: MappingSource::Column(column.into()),
+            fields: vec![field.into()],
+        });
+        self
+    }
+
+    /// Set a Salesforce field to the same value on every row.
+    pub fn with_constant(mut self, value: impl Into<String>, field: impl Into<String>) -> Self {
+        self.entries.push(MappingEntry {
+            source: MappingSource::Constant(value.into()),
+            fields: vec![field.into()],
+        });
+        self
+    }
+
+    /// The entries in file order.
+    pub fn entries(&self) -> &[MappingEntry] {
+        &self.entries
+    }
+
+    /// Parse the contents of a `.sdl` file.
+    pub fn parse(sdl: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (line_no, raw) in sdl.lines().enumerate() {
+            let line = raw.trim_start();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+                continue;
+            }
+            let (key, value) = split_property(line);
+            let key = unescape(key).map_err(|msg| {
+                Error::new(ErrorKind::Mapping(format!("line {}: {}", line_no + 1, msg)))
+            })?;
+            let value = unescape(value).map_err(|msg| {
+                Error::new(ErrorKind::Mapping(format!("line {}: {}", line_no + 1, msg)))
+            })?;
+
+            let source = match key.strip_prefix('"').and_then(|k| k.strip_suffix('"')) {
+                Some(constant) => MappingSource::Constant(constant.to_string()),
+                None => MappingSource::Column(key),
+            };
+            let fields = value
+                .split(',')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect();
+            entries.push(MappingEntry { source, fields });
+        }
+        Ok(Self { entries })
+    }
+
+    /// Read a `.sdl` file from disk.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let sdl = std::fs::read_to_string(path).map_err(|e| Error {
+            kind: ErrorKind::Mapping(format!("failed to read {}: {}", path.display(), e)),
+            source: Some(Box::new(e)),
+        })?;
+        Self::parse(&sdl)
+    }
+
+    /// Render the mapping in `.sdl` format.
+    pub fn to_sdl(&self) -> String {
+        let mut out = String::from("#Mapping values\n");
+        for entry in &self.entries {
+            let key = match &entry.source {
+                MappingSource::Column(column) => escape(column, true),
+                MappingSource::Constant(value) => format!("\"{}\"", escape(value, true)),
+            };
+            out.push_str(&key);
+            out.push('=');
+            out.push_str(&escape(&entry.fields.join(","), false));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Write the mapping to a `.sdl` file.
+    pub fn write_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_sdl()).map_err(|e| Error {
+            kind: ErrorKind::Mapping(format!("failed to write {}: {}", path.display(), e)),
+            source: Some(Box::new(e)),
+        })
+    }
+
+    /// Rewrite `csv_data` so its columns are the mapped Salesforce fields.
+    ///
+    /// Unmapped CSV columns are dropped, mapping entries whose column is not
+    /// in the CSV are ignored (as Data Loader does), and constants are added
+    /// to every row. Lookup targets written as `Relationship:Field` become
+    /// the Bulk API 2.0 form `Relationship.Field`.
+    pub fn apply(&self, csv_data: &str) -> Result<String> {
+        let mut reader = csv::ReaderBuilder::new().from_reader(csv_data.as_bytes());
+        let headers = reader.headers()?.clone();
+
+        // (value source, output header) for every output column
+        #[derive(Clone, Copy)]
+        enum Cell<'a> {
+            Index(usize),
+            Constant(&'a str),
+        }
+        let mut columns: Vec<(Cell<'_>, String)> = Vec::new();
+        for entry in &self.entries {
+            let cell = match &entry.source {
+                MappingSource::Column(name) => {
+                    match headers.iter().position(|h| h.eq_ignore_ascii_case(name)) {
+                        Some(idx) => Cell::Index(idx),
+                        None => continue,
+                    }
+                }
+                MappingSource::Constant(value) => Cell::Constant(value),
+            };
+            for field in &entry.fields {
+                columns.push((cell, bulk_field_name(field)));
+            }
+        }
+        if columns.is_empty() {
+            return Err(Error::new(ErrorKind::Mapping(
+                "no mapped columns found in CSV header".to_string(),
+            )));
+        }
+
+        let mut writer = csv::WriterBuilder::new().from_writer(Vec::new());
+        writer.write_record(columns.iter().map(|(_, header)| header.as_str()))?;
+        for record in reader.records() {
+            let record = record?;
+            writer.write_record(columns.iter().map(|(cell, _)| match cell {
+                Cell::Index(idx) => record.get(*idx).unwrap_or(""),
+                Cell::Constant(value) => value,
+            }))?;
+        }
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| Error::new(ErrorKind::Csv(e.to_string())))?;
+        String::from_utf8(bytes).map_err(|e| Error::new(ErrorKind::Csv(e.to_string())))
+    }
+}
+
+/// Convert Data Loader lookup notation (`Account:External_Id__c`) to the
+/// Bulk API 2.0 header form (`Account.External_Id__c`).
+fn bulk_field_name(field: &str) -> String {
+    match field.rsplit_once(':') {
+        Some((relationship, target)) => format!("{}.{}", relationship, target),
+        None => field.to_string(),
+    }
+}
+
+/// Split a properties line into its raw (still escaped) key and value.
+fn split_property(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '=' | ':' => return (&line[..i], line[i + 1..].trim_start()),
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+/// Resolve Java properties escapes (`\:`, `\=`, `\ `, `\\`, `\t`, `\uXXXX`, ...).
+fn unescape(s: &str) -> std::result::Result<String, String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.trim_end().chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('f') => out.push('\u{c}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let code = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid unicode escape \\u{}", hex))?;
+                out.push(code);
+            }
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    Ok(out)
+}
+
+/// Escape a key or value for a properties file.
+fn escape(s: &str, is_key: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ':' => out.push_str("\\:"),
+            '=' => out.push_str("\\="),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            ' ' if is_key || i == 0 => out.push_str("\\ "),
+            '#' | '!' if i == 0 => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDL: &str = "#Mapping values\n\
+        #Thu Jan 04 10:00:00 PST 2024\n\
+        ACCOUNT_NAME=Name\n\
+        Parent\\ Ext=Parent\\:External_Id__c\n\
+        \"Customer\"=Type\n\
+        PHONE=Phone, Fax\n\
+        NOTES=\n";
+
+    #[test]
+    fn test_parse_sdl() {
+        let mapping = FieldMapping::parse(SDL).unwrap();
+        let entries = mapping.entries();
+        assert_eq!(entries.len(), 5);
+        assert_eq!(
+            entries[0].source,
+            MappingSource::Column("ACCOUNT_NAME".to_string())
+        );
+        assert_eq!(
+            entries[1].source,
+            MappingSource::Column("Parent Ext".to_string())
+        );
+        assert_eq!(entries[1].fields, vec!["Parent:External_Id__c"]);
+        assert_eq!(
+            entries[2].source,
+            MappingSource::Constant("Customer".to_string())
+        );
+        assert_eq!(entries[3].fields, vec!["Phone", "Fax"]);
+        assert!(entries[4].fields.is_empty());
+    }
+
+    #[test]
+    fn test_sdl_roundtrip() {
+        let mapping = FieldMapping::parse(SDL).unwrap();
+        let reparsed = FieldMapping::parse(&mapping.to_sdl()).unwrap();
+        assert_eq!(mapping, reparsed);
+    }
+
+    #[test]
+    fn test_apply_mapping() {
+        let mapping = FieldMapping::parse(SDL).unwrap();
+        let csv_data = "account_name,Parent Ext,PHONE,NOTES,IGNORED\n\
+                        Acme,P-1,555-0100,hello,x\n\
+                        Globex,,555-0101,,y\n";
+        let mapped = mapping.apply(csv_data).unwrap();
+        assert_eq!(
+            mapped,
+            "Name,Parent.External_Id__c,Type,Phone,Fax\n\
+             Acme,P-1,Customer,555-0100,555-0100\n\
+             Globex,,Customer,555-0101,555-0101\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_no_matching_columns() {
+        let mapping = FieldMapping::new().with_column("MISSING", "Name");
+        let err = mapping.apply("A,B\n1,2\n").unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::Mapping(_)));
+    }
+
+    #[test]
+    fn test_builder_to_sdl() {
+        let sdl = FieldMapping::new()
+            .with_column("Acct Name", "Name")
+            .with_constant("Prospect", "Type")
+            .with_column("OWNER", "Owner:Username")
+            .to_sdl();
+        assert_eq!(
+            sdl,
+            "#Mapping values\nAcct\\ Name=Name\n\"Prospect\"=Type\nOWNER=Owner\\:Username\n"
+        );
+    }
+}
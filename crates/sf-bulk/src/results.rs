@@ -0,0 +1,174 @@
+//! Row-by-row decoding of query results as they download.
+
+use std::sync::Arc;
+
+use csv::StringRecord;
+use serde::de::DeserializeOwned;
+
+use crate::error::Result;
+
+/// One row of bulk query results.
+///
+/// Yielded by
+/// [`get_query_results_stream`](crate::BulkApiClient::get_query_results_stream).
+/// Values are the CSV text Salesforce returned; nulls are empty strings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResultRow {
+    headers: Arc<StringRecord>,
+    record: StringRecord,
+}
+
+impl QueryResultRow {
+    /// The value in `column`, or `None` if the results have no such column.
+    pub fn get(&self, column: &str) -> Option<&str> {
+        let index = self.headers.iter().position(|h| h == column)?;
+        self.record.get(index)
+    }
+
+    /// The column names, in result order.
+    pub fn columns(&self) -> &StringRecord {
+        &self.headers
+    }
+
+    /// The values, in column order.
+    pub fn values(&self) -> &StringRecord {
+        &self.record
+    }
+
+    /// Decode the row into a struct keyed by column name.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(self.record.deserialize(Some(&self.headers))?)
+    }
+}
+
+/// Splits a CSV body arriving in chunks into complete records.
+///
+/// Quoted values may contain line breaks, so a record ends at a newline
+/// only outside quotes. Only the incomplete tail of the body is held
+/// between chunks.
+#[derive(Debug, Default)]
+pub(crate) struct RowDecoder {
+    buf: Vec<u8>,
+    scanned: usize,
+    in_quotes: bool,
+    headers: Option<Arc<StringRecord>>,
+    page_header_pending: bool,
+}
+
+impl RowDecoder {
+    /// Start a new results page, whose first record repeats the header.
+    pub(crate) fn start_page(&mut self) {
+        self.buf.clear();
+        self.scanned = 0;
+        self.in_quotes = false;
+        self.page_header_pending = true;
+    }
+
+    /// Add a chunk of the page, returning the rows it completed.
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Result<Vec<QueryResultRow>> {
+        self.buf.extend_from_slice(chunk);
+        let mut end = None;
+        for (i, &byte) in self.buf.iter().enumerate().skip(self.scanned) {
+            match byte {
+                b'"' => self.in_quotes = !self.in_quotes,
+                b'\n' if !self.in_quotes => end = Some(i + 1),
+                _ => {}
+            }
+        }
+        self.scanned = self.buf.len();
+        let Some(end) = end else {
+            return Ok(Vec::new());
+        };
+        let complete: Vec<u8> = self.buf.drain(..end).collect();
+        self.scanned -= end;
+        self.decode(&complete)
+    }
+
+    /// End the page, returning the rows left in its tail.
+    pub(crate) fn finish_page(&mut self) -> Result<Vec<QueryResultRow>> {
+        let rest = std::mem::take(&mut self.buf);
+        self.scanned = 0;
+        self.decode(&rest)
+    }
+
+    fn decode(&mut self, bytes: &[u8]) -> Result<Vec<QueryResultRow>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(bytes);
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            if self.page_header_pending {
+                self.page_header_pending = false;
+                if self.headers.is_none() {
+                    self.headers = Some(Arc::new(record));
+                }
+                continue;
+            }
+            if let Some(headers) = &self.headers {
+                rows.push(QueryResultRow {
+                    headers: Arc::clone(headers),
+                    record,
+                });
+            }
+        }
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_split_across_chunks_and_pages() {
+        let mut decoder = RowDecoder::default();
+        decoder.start_page();
+        let mut rows = decoder
+            .push(b"\"Id\",\"Description\"\n\"001A\",\"two\nli")
+            .unwrap();
+        assert!(rows.is_empty());
+        rows.extend(
+            decoder
+                .push(b"nes\"\n\"001B\",\"say \"\"hi\"\"\"\n\"001C\"")
+                .unwrap(),
+        );
+        rows.extend(decoder.push(b",\"\"").unwrap());
+        rows.extend(decoder.finish_page().unwrap());
+
+        decoder.start_page();
+        rows.extend(
+            decoder
+                .push(b"\"Id\",\"Description\"\r\n\"001D\",\"x\"\r\n")
+                .unwrap(),
+        );
+        rows.extend(decoder.finish_page().unwrap());
+
+        let ids: Vec<_> = rows.iter().map(|r| r.get("Id").unwrap()).collect();
+        assert_eq!(ids, ["001A", "001B", "001C", "001D"]);
+        assert_eq!(rows[0].get("Description"), Some("two\nlines"));
+        assert_eq!(rows[1].get("Description"), Some("say \"hi\""));
+        assert_eq!(rows[2].get("Description"), Some(""));
+        assert_eq!(rows[0].get("Name"), None);
+    }
+
+    #[test]
+    fn test_row_deserialize() {
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Account {
+            id: String,
+            number_of_employees: Option<u32>,
+        }
+
+        let mut decoder = RowDecoder::default();
+        decoder.start_page();
+        let rows = decoder
+            .push(b"Id,NumberOfEmployees\n001A,12\n001B,\n")
+            .unwrap();
+        let accounts: Vec<Account> = rows.iter().map(|r| r.deserialize().unwrap()).collect();
+        assert_eq!(accounts[0].id, "001A");
+        assert_eq!(accounts[0].number_of_employees, Some(12));
+        assert_eq!(accounts[1].number_of_employees, None);
+    }
+}
@@ -165,6 +165,48 @@ impl ColumnDelimiter {
     }
 }
 
+/// Whether a job's batches run at the same time or one after another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConcurrencyMode {
+    /// Batches run in parallel (the Salesforce default).
+    #[default]
+    Parallel,
+    /// Batches run one at a time, avoiding record lock contention.
+    Serial,
+}
+
+impl ConcurrencyMode {
+    /// Get the API string for this mode.
+    pub fn api_name(&self) -> &'static str {
+        match self {
+            ConcurrencyMode::Parallel => "Parallel",
+            ConcurrencyMode::Serial => "Serial",
+        }
+    }
+}
+
+/// Kinds of ingest job, as listed by the ingest jobs endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobType {
+    /// Bulk API 2.0 job for big objects
+    BigObjectIngest,
+    /// Bulk API 1.0 job
+    Classic,
+    /// Bulk API 2.0 job
+    V2Ingest,
+}
+
+impl JobType {
+    /// Get the API string for this job type.
+    pub fn api_name(&self) -> &'static str {
+        match self {
+            JobType::BigObjectIngest => "BigObjectIngest",
+            JobType::Classic => "Classic",
+            JobType::V2Ingest => "V2Ingest",
+        }
+    }
+}
+
 // =============================================================================
 // Request Types
 // =============================================================================
@@ -244,10 +286,64 @@ impl CreateIngestJobRequest {
     }
 }
 
+/// Which ingest jobs to list.
+///
+/// Passed to
+/// [`get_ingest_jobs_stream`](crate::BulkApiClient::get_ingest_jobs_stream).
+/// With no filters set, every ingest job is listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestJobFilter {
+    /// Only jobs with (or without) PK chunking enabled
+    pub is_pk_chunking_enabled: Option<bool>,
+    /// Only jobs of this kind
+    pub job_type: Option<JobType>,
+    /// Only jobs in this concurrency mode
+    pub concurrency_mode: Option<ConcurrencyMode>,
+}
+
+impl IngestJobFilter {
+    /// A filter that matches every job.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only list jobs with (or without) PK chunking enabled.
+    pub fn with_pk_chunking_enabled(mut self, enabled: bool) -> Self {
+        self.is_pk_chunking_enabled = Some(enabled);
+        self
+    }
+
+    /// Only list jobs of this kind.
+    pub fn with_job_type(mut self, job_type: JobType) -> Self {
+        self.job_type = Some(job_type);
+        self
+    }
+
+    /// Only list jobs in this concurrency mode.
+    pub fn with_concurrency_mode(mut self, mode: ConcurrencyMode) -> Self {
+        self.concurrency_mode = Some(mode);
+        self
+    }
+
+    /// The query string for the first page, without the leading `?`.
+    pub(crate) fn query_string(&self) -> String {
+        let mut params = Vec::new();
+        if let Some(enabled) = self.is_pk_chunking_enabled {
+            params.push(format!("isPkChunkingEnabled={enabled}"));
+        }
+        if let Some(job_type) = self.job_type {
+            params.push(format!("jobType={}", job_type.api_name()));
+        }
+        if let Some(mode) = self.concurrency_mode {
+            params.push(format!("concurrencyMode={}", mode.api_name()));
+        }
+        params.join("&")
+    }
+}
+
 /// Request to create a query job (internal use only).
 ///
-/// This type is not exposed publicly to prevent bypassing SOQL injection prevention.
-/// Use `BulkApiClient::execute_query()` with QueryBuilder instead.
+/// Built by `BulkApiClient::create_query_job()` and `execute_query()`.
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct CreateQueryJobRequest {
@@ -273,7 +369,6 @@ impl CreateQueryJobRequest {
     }
 
     /// Use queryAll instead of query (includes deleted records).
-    #[allow(dead_code)]
     pub(crate) fn with_query_all(mut self) -> Self {
         self.operation = BulkOperation::QueryAll;
         self
@@ -324,6 +419,9 @@ pub struct IngestJob {
     /// Concurrency mode
     #[serde(default)]
     pub concurrency_mode: Option<String>,
+    /// Job type (V2Ingest, Classic, or BigObjectIngest)
+    #[serde(default)]
+    pub job_type: Option<String>,
     /// Error message if failed
     #[serde(default)]
     pub error_message: Option<String>,
@@ -680,6 +778,7 @@ mod tests {
                 total_processing_time: None,
                 api_version: None,
                 concurrency_mode: None,
+                job_type: None,
                 error_message: None,
             },
             successful_results: Some("Id,Name\n001xx,Acme".to_string()),
@@ -706,6 +805,7 @@ mod tests {
                 total_processing_time: None,
                 api_version: None,
                 concurrency_mode: None,
+                job_type: None,
                 error_message: None,
             },
             successful_results: None,
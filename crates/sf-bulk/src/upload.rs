@@ -0,0 +1,178 @@
+//! Recovering from failed uploads of ingest job data.
+//!
+//! The HTTP client already retries a request that fails transiently, but
+//! a load that keeps failing still leaves an open job behind with nothing
+//! in it. [`UploadRecovery`] retries the upload itself, checking the job is
+//! still open between attempts, and when the job can't be saved aborts it
+//! and uploads to a fresh one. A job only processes data once it is
+//! closed, so an aborted job never writes any records and recreating it
+//! can't load the data twice.
+
+use std::time::Duration;
+
+use busbar_sf_client::RetryOn;
+
+use crate::error::Error;
+
+/// How failed uploads of job data are retried and recovered.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bulk::{BulkApiClient, UploadRecovery};
+///
+/// let client = BulkApiClient::new(instance_url, token)?
+///     .with_upload_recovery(UploadRecovery::new().with_max_recreates(2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadRecovery {
+    pub(crate) max_attempts: u32,
+    pub(crate) max_recreates: u32,
+    pub(crate) retry_delay: Duration,
+}
+
+impl Default for UploadRecovery {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            max_recreates: 1,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl UploadRecovery {
+    /// Three uploads per job, then one replacement job.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Upload to each job at most `attempts` times. At least one upload
+    /// is always made.
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Abort and recreate the job at most `recreates` times. Zero gives
+    /// up once the first job's uploads are exhausted.
+    pub fn with_max_recreates(mut self, recreates: u32) -> Self {
+        self.max_recreates = recreates;
+        self
+    }
+
+    /// Wait `delay` before the second upload to a job, doubling it before
+    /// each one after.
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// The wait before the upload after attempt `attempt`.
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        self.retry_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+}
+
+/// One job's part in an upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobUpload {
+    /// The job uploaded to.
+    pub job_id: String,
+    /// Bytes Salesforce accepted. A job takes its data in a single request,
+    /// so this is either zero or all of it.
+    pub bytes_sent: usize,
+    /// Uploads made to the job.
+    pub attempts: u32,
+    /// Whether the job was aborted after its uploads failed.
+    pub aborted: bool,
+}
+
+/// The state of an upload, across every job it used.
+///
+/// Returned by
+/// [`upload_ingest_data_resumable`](crate::BulkApiClient::upload_ingest_data_resumable).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// Size of the data being uploaded.
+    pub bytes_total: usize,
+    /// Jobs in the order they were created; only the last can hold data.
+    pub jobs: Vec<JobUpload>,
+}
+
+impl UploadProgress {
+    pub(crate) fn new(bytes_total: usize) -> Self {
+        Self {
+            bytes_total,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// The job holding the data once the upload is complete.
+    pub fn job_id(&self) -> Option<&str> {
+        self.jobs
+            .last()
+            .filter(|job| job.bytes_sent == self.bytes_total)
+            .map(|job| job.job_id.as_str())
+    }
+
+    /// Whether every byte has been accepted by Salesforce.
+    pub fn is_complete(&self) -> bool {
+        self.job_id().is_some()
+    }
+
+    /// Jobs aborted along the way.
+    pub fn aborted_jobs(&self) -> impl Iterator<Item = &str> {
+        self.jobs
+            .iter()
+            .filter(|job| job.aborted)
+            .map(|job| job.job_id.as_str())
+    }
+}
+
+/// Whether `error` is a failure the HTTP client would retry: a dropped
+/// connection, timeout, rate limit, or server error.
+pub(crate) fn is_transient(error: &Error) -> bool {
+    error
+        .source
+        .as_deref()
+        .and_then(|source| source.downcast_ref::<busbar_sf_client::Error>())
+        .is_some_and(|e| RetryOn::ALL.iter().any(|class| class.matches(&e.kind)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_recovery_delay_doubles() {
+        let recovery = UploadRecovery::new()
+            .with_retry_delay(Duration::from_millis(100))
+            .with_max_attempts(0);
+        assert_eq!(recovery.max_attempts, 1);
+        assert_eq!(recovery.delay(1), Duration::from_millis(100));
+        assert_eq!(recovery.delay(3), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_upload_progress_job_id() {
+        let mut progress = UploadProgress::new(10);
+        progress.jobs.push(JobUpload {
+            job_id: "750A".to_string(),
+            bytes_sent: 0,
+            attempts: 3,
+            aborted: true,
+        });
+        assert_eq!(progress.job_id(), None);
+
+        progress.jobs.push(JobUpload {
+            job_id: "750B".to_string(),
+            bytes_sent: 10,
+            attempts: 1,
+            aborted: false,
+        });
+        assert_eq!(progress.job_id(), Some("750B"));
+        assert_eq!(progress.aborted_jobs().collect::<Vec<_>>(), ["750A"]);
+    }
+}
@@ -0,0 +1,940 @@
+//! Bulk API 1.0 client.
+//!
+//! Bulk API 2.0 (the rest of this crate) covers most loads, but 1.0 is
+//! still the way to extract very large tables: with PK chunking,
+//! Salesforce splits one query into batches over ranges of record IDs
+//! and runs them in parallel, where a 2.0 query job scans the table in one
+//! pass. 1.0 also takes XML batches and lets a job run its batches
+//! serially to avoid lock contention.
+//!
+//! Job and batch information is exchanged as XML; batch data is CSV or
+//! XML as chosen when the job is created.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use busbar_sf_bulk::v1::{BulkV1Client, PkChunking};
+//! use busbar_sf_bulk::QueryBuilder;
+//!
+//! let client = BulkV1Client::new(instance_url, access_token)?;
+//! let extract = client
+//!     .execute_pk_chunked_query(
+//!         QueryBuilder::new("Task")?.select(&["Id", "Subject", "WhatId"]),
+//!         PkChunking::new().with_chunk_size(250_000),
+//!     )
+//!     .await?;
+//! for csv in &extract.results {
+//!     // one CSV per chunk of up to 250,000 records
+//! }
+//! ```
+
+use std::time::Duration;
+
+use tokio::time::sleep;
+use tracing::instrument;
+
+use busbar_sf_client::security::xml;
+use busbar_sf_client::{ClientConfig, SalesforceClient};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::BulkOperation;
+pub use crate::types::ConcurrencyMode;
+
+/// Default polling interval for job status checks.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default maximum wait time for job completion.
+const DEFAULT_MAX_WAIT: Duration = Duration::from_secs(3600);
+
+/// Namespace of Bulk API 1.0 job and batch documents.
+const ASYNC_API_NAMESPACE: &str = "http://www.force.com/2009/06/asyncapi/dataload";
+
+/// Format of a job's batch data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContentType {
+    #[default]
+    Csv,
+    Xml,
+}
+
+impl ContentType {
+    /// Get the API string for this content type.
+    pub fn api_name(&self) -> &'static str {
+        match self {
+            ContentType::Csv => "CSV",
+            ContentType::Xml => "XML",
+        }
+    }
+
+    fn mime_type(&self) -> &'static str {
+        match self {
+            ContentType::Csv => "text/csv; charset=UTF-8",
+            ContentType::Xml => "application/xml; charset=UTF-8",
+        }
+    }
+}
+
+/// Bulk API 1.0 job states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobState {
+    /// Job is open and accepting batches
+    Open,
+    /// No more batches will be added; queued batches still run
+    Closed,
+    /// Job was aborted
+    Aborted,
+    /// Job failed
+    Failed,
+}
+
+impl JobState {
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "Open" => Ok(Self::Open),
+            "Closed" => Ok(Self::Closed),
+            "Aborted" => Ok(Self::Aborted),
+            "Failed" => Ok(Self::Failed),
+            other => Err(unexpected("job state", other)),
+        }
+    }
+}
+
+/// Bulk API 1.0 batch states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchState {
+    /// Waiting to be processed
+    Queued,
+    /// Being processed
+    InProgress,
+    /// Processed; individual records may still have failed
+    Completed,
+    /// Could not be processed; see the batch's state message
+    Failed,
+    /// Will not be processed, such as the original batch of a PK-chunked
+    /// query once its chunks are created
+    NotProcessed,
+}
+
+impl BatchState {
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "Queued" => Ok(Self::Queued),
+            "InProgress" => Ok(Self::InProgress),
+            "Completed" => Ok(Self::Completed),
+            "Failed" => Ok(Self::Failed),
+            "Not Processed" | "NotProcessed" => Ok(Self::NotProcessed),
+            other => Err(unexpected("batch state", other)),
+        }
+    }
+
+    /// Check if batch is in a terminal state.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, BatchState::Queued | BatchState::InProgress)
+    }
+}
+
+/// Primary key chunking for query jobs, sent as the
+/// `Sforce-Enable-PKChunking` header.
+///
+/// Salesforce splits the query into batches over consecutive ranges of
+/// record IDs. With no options set, its defaults apply (100,000 records
+/// per chunk).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PkChunking {
+    chunk_size: Option<u32>,
+    parent: Option<String>,
+    start_row: Option<String>,
+}
+
+impl PkChunking {
+    /// Chunking with Salesforce's defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records per chunk, up to 250,000.
+    pub fn with_chunk_size(mut self, size: u32) -> Self {
+        self.chunk_size = Some(size);
+        self
+    }
+
+    /// Chunk on the parent object's IDs, for sharing objects such as
+    /// `AccountShare` (parent `Account`).
+    pub fn with_parent(mut self, parent: impl Into<String>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+
+    /// Start the first chunk at this record ID.
+    pub fn with_start_row(mut self, id: impl Into<String>) -> Self {
+        self.start_row = Some(id.into());
+        self
+    }
+
+    /// The header value.
+    pub fn header_value(&self) -> String {
+        let mut options = Vec::new();
+        if let Some(size) = self.chunk_size {
+            options.push(format!("chunkSize={size}"));
+        }
+        if let Some(parent) = &self.parent {
+            options.push(format!("parent={parent}"));
+        }
+        if let Some(start_row) = &self.start_row {
+            options.push(format!("startRow={start_row}"));
+        }
+        if options.is_empty() {
+            "true".to_string()
+        } else {
+            options.join("; ")
+        }
+    }
+}
+
+/// Request to create a Bulk API 1.0 job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateJobRequest {
+    /// SObject API name
+    pub object: String,
+    /// Operation type
+    pub operation: BulkOperation,
+    /// External ID field for upsert
+    pub external_id_field_name: Option<String>,
+    /// Whether batches run in parallel or serially
+    pub concurrency_mode: ConcurrencyMode,
+    /// Format of the batch data
+    pub content_type: ContentType,
+    /// PK chunking, for query jobs
+    pub pk_chunking: Option<PkChunking>,
+}
+
+impl CreateJobRequest {
+    /// Create a new job request.
+    pub fn new(sobject: impl Into<String>, operation: BulkOperation) -> Self {
+        Self {
+            object: sobject.into(),
+            operation,
+            external_id_field_name: None,
+            concurrency_mode: ConcurrencyMode::default(),
+            content_type: ContentType::default(),
+            pk_chunking: None,
+        }
+    }
+
+    /// Set the external ID field for upsert operations.
+    pub fn with_external_id_field(mut self, field: impl Into<String>) -> Self {
+        self.external_id_field_name = Some(field.into());
+        self
+    }
+
+    /// Set whether batches run in parallel or serially.
+    pub fn with_concurrency_mode(mut self, mode: ConcurrencyMode) -> Self {
+        self.concurrency_mode = mode;
+        self
+    }
+
+    /// Set the format of the batch data.
+    pub fn with_content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    /// Split a query job into chunks by record ID.
+    pub fn with_pk_chunking(mut self, chunking: PkChunking) -> Self {
+        self.pk_chunking = Some(chunking);
+        self
+    }
+
+    fn to_xml(&self) -> String {
+        let mut body = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <jobInfo xmlns=\"{ASYNC_API_NAMESPACE}\">\
+             <operation>{}</operation><object>{}</object>",
+            self.operation.api_name(),
+            xml::escape(&self.object)
+        );
+        if let Some(field) = &self.external_id_field_name {
+            body.push_str(&format!(
+                "<externalIdFieldName>{}</externalIdFieldName>",
+                xml::escape(field)
+            ));
+        }
+        body.push_str(&format!(
+            "<concurrencyMode>{}</concurrencyMode><contentType>{}</contentType></jobInfo>",
+            self.concurrency_mode.api_name(),
+            self.content_type.api_name()
+        ));
+        body
+    }
+}
+
+/// A Bulk API 1.0 job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobInfo {
+    /// Job ID
+    pub id: String,
+    /// SObject API name
+    pub object: String,
+    /// Operation, as returned by the API
+    pub operation: String,
+    /// Current state
+    pub state: JobState,
+    /// Concurrency mode, as returned by the API
+    pub concurrency_mode: Option<String>,
+    /// Content type, as returned by the API
+    pub content_type: Option<String>,
+    /// Batches in the job
+    pub number_batches_total: i64,
+    /// Batches that completed
+    pub number_batches_completed: i64,
+    /// Batches that failed
+    pub number_batches_failed: i64,
+    /// Records processed across all batches
+    pub number_records_processed: i64,
+    /// Records that failed across all batches
+    pub number_records_failed: i64,
+}
+
+impl JobInfo {
+    fn from_xml(body: &str) -> Result<Self> {
+        Ok(Self {
+            id: required(body, "id")?,
+            object: element(body, "object").unwrap_or_default(),
+            operation: element(body, "operation").unwrap_or_default(),
+            state: JobState::parse(&required(body, "state")?)?,
+            concurrency_mode: element(body, "concurrencyMode"),
+            content_type: element(body, "contentType"),
+            number_batches_total: count(body, "numberBatchesTotal"),
+            number_batches_completed: count(body, "numberBatchesCompleted"),
+            number_batches_failed: count(body, "numberBatchesFailed"),
+            number_records_processed: count(body, "numberRecordsProcessed"),
+            number_records_failed: count(body, "numberRecordsFailed"),
+        })
+    }
+}
+
+/// A batch of a Bulk API 1.0 job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchInfo {
+    /// Batch ID
+    pub id: String,
+    /// ID of the job the batch belongs to
+    pub job_id: String,
+    /// Current state
+    pub state: BatchState,
+    /// Why the batch failed or wasn't processed
+    pub state_message: Option<String>,
+    /// Records processed
+    pub number_records_processed: i64,
+    /// Records that failed
+    pub number_records_failed: i64,
+}
+
+impl BatchInfo {
+    fn from_xml(body: &str) -> Result<Self> {
+        Ok(Self {
+            id: required(body, "id")?,
+            job_id: element(body, "jobId").unwrap_or_default(),
+            state: BatchState::parse(&required(body, "state")?)?,
+            state_message: element(body, "stateMessage").filter(|m| !m.is_empty()),
+            number_records_processed: count(body, "numberRecordsProcessed"),
+            number_records_failed: count(body, "numberRecordsFailed"),
+        })
+    }
+}
+
+/// Result of a PK-chunked query.
+#[derive(Debug, Clone)]
+pub struct PkChunkedQueryResult {
+    /// The closed job
+    pub job: JobInfo,
+    /// Every batch of the job, including the original unprocessed one
+    pub batches: Vec<BatchInfo>,
+    /// Result CSV of each chunk, each with its own header row
+    pub results: Vec<String>,
+}
+
+impl PkChunkedQueryResult {
+    /// Records returned across all chunks.
+    pub fn records_processed(&self) -> i64 {
+        self.batches
+            .iter()
+            .map(|b| b.number_records_processed)
+            .sum()
+    }
+}
+
+/// Salesforce Bulk API 1.0 client.
+///
+/// Provides low-level job and batch operations, plus
+/// [`execute_pk_chunked_query`](Self::execute_pk_chunked_query) for large
+/// extracts.
+#[derive(Debug, Clone)]
+pub struct BulkV1Client {
+    client: SalesforceClient,
+    poll_interval: Duration,
+    max_wait: Duration,
+}
+
+impl BulkV1Client {
+    /// Create a new Bulk API 1.0 client with the given instance URL and access token.
+    pub fn new(instance_url: impl Into<String>, access_token: impl Into<String>) -> Result<Self> {
+        let client = SalesforceClient::new(instance_url, access_token)?;
+        Ok(Self::from_client(client))
+    }
+
+    /// Create a new Bulk API 1.0 client with custom HTTP configuration.
+    pub fn with_config(
+        instance_url: impl Into<String>,
+        access_token: impl Into<String>,
+        config: ClientConfig,
+    ) -> Result<Self> {
+        let client = SalesforceClient::with_config(instance_url, access_token, config)?;
+        Ok(Self::from_client(client))
+    }
+
+    /// Create a Bulk API 1.0 client from an existing SalesforceClient.
+    pub fn from_client(client: SalesforceClient) -> Self {
+        Self {
+            client,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            max_wait: DEFAULT_MAX_WAIT,
+        }
+    }
+
+    /// Get the underlying SalesforceClient.
+    pub fn inner(&self) -> &SalesforceClient {
+        &self.client
+    }
+
+    /// Set the polling interval for batch status checks.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Set the maximum wait time for a job's batches to finish.
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    // =========================================================================
+    // Jobs
+    // =========================================================================
+
+    /// Create a new job.
+    #[instrument(skip(self, request), fields(object = %request.object))]
+    pub async fn create_job(&self, request: &CreateJobRequest) -> Result<JobInfo> {
+        let mut req = self
+            .request(self.client.post(&self.async_url("job")))
+            .text(request.to_xml())
+            .header("Content-Type", "application/xml; charset=UTF-8");
+        if let Some(chunking) = &request.pk_chunking {
+            req = req.header("Sforce-Enable-PKChunking", chunking.header_value());
+        }
+        let body = self.send(req, "create job").await?;
+        JobInfo::from_xml(&body)
+    }
+
+    /// Get a job's current state.
+    #[instrument(skip(self))]
+    pub async fn get_job(&self, job_id: &str) -> Result<JobInfo> {
+        let req = self.request(self.client.get(&self.async_url(&format!("job/{job_id}"))));
+        let body = self.send(req, "get job").await?;
+        JobInfo::from_xml(&body)
+    }
+
+    /// Close a job so no more batches can be added.
+    #[instrument(skip(self))]
+    pub async fn close_job(&self, job_id: &str) -> Result<JobInfo> {
+        self.set_job_state(job_id, "Closed").await
+    }
+
+    /// Abort a job; unprocessed batches are not run.
+    #[instrument(skip(self))]
+    pub async fn abort_job(&self, job_id: &str) -> Result<JobInfo> {
+        self.set_job_state(job_id, "Aborted").await
+    }
+
+    async fn set_job_state(&self, job_id: &str, state: &str) -> Result<JobInfo> {
+        let body = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <jobInfo xmlns=\"{ASYNC_API_NAMESPACE}\"><state>{state}</state></jobInfo>"
+        );
+        let req = self
+            .request(self.client.post(&self.async_url(&format!("job/{job_id}"))))
+            .text(body)
+            .header("Content-Type", "application/xml; charset=UTF-8");
+        let body = self.send(req, "update job").await?;
+        JobInfo::from_xml(&body)
+    }
+
+    // =========================================================================
+    // Batches
+    // =========================================================================
+
+    /// Add a batch of records to an ingest job.
+    ///
+    /// `data` must be in the job's `content_type`.
+    #[instrument(skip(self, data))]
+    pub async fn add_batch(
+        &self,
+        job_id: &str,
+        content_type: ContentType,
+        data: &str,
+    ) -> Result<BatchInfo> {
+        let req = self
+            .request(
+                self.client
+                    .post(&self.async_url(&format!("job/{job_id}/batch"))),
+            )
+            .text(data)
+            .header("Content-Type", content_type.mime_type());
+        let body = self.send(req, "add batch").await?;
+        BatchInfo::from_xml(&body)
+    }
+
+    /// Add a query batch to a query job.
+    ///
+    /// # Security
+    ///
+    /// `soql` is sent as-is. Build it with `QueryBuilder` or escape every
+    /// value with `busbar_sf_client::security::soql`; with the
+    /// `query-builder` feature,
+    /// [`execute_pk_chunked_query`](Self::execute_pk_chunked_query) does
+    /// this for you.
+    #[instrument(skip(self, soql))]
+    pub async fn add_query_batch(
+        &self,
+        job_id: &str,
+        content_type: ContentType,
+        soql: &str,
+    ) -> Result<BatchInfo> {
+        self.add_batch(job_id, content_type, soql).await
+    }
+
+    /// Get a batch's current state.
+    #[instrument(skip(self))]
+    pub async fn get_batch(&self, job_id: &str, batch_id: &str) -> Result<BatchInfo> {
+        let url = self.async_url(&format!("job/{job_id}/batch/{batch_id}"));
+        let body = self
+            .send(self.request(self.client.get(&url)), "get batch")
+            .await?;
+        BatchInfo::from_xml(&body)
+    }
+
+    /// Get every batch of a job, including the chunks a PK-chunked query
+    /// was split into.
+    #[instrument(skip(self))]
+    pub async fn get_batches(&self, job_id: &str) -> Result<Vec<BatchInfo>> {
+        let url = self.async_url(&format!("job/{job_id}/batch"));
+        let body = self
+            .send(self.request(self.client.get(&url)), "get batches")
+            .await?;
+        blocks(&body, "batchInfo")
+            .into_iter()
+            .map(BatchInfo::from_xml)
+            .collect()
+    }
+
+    /// Get the per-record results of an ingest batch, in the job's
+    /// content type.
+    #[instrument(skip(self))]
+    pub async fn get_batch_results(&self, job_id: &str, batch_id: &str) -> Result<String> {
+        let url = self.async_url(&format!("job/{job_id}/batch/{batch_id}/result"));
+        self.send(self.request(self.client.get(&url)), "get batch results")
+            .await
+    }
+
+    /// Get the IDs of a completed query batch's result sets.
+    #[instrument(skip(self))]
+    pub async fn get_query_result_ids(&self, job_id: &str, batch_id: &str) -> Result<Vec<String>> {
+        let url = self.async_url(&format!("job/{job_id}/batch/{batch_id}/result"));
+        let body = self
+            .send(self.request(self.client.get(&url)), "get query results")
+            .await?;
+        Ok(blocks(&body, "result").into_iter().map(unescape).collect())
+    }
+
+    /// Get one result set of a query batch, in the job's content type.
+    #[instrument(skip(self))]
+    pub async fn get_query_result(
+        &self,
+        job_id: &str,
+        batch_id: &str,
+        result_id: &str,
+    ) -> Result<String> {
+        let url = self.async_url(&format!("job/{job_id}/batch/{batch_id}/result/{result_id}"));
+        self.send(self.request(self.client.get(&url)), "get query result")
+            .await
+    }
+
+    /// Poll until every batch of a job has finished, returning them.
+    ///
+    /// A PK-chunked query reports only its original batch until the
+    /// chunks are created, then marks it `NotProcessed`, so this waits for
+    /// that too.
+    #[instrument(skip(self))]
+    pub async fn wait_for_batches(&self, job_id: &str) -> Result<Vec<BatchInfo>> {
+        let start = std::time::Instant::now();
+        loop {
+            let batches = self.get_batches(job_id).await?;
+            if !batches.is_empty() && batches.iter().all(|b| b.state.is_terminal()) {
+                return Ok(batches);
+            }
+            if start.elapsed() > self.max_wait {
+                return Err(Error::new(ErrorKind::Timeout(format!(
+                    "Batches of job {} did not finish within {:?}",
+                    job_id, self.max_wait
+                ))));
+            }
+            sleep(self.poll_interval).await;
+        }
+    }
+
+    // =========================================================================
+    // High-Level Operations
+    // =========================================================================
+
+    /// Extract a large table with a PK-chunked query job.
+    ///
+    /// Creates a CSV query job with PK chunking, adds the query, closes the
+    /// job, waits for every chunk, and downloads each chunk's results.
+    /// Fails if PK chunking couldn't be applied or any chunk failed.
+    ///
+    /// # Security
+    ///
+    /// QueryBuilder escapes all user input to prevent SOQL injection.
+    #[cfg(feature = "query-builder")]
+    #[instrument(skip(self, query_builder, chunking))]
+    pub async fn execute_pk_chunked_query<T>(
+        &self,
+        query_builder: busbar_sf_rest::QueryBuilder<T>,
+        chunking: PkChunking,
+    ) -> Result<PkChunkedQueryResult>
+    where
+        T: serde::de::DeserializeOwned + Clone,
+    {
+        let sobject = query_builder.sobject().to_string();
+        let soql = query_builder
+            .build()
+            .map_err(|e| Error::new(ErrorKind::Api(format!("Failed to build query: {}", e))))?;
+
+        let request =
+            CreateJobRequest::new(sobject, BulkOperation::Query).with_pk_chunking(chunking);
+        let job = self.create_job(&request).await?;
+        self.add_query_batch(&job.id, ContentType::Csv, &soql)
+            .await?;
+        self.close_job(&job.id).await?;
+
+        let batches = self.wait_for_batches(&job.id).await?;
+        if let Some(failed) = batches.iter().find(|b| b.state == BatchState::Failed) {
+            return Err(Error::new(ErrorKind::Job(format!(
+                "Batch {} of job {} failed: {}",
+                failed.id,
+                job.id,
+                failed.state_message.as_deref().unwrap_or("no message")
+            ))));
+        }
+
+        let mut results = Vec::new();
+        // The original batch is `NotProcessed` once the chunks exist, so
+        // only chunk batches are `Completed`.
+        for batch in batches.iter().filter(|b| b.state == BatchState::Completed) {
+            for result_id in self.get_query_result_ids(&job.id, &batch.id).await? {
+                results.push(
+                    self.get_query_result(&job.id, &batch.id, &result_id)
+                        .await?,
+                );
+            }
+        }
+
+        Ok(PkChunkedQueryResult {
+            job: self.get_job(&job.id).await?,
+            batches,
+            results,
+        })
+    }
+
+    // =========================================================================
+    // Helpers
+    // =========================================================================
+
+    fn async_url(&self, path: &str) -> String {
+        format!(
+            "{}/services/async/{}/{}",
+            self.client.instance_url(),
+            self.client.api_version(),
+            path
+        )
+    }
+
+    /// Bulk API 1.0 authenticates with the `X-SFDC-Session` header rather
+    /// than `Authorization`.
+    fn request(&self, req: busbar_sf_client::RequestBuilder) -> busbar_sf_client::RequestBuilder {
+        req.header("X-SFDC-Session", self.client.access_token())
+    }
+
+    /// Send a request and return the response body, turning Bulk API 1.0
+    /// error documents into errors that carry their exception code.
+    async fn send(&self, req: busbar_sf_client::RequestBuilder, action: &str) -> Result<String> {
+        match self.client.execute(req).await {
+            Ok(response) => Ok(response.text().await?),
+            Err(err) => {
+                let document = match &err.kind {
+                    busbar_sf_client::ErrorKind::Http { message, .. } => Some(message.as_str()),
+                    _ => None,
+                };
+                let exception = document.and_then(|body| {
+                    Some((
+                        element(body, "exceptionCode")?,
+                        element(body, "exceptionMessage")?,
+                    ))
+                });
+                match exception {
+                    Some((code, message)) => Err(Error {
+                        kind: ErrorKind::Api(format!("Failed to {action}: {code}: {message}")),
+                        source: Some(Box::new(err)),
+                    }),
+                    None => Err(err.into()),
+                }
+            }
+        }
+    }
+}
+
+fn unexpected(what: &str, value: &str) -> Error {
+    Error::new(ErrorKind::Api(format!("Unexpected {what}: {value}")))
+}
+
+/// The unescaped text of the first `<tag>` element.
+fn element(xml: &str, tag: &str) -> Option<String> {
+    blocks(xml, tag).first().map(|s| unescape(s))
+}
+
+fn required(xml: &str, tag: &str) -> Result<String> {
+    element(xml, tag)
+        .ok_or_else(|| Error::new(ErrorKind::Api(format!("Response has no <{tag}> element"))))
+}
+
+fn count(xml: &str, tag: &str) -> i64 {
+    element(xml, tag)
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or_default()
+}
+
+/// Return the contents of every `<tag>...</tag>` (or `<tag attr>...</tag>`)
+/// element. Bulk API 1.0 documents use a default namespace, so tags are
+/// matched without a prefix.
+fn blocks<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut blocks = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // Skip longer tag names that share the prefix (e.g. <result-list>).
+        if !after.starts_with(['>', ' ', '/']) {
+            rest = after;
+            continue;
+        }
+        let Some(gt) = after.find('>') else { break };
+        if after[..gt].ends_with('/') {
+            blocks.push("");
+            rest = &after[gt + 1..];
+            continue;
+        }
+        let content = &after[gt + 1..];
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+        blocks.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+    blocks
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pk_chunking_header() {
+        assert_eq!(PkChunking::new().header_value(), "true");
+        assert_eq!(
+            PkChunking::new()
+                .with_chunk_size(50_000)
+                .with_parent("Account")
+                .with_start_row("001000000000001")
+                .header_value(),
+            "chunkSize=50000; parent=Account; startRow=001000000000001"
+        );
+    }
+
+    #[test]
+    fn test_job_request_xml() {
+        let xml = CreateJobRequest::new("Contact", BulkOperation::Upsert)
+            .with_external_id_field("Legacy_Id__c")
+            .with_concurrency_mode(ConcurrencyMode::Serial)
+            .with_content_type(ContentType::Xml)
+            .to_xml();
+        assert!(xml.contains(
+            "<operation>upsert</operation><object>Contact</object>\
+             <externalIdFieldName>Legacy_Id__c</externalIdFieldName>\
+             <concurrencyMode>Serial</concurrencyMode><contentType>XML</contentType>"
+        ));
+    }
+
+    #[test]
+    fn test_batch_info_list_parsing() {
+        let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+<batchInfoList xmlns="http://www.force.com/2009/06/asyncapi/dataload">
+ <batchInfo><id>751A</id><jobId>750A</jobId><state>NotProcessed</state>
+  <stateMessage>PK Chunking &amp; more</stateMessage>
+  <numberRecordsProcessed>0</numberRecordsProcessed></batchInfo>
+ <batchInfo><id>751B</id><jobId>750A</jobId><state>Completed</state>
+  <numberRecordsProcessed>100000</numberRecordsProcessed>
+  <numberRecordsFailed>0</numberRecordsFailed></batchInfo>
+</batchInfoList>"#;
+        let batches: Vec<_> = blocks(body, "batchInfo")
+            .into_iter()
+            .map(|b| BatchInfo::from_xml(b).unwrap())
+            .collect();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].state, BatchState::NotProcessed);
+        assert_eq!(
+            batches[0].state_message.as_deref(),
+            Some("PK Chunking & more")
+        );
+        assert_eq!(batches[1].number_records_processed, 100_000);
+        assert!(batches.iter().all(|b| b.state.is_terminal()));
+    }
+
+    #[cfg(feature = "query-builder")]
+    #[tokio::test]
+    async fn test_execute_pk_chunked_query_wiremock() {
+        use busbar_sf_rest::QueryBuilder;
+        use wiremock::matchers::{body_string_contains, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let job = |state: &str| {
+            format!(
+                "<jobInfo xmlns=\"{ASYNC_API_NAMESPACE}\"><id>750A</id><object>Account</object>\
+                 <operation>query</operation><state>{state}</state></jobInfo>"
+            )
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/services/async/62.0/job"))
+            .and(header("X-SFDC-Session", "test-token"))
+            .and(header("Sforce-Enable-PKChunking", "chunkSize=2"))
+            .and(body_string_contains("<object>Account</object>"))
+            .respond_with(ResponseTemplate::new(201).set_body_string(job("Open")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/async/62.0/job/750A/batch"))
+            .and(body_string_contains("SELECT Id, Name FROM Account"))
+            .respond_with(ResponseTemplate::new(201).set_body_string(
+                "<batchInfo><id>751A</id><jobId>750A</jobId><state>Queued</state></batchInfo>",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/async/62.0/job/750A"))
+            .and(body_string_contains("<state>Closed</state>"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(job("Closed")))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/async/62.0/job/750A"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(job("Closed")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/async/62.0/job/750A/batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<batchInfoList>\
+                 <batchInfo><id>751A</id><state>NotProcessed</state></batchInfo>\
+                 <batchInfo><id>751B</id><state>Completed</state>\
+                 <numberRecordsProcessed>2</numberRecordsProcessed></batchInfo>\
+                 <batchInfo><id>751C</id><state>Completed</state>\
+                 <numberRecordsProcessed>1</numberRecordsProcessed></batchInfo>\
+                 </batchInfoList>",
+            ))
+            .mount(&mock_server)
+            .await;
+        for (batch, csv) in [
+            (
+                "751B",
+                "\"Id\",\"Name\"\n\"001A\",\"Acme\"\n\"001B\",\"Globex\"\n",
+            ),
+            ("751C", "\"Id\",\"Name\"\n\"001C\",\"Initech\"\n"),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/services/async/62.0/job/750A/batch/{batch}/result")))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    "<result-list xmlns=\"{ASYNC_API_NAMESPACE}\"><result>752{batch}</result></result-list>"
+                )))
+                .mount(&mock_server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "/services/async/62.0/job/750A/batch/{batch}/result/752{batch}"
+                )))
+                .respond_with(ResponseTemplate::new(200).set_body_string(csv))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let client = BulkV1Client::new(mock_server.uri(), "test-token").unwrap();
+        let extract = client
+            .execute_pk_chunked_query(
+                QueryBuilder::<serde_json::Value>::new("Account")
+                    .unwrap()
+                    .select(&["Id", "Name"]),
+                PkChunking::new().with_chunk_size(2),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(extract.batches.len(), 3);
+        assert_eq!(extract.records_processed(), 3);
+        assert_eq!(extract.results.len(), 2);
+        assert!(extract.results[1].contains("Initech"));
+    }
+
+    #[tokio::test]
+    async fn test_error_document_is_reported() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/services/async/62.0/job/750A"))
+            .respond_with(ResponseTemplate::new(400).set_body_string(
+                "<error xmlns=\"http://www.force.com/2009/06/asyncapi/dataload\">\
+                 <exceptionCode>InvalidJob</exceptionCode>\
+                 <exceptionMessage>Invalid job id: 750A</exceptionMessage></error>",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let client = BulkV1Client::new(mock_server.uri(), "test-token").unwrap();
+        let err = client.get_job("750A").await.unwrap_err();
+        assert!(err.to_string().contains("InvalidJob: Invalid job id: 750A"));
+    }
+}
@@ -0,0 +1,553 @@
+//! Pre-upload CSV validation.
+//!
+//! A bad header or a malformed date is usually only reported once the job
+//! has run, one failed row at a time, after the job has already counted
+//! against the org's daily limit. [`CsvValidator`] checks a CSV against the
+//! object's describe first: every column must name a field the operation
+//! can write, required fields must be present for inserts, and values are
+//! checked against the field types.
+
+use std::collections::HashSet;
+
+use busbar_sf_client::security::url as url_security;
+use busbar_sf_rest::{DescribeSObjectResult, FieldDescribe};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::BulkOperation;
+
+/// How Bulk API 2.0 CSVs spell an explicit null.
+const NULL_VALUE: &str = "#N/A";
+
+/// Default number of data rows whose values are checked.
+const DEFAULT_SAMPLE_ROWS: usize = 1000;
+
+/// What is wrong with a column or value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CsvIssueKind {
+    /// The column isn't a field or relationship on the object.
+    UnknownField,
+    /// The field exists but the operation can't set it.
+    NotWritable,
+    /// The same field appears in more than one column.
+    DuplicateColumn,
+    /// A column the operation needs is missing: `Id` for updates and
+    /// deletes, the external ID field for upserts, required fields for
+    /// inserts.
+    MissingColumn,
+    /// A required field is empty or `#N/A`.
+    MissingValue,
+    /// The value can't be converted to the field's type, or isn't one of a
+    /// restricted picklist's values.
+    InvalidValue,
+    /// The value is longer than the field allows.
+    TooLong,
+    /// The row has a different number of values than the header.
+    ColumnCount,
+}
+
+/// One problem found in a CSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvIssue {
+    /// Data row the issue is in, starting at 1 for the row after the header.
+    /// `None` for header issues.
+    pub row: Option<usize>,
+    /// The column header, when the issue is about a single column.
+    pub column: Option<String>,
+    pub kind: CsvIssueKind,
+    pub message: String,
+}
+
+/// Everything [`CsvValidator::validate`] found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CsvValidationReport {
+    /// Data rows whose values were checked.
+    pub rows_checked: usize,
+    pub issues: Vec<CsvIssue>,
+}
+
+impl CsvValidationReport {
+    /// Whether no issues were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues about the header row.
+    pub fn header_issues(&self) -> impl Iterator<Item = &CsvIssue> {
+        self.issues.iter().filter(|issue| issue.row.is_none())
+    }
+}
+
+/// Checks ingest CSVs against an object's describe.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_bulk::{BulkOperation, CsvValidator};
+///
+/// let describe = rest_client.describe_sobject("Contact").await?;
+/// let report = CsvValidator::new(&describe, BulkOperation::Insert).validate(&csv_data)?;
+/// for issue in &report.issues {
+///     eprintln!("row {:?}, {:?}: {}", issue.row, issue.column, issue.message);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CsvValidator<'a> {
+    describe: &'a DescribeSObjectResult,
+    operation: BulkOperation,
+    external_id_field: Option<String>,
+    sample_rows: usize,
+}
+
+/// How a header resolved against the describe.
+enum Column<'a> {
+    Field(&'a FieldDescribe),
+    /// `Relationship.ExternalIdField`; the value is checked by Salesforce
+    /// against the related object.
+    Relationship(&'a FieldDescribe),
+    Unresolved,
+}
+
+impl<'a> CsvValidator<'a> {
+    /// Validate CSVs for `operation` on the object `describe` describes.
+    pub fn new(describe: &'a DescribeSObjectResult, operation: BulkOperation) -> Self {
+        Self {
+            describe,
+            operation,
+            external_id_field: None,
+            sample_rows: DEFAULT_SAMPLE_ROWS,
+        }
+    }
+
+    /// The external ID field an upsert matches on.
+    pub fn with_external_id_field(mut self, field: impl Into<String>) -> Self {
+        self.external_id_field = Some(field.into());
+        self
+    }
+
+    /// Check values in at most `rows` data rows (default 1000). Headers are
+    /// always checked.
+    pub fn with_sample_rows(mut self, rows: usize) -> Self {
+        self.sample_rows = rows;
+        self
+    }
+
+    /// Validate `csv_data`.
+    ///
+    /// Fails only if the operation isn't an ingest operation or the CSV
+    /// can't be parsed; everything else is reported in the returned report.
+    pub fn validate(&self, csv_data: &str) -> Result<CsvValidationReport> {
+        if matches!(
+            self.operation,
+            BulkOperation::Query | BulkOperation::QueryAll
+        ) {
+            return Err(Error::new(ErrorKind::Csv(format!(
+                "{} is not an ingest operation",
+                self.operation.api_name()
+            ))));
+        }
+
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(csv_data.as_bytes());
+        let headers = reader.headers()?.clone();
+        let mut report = CsvValidationReport::default();
+
+        let columns: Vec<Column<'a>> = headers
+            .iter()
+            .map(|header| self.resolve(header, &mut report))
+            .collect();
+        self.check_required_columns(&headers, &columns, &mut report);
+
+        for (index, record) in reader.records().take(self.sample_rows).enumerate() {
+            let record = record?;
+            let row = index + 1;
+            report.rows_checked += 1;
+            if record.len() != headers.len() {
+                report.issues.push(CsvIssue {
+                    row: Some(row),
+                    column: None,
+                    kind: CsvIssueKind::ColumnCount,
+                    message: format!(
+                        "Row has {} values but the header has {} columns",
+                        record.len(),
+                        headers.len()
+                    ),
+                });
+                continue;
+            }
+            for ((header, column), value) in headers.iter().zip(&columns).zip(record.iter()) {
+                if let Column::Field(field) = column {
+                    if let Some((kind, message)) = self.check_value(field, value) {
+                        report.issues.push(CsvIssue {
+                            row: Some(row),
+                            column: Some(header.to_string()),
+                            kind,
+                            message,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Match a header to a field and check the operation may write it.
+    fn resolve(&self, header: &str, report: &mut CsvValidationReport) -> Column<'a> {
+        let mut issue = |kind, message| {
+            report.issues.push(CsvIssue {
+                row: None,
+                column: Some(header.to_string()),
+                kind,
+                message,
+            })
+        };
+
+        let (column, field) = match header.split_once('.') {
+            Some((relationship, _)) => {
+                // Polymorphic lookups name the target type: `Owner:User.Email`
+                let relationship = relationship.split(':').next().unwrap_or(relationship);
+                let field = self.describe.fields.iter().find(|f| {
+                    f.relationship_name
+                        .as_deref()
+                        .is_some_and(|name| name.eq_ignore_ascii_case(relationship))
+                });
+                match field {
+                    Some(field) => (Column::Relationship(field), field),
+                    None => {
+                        issue(
+                            CsvIssueKind::UnknownField,
+                            format!(
+                                "{} has no relationship named {}",
+                                self.describe.name, relationship
+                            ),
+                        );
+                        return Column::Unresolved;
+                    }
+                }
+            }
+            None => match self.field(header) {
+                Some(field) => (Column::Field(field), field),
+                None => {
+                    issue(
+                        CsvIssueKind::UnknownField,
+                        format!("{} has no field named {}", self.describe.name, header),
+                    );
+                    return Column::Unresolved;
+                }
+            },
+        };
+
+        let is_id = field.name == "Id";
+        let writable = match self.operation {
+            BulkOperation::Insert => field.createable,
+            BulkOperation::Update => is_id || field.updateable,
+            BulkOperation::Upsert => {
+                is_id || field.createable || field.updateable || self.is_external_id(field)
+            }
+            BulkOperation::Delete | BulkOperation::HardDelete => is_id,
+            BulkOperation::Query | BulkOperation::QueryAll => false,
+        };
+        if !writable {
+            issue(
+                CsvIssueKind::NotWritable,
+                format!(
+                    "{}.{} can't be set by {}",
+                    self.describe.name,
+                    field.name,
+                    self.operation.api_name()
+                ),
+            );
+        }
+        column
+    }
+
+    /// Report columns the operation needs that the header lacks, and
+    /// fields set by more than one column.
+    fn check_required_columns(
+        &self,
+        headers: &csv::StringRecord,
+        columns: &[Column<'_>],
+        report: &mut CsvValidationReport,
+    ) {
+        let mut present = HashSet::new();
+        for (header, column) in headers.iter().zip(columns) {
+            let field = match column {
+                Column::Field(field) | Column::Relationship(field) => field,
+                Column::Unresolved => continue,
+            };
+            if !present.insert(field.name.as_str()) {
+                report.issues.push(CsvIssue {
+                    row: None,
+                    column: Some(header.to_string()),
+                    kind: CsvIssueKind::DuplicateColumn,
+                    message: format!("{} is set by more than one column", field.name),
+                });
+            }
+        }
+
+        let mut missing = |field: &str, why: &str| {
+            if !present.contains(field) {
+                report.issues.push(CsvIssue {
+                    row: None,
+                    column: None,
+                    kind: CsvIssueKind::MissingColumn,
+                    message: format!("Missing {} column: {}", field, why),
+                });
+            }
+        };
+        match self.operation {
+            BulkOperation::Update | BulkOperation::Delete | BulkOperation::HardDelete => {
+                missing("Id", "records are matched by Id");
+            }
+            BulkOperation::Upsert => {
+                let key = self
+                    .external_id_field
+                    .as_deref()
+                    .and_then(|name| self.field(name))
+                    .map_or("Id", |field| field.name.as_str());
+                missing(key, "records are matched on it");
+            }
+            BulkOperation::Insert => {
+                for field in self.describe.fields.iter().filter(|f| is_required(f)) {
+                    missing(&field.name, "the field is required");
+                }
+            }
+            BulkOperation::Query | BulkOperation::QueryAll => {}
+        }
+    }
+
+    /// Check one value against its field.
+    fn check_value(&self, field: &FieldDescribe, value: &str) -> Option<(CsvIssueKind, String)> {
+        if value.is_empty() || value == NULL_VALUE {
+            let required = match self.operation {
+                BulkOperation::Insert => is_required(field),
+                BulkOperation::Upsert => self.is_external_id(field),
+                _ => field.name == "Id",
+            };
+            return required.then(|| {
+                (
+                    CsvIssueKind::MissingValue,
+                    format!("{} is required", field.name),
+                )
+            });
+        }
+
+        let time = value.strip_suffix('Z').unwrap_or(value);
+        let expected = match field.field_type.as_str() {
+            "boolean"
+                if !["true", "false", "1", "0"]
+                    .iter()
+                    .any(|b| value.eq_ignore_ascii_case(b)) =>
+            {
+                Some("boolean")
+            }
+            "int" if value.parse::<i64>().is_err() => Some("integer"),
+            "double" | "currency" | "percent" if value.parse::<f64>().is_err() => Some("number"),
+            "date" if chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err() => {
+                Some("date (YYYY-MM-DD)")
+            }
+            "datetime" if !is_datetime(value) => Some("datetime (YYYY-MM-DDThh:mm:ss.sssZ)"),
+            "time" if chrono::NaiveTime::parse_from_str(time, "%H:%M:%S%.f").is_err() => {
+                Some("time (hh:mm:ss.sssZ)")
+            }
+            "id" | "reference" if !url_security::is_valid_salesforce_id(value) => {
+                Some("Salesforce ID")
+            }
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            return Some((
+                CsvIssueKind::InvalidValue,
+                format!("{:?} is not a valid {}", value, expected),
+            ));
+        }
+
+        if matches!(field.field_type.as_str(), "picklist" | "multipicklist")
+            && field.restricted_picklist == Some(true)
+        {
+            let allowed = field.picklist_values.iter().flatten().filter(|p| p.active);
+            if let Some(bad) = value
+                .split(';')
+                .find(|v| !allowed.clone().any(|p| p.value == *v))
+            {
+                return Some((
+                    CsvIssueKind::InvalidValue,
+                    format!("{:?} is not an active value of {}", bad, field.name),
+                ));
+            }
+        }
+
+        match field.length {
+            Some(max) if max > 0 && value.chars().count() > max as usize => Some((
+                CsvIssueKind::TooLong,
+                format!(
+                    "{} characters is longer than {} allows ({})",
+                    value.chars().count(),
+                    field.name,
+                    max
+                ),
+            )),
+            _ => None,
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<&'a FieldDescribe> {
+        self.describe
+            .fields
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    fn is_external_id(&self, field: &FieldDescribe) -> bool {
+        self.external_id_field
+            .as_deref()
+            .is_some_and(|name| field.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Whether an insert must set `field`.
+fn is_required(field: &FieldDescribe) -> bool {
+    field.createable
+        && !field.nillable
+        && !field.defaulted_on_create.unwrap_or(false)
+        && field.field_type != "boolean"
+}
+
+fn is_datetime(value: &str) -> bool {
+    chrono::DateTime::parse_from_rfc3339(value).is_ok()
+        || chrono::DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z").is_ok()
+        || chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe() -> DescribeSObjectResult {
+        serde_json::from_value(serde_json::json!({
+            "name": "Contact",
+            "label": "Contact",
+            "custom": false,
+            "fields": [
+                {"name": "Id", "label": "Id", "type": "id", "length": 18,
+                 "nillable": false, "defaultedOnCreate": true},
+                {"name": "LastName", "label": "Last Name", "type": "string", "length": 10,
+                 "createable": true, "updateable": true, "nillable": false},
+                {"name": "Birthdate", "label": "Birthdate", "type": "date",
+                 "createable": true, "updateable": true, "nillable": true},
+                {"name": "AccountId", "label": "Account", "type": "reference",
+                 "createable": true, "updateable": true, "nillable": true,
+                 "relationshipName": "Account", "referenceTo": ["Account"]},
+                {"name": "Level__c", "label": "Level", "type": "picklist",
+                 "createable": true, "updateable": true, "nillable": true,
+                 "restrictedPicklist": true,
+                 "picklistValues": [
+                     {"value": "Primary", "label": "Primary", "active": true, "defaultValue": false},
+                     {"value": "Old", "label": "Old", "active": false, "defaultValue": false}
+                 ]},
+                {"name": "Legacy_Key__c", "label": "Legacy Key", "type": "string", "length": 36,
+                 "createable": true, "updateable": true, "nillable": true, "externalId": true},
+                {"name": "CreatedDate", "label": "Created Date", "type": "datetime",
+                 "nillable": false, "defaultedOnCreate": true}
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn kinds(report: &CsvValidationReport) -> Vec<(Option<usize>, CsvIssueKind)> {
+        report.issues.iter().map(|i| (i.row, i.kind)).collect()
+    }
+
+    #[test]
+    fn test_valid_insert() {
+        let describe = describe();
+        let csv = "LastName,Birthdate,Account.Legacy_Key__c,Level__c\n\
+                   Smith,1980-04-01,A-1,Primary\n\
+                   Jones,#N/A,A-2,\n";
+        let report = CsvValidator::new(&describe, BulkOperation::Insert)
+            .validate(csv)
+            .unwrap();
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.rows_checked, 2);
+    }
+
+    #[test]
+    fn test_header_issues() {
+        let describe = describe();
+        let csv = "Birthdate,Nickname__c,CreatedDate,Parent.Name,birthdate\n";
+        let report = CsvValidator::new(&describe, BulkOperation::Insert)
+            .validate(csv)
+            .unwrap();
+        assert_eq!(
+            kinds(&report),
+            vec![
+                (None, CsvIssueKind::UnknownField),
+                (None, CsvIssueKind::NotWritable),
+                (None, CsvIssueKind::UnknownField),
+                (None, CsvIssueKind::DuplicateColumn),
+                (None, CsvIssueKind::MissingColumn),
+            ]
+        );
+        assert!(report.issues[4].message.contains("LastName"));
+
+        let report = CsvValidator::new(&describe, BulkOperation::Upsert)
+            .with_external_id_field("Legacy_Key__c")
+            .validate("LastName\nSmith\n")
+            .unwrap();
+        assert_eq!(kinds(&report), vec![(None, CsvIssueKind::MissingColumn)]);
+        assert!(report.issues[0].message.contains("Legacy_Key__c"));
+    }
+
+    #[test]
+    fn test_value_issues() {
+        let describe = describe();
+        let csv = "Id,LastName,Birthdate,AccountId,Level__c\n\
+                   003xx000004TmiQAAS,Smith,04/01/1980,001xx000003DGb2AAG,Primary\n\
+                   003xx000004TmiRAAS,#N/A,1980-04-01,not-an-id,Old\n\
+                   003xx000004TmiSAAS,Featherstonehaugh,,,\n\
+                   003xx000004TmiTAAS,Short\n";
+        let report = CsvValidator::new(&describe, BulkOperation::Update)
+            .validate(csv)
+            .unwrap();
+        assert_eq!(
+            kinds(&report),
+            vec![
+                (Some(1), CsvIssueKind::InvalidValue),
+                (Some(2), CsvIssueKind::InvalidValue),
+                (Some(2), CsvIssueKind::InvalidValue),
+                (Some(3), CsvIssueKind::TooLong),
+                (Some(4), CsvIssueKind::ColumnCount),
+            ]
+        );
+        assert_eq!(report.issues[0].column.as_deref(), Some("Birthdate"));
+
+        let report = CsvValidator::new(&describe, BulkOperation::Insert)
+            .with_sample_rows(1)
+            .validate("LastName\n\nSmith\n")
+            .unwrap();
+        assert_eq!(report.rows_checked, 1);
+    }
+
+    #[test]
+    fn test_required_values_on_insert() {
+        let describe = describe();
+        let report = CsvValidator::new(&describe, BulkOperation::Insert)
+            .validate("LastName,Birthdate\n#N/A,\n")
+            .unwrap();
+        assert_eq!(kinds(&report), vec![(Some(1), CsvIssueKind::MissingValue)]);
+    }
+
+    #[test]
+    fn test_delete_and_query() {
+        let describe = describe();
+        let report = CsvValidator::new(&describe, BulkOperation::Delete)
+            .validate("Id,LastName\n003xx000004TmiQAAS,Smith\n")
+            .unwrap();
+        assert_eq!(kinds(&report), vec![(None, CsvIssueKind::NotWritable)]);
+
+        assert!(CsvValidator::new(&describe, BulkOperation::Query)
+            .validate("Id\n")
+            .is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Salesforce call option headers.
+
+use crate::request::RequestBuilder;
+
+const CALL_OPTIONS: &str = "Sforce-Call-Options";
+const AUTO_ASSIGN: &str = "Sforce-Auto-Assign";
+const QUERY_OPTIONS: &str = "Sforce-Query-Options";
+const ACCEPT_LANGUAGE: &str = "Accept-Language";
+
+/// Options sent as `Sforce-Call-Options`, `Sforce-Auto-Assign`,
+/// `Sforce-Query-Options`, and `Accept-Language` headers.
+///
+/// Set them for every request with
+/// [`ClientConfigBuilder::with_call_options`](crate::ClientConfigBuilder::with_call_options)
+/// or [`SalesforceClient::with_call_options`](crate::SalesforceClient::with_call_options),
+/// or for one request with [`RequestBuilder::call_options`]. Headers set on
+/// a request take precedence over the client's for that header.
+///
+/// # Example
+///
+/// ```
+/// use busbar_sf_client::{AutoAssign, CallOptions};
+///
+/// let options = CallOptions::new()
+///     .with_client("acme-sync/2.1")
+///     .with_default_namespace("acme")
+///     .with_auto_assign(AutoAssign::Disabled)
+///     .with_query_batch_size(500);
+/// assert_eq!(
+///     options.call_options_header().as_deref(),
+///     Some("client=acme-sync/2.1, defaultNamespace=acme")
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallOptions {
+    /// Client identifier, e.g. a partner application token.
+    pub client: Option<String>,
+    /// Namespace prefix to assume for unqualified names.
+    pub default_namespace: Option<String>,
+    /// Whether assignment rules run when Cases and Leads are created or updated.
+    pub auto_assign: Option<AutoAssign>,
+    /// Records per query batch; Salesforce accepts 200 to 2000.
+    pub query_batch_size: Option<u32>,
+    /// Language for labels, picklist values, and error messages, e.g.
+    /// `de` or `fr-CA`. Describes are returned translated into it.
+    pub accept_language: Option<String>,
+}
+
+/// Value of the `Sforce-Auto-Assign` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutoAssign {
+    /// Run the active assignment rule.
+    Enabled,
+    /// Don't run assignment rules.
+    Disabled,
+    /// Run the assignment rule with this ID.
+    Rule(String),
+}
+
+impl AutoAssign {
+    /// The header value.
+    pub fn header_value(&self) -> &str {
+        match self {
+            AutoAssign::Enabled => "TRUE",
+            AutoAssign::Disabled => "FALSE",
+            AutoAssign::Rule(id) => id,
+        }
+    }
+}
+
+impl CallOptions {
+    /// Options with nothing set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the client identifier.
+    pub fn with_client(mut self, client: impl Into<String>) -> Self {
+        self.client = Some(client.into());
+        self
+    }
+
+    /// Set the default namespace.
+    pub fn with_default_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.default_namespace = Some(namespace.into());
+        self
+    }
+
+    /// Set assignment rule behavior.
+    pub fn with_auto_assign(mut self, auto_assign: AutoAssign) -> Self {
+        self.auto_assign = Some(auto_assign);
+        self
+    }
+
+    /// Set the query batch size.
+    pub fn with_query_batch_size(mut self, batch_size: u32) -> Self {
+        self.query_batch_size = Some(batch_size);
+        self
+    }
+
+    /// Set the language describes and messages are returned in.
+    pub fn with_accept_language(mut self, language: impl Into<String>) -> Self {
+        self.accept_language = Some(language.into());
+        self
+    }
+
+    /// Whether no option is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// The `Sforce-Call-Options` header value, if the client or default
+    /// namespace is set.
+    pub fn call_options_header(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            self.client.as_ref().map(|c| format!("client={c}")),
+            self.default_namespace
+                .as_ref()
+                .map(|ns| format!("defaultNamespace={ns}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// The headers these options produce.
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        if let Some(value) = self.call_options_header() {
+            headers.push((CALL_OPTIONS, value));
+        }
+        if let Some(ref auto_assign) = self.auto_assign {
+            headers.push((AUTO_ASSIGN, auto_assign.header_value().to_string()));
+        }
+        if let Some(batch_size) = self.query_batch_size {
+            headers.push((QUERY_OPTIONS, format!("batchSize={batch_size}")));
+        }
+        if let Some(ref language) = self.accept_language {
+            headers.push((ACCEPT_LANGUAGE, language.clone()));
+        }
+        headers
+    }
+
+    /// Add these headers to `request` where it doesn't already set them.
+    pub(crate) fn apply_defaults(&self, request: &mut RequestBuilder) {
+        for (name, value) in self.headers() {
+            let already_set = request
+                .headers()
+                .keys()
+                .any(|existing| existing.eq_ignore_ascii_case(name));
+            if !already_set {
+                request.set_header(name, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::RequestMethod;
+
+    #[test]
+    fn test_headers() {
+        assert!(CallOptions::new().headers().is_empty());
+
+        let options = CallOptions::new()
+            .with_default_namespace("ns")
+            .with_auto_assign(AutoAssign::Rule("01Q000000000001".into()))
+            .with_query_batch_size(200)
+            .with_accept_language("de");
+        assert_eq!(
+            options.headers(),
+            vec![
+                (CALL_OPTIONS, "defaultNamespace=ns".to_string()),
+                (AUTO_ASSIGN, "01Q000000000001".to_string()),
+                (QUERY_OPTIONS, "batchSize=200".to_string()),
+                (ACCEPT_LANGUAGE, "de".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_request_headers_take_precedence() {
+        let defaults = CallOptions::new()
+            .with_auto_assign(AutoAssign::Enabled)
+            .with_query_batch_size(2000);
+        let mut request = RequestBuilder::new(RequestMethod::Get, "https://example.com")
+            .call_options(&CallOptions::new().with_query_batch_size(500));
+        defaults.apply_defaults(&mut request);
+
+        assert_eq!(request.headers()[AUTO_ASSIGN], "TRUE");
+        assert_eq!(request.headers()[QUERY_OPTIONS], "batchSize=500");
+    }
+}
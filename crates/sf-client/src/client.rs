@@ -1,43 +1,45 @@
 //! Core HTTP client with retry, compression, and Salesforce-specific handling.
 
-use std::time::Duration;
-use tracing::{debug, info, instrument, warn};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{instrument, warn};
 
+use crate::call_options::CallOptions;
 use crate::config::ClientConfig;
 use crate::error::{Error, ErrorKind, Result};
+use crate::interceptor::{Interceptor, Interceptors, RequestOutcome};
 use crate::request::{RequestBody, RequestBuilder, RequestMethod};
-use crate::response::{Response, ResponseExt};
+use crate::response::{parse_limit_info, Response, ResponseExt};
 use crate::retry::RetryPolicy;
+use crate::trace;
+use crate::transport::Transport;
+use crate::usage::UsageTracker;
 
 /// HTTP client for Salesforce APIs with built-in retry, compression, and error handling.
 #[derive(Debug, Clone)]
 pub struct SfHttpClient {
     inner: reqwest::Client,
+    transport: Arc<dyn Transport>,
     config: ClientConfig,
+    usage: Option<Arc<UsageTracker>>,
+    interceptors: Interceptors,
 }
 
 impl SfHttpClient {
     /// Create a new HTTP client with default configuration.
     pub fn new(config: ClientConfig) -> Result<Self> {
-        let mut builder = reqwest::Client::builder()
-            .timeout(config.timeout)
-            .connect_timeout(config.connect_timeout)
-            .pool_idle_timeout(config.pool_idle_timeout)
-            .pool_max_idle_per_host(config.pool_max_idle_per_host)
-            .user_agent(&config.user_agent);
-
-        // Configure compression
-        if config.compression.accept_compressed {
-            builder = builder.gzip(true).deflate(true);
-        } else {
-            builder = builder.gzip(false).deflate(false);
-        }
-
-        let inner = builder
+        let inner = config
+            .http_client_builder()?
             .build()
             .map_err(|e| Error::with_source(ErrorKind::Config(e.to_string()), e))?;
 
-        Ok(Self { inner, config })
+        Ok(Self {
+            transport: Arc::new(inner.clone()),
+            inner,
+            config,
+            usage: None,
+            interceptors: Interceptors::default(),
+        })
     }
 
     /// Create a new HTTP client with default configuration.
@@ -50,6 +52,41 @@ impl SfHttpClient {
         &self.config
     }
 
+    /// Record all requests sent by this client (and its clones) into `tracker`.
+    pub fn with_usage_tracker(mut self, tracker: Arc<UsageTracker>) -> Self {
+        self.usage = Some(tracker);
+        self
+    }
+
+    /// Get the attached usage tracker, if any.
+    pub fn usage_tracker(&self) -> Option<&Arc<UsageTracker>> {
+        self.usage.as_ref()
+    }
+
+    /// Run `interceptor` around every request sent by this client (and its clones).
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Send requests through `transport` instead of the network.
+    ///
+    /// Everything above the wire still runs: retries, interceptors, usage
+    /// tracking, and Salesforce error handling. See
+    /// [`MockTransport`](crate::MockTransport) for canned test responses.
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Send `options` as call option headers with every request.
+    ///
+    /// Replaces any call options from the [`ClientConfig`].
+    pub fn with_call_options(mut self, options: CallOptions) -> Self {
+        self.config.call_options = options;
+        self
+    }
+
     /// Create a GET request builder.
     pub fn get(&self, url: impl Into<String>) -> RequestBuilder {
         RequestBuilder::new(RequestMethod::Get, url)
@@ -81,47 +118,74 @@ impl SfHttpClient {
     }
 
     /// Execute a request with automatic retry handling.
-    #[instrument(skip(self, request), fields(method = ?request.method, url = %request.url))]
-    pub async fn execute(&self, request: RequestBuilder) -> Result<Response> {
+    #[instrument(skip(self, request), fields(method = ?request.method, url = %trace::redact_url(&request.url)))]
+    pub async fn execute(&self, mut request: RequestBuilder) -> Result<Response> {
+        self.config.call_options.apply_defaults(&mut request);
+        self.interceptors.before_request(&mut request)?;
+
         let mut retry_policy = self
             .config
             .retry
             .as_ref()
             .map(|c| RetryPolicy::new(c.clone()));
 
+        let mut attempt = 0;
         loop {
-            let result = self.execute_once(&request).await;
+            attempt += 1;
+            let started = Instant::now();
+            let sent = self.execute_once(&request).await;
+            let elapsed = started.elapsed();
+            let status = match &sent {
+                Ok(response) => Some(response.status()),
+                Err(err) => match err.kind {
+                    ErrorKind::Http { status, .. } => Some(status),
+                    ErrorKind::RateLimited { .. } => Some(429),
+                    _ => None,
+                },
+            };
 
-            match result {
-                Ok(response) => {
-                    // Check for Salesforce API errors
-                    return response.check_salesforce_error().await;
-                }
-                Err(err) if err.is_retryable() => {
-                    if let Some(ref mut policy) = retry_policy {
-                        if let Some(delay) = policy.next_delay(err.retry_after()) {
-                            warn!(
-                                attempt = policy.attempt(),
-                                delay_ms = delay.as_millis(),
-                                error = %err,
-                                "Request failed, retrying"
-                            );
-                            tokio::time::sleep(delay).await;
-                            continue;
-                        }
-
-                        // Exhausted retries
-                        return Err(Error::new(ErrorKind::RetriesExhausted {
-                            attempts: policy.attempt(),
-                        }));
-                    }
-
-                    // No retry policy configured
-                    return Err(err);
+            // Salesforce error bodies are parsed before deciding whether to
+            // retry, so errors such as REQUEST_LIMIT_EXCEEDED can be retried.
+            let result = match sent {
+                Ok(response) if request.soap && response.status() == 500 => Ok(response),
+                Ok(response) => response.check_salesforce_error().await,
+                Err(err) => Err(err),
+            };
+            self.interceptors.after_response(&RequestOutcome {
+                method: request.method,
+                url: &request.url,
+                attempt,
+                status,
+                elapsed,
+                error: result.as_ref().err(),
+            });
+
+            let err = match result {
+                Ok(response) => return Ok(response),
+                Err(err) => err,
+            };
+            let Some(ref mut policy) = retry_policy else {
+                // No retry policy configured
+                return Err(err);
+            };
+            if !policy.is_retryable(&err) {
+                return Err(err);
+            }
+            match policy.next_delay(err.retry_after()) {
+                Some(delay) => {
+                    warn!(
+                        attempt = policy.attempt(),
+                        delay_ms = delay.as_millis(),
+                        error = %err,
+                        "Request failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
                 }
-                Err(err) => {
-                    // Non-retryable error
-                    return Err(err);
+                None => {
+                    // Exhausted retries
+                    return Err(Error::new(ErrorKind::RetriesExhausted {
+                        attempts: policy.attempt(),
+                    }));
                 }
             }
         }
@@ -192,27 +256,55 @@ impl SfHttpClient {
                     req.header("Content-Type", "application/x-www-form-urlencoded")
                         .body(encoded)
                 }
+                RequestBody::Stream(stream) => req
+                    .header("Content-Length", stream.len())
+                    .body(reqwest::Body::wrap_stream(stream.open())),
             };
         }
+        let streamed_len = match request.body {
+            Some(RequestBody::Stream(ref stream)) => stream.len(),
+            _ => 0,
+        };
 
+        if let Some(ref usage) = self.usage {
+            usage.check_soft_limit()?;
+        }
+        let req = req.build()?;
+        if let Some(ref usage) = self.usage {
+            let body_len = req
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map_or(streamed_len, |b| b.len() as u64);
+            usage.record_request(body_len);
+        }
         if self.config.enable_tracing {
-            debug!(
-                method = ?request.method,
-                url = %request.url,
-                "Sending request"
-            );
+            trace::trace_headers(req.headers());
         }
 
-        let response = req.send().await?;
-
+        let method = request.method.to_reqwest();
+        let method = method.as_str();
+        let started = Instant::now();
+        let response = match self.transport.send(req).await {
+            Ok(response) => response,
+            Err(e) => {
+                if self.config.enable_tracing {
+                    trace::trace_failure(method, &url, &e, started.elapsed());
+                }
+                return Err(e);
+            }
+        };
         if self.config.enable_tracing {
-            let status = response.status().as_u16();
-            let content_length = response.content_length();
+            trace::trace_response(method, &url, &response, started.elapsed());
+        }
 
-            if response.status().is_success() {
-                debug!(status, content_length, "Response received");
-            } else {
-                info!(status, content_length, "Non-success response");
+        if let Some(ref usage) = self.usage {
+            if let Some(api_usage) = response
+                .headers()
+                .get("sforce-limit-info")
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_limit_info)
+            {
+                usage.record_api_usage(api_usage);
             }
         }
 
@@ -230,15 +322,16 @@ impl SfHttpClient {
             return Err(Error::new(ErrorKind::RateLimited { retry_after }));
         }
 
-        // Check for retryable server errors (500, 502, 503, 504)
-        if matches!(status, 500 | 502 | 503 | 504) {
+        // Check for retryable server errors (500, 502, 503, 504). A SOAP
+        // call's 500 is a fault, not an outage.
+        if matches!(status, 500 | 502 | 503 | 504) && !(request.soap && status == 500) {
             return Err(Error::new(ErrorKind::Http {
                 status,
                 message: format!("Server error: {}", status),
             }));
         }
 
-        Ok(Response::new(response))
+        Ok(Response::new(response).with_usage(self.usage.clone()))
     }
 
     /// Execute a request and return the response, checking for errors.
@@ -296,6 +389,179 @@ mod tests {
         assert!(response.is_success());
     }
 
+    #[tokio::test]
+    async fn test_soap_fault_is_returned_without_retry() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/soap"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("<faultcode>sf:X</faultcode>"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = SfHttpClient::default_client().unwrap();
+        let response = client
+            .execute(
+                client
+                    .post(format!("{}/soap", mock_server.uri()))
+                    .xml("<envelope/>")
+                    .soap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 500);
+        assert_eq!(
+            response.text().await.unwrap(),
+            "<faultcode>sf:X</faultcode>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_usage_tracker_records_traffic() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/echo"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("0123456789"))
+            .mount(&mock_server)
+            .await;
+
+        let tracker = Arc::new(UsageTracker::new());
+        let client = SfHttpClient::new(ClientConfig::builder().without_retry().build())
+            .unwrap()
+            .with_usage_tracker(tracker.clone());
+
+        for _ in 0..2 {
+            let body = client
+                .send(
+                    client
+                        .post(format!("{}/echo", mock_server.uri()))
+                        .text("hello"),
+                )
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap();
+            assert_eq!(body, "0123456789");
+        }
+
+        let usage = tracker.snapshot();
+        assert_eq!(usage.requests, 2);
+        assert_eq!(usage.bytes_sent, 10);
+        assert_eq!(usage.bytes_received, 20);
+    }
+
+    #[tokio::test]
+    async fn test_api_soft_limit_from_limit_info() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/limits"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Sforce-Limit-Info", "api-usage=14250/15000")
+                    .set_body_json(serde_json::json!({})),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let tracker = Arc::new(UsageTracker::new().with_api_soft_limit(95.0));
+        let client = SfHttpClient::new(ClientConfig::builder().without_retry().build())
+            .unwrap()
+            .with_usage_tracker(tracker.clone());
+        let url = format!("{}/limits", mock_server.uri());
+
+        client.send(client.get(&url)).await.unwrap();
+        let api_usage = tracker.snapshot().api_usage.unwrap();
+        assert_eq!((api_usage.used, api_usage.limit), (14250, 15000));
+
+        // 95% reached: the next request is refused without being sent.
+        let err = client.send(client.get(&url)).await.unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::ApiSoftLimit { .. }));
+        assert_eq!(tracker.snapshot().requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_sets_header_and_observes_attempts() {
+        use crate::interceptor::{Interceptor, RequestOutcome};
+        use std::sync::Mutex;
+
+        struct Recorder(Arc<Mutex<Vec<Option<u16>>>>);
+
+        impl Interceptor for Recorder {
+            fn before_request(&self, request: &mut RequestBuilder) -> Result<()> {
+                request.set_header("X-Correlation-Id", "abc-123");
+                Ok(())
+            }
+
+            fn after_response(&self, outcome: &RequestOutcome<'_>) {
+                assert_eq!(outcome.attempt as usize, self.0.lock().unwrap().len() + 1);
+                self.0.lock().unwrap().push(outcome.status);
+            }
+        }
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .and(header("X-Correlation-Id", "abc-123"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .and(header("X-Correlation-Id", "abc-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&mock_server)
+            .await;
+
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let client = SfHttpClient::new(
+            ClientConfig::builder()
+                .with_retry(
+                    crate::RetryConfig::default().with_initial_delay(Duration::from_millis(10)),
+                )
+                .build(),
+        )
+        .unwrap()
+        .with_interceptor(Recorder(statuses.clone()));
+
+        client
+            .send(client.get(format!("{}/flaky", mock_server.uri())))
+            .await
+            .unwrap();
+        assert_eq!(*statuses.lock().unwrap(), vec![Some(503), Some(200)]);
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_short_circuits() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let client = SfHttpClient::new(ClientConfig::builder().without_retry().build())
+            .unwrap()
+            .with_interceptor(|request: &mut RequestBuilder| {
+                if request.method() == RequestMethod::Delete {
+                    return Err(Error::new(ErrorKind::Other("deletes are disabled".into())));
+                }
+                Ok(())
+            });
+
+        let err = client
+            .send(client.delete(format!("{}/sobjects/Account/001", mock_server.uri())))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("deletes are disabled"));
+    }
+
     #[tokio::test]
     async fn test_salesforce_error_response() {
         let mock_server = MockServer::start().await;
@@ -402,6 +668,75 @@ mod tests {
         assert_eq!(call_count.load(Ordering::SeqCst), 3);
     }
 
+    #[tokio::test]
+    async fn test_retry_on_request_limit_exceeded() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let mock_server = MockServer::start().await;
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = call_count.clone();
+
+        Mock::given(method("GET"))
+            .and(path("/limited"))
+            .respond_with(move |_: &wiremock::Request| {
+                if call_count_clone.fetch_add(1, Ordering::SeqCst) == 0 {
+                    ResponseTemplate::new(403).set_body_json(serde_json::json!([{
+                        "errorCode": "REQUEST_LIMIT_EXCEEDED",
+                        "message": "ConcurrentPerOrgLongTxn Limit exceeded."
+                    }]))
+                } else {
+                    ResponseTemplate::new(200).set_body_json(serde_json::json!({}))
+                }
+            })
+            .mount(&mock_server)
+            .await;
+
+        let client = SfHttpClient::new(
+            ClientConfig::builder()
+                .with_retry(
+                    crate::RetryConfig::default().with_initial_delay(Duration::from_millis(10)),
+                )
+                .build(),
+        )
+        .unwrap();
+
+        let response = client
+            .send(client.get(format!("{}/limited", mock_server.uri())))
+            .await
+            .unwrap();
+
+        assert!(response.is_success());
+        assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_excludes_class() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/down"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = SfHttpClient::new(
+            ClientConfig::builder()
+                .with_retry(
+                    crate::RetryConfig::default().with_retry_on([crate::RetryOn::RateLimited]),
+                )
+                .build(),
+        )
+        .unwrap();
+
+        let err = client
+            .send(client.get(format!("{}/down", mock_server.uri())))
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::Http { status: 503, .. }));
+    }
+
     #[tokio::test]
     async fn test_conditional_request_304() {
         let mock_server = MockServer::start().await;
@@ -2,6 +2,8 @@
 
 use std::time::Duration;
 
+use crate::retry::RetryOn;
+
 /// Result type alias for sf-client operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -80,7 +82,8 @@ pub enum ErrorKind {
     #[error("Not found: {0}")]
     NotFound(String),
 
-    /// Precondition failed (HTTP 412) - ETag mismatch.
+    /// Precondition failed (HTTP 412): the ETag no longer matches, or the
+    /// resource changed after the `If-Unmodified-Since` time.
     #[error("Precondition failed: {0}")]
     PreconditionFailed(String),
 
@@ -116,38 +119,42 @@ pub enum ErrorKind {
         fields: Vec<String>,
     },
 
+    /// Refused locally: the org's reported daily API usage has reached the
+    /// soft limit set with [`UsageTracker::with_api_soft_limit`](crate::UsageTracker::with_api_soft_limit).
+    #[error("API usage {used}/{limit} has reached the configured soft limit")]
+    ApiSoftLimit { used: u64, limit: u64 },
+
     /// All retries exhausted.
     #[error("All {attempts} retry attempts exhausted")]
     RetriesExhausted { attempts: u32 },
 
+    /// The target instance is in (or about to enter) a maintenance window.
+    #[error("Instance {instance} has maintenance \"{name}\" until {ends_at}")]
+    MaintenanceWindow {
+        instance: String,
+        name: String,
+        ends_at: chrono::DateTime<chrono::Utc>,
+    },
+
     /// Other error.
     #[error("{0}")]
     Other(String),
 }
 
 impl ErrorKind {
-    /// Returns true if this error kind is retryable.
+    /// Returns true if the default [`RetryConfig`](crate::RetryConfig)
+    /// retries this error kind, i.e. it belongs to one of the
+    /// [`RetryOn::ALL`] classes.
     pub fn is_retryable(&self) -> bool {
-        match self {
-            ErrorKind::RateLimited { .. } => true,
-            ErrorKind::Timeout => true,
-            ErrorKind::Connection(_) => true,
-            ErrorKind::Http { status, .. } => is_retryable_status(*status),
-            _ => false,
-        }
+        RetryOn::ALL.iter().any(|class| class.matches(self))
     }
 }
 
-/// Check if an HTTP status code is typically retryable.
-fn is_retryable_status(status: u16) -> bool {
-    matches!(status, 429 | 500 | 502 | 503 | 504)
-}
-
 impl From<reqwest::Error> for Error {
     fn from(err: reqwest::Error) -> Self {
         let kind = if err.is_timeout() {
             ErrorKind::Timeout
-        } else if err.is_connect() {
+        } else if err.is_connect() || is_connection_dropped(&err) {
             ErrorKind::Connection(err.to_string())
         } else if let Some(status) = err.status() {
             ErrorKind::Http {
@@ -162,6 +169,24 @@ impl From<reqwest::Error> for Error {
     }
 }
 
+/// Whether an established connection was reset or closed mid-request.
+fn is_connection_dropped(err: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(e) = source {
+        if let Some(io) = e.downcast_ref::<std::io::Error>() {
+            return matches!(
+                io.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            );
+        }
+        source = e.source();
+    }
+    false
+}
+
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
         Error::with_source(ErrorKind::Json(err.to_string()), err)
@@ -197,6 +222,15 @@ mod tests {
 
         let err = Error::new(ErrorKind::Authentication("invalid".to_string()));
         assert!(!err.is_retryable());
+
+        // Agrees with the default retry policy.
+        let err = Error::new(ErrorKind::SalesforceApi {
+            error_code: "REQUEST_LIMIT_EXCEEDED".to_string(),
+            message: "ConcurrentPerOrgLongTxn Limit exceeded".to_string(),
+            fields: Vec::new(),
+        });
+        assert!(err.is_retryable());
+        assert!(crate::RetryPolicy::new(crate::RetryConfig::default()).is_retryable(&err));
     }
 
     #[test]
@@ -0,0 +1,272 @@
+//! Salesforce record IDs.
+//!
+//! Every record has a 15-character, case-sensitive ID and an 18-character
+//! form that appends a checksum of the letter case, so it survives tools
+//! that ignore case (spreadsheets, some databases). [`SalesforceId`] holds
+//! the 18-character form, so the two spellings of the same record compare
+//! equal.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Characters of the 18-character suffix, indexed by the case bits of a
+/// 5-character chunk.
+const SUFFIX_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ012345";
+
+/// Error returned when a string is not a Salesforce ID.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid Salesforce ID '{id}': {reason}")]
+pub struct ParseIdError {
+    id: String,
+    reason: &'static str,
+}
+
+impl ParseIdError {
+    fn new(id: &str, reason: &'static str) -> Self {
+        Self {
+            id: id.to_string(),
+            reason,
+        }
+    }
+}
+
+/// A validated Salesforce record ID.
+///
+/// Parses from either the 15- or 18-character form and always holds the
+/// 18-character one. An 18-character ID whose letters were all lowercased
+/// or uppercased is restored from its checksum, so comparisons between
+/// IDs are effectively case-insensitive; an ID with mixed case that
+/// disagrees with its checksum is rejected.
+///
+/// Dereferences to the 18-character `str`, so it can be passed wherever
+/// an ID is taken as `&str`.
+///
+/// # Example
+///
+/// ```
+/// use busbar_sf_client::SalesforceId;
+///
+/// let id: SalesforceId = "001Dn00000AbCdE".parse().unwrap();
+/// assert_eq!(id.as_str(), "001Dn00000AbCdEIAV");
+/// assert_eq!(id.as_15(), "001Dn00000AbCdE");
+/// assert_eq!(id.key_prefix(), "001");
+/// assert_eq!(id, "001DN00000ABCDEIAV");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SalesforceId(String);
+
+impl SalesforceId {
+    /// Parse a 15- or 18-character ID.
+    pub fn new(id: &str) -> Result<Self, ParseIdError> {
+        if id.len() != 15 && id.len() != 18 {
+            return Err(ParseIdError::new(id, "must be 15 or 18 characters"));
+        }
+        if !id.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(ParseIdError::new(id, "must be alphanumeric"));
+        }
+
+        let body = &id[..15];
+        let checksum = checksum(body);
+        if id.len() == 15 {
+            return Ok(Self(format!("{body}{checksum}")));
+        }
+
+        let suffix = id[15..].to_ascii_uppercase();
+        if suffix == checksum {
+            return Ok(Self(format!("{body}{suffix}")));
+        }
+
+        // The case of the body was lost; rebuild it from the suffix.
+        let has_upper = body.bytes().any(|b| b.is_ascii_uppercase());
+        let has_lower = body.bytes().any(|b| b.is_ascii_lowercase());
+        if has_upper && has_lower {
+            return Err(ParseIdError::new(id, "checksum does not match"));
+        }
+        let mut restored = String::with_capacity(18);
+        for (chunk, flag) in body.as_bytes().chunks(5).zip(suffix.bytes()) {
+            let Some(bits) = SUFFIX_ALPHABET.iter().position(|&c| c == flag) else {
+                return Err(ParseIdError::new(id, "checksum does not match"));
+            };
+            for (i, &b) in chunk.iter().enumerate() {
+                let upper = bits & (1 << i) != 0;
+                if upper && !b.is_ascii_alphabetic() {
+                    return Err(ParseIdError::new(id, "checksum does not match"));
+                }
+                restored.push(if upper {
+                    b.to_ascii_uppercase()
+                } else {
+                    b.to_ascii_lowercase()
+                } as char);
+            }
+        }
+        restored.push_str(&suffix);
+        Ok(Self(restored))
+    }
+
+    /// The 18-character form.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The 15-character, case-sensitive form.
+    pub fn as_15(&self) -> &str {
+        &self.0[..15]
+    }
+
+    /// The three-character prefix identifying the object type, e.g. `001`
+    /// for Account or `003` for Contact.
+    pub fn key_prefix(&self) -> &str {
+        &self.0[..3]
+    }
+
+    /// Whether `other` is a 15- or 18-character spelling of this ID, in
+    /// any letter case for the 18-character form.
+    pub fn matches(&self, other: &str) -> bool {
+        Self::new(other).is_ok_and(|other| other == *self)
+    }
+}
+
+/// Checksum suffix for a 15-character ID.
+fn checksum(body: &str) -> String {
+    body.as_bytes()
+        .chunks(5)
+        .map(|chunk| {
+            let bits = chunk
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| b.is_ascii_uppercase())
+                .fold(0usize, |bits, (i, _)| bits | (1 << i));
+            SUFFIX_ALPHABET[bits] as char
+        })
+        .collect()
+}
+
+impl FromStr for SalesforceId {
+    type Err = ParseIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<&str> for SalesforceId {
+    type Error = ParseIdError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for SalesforceId {
+    type Error = ParseIdError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(&s)
+    }
+}
+
+impl From<SalesforceId> for String {
+    fn from(id: SalesforceId) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for SalesforceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::ops::Deref for SalesforceId {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SalesforceId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for SalesforceId {
+    fn eq(&self, other: &str) -> bool {
+        self.matches(other)
+    }
+}
+
+impl PartialEq<&str> for SalesforceId {
+    fn eq(&self, other: &&str) -> bool {
+        self.matches(other)
+    }
+}
+
+impl PartialEq<String> for SalesforceId {
+    fn eq(&self, other: &String) -> bool {
+        self.matches(other)
+    }
+}
+
+impl Serialize for SalesforceId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SalesforceId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::new(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_15_to_18() {
+        let id = SalesforceId::new("001000000000001").unwrap();
+        assert_eq!(id.as_str(), "001000000000001AAA");
+        let id = SalesforceId::new("a0B5g00000XyZaB").unwrap();
+        assert_eq!(id.as_str(), "a0B5g00000XyZaBEAV");
+        assert_eq!(id.as_15(), "a0B5g00000XyZaB");
+        assert_eq!(id.key_prefix(), "a0B");
+    }
+
+    #[test]
+    fn test_18_with_lost_case() {
+        let id = SalesforceId::new("a0B5g00000XyZaBEAV").unwrap();
+        assert_eq!(SalesforceId::new("A0B5G00000XYZABEAV").unwrap(), id);
+        assert_eq!(SalesforceId::new("a0b5g00000xyzabeav").unwrap(), id);
+        assert_eq!(id, "a0B5g00000XyZaB");
+        assert_eq!(id, "a0b5g00000xyzabeav");
+        assert_ne!(id, "a0b5g00000xyzab");
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(SalesforceId::new("001").is_err());
+        assert!(SalesforceId::new("001000000000001AA").is_err());
+        assert!(SalesforceId::new("001000000000-01").is_err());
+        // Mixed case that disagrees with the checksum.
+        let err = SalesforceId::new("a0B5g00000XyZaBAAA").unwrap_err();
+        assert!(err.to_string().contains("checksum"));
+        // Uppercase flag on a digit.
+        assert!(SalesforceId::new("001000000000001BAA").is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let id: SalesforceId = serde_json::from_str("\"001000000000001\"").unwrap();
+        assert_eq!(
+            serde_json::to_string(&id).unwrap(),
+            "\"001000000000001AAA\""
+        );
+        assert!(serde_json::from_str::<SalesforceId>("\"nope\"").is_err());
+    }
+}
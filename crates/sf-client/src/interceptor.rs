@@ -0,0 +1,95 @@
+//! Hooks for observing and modifying requests sent by [`SfHttpClient`](crate::SfHttpClient).
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::{Error, Result};
+use crate::request::{RequestBuilder, RequestMethod};
+
+/// Hooks called around every request an [`SfHttpClient`](crate::SfHttpClient) sends.
+///
+/// Attach interceptors with
+/// [`SalesforceClient::with_interceptor`](crate::SalesforceClient::with_interceptor).
+/// They run in the order they were added.
+///
+/// Closures of the form `Fn(&mut RequestBuilder) -> Result<()>` implement
+/// this trait, which covers the common case of adding a header:
+///
+/// ```rust,ignore
+/// use busbar_sf_client::{RequestBuilder, SalesforceClient};
+///
+/// let client = SalesforceClient::new(instance_url, token)?.with_interceptor(
+///     |request: &mut RequestBuilder| {
+///         request.set_header("X-Correlation-Id", current_correlation_id());
+///         Ok(())
+///     },
+/// );
+/// ```
+pub trait Interceptor: Send + Sync {
+    /// Called once per request, before the first attempt.
+    ///
+    /// The request may be modified in place. Returning an error stops the
+    /// request from being sent and is returned to the caller as-is.
+    fn before_request(&self, request: &mut RequestBuilder) -> Result<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called after every attempt, including ones that will be retried.
+    fn after_response(&self, outcome: &RequestOutcome<'_>) {
+        let _ = outcome;
+    }
+}
+
+impl<F> Interceptor for F
+where
+    F: Fn(&mut RequestBuilder) -> Result<()> + Send + Sync,
+{
+    fn before_request(&self, request: &mut RequestBuilder) -> Result<()> {
+        self(request)
+    }
+}
+
+/// What happened to a single request attempt.
+#[derive(Debug)]
+pub struct RequestOutcome<'a> {
+    /// HTTP method of the request.
+    pub method: RequestMethod,
+    /// Request URL, without query parameters added by the builder.
+    pub url: &'a str,
+    /// 1 for the first attempt, incremented for each retry.
+    pub attempt: u32,
+    /// HTTP status, if a response was received.
+    pub status: Option<u16>,
+    /// Time from sending the request to receiving the response headers.
+    pub elapsed: Duration,
+    /// The error for this attempt, if it failed.
+    pub error: Option<&'a Error>,
+}
+
+/// The interceptors attached to a client.
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Vec<Arc<dyn Interceptor>>);
+
+impl Interceptors {
+    pub(crate) fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.0.push(interceptor);
+    }
+
+    pub(crate) fn before_request(&self, request: &mut RequestBuilder) -> Result<()> {
+        self.0.iter().try_for_each(|i| i.before_request(request))
+    }
+
+    pub(crate) fn after_response(&self, outcome: &RequestOutcome<'_>) {
+        for interceptor in &self.0 {
+            interceptor.after_response(outcome);
+        }
+    }
+}
+
+impl fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Interceptors({})", self.0.len())
+    }
+}
@@ -6,9 +6,14 @@
 //! - Automatic retry with exponential backoff and jitter
 //! - Compression support (gzip, deflate)
 //! - Rate limit detection and handling
+//! - Daily API usage tracking from `Sforce-Limit-Info`, with an optional soft limit
 //! - ETag/conditional request support
 //! - Connection pooling
-//! - Request/response tracing
+//! - Request/response tracing with credential redaction
+//! - Interceptors for custom headers, metrics, and request vetoes
+//! - Pluggable transport, with a mock for unit tests
+//! - Validated record IDs with 15/18-character conversion
+//! - Call option headers (`Sforce-Call-Options`, `Sforce-Auto-Assign`, `Sforce-Query-Options`)
 //!
 //! ## Architecture
 //!
@@ -60,23 +65,36 @@
 //! }
 //! ```
 
+mod call_options;
 mod client;
 mod config;
 mod error;
+mod id;
+mod interceptor;
 mod request;
 mod response;
 mod retry;
 mod salesforce_client;
 pub mod security;
+pub mod trace;
+mod transport;
+pub mod trust;
 pub mod types;
+mod usage;
 
+pub use call_options::{AutoAssign, CallOptions};
 pub use client::SfHttpClient;
-pub use config::{ClientConfig, ClientConfigBuilder, CompressionConfig};
+pub use config::{ClientConfig, ClientConfigBuilder, CompressionConfig, ProxyConfig, TlsConfig};
 pub use error::{Error, ErrorKind, Result};
+pub use id::{ParseIdError, SalesforceId};
+pub use interceptor::{Interceptor, RequestOutcome};
 pub use request::{RequestBuilder, RequestMethod};
 pub use response::{ApiUsage, Response, ResponseExt};
-pub use retry::{BackoffStrategy, RetryConfig, RetryPolicy};
+pub use retry::{BackoffStrategy, RetryConfig, RetryOn, RetryPolicy};
 pub use salesforce_client::{QueryResult, SalesforceClient};
+pub use transport::{MockResponse, MockTransport, RecordedRequest, Transport};
+pub use trust::{MaintenancePreflight, PreflightAction, TrustClient};
+pub use usage::{UsageSnapshot, UsageTracker, DEFAULT_SOFT_LIMIT_TTL};
 
 #[cfg(feature = "dependencies")]
 pub use types::MetadataComponentDependency;
@@ -1,9 +1,12 @@
 //! HTTP request building with Salesforce-specific headers.
 
 use bytes::Bytes;
+use futures::stream::BoxStream;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::call_options::{AutoAssign, CallOptions};
 use crate::error::Result;
 
 /// HTTP request method.
@@ -48,6 +51,8 @@ pub struct RequestBuilder {
     pub(crate) if_modified_since: Option<String>,
     /// Timestamp for If-Unmodified-Since header.
     pub(crate) if_unmodified_since: Option<String>,
+    /// Whether a 500 response carries a SOAP fault for the caller to read.
+    pub(crate) soap: bool,
 }
 
 /// Request body content.
@@ -57,6 +62,38 @@ pub enum RequestBody {
     Text(String),
     Bytes(Bytes),
     Form(HashMap<String, String>),
+    Stream(StreamBody),
+}
+
+/// Opens a fresh stream over a request body.
+type OpenStream = dyn Fn() -> BoxStream<'static, std::io::Result<Bytes>> + Send + Sync;
+
+/// A body streamed to the server instead of held in memory; see
+/// [`RequestBuilder::stream`].
+#[derive(Clone)]
+pub struct StreamBody {
+    len: u64,
+    open: Arc<OpenStream>,
+}
+
+impl StreamBody {
+    /// Length of the body in bytes, sent as `Content-Length`.
+    pub(crate) fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// A stream over the body from its first byte.
+    pub(crate) fn open(&self) -> BoxStream<'static, std::io::Result<Bytes>> {
+        (self.open)()
+    }
+}
+
+impl std::fmt::Debug for StreamBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamBody")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
 }
 
 impl RequestBuilder {
@@ -73,6 +110,7 @@ impl RequestBuilder {
             if_none_match: None,
             if_modified_since: None,
             if_unmodified_since: None,
+            soap: false,
         }
     }
 
@@ -82,6 +120,34 @@ impl RequestBuilder {
         self
     }
 
+    /// Get the HTTP method.
+    pub fn method(&self) -> RequestMethod {
+        self.method
+    }
+
+    /// Get the request URL, without query parameters added via [`RequestBuilder::query`].
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the headers set so far.
+    ///
+    /// Authorization and conditional headers are set separately and are
+    /// not included.
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
+
+    /// Set a header in place, replacing any existing value.
+    pub fn set_header(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.headers.insert(name.into(), value.into());
+    }
+
+    /// Remove a header, returning its value if it was set.
+    pub fn remove_header(&mut self, name: &str) -> Option<String> {
+        self.headers.remove(name)
+    }
+
     /// Add a header.
     pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
         self.headers.insert(name.into(), value.into());
@@ -153,6 +219,32 @@ impl RequestBuilder {
         self
     }
 
+    /// Set a body of `len` bytes that is streamed rather than held in memory.
+    ///
+    /// `open` is called once per attempt, so a retried request sends the
+    /// body again from the start.
+    pub fn stream<F>(mut self, len: u64, open: F) -> Self
+    where
+        F: Fn() -> BoxStream<'static, std::io::Result<Bytes>> + Send + Sync + 'static,
+    {
+        self.body = Some(RequestBody::Stream(StreamBody {
+            len,
+            open: Arc::new(open),
+        }));
+        self
+    }
+
+    /// Mark the request as a SOAP call.
+    ///
+    /// SOAP faults come back as HTTP 500 with the fault in the body, so a
+    /// 500 response is returned as it is, for the caller to parse, rather
+    /// than failing as a retryable server error. Other 5xx statuses still
+    /// fail and are retried.
+    pub fn soap(mut self) -> Self {
+        self.soap = true;
+        self
+    }
+
     /// Set If-Match header for optimistic concurrency.
     /// The request will fail with 412 if the ETag doesn't match.
     pub fn if_match(mut self, etag: impl Into<String>) -> Self {
@@ -203,6 +295,25 @@ impl RequestBuilder {
         );
         self
     }
+
+    /// Set Sforce-Auto-Assign header (assignment rules for Cases and Leads).
+    pub fn sforce_auto_assign(mut self, auto_assign: &AutoAssign) -> Self {
+        self.headers.insert(
+            "Sforce-Auto-Assign".to_string(),
+            auto_assign.header_value().to_string(),
+        );
+        self
+    }
+
+    /// Set the call option headers for this request.
+    ///
+    /// These override the client's call options header by header.
+    pub fn call_options(mut self, options: &CallOptions) -> Self {
+        for (name, value) in options.headers() {
+            self.headers.insert(name.to_string(), value);
+        }
+        self
+    }
 }
 
 #[cfg(test)]
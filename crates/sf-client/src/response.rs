@@ -1,20 +1,29 @@
 //! HTTP response handling with Salesforce-specific extensions.
 
 use serde::de::DeserializeOwned;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::error::{Error, ErrorKind, Result};
+use crate::usage::UsageTracker;
 
 /// Wrapper around reqwest::Response with additional functionality.
 #[derive(Debug)]
 pub struct Response {
     inner: reqwest::Response,
+    usage: Option<Arc<UsageTracker>>,
 }
 
 impl Response {
     /// Create a new Response from a reqwest::Response.
     pub(crate) fn new(inner: reqwest::Response) -> Self {
-        Self { inner }
+        Self { inner, usage: None }
+    }
+
+    /// Record body bytes into `usage` when the body is read.
+    pub(crate) fn with_usage(mut self, usage: Option<Arc<UsageTracker>>) -> Self {
+        self.usage = usage;
+        self
     }
 
     /// Get the HTTP status code.
@@ -73,45 +82,71 @@ impl Response {
 
     /// Get the response body as text.
     pub async fn text(self) -> Result<String> {
-        self.inner.text().await.map_err(Into::into)
+        let bytes = self.bytes().await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
     }
 
     /// Get the response body as bytes.
     pub async fn bytes(self) -> Result<bytes::Bytes> {
-        self.inner.bytes().await.map_err(Into::into)
+        let bytes = self.inner.bytes().await?;
+        if let Some(usage) = self.usage {
+            usage.record_received(bytes.len() as u64);
+        }
+        Ok(bytes)
+    }
+
+    /// Stream the response body chunk by chunk as it arrives.
+    ///
+    /// Use this instead of [`Response::bytes`] for large bodies that should
+    /// not be buffered in memory.
+    pub fn bytes_stream(self) -> impl futures::Stream<Item = Result<bytes::Bytes>> + Send {
+        futures::stream::try_unfold(self, |mut response| async move {
+            let Some(chunk) = response.inner.chunk().await? else {
+                return Ok(None);
+            };
+            if let Some(usage) = &response.usage {
+                usage.record_received(chunk.len() as u64);
+            }
+            Ok::<_, Error>(Some((chunk, response)))
+        })
     }
 
     /// Deserialize the response body as JSON.
     pub async fn json<T: DeserializeOwned>(self) -> Result<T> {
-        self.inner.json().await.map_err(Into::into)
+        let bytes = self.bytes().await?;
+        serde_json::from_slice(&bytes).map_err(Into::into)
     }
 
     /// Get access to the inner reqwest::Response.
     pub fn into_inner(self) -> reqwest::Response {
+        if let (Some(usage), Some(len)) = (self.usage, self.inner.content_length()) {
+            usage.record_received(len);
+        }
         self.inner
     }
 
     /// Get API usage limits from response headers.
     pub fn api_usage(&self) -> Option<ApiUsage> {
-        // Salesforce returns usage in Sforce-Limit-Info header
-        // Format: "api-usage=25/15000"
-        let info = self.header("sforce-limit-info")?;
-
-        for part in info.split(',') {
-            let part = part.trim();
-            if part.starts_with("api-usage=") {
-                let usage = part.trim_start_matches("api-usage=");
-                let parts: Vec<&str> = usage.split('/').collect();
-                if parts.len() == 2 {
-                    let used = parts[0].parse().ok()?;
-                    let limit = parts[1].parse().ok()?;
-                    return Some(ApiUsage { used, limit });
-                }
-            }
-        }
+        parse_limit_info(self.header("sforce-limit-info")?)
+    }
+}
 
-        None
+/// Parse the `api-usage` entry of a `Sforce-Limit-Info` header value.
+///
+/// The header looks like `api-usage=25/15000`, possibly alongside other
+/// comma-separated entries such as `per-app-api-usage=...`.
+pub(crate) fn parse_limit_info(info: &str) -> Option<ApiUsage> {
+    for part in info.split(',') {
+        let part = part.trim();
+        if let Some(usage) = part.strip_prefix("api-usage=") {
+            let (used, limit) = usage.split_once('/')?;
+            return Some(ApiUsage {
+                used: used.trim().parse().ok()?,
+                limit: limit.trim().parse().ok()?,
+            });
+        }
     }
+    None
 }
 
 /// API usage information from response headers.
@@ -168,6 +203,19 @@ impl ResponseExt for Response {
             return Err(Error::new(ErrorKind::RateLimited { retry_after: None }));
         }
 
+        // A failed If-Match/If-Unmodified-Since is always a precondition
+        // failure, whatever error code the body carries, so callers can
+        // detect lost updates without matching on codes.
+        if status == 412 {
+            let message = serde_json::from_str::<Vec<SalesforceErrorResponse>>(&body)
+                .ok()
+                .and_then(|errors| errors.into_iter().next())
+                .map_or_else(|| body.clone(), |err| err.message);
+            return Err(Error::new(ErrorKind::PreconditionFailed(
+                sanitize_error_message(&message),
+            )));
+        }
+
         // Try to parse as Salesforce error JSON
         if let Ok(errors) = serde_json::from_str::<Vec<SalesforceErrorResponse>>(&body) {
             if let Some(err) = errors.into_iter().next() {
@@ -195,7 +243,6 @@ impl ResponseExt for Response {
             401 => ErrorKind::Authentication(sanitized),
             403 => ErrorKind::Authorization(sanitized),
             404 => ErrorKind::NotFound(sanitized),
-            412 => ErrorKind::PreconditionFailed(sanitized),
             _ => ErrorKind::Http {
                 status,
                 message: sanitized,
@@ -279,6 +326,26 @@ mod tests {
         assert!((usage.percentage() - 100.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_parse_limit_info() {
+        assert_eq!(
+            parse_limit_info("api-usage=25/15000"),
+            Some(ApiUsage {
+                used: 25,
+                limit: 15000
+            })
+        );
+        assert_eq!(
+            parse_limit_info("per-app-api-usage=2/250(appName=sample), api-usage=30/15000"),
+            Some(ApiUsage {
+                used: 30,
+                limit: 15000
+            })
+        );
+        assert_eq!(parse_limit_info("api-usage=abc"), None);
+        assert_eq!(parse_limit_info(""), None);
+    }
+
     // =========================================================================
     // sanitize_error_message tests
     // =========================================================================
@@ -3,6 +3,52 @@
 use rand::Rng;
 use std::time::Duration;
 
+use crate::error::{Error, ErrorKind};
+
+/// A class of failure that a [`RetryConfig`] may retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryOn {
+    /// HTTP 429 responses.
+    RateLimited,
+    /// HTTP 500, 502, 503, and 504 responses.
+    ServerError,
+    /// Requests that timed out.
+    Timeout,
+    /// Failed connections, including connections reset mid-request.
+    Connection,
+    /// Salesforce `REQUEST_LIMIT_EXCEEDED` errors, returned when too many
+    /// concurrent long-running requests are in flight.
+    RequestLimitExceeded,
+}
+
+impl RetryOn {
+    /// Every retry class.
+    pub const ALL: [RetryOn; 5] = [
+        RetryOn::RateLimited,
+        RetryOn::ServerError,
+        RetryOn::Timeout,
+        RetryOn::Connection,
+        RetryOn::RequestLimitExceeded,
+    ];
+
+    /// Whether `kind` belongs to this class.
+    pub fn matches(&self, kind: &ErrorKind) -> bool {
+        match (self, kind) {
+            (RetryOn::RateLimited, ErrorKind::RateLimited { .. }) => true,
+            (RetryOn::RateLimited, ErrorKind::Http { status, .. }) => *status == 429,
+            (RetryOn::ServerError, ErrorKind::Http { status, .. }) => {
+                matches!(status, 500 | 502 | 503 | 504)
+            }
+            (RetryOn::Timeout, ErrorKind::Timeout) => true,
+            (RetryOn::Connection, ErrorKind::Connection(_)) => true,
+            (RetryOn::RequestLimitExceeded, ErrorKind::SalesforceApi { error_code, .. }) => {
+                error_code == "REQUEST_LIMIT_EXCEEDED"
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Configuration for retry behavior.
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -18,6 +64,8 @@ pub struct RetryConfig {
     pub respect_retry_after: bool,
     /// Maximum time to wait from Retry-After header.
     pub max_retry_after: Duration,
+    /// Failure classes that are retried; anything else fails immediately.
+    pub retry_on: Vec<RetryOn>,
 }
 
 impl Default for RetryConfig {
@@ -29,6 +77,7 @@ impl Default for RetryConfig {
             backoff: BackoffStrategy::ExponentialWithJitter { factor: 2.0 },
             respect_retry_after: true,
             max_retry_after: Duration::from_secs(60),
+            retry_on: RetryOn::ALL.to_vec(),
         }
     }
 }
@@ -58,6 +107,17 @@ impl RetryConfig {
         self
     }
 
+    /// Only retry the given failure classes.
+    pub fn with_retry_on(mut self, classes: impl IntoIterator<Item = RetryOn>) -> Self {
+        self.retry_on = classes.into_iter().collect();
+        self
+    }
+
+    /// Returns true if `error` falls in one of the configured retry classes.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        self.retry_on.iter().any(|class| class.matches(&error.kind))
+    }
+
     /// Disable retries.
     pub fn no_retry() -> Self {
         Self {
@@ -75,6 +135,7 @@ impl RetryConfig {
             backoff: BackoffStrategy::ExponentialWithJitter { factor: 2.0 },
             respect_retry_after: true,
             max_retry_after: Duration::from_secs(120),
+            retry_on: RetryOn::ALL.to_vec(),
         }
     }
 }
@@ -136,6 +197,14 @@ impl RetryPolicy {
         self.attempt
     }
 
+    /// Returns true if `error` is worth retrying under this policy's config.
+    ///
+    /// This does not consider how many attempts remain; see
+    /// [`RetryPolicy::should_retry`].
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        self.config.is_retryable(error)
+    }
+
     /// Returns true if we should retry after a failure.
     pub fn should_retry(&self) -> bool {
         self.attempt < self.config.max_attempts
@@ -285,6 +354,35 @@ mod tests {
         assert_eq!(delay, Duration::from_secs(60));
     }
 
+    #[test]
+    fn test_retry_on_classes() {
+        let config = RetryConfig::default();
+        let limit = Error::new(ErrorKind::SalesforceApi {
+            error_code: "REQUEST_LIMIT_EXCEEDED".to_string(),
+            message: "ConcurrentPerOrgLongTxn Limit exceeded".to_string(),
+            fields: vec![],
+        });
+        let server = Error::new(ErrorKind::Http {
+            status: 503,
+            message: String::new(),
+        });
+        let reset = Error::new(ErrorKind::Connection("connection reset".to_string()));
+        assert!(config.is_retryable(&limit));
+        assert!(config.is_retryable(&server));
+        assert!(config.is_retryable(&reset));
+        assert!(!config.is_retryable(&Error::new(ErrorKind::NotFound(String::new()))));
+        assert!(!config.is_retryable(&Error::new(ErrorKind::SalesforceApi {
+            error_code: "INVALID_FIELD".to_string(),
+            message: String::new(),
+            fields: vec![],
+        })));
+
+        let config = RetryConfig::default().with_retry_on([RetryOn::RateLimited]);
+        assert!(!config.is_retryable(&server));
+        assert!(!config.is_retryable(&limit));
+        assert!(config.is_retryable(&Error::new(ErrorKind::RateLimited { retry_after: None })));
+    }
+
     #[test]
     fn test_policy_reset() {
         let config = RetryConfig::default().with_max_attempts(2);
@@ -9,12 +9,17 @@
 //! - Sensitive parameters are skipped in tracing spans
 
 use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
 use tracing::instrument;
 
+use crate::call_options::CallOptions;
 use crate::client::SfHttpClient;
 use crate::config::ClientConfig;
 use crate::error::{Error, ErrorKind, Result};
+use crate::interceptor::Interceptor;
 use crate::request::RequestBuilder;
+use crate::transport::Transport;
+use crate::usage::UsageTracker;
 use crate::DEFAULT_API_VERSION;
 
 /// High-level Salesforce API client.
@@ -90,6 +95,61 @@ impl SalesforceClient {
         self
     }
 
+    /// Record every request made through this client into `tracker`.
+    ///
+    /// The tracker is shared by clones made after this call, so a single
+    /// tracker can meter a REST, Bulk, and Tooling client built from the
+    /// same `SalesforceClient`.
+    pub fn with_usage_tracker(mut self, tracker: Arc<UsageTracker>) -> Self {
+        self.http = self.http.with_usage_tracker(tracker);
+        self
+    }
+
+    /// Run `interceptor` around every request made through this client.
+    ///
+    /// Like the usage tracker, interceptors are shared by clones made after
+    /// this call, including REST, Bulk, and Tooling clients built from it.
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.http = self.http.with_interceptor(interceptor);
+        self
+    }
+
+    /// Send every request made through this client through `transport`.
+    ///
+    /// REST, Bulk, and Tooling clients built from this client with
+    /// `from_client` use it too, so one [`MockTransport`](crate::MockTransport)
+    /// can stand in for Salesforce in their tests.
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.http = self.http.with_transport(transport);
+        self
+    }
+
+    /// Send `options` as call option headers with every request made
+    /// through this client.
+    ///
+    /// Clients are cheap to clone, so a clone with different options can
+    /// serve a single call:
+    ///
+    /// ```rust,ignore
+    /// let no_assignment = client
+    ///     .clone()
+    ///     .with_call_options(CallOptions::new().with_auto_assign(AutoAssign::Disabled));
+    /// ```
+    pub fn with_call_options(mut self, options: CallOptions) -> Self {
+        self.http = self.http.with_call_options(options);
+        self
+    }
+
+    /// The call options sent with every request.
+    pub fn call_options(&self) -> &CallOptions {
+        &self.http.config().call_options
+    }
+
+    /// Get the attached usage tracker, if any.
+    pub fn usage_tracker(&self) -> Option<&Arc<UsageTracker>> {
+        self.http.usage_tracker()
+    }
+
     /// Get the instance URL.
     pub fn instance_url(&self) -> &str {
         &self.instance_url
@@ -484,4 +544,37 @@ mod tests {
             "https://na1.salesforce.com/services/data/v62.0/limits"
         );
     }
+
+    #[tokio::test]
+    async fn test_mock_transport_with_retries() {
+        use crate::{MockResponse, MockTransport, RetryConfig};
+
+        let transport = MockTransport::new()
+            .on_once("GET", "/services/data/v62.0/limits", MockResponse::new(503))
+            .on(
+                "GET",
+                "/services/data/v62.0/limits",
+                MockResponse::json(
+                    200,
+                    &serde_json::json!({"DailyApiRequests": {"Max": 15000}}),
+                ),
+            );
+        let config = ClientConfig::builder()
+            .with_retry(RetryConfig {
+                initial_delay: std::time::Duration::from_millis(1),
+                ..RetryConfig::default()
+            })
+            .build();
+        let client =
+            SalesforceClient::with_config("https://na1.salesforce.com", "token123", config)
+                .unwrap()
+                .with_transport(transport.clone());
+
+        let limits: serde_json::Value = client.rest_get("limits").await.unwrap();
+        assert_eq!(limits["DailyApiRequests"]["Max"], 15000);
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].header("authorization"), Some("Bearer token123"));
+    }
 }
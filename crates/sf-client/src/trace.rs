@@ -0,0 +1,260 @@
+//! Debug-level tracing of Salesforce calls, with credentials redacted.
+//!
+//! Every call made through [`SfHttpClient`](crate::SfHttpClient), and the
+//! SOAP and OAuth calls that bypass it, emits one `debug` event with the
+//! method, host, path, status, duration, and the org's API usage when the
+//! response reports it. URLs are passed through [`redact_url`] first, and
+//! request headers (logged at `trace` level) through [`redact_header`], so
+//! these events are safe to enable in production.
+
+use std::time::Duration;
+
+use tracing::debug;
+
+use crate::response::parse_limit_info;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose values are never logged.
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-sfdc-session",
+];
+
+/// Query parameters whose values are never logged.
+const SENSITIVE_PARAMS: &[&str] = &[
+    "sid",
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "code",
+    "password",
+    "assertion",
+    "token",
+];
+
+/// The value of header `name` as it may be logged.
+pub fn redact_header<'a>(name: &str, value: &'a str) -> &'a str {
+    if SENSITIVE_HEADERS
+        .iter()
+        .any(|h| h.eq_ignore_ascii_case(name))
+    {
+        REDACTED
+    } else {
+        value
+    }
+}
+
+/// `url` with user info, credential query parameters, and session IDs
+/// replaced by `[REDACTED]`. Works on absolute URLs and bare paths.
+pub fn redact_url(url: &str) -> String {
+    let (base, query) = match url.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (url, None),
+    };
+
+    let mut out = String::with_capacity(url.len());
+    match base.split_once("://") {
+        Some((scheme, rest)) => {
+            let authority_end = rest.find('/').unwrap_or(rest.len());
+            let (authority, path) = rest.split_at(authority_end);
+            out.push_str(scheme);
+            out.push_str("://");
+            if let Some((_, host)) = authority.rsplit_once('@') {
+                out.push_str(REDACTED);
+                out.push('@');
+                out.push_str(host);
+            } else {
+                out.push_str(authority);
+            }
+            out.push_str(path);
+        }
+        None => out.push_str(base),
+    }
+
+    if let Some(query) = query {
+        out.push('?');
+        let pairs: Vec<String> = query
+            .split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((key, _)) if SENSITIVE_PARAMS.iter().any(|p| p.eq_ignore_ascii_case(key)) => {
+                    format!("{key}={REDACTED}")
+                }
+                _ => pair.to_string(),
+            })
+            .collect();
+        out.push_str(&pairs.join("&"));
+    }
+
+    redact_session_ids(&out)
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-'
+}
+
+/// Redact Salesforce session/access tokens: `00D` + 12-15 alnum (org id
+/// prefix) + `!` + 20+ token chars. e.g. `00Dxx0000001abcEAA!AQEAQNPS...`.
+///
+/// Bare record IDs, including org IDs, are left untouched.
+pub fn redact_session_ids(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut i = 0;
+    while i < s.len() {
+        if s[i..].starts_with("00D") {
+            let prefix_start = i + 3;
+            let prefix_len = s[prefix_start..]
+                .char_indices()
+                .take_while(|(_, c)| c.is_ascii_alphanumeric())
+                .last()
+                .map(|(idx, c)| idx + c.len_utf8())
+                .unwrap_or(0);
+            let bang_pos = prefix_start + prefix_len;
+            if (12..=15).contains(&prefix_len) && s[bang_pos..].starts_with('!') {
+                let value_start = bang_pos + 1;
+                let value_len = s[value_start..]
+                    .char_indices()
+                    .take_while(|(_, c)| is_token_char(*c))
+                    .last()
+                    .map(|(idx, c)| idx + c.len_utf8())
+                    .unwrap_or(0);
+                if value_len >= 20 {
+                    out.push_str(REDACTED);
+                    i = value_start + value_len;
+                    continue;
+                }
+            }
+        }
+        let ch = s[i..].chars().next().unwrap();
+        out.push(ch);
+        i += ch.len_utf8();
+    }
+    out
+}
+
+/// Split a redacted URL into host and path-with-query for logging.
+fn host_and_path(url: &str) -> (&str, &str) {
+    match url.split_once("://") {
+        Some((_, rest)) => {
+            let path_start = rest.find(['/', '?']).unwrap_or(rest.len());
+            let (host, path) = rest.split_at(path_start);
+            (host, path)
+        }
+        None => ("", url),
+    }
+}
+
+/// Log request headers at `trace` level, redacted.
+pub fn trace_headers(headers: &reqwest::header::HeaderMap) {
+    if !tracing::enabled!(tracing::Level::TRACE) {
+        return;
+    }
+    for (name, value) in headers {
+        let value = value.to_str().unwrap_or("<binary>");
+        tracing::trace!(
+            header = %name,
+            value = redact_header(name.as_str(), value),
+            "Request header"
+        );
+    }
+}
+
+/// Log a completed call at `debug` level.
+pub fn trace_response(method: &str, url: &str, response: &reqwest::Response, elapsed: Duration) {
+    if !tracing::enabled!(tracing::Level::DEBUG) {
+        return;
+    }
+    let url = redact_url(url);
+    let (host, path) = host_and_path(&url);
+    let api_usage = response
+        .headers()
+        .get("sforce-limit-info")
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_limit_info);
+    debug!(
+        method,
+        host,
+        path,
+        status = response.status().as_u16(),
+        duration_ms = elapsed.as_millis() as u64,
+        api_used = api_usage.map(|u| u.used),
+        api_limit = api_usage.map(|u| u.limit),
+        "Salesforce call"
+    );
+}
+
+/// Log a call that failed before a response arrived at `debug` level.
+pub fn trace_failure(method: &str, url: &str, error: &dyn std::fmt::Display, elapsed: Duration) {
+    if !tracing::enabled!(tracing::Level::DEBUG) {
+        return;
+    }
+    let url = redact_url(url);
+    let (host, path) = host_and_path(&url);
+    debug!(
+        method,
+        host,
+        path,
+        duration_ms = elapsed.as_millis() as u64,
+        error = %redact_session_ids(&error.to_string()),
+        "Salesforce call failed"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_header() {
+        assert_eq!(redact_header("Authorization", "Bearer abc"), REDACTED);
+        assert_eq!(redact_header("cookie", "sid=abc"), REDACTED);
+        assert_eq!(
+            redact_header("Content-Type", "application/json"),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("https://user:pw@proxy.example.com:3128/path?a=1"),
+            "https://[REDACTED]@proxy.example.com:3128/path?a=1"
+        );
+        assert_eq!(
+            redact_url("/secur/frontdoor.jsp?sid=abc123&retURL=%2Fhome"),
+            "/secur/frontdoor.jsp?sid=[REDACTED]&retURL=%2Fhome"
+        );
+        assert_eq!(
+            redact_url("https://na1.salesforce.com/services/data/v62.0/query?q=SELECT+Id"),
+            "https://na1.salesforce.com/services/data/v62.0/query?q=SELECT+Id"
+        );
+        let out = redact_url("/x?state=00Dxx0000001abcEAA!AQEAQNPSomeLongTokenBody123");
+        assert!(!out.contains("AQEAQNPSomeLongTokenBody123"));
+    }
+
+    #[test]
+    fn test_host_and_path() {
+        assert_eq!(
+            host_and_path("https://na1.salesforce.com/services/data?q=1"),
+            ("na1.salesforce.com", "/services/data?q=1")
+        );
+        assert_eq!(host_and_path("/services/data"), ("", "/services/data"));
+    }
+
+    #[test]
+    fn redacts_session_id() {
+        let text = "SOAP fault echoed request: <sessionId>00Dxx0000001abcEAA!AQEAQNPSomeLongTokenBody123</sessionId>";
+        let out = redact_session_ids(text);
+        assert!(!out.contains("AQEAQNPSomeLongTokenBody123"));
+        assert!(out.contains(REDACTED));
+    }
+
+    #[test]
+    fn leaves_bare_record_ids_untouched() {
+        let text = "ScratchOrgInfo 00DQL00000XUFu0AAF not found";
+        assert_eq!(redact_session_ids(text), text);
+    }
+}
@@ -0,0 +1,294 @@
+//! The layer that puts requests on the wire.
+//!
+//! [`SfHttpClient`](crate::SfHttpClient) builds each request (headers,
+//! body, retries, usage accounting) and hands the finished
+//! `reqwest::Request` to a [`Transport`]. The default transport is the
+//! reqwest client built from the [`ClientConfig`](crate::ClientConfig).
+//! Tests swap in a [`MockTransport`] to answer with canned responses,
+//! without a mock server or a live org.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Sends a built request and returns the raw response.
+///
+/// Retries, Salesforce error parsing, and tracing happen above this layer,
+/// so an implementation only has to move bytes.
+pub trait Transport: Send + Sync + fmt::Debug {
+    /// Send `request`.
+    fn send(&self, request: reqwest::Request) -> BoxFuture<'static, Result<reqwest::Response>>;
+}
+
+impl Transport for reqwest::Client {
+    fn send(&self, request: reqwest::Request) -> BoxFuture<'static, Result<reqwest::Response>> {
+        let pending = self.execute(request);
+        Box::pin(async move { pending.await.map_err(Error::from) })
+    }
+}
+
+/// A canned response for a [`MockTransport`].
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: bytes::Bytes,
+}
+
+impl MockResponse {
+    /// A response with `status` and an empty body.
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: bytes::Bytes::new(),
+        }
+    }
+
+    /// A JSON response.
+    pub fn json(status: u16, body: &serde_json::Value) -> Self {
+        Self::new(status)
+            .with_header("Content-Type", "application/json")
+            .with_body(body.to_string())
+    }
+
+    /// A plain body with the given content type, e.g. a SOAP envelope
+    /// (`text/xml`) or a Bulk API result (`text/csv`).
+    pub fn text(status: u16, content_type: &str, body: impl Into<String>) -> Self {
+        Self::new(status)
+            .with_header("Content-Type", content_type)
+            .with_body(body.into())
+    }
+
+    /// Add a response header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the response body.
+    pub fn with_body(mut self, body: impl Into<bytes::Bytes>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    fn to_reqwest(&self) -> Result<reqwest::Response> {
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder.body(self.body.clone()).map_err(|e| {
+            Error::with_source(ErrorKind::Other("Invalid mock response".to_string()), e)
+        })?;
+        Ok(reqwest::Response::from(response))
+    }
+}
+
+/// A request seen by a [`MockTransport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    /// HTTP method, e.g. `GET`.
+    pub method: String,
+    /// Full request URL, including the query string.
+    pub url: String,
+    /// Request headers, in order.
+    pub headers: Vec<(String, String)>,
+    /// Request body, if it was sent in one piece.
+    pub body: Option<Vec<u8>>,
+}
+
+impl RecordedRequest {
+    /// The first header named `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The body as UTF-8 text.
+    pub fn body_text(&self) -> Option<&str> {
+        self.body
+            .as_deref()
+            .and_then(|b| std::str::from_utf8(b).ok())
+    }
+}
+
+#[derive(Debug)]
+struct Route {
+    method: String,
+    path: String,
+    response: MockResponse,
+    once: bool,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    routes: Vec<Route>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// A [`Transport`] that answers from canned responses and records every
+/// request it receives.
+///
+/// Routes match on method and URL path (the query string is ignored) and
+/// are tried in the order they were added. A route added with
+/// [`MockTransport::on_once`] answers a single request, which makes it
+/// easy to script a failure followed by a success. A request no route
+/// matches fails with an error naming it.
+///
+/// Clones share routes and recorded requests, so keep a clone to inspect
+/// after handing one to a client.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_client::{MockResponse, MockTransport, SalesforceClient};
+///
+/// let transport = MockTransport::new().on(
+///     "GET",
+///     "/services/data/v62.0/limits",
+///     MockResponse::json(200, &serde_json::json!({"DailyApiRequests": {"Max": 15000}})),
+/// );
+/// let client = SalesforceClient::new("https://example.my.salesforce.com", "token")
+///     .unwrap()
+///     .with_transport(transport.clone());
+///
+/// let limits: serde_json::Value = client.rest_get("limits").await.unwrap();
+/// assert_eq!(limits["DailyApiRequests"]["Max"], 15000);
+/// assert_eq!(transport.requests()[0].header("Authorization"), Some("Bearer token"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockTransport {
+    /// A transport with no routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer every `method` request to `path` with `response`.
+    pub fn on(self, method: &str, path: impl Into<String>, response: MockResponse) -> Self {
+        self.route(method, path.into(), response, false)
+    }
+
+    /// Answer the next `method` request to `path` with `response`.
+    pub fn on_once(self, method: &str, path: impl Into<String>, response: MockResponse) -> Self {
+        self.route(method, path.into(), response, true)
+    }
+
+    fn route(self, method: &str, path: String, response: MockResponse, once: bool) -> Self {
+        self.lock().routes.push(Route {
+            method: method.to_ascii_uppercase(),
+            path,
+            response,
+            once,
+        });
+        self
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn respond(&self, request: &reqwest::Request) -> Result<reqwest::Response> {
+        let method = request.method().as_str();
+        let path = request.url().path();
+
+        let mut state = self.lock();
+        state.requests.push(RecordedRequest {
+            method: method.to_string(),
+            url: request.url().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|(n, v)| {
+                    (
+                        n.as_str().to_string(),
+                        String::from_utf8_lossy(v.as_bytes()).into_owned(),
+                    )
+                })
+                .collect(),
+            body: request
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(<[u8]>::to_vec),
+        });
+
+        let Some(index) = state
+            .routes
+            .iter()
+            .position(|r| r.method == method && r.path == path)
+        else {
+            return Err(Error::new(ErrorKind::Other(format!(
+                "No mock response for {method} {path}"
+            ))));
+        };
+        let response = if state.routes[index].once {
+            state.routes.remove(index).response
+        } else {
+            state.routes[index].response.clone()
+        };
+        response.to_reqwest()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: reqwest::Request) -> BoxFuture<'static, Result<reqwest::Response>> {
+        let response = self.respond(&request);
+        Box::pin(async move { response })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(url: &str) -> reqwest::Request {
+        reqwest::Client::new().get(url).build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mock_transport_routes() {
+        let transport = MockTransport::new()
+            .on_once("GET", "/status", MockResponse::new(503))
+            .on(
+                "get",
+                "/status",
+                MockResponse::text(200, "text/plain", "up").with_header("X-Test", "1"),
+            );
+
+        let first = transport
+            .send(get("https://x.test/status?a=1"))
+            .await
+            .unwrap();
+        assert_eq!(first.status().as_u16(), 503);
+
+        for _ in 0..2 {
+            let response = transport.send(get("https://x.test/status")).await.unwrap();
+            assert_eq!(response.status().as_u16(), 200);
+            assert_eq!(response.headers()["x-test"], "1");
+            assert_eq!(response.text().await.unwrap(), "up");
+        }
+
+        let err = transport
+            .send(get("https://x.test/missing"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("GET /missing"));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].url, "https://x.test/status?a=1");
+    }
+}
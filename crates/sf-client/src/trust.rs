@@ -0,0 +1,357 @@
+//! Salesforce Trust (status.salesforce.com) API client and maintenance pre-flight.
+//!
+//! The Trust API is public and unauthenticated. It reports the current
+//! status of each Salesforce instance (e.g. `NA135`, `USA598S`) along with
+//! scheduled maintenance windows and open incidents.
+//!
+//! [`MaintenancePreflight`] wraps it into a check that long-running
+//! operations (bulk loads, deploys) can run before starting, so work isn't
+//! kicked off just before the instance goes read-only.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{instrument, warn};
+
+use crate::client::SfHttpClient;
+use crate::config::ClientConfig;
+use crate::error::{Error, ErrorKind, Result};
+
+/// Base URL of the public Salesforce Trust API.
+pub const DEFAULT_TRUST_API_URL: &str = "https://api.status.salesforce.com/v1";
+
+/// Client for the Salesforce Trust status API.
+#[derive(Debug, Clone)]
+pub struct TrustClient {
+    http: SfHttpClient,
+    base_url: String,
+}
+
+impl TrustClient {
+    /// Create a client for the public Trust API.
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            http: SfHttpClient::new(ClientConfig::default())?,
+            base_url: DEFAULT_TRUST_API_URL.to_string(),
+        })
+    }
+
+    /// Point the client at a different base URL (e.g. a mock server).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Get the status, maintenances, and incidents for an instance.
+    ///
+    /// `instance_key` is the instance name, e.g. `NA135`. For an org, it is
+    /// available as `SELECT InstanceName FROM Organization`.
+    #[instrument(skip(self))]
+    pub async fn instance_status(&self, instance_key: &str) -> Result<InstanceStatus> {
+        if instance_key.is_empty() || !instance_key.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(Error::new(ErrorKind::Config(format!(
+                "Invalid instance key: {instance_key}"
+            ))));
+        }
+        let url = format!("{}/instances/{}/status", self.base_url, instance_key);
+        let request = self
+            .http
+            .get(url)
+            .header("Accept", "application/json")
+            .query("childProducts", "false");
+        self.http.send_json(request).await
+    }
+}
+
+/// Status of a Salesforce instance as reported by the Trust API.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InstanceStatus {
+    pub key: String,
+    pub location: Option<String>,
+    pub environment: Option<String>,
+    #[serde(rename = "releaseVersion")]
+    pub release_version: Option<String>,
+    /// e.g. `OK`, `MAINTENANCE_CORE`, `MAJOR_INCIDENT_CORE`.
+    pub status: String,
+    #[serde(rename = "isActive", default)]
+    pub is_active: bool,
+    #[serde(rename = "Maintenances", default)]
+    pub maintenances: Vec<Maintenance>,
+    #[serde(rename = "Incidents", default)]
+    pub incidents: Vec<serde_json::Value>,
+}
+
+impl InstanceStatus {
+    /// Find a maintenance that is running at `at`, or starts within `lookahead` of it.
+    ///
+    /// Completed and canceled maintenances are ignored.
+    pub fn active_maintenance(
+        &self,
+        at: DateTime<Utc>,
+        lookahead: Duration,
+    ) -> Option<&Maintenance> {
+        let horizon = at + chrono::Duration::from_std(lookahead).unwrap_or(chrono::Duration::MAX);
+        self.maintenances.iter().find(|m| {
+            !m.is_finished() && m.planned_start_time <= horizon && m.planned_end_time >= at
+        })
+    }
+}
+
+/// A scheduled maintenance window.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Maintenance {
+    pub id: i64,
+    pub name: String,
+    #[serde(rename = "plannedStartTime")]
+    pub planned_start_time: DateTime<Utc>,
+    #[serde(rename = "plannedEndTime")]
+    pub planned_end_time: DateTime<Utc>,
+    pub message: MaintenanceMessage,
+}
+
+impl Maintenance {
+    /// Whether the maintenance has already completed or was canceled.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.message.event_status.as_deref(),
+            Some("completed") | Some("canceled")
+        )
+    }
+}
+
+/// Details of a maintenance window.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MaintenanceMessage {
+    /// e.g. `release`, `maintenance`.
+    #[serde(rename = "maintenanceType")]
+    pub maintenance_type: Option<String>,
+    /// e.g. `fullyAvailable`, `readOnly`, `unavailable`.
+    pub availability: Option<String>,
+    /// e.g. `confirmed`, `inProgress`, `completed`, `canceled`.
+    #[serde(rename = "eventStatus")]
+    pub event_status: Option<String>,
+}
+
+/// What [`MaintenancePreflight`] does when it finds a maintenance window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreflightAction {
+    /// Log a warning and continue.
+    #[default]
+    Warn,
+    /// Fail with [`ErrorKind::MaintenanceWindow`] so the caller can reschedule.
+    Defer,
+}
+
+/// Pre-flight check against the Trust API for an instance's maintenance windows.
+///
+/// If the Trust API itself cannot be reached, the check logs a warning and
+/// passes; an unavailable status page should not block work.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_client::{MaintenancePreflight, PreflightAction};
+///
+/// let preflight = MaintenancePreflight::new("NA135")?
+///     .with_action(PreflightAction::Defer)
+///     .with_lookahead(Duration::from_secs(2 * 60 * 60));
+/// preflight.check().await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaintenancePreflight {
+    trust: TrustClient,
+    instance_key: String,
+    lookahead: Duration,
+    action: PreflightAction,
+}
+
+impl MaintenancePreflight {
+    /// Create a pre-flight for the given instance key (e.g. `NA135`).
+    ///
+    /// Defaults to warning about maintenance that is running now or starts
+    /// within the next hour.
+    pub fn new(instance_key: impl Into<String>) -> Result<Self> {
+        Ok(Self {
+            trust: TrustClient::new()?,
+            instance_key: instance_key.into(),
+            lookahead: Duration::from_secs(60 * 60),
+            action: PreflightAction::Warn,
+        })
+    }
+
+    /// Use a specific Trust API client.
+    pub fn with_trust_client(mut self, trust: TrustClient) -> Self {
+        self.trust = trust;
+        self
+    }
+
+    /// How far ahead to look for upcoming maintenance.
+    pub fn with_lookahead(mut self, lookahead: Duration) -> Self {
+        self.lookahead = lookahead;
+        self
+    }
+
+    /// Set what happens when a maintenance window is found.
+    pub fn with_action(mut self, action: PreflightAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Run the check.
+    #[instrument(skip(self), fields(instance = %self.instance_key))]
+    pub async fn check(&self) -> Result<()> {
+        let status = match self.trust.instance_status(&self.instance_key).await {
+            Ok(status) => status,
+            Err(e) => {
+                warn!(error = %e, "Trust API unavailable, skipping maintenance pre-flight");
+                return Ok(());
+            }
+        };
+
+        let Some(maintenance) = status.active_maintenance(Utc::now(), self.lookahead) else {
+            return Ok(());
+        };
+
+        match self.action {
+            PreflightAction::Warn => {
+                warn!(
+                    maintenance = %maintenance.name,
+                    start = %maintenance.planned_start_time,
+                    end = %maintenance.planned_end_time,
+                    "Instance has a maintenance window; continuing"
+                );
+                Ok(())
+            }
+            PreflightAction::Defer => Err(Error::new(ErrorKind::MaintenanceWindow {
+                instance: status.key.clone(),
+                name: maintenance.name.clone(),
+                ends_at: maintenance.planned_end_time,
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn status_json(
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        event_status: &str,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "key": "NA135",
+            "location": "NA",
+            "environment": "production",
+            "releaseVersion": "Winter '25 Patch 12.4",
+            "status": "OK",
+            "isActive": true,
+            "Maintenances": [{
+                "id": 123,
+                "name": "NA135 Maintenance",
+                "plannedStartTime": start.to_rfc3339(),
+                "plannedEndTime": end.to_rfc3339(),
+                "message": {
+                    "maintenanceType": "maintenance",
+                    "availability": "readOnly",
+                    "eventStatus": event_status
+                }
+            }],
+            "Incidents": []
+        })
+    }
+
+    #[test]
+    fn test_active_maintenance_window() {
+        let now = Utc::now();
+        let json = status_json(
+            now + chrono::Duration::minutes(30),
+            now + chrono::Duration::hours(2),
+            "confirmed",
+        );
+        let status: InstanceStatus = serde_json::from_value(json).unwrap();
+
+        assert!(status
+            .active_maintenance(now, Duration::from_secs(3600))
+            .is_some());
+        assert!(status
+            .active_maintenance(now, Duration::from_secs(600))
+            .is_none());
+    }
+
+    #[test]
+    fn test_finished_maintenance_ignored() {
+        let now = Utc::now();
+        let json = status_json(
+            now - chrono::Duration::minutes(30),
+            now + chrono::Duration::hours(1),
+            "completed",
+        );
+        let status: InstanceStatus = serde_json::from_value(json).unwrap();
+        assert!(status.active_maintenance(now, Duration::ZERO).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_preflight_defers_during_maintenance() {
+        let mock_server = MockServer::start().await;
+        let now = Utc::now();
+
+        Mock::given(method("GET"))
+            .and(path("/instances/NA135/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(status_json(
+                now - chrono::Duration::minutes(5),
+                now + chrono::Duration::hours(1),
+                "inProgress",
+            )))
+            .mount(&mock_server)
+            .await;
+
+        let trust = TrustClient::new().unwrap().with_base_url(mock_server.uri());
+        let preflight = MaintenancePreflight::new("NA135")
+            .unwrap()
+            .with_trust_client(trust.clone());
+
+        // Warn (default) passes.
+        preflight.check().await.unwrap();
+
+        // Defer fails with the maintenance details.
+        let err = preflight
+            .with_action(PreflightAction::Defer)
+            .check()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            ErrorKind::MaintenanceWindow { ref name, .. } if name == "NA135 Maintenance"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_preflight_passes_when_trust_unavailable() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let trust = TrustClient::new().unwrap().with_base_url(mock_server.uri());
+        MaintenancePreflight::new("NA135")
+            .unwrap()
+            .with_trust_client(trust)
+            .with_action(PreflightAction::Defer)
+            .check()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_instance_key() {
+        let trust = TrustClient::new().unwrap();
+        assert!(trust.instance_status("../admin").await.is_err());
+    }
+}
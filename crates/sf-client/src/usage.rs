@@ -0,0 +1,214 @@
+//! HTTP traffic accounting for chargeback and budget enforcement.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::response::ApiUsage;
+
+/// Shared counters recording the traffic sent through an [`SfHttpClient`](crate::SfHttpClient).
+///
+/// Attach a tracker with [`SalesforceClient::with_usage_tracker`](crate::SalesforceClient::with_usage_tracker);
+/// every clone of that client then records into the same counters. Each
+/// attempt counts as a request, so retries are included.
+///
+/// Response bytes are recorded when the body is read (after decompression).
+/// Bodies taken via [`Response::into_inner`](crate::Response::into_inner)
+/// fall back to the `Content-Length` header, if present.
+///
+/// The tracker also keeps the org's daily API consumption as last reported
+/// in a `Sforce-Limit-Info` response header. With
+/// [`UsageTracker::with_api_soft_limit`], requests are refused with
+/// [`ErrorKind::ApiSoftLimit`] once that consumption reaches a threshold,
+/// leaving headroom for other integrations sharing the org. The guard
+/// lifts once the reading is older than [`DEFAULT_SOFT_LIMIT_TTL`] (see
+/// [`UsageTracker::with_api_soft_limit_ttl`]): the next request goes out and
+/// its response either re-arms the guard or shows usage has dropped.
+/// [`UsageTracker::clear_api_usage`] lifts it straight away.
+#[derive(Debug, Default)]
+pub struct UsageTracker {
+    requests: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    api_usage: Mutex<Option<(ApiUsage, Instant)>>,
+    soft_limit_percent: Option<f64>,
+    soft_limit_ttl: Option<Duration>,
+}
+
+/// How long a reported API usage keeps the soft limit in force.
+pub const DEFAULT_SOFT_LIMIT_TTL: Duration = Duration::from_secs(5 * 60);
+
+impl UsageTracker {
+    /// Create a tracker with all counters at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refuse requests once the org has used `percent` of its daily API
+    /// requests, according to the most recent `Sforce-Limit-Info` header.
+    ///
+    /// No request is refused until a response has reported usage.
+    pub fn with_api_soft_limit(mut self, percent: f64) -> Self {
+        self.soft_limit_percent = Some(percent);
+        self
+    }
+
+    /// How long a usage reading at or above the soft limit keeps refusing
+    /// requests. Defaults to [`DEFAULT_SOFT_LIMIT_TTL`].
+    pub fn with_api_soft_limit_ttl(mut self, ttl: Duration) -> Self {
+        self.soft_limit_ttl = Some(ttl);
+        self
+    }
+
+    /// Take a point-in-time copy of the counters.
+    pub fn snapshot(&self) -> UsageSnapshot {
+        UsageSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            api_usage: self.api_usage(),
+        }
+    }
+
+    /// The org's daily API usage from the most recent response that reported it.
+    pub fn api_usage(&self) -> Option<ApiUsage> {
+        self.api_usage
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .map(|(usage, _)| usage)
+    }
+
+    /// Forget the last reported API usage, lifting the soft limit until a
+    /// response reports usage again.
+    ///
+    /// Call this once usage is known to have dropped, for example after
+    /// checking [`/limits`](https://developer.salesforce.com/docs/atlas.en-us.api_rest.meta/api_rest/resources_limits.htm)
+    /// out of band.
+    pub fn clear_api_usage(&self) {
+        *self.api_usage.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    pub(crate) fn record_api_usage(&self, usage: ApiUsage) {
+        *self.api_usage.lock().unwrap_or_else(|e| e.into_inner()) = Some((usage, Instant::now()));
+    }
+
+    /// Fail if the last reported API usage is at or above the soft limit
+    /// and was reported within the TTL.
+    pub(crate) fn check_soft_limit(&self) -> Result<()> {
+        let reading = *self.api_usage.lock().unwrap_or_else(|e| e.into_inner());
+        let (Some(percent), Some((usage, reported_at))) = (self.soft_limit_percent, reading) else {
+            return Ok(());
+        };
+        let ttl = self.soft_limit_ttl.unwrap_or(DEFAULT_SOFT_LIMIT_TTL);
+        if usage.is_above_threshold(percent) && reported_at.elapsed() < ttl {
+            return Err(Error::new(ErrorKind::ApiSoftLimit {
+                used: usage.used,
+                limit: usage.limit,
+            }));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn record_request(&self, body_len: u64) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(body_len, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_received(&self, len: u64) {
+        self.bytes_received.fetch_add(len, Ordering::Relaxed);
+    }
+}
+
+/// Counter values captured by [`UsageTracker::snapshot`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsageSnapshot {
+    /// HTTP requests sent, including retried attempts.
+    pub requests: u64,
+    /// Request body bytes sent.
+    pub bytes_sent: u64,
+    /// Response body bytes received.
+    pub bytes_received: u64,
+    /// The org's daily API usage as last reported by Salesforce.
+    pub api_usage: Option<ApiUsage>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_records() {
+        let tracker = UsageTracker::new();
+        tracker.record_request(10);
+        tracker.record_request(0);
+        tracker.record_received(250);
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.requests, 2);
+        assert_eq!(snapshot.bytes_sent, 10);
+        assert_eq!(snapshot.bytes_received, 250);
+        assert_eq!(snapshot.api_usage, None);
+    }
+
+    #[test]
+    fn test_soft_limit() {
+        let tracker = UsageTracker::new().with_api_soft_limit(90.0);
+        assert!(tracker.check_soft_limit().is_ok());
+
+        tracker.record_api_usage(ApiUsage {
+            used: 8_000,
+            limit: 10_000,
+        });
+        assert!(tracker.check_soft_limit().is_ok());
+
+        tracker.record_api_usage(ApiUsage {
+            used: 9_000,
+            limit: 10_000,
+        });
+        let err = tracker.check_soft_limit().unwrap_err();
+        assert!(matches!(
+            err.kind,
+            ErrorKind::ApiSoftLimit {
+                used: 9_000,
+                limit: 10_000
+            }
+        ));
+        assert!(!err.is_retryable());
+
+        // Clearing the reading lifts the guard until usage is reported again.
+        tracker.clear_api_usage();
+        assert!(tracker.check_soft_limit().is_ok());
+
+        // Without a soft limit the tracker only records.
+        let tracker = UsageTracker::new();
+        tracker.record_api_usage(ApiUsage {
+            used: 10_000,
+            limit: 10_000,
+        });
+        assert!(tracker.check_soft_limit().is_ok());
+        assert_eq!(tracker.snapshot().api_usage.unwrap().remaining(), 0);
+    }
+
+    #[test]
+    fn test_soft_limit_lifts_after_ttl() {
+        let tracker = UsageTracker::new()
+            .with_api_soft_limit(90.0)
+            .with_api_soft_limit_ttl(Duration::from_millis(20));
+        tracker.record_api_usage(ApiUsage {
+            used: 9_500,
+            limit: 10_000,
+        });
+        assert!(tracker.check_soft_limit().is_err());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(tracker.check_soft_limit().is_ok());
+
+        // A fresh report at the threshold re-arms the guard.
+        tracker.record_api_usage(ApiUsage {
+            used: 9_600,
+            limit: 10_000,
+        });
+        assert!(tracker.check_soft_limit().is_err());
+    }
+}
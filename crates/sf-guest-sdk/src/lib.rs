@@ -18,7 +18,7 @@
 //!
 //! - **REST API**: SOQL queries, CRUD, composite, collections, search, limits
 //! - **Bulk API**: Ingest jobs, query jobs, CSV upload/download
-//! - **Tooling API**: Apex execution, tooling SOQL, tooling CRUD
+//! - **Tooling API**: Apex execution, tooling SOQL, tooling CRUD, async job monitoring
 //! - **Metadata API**: Deploy, retrieve, list, describe metadata
 //!
 //! ## Example Plugin
@@ -63,13 +63,17 @@ extern "ExtismHost" {
     // REST API
     fn sf_query(input: Vec<u8>) -> Vec<u8>;
     fn sf_query_more(input: Vec<u8>) -> Vec<u8>;
+    fn sf_query_cursor_next(input: Vec<u8>) -> Vec<u8>;
     fn sf_create(input: Vec<u8>) -> Vec<u8>;
     fn sf_get(input: Vec<u8>) -> Vec<u8>;
     fn sf_update(input: Vec<u8>) -> Vec<u8>;
     fn sf_delete(input: Vec<u8>) -> Vec<u8>;
     fn sf_upsert(input: Vec<u8>) -> Vec<u8>;
+    fn sf_get_by_external_id(input: Vec<u8>) -> Vec<u8>;
+    fn sf_delete_by_external_id(input: Vec<u8>) -> Vec<u8>;
     fn sf_describe_global(input: Vec<u8>) -> Vec<u8>;
     fn sf_describe_sobject(input: Vec<u8>) -> Vec<u8>;
+    fn sf_schema_snapshot(input: Vec<u8>) -> Vec<u8>;
     fn sf_search(input: Vec<u8>) -> Vec<u8>;
     fn sf_composite(input: Vec<u8>) -> Vec<u8>;
     fn sf_composite_batch(input: Vec<u8>) -> Vec<u8>;
@@ -78,6 +82,8 @@ extern "ExtismHost" {
     fn sf_update_multiple(input: Vec<u8>) -> Vec<u8>;
     fn sf_get_multiple(input: Vec<u8>) -> Vec<u8>;
     fn sf_delete_multiple(input: Vec<u8>) -> Vec<u8>;
+    fn sf_empty_recycle_bin(input: Vec<u8>) -> Vec<u8>;
+    fn sf_undelete(input: Vec<u8>) -> Vec<u8>;
     fn sf_limits(input: Vec<u8>) -> Vec<u8>;
     fn sf_versions(input: Vec<u8>) -> Vec<u8>;
 
@@ -104,6 +110,7 @@ extern "ExtismHost" {
     // REST API: Sync
     fn sf_get_deleted(input: Vec<u8>) -> Vec<u8>;
     fn sf_get_updated(input: Vec<u8>) -> Vec<u8>;
+    fn sf_changes_since(input: Vec<u8>) -> Vec<u8>;
 
     // Bulk API
     fn sf_bulk_create_ingest_job(input: Vec<u8>) -> Vec<u8>;
@@ -114,15 +121,25 @@ extern "ExtismHost" {
     fn sf_bulk_get_job_results(input: Vec<u8>) -> Vec<u8>;
     fn sf_bulk_delete_ingest_job(input: Vec<u8>) -> Vec<u8>;
     fn sf_bulk_get_all_ingest_jobs(input: Vec<u8>) -> Vec<u8>;
+    fn sf_bulk_create_query_job(input: Vec<u8>) -> Vec<u8>;
+    fn sf_bulk_get_query_job(input: Vec<u8>) -> Vec<u8>;
     fn sf_bulk_abort_query_job(input: Vec<u8>) -> Vec<u8>;
     fn sf_bulk_get_query_results(input: Vec<u8>) -> Vec<u8>;
 
     // Tooling API
     fn sf_tooling_query(input: Vec<u8>) -> Vec<u8>;
+    fn sf_tooling_query_more(input: Vec<u8>) -> Vec<u8>;
     fn sf_tooling_execute_anonymous(input: Vec<u8>) -> Vec<u8>;
     fn sf_tooling_get(input: Vec<u8>) -> Vec<u8>;
     fn sf_tooling_create(input: Vec<u8>) -> Vec<u8>;
+    fn sf_tooling_update(input: Vec<u8>) -> Vec<u8>;
+    fn sf_tooling_upsert(input: Vec<u8>) -> Vec<u8>;
+    fn sf_tooling_composite(input: Vec<u8>) -> Vec<u8>;
     fn sf_tooling_delete(input: Vec<u8>) -> Vec<u8>;
+    fn sf_tooling_async_apex_jobs(input: Vec<u8>) -> Vec<u8>;
+    fn sf_tooling_abort_job(input: Vec<u8>) -> Vec<u8>;
+    fn sf_tooling_run_tests(input: Vec<u8>) -> Vec<u8>;
+    fn sf_tooling_test_run_summary(input: Vec<u8>) -> Vec<u8>;
 
     // Metadata API
     fn sf_metadata_deploy(input: Vec<u8>) -> Vec<u8>;
@@ -153,6 +170,11 @@ extern "ExtismHost" {
     fn sf_knowledge_articles(input: Vec<u8>) -> Vec<u8>;
     fn sf_data_category_groups(input: Vec<u8>) -> Vec<u8>;
     fn sf_data_categories(input: Vec<u8>) -> Vec<u8>;
+    fn sf_knowledge_article(input: Vec<u8>) -> Vec<u8>;
+    fn sf_create_knowledge_draft(input: Vec<u8>) -> Vec<u8>;
+    fn sf_publish_knowledge_articles(input: Vec<u8>) -> Vec<u8>;
+    fn sf_archive_knowledge_articles(input: Vec<u8>) -> Vec<u8>;
+    fn sf_knowledge_draft_from_online(input: Vec<u8>) -> Vec<u8>;
 
     // Priority 2: Standalone
     fn sf_tabs(input: Vec<u8>) -> Vec<u8>;
@@ -162,6 +184,7 @@ extern "ExtismHost" {
     fn sf_relevant_items(input: Vec<u8>) -> Vec<u8>;
     fn sf_compact_layouts_multi(input: Vec<u8>) -> Vec<u8>;
     fn sf_platform_event_schema(input: Vec<u8>) -> Vec<u8>;
+    fn sf_publish_event(input: Vec<u8>) -> Vec<u8>;
     fn sf_lightning_toggle_metrics(input: Vec<u8>) -> Vec<u8>;
     fn sf_lightning_usage(input: Vec<u8>) -> Vec<u8>;
 
@@ -195,6 +218,21 @@ extern "ExtismHost" {
 
     // Priority 2: Composite Enhancement
     fn sf_composite_graph(input: Vec<u8>) -> Vec<u8>;
+
+    // Outbound HTTP
+    fn sf_http_request(input: Vec<u8>) -> Vec<u8>;
+
+    // Apex REST
+    fn sf_apex_rest(input: Vec<u8>) -> Vec<u8>;
+
+    // Invocation context
+    fn sf_invocation_context(input: Vec<u8>) -> Vec<u8>;
+
+    // Long-running operations
+    fn sf_operation_status(input: Vec<u8>) -> Vec<u8>;
+
+    // Capability discovery
+    fn sf_capabilities(input: Vec<u8>) -> Vec<u8>;
 }
 
 // =============================================================================
@@ -204,7 +242,7 @@ extern "ExtismHost" {
 /// Execute a SOQL query.
 ///
 /// Returns the first page of results. Check `done` and `next_records_url`
-/// for pagination.
+/// for pagination, or `cursor` when the host's query memory budget ran out.
 ///
 /// # Example
 ///
@@ -215,20 +253,32 @@ extern "ExtismHost" {
 /// }
 /// ```
 pub fn query(soql: &str) -> Result<QueryResponse, Error> {
-    let request = QueryRequest {
-        soql: soql.to_string(),
-        include_deleted: false,
-    };
-    call_host_fn(|input| unsafe { sf_query(input) }, &request)
+    query_bound(&QueryRequest::new(soql))
 }
 
 /// Execute a SOQL query including deleted/archived records.
 pub fn query_all(soql: &str) -> Result<QueryResponse, Error> {
     let request = QueryRequest {
-        soql: soql.to_string(),
         include_deleted: true,
+        ..QueryRequest::new(soql)
     };
-    call_host_fn(|input| unsafe { sf_query(input) }, &request)
+    query_bound(&request)
+}
+
+/// Execute a SOQL template whose `:name` placeholders the host fills in.
+///
+/// The host escapes each bound value, and hosts that require bound
+/// queries reject string literals written into the SOQL itself.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let request = QueryRequest::new("SELECT Id FROM Contact WHERE Email = :email")
+///     .bind("email", SoqlBind::String(input.email));
+/// let result = query_bound(&request)?;
+/// ```
+pub fn query_bound(request: &QueryRequest) -> Result<QueryResponse, Error> {
+    call_host_fn(|input| unsafe { sf_query(input) }, request)
 }
 
 /// Fetch the next page of query results.
@@ -241,6 +291,82 @@ pub fn query_more(next_records_url: &str) -> Result<QueryResponse, Error> {
     call_host_fn(|input| unsafe { sf_query_more(input) }, &request)
 }
 
+/// Read up to `max_records` of the query results the host is holding
+/// behind `cursor`.
+///
+/// Hosts with a query memory budget stop returning pages once the budget
+/// is used, and set [`QueryResponse::cursor`] instead. Process each chunk
+/// before reading the next so the whole result never sits in memory.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let page = query("SELECT Id FROM Task")?;
+/// handle(&page.records);
+/// if let Some(cursor) = page.cursor {
+///     loop {
+///         let chunk = query_cursor_next(&cursor, Some(500))?;
+///         handle(&chunk.records);
+///         if chunk.done {
+///             break;
+///         }
+///     }
+/// }
+/// ```
+pub fn query_cursor_next(cursor: &str, max_records: Option<u32>) -> Result<QueryCursorPage, Error> {
+    let request = QueryCursorRequest {
+        cursor: cursor.to_string(),
+        max_records,
+    };
+    call_host_fn(|input| unsafe { sf_query_cursor_next(input) }, &request)
+}
+
+/// Execute a SOQL query and deserialize every record into `T`, fetching
+/// all pages.
+///
+/// Record `attributes` are dropped first; see [`QueryResponse::records_as`].
+///
+/// # Example
+/// ```ignore
+/// #[derive(serde::Deserialize)]
+/// struct Account {
+///     #[serde(rename = "Id")]
+///     id: String,
+///     #[serde(rename = "Name")]
+///     name: String,
+/// }
+///
+/// let accounts: Vec<Account> = query_as("SELECT Id, Name FROM Account")?;
+/// ```
+pub fn query_as<T: serde::de::DeserializeOwned>(soql: &str) -> Result<Vec<T>, Error> {
+    let mut page = query(soql)?;
+    let mut records = Vec::new();
+    loop {
+        records.extend(
+            page.records_as::<T>()
+                .map_err(|e| Error::msg(format!("deserialize error: {e}")))?,
+        );
+        if let Some(cursor) = page.cursor.take() {
+            // Past the host's memory budget; the rest comes through the cursor.
+            loop {
+                let chunk = query_cursor_next(&cursor, None)?;
+                records.extend(
+                    chunk
+                        .records_as::<T>()
+                        .map_err(|e| Error::msg(format!("deserialize error: {e}")))?,
+                );
+                if chunk.done {
+                    return Ok(records);
+                }
+            }
+        }
+        match page.next_records_url {
+            Some(ref next) if !page.done => page = query_more(next)?,
+            _ => return Ok(records),
+        }
+    }
+}
+
 /// Create a new record.
 ///
 /// Returns the result including the new record's ID.
@@ -292,6 +418,39 @@ pub fn update(sobject: &str, id: &str, record: &serde_json::Value) -> Result<(),
         sobject: sobject.to_string(),
         id: id.to_string(),
         record: record.clone(),
+        precondition: None,
+    };
+    call_host_fn(|input| unsafe { sf_update(input) }, &request)
+}
+
+/// Update a record only if nobody changed it since it was read.
+///
+/// Fails with code `PRECONDITION_FAILED` if the record no longer meets
+/// `precondition`; re-read it and try again.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let record = get("Account", id, Some(vec!["Name".into(), "LastModifiedDate".into()]))?;
+/// // Convert LastModifiedDate to an HTTP date first.
+/// update_if_unchanged(
+///     "Account",
+///     id,
+///     &serde_json::json!({"Name": "Renamed"}),
+///     WritePrecondition::IfUnmodifiedSince("Wed, 21 Oct 2015 07:28:00 GMT".into()),
+/// )?;
+/// ```
+pub fn update_if_unchanged(
+    sobject: &str,
+    id: &str,
+    record: &serde_json::Value,
+    precondition: WritePrecondition,
+) -> Result<(), Error> {
+    let request = UpdateRequest {
+        sobject: sobject.to_string(),
+        id: id.to_string(),
+        record: record.clone(),
+        precondition: Some(precondition),
     };
     call_host_fn(|input| unsafe { sf_update(input) }, &request)
 }
@@ -307,6 +466,23 @@ pub fn delete(sobject: &str, id: &str) -> Result<(), Error> {
     let request = DeleteRequest {
         sobject: sobject.to_string(),
         id: id.to_string(),
+        precondition: None,
+    };
+    call_host_fn(|input| unsafe { sf_delete(input) }, &request)
+}
+
+/// Delete a record only if nobody changed it since it was read.
+///
+/// Fails like [`update_if_unchanged`].
+pub fn delete_if_unchanged(
+    sobject: &str,
+    id: &str,
+    precondition: WritePrecondition,
+) -> Result<(), Error> {
+    let request = DeleteRequest {
+        sobject: sobject.to_string(),
+        id: id.to_string(),
+        precondition: Some(precondition),
     };
     call_host_fn(|input| unsafe { sf_delete(input) }, &request)
 }
@@ -329,19 +505,86 @@ pub fn upsert(
     call_host_fn(|input| unsafe { sf_upsert(input) }, &request)
 }
 
+/// Get a record by an external ID field.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let account = get_by_external_id("Account", "External_Id__c", "EXT-001")?;
+/// ```
+pub fn get_by_external_id(
+    sobject: &str,
+    external_id_field: &str,
+    external_id_value: &str,
+) -> Result<serde_json::Value, Error> {
+    let request = ExternalIdRequest {
+        sobject: sobject.to_string(),
+        external_id_field: external_id_field.to_string(),
+        external_id_value: external_id_value.to_string(),
+    };
+    call_host_fn(|input| unsafe { sf_get_by_external_id(input) }, &request)
+}
+
+/// Delete a record by an external ID field.
+pub fn delete_by_external_id(
+    sobject: &str,
+    external_id_field: &str,
+    external_id_value: &str,
+) -> Result<(), Error> {
+    let request = ExternalIdRequest {
+        sobject: sobject.to_string(),
+        external_id_field: external_id_field.to_string(),
+        external_id_value: external_id_value.to_string(),
+    };
+    call_host_fn(|input| unsafe { sf_delete_by_external_id(input) }, &request)
+}
+
 /// Get metadata for all SObjects in the org.
 pub fn describe_global() -> Result<serde_json::Value, Error> {
     call_host_fn_no_input(|input| unsafe { sf_describe_global(input) })
 }
 
 /// Get metadata for a specific SObject.
-pub fn describe_sobject(sobject: &str) -> Result<serde_json::Value, Error> {
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let account = describe_sobject("Account")?;
+/// let required: Vec<&str> = account
+///     .fields
+///     .iter()
+///     .filter(|f| f.is_required_on_create())
+///     .map(|f| f.name.as_str())
+///     .collect();
+/// ```
+pub fn describe_sobject(sobject: &str) -> Result<SObjectDescribe, Error> {
     let request = DescribeSObjectRequest {
         sobject: sobject.to_string(),
     };
     call_host_fn(|input| unsafe { sf_describe_sobject(input) }, &request)
 }
 
+/// Get the full, untyped describe of an SObject, for properties
+/// [`SObjectDescribe`] doesn't cover.
+pub fn describe_sobject_raw(sobject: &str) -> Result<serde_json::Value, Error> {
+    let request = DescribeSObjectRequest {
+        sobject: sobject.to_string(),
+    };
+    call_host_fn(|input| unsafe { sf_describe_sobject(input) }, &request)
+}
+
+/// Get a compact schema of several SObjects in one call.
+///
+/// Cheaper than a `describe_sobject()` per object when a guest only needs
+/// field names, types, and writability. Keep the returned `digest` to tell
+/// whether the schema changed since a cached snapshot.
+pub fn schema_snapshot(sobjects: &[&str]) -> Result<SchemaSnapshot, Error> {
+    let request = SchemaSnapshotRequest {
+        sobjects: sobjects.iter().map(|s| s.to_string()).collect(),
+    };
+    call_host_fn(|input| unsafe { sf_schema_snapshot(input) }, &request)
+}
+
 /// Execute a SOSL full-text search.
 pub fn search(sosl: &str) -> Result<SearchResponse, Error> {
     let request = SearchRequest {
@@ -353,7 +596,10 @@ pub fn search(sosl: &str) -> Result<SearchResponse, Error> {
 /// Execute a composite API request.
 ///
 /// Allows multiple subrequests in a single API call. Subrequests can
-/// reference results from earlier subrequests using `@{referenceId}`.
+/// reference results from earlier subrequests using `@{referenceId}`,
+/// and carry their own headers via
+/// [`CompositeSubrequest::with_header`]
+/// (for example `Sforce-Auto-Assign` or `If-Match`).
 pub fn composite(request: &CompositeRequest) -> Result<CompositeResponse, Error> {
     call_host_fn(|input| unsafe { sf_composite(input) }, request)
 }
@@ -423,6 +669,20 @@ pub fn delete_multiple(
     call_host_fn(|input| unsafe { sf_delete_multiple(input) }, &request)
 }
 
+/// Permanently delete records from the Recycle Bin (up to 200).
+///
+/// The records must already be deleted.
+pub fn empty_recycle_bin(ids: Vec<String>) -> Result<Vec<CollectionResult>, Error> {
+    let request = EmptyRecycleBinRequest { ids };
+    call_host_fn(|input| unsafe { sf_empty_recycle_bin(input) }, &request)
+}
+
+/// Restore deleted records from the Recycle Bin (up to 200).
+pub fn undelete(ids: Vec<String>) -> Result<Vec<CollectionResult>, Error> {
+    let request = UndeleteRequest { ids };
+    call_host_fn(|input| unsafe { sf_undelete(input) }, &request)
+}
+
 /// Get API limits for the org.
 pub fn limits() -> Result<serde_json::Value, Error> {
     call_host_fn_no_input(|input| unsafe { sf_limits(input) })
@@ -577,7 +837,9 @@ pub fn invoke_quick_action(
 /// Get deleted records for an SObject within a date range.
 ///
 /// The start and end parameters should be ISO 8601 date-time strings
-/// (e.g., "2024-01-01T00:00:00Z").
+/// (e.g., "2024-01-01T00:00:00Z"). The host clamps the range to the last
+/// 30 days and splits ranges with too many records into several calls, so
+/// any range may be passed.
 pub fn get_deleted(sobject: &str, start: &str, end: &str) -> Result<GetDeletedResult, Error> {
     let request = GetDeletedRequest {
         sobject: sobject.to_string(),
@@ -590,7 +852,8 @@ pub fn get_deleted(sobject: &str, start: &str, end: &str) -> Result<GetDeletedRe
 /// Get updated record IDs for an SObject within a date range.
 ///
 /// The start and end parameters should be ISO 8601 date-time strings
-/// (e.g., "2024-01-01T00:00:00Z").
+/// (e.g., "2024-01-01T00:00:00Z"). As with [`get_deleted`], the host
+/// clamps and splits the range.
 pub fn get_updated(sobject: &str, start: &str, end: &str) -> Result<GetUpdatedResult, Error> {
     let request = GetUpdatedRequest {
         sobject: sobject.to_string(),
@@ -600,6 +863,18 @@ pub fn get_updated(sobject: &str, start: &str, end: &str) -> Result<GetUpdatedRe
     call_host_fn(|input| unsafe { sf_get_updated(input) }, &request)
 }
 
+/// Get every record of an SObject updated or deleted since `watermark`.
+///
+/// Store the returned watermark and pass it to the next call to pick up
+/// where this one left off.
+pub fn changes_since(sobject: &str, watermark: &str) -> Result<ChangeJournal, Error> {
+    let request = ChangesSinceRequest {
+        sobject: sobject.to_string(),
+        watermark: watermark.to_string(),
+    };
+    call_host_fn(|input| unsafe { sf_changes_since(input) }, &request)
+}
+
 // =============================================================================
 // Bulk API wrappers
 // =============================================================================
@@ -609,22 +884,28 @@ pub fn get_updated(sobject: &str, start: &str, end: &str) -> Result<GetUpdatedRe
 /// # Example
 ///
 /// ```rust,ignore
-/// let job = bulk_create_ingest_job("Account", "insert", None, "COMMA", "LF")?;
+/// let job = bulk_create_ingest_job(
+///     "Account",
+///     BulkOperation::Insert,
+///     None,
+///     ColumnDelimiter::Comma,
+///     LineEnding::Lf,
+/// )?;
 /// let job_id = job.id;
 /// ```
 pub fn bulk_create_ingest_job(
     sobject: &str,
-    operation: &str,
+    operation: BulkOperation,
     external_id_field: Option<String>,
-    column_delimiter: &str,
-    line_ending: &str,
+    column_delimiter: ColumnDelimiter,
+    line_ending: LineEnding,
 ) -> Result<BulkJobResponse, Error> {
     let request = BulkCreateIngestJobRequest {
         sobject: sobject.to_string(),
-        operation: operation.to_string(),
+        operation,
         external_id_field,
-        column_delimiter: column_delimiter.to_string(),
-        line_ending: line_ending.to_string(),
+        column_delimiter,
+        line_ending,
     };
     call_host_fn(
         |input| unsafe { sf_bulk_create_ingest_job(input) },
@@ -710,6 +991,52 @@ pub fn bulk_get_all_ingest_jobs() -> Result<BulkJobListResponse, Error> {
     call_host_fn_no_input(|input| unsafe { sf_bulk_get_all_ingest_jobs(input) })
 }
 
+/// List the ingest jobs matching `request`'s filters.
+///
+/// The host reads every page of results, so the response holds all
+/// matching jobs.
+pub fn bulk_list_ingest_jobs(
+    request: &BulkGetAllIngestJobsRequest,
+) -> Result<BulkJobListResponse, Error> {
+    call_host_fn(
+        |input| unsafe { sf_bulk_get_all_ingest_jobs(input) },
+        request,
+    )
+}
+
+/// Create a bulk query job.
+///
+/// Use [`QueryOperation::QueryAll`] to include deleted and archived
+/// records. Poll the job with [`bulk_get_query_job`] until its
+/// state is `JobComplete`, then page through the CSV with
+/// [`bulk_get_query_results`]. Use [`bulk_create_query_job_bound`] to pass
+/// values as binds.
+pub fn bulk_create_query_job(
+    soql: &str,
+    operation: QueryOperation,
+) -> Result<BulkJobResponse, Error> {
+    bulk_create_query_job_bound(&BulkCreateQueryJobRequest {
+        soql: soql.to_string(),
+        operation,
+        binds: Default::default(),
+    })
+}
+
+/// Create a bulk query job whose SOQL is a template filled from `binds`.
+pub fn bulk_create_query_job_bound(
+    request: &BulkCreateQueryJobRequest,
+) -> Result<BulkJobResponse, Error> {
+    call_host_fn(|input| unsafe { sf_bulk_create_query_job(input) }, request)
+}
+
+/// Get the status of a bulk query job.
+pub fn bulk_get_query_job(job_id: &str) -> Result<BulkJobResponse, Error> {
+    let request = BulkJobIdRequest {
+        job_id: job_id.to_string(),
+    };
+    call_host_fn(|input| unsafe { sf_bulk_get_query_job(input) }, &request)
+}
+
 /// Abort a bulk query job.
 pub fn bulk_abort_query_job(job_id: &str) -> Result<BulkJobResponse, Error> {
     let request = BulkJobIdRequest {
@@ -750,6 +1077,32 @@ pub fn tooling_query(soql: &str) -> Result<QueryResponse, Error> {
     call_host_fn(|input| unsafe { sf_tooling_query(input) }, &request)
 }
 
+/// Fetch the next page of Tooling query results.
+///
+/// Use the `next_records_url` from a previous [`tooling_query`] response.
+pub fn tooling_query_more(next_records_url: &str) -> Result<QueryResponse, Error> {
+    let request = ToolingQueryMoreRequest {
+        next_records_url: next_records_url.to_string(),
+    };
+    call_host_fn(|input| unsafe { sf_tooling_query_more(input) }, &request)
+}
+
+/// Execute a Tooling API SOQL query and return the records of every page.
+///
+/// # Example
+/// ```ignore
+/// let classes = tooling_query_all("SELECT Id, Name FROM ApexClass")?;
+/// ```
+pub fn tooling_query_all(soql: &str) -> Result<Vec<serde_json::Value>, Error> {
+    let mut page = tooling_query(soql)?;
+    let mut records = std::mem::take(&mut page.records);
+    while let (false, Some(next)) = (page.done, page.next_records_url.as_deref()) {
+        page = tooling_query_more(next)?;
+        records.append(&mut page.records);
+    }
+    Ok(records)
+}
+
 /// Execute anonymous Apex code.
 ///
 /// # Example
@@ -761,6 +1114,34 @@ pub fn tooling_query(soql: &str) -> Result<QueryResponse, Error> {
 pub fn tooling_execute_anonymous(apex_code: &str) -> Result<ExecuteAnonymousResponse, Error> {
     let request = ExecuteAnonymousRequest {
         apex_code: apex_code.to_string(),
+        capture_log: false,
+    };
+    call_host_fn(
+        |input| unsafe { sf_tooling_execute_anonymous(input) },
+        &request,
+    )
+}
+
+/// Execute anonymous Apex code and return its debug log in `debug_log`.
+///
+/// The host sets a temporary trace flag on the running user for the call.
+/// Compile and runtime failures come back with `success: false` rather
+/// than as an `Err`, so the log that explains them isn't lost.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let result = tooling_execute_anonymous_with_log("Integer i = 1 / 0;")?;
+/// if !result.success {
+///     let log = result.debug_log.unwrap_or_default();
+/// }
+/// ```
+pub fn tooling_execute_anonymous_with_log(
+    apex_code: &str,
+) -> Result<ExecuteAnonymousResponse, Error> {
+    let request = ExecuteAnonymousRequest {
+        apex_code: apex_code.to_string(),
+        capture_log: true,
     };
     call_host_fn(
         |input| unsafe { sf_tooling_execute_anonymous(input) },
@@ -789,6 +1170,43 @@ pub fn tooling_create(
     call_host_fn(|input| unsafe { sf_tooling_create(input) }, &request)
 }
 
+/// Update fields on a Tooling API record.
+///
+/// # Example
+/// ```ignore
+/// // Push out a trace flag's expiry.
+/// tooling_update(
+///     "TraceFlag",
+///     &flag_id,
+///     &serde_json::json!({"ExpirationDate": "2026-12-31T23:59:59.000Z"}),
+/// )?;
+/// ```
+pub fn tooling_update(sobject: &str, id: &str, record: &serde_json::Value) -> Result<(), Error> {
+    let request = ToolingUpdateRequest {
+        sobject: sobject.to_string(),
+        id: id.to_string(),
+        record: record.clone(),
+    };
+    call_host_fn(|input| unsafe { sf_tooling_update(input) }, &request)
+}
+
+/// Upsert a Tooling API record by an external ID field, such as
+/// `DeveloperName` on `DebugLevel`.
+pub fn tooling_upsert(
+    sobject: &str,
+    external_id_field: &str,
+    external_id_value: &str,
+    record: &serde_json::Value,
+) -> Result<UpsertResponse, Error> {
+    let request = ToolingUpsertRequest {
+        sobject: sobject.to_string(),
+        external_id_field: external_id_field.to_string(),
+        external_id_value: external_id_value.to_string(),
+        record: record.clone(),
+    };
+    call_host_fn(|input| unsafe { sf_tooling_upsert(input) }, &request)
+}
+
 /// Delete a Tooling API record.
 pub fn tooling_delete(sobject: &str, id: &str) -> Result<(), Error> {
     let request = ToolingDeleteRequest {
@@ -798,6 +1216,99 @@ pub fn tooling_delete(sobject: &str, id: &str) -> Result<(), Error> {
     call_host_fn(|input| unsafe { sf_tooling_delete(input) }, &request)
 }
 
+/// Execute a Tooling API composite request.
+///
+/// Subrequests use Tooling URLs and run in order, so later ones can refer
+/// to earlier results; with `all_or_none` they succeed or fail together.
+///
+/// # Example
+/// ```ignore
+/// let sub = |method: &str, url: &str, reference_id: &str, body| CompositeSubrequest {
+///     method: method.into(),
+///     url: format!("/services/data/v62.0/tooling/sobjects/{url}"),
+///     reference_id: reference_id.into(),
+///     body: Some(body),
+///     http_headers: Default::default(),
+/// };
+/// let response = tooling_composite(&CompositeRequest {
+///     all_or_none: true,
+///     subrequests: vec![
+///         sub("POST", "MetadataContainer", "container", json!({"Name": "HotFix"})),
+///         sub("POST", "ApexClassMember", "member", json!({
+///             "MetadataContainerId": "@{container.id}",
+///             "ContentEntityId": class_id,
+///             "Body": source,
+///         })),
+///         sub("POST", "ContainerAsyncRequest", "deploy", json!({
+///             "MetadataContainerId": "@{container.id}",
+///             "IsCheckOnly": false,
+///         })),
+///     ],
+/// })?;
+/// ```
+pub fn tooling_composite(request: &CompositeRequest) -> Result<CompositeResponse, Error> {
+    call_host_fn(|input| unsafe { sf_tooling_composite(input) }, request)
+}
+
+/// List async Apex jobs (batch, queueable, future, scheduled), newest first.
+///
+/// # Example
+/// ```ignore
+/// // Queueables still waiting after an hour.
+/// let stuck = tooling_async_apex_jobs(&AsyncApexJobsRequest {
+///     statuses: vec!["Queued".into(), "Holding".into()],
+///     job_types: vec!["Queueable".into()],
+///     older_than_minutes: Some(60),
+///     ..Default::default()
+/// })?;
+/// ```
+pub fn tooling_async_apex_jobs(
+    request: &AsyncApexJobsRequest,
+) -> Result<Vec<serde_json::Value>, Error> {
+    call_host_fn(
+        |input| unsafe { sf_tooling_async_apex_jobs(input) },
+        request,
+    )
+}
+
+/// Abort an async Apex job or scheduled job (`System.abortJob`).
+pub fn tooling_abort_job(id: &str) -> Result<(), Error> {
+    let request = AbortJobRequest { id: id.to_string() };
+    call_host_fn(|input| unsafe { sf_tooling_abort_job(input) }, &request)
+}
+
+/// Enqueue Apex tests (`runTestsAsynchronous`), returning the test run ID.
+///
+/// # Example
+/// ```ignore
+/// let run_id = tooling_run_tests(&ToolingRunTestsRequest {
+///     test_level: Some("RunLocalTests".into()),
+///     ..Default::default()
+/// })?;
+/// // Later, or on each invocation until it's complete:
+/// let summary = tooling_test_run_summary(&run_id)?;
+/// if summary.complete && !summary.success {
+///     let failed: Vec<_> = summary.results.iter().filter(|r| r.outcome != "Pass").collect();
+///     // Report `failed`'s messages and stack traces, and fail the pipeline.
+/// }
+/// ```
+pub fn tooling_run_tests(request: &ToolingRunTestsRequest) -> Result<String, Error> {
+    let response: ToolingRunTestsResponse =
+        call_host_fn(|input| unsafe { sf_tooling_run_tests(input) }, request)?;
+    Ok(response.test_run_id)
+}
+
+/// Get the class statuses and per-method results of a test run.
+pub fn tooling_test_run_summary(test_run_id: &str) -> Result<TestRunSummary, Error> {
+    let request = TestRunSummaryRequest {
+        test_run_id: test_run_id.to_string(),
+    };
+    call_host_fn(
+        |input| unsafe { sf_tooling_test_run_summary(input) },
+        &request,
+    )
+}
+
 // =============================================================================
 // Metadata API wrappers
 // =============================================================================
@@ -817,6 +1328,9 @@ pub fn metadata_deploy(
 }
 
 /// Check the status of a metadata deployment.
+///
+/// With `include_details`, the result's `details` lists each component
+/// failure, failed test, and code coverage warning.
 pub fn metadata_check_deploy_status(
     async_process_id: &str,
     include_details: bool,
@@ -840,6 +1354,9 @@ pub fn metadata_retrieve(request: &MetadataRetrieveRequest) -> Result<MetadataRe
 }
 
 /// Check the status of a metadata retrieve operation.
+///
+/// Once the retrieve is done, `file_properties` lists each retrieved file
+/// and `messages` any components that couldn't be retrieved.
 pub fn metadata_check_retrieve_status(
     async_process_id: &str,
     include_zip: bool,
@@ -985,6 +1502,76 @@ pub fn data_categories(group: &str, sobject: Option<String>) -> Result<serde_jso
     call_host_fn(|input| unsafe { sf_data_categories(input) }, &request)
 }
 
+/// Get the full content of a published knowledge article by ID or URL name.
+pub fn knowledge_article(article: &str) -> Result<serde_json::Value, Error> {
+    let request = KnowledgeArticleRequest {
+        article: article.to_string(),
+    };
+    call_host_fn(|input| unsafe { sf_knowledge_article(input) }, &request)
+}
+
+/// Create a draft knowledge article; the response ID is the new version's ID.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let draft = create_knowledge_draft(CreateKnowledgeDraftRequest {
+///     article_type: "Knowledge__kav".to_string(),
+///     title: "Reset your password".to_string(),
+///     url_name: "reset-password".to_string(),
+///     summary: None,
+///     language: None,
+///     fields: Default::default(),
+/// })?;
+/// publish_knowledge_articles(&[draft.id.as_str()], false)?;
+/// ```
+pub fn create_knowledge_draft(
+    request: CreateKnowledgeDraftRequest,
+) -> Result<CreateResponse, Error> {
+    call_host_fn(
+        |input| unsafe { sf_create_knowledge_draft(input) },
+        &request,
+    )
+}
+
+/// Publish draft article versions, optionally as a new major version.
+pub fn publish_knowledge_articles(version_ids: &[&str], new_version: bool) -> Result<(), Error> {
+    let request = PublishKnowledgeArticlesRequest {
+        version_ids: version_ids.iter().map(|s| s.to_string()).collect(),
+        new_version,
+    };
+    call_host_fn(
+        |input| unsafe { sf_publish_knowledge_articles(input) },
+        &request,
+    )
+}
+
+/// Archive published article versions.
+pub fn archive_knowledge_articles(version_ids: &[&str]) -> Result<(), Error> {
+    let request = ArchiveKnowledgeArticlesRequest {
+        version_ids: version_ids.iter().map(|s| s.to_string()).collect(),
+    };
+    call_host_fn(
+        |input| unsafe { sf_archive_knowledge_articles(input) },
+        &request,
+    )
+}
+
+/// Create an editable draft from a published article's `KnowledgeArticleId`.
+pub fn knowledge_draft_from_online(
+    knowledge_article_id: &str,
+    unpublish: bool,
+) -> Result<CreateResponse, Error> {
+    let request = KnowledgeDraftFromOnlineRequest {
+        knowledge_article_id: knowledge_article_id.to_string(),
+        unpublish,
+    };
+    call_host_fn(
+        |input| unsafe { sf_knowledge_draft_from_online(input) },
+        &request,
+    )
+}
+
 // =============================================================================
 // Priority 2: Standalone wrappers
 // =============================================================================
@@ -1026,6 +1613,34 @@ pub fn platform_event_schema(event_name: &str) -> Result<serde_json::Value, Erro
     call_host_fn(|input| unsafe { sf_platform_event_schema(input) }, &request)
 }
 
+/// Publish a platform event.
+///
+/// `event` is the event object's API name and `payload` its field values.
+/// A rejected event comes back with `success` unset and the reasons in
+/// `errors`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let result = publish_event(
+///     "Order_Shipped__e",
+///     serde_json::json!({"Order_Number__c": "A-1"}),
+/// )?;
+/// if !result.success {
+///     // inspect result.errors
+/// }
+/// ```
+pub fn publish_event(
+    event: &str,
+    payload: serde_json::Value,
+) -> Result<PublishEventResponse, Error> {
+    let request = PublishEventRequest {
+        event: event.to_string(),
+        payload,
+    };
+    call_host_fn(|input| unsafe { sf_publish_event(input) }, &request)
+}
+
 pub fn lightning_toggle_metrics() -> Result<serde_json::Value, Error> {
     call_host_fn_no_input(|input| unsafe { sf_lightning_toggle_metrics(input) })
 }
@@ -1169,8 +1784,350 @@ pub fn search_result_layouts(sobjects: Vec<String>) -> Result<Vec<serde_json::Va
 // Priority 2: Composite Enhancement wrappers
 // =============================================================================
 
-pub fn composite_graph(request: serde_json::Value) -> Result<serde_json::Value, Error> {
-    call_host_fn(|input| unsafe { sf_composite_graph(input) }, &request)
+/// Execute a composite graph API request.
+///
+/// Each graph succeeds or is rolled back as a whole. Build graphs with
+/// [`GraphBuilder`].
+pub fn composite_graph(request: &CompositeGraphRequest) -> Result<CompositeGraphResponse, Error> {
+    call_host_fn(|input| unsafe { sf_composite_graph(input) }, request)
+}
+
+/// API version used in URLs built by [`GraphBuilder`].
+const GRAPH_API_VERSION: &str = "62.0";
+
+/// Most nodes the composite graph API accepts in one graph.
+pub const MAX_GRAPH_NODES: usize = 500;
+
+/// A node added to a [`GraphBuilder`]; later nodes use it to reference
+/// the node's result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRef {
+    reference_id: String,
+}
+
+impl NodeRef {
+    /// The node's `referenceId`.
+    pub fn reference_id(&self) -> &str {
+        &self.reference_id
+    }
+
+    /// `@{referenceId.id}`: the ID of the record the node created.
+    pub fn id(&self) -> String {
+        self.field("id")
+    }
+
+    /// `@{referenceId.path}` for any path into the node's response body.
+    pub fn field(&self, path: &str) -> String {
+        format!("@{{{}.{}}}", self.reference_id, path)
+    }
+}
+
+/// Builds one graph for [`composite_graph`].
+///
+/// Nodes run in the order they are added and reference earlier nodes
+/// through the returned [`NodeRef`]s. [`build`](Self::build) rejects
+/// references to nodes that are missing or come later.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut graph = GraphBuilder::new("onboarding");
+/// let account = graph.create("acct", "Account", json!({"Name": "Acme"}));
+/// graph.create("contact", "Contact", json!({"LastName": "Smith", "AccountId": account.id()}));
+///
+/// let request = CompositeGraphRequest { graphs: vec![graph.build()?] };
+/// let response = composite_graph(&request)?;
+/// let account_id = response.graphs[0].created_id("acct");
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraphBuilder {
+    graph_id: String,
+    nodes: Vec<CompositeSubrequest>,
+}
+
+impl GraphBuilder {
+    /// Start a graph with the given `graphId`.
+    pub fn new(graph_id: impl Into<String>) -> Self {
+        Self {
+            graph_id: graph_id.into(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Add an arbitrary subrequest as a node.
+    pub fn node(&mut self, subrequest: CompositeSubrequest) -> NodeRef {
+        let node = NodeRef {
+            reference_id: subrequest.reference_id.clone(),
+        };
+        self.nodes.push(subrequest);
+        node
+    }
+
+    /// Add a node that creates an `sobject` record.
+    pub fn create(
+        &mut self,
+        reference_id: impl Into<String>,
+        sobject: &str,
+        record: serde_json::Value,
+    ) -> NodeRef {
+        self.push(
+            reference_id,
+            "POST",
+            sobject_url(sobject, &[]),
+            Some(record),
+        )
+    }
+
+    /// Add a node that updates the `sobject` record `id`, which may be a
+    /// reference such as [`NodeRef::id`].
+    pub fn update(
+        &mut self,
+        reference_id: impl Into<String>,
+        sobject: &str,
+        id: &str,
+        record: serde_json::Value,
+    ) -> NodeRef {
+        self.push(
+            reference_id,
+            "PATCH",
+            sobject_url(sobject, &[id]),
+            Some(record),
+        )
+    }
+
+    /// Add a node that upserts an `sobject` record on an external ID field.
+    pub fn upsert(
+        &mut self,
+        reference_id: impl Into<String>,
+        sobject: &str,
+        external_id_field: &str,
+        external_id: &str,
+        record: serde_json::Value,
+    ) -> NodeRef {
+        let url = sobject_url(sobject, &[external_id_field, external_id]);
+        self.push(reference_id, "PATCH", url, Some(record))
+    }
+
+    /// Add a node that deletes the `sobject` record `id`.
+    pub fn delete(&mut self, reference_id: impl Into<String>, sobject: &str, id: &str) -> NodeRef {
+        self.push(reference_id, "DELETE", sobject_url(sobject, &[id]), None)
+    }
+
+    /// Add a node that reads the `sobject` record `id`.
+    pub fn get(&mut self, reference_id: impl Into<String>, sobject: &str, id: &str) -> NodeRef {
+        self.push(reference_id, "GET", sobject_url(sobject, &[id]), None)
+    }
+
+    /// Finish the graph, checking its size, `referenceId`s, and references.
+    pub fn build(self) -> Result<GraphRequest, Error> {
+        if self.nodes.is_empty() || self.nodes.len() > MAX_GRAPH_NODES {
+            return Err(Error::msg(format!(
+                "graph '{}' must have between 1 and {MAX_GRAPH_NODES} nodes",
+                self.graph_id
+            )));
+        }
+        let mut seen = std::collections::HashSet::new();
+        for node in &self.nodes {
+            let mut texts = vec![node.url.clone()];
+            texts.extend(node.http_headers.values().cloned());
+            if let Some(body) = &node.body {
+                texts.push(body.to_string());
+            }
+            for text in &texts {
+                for reference in reference_ids(text) {
+                    if !seen.contains(reference) {
+                        return Err(Error::msg(format!(
+                            "node '{}' references '{reference}', which is not an earlier node",
+                            node.reference_id
+                        )));
+                    }
+                }
+            }
+            let valid = node
+                .reference_id
+                .starts_with(|c: char| c.is_ascii_alphabetic())
+                && node
+                    .reference_id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(Error::msg(format!(
+                    "invalid referenceId '{}'",
+                    node.reference_id
+                )));
+            }
+            if !seen.insert(node.reference_id.as_str()) {
+                return Err(Error::msg(format!(
+                    "duplicate referenceId '{}'",
+                    node.reference_id
+                )));
+            }
+        }
+        Ok(GraphRequest {
+            graph_id: self.graph_id,
+            subrequests: self.nodes,
+        })
+    }
+
+    fn push(
+        &mut self,
+        reference_id: impl Into<String>,
+        method: &str,
+        url: String,
+        body: Option<serde_json::Value>,
+    ) -> NodeRef {
+        self.node(CompositeSubrequest {
+            method: method.to_string(),
+            url,
+            reference_id: reference_id.into(),
+            body,
+            http_headers: Default::default(),
+        })
+    }
+}
+
+fn sobject_url(sobject: &str, segments: &[&str]) -> String {
+    let mut url = format!("/services/data/v{GRAPH_API_VERSION}/sobjects/{sobject}");
+    for segment in segments {
+        url.push('/');
+        url.push_str(segment);
+    }
+    url
+}
+
+/// The `referenceId` of every `@{referenceId.path}` in `s`.
+fn reference_ids(s: &str) -> Vec<&str> {
+    s.match_indices("@{")
+        .filter_map(|(at, _)| {
+            let rest = &s[at + 2..];
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (len > 0).then(|| &rest[..len])
+        })
+        .collect()
+}
+
+// =============================================================================
+// Apex REST wrappers
+// =============================================================================
+
+/// Call a custom Apex REST endpoint.
+///
+/// `path` is relative to `/services/apexrest/` and must be allow-listed by
+/// the host.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let order = apex_rest("POST", "Orders/v1", Some(serde_json::json!({"sku": "A-1"})))?;
+/// ```
+pub fn apex_rest(
+    method: &str,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> Result<serde_json::Value, Error> {
+    let request = ApexRestRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+        headers: Default::default(),
+    };
+    call_host_fn(|input| unsafe { sf_apex_rest(input) }, &request)
+}
+
+// =============================================================================
+// Outbound HTTP wrappers
+// =============================================================================
+
+/// Call a third-party API through the host.
+///
+/// Reference host-registered secrets in header values with
+/// `{{secret:NAME}}`; the host fills them in, so the secret never reaches
+/// the guest. Non-2xx responses are returned, not treated as errors.
+///
+/// The host decides which hosts are reachable and how large and slow a
+/// request may be; refusals come back as `HOST_NOT_ALLOWED`,
+/// `REQUEST_TOO_LARGE`, `RESPONSE_TOO_LARGE`, or `TIMEOUT`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut headers = std::collections::BTreeMap::new();
+/// headers.insert("Authorization".to_string(), "Bearer {{secret:clearbit}}".to_string());
+/// let response = http_request(OutboundHttpRequest {
+///     method: "GET".to_string(),
+///     url: "https://company.clearbit.com/v2/companies/find?domain=example.com".to_string(),
+///     headers,
+///     body: None,
+/// })?;
+/// ```
+pub fn http_request(request: OutboundHttpRequest) -> Result<OutboundHttpResponse, Error> {
+    call_host_fn(|input| unsafe { sf_http_request(input) }, &request)
+}
+
+// =============================================================================
+// Invocation context
+// =============================================================================
+
+/// Get the context the host passed with this invocation: its ID, the org
+/// and user it runs for, and any host-defined payload.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(serde::Deserialize)]
+/// struct Trigger {
+///     #[serde(rename = "opportunityId")]
+///     opportunity_id: String,
+/// }
+///
+/// let ctx = invocation_context()?;
+/// let trigger: Trigger = ctx.payload_as()?;
+/// ```
+pub fn invocation_context() -> Result<InvocationContext, Error> {
+    call_host_fn_no_input(|input| unsafe { sf_invocation_context(input) })
+}
+
+// =============================================================================
+// Long-running operations
+// =============================================================================
+
+/// Check on a deploy, retrieve, or bulk ingest job by the ID it returned
+/// when started.
+///
+/// Works from any later invocation, so plugins can start long-running work
+/// in one call and finish it in another without keeping state.
+pub fn operation_status(handle: &str) -> Result<OperationStatus, Error> {
+    let request = OperationStatusRequest {
+        handle: handle.to_string(),
+    };
+    call_host_fn(|input| unsafe { sf_operation_status(input) }, &request)
+}
+
+// =============================================================================
+// Capability discovery
+// =============================================================================
+
+/// Get the host functions, ABI version, policies, and limits the host
+/// applies to this plugin.
+///
+/// Check for optional features before relying on them, rather than
+/// handling a failure at call time.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let caps = capabilities()?;
+/// if caps.has(host_fn_names::QUERY_CURSOR_NEXT) {
+///     // stream large results through the cursor
+/// }
+/// if let Some(notice) = caps.deprecation(host_fn_names::QUERY_MORE) {
+///     log!(LogLevel::Warn, "sf_query_more is deprecated: {}", notice.note);
+/// }
+/// ```
+pub fn capabilities() -> Result<Capabilities, Error> {
+    call_host_fn_no_input(|input| unsafe { sf_capabilities(input) })
 }
 
 // =============================================================================
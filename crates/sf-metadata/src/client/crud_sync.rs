@@ -47,13 +47,7 @@ impl super::MetadataClient {
             metadata_elements = metadata_elements.join("\n"),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("createMetadata"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("createMetadata", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -105,13 +99,7 @@ impl super::MetadataClient {
             metadata_type = xml::escape(metadata_type),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("readMetadata"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("readMetadata", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -165,13 +153,7 @@ impl super::MetadataClient {
             metadata_elements = metadata_elements.join("\n"),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("updateMetadata"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("updateMetadata", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -207,9 +189,17 @@ impl super::MetadataClient {
             .map(|obj| self.build_metadata_element(metadata_type, obj))
             .collect();
 
+        self.upsert_metadata_elements(&metadata_elements).await
+    }
+
+    /// Send an `upsertMetadata` call with pre-built `<met:metadata>` elements.
+    pub(super) async fn upsert_metadata_elements(
+        &self,
+        metadata_elements: &[String],
+    ) -> Result<Vec<UpsertResult>> {
         let envelope = format!(
             r#"<?xml version="1.0" encoding="utf-8"?>
-<soapenv:Envelope xmlns:soapenv="http://schemas.xmlsoap.org/soap/envelope/" xmlns:met="http://soap.sforce.com/2006/04/metadata" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+<soapenv:Envelope xmlns:soapenv="http://schemas.xmlsoap.org/soap/envelope/" xmlns:met="http://soap.sforce.com/2006/04/metadata" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:xsd="http://www.w3.org/2001/XMLSchema">
   <soapenv:Header>
     <met:SessionHeader>
       <met:sessionId>{session_id}</met:sessionId>
@@ -225,13 +215,7 @@ impl super::MetadataClient {
             metadata_elements = metadata_elements.join("\n"),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("upsertMetadata"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("upsertMetadata", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -287,13 +271,7 @@ impl super::MetadataClient {
             metadata_type = xml::escape(metadata_type),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("deleteMetadata"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("deleteMetadata", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -338,13 +316,7 @@ impl super::MetadataClient {
             new_full_name = xml::escape(new_full_name),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("renameMetadata"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("renameMetadata", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -0,0 +1,85 @@
+use crate::custom_metadata::CustomMetadataRecord;
+use crate::error::Result;
+use crate::types::UpsertResult;
+
+impl super::MetadataClient {
+    /// Create or update custom metadata type records.
+    ///
+    /// Records are sent through `upsertMetadata` in batches of 10, so any
+    /// number can be passed. Every record is validated before the first
+    /// batch goes out; a failure partway through leaves earlier batches
+    /// applied. Per-record failures come back in the results rather than
+    /// as an error.
+    pub async fn upsert_custom_metadata(
+        &self,
+        records: &[CustomMetadataRecord],
+    ) -> Result<Vec<UpsertResult>> {
+        for record in records {
+            record.validate()?;
+        }
+        let mut results = Vec::with_capacity(records.len());
+        for batch in records.chunks(10) {
+            let elements: Vec<String> = batch.iter().map(|r| r.to_soap_element()).collect();
+            results.extend(self.upsert_metadata_elements(&elements).await?);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MetadataClient;
+    use crate::CustomMetadataRecord;
+
+    #[tokio::test]
+    async fn test_upsert_custom_metadata_batches() {
+        use wiremock::matchers::{body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        let result = |name: &str| {
+            format!(
+                "<result><created>true</created><fullName>{}</fullName><success>true</success></result>",
+                name
+            )
+        };
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("Flag.R0"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "<soapenv:Envelope><soapenv:Body><upsertMetadataResponse>{}</upsertMetadataResponse></soapenv:Body></soapenv:Envelope>",
+                (0..10).map(|i| result(&format!("Flag.R{}", i))).collect::<String>()
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("Flag.R10"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "<soapenv:Envelope><soapenv:Body><upsertMetadataResponse>{}</upsertMetadataResponse></soapenv:Body></soapenv:Envelope>",
+                result("Flag.R10")
+            )))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = MetadataClient::from_parts(mock_server.uri(), "token");
+        let records: Vec<_> = (0..11)
+            .map(|i| {
+                CustomMetadataRecord::new("Flag__mdt", format!("R{}", i), "R")
+                    .with_value("Enabled__c", true)
+            })
+            .collect();
+        let results = client
+            .upsert_custom_metadata(&records)
+            .await
+            .expect("upsert should succeed");
+        assert_eq!(results.len(), 11);
+        assert!(results.iter().all(|r| r.success && r.created));
+
+        let bad = [CustomMetadataRecord::new("Flag", "Not Valid", "x")];
+        assert!(client.upsert_custom_metadata(&bad).await.is_err());
+    }
+}
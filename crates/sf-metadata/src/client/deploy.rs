@@ -3,6 +3,7 @@ use crate::error::{Error, ErrorKind, Result};
 use crate::types::TestLevel;
 use base64::{engine::general_purpose, Engine as _};
 use busbar_sf_client::security::xml;
+use futures::stream::{self, Stream};
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -13,7 +14,13 @@ impl super::MetadataClient {
     /// in the correct directory structure (e.g., `classes/MyClass.cls`).
     ///
     /// Returns the async process ID for tracking the deployment.
+    ///
+    /// If a maintenance pre-flight is configured, it runs first.
     pub async fn deploy(&self, package_zip: &[u8], options: DeployOptions) -> Result<String> {
+        if let Some(preflight) = &self.maintenance_preflight {
+            preflight.check().await?;
+        }
+
         let encoded_zip = general_purpose::STANDARD.encode(package_zip);
 
         let test_level_xml = options
@@ -74,13 +81,7 @@ impl super::MetadataClient {
             run_tests = run_tests_xml,
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("deploy"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("deploy", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -121,13 +122,7 @@ impl super::MetadataClient {
             include_details = include_details,
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("checkDeployStatus"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("checkDeployStatus", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -144,6 +139,22 @@ impl super::MetadataClient {
         async_process_id: &str,
         timeout: Duration,
         poll_interval: Duration,
+    ) -> Result<DeployResult> {
+        self.watch_deploy_status(async_process_id, timeout, poll_interval, |_| {})
+            .await
+    }
+
+    /// Poll for deploy completion, passing every status check to
+    /// `on_progress`, including the last.
+    ///
+    /// Fails like [`poll_deploy_status`](Self::poll_deploy_status) on
+    /// timeout or an unsuccessful deploy.
+    pub async fn watch_deploy_status(
+        &self,
+        async_process_id: &str,
+        timeout: Duration,
+        poll_interval: Duration,
+        mut on_progress: impl FnMut(&DeployResult),
     ) -> Result<DeployResult> {
         let start = tokio::time::Instant::now();
 
@@ -153,6 +164,7 @@ impl super::MetadataClient {
             }
 
             let result = self.check_deploy_status(async_process_id, true).await?;
+            on_progress(&result);
 
             if result.done {
                 if result.success {
@@ -168,6 +180,53 @@ impl super::MetadataClient {
         }
     }
 
+    /// Stream the status of a deploy, checking it every `poll_interval`.
+    ///
+    /// The first check is made as soon as the stream is polled. The stream
+    /// ends after the check that finds the deploy done, whether or not it
+    /// succeeded, and stops at the first error. It never times out; drop it
+    /// to stop watching.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut statuses = std::pin::pin!(client.deploy_status_stream(&id, Duration::from_secs(5)));
+    /// while let Some(status) = statuses.try_next().await? {
+    ///     println!(
+    ///         "{}/{} components, {}/{} tests",
+    ///         status.number_components_deployed,
+    ///         status.number_components_total,
+    ///         status.number_tests_completed,
+    ///         status.number_tests_total,
+    ///     );
+    /// }
+    /// ```
+    pub fn deploy_status_stream(
+        &self,
+        async_process_id: &str,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<DeployResult>> + Send + '_ {
+        let async_process_id = async_process_id.to_string();
+        // The state is whether to wait before the next check, or None once
+        // the deploy is done.
+        stream::try_unfold(Some(false), move |state| {
+            let async_process_id = async_process_id.clone();
+            async move {
+                let Some(wait) = state else {
+                    return Ok(None);
+                };
+                if wait {
+                    sleep(poll_interval).await;
+                }
+                let result = self.check_deploy_status(&async_process_id, true).await?;
+                let next = (!result.done).then_some(true);
+                Ok(Some((result, next)))
+            }
+        })
+    }
+
     /// Deploy and wait for completion.
     pub async fn deploy_and_wait(
         &self,
@@ -181,6 +240,46 @@ impl super::MetadataClient {
             .await
     }
 
+    /// Deploy and wait for completion, reporting progress at each status
+    /// check through `on_progress`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let result = client
+    ///     .deploy_and_watch(&zip, options, timeout, Duration::from_secs(5), |status| {
+    ///         println!(
+    ///             "{}/{} components deployed",
+    ///             status.number_components_deployed, status.number_components_total
+    ///         );
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn deploy_and_watch(
+        &self,
+        package_zip: &[u8],
+        options: DeployOptions,
+        timeout: Duration,
+        poll_interval: Duration,
+        on_progress: impl FnMut(&DeployResult),
+    ) -> Result<DeployResult> {
+        let async_id = self.deploy(package_zip, options).await?;
+        self.watch_deploy_status(&async_id, timeout, poll_interval, on_progress)
+            .await
+    }
+
+    /// Deploy and stream its status; see
+    /// [`deploy_status_stream`](Self::deploy_status_stream).
+    pub async fn deploy_and_stream(
+        &self,
+        package_zip: &[u8],
+        options: DeployOptions,
+        poll_interval: Duration,
+    ) -> Result<impl Stream<Item = Result<DeployResult>> + Send + '_> {
+        let async_id = self.deploy(package_zip, options).await?;
+        Ok(self.deploy_status_stream(&async_id, poll_interval))
+    }
+
     /// Cancel an in-progress deployment.
     ///
     /// Requests cancellation of a deployment identified by its async process ID.
@@ -211,13 +310,7 @@ impl super::MetadataClient {
             process_id = xml::escape(async_process_id),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("cancelDeploy"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("cancelDeploy", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -254,13 +347,7 @@ impl super::MetadataClient {
             validation_id = xml::escape(validation_id),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("deployRecentValidation"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("deployRecentValidation", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -275,3 +362,91 @@ impl super::MetadataClient {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::MetadataClient;
+    use crate::deploy::DeployStatus;
+    use crate::DeployOptions;
+    use futures::TryStreamExt;
+    use std::time::Duration;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn status_response(done: bool, status: &str, deployed: u32) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_string(format!(
+            "<soapenv:Envelope><soapenv:Body><checkDeployStatusResponse><result>\
+             <done>{}</done><id>0Af000000000001</id><status>{}</status><success>{}</success>\
+             <numberComponentsDeployed>{}</numberComponentsDeployed>\
+             <numberComponentsTotal>2</numberComponentsTotal>\
+             </result></checkDeployStatusResponse></soapenv:Body></soapenv:Envelope>",
+            done, status, done, deployed
+        ))
+    }
+
+    async fn mount_deploy(mock_server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<deploy "))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<soapenv:Envelope><soapenv:Body><deployResponse><result>\
+                 <done>false</done><id>0Af000000000001</id><state>Queued</state>\
+                 </result></deployResponse></soapenv:Body></soapenv:Envelope>",
+            ))
+            .expect(1)
+            .mount(mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("checkDeployStatus"))
+            .respond_with(status_response(false, "InProgress", 1))
+            .up_to_n_times(1)
+            .mount(mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("checkDeployStatus"))
+            .respond_with(status_response(true, "Succeeded", 2))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_deploy_and_watch_reports_each_check() {
+        let mock_server = MockServer::start().await;
+        mount_deploy(&mock_server).await;
+
+        let client = MetadataClient::from_parts(mock_server.uri(), "token");
+        let mut progress = Vec::new();
+        let result = client
+            .deploy_and_watch(
+                b"zip",
+                DeployOptions::default(),
+                Duration::from_secs(10),
+                Duration::ZERO,
+                |status| progress.push(status.number_components_deployed),
+            )
+            .await
+            .expect("deploy should succeed");
+        assert_eq!(result.status, DeployStatus::Succeeded);
+        assert_eq!(progress, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_and_stream_ends_when_done() {
+        let mock_server = MockServer::start().await;
+        mount_deploy(&mock_server).await;
+
+        let client = MetadataClient::from_parts(mock_server.uri(), "token");
+        let statuses: Vec<_> = client
+            .deploy_and_stream(b"zip", DeployOptions::default(), Duration::ZERO)
+            .await
+            .expect("deploy should start")
+            .try_collect()
+            .await
+            .expect("status checks should succeed");
+        assert_eq!(statuses.len(), 2);
+        assert!(!statuses[0].done);
+        assert_eq!(statuses[1].status, DeployStatus::Succeeded);
+    }
+}
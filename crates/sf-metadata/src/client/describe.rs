@@ -23,13 +23,7 @@ impl super::MetadataClient {
             api_version = self.api_version,
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("describeMetadata"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("describeMetadata", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -85,13 +79,7 @@ impl super::MetadataClient {
             type_name = xml::escape(type_name),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("describeValueType"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("describeValueType", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use crate::diff::{MetadataDiff, MetadataSnapshot};
+use crate::error::Result;
+use crate::package::RetrievePackage;
+use crate::retrieve::PackageManifest;
+
+impl super::MetadataClient {
+    /// Retrieve the components in `manifest` as a [`MetadataSnapshot`].
+    pub async fn retrieve_snapshot(
+        &self,
+        manifest: &PackageManifest,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<MetadataSnapshot> {
+        let result = self
+            .retrieve_unpackaged_and_wait(manifest, timeout, poll_interval)
+            .await?;
+        MetadataSnapshot::from_retrieve(&RetrievePackage::from_result(&result)?)
+    }
+
+    /// Retrieve `manifest` from this org and `target` at the same time and
+    /// diff them: what deploying this org's components would change in
+    /// `target`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let diff = staging.diff_against(&production, &manifest, timeout, poll_interval).await?;
+    /// println!("{} added, {} removed, {} modified",
+    ///     diff.added.len(), diff.removed.len(), diff.modified.len());
+    /// ```
+    pub async fn diff_against(
+        &self,
+        target: &super::MetadataClient,
+        manifest: &PackageManifest,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<MetadataDiff> {
+        let (source, target) = futures::try_join!(
+            self.retrieve_snapshot(manifest, timeout, poll_interval),
+            target.retrieve_snapshot(manifest, timeout, poll_interval),
+        )?;
+        Ok(target.diff(&source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MetadataClient;
+    use super::*;
+    use crate::package::DeployPackage;
+    use base64::Engine;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn org(class_body: &str) -> MockServer {
+        let zip = DeployPackage::default()
+            .with_file("unpackaged/package.xml", "<Package/>")
+            .with_file("unpackaged/classes/Invoices.cls", class_body)
+            .with_file("unpackaged/classes/Invoices.cls-meta.xml", "<ApexClass/>")
+            .to_zip()
+            .unwrap();
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<retrieveRequest>"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<soapenv:Envelope><soapenv:Body><retrieveResponse><result><id>09S1</id></result></retrieveResponse></soapenv:Body></soapenv:Envelope>",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("checkRetrieveStatus"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                "<soapenv:Envelope><soapenv:Body><checkRetrieveStatusResponse><result>\
+                 <id>09S1</id><done>true</done><status>Succeeded</status><success>true</success>\
+                 <zipFile>{}</zipFile></result></checkRetrieveStatusResponse></soapenv:Body></soapenv:Envelope>",
+                base64::engine::general_purpose::STANDARD.encode(zip)
+            )))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_diff_against_compares_both_orgs() {
+        let (staging, production) = (
+            org("public class Invoices { void pay() {} }").await,
+            org("public class Invoices {}").await,
+        );
+        let manifest =
+            PackageManifest::new("62.0").add_type("ApexClass", vec!["Invoices".to_string()]);
+
+        let diff = MetadataClient::from_parts(staging.uri(), "token")
+            .diff_against(
+                &MetadataClient::from_parts(production.uri(), "token"),
+                &manifest,
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            )
+            .await
+            .unwrap();
+
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        let change = &diff.modified[0].changes[0];
+        assert_eq!(change.file, "classes/Invoices.cls");
+        assert_eq!(change.before.as_deref(), Some("public class Invoices {}"));
+        assert_eq!(
+            change.after.as_deref(),
+            Some("public class Invoices { void pay() {} }")
+        );
+    }
+}
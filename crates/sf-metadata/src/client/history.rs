@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use crate::deploy::{DeployOptions, DeployResult};
+use crate::error::{ErrorKind, Result};
+use crate::history::{DeployHistory, DeployRecord};
+use crate::package::DeployPackage;
+
+impl super::MetadataClient {
+    /// Deploy `package`, wait for it to finish, and record it in `history`:
+    /// once as it starts and again with the final result.
+    ///
+    /// A failed deploy is recorded with its result before the error is
+    /// returned. A deploy that times out stays recorded without one.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use busbar_sf_metadata::{DeployOptions, FileDeployHistory};
+    ///
+    /// let history = FileDeployHistory::new(".busbar/deploys.jsonl");
+    /// let result = client
+    ///     .deploy_and_record(&package, options, &history, Some("release-plugin"), timeout, poll_interval)
+    ///     .await?;
+    /// ```
+    pub async fn deploy_and_record(
+        &self,
+        package: &DeployPackage,
+        options: DeployOptions,
+        history: &dyn DeployHistory,
+        deployed_by: Option<&str>,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<DeployResult> {
+        let async_id = self.deploy(&package.to_zip()?, options.clone()).await?;
+        let mut record = DeployRecord::new(&async_id, package, options);
+        record.deployed_by = deployed_by.map(str::to_string);
+        history.record(&record)?;
+
+        let outcome = self
+            .poll_deploy_status(&async_id, timeout, poll_interval)
+            .await;
+        let result = match &outcome {
+            Ok(result) => result.clone(),
+            Err(e) => match &e.kind {
+                ErrorKind::DeploymentFailed { result } => (**result).clone(),
+                _ => return outcome,
+            },
+        };
+        record.finish(result);
+        history.record(&record)?;
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MetadataClient;
+    use super::*;
+    use crate::history::FileDeployHistory;
+    use crate::retrieve::PackageManifest;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_deploy_and_record_records_failed_deploy() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<deploy "))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<soapenv:Envelope><soapenv:Body><deployResponse><result><id>0Af1</id></result></deployResponse></soapenv:Body></soapenv:Envelope>",
+            ))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("checkDeployStatus"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<soapenv:Envelope><soapenv:Body><checkDeployStatusResponse><result>\
+                 <id>0Af1</id><done>true</done><status>Failed</status><success>false</success>\
+                 <numberTestsCompleted>4</numberTestsCompleted><numberTestErrors>1</numberTestErrors>\
+                 </result></checkDeployStatusResponse></soapenv:Body></soapenv:Envelope>",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let history = FileDeployHistory::new(dir.path().join("deploys.jsonl"));
+        let package = DeployPackage::new(&PackageManifest::new("62.0"));
+        let outcome = MetadataClient::from_parts(mock_server.uri(), "token")
+            .deploy_and_record(
+                &package,
+                DeployOptions::default(),
+                &history,
+                Some("release-plugin"),
+                Duration::from_secs(5),
+                Duration::from_millis(10),
+            )
+            .await;
+        assert!(matches!(
+            outcome.unwrap_err().kind,
+            ErrorKind::DeploymentFailed { .. }
+        ));
+
+        let record = history.get("0Af1").unwrap().unwrap();
+        assert_eq!(record.deployed_by.as_deref(), Some("release-plugin"));
+        assert!(record.finished_at.is_some());
+        let result = record.result.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.number_tests_completed, 4);
+    }
+}
@@ -1,7 +1,24 @@
+use std::collections::BTreeMap;
+
 use busbar_sf_client::security::xml;
+use futures::stream::{self, StreamExt, TryStreamExt};
 
 use crate::error::{Error, ErrorKind, Result};
+use crate::inventory::{InventoryChanges, OrgInventory};
 use crate::list::MetadataComponent;
+use crate::registry::{MetadataRegistry, SourceStrategy};
+use crate::retrieve::PackageManifest;
+
+/// Queries `listMetadata` accepts per call.
+const LIST_METADATA_BATCH_SIZE: usize = 3;
+
+/// `listMetadata` calls [`list_all_metadata`](super::MetadataClient::list_all_metadata)
+/// keeps in flight at once.
+const LIST_METADATA_CONCURRENCY: usize = 4;
+
+/// The folder reports and email templates live in when they aren't filed
+/// anywhere else. `listMetadata` doesn't list it with the other folders.
+const UNFILED_PUBLIC_FOLDER: &str = "unfiled$public";
 
 impl super::MetadataClient {
     /// List metadata components of a specific type.
@@ -10,9 +27,178 @@ impl super::MetadataClient {
         metadata_type: &str,
         folder: Option<&str>,
     ) -> Result<Vec<MetadataComponent>> {
-        let folder_xml = folder
-            .map(|f| format!("\n      <folder>{}</folder>", xml::escape(f)))
-            .unwrap_or_default();
+        self.list_metadata_batch(&[(metadata_type, folder)]).await
+    }
+
+    /// List the components matching each `(type, folder)` query.
+    ///
+    /// `listMetadata` takes up to three queries per call, so the queries go
+    /// out in batches of three and the results come back together, in no
+    /// particular order. Each component's `metadata_type` says which query
+    /// it matched.
+    pub async fn list_metadata_many(
+        &self,
+        queries: &[(&str, Option<&str>)],
+    ) -> Result<Vec<MetadataComponent>> {
+        let mut components = Vec::new();
+        for batch in queries.chunks(LIST_METADATA_BATCH_SIZE) {
+            components.extend(self.list_metadata_batch(batch).await?);
+        }
+        Ok(components)
+    }
+
+    /// List every component in the org, by type.
+    ///
+    /// Every type `describeMetadata` reports is listed, child types such as
+    /// `CustomField` included, with a few `listMetadata` calls in flight at
+    /// once. Types with no components map to an empty list. Folder-based
+    /// types (reports, dashboards, documents, email templates) are listed
+    /// folder by folder, with the folders themselves under their folder
+    /// type, such as `ReportFolder`.
+    pub async fn list_all_metadata(&self) -> Result<BTreeMap<String, Vec<MetadataComponent>>> {
+        let describe = self.describe_metadata().await?;
+        let mut inventory: BTreeMap<String, Vec<MetadataComponent>> = BTreeMap::new();
+        for metadata_type in &describe.metadata_objects {
+            inventory.entry(metadata_type.xml_name.clone()).or_default();
+            for child in &metadata_type.child_xml_names {
+                inventory.entry(child.clone()).or_default();
+            }
+        }
+
+        let folder_based: Vec<&str> = describe
+            .metadata_objects
+            .iter()
+            .filter(|metadata_type| metadata_type.in_folder)
+            .map(|metadata_type| metadata_type.xml_name.as_str())
+            .collect();
+        let folders = self.folders_by_type(&folder_based).await?;
+        let mut queries: Vec<(&str, Option<&str>)> = inventory
+            .keys()
+            .filter(|name| !folder_based.contains(&name.as_str()))
+            .map(|name| (name.as_str(), None))
+            .collect();
+        queries.extend(folder_queries(&folders));
+        let batches: Vec<Vec<MetadataComponent>> = stream::iter(
+            queries
+                .chunks(LIST_METADATA_BATCH_SIZE)
+                .map(|batch| self.list_metadata_batch(batch)),
+        )
+        .buffer_unordered(LIST_METADATA_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+        let listed_folders = folders.into_iter().flat_map(|(_, folders)| folders);
+        let mut listed: BTreeMap<String, Vec<MetadataComponent>> = BTreeMap::new();
+        for component in batches.into_iter().flatten().chain(listed_folders) {
+            listed
+                .entry(component.metadata_type.clone())
+                .or_default()
+                .push(component);
+        }
+        for (metadata_type, components) in listed {
+            inventory
+                .entry(metadata_type)
+                .or_default()
+                .extend(components);
+        }
+        Ok(inventory)
+    }
+
+    /// List the folders of a folder-based type, such as `Report`.
+    ///
+    /// Folders are listed as their own type, `ReportFolder` for reports
+    /// and `EmailFolder` for email templates, but are members of the type
+    /// they hold in a manifest.
+    pub async fn list_folders(&self, metadata_type: &str) -> Result<Vec<MetadataComponent>> {
+        self.list_metadata(&folder_type(metadata_type), None).await
+    }
+
+    /// List the components in every folder of each folder-based type.
+    ///
+    /// Folder-based types only list a folder at a time, and a `*`
+    /// wildcard in a manifest doesn't reach into folders either, so the
+    /// folders are listed first and then each one's contents, unfiled
+    /// reports and email templates included. The folders themselves
+    /// aren't in the result; see [`list_folders`](Self::list_folders).
+    pub async fn list_in_folders(&self, metadata_types: &[&str]) -> Result<Vec<MetadataComponent>> {
+        let folders = self.folders_by_type(metadata_types).await?;
+        self.list_metadata_many(&folder_queries(&folders)).await
+    }
+
+    /// A manifest of every folder of each folder-based type and everything
+    /// in them, to retrieve in place of a wildcard.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let manifest = client
+    ///     .folder_manifest(&["Report", "Dashboard"], "62.0")
+    ///     .await?;
+    /// let result = client
+    ///     .retrieve_unpackaged_and_wait(&manifest, timeout, poll_interval)
+    ///     .await?;
+    /// ```
+    pub async fn folder_manifest(
+        &self,
+        metadata_types: &[&str],
+        version: impl Into<String>,
+    ) -> Result<PackageManifest> {
+        let folders = self.folders_by_type(metadata_types).await?;
+        let contents = self.list_metadata_many(&folder_queries(&folders)).await?;
+
+        let mut manifest = PackageManifest::new(version);
+        for (metadata_type, folders) in &folders {
+            let mut members: Vec<String> = folders
+                .iter()
+                .chain(
+                    contents
+                        .iter()
+                        .filter(|c| c.metadata_type == *metadata_type),
+                )
+                .map(|component| component.full_name.clone())
+                .collect();
+            members.sort();
+            members.dedup();
+            if !members.is_empty() {
+                manifest = manifest.add_type(metadata_type.as_str(), members);
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Each folder-based type with its folders. A folder type goes in a call
+    /// of its own, since its results may not say which type they're for.
+    async fn folders_by_type(
+        &self,
+        metadata_types: &[&str],
+    ) -> Result<Vec<(String, Vec<MetadataComponent>)>> {
+        stream::iter(metadata_types.iter().map(|metadata_type| async move {
+            let folders = self.list_folders(metadata_type).await?;
+            Ok::<_, Error>((metadata_type.to_string(), folders))
+        }))
+        .buffered(LIST_METADATA_CONCURRENCY)
+        .try_collect()
+        .await
+    }
+
+    /// Send one `listMetadata` call of up to three queries.
+    async fn list_metadata_batch(
+        &self,
+        queries: &[(&str, Option<&str>)],
+    ) -> Result<Vec<MetadataComponent>> {
+        let queries_xml: String = queries
+            .iter()
+            .map(|(metadata_type, folder)| {
+                let folder_xml = folder
+                    .map(|f| format!("\n        <folder>{}</folder>", xml::escape(f)))
+                    .unwrap_or_default();
+                format!(
+                    "\n      <queries>\n        <type>{}</type>{}\n      </queries>",
+                    xml::escape(metadata_type),
+                    folder_xml
+                )
+            })
+            .collect();
 
         let envelope = format!(
             r#"<?xml version="1.0" encoding="utf-8"?>
@@ -23,27 +209,17 @@ impl super::MetadataClient {
     </SessionHeader>
   </soap:Header>
   <soap:Body>
-    <listMetadata xmlns="http://soap.sforce.com/2006/04/metadata">
-      <queries>
-        <type>{metadata_type}</type>{folder}
-      </queries>
+    <listMetadata xmlns="http://soap.sforce.com/2006/04/metadata">{queries}
       <asOfVersion>{api_version}</asOfVersion>
     </listMetadata>
   </soap:Body>
 </soap:Envelope>"#,
             session_id = self.access_token,
-            metadata_type = xml::escape(metadata_type),
-            folder = folder_xml,
+            queries = queries_xml,
             api_version = self.api_version,
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("listMetadata"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("listMetadata", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -51,6 +227,247 @@ impl super::MetadataClient {
             return Err(Error::new(ErrorKind::SoapFault(fault.to_string())));
         }
 
-        self.parse_list_metadata_result(&response_text, metadata_type)
+        // Results name their type; a lone query's type covers any that don't.
+        let fallback_type = match queries {
+            [(metadata_type, _)] => metadata_type,
+            _ => "",
+        };
+        self.parse_list_metadata_result(&response_text, fallback_type)
+    }
+
+    /// List each of `metadata_types` and record the results in `inventory`,
+    /// returning the components added, modified, or removed since its last
+    /// scan of each type.
+    ///
+    /// Every type is listed before `inventory` is touched, so a failed
+    /// listing leaves it unchanged. Folder-based types (reports, dashboards,
+    /// documents, email templates) are listed folder by folder, as
+    /// [`list_in_folders`](Self::list_in_folders) does.
+    pub async fn refresh_inventory(
+        &self,
+        inventory: &mut OrgInventory,
+        metadata_types: &[&str],
+    ) -> Result<InventoryChanges> {
+        let listed_at = chrono::Utc::now();
+        let registry = MetadataRegistry::default();
+        let (folder_based, other): (Vec<&str>, Vec<&str>) =
+            metadata_types.iter().partition(|metadata_type| {
+                matches!(
+                    registry.by_name(metadata_type).map(|t| &t.strategy),
+                    Some(SourceStrategy::InFolder { .. })
+                )
+            });
+        let folders = self.folders_by_type(&folder_based).await?;
+        let mut queries: Vec<(&str, Option<&str>)> = other.iter().map(|t| (*t, None)).collect();
+        queries.extend(folder_queries(&folders));
+        let mut components = self.list_metadata_many(&queries).await?;
+        let scans = metadata_types
+            .iter()
+            .map(|metadata_type| {
+                let (matched, rest) = components
+                    .drain(..)
+                    .partition(|c| c.metadata_type == *metadata_type);
+                components = rest;
+                (metadata_type.to_string(), matched)
+            })
+            .collect();
+        Ok(inventory.record_all(scans, listed_at))
+    }
+}
+
+/// The type a folder-based type's folders are listed as.
+fn folder_type(metadata_type: &str) -> String {
+    match metadata_type {
+        "EmailTemplate" => "EmailFolder".to_string(),
+        other => format!("{}Folder", other),
+    }
+}
+
+/// `(type, folder)` queries for every folder of each type, including the
+/// unfiled folder for the types that have one.
+fn folder_queries(folders: &[(String, Vec<MetadataComponent>)]) -> Vec<(&str, Option<&str>)> {
+    folders
+        .iter()
+        .flat_map(|(metadata_type, folders)| {
+            let unfiled = matches!(metadata_type.as_str(), "Report" | "EmailTemplate")
+                .then_some(UNFILED_PUBLIC_FOLDER);
+            unfiled
+                .into_iter()
+                .chain(folders.iter().map(|folder| folder.full_name.as_str()))
+                .map(move |folder| (metadata_type.as_str(), Some(folder)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MetadataClient;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn list_response(results: &[(&str, &str)]) -> ResponseTemplate {
+        let results: String = results
+            .iter()
+            .map(|(metadata_type, name)| {
+                format!(
+                    "<result><fullName>{}</fullName><type>{}</type></result>",
+                    name, metadata_type
+                )
+            })
+            .collect();
+        ResponseTemplate::new(200).set_body_string(format!(
+            "<soapenv:Envelope><soapenv:Body><listMetadataResponse>{}</listMetadataResponse></soapenv:Body></soapenv:Envelope>",
+            results
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_list_metadata_many_batches_queries() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<type>ApexClass</type>"))
+            .and(body_string_contains("<folder>Sales</folder>"))
+            .respond_with(list_response(&[
+                ("ApexClass", "Invoices"),
+                ("Report", "Sales/Pipeline"),
+            ]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<type>Layout</type>"))
+            .respond_with(list_response(&[("Layout", "Account-Account Layout")]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = MetadataClient::from_parts(mock_server.uri(), "token");
+        let components = client
+            .list_metadata_many(&[
+                ("ApexClass", None),
+                ("ApexTrigger", None),
+                ("Report", Some("Sales")),
+                ("Layout", None),
+            ])
+            .await
+            .expect("listing should succeed");
+        let names: Vec<_> = components.iter().map(|c| c.full_name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Invoices", "Sales/Pipeline", "Account-Account Layout"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_all_metadata_groups_by_type() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("describeMetadata"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<soapenv:Envelope><soapenv:Body><describeMetadataResponse><result>\
+                 <metadataObjects><xmlName>ApexClass</xmlName><inFolder>false</inFolder><metaFile>true</metaFile></metadataObjects>\
+                 <metadataObjects><xmlName>CustomObject</xmlName><childXmlNames>CustomField</childXmlNames>\
+                 <inFolder>false</inFolder><metaFile>false</metaFile></metadataObjects>\
+                 <metadataObjects><xmlName>Layout</xmlName><inFolder>false</inFolder><metaFile>false</metaFile></metadataObjects>\
+                 <metadataObjects><xmlName>Report</xmlName><inFolder>true</inFolder><metaFile>false</metaFile></metadataObjects>\
+                 </result></describeMetadataResponse></soapenv:Body></soapenv:Envelope>",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<type>ApexClass</type>"))
+            .respond_with(list_response(&[
+                ("ApexClass", "Invoices"),
+                ("CustomField", "Invoice__c.Amount__c"),
+                ("CustomObject", "Invoice__c"),
+            ]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<type>ReportFolder</type>"))
+            .respond_with(list_response(&[("ReportFolder", "Sales")]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<type>Layout</type>"))
+            .and(body_string_contains("<folder>unfiled$public</folder>"))
+            .and(body_string_contains("<folder>Sales</folder>"))
+            .respond_with(list_response(&[("Report", "Sales/Pipeline")]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = MetadataClient::from_parts(mock_server.uri(), "token");
+        let inventory = client
+            .list_all_metadata()
+            .await
+            .expect("listing should succeed");
+        assert_eq!(
+            inventory.keys().collect::<Vec<_>>(),
+            [
+                "ApexClass",
+                "CustomField",
+                "CustomObject",
+                "Layout",
+                "Report",
+                "ReportFolder"
+            ]
+        );
+        assert_eq!(inventory["Report"][0].full_name, "Sales/Pipeline");
+        assert_eq!(inventory["ReportFolder"][0].full_name, "Sales");
+        assert_eq!(
+            inventory["CustomField"][0].full_name,
+            "Invoice__c.Amount__c"
+        );
+        assert!(inventory["Layout"].is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_folder_manifest_lists_folder_contents() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<type>DashboardFolder</type>"))
+            .respond_with(list_response(&[("DashboardFolder", "Exec")]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<type>EmailFolder</type>"))
+            .respond_with(list_response(&[]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/m/62.0"))
+            .and(body_string_contains("<folder>Exec</folder>"))
+            .and(body_string_contains("<folder>unfiled$public</folder>"))
+            .respond_with(list_response(&[
+                ("Dashboard", "Exec/Revenue"),
+                ("EmailTemplate", "unfiled$public/Welcome"),
+            ]))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = MetadataClient::from_parts(mock_server.uri(), "token");
+        let manifest = client
+            .folder_manifest(&["Dashboard", "EmailTemplate"], "62.0")
+            .await
+            .expect("listing should succeed");
+        assert_eq!(manifest.types[0].name, "Dashboard");
+        assert_eq!(manifest.types[0].members, ["Exec", "Exec/Revenue"]);
+        assert_eq!(manifest.types[1].name, "EmailTemplate");
+        assert_eq!(manifest.types[1].members, ["unfiled$public/Welcome"]);
     }
 }
@@ -1,18 +1,27 @@
 //! Metadata API client.
 
 use busbar_sf_auth::{Credentials, SalesforceCredentials};
+use std::sync::Arc;
+
+use busbar_sf_client::{trace, MaintenancePreflight, Transport};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 
 use crate::error::Result;
 use crate::types::DEFAULT_API_VERSION;
 
 mod crud_sync;
+mod custom_metadata;
 mod deploy;
 mod describe;
+mod diff;
+#[cfg(feature = "history")]
+mod history;
 mod list;
 mod retrieve;
 mod xml_helpers;
 
+pub(crate) use xml_helpers::xml_unescape;
+
 /// SOAP Action header name.
 static SOAP_ACTION_HEADER: HeaderName = HeaderName::from_static("soapaction");
 
@@ -23,6 +32,8 @@ pub struct MetadataClient {
     access_token: String,
     api_version: String,
     http_client: reqwest::Client,
+    transport: Arc<dyn Transport>,
+    maintenance_preflight: Option<MaintenancePreflight>,
 }
 
 impl MetadataClient {
@@ -33,6 +44,8 @@ impl MetadataClient {
             access_token: credentials.access_token().to_string(),
             api_version: DEFAULT_API_VERSION.to_string(),
             http_client: reqwest::Client::new(),
+            transport: Arc::new(reqwest::Client::new()),
+            maintenance_preflight: None,
         })
     }
 
@@ -43,6 +56,8 @@ impl MetadataClient {
             access_token: access_token.into(),
             api_version: DEFAULT_API_VERSION.to_string(),
             http_client: reqwest::Client::new(),
+            transport: Arc::new(reqwest::Client::new()),
+            maintenance_preflight: None,
         }
     }
 
@@ -54,10 +69,24 @@ impl MetadataClient {
 
     /// Set a custom HTTP client.
     pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.transport = Arc::new(client.clone());
         self.http_client = client;
         self
     }
 
+    /// Send SOAP requests through `transport` instead of the network,
+    /// e.g. a [`MockTransport`](busbar_sf_client::MockTransport) in tests.
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Check the Trust API for instance maintenance before each deploy.
+    pub fn with_maintenance_preflight(mut self, preflight: MaintenancePreflight) -> Self {
+        self.maintenance_preflight = Some(preflight);
+        self
+    }
+
     /// Get the current API version.
     pub fn api_version(&self) -> &str {
         &self.api_version
@@ -85,6 +114,35 @@ impl MetadataClient {
         );
         headers
     }
+
+    /// POST a SOAP envelope to the Metadata API endpoint.
+    ///
+    /// Call tracing happens inside a span carrying the SOAP action, since
+    /// every Metadata API call shares the same path.
+    #[tracing::instrument(level = "debug", skip(self, envelope))]
+    pub(crate) async fn send_soap(
+        &self,
+        soap_action: &str,
+        envelope: String,
+    ) -> Result<reqwest::Response> {
+        let url = self.metadata_url();
+        let headers = self.build_headers(soap_action);
+        trace::trace_headers(&headers);
+
+        let request = self
+            .http_client
+            .post(&url)
+            .headers(headers)
+            .body(envelope)
+            .build()?;
+        let started = std::time::Instant::now();
+        let sent = self.transport.send(request).await;
+        match &sent {
+            Ok(response) => trace::trace_response("POST", &url, response, started.elapsed()),
+            Err(e) => trace::trace_failure("POST", &url, e, started.elapsed()),
+        }
+        Ok(sent?)
+    }
 }
 
 #[cfg(test)]
@@ -126,4 +184,36 @@ mod tests {
         assert_eq!(headers.get("soapaction").unwrap(), "deploy");
         assert_eq!(headers.get("authorization").unwrap(), "Bearer token123");
     }
+
+    #[tokio::test]
+    async fn test_describe_metadata_with_mock_transport() {
+        use busbar_sf_client::{MockResponse, MockTransport};
+
+        let transport = MockTransport::new().on(
+            "POST",
+            "/services/Soap/m/62.0",
+            MockResponse::text(
+                200,
+                "text/xml",
+                r#"<soapenv:Envelope><soapenv:Body><describeMetadataResponse><result>
+                <metadataObjects><xmlName>ApexClass</xmlName><directoryName>classes</directoryName>
+                <suffix>cls</suffix><inFolder>false</inFolder><metaFile>true</metaFile></metadataObjects>
+                <organizationNamespace></organizationNamespace><partialSaveAllowed>true</partialSaveAllowed>
+                </result></describeMetadataResponse></soapenv:Body></soapenv:Envelope>"#,
+            ),
+        );
+        let client = MetadataClient::from_parts("https://na1.salesforce.com", "token123")
+            .with_api_version("62.0")
+            .with_transport(transport.clone());
+
+        let result = client.describe_metadata().await.unwrap();
+        assert_eq!(result.metadata_objects[0].xml_name, "ApexClass");
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.header("soapaction"), Some("describeMetadata"));
+        assert!(request
+            .body_text()
+            .unwrap()
+            .contains("<tns:describeMetadata>"));
+    }
 }
@@ -49,13 +49,7 @@ impl super::MetadataClient {
             package_xml = package_xml,
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("retrieve"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("retrieve", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -94,13 +88,7 @@ impl super::MetadataClient {
             package_name = xml::escape(package_name),
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("retrieve"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("retrieve", envelope).await?;
 
         let response_text = response.text().await?;
 
@@ -141,13 +129,7 @@ impl super::MetadataClient {
             include_zip = include_zip,
         );
 
-        let response = self
-            .http_client
-            .post(self.metadata_url())
-            .headers(self.build_headers("checkRetrieveStatus"))
-            .body(envelope)
-            .send()
-            .await?;
+        let response = self.send_soap("checkRetrieveStatus", envelope).await?;
 
         let response_text = response.text().await?;
 
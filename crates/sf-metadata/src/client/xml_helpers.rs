@@ -2,13 +2,13 @@ use crate::deploy::{ComponentFailure, DeployResult, DeployStatus};
 use crate::describe::{DescribeMetadataResult, MetadataType};
 use crate::error::{Error, ErrorKind, Result};
 use crate::list::MetadataComponent;
-use crate::redact::redact_session_ids;
 use crate::retrieve::{RetrieveMessage, RetrieveResult, RetrieveStatus};
 use crate::types::{
-    ComponentSuccess, DeleteResult, FileProperties, MetadataError, ReadResult, SaveResult,
-    SoapFault, TestFailure, UpsertResult,
+    CodeCoverageWarning, ComponentSuccess, DeleteResult, FileProperties, MetadataError, ReadResult,
+    SaveResult, SoapFault, TestFailure, UpsertResult,
 };
 use busbar_sf_client::security::xml;
+use busbar_sf_client::trace::redact_session_ids;
 
 impl super::MetadataClient {
     /// Parse a SOAP fault from the response. This is the ONE place all
@@ -131,6 +131,7 @@ impl super::MetadataClient {
         let component_failures = self.parse_component_failures(xml);
         let component_successes = self.parse_component_successes(xml);
         let test_failures = self.parse_test_failures(xml);
+        let code_coverage_warnings = self.parse_code_coverage_warnings(xml);
 
         Ok(DeployResult {
             id,
@@ -147,6 +148,7 @@ impl super::MetadataClient {
             component_failures,
             component_successes,
             test_failures,
+            code_coverage_warnings,
             state_detail,
         })
     }
@@ -262,6 +264,35 @@ impl super::MetadataClient {
         failures
     }
 
+    /// Parse code coverage warnings from XML.
+    pub(crate) fn parse_code_coverage_warnings(&self, xml: &str) -> Vec<CodeCoverageWarning> {
+        let mut warnings = Vec::new();
+        let pattern = "<codeCoverageWarnings>";
+        let mut search_from = xml;
+
+        while let Some(start) = search_from.find(pattern) {
+            let remaining = &search_from[start..];
+            if let Some(end) = remaining.find("</codeCoverageWarnings>") {
+                let block = &remaining[..end + "</codeCoverageWarnings>".len()];
+
+                let warning = CodeCoverageWarning {
+                    name: self.extract_element(block, "name"),
+                    message: self
+                        .extract_element(block, "message")
+                        .unwrap_or_else(|| "Unknown warning".to_string()),
+                    namespace: self.extract_element(block, "namespace"),
+                };
+
+                warnings.push(warning);
+                search_from = &remaining[end + "</codeCoverageWarnings>".len()..];
+            } else {
+                break;
+            }
+        }
+
+        warnings
+    }
+
     /// Parse retrieve result from XML.
     pub(crate) fn parse_retrieve_result(&self, xml: &str) -> Result<RetrieveResult> {
         let id = self
@@ -906,23 +937,43 @@ impl super::MetadataClient {
             if let Some(end) = remaining.find("</result>") {
                 let block = &remaining[..end + "</result>".len()];
 
-                let mut metadata_obj = serde_json::Map::new();
+                // readMetadata returns every component in one <result>, each
+                // as a <records> element (nil for names that don't exist).
+                if block.contains("<records") {
+                    records.extend(
+                        Self::split_elements(block, "records")
+                            .into_iter()
+                            .filter_map(|(element, content)| {
+                                let mut metadata_obj = Self::parse_scalar_children(content);
+                                if !metadata_obj.contains_key("fullName") {
+                                    return None;
+                                }
+                                metadata_obj.insert(
+                                    "_rawXml".to_string(),
+                                    serde_json::Value::String(element.to_string()),
+                                );
+                                Some(serde_json::Value::Object(metadata_obj))
+                            }),
+                    );
+                } else {
+                    let mut metadata_obj = serde_json::Map::new();
 
-                if let Some(full_name) = self.extract_element(block, "fullName") {
-                    metadata_obj
-                        .insert("fullName".to_string(), serde_json::Value::String(full_name));
-                }
+                    if let Some(full_name) = self.extract_element(block, "fullName") {
+                        metadata_obj
+                            .insert("fullName".to_string(), serde_json::Value::String(full_name));
+                    }
 
-                if let Some(label) = self.extract_element(block, "label") {
-                    metadata_obj.insert("label".to_string(), serde_json::Value::String(label));
-                }
+                    if let Some(label) = self.extract_element(block, "label") {
+                        metadata_obj.insert("label".to_string(), serde_json::Value::String(label));
+                    }
 
-                metadata_obj.insert(
-                    "_rawXml".to_string(),
-                    serde_json::Value::String(block.to_string()),
-                );
+                    metadata_obj.insert(
+                        "_rawXml".to_string(),
+                        serde_json::Value::String(block.to_string()),
+                    );
 
-                records.push(serde_json::Value::Object(metadata_obj));
+                    records.push(serde_json::Value::Object(metadata_obj));
+                }
 
                 search_from = &remaining[end + "</result>".len()..];
             } else {
@@ -933,6 +984,80 @@ impl super::MetadataClient {
         Ok(ReadResult { records })
     }
 
+    /// Split out each non-empty `<tag ...>...</tag>` element as
+    /// `(whole element, content)`. Self-closing elements are skipped.
+    pub(crate) fn split_elements<'a>(xml: &'a str, tag: &str) -> Vec<(&'a str, &'a str)> {
+        let open = format!("<{}", tag);
+        let close = format!("</{}>", tag);
+        let mut elements = Vec::new();
+        let mut search_from = xml;
+        while let Some(start) = search_from.find(&open) {
+            let remaining = &search_from[start..];
+            let Some(open_end) = remaining.find('>') else {
+                break;
+            };
+            let after_name = remaining.as_bytes().get(open.len()).copied();
+            if !matches!(after_name, Some(b' ' | b'>' | b'/')) {
+                search_from = &remaining[open.len()..];
+                continue;
+            }
+            if remaining[..open_end].ends_with('/') {
+                search_from = &remaining[open_end + 1..];
+                continue;
+            }
+            let Some(close_start) = remaining.find(&close) else {
+                break;
+            };
+            let element_end = close_start + close.len();
+            elements.push((
+                &remaining[..element_end],
+                &remaining[open_end + 1..close_start],
+            ));
+            search_from = &remaining[element_end..];
+        }
+        elements
+    }
+
+    /// Collect the text-only child elements of `content` as strings.
+    ///
+    /// Elements with children, nil elements, and repeats of a name already
+    /// seen are skipped.
+    pub(crate) fn parse_scalar_children(
+        content: &str,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let mut fields = serde_json::Map::new();
+        let mut rest = content;
+        while let Some(start) = rest.find('<') {
+            let remaining = &rest[start + 1..];
+            let Some(open_end) = remaining.find('>') else {
+                break;
+            };
+            let open_tag = &remaining[..open_end];
+            let name = open_tag
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default();
+            if open_tag.ends_with('/') || name.is_empty() || name.starts_with(['/', '?', '!']) {
+                rest = &remaining[open_end + 1..];
+                continue;
+            }
+            let close = format!("</{}>", name);
+            let body = &remaining[open_end + 1..];
+            let Some(close_start) = body.find(&close) else {
+                break;
+            };
+            let text = &body[..close_start];
+            if !text.contains('<') && !fields.contains_key(name) {
+                fields.insert(
+                    name.to_string(),
+                    serde_json::Value::String(xml_unescape(text)),
+                );
+            }
+            rest = &body[close_start + close.len()..];
+        }
+        fields
+    }
+
     /// Parse single SaveResult from rename operation.
     pub(crate) fn parse_rename_result(&self, xml: &str) -> Result<SaveResult> {
         let full_name = self.extract_element(xml, "fullName").unwrap_or_default();
@@ -982,6 +1107,18 @@ impl super::MetadataClient {
     }
 }
 
+/// Decode the predefined XML entities.
+pub(crate) fn xml_unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::MetadataClient;
@@ -1135,6 +1272,49 @@ mod tests {
         assert_eq!(result.component_failures[0].column_number, Some(5));
     }
 
+    #[test]
+    fn test_parse_deploy_result_run_test_details() {
+        let client = MetadataClient::from_parts("url", "token");
+        let xml = r#"
+            <result>
+                <id>0Af789</id>
+                <done>true</done>
+                <status>Failed</status>
+                <success>false</success>
+                <details>
+                    <runTestResult>
+                        <codeCoverageWarnings>
+                            <message>Test coverage of selected Apex Class is 40%, at least 75% test coverage is required</message>
+                            <name>Invoices</name>
+                            <namespace xsi:nil="true"/>
+                        </codeCoverageWarnings>
+                        <failures>
+                            <message>System.AssertException: Assertion Failed</message>
+                            <methodName>testTotals</methodName>
+                            <name>InvoicesTest</name>
+                            <stackTrace>Class.InvoicesTest.testTotals: line 12, column 1</stackTrace>
+                        </failures>
+                        <numFailures>1</numFailures>
+                    </runTestResult>
+                </details>
+            </result>
+        "#;
+
+        let result = client.parse_deploy_result(xml).unwrap();
+        assert_eq!(result.test_failures.len(), 1);
+        assert_eq!(
+            result.test_failures[0].method_name.as_deref(),
+            Some("testTotals")
+        );
+        assert_eq!(result.code_coverage_warnings.len(), 1);
+        assert_eq!(
+            result.code_coverage_warnings[0].name.as_deref(),
+            Some("Invoices")
+        );
+        assert!(result.code_coverage_warnings[0].message.contains("75%"));
+        assert_eq!(result.code_coverage_warnings[0].namespace, None);
+    }
+
     #[test]
     fn test_parse_component_failures() {
         let client = MetadataClient::from_parts("url", "token");
@@ -1537,6 +1717,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_read_result_records() {
+        let client = MetadataClient::from_parts("url", "token");
+        let xml = r#"
+            <readMetadataResponse>
+                <result>
+                    <records xsi:type="RemoteSiteSetting">
+                        <fullName>Acme_API</fullName>
+                        <description>Tom &amp; Jerry</description>
+                        <disableProtocolSecurity>false</disableProtocolSecurity>
+                        <isActive>true</isActive>
+                        <url>https://api.acme.com</url>
+                    </records>
+                    <records xsi:nil="true"/>
+                    <records xsi:type="CustomMetadata">
+                        <fullName>Flag.Checkout</fullName>
+                        <label>Checkout</label>
+                        <values>
+                            <field>Enabled__c</field>
+                            <value xsi:type="xsd:boolean">true</value>
+                        </values>
+                    </records>
+                </result>
+            </readMetadataResponse>
+        "#;
+
+        let result = client.parse_read_result(xml).unwrap();
+        assert_eq!(result.records.len(), 2);
+        assert_eq!(result.records[0]["fullName"], "Acme_API");
+        assert_eq!(result.records[0]["url"], "https://api.acme.com");
+        assert_eq!(result.records[0]["isActive"], "true");
+        assert_eq!(result.records[0]["description"], "Tom & Jerry");
+        assert_eq!(result.records[1]["label"], "Checkout");
+        assert!(result.records[1].get("values").is_none());
+        assert!(result.records[1]["_rawXml"]
+            .as_str()
+            .unwrap()
+            .contains("<field>Enabled__c</field>"));
+    }
+
     #[test]
     fn test_parse_rename_result() {
         let client = MetadataClient::from_parts("url", "token");
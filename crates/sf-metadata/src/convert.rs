@@ -0,0 +1,1002 @@
+//! Converting between SFDX source format and Metadata API format.
+//!
+//! The two formats differ in a few ways:
+//!
+//! - Components whose only file is XML are `Foo.layout` in Metadata API
+//!   format and `Foo.layout-meta.xml` in source format.
+//! - A folder's file is `reports/Sales-meta.xml` in one and
+//!   `reports/Sales.reportFolder-meta.xml` in the other.
+//! - Custom objects are one file in Metadata API format, and a directory
+//!   with a file per field, list view, record type, and so on in source
+//!   format.
+//! - Static resources are a `.resource` file in Metadata API format, and
+//!   a file with its real extension, or a directory for a zip, in source
+//!   format.
+//!
+//! Everything else is copied as it is.
+
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::package::{zip_error, DeployPackage, RetrievePackage};
+use crate::registry::{ChildType, MetadataRegistry, RegistryType, SourceStrategy};
+use crate::retrieve::PackageManifest;
+use crate::source::{
+    manifest_from_components, project_roots, relative_files, source_components, source_type_dirs,
+};
+use crate::types::DEFAULT_API_VERSION;
+
+/// Files by `/`-separated path.
+type Files = BTreeMap<String, Vec<u8>>;
+
+/// A decomposed child's type, name, and file.
+type ChildFile<'a> = (&'a ChildType, &'a str, &'a [u8]);
+
+const METADATA_NAMESPACE: &str = "http://soap.sforce.com/2006/04/metadata";
+
+/// Converts projects between SFDX source format and Metadata API format.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_metadata::{DeployOptions, FormatConverter, RetrievePackage};
+///
+/// let converter = FormatConverter::new();
+///
+/// // Deploy a source-format project.
+/// let package = converter.to_deploy_package("my-project")?;
+/// let async_id = client.deploy(&package.to_zip()?, DeployOptions::default()).await?;
+///
+/// // Write a retrieve back into it.
+/// let retrieved = RetrievePackage::from_result(&result)?;
+/// converter.retrieve_to_source_dir(&retrieved, "my-project/force-app/main/default")?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FormatConverter {
+    registry: MetadataRegistry,
+}
+
+impl FormatConverter {
+    /// A converter using the bundled [`MetadataRegistry`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the type layouts in `registry`.
+    pub fn with_registry(mut self, registry: MetadataRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Convert a source-format project into a deploy package, with a
+    /// `package.xml` listing every component.
+    ///
+    /// `source` is found the same way as for
+    /// [`PackageManifest::from_source_dir`].
+    pub fn to_deploy_package(&self, source: impl AsRef<Path>) -> Result<DeployPackage> {
+        let (files, _) = self.source_to_mdapi(source.as_ref())?;
+        Ok(DeployPackage::from_files(files))
+    }
+
+    /// Convert a source-format project into Metadata API format under
+    /// `output`, `package.xml` included. Returns the manifest written.
+    pub fn to_mdapi_dir(
+        &self,
+        source: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<PackageManifest> {
+        let (files, manifest) = self.source_to_mdapi(source.as_ref())?;
+        write_files(output.as_ref(), files)?;
+        Ok(manifest)
+    }
+
+    /// Convert a Metadata API format directory into source format under
+    /// `output`, such as `force-app/main/default`. Returns the paths
+    /// written.
+    ///
+    /// Manifests and directories of unknown types are left out.
+    pub fn to_source_dir(
+        &self,
+        mdapi: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<Vec<PathBuf>> {
+        let mdapi = mdapi.as_ref();
+        let files = read_files(mdapi, &relative_files(mdapi)?)?;
+        write_files(output.as_ref(), self.mdapi_to_source(files)?)
+    }
+
+    /// Convert a retrieve's zip into source format under `output`, like
+    /// [`to_source_dir`](Self::to_source_dir). The `unpackaged/` or
+    /// package name directory the zip puts everything under is dropped.
+    pub fn retrieve_to_source_dir(
+        &self,
+        package: &RetrievePackage,
+        output: impl AsRef<Path>,
+    ) -> Result<Vec<PathBuf>> {
+        let files = package.package_entries()?;
+        write_files(output.as_ref(), self.mdapi_to_source(files)?)
+    }
+
+    fn source_to_mdapi(&self, source: &Path) -> Result<(Files, PackageManifest)> {
+        let (roots, version) = project_roots(source)?;
+
+        // A type's files can be spread over several package directories,
+        // and a custom object's fields over several of those.
+        let mut by_type: BTreeMap<&str, (&RegistryType, Files)> = BTreeMap::new();
+        let mut components = Vec::new();
+        for root in &roots {
+            components.extend(source_components(root, &self.registry)?);
+            for (registry_type, dir) in source_type_dirs(root, &self.registry)? {
+                let files = read_files(&dir, &relative_files(&dir)?)?;
+                by_type
+                    .entry(&registry_type.name)
+                    .or_insert_with(|| (registry_type, Files::new()))
+                    .1
+                    .extend(files);
+            }
+        }
+
+        let mut output = Files::new();
+        for (registry_type, files) in by_type.into_values() {
+            for (path, contents) in type_to_mdapi(registry_type, &files)? {
+                output.insert(
+                    format!("{}/{}", registry_type.directory_name, path),
+                    contents,
+                );
+            }
+        }
+
+        let manifest = manifest_from_components(
+            components,
+            version.unwrap_or_else(|| DEFAULT_API_VERSION.to_string()),
+        );
+        output.insert(
+            "package.xml".to_string(),
+            manifest.to_package_xml().into_bytes(),
+        );
+        Ok((output, manifest))
+    }
+
+    fn mdapi_to_source(&self, files: Files) -> Result<Files> {
+        let mut by_type: BTreeMap<&str, (&RegistryType, Files)> = BTreeMap::new();
+        for (path, contents) in files {
+            let Some((directory, path)) = path.split_once('/') else {
+                continue;
+            };
+            match self.registry.by_directory(directory) {
+                Some(registry_type) => {
+                    by_type
+                        .entry(&registry_type.name)
+                        .or_insert_with(|| (registry_type, Files::new()))
+                        .1
+                        .insert(path.to_string(), contents);
+                }
+                None => tracing::debug!(directory, "skipping unknown metadata directory"),
+            }
+        }
+
+        let mut output = Files::new();
+        for (registry_type, files) in by_type.into_values() {
+            for (path, contents) in type_to_source(registry_type, &files)? {
+                output.insert(
+                    format!("{}/{}", registry_type.directory_name, path),
+                    contents,
+                );
+            }
+        }
+        Ok(output)
+    }
+}
+
+// =============================================================================
+// Source format to Metadata API format
+// =============================================================================
+
+fn type_to_mdapi(registry_type: &RegistryType, files: &Files) -> Result<Files> {
+    let suffix = registry_type.suffix.as_deref().unwrap_or_default();
+    let mut output = Files::new();
+
+    match &registry_type.strategy {
+        SourceStrategy::File | SourceStrategy::Bundle => {
+            let meta_suffix = format!(".{}-meta.xml", suffix);
+            let mut consumed = Vec::new();
+            for (path, contents) in files {
+                if path.contains('/') {
+                    // Bundle contents go across as they are; a file type's
+                    // subdirectory is a static resource's unzipped content.
+                    if registry_type.strategy == SourceStrategy::Bundle {
+                        output.insert(path.clone(), contents.clone());
+                    }
+                    continue;
+                }
+                let Some(name) = path
+                    .strip_suffix(&meta_suffix)
+                    .filter(|_| !suffix.is_empty())
+                else {
+                    continue;
+                };
+                let content_path = format!("{}.{}", name, suffix);
+                match content_file(files, name, suffix) {
+                    Some((path, content)) => {
+                        consumed.push(path);
+                        output.insert(content_path.clone(), content.clone());
+                        output.insert(format!("{}-meta.xml", content_path), contents.clone());
+                    }
+                    None if has_dir(files, name) => {
+                        output.insert(content_path.clone(), zip_dir(files, name)?);
+                        output.insert(format!("{}-meta.xml", content_path), contents.clone());
+                    }
+                    None => {
+                        output.insert(content_path, contents.clone());
+                    }
+                }
+            }
+            if registry_type.strategy == SourceStrategy::File {
+                // Anything that isn't a component's file is passed along
+                // for the deploy to report on.
+                for (path, contents) in files {
+                    if !path.contains('/')
+                        && !path.ends_with("-meta.xml")
+                        && !consumed.contains(&path.as_str())
+                        && !output.contains_key(path)
+                    {
+                        output.insert(path.clone(), contents.clone());
+                    }
+                }
+            }
+        }
+        SourceStrategy::InFolder { folder_suffix } => {
+            let folder_meta_suffix = format!(".{}-meta.xml", folder_suffix);
+            let meta_suffix = format!(".{}-meta.xml", suffix);
+            for (path, contents) in files {
+                if let Some(folder) = path.strip_suffix(&folder_meta_suffix) {
+                    output.insert(format!("{}-meta.xml", folder), contents.clone());
+                } else if let Some(name) = path.strip_suffix(&meta_suffix) {
+                    match content_file(files, name, suffix) {
+                        Some((content_path, content)) => {
+                            output.insert(content_path.to_string(), content.clone());
+                            output.insert(format!("{}-meta.xml", content_path), contents.clone());
+                        }
+                        None => {
+                            output.insert(format!("{}.{}", name, suffix), contents.clone());
+                        }
+                    }
+                }
+            }
+        }
+        SourceStrategy::Decomposed { children } => {
+            let mut parents: BTreeMap<&str, (Option<&[u8]>, Vec<ChildFile>)> = BTreeMap::new();
+            for (path, contents) in files {
+                let segments: Vec<&str> = path.split('/').collect();
+                match segments.as_slice() {
+                    [parent, file] if *file == format!("{}.{}-meta.xml", parent, suffix) => {
+                        parents.entry(parent).or_default().0 = Some(contents);
+                    }
+                    [parent, directory, file] => {
+                        let child = children.iter().find(|c| c.directory_name == *directory);
+                        let name = child
+                            .and_then(|c| file.strip_suffix(&format!(".{}-meta.xml", c.suffix)));
+                        match (child, name) {
+                            (Some(child), Some(name)) => parents
+                                .entry(parent)
+                                .or_default()
+                                .1
+                                .push((child, name, contents)),
+                            _ => tracing::warn!(path, "skipping unknown file in decomposed type"),
+                        }
+                    }
+                    _ => tracing::warn!(path, "skipping unknown file in decomposed type"),
+                }
+            }
+            for (parent, (parent_xml, parent_children)) in parents {
+                let xml = compose(&registry_type.name, parent_xml, &parent_children)?;
+                output.insert(format!("{}.{}", parent, suffix), xml.into_bytes());
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// The content file beside a component's `-meta.xml`: `name.suffix`, or
+/// `name` with another extension, as documents and static resources have.
+fn content_file<'a>(files: &'a Files, name: &str, suffix: &str) -> Option<(&'a str, &'a Vec<u8>)> {
+    let exact = format!("{}.{}", name, suffix);
+    if let Some((path, contents)) = files.get_key_value(&exact) {
+        return Some((path, contents));
+    }
+    files.iter().find_map(|(path, contents)| {
+        let extension = path.strip_prefix(name)?.strip_prefix('.')?;
+        (!extension.is_empty() && !extension.contains(['.', '/']))
+            .then_some((path.as_str(), contents))
+    })
+}
+
+fn has_dir(files: &Files, name: &str) -> bool {
+    let prefix = format!("{}/", name);
+    files.keys().any(|path| path.starts_with(&prefix))
+}
+
+/// Zip the files under `name/`, as static resources are deployed.
+fn zip_dir(files: &Files, name: &str) -> Result<Vec<u8>> {
+    let prefix = format!("{}/", name);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (path, contents) in files {
+        if let Some(path) = path.strip_prefix(&prefix) {
+            zip.start_file(path, SimpleFileOptions::default())
+                .map_err(zip_error)?;
+            zip.write_all(contents)?;
+        }
+    }
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Merge a decomposed component's children back into its parent's XML.
+fn compose(type_name: &str, parent_xml: Option<&[u8]>, children: &[ChildFile]) -> Result<String> {
+    let parent_xml = match parent_xml {
+        Some(xml) => utf8(xml)?.to_string(),
+        None => format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<{} xmlns=\"{}\">\n</{}>\n",
+            type_name, METADATA_NAMESPACE, type_name
+        ),
+    };
+    let root = Root::parse(&parent_xml)?;
+
+    let mut elements: Vec<(String, String)> = root
+        .elements(&parent_xml)
+        .into_iter()
+        .map(|e| (e.name.to_string(), parent_xml[e.start..e.end].to_string()))
+        .collect();
+    for (child, name, xml) in children {
+        let xml = utf8(xml)?;
+        let child_root = Root::parse(xml)?;
+        let mut inner = xml[child_root.inner_start..child_root.inner_end].to_string();
+        let named = child_root
+            .elements(xml)
+            .iter()
+            .any(|e| e.name == "fullName" || e.name == "name");
+        if !named {
+            inner = format!("\n    <fullName>{}</fullName>{}", name, inner);
+        }
+        elements.push((
+            child.xml_element.clone(),
+            format!(
+                "<{}>{}</{}>",
+                child.xml_element,
+                reindent(&inner, 1),
+                child.xml_element
+            ),
+        ));
+    }
+    // The Metadata API schemas list elements alphabetically, after the
+    // full name.
+    elements.sort_by(|(a, _), (b, _)| (a != "fullName", a).cmp(&(b != "fullName", b)));
+
+    Ok(root.rebuild(&parent_xml, elements.iter().map(|(_, xml)| xml.as_str())))
+}
+
+// =============================================================================
+// Metadata API format to source format
+// =============================================================================
+
+fn type_to_source(registry_type: &RegistryType, files: &Files) -> Result<Files> {
+    let suffix = registry_type.suffix.as_deref().unwrap_or_default();
+    let dot_suffix = format!(".{}", suffix);
+    let mut output = Files::new();
+
+    match &registry_type.strategy {
+        SourceStrategy::File | SourceStrategy::Bundle => {
+            for (path, contents) in files {
+                let name = path
+                    .strip_suffix(&dot_suffix)
+                    .filter(|name| !suffix.is_empty() && !name.contains('/'));
+                let Some(name) = name else {
+                    output.insert(path.clone(), contents.clone());
+                    continue;
+                };
+                match files.get(&format!("{}-meta.xml", path)) {
+                    Some(meta) if registry_type.name == "StaticResource" => {
+                        output.extend(static_resource_content(name, meta, contents)?);
+                    }
+                    Some(_) => {
+                        output.insert(path.clone(), contents.clone());
+                    }
+                    None => {
+                        output.insert(format!("{}-meta.xml", path), contents.clone());
+                    }
+                }
+            }
+        }
+        SourceStrategy::InFolder { folder_suffix } => {
+            for (path, contents) in files {
+                if let Some(meta_of) = path.strip_suffix("-meta.xml") {
+                    if !meta_of.contains('/') {
+                        output.insert(
+                            format!("{}.{}-meta.xml", meta_of, folder_suffix),
+                            contents.clone(),
+                        );
+                    } else if meta_of.ends_with(&dot_suffix) {
+                        output.insert(path.clone(), contents.clone());
+                    } else {
+                        // A document's meta file is named after its stem.
+                        let stem = meta_of.rsplit_once('.').map_or(meta_of, |(stem, _)| stem);
+                        output.insert(format!("{}{}-meta.xml", stem, dot_suffix), contents.clone());
+                    }
+                } else if files.contains_key(&format!("{}-meta.xml", path)) {
+                    output.insert(path.clone(), contents.clone());
+                } else if path.ends_with(&dot_suffix) {
+                    output.insert(format!("{}-meta.xml", path), contents.clone());
+                } else {
+                    output.insert(path.clone(), contents.clone());
+                }
+            }
+        }
+        SourceStrategy::Decomposed { children } => {
+            for (path, contents) in files {
+                let name = path
+                    .strip_suffix(&dot_suffix)
+                    .filter(|name| !suffix.is_empty() && !name.contains('/'));
+                match name {
+                    Some(name) => output.extend(decompose(name, suffix, children, contents)?),
+                    None => {
+                        output.insert(path.clone(), contents.clone());
+                    }
+                }
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// Split a decomposed component's XML into its parent's file and a file
+/// per child.
+fn decompose(name: &str, suffix: &str, children: &[ChildType], xml: &[u8]) -> Result<Files> {
+    let xml = utf8(xml)?;
+    let root = Root::parse(xml)?;
+    let mut output = Files::new();
+    let mut kept = Vec::new();
+
+    for element in root.elements(xml) {
+        let element_xml = &xml[element.start..element.end];
+        let child = children.iter().find(|c| c.xml_element == element.name);
+        let (Some(child), Some(element_root)) = (child, Root::parse(element_xml).ok()) else {
+            kept.push(element_xml);
+            continue;
+        };
+        let child_elements = element_root.elements(element_xml);
+        let named = ["fullName", "name"]
+            .iter()
+            .find_map(|tag| child_elements.iter().find(|e| e.name == *tag));
+        let Some(child_name) = named.map(|e| text(element_xml, e)) else {
+            kept.push(element_xml);
+            continue;
+        };
+        let inner = &element_xml[element_root.inner_start..element_root.inner_end];
+        output.insert(
+            format!(
+                "{}/{}/{}.{}-meta.xml",
+                name, child.directory_name, child_name, child.suffix
+            ),
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<{} xmlns=\"{}\">{}</{}>\n",
+                child.name,
+                METADATA_NAMESPACE,
+                reindent(inner, -1),
+                child.name
+            )
+            .into_bytes(),
+        );
+    }
+
+    output.insert(
+        format!("{}/{}.{}-meta.xml", name, name, suffix),
+        root.rebuild(xml, kept).into_bytes(),
+    );
+    Ok(output)
+}
+
+/// A static resource's content under its real extension, or unzipped
+/// into a directory.
+fn static_resource_content(name: &str, meta: &[u8], contents: &[u8]) -> Result<Files> {
+    let meta = utf8(meta)?;
+    let content_type = Root::parse(meta)
+        .ok()
+        .and_then(|root| {
+            root.elements(meta)
+                .into_iter()
+                .find(|e| e.name == "contentType")
+                .map(|e| text(meta, &e))
+        })
+        .unwrap_or_default();
+
+    let mut output = Files::new();
+    if matches!(
+        content_type.as_str(),
+        "application/zip" | "application/x-zip-compressed"
+    ) {
+        if let Ok(mut archive) = ZipArchive::new(Cursor::new(contents)) {
+            for i in 0..archive.len() {
+                let mut file = archive.by_index(i).map_err(zip_error)?;
+                if file.is_dir() || file.enclosed_name().is_none() {
+                    continue;
+                }
+                let mut unzipped = Vec::new();
+                file.read_to_end(&mut unzipped)?;
+                output.insert(format!("{}/{}", name, file.name()), unzipped);
+            }
+            return Ok(output);
+        }
+    }
+    let extension = match content_type.as_str() {
+        "application/javascript" | "text/javascript" | "application/x-javascript" => "js",
+        "text/css" => "css",
+        "application/json" => "json",
+        "text/html" => "html",
+        "text/plain" => "txt",
+        "application/xml" | "text/xml" => "xml",
+        "application/pdf" => "pdf",
+        "image/png" => "png",
+        "image/jpeg" => "jpeg",
+        "image/gif" => "gif",
+        "image/svg+xml" => "svg",
+        _ => "resource",
+    };
+    output.insert(format!("{}.{}", name, extension), contents.to_vec());
+    Ok(output)
+}
+
+// =============================================================================
+// XML helpers
+// =============================================================================
+
+/// The root element of a metadata file.
+struct Root<'a> {
+    name: &'a str,
+    /// Just past the opening tag.
+    inner_start: usize,
+    /// The start of the closing tag.
+    inner_end: usize,
+}
+
+/// A top-level element inside a root.
+struct Element<'a> {
+    name: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Root<'a> {
+    fn parse(xml: &'a str) -> Result<Self> {
+        let invalid = || Error::new(ErrorKind::Parse("no root element".to_string()));
+        let mut from = 0;
+        let open = loop {
+            let open = from + xml[from..].find('<').ok_or_else(invalid)?;
+            if xml[open..].starts_with("<?") || xml[open..].starts_with("<!") {
+                from = open + xml[open..].find('>').ok_or_else(invalid)? + 1;
+            } else {
+                break open;
+            }
+        };
+        let close = open + xml[open..].find('>').ok_or_else(invalid)?;
+        let tag = &xml[open + 1..close];
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        if tag.ends_with('/') {
+            return Ok(Self {
+                name,
+                inner_start: close + 1,
+                inner_end: close + 1,
+            });
+        }
+        let inner_end = xml.rfind(&format!("</{}", name)).ok_or_else(invalid)?;
+        Ok(Self {
+            name,
+            inner_start: close + 1,
+            inner_end,
+        })
+    }
+
+    /// The elements directly inside the root.
+    fn elements(&self, xml: &'a str) -> Vec<Element<'a>> {
+        let mut elements = Vec::new();
+        let mut depth = 0usize;
+        let mut current: Option<(&str, usize)> = None;
+        let mut i = self.inner_start;
+        while let Some(offset) = xml[i..self.inner_end].find('<') {
+            let start = i + offset;
+            let rest = &xml[start..self.inner_end];
+            let skip_to =
+                |terminator: &str| rest.find(terminator).map(|end| end + terminator.len());
+            let skipped = if rest.starts_with("<!--") {
+                skip_to("-->")
+            } else if rest.starts_with("<![CDATA[") {
+                skip_to("]]>")
+            } else if rest.starts_with("<?") {
+                skip_to("?>")
+            } else {
+                None
+            };
+            if let Some(skipped) = skipped {
+                i = start + skipped;
+                continue;
+            }
+            let Some(close) = rest.find('>').map(|close| start + close) else {
+                break;
+            };
+            let tag = &xml[start + 1..close];
+            if tag.starts_with('/') {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    if let Some((name, element_start)) = current.take() {
+                        elements.push(Element {
+                            name,
+                            start: element_start,
+                            end: close + 1,
+                        });
+                    }
+                }
+            } else {
+                let name = tag
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .next()
+                    .unwrap_or_default();
+                if tag.ends_with('/') {
+                    if depth == 0 {
+                        elements.push(Element {
+                            name,
+                            start,
+                            end: close + 1,
+                        });
+                    }
+                } else {
+                    if depth == 0 {
+                        current = Some((name, start));
+                    }
+                    depth += 1;
+                }
+            }
+            i = close + 1;
+        }
+        elements
+    }
+
+    /// The file with its root's contents replaced by `elements`, one per
+    /// line.
+    fn rebuild<'e>(&self, xml: &str, elements: impl IntoIterator<Item = &'e str>) -> String {
+        let mut rebuilt = xml[..self.inner_start].to_string();
+        rebuilt.push('\n');
+        for element in elements {
+            rebuilt.push_str("    ");
+            rebuilt.push_str(element);
+            rebuilt.push('\n');
+        }
+        rebuilt.push_str(&format!("</{}>\n", self.name));
+        rebuilt
+    }
+}
+
+/// The text inside an element, such as a name.
+fn text(xml: &str, element: &Element<'_>) -> String {
+    let element_xml = &xml[element.start..element.end];
+    let inner = element_xml
+        .find('>')
+        .zip(element_xml.rfind("</"))
+        .map_or("", |(open, close)| {
+            &element_xml[(open + 1).min(close)..close]
+        });
+    inner.trim().to_string()
+}
+
+/// Shift the lines of an element's contents `levels` levels of four-space
+/// indentation deeper (or shallower, when negative), ending with the
+/// indentation its closing tag needs.
+fn reindent(inner: &str, levels: isize) -> String {
+    let body = inner.trim_start_matches(['\r', '\n']).trim_end();
+    if body.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = body
+        .lines()
+        .map(|line| {
+            if levels >= 0 {
+                format!("{}{}", "    ".repeat(levels as usize), line)
+            } else {
+                let remove = 4 * levels.unsigned_abs();
+                let indent = line.len() - line.trim_start_matches(' ').len();
+                line[indent.min(remove)..].to_string()
+            }
+        })
+        .collect();
+    let close_indent = if levels > 0 { "    " } else { "" };
+    format!("\n{}\n{}", lines.join("\n"), close_indent)
+}
+
+fn utf8(bytes: &[u8]) -> Result<&str> {
+    std::str::from_utf8(bytes).map_err(|e| {
+        Error::with_source(
+            ErrorKind::Parse(format!("metadata file is not UTF-8: {}", e)),
+            e,
+        )
+    })
+}
+
+// =============================================================================
+// Files on disk
+// =============================================================================
+
+pub(crate) fn read_files(dir: &Path, paths: &[String]) -> Result<Files> {
+    paths
+        .iter()
+        .map(|path| Ok((path.clone(), std::fs::read(dir.join(path))?)))
+        .collect()
+}
+
+fn write_files(dir: &Path, files: Files) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for (path, contents) in files {
+        let relative = Path::new(&path);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(Error::new(ErrorKind::Other(format!(
+                "refusing to write {} outside {}",
+                path,
+                dir.display()
+            ))));
+        }
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, contents)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn file(package: &DeployPackage, path: &str) -> String {
+        let contents = package
+            .file(path)
+            .unwrap_or_else(|| panic!("missing {}", path));
+        String::from_utf8(contents.to_vec()).unwrap()
+    }
+
+    const INVOICE_OBJECT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<CustomObject xmlns="http://soap.sforce.com/2006/04/metadata">
+    <fields>
+        <fullName>Amount__c</fullName>
+        <label>Amount</label>
+        <type>Currency</type>
+    </fields>
+    <fields>
+        <fullName>Due__c</fullName>
+        <label>Due</label>
+        <type>Date</type>
+    </fields>
+    <label>Invoice</label>
+    <listViews>
+        <fullName>All</fullName>
+        <filterScope>Everything</filterScope>
+        <label>All</label>
+    </listViews>
+    <sharingModel>ReadWrite</sharingModel>
+</CustomObject>
+"#;
+
+    #[test]
+    fn test_source_to_deploy_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "sfdx-project.json",
+            r#"{"packageDirectories": [{"path": "force-app"}], "sourceApiVersion": "61.0"}"#,
+        );
+        let default = "force-app/main/default";
+        write(
+            root,
+            &format!("{default}/classes/Invoices.cls"),
+            "public class Invoices {}",
+        );
+        write(
+            root,
+            &format!("{default}/classes/Invoices.cls-meta.xml"),
+            "<ApexClass/>",
+        );
+        write(
+            root,
+            &format!("{default}/layouts/Invoice__c-Layout.layout-meta.xml"),
+            "<Layout/>",
+        );
+        write(
+            root,
+            &format!("{default}/staticresources/app.resource-meta.xml"),
+            "<StaticResource><contentType>application/zip</contentType></StaticResource>",
+        );
+        write(
+            root,
+            &format!("{default}/staticresources/app/index.js"),
+            "run()",
+        );
+        write(
+            root,
+            &format!("{default}/reports/Sales.reportFolder-meta.xml"),
+            "<ReportFolder/>",
+        );
+        write(
+            root,
+            &format!("{default}/reports/Sales/Pipeline.report-meta.xml"),
+            "<Report/>",
+        );
+        write(root, &format!("{default}/documents/Shared/logo.png"), "png");
+        write(
+            root,
+            &format!("{default}/documents/Shared/logo.document-meta.xml"),
+            "<Document/>",
+        );
+        write(
+            root,
+            &format!("{default}/lwc/invoiceList/invoiceList.js"),
+            "export default {}",
+        );
+        write(
+            root,
+            &format!("{default}/lwc/invoiceList/invoiceList.js-meta.xml"),
+            "<LightningComponentBundle/>",
+        );
+
+        let object = format!("{default}/objects/Invoice__c");
+        write(
+            root,
+            &format!("{object}/Invoice__c.object-meta.xml"),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<CustomObject xmlns=\"http://soap.sforce.com/2006/04/metadata\">\n    <sharingModel>ReadWrite</sharingModel>\n    <label>Invoice</label>\n</CustomObject>\n",
+        );
+        write(
+            root,
+            &format!("{object}/fields/Due__c.field-meta.xml"),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<CustomField xmlns=\"http://soap.sforce.com/2006/04/metadata\">\n    <fullName>Due__c</fullName>\n    <label>Due</label>\n    <type>Date</type>\n</CustomField>\n",
+        );
+        write(
+            root,
+            &format!("{object}/fields/Amount__c.field-meta.xml"),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<CustomField xmlns=\"http://soap.sforce.com/2006/04/metadata\">\n    <label>Amount</label>\n    <type>Currency</type>\n</CustomField>\n",
+        );
+        write(
+            root,
+            &format!("{object}/listViews/All.listView-meta.xml"),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<ListView xmlns=\"http://soap.sforce.com/2006/04/metadata\">\n    <fullName>All</fullName>\n    <filterScope>Everything</filterScope>\n    <label>All</label>\n</ListView>\n",
+        );
+
+        let package = FormatConverter::new().to_deploy_package(root).unwrap();
+        assert_eq!(
+            package.file_names().collect::<Vec<_>>(),
+            [
+                "classes/Invoices.cls",
+                "classes/Invoices.cls-meta.xml",
+                "documents/Shared/logo.png",
+                "documents/Shared/logo.png-meta.xml",
+                "layouts/Invoice__c-Layout.layout",
+                "lwc/invoiceList/invoiceList.js",
+                "lwc/invoiceList/invoiceList.js-meta.xml",
+                "objects/Invoice__c.object",
+                "package.xml",
+                "reports/Sales-meta.xml",
+                "reports/Sales/Pipeline.report",
+                "staticresources/app.resource",
+                "staticresources/app.resource-meta.xml",
+            ]
+        );
+        assert_eq!(file(&package, "objects/Invoice__c.object"), INVOICE_OBJECT);
+        assert_eq!(file(&package, "reports/Sales/Pipeline.report"), "<Report/>");
+
+        let resource = package.file("staticresources/app.resource").unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(resource)).unwrap();
+        let mut index = String::new();
+        archive
+            .by_name("index.js")
+            .unwrap()
+            .read_to_string(&mut index)
+            .unwrap();
+        assert_eq!(index, "run()");
+
+        let package_xml = file(&package, "package.xml");
+        for expected in [
+            "<members>Invoice__c.Amount__c</members>",
+            "<members>Invoice__c.All</members>",
+            "<members>Sales/Pipeline</members>",
+            "<members>Shared/logo.png</members>",
+            "<version>61.0</version>",
+        ] {
+            assert!(package_xml.contains(expected), "missing {}", expected);
+        }
+    }
+
+    #[test]
+    fn test_mdapi_round_trips_through_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let mdapi = dir.path().join("mdapi");
+        write(&mdapi, "package.xml", "<Package/>");
+        write(&mdapi, "objects/Invoice__c.object", INVOICE_OBJECT);
+        write(&mdapi, "classes/Invoices.cls", "public class Invoices {}");
+        write(&mdapi, "classes/Invoices.cls-meta.xml", "<ApexClass/>");
+        write(&mdapi, "layouts/Invoice__c-Layout.layout", "<Layout/>");
+        write(&mdapi, "reports/Sales-meta.xml", "<ReportFolder/>");
+        write(&mdapi, "reports/Sales/Pipeline.report", "<Report/>");
+        write(&mdapi, "documents/Shared-meta.xml", "<DocumentFolder/>");
+        write(&mdapi, "documents/Shared/logo.png", "png");
+        write(&mdapi, "documents/Shared/logo.png-meta.xml", "<Document/>");
+        write(&mdapi, "staticresources/style.resource", "body {}");
+        write(
+            &mdapi,
+            "staticresources/style.resource-meta.xml",
+            "<StaticResource><contentType>text/css</contentType></StaticResource>",
+        );
+
+        let converter = FormatConverter::new();
+        let source = dir.path().join("source");
+        converter.to_source_dir(&mdapi, &source).unwrap();
+
+        for expected in [
+            "objects/Invoice__c/Invoice__c.object-meta.xml",
+            "objects/Invoice__c/fields/Amount__c.field-meta.xml",
+            "objects/Invoice__c/listViews/All.listView-meta.xml",
+            "layouts/Invoice__c-Layout.layout-meta.xml",
+            "reports/Sales.reportFolder-meta.xml",
+            "reports/Sales/Pipeline.report-meta.xml",
+            "documents/Shared.documentFolder-meta.xml",
+            "documents/Shared/logo.png",
+            "documents/Shared/logo.document-meta.xml",
+            "staticresources/style.css",
+            "staticresources/style.resource-meta.xml",
+        ] {
+            assert!(source.join(expected).is_file(), "missing {}", expected);
+        }
+        assert!(!source.join("package.xml").exists());
+        assert_eq!(
+            std::fs::read_to_string(source.join("objects/Invoice__c/fields/Due__c.field-meta.xml"))
+                .unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<CustomField xmlns=\"http://soap.sforce.com/2006/04/metadata\">\n    <fullName>Due__c</fullName>\n    <label>Due</label>\n    <type>Date</type>\n</CustomField>\n"
+        );
+
+        let package = converter.to_deploy_package(&source).unwrap();
+        assert_eq!(file(&package, "objects/Invoice__c.object"), INVOICE_OBJECT);
+        assert_eq!(file(&package, "staticresources/style.resource"), "body {}");
+        assert!(package.file("documents/Shared/logo.png-meta.xml").is_some());
+    }
+
+    #[test]
+    fn test_retrieve_to_source_dir_drops_package_directory() {
+        let zip = DeployPackage::default()
+            .with_file("unpackaged/package.xml", "<Package/>")
+            .with_file("unpackaged/layouts/Account-Layout.layout", "<Layout/>")
+            .to_zip()
+            .unwrap();
+        let retrieved = RetrievePackage::from_zip(zip).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let written = FormatConverter::new()
+            .retrieve_to_source_dir(&retrieved, dir.path())
+            .unwrap();
+        assert_eq!(
+            written,
+            [dir.path().join("layouts/Account-Layout.layout-meta.xml")]
+        );
+    }
+}
@@ -0,0 +1,491 @@
+//! Custom Metadata Type records (the `CustomMetadata` metadata type).
+
+use std::collections::BTreeMap;
+
+use busbar_sf_client::security::xml;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::client::xml_unescape;
+use crate::error::{Error, ErrorKind, Result};
+use crate::MetadataClient;
+
+/// A typed field value on a custom metadata record.
+///
+/// Each variant is written with its matching `xsi:type`, which Salesforce
+/// requires for non-text fields.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomMetadataValue {
+    /// Text, text area, picklist, email, phone, URL, or metadata relationship.
+    Text(String),
+    /// Checkbox.
+    Boolean(bool),
+    /// Number or percent.
+    Number(f64),
+    /// Date.
+    Date(NaiveDate),
+    /// Date/time.
+    DateTime(DateTime<Utc>),
+}
+
+impl CustomMetadataValue {
+    fn xsi_type(&self) -> &'static str {
+        match self {
+            CustomMetadataValue::Text(_) => "xsd:string",
+            CustomMetadataValue::Boolean(_) => "xsd:boolean",
+            CustomMetadataValue::Number(_) => "xsd:double",
+            CustomMetadataValue::Date(_) => "xsd:date",
+            CustomMetadataValue::DateTime(_) => "xsd:dateTime",
+        }
+    }
+
+    fn to_xml_text(&self) -> String {
+        match self {
+            CustomMetadataValue::Text(s) => xml::escape(s),
+            other => other.to_string(),
+        }
+    }
+
+    /// Parse a value as read back from the org, by its `xsi:type`.
+    fn from_xsi(xsi_type: &str, text: &str) -> Self {
+        let parsed = match xsi_type {
+            "xsd:boolean" => text.parse().ok().map(CustomMetadataValue::Boolean),
+            "xsd:double" | "xsd:int" | "xsd:long" => {
+                text.parse().ok().map(CustomMetadataValue::Number)
+            }
+            "xsd:date" => NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .map(CustomMetadataValue::Date),
+            "xsd:dateTime" => DateTime::parse_from_rfc3339(text)
+                .ok()
+                .map(|t| CustomMetadataValue::DateTime(t.with_timezone(&Utc))),
+            _ => None,
+        };
+        parsed.unwrap_or_else(|| CustomMetadataValue::Text(text.to_string()))
+    }
+}
+
+impl std::fmt::Display for CustomMetadataValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CustomMetadataValue::Text(s) => f.write_str(s),
+            CustomMetadataValue::Boolean(b) => write!(f, "{}", b),
+            CustomMetadataValue::Number(n) => write!(f, "{}", n),
+            CustomMetadataValue::Date(d) => write!(f, "{}", d.format("%Y-%m-%d")),
+            CustomMetadataValue::DateTime(t) => {
+                f.write_str(&t.to_rfc3339_opts(SecondsFormat::Millis, true))
+            }
+        }
+    }
+}
+
+impl From<&str> for CustomMetadataValue {
+    fn from(value: &str) -> Self {
+        CustomMetadataValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for CustomMetadataValue {
+    fn from(value: String) -> Self {
+        CustomMetadataValue::Text(value)
+    }
+}
+
+impl From<bool> for CustomMetadataValue {
+    fn from(value: bool) -> Self {
+        CustomMetadataValue::Boolean(value)
+    }
+}
+
+impl From<i32> for CustomMetadataValue {
+    fn from(value: i32) -> Self {
+        CustomMetadataValue::Number(value.into())
+    }
+}
+
+impl From<f64> for CustomMetadataValue {
+    fn from(value: f64) -> Self {
+        CustomMetadataValue::Number(value)
+    }
+}
+
+impl From<NaiveDate> for CustomMetadataValue {
+    fn from(value: NaiveDate) -> Self {
+        CustomMetadataValue::Date(value)
+    }
+}
+
+impl From<DateTime<Utc>> for CustomMetadataValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        CustomMetadataValue::DateTime(value)
+    }
+}
+
+/// A record of a custom metadata type, e.g. `Feature_Flag__mdt.Checkout`.
+///
+/// Upsert records with
+/// [`MetadataClient::upsert_custom_metadata`](crate::MetadataClient::upsert_custom_metadata),
+/// or write [`to_metadata_xml`](Self::to_metadata_xml) to
+/// [`file_path`](Self::file_path) in a deploy package.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Serialize)]
+/// struct FeatureFlag {
+///     #[serde(rename = "Enabled__c")]
+///     enabled: bool,
+///     #[serde(rename = "Rollout_Percent__c")]
+///     rollout_percent: f64,
+/// }
+///
+/// let record = CustomMetadataRecord::from_serialize(
+///     "Feature_Flag__mdt",
+///     "Checkout",
+///     "Checkout",
+///     &FeatureFlag { enabled: true, rollout_percent: 25.0 },
+/// )?;
+/// client.upsert_custom_metadata(&[record]).await?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomMetadataRecord {
+    /// The type's API name without the `__mdt` suffix.
+    pub type_name: String,
+    /// The record's developer name.
+    pub developer_name: String,
+    /// The record's label.
+    pub label: String,
+    /// Whether the record is hidden from subscribers of a managed package.
+    pub protected: bool,
+    /// Field values by field API name. `None` clears the field.
+    pub values: BTreeMap<String, Option<CustomMetadataValue>>,
+}
+
+impl CustomMetadataRecord {
+    /// An empty record. `type_name` may include the `__mdt` suffix.
+    pub fn new(
+        type_name: impl Into<String>,
+        developer_name: impl Into<String>,
+        label: impl Into<String>,
+    ) -> Self {
+        let type_name = type_name.into();
+        let type_name = type_name
+            .strip_suffix("__mdt")
+            .map(str::to_string)
+            .unwrap_or(type_name);
+        Self {
+            type_name,
+            developer_name: developer_name.into(),
+            label: label.into(),
+            protected: false,
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// A record whose values come from a struct's serialized fields.
+    ///
+    /// Field names are used as-is, so rename them to their API names
+    /// (`#[serde(rename = "Enabled__c")]`). Strings become text, booleans
+    /// checkboxes, numbers numbers, and `None` clears the field. Nested
+    /// structs and sequences are rejected. Use
+    /// [`with_value`](Self::with_value) for dates.
+    pub fn from_serialize<T: Serialize>(
+        type_name: impl Into<String>,
+        developer_name: impl Into<String>,
+        label: impl Into<String>,
+        values: &T,
+    ) -> Result<Self> {
+        let mut record = Self::new(type_name, developer_name, label);
+        let serde_json::Value::Object(fields) = serde_json::to_value(values)
+            .map_err(|e| Error::with_source(ErrorKind::Other(e.to_string()), e))?
+        else {
+            return Err(Error::new(ErrorKind::Other(
+                "custom metadata values must serialize to a struct or map".to_string(),
+            )));
+        };
+        for (field, value) in fields {
+            let value = match value {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(s) => Some(CustomMetadataValue::Text(s)),
+                serde_json::Value::Bool(b) => Some(CustomMetadataValue::Boolean(b)),
+                serde_json::Value::Number(n) => n.as_f64().map(CustomMetadataValue::Number),
+                serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+                    return Err(Error::new(ErrorKind::Other(format!(
+                        "custom metadata field {} must be a scalar",
+                        field
+                    ))));
+                }
+            };
+            record.values.insert(field, value);
+        }
+        Ok(record)
+    }
+
+    /// Rebuild a record from a
+    /// [`read_metadata`](crate::MetadataClient::read_metadata)`("CustomMetadata", ..)`
+    /// result.
+    ///
+    /// Returns `None` if `record` isn't a custom metadata record.
+    pub fn from_read_record(record: &serde_json::Value) -> Option<Self> {
+        let (type_name, developer_name) = record["fullName"].as_str()?.split_once('.')?;
+        let raw = record["_rawXml"].as_str()?;
+        let mut parsed = Self::new(
+            type_name,
+            developer_name,
+            record["label"].as_str().unwrap_or_default(),
+        );
+        parsed.protected = record["protected"].as_str() == Some("true");
+        for (_, content) in MetadataClient::split_elements(raw, "values") {
+            let fields = MetadataClient::parse_scalar_children(content);
+            let Some(field) = fields.get("field").and_then(|f| f.as_str()) else {
+                continue;
+            };
+            let value = MetadataClient::split_elements(content, "value")
+                .first()
+                .map(|(element, text)| {
+                    let xsi_type = element
+                        .split_once("xsi:type=\"")
+                        .and_then(|(_, rest)| rest.split_once('"'))
+                        .map_or("", |(xsi_type, _)| xsi_type);
+                    CustomMetadataValue::from_xsi(xsi_type, &xml_unescape(text))
+                });
+            parsed.values.insert(field.to_string(), value);
+        }
+        Some(parsed)
+    }
+
+    /// Set a field value.
+    pub fn with_value(
+        mut self,
+        field: impl Into<String>,
+        value: impl Into<CustomMetadataValue>,
+    ) -> Self {
+        self.values.insert(field.into(), Some(value.into()));
+        self
+    }
+
+    /// Clear a field.
+    pub fn with_null(mut self, field: impl Into<String>) -> Self {
+        self.values.insert(field.into(), None);
+        self
+    }
+
+    /// Set whether the record is protected.
+    pub fn protected(mut self, protected: bool) -> Self {
+        self.protected = protected;
+        self
+    }
+
+    /// The record's metadata full name, `Type.DeveloperName`.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.type_name, self.developer_name)
+    }
+
+    /// The record's path in a deploy package.
+    pub fn file_path(&self) -> String {
+        format!("customMetadata/{}.md", self.full_name())
+    }
+
+    /// The record's `.md` file contents for a deploy package.
+    pub fn to_metadata_xml(&self) -> String {
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <CustomMetadata xmlns=\"http://soap.sforce.com/2006/04/metadata\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xmlns:xsd=\"http://www.w3.org/2001/XMLSchema\">\n",
+        );
+        self.write_body(&mut out, "", "    ");
+        out.push_str("</CustomMetadata>\n");
+        out
+    }
+
+    /// Check the names Salesforce would reject before sending anything.
+    pub(crate) fn validate(&self) -> Result<()> {
+        for (what, name) in [
+            ("type name", self.type_name.as_str()),
+            ("developer name", self.developer_name.as_str()),
+        ] {
+            if !is_valid_api_name(name) {
+                return Err(Error::new(ErrorKind::Other(format!(
+                    "invalid custom metadata {}: {:?}",
+                    what, name
+                ))));
+            }
+        }
+        if let Some(field) = self.values.keys().find(|f| !is_valid_field_name(f)) {
+            return Err(Error::new(ErrorKind::Other(format!(
+                "invalid custom metadata field name: {:?}",
+                field
+            ))));
+        }
+        Ok(())
+    }
+
+    /// The record as a `<met:metadata>` element for CRUD calls.
+    pub(crate) fn to_soap_element(&self) -> String {
+        let mut out = String::from("      <met:metadata xsi:type=\"met:CustomMetadata\">\n");
+        out.push_str(&format!(
+            "        <met:fullName>{}</met:fullName>\n",
+            xml::escape(&self.full_name())
+        ));
+        self.write_body(&mut out, "met:", "        ");
+        out.push_str("      </met:metadata>");
+        out
+    }
+
+    fn write_body(&self, out: &mut String, prefix: &str, indent: &str) {
+        out.push_str(&format!(
+            "{indent}<{prefix}label>{}</{prefix}label>\n",
+            xml::escape(&self.label)
+        ));
+        out.push_str(&format!(
+            "{indent}<{prefix}protected>{}</{prefix}protected>\n",
+            self.protected
+        ));
+        for (field, value) in &self.values {
+            out.push_str(&format!("{indent}<{prefix}values>\n"));
+            out.push_str(&format!(
+                "{indent}    <{prefix}field>{}</{prefix}field>\n",
+                xml::escape(field)
+            ));
+            match value {
+                Some(value) => out.push_str(&format!(
+                    "{indent}    <{prefix}value xsi:type=\"{}\">{}</{prefix}value>\n",
+                    value.xsi_type(),
+                    value.to_xml_text()
+                )),
+                None => out.push_str(&format!("{indent}    <{prefix}value xsi:nil=\"true\"/>\n")),
+            }
+            out.push_str(&format!("{indent}</{prefix}values>\n"));
+        }
+    }
+}
+
+/// Letters, digits, and single underscores, starting with a letter.
+fn is_valid_developer_name(name: &str) -> bool {
+    name.len() <= 40
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && !name.ends_with('_')
+        && !name.contains("__")
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A developer name with an optional namespace prefix (`ns__Name`).
+fn is_valid_api_name(name: &str) -> bool {
+    match name.split_once("__") {
+        Some((namespace, base)) => {
+            is_valid_developer_name(namespace) && is_valid_developer_name(base)
+        }
+        None => is_valid_developer_name(name),
+    }
+}
+
+/// A custom field name, optionally namespaced (`ns__Field__c`).
+fn is_valid_field_name(name: &str) -> bool {
+    name.strip_suffix("__c").is_some_and(is_valid_api_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct FeatureFlag {
+        #[serde(rename = "Enabled__c")]
+        enabled: bool,
+        #[serde(rename = "Rollout_Percent__c")]
+        rollout_percent: u8,
+        #[serde(rename = "Owner_Email__c")]
+        owner_email: Option<String>,
+        #[serde(rename = "Notes__c")]
+        notes: String,
+    }
+
+    #[test]
+    fn test_from_serialize_coerces_values() {
+        let record = CustomMetadataRecord::from_serialize(
+            "Feature_Flag__mdt",
+            "Checkout",
+            "Checkout",
+            &FeatureFlag {
+                enabled: true,
+                rollout_percent: 25,
+                owner_email: None,
+                notes: "a < b".to_string(),
+            },
+        )
+        .unwrap()
+        .with_value("Starts_On__c", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+        assert_eq!(record.full_name(), "Feature_Flag.Checkout");
+        assert_eq!(
+            record.file_path(),
+            "customMetadata/Feature_Flag.Checkout.md"
+        );
+        record.validate().unwrap();
+
+        let xml = record.to_metadata_xml();
+        assert!(xml.contains("<label>Checkout</label>"));
+        assert!(xml.contains(
+            "<field>Enabled__c</field>\n        <value xsi:type=\"xsd:boolean\">true</value>"
+        ));
+        assert!(xml.contains("<value xsi:type=\"xsd:double\">25</value>"));
+        assert!(xml.contains("<field>Owner_Email__c</field>\n        <value xsi:nil=\"true\"/>"));
+        assert!(xml.contains("<value xsi:type=\"xsd:string\">a &lt; b</value>"));
+        assert!(xml.contains("<value xsi:type=\"xsd:date\">2024-03-01</value>"));
+
+        let element = record.to_soap_element();
+        assert!(element.contains("xsi:type=\"met:CustomMetadata\""));
+        assert!(element.contains("<met:fullName>Feature_Flag.Checkout</met:fullName>"));
+        assert!(element.contains("<met:value xsi:type=\"xsd:boolean\">true</met:value>"));
+    }
+
+    #[test]
+    fn test_from_read_record() {
+        let record = CustomMetadataRecord::new("Flag", "Checkout", "Check & Out")
+            .with_value("Enabled__c", true)
+            .with_value("Percent__c", 12.5)
+            .with_value("Notes__c", "x < y")
+            .with_value("Starts_On__c", NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+            .with_null("Owner__c");
+        let xml = record.to_metadata_xml();
+        let read = serde_json::json!({
+            "fullName": "Flag.Checkout",
+            "label": "Check & Out",
+            "protected": "false",
+            "_rawXml": xml,
+        });
+        assert_eq!(CustomMetadataRecord::from_read_record(&read), Some(record));
+        assert!(
+            CustomMetadataRecord::from_read_record(&serde_json::json!({"fullName": "NoDot"}))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_scalar_values_and_bad_names() {
+        let err = CustomMetadataRecord::from_serialize(
+            "Flag",
+            "A",
+            "A",
+            &serde_json::json!({"Tags__c": ["x"]}),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Tags__c"));
+        assert!(CustomMetadataRecord::from_serialize("Flag", "A", "A", &5).is_err());
+
+        assert!(CustomMetadataRecord::new("Flag", "Bad Name", "x")
+            .validate()
+            .is_err());
+        assert!(CustomMetadataRecord::new("Flag", "Ok", "x")
+            .with_value("Enabled", true)
+            .validate()
+            .is_err());
+        assert!(CustomMetadataRecord::new("Flag", "Ok_", "x")
+            .validate()
+            .is_err());
+        CustomMetadataRecord::new("ns__Flag__mdt", "Ok", "x")
+            .with_value("ns__Enabled__c", true)
+            .validate()
+            .unwrap();
+    }
+}
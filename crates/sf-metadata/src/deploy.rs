@@ -1,10 +1,11 @@
 //! Deploy operations.
 
-use crate::types::{ComponentSuccess, TestFailure, TestLevel};
+use crate::types::{CodeCoverageWarning, ComponentSuccess, TestFailure, TestLevel};
 use serde::{Deserialize, Serialize};
 
 /// Options for deployment.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DeployOptions {
     /// Allow references to missing files in the zip.
     pub allow_missing_files: bool,
@@ -16,7 +17,8 @@ pub struct DeployOptions {
     pub ignore_warnings: bool,
     /// Retrieve metadata after deploy.
     pub perform_retrieve: bool,
-    /// Hard delete components (only in sandbox/DE orgs).
+    /// Hard delete components removed by destructive changes instead of
+    /// sending them to the recycle bin (only in sandbox/DE orgs).
     pub purge_on_delete: bool,
     /// Rollback all changes if any component fails.
     pub rollback_on_error: bool,
@@ -78,7 +80,7 @@ impl std::str::FromStr for DeployStatus {
 }
 
 /// Result of a deployment.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeployResult {
     /// Async process ID.
     pub id: String,
@@ -108,6 +110,8 @@ pub struct DeployResult {
     pub component_successes: Vec<ComponentSuccess>,
     /// Test failures.
     pub test_failures: Vec<TestFailure>,
+    /// Code coverage warnings from the tests run.
+    pub code_coverage_warnings: Vec<CodeCoverageWarning>,
     /// State detail message.
     pub state_detail: Option<String>,
 }
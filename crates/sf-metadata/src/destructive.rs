@@ -0,0 +1,132 @@
+//! Destructive changes: components a deploy deletes.
+
+use crate::retrieve::{package_xml, PackageTypeMembers};
+
+/// File name of the components deleted before the package is deployed.
+pub const DESTRUCTIVE_CHANGES_PRE: &str = "destructiveChangesPre.xml";
+
+/// File name of the components deleted after the package is deployed.
+pub const DESTRUCTIVE_CHANGES_POST: &str = "destructiveChangesPost.xml";
+
+/// Components for a deploy to delete, written to `destructiveChangesPre.xml`
+/// and `destructiveChangesPost.xml` in the deploy zip.
+///
+/// Delete before when the package no longer references the component, such
+/// as a field being replaced, and after when the package removes the last
+/// references to it, such as a class that other deployed classes stop
+/// calling.
+///
+/// Deleted components go to the recycle bin unless the deploy sets
+/// [`DeployOptions::purge_on_delete`](crate::DeployOptions::purge_on_delete),
+/// which production orgs don't allow.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_metadata::{DeployOptions, DeployPackage, DestructiveChanges, PackageManifest};
+///
+/// let changes = DestructiveChanges::new()
+///     .delete_after("ApexClass", vec!["LegacyInvoices".to_string()])
+///     .delete_after("CustomField", vec!["Invoice__c.Old_Amount__c".to_string()]);
+///
+/// // Nothing to deploy but the deletions.
+/// let package = DeployPackage::new(&PackageManifest::new("62.0"))
+///     .with_destructive_changes(&changes);
+/// let async_id = client.deploy(&package.to_zip()?, DeployOptions::default()).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DestructiveChanges {
+    pre: Vec<PackageTypeMembers>,
+    post: Vec<PackageTypeMembers>,
+}
+
+impl DestructiveChanges {
+    /// No deletions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delete `members` of a metadata type before the package is deployed.
+    pub fn delete_before(mut self, metadata_type: impl Into<String>, members: Vec<String>) -> Self {
+        add_members(&mut self.pre, metadata_type.into(), members);
+        self
+    }
+
+    /// Delete `members` of a metadata type after the package is deployed.
+    pub fn delete_after(mut self, metadata_type: impl Into<String>, members: Vec<String>) -> Self {
+        add_members(&mut self.post, metadata_type.into(), members);
+        self
+    }
+
+    /// Components deleted before the package is deployed, by type.
+    pub fn pre(&self) -> &[PackageTypeMembers] {
+        &self.pre
+    }
+
+    /// Components deleted after the package is deployed, by type.
+    pub fn post(&self) -> &[PackageTypeMembers] {
+        &self.post
+    }
+
+    /// Whether nothing is deleted.
+    pub fn is_empty(&self) -> bool {
+        self.pre.is_empty() && self.post.is_empty()
+    }
+
+    /// The `destructiveChangesPre.xml` file, if anything is deleted before.
+    pub fn to_pre_xml(&self) -> Option<String> {
+        (!self.pre.is_empty()).then(|| package_xml(&self.pre, None))
+    }
+
+    /// The `destructiveChangesPost.xml` file, if anything is deleted after.
+    pub fn to_post_xml(&self) -> Option<String> {
+        (!self.post.is_empty()).then(|| package_xml(&self.post, None))
+    }
+}
+
+/// Add `members` under `name`, merging with the type's existing members.
+fn add_members(types: &mut Vec<PackageTypeMembers>, name: String, members: Vec<String>) {
+    let index = match types.iter().position(|t| t.name == name) {
+        Some(index) => index,
+        None => {
+            types.push(PackageTypeMembers {
+                name,
+                members: Vec::new(),
+            });
+            types.len() - 1
+        }
+    };
+    for member in members {
+        if !types[index].members.contains(&member) {
+            types[index].members.push(member);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destructive_changes_xml() {
+        let changes = DestructiveChanges::new()
+            .delete_after("ApexClass", vec!["Legacy".to_string()])
+            .delete_after("ApexClass", vec!["Legacy".to_string(), "Old".to_string()])
+            .delete_after("CustomField", vec!["Invoice__c.Amount__c".to_string()]);
+
+        assert!(!changes.is_empty());
+        assert!(changes.to_pre_xml().is_none());
+        assert_eq!(changes.post()[0].members, ["Legacy", "Old"]);
+        assert_eq!(
+            changes.to_post_xml().unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <Package xmlns=\"http://soap.sforce.com/2006/04/metadata\">\n    \
+             <types>\n        <members>Legacy</members>\n        <members>Old</members>\n        \
+             <name>ApexClass</name>\n    </types>\n    \
+             <types>\n        <members>Invoice__c.Amount__c</members>\n        \
+             <name>CustomField</name>\n    </types>\n\
+             </Package>\n"
+        );
+        assert!(DestructiveChanges::new().is_empty());
+    }
+}
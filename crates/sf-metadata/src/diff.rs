@@ -0,0 +1,597 @@
+//! Comparing the metadata in two orgs, or in an org and a local project.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::convert::{read_files, FormatConverter};
+use crate::destructive::DestructiveChanges;
+use crate::error::Result;
+use crate::package::RetrievePackage;
+use crate::registry::{MetadataRegistry, RegistryType, SourceStrategy};
+use crate::retrieve::PackageManifest;
+use crate::source::relative_files;
+use crate::xml_value::parse_tree;
+
+/// A component's files by path.
+type ComponentFiles = BTreeMap<String, Vec<u8>>;
+
+/// A metadata component, by type and full name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ComponentKey {
+    pub metadata_type: String,
+    pub full_name: String,
+}
+
+/// The metadata in an org or project at one point, in Metadata API format,
+/// ready to [`diff`](Self::diff) against another.
+///
+/// Files are grouped into components by the bundled
+/// [`MetadataRegistry`]. Files in directories it doesn't know are kept
+/// under their directory name as the type.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_metadata::MetadataSnapshot;
+///
+/// let org = client.retrieve_snapshot(&manifest, timeout, poll_interval).await?;
+/// let local = MetadataSnapshot::from_source_dir("my-project")?;
+///
+/// // What deploying the project would change in the org.
+/// let diff = org.diff(&local);
+/// for component in &diff.modified {
+///     for change in &component.changes {
+///         println!("{} {}: {:?} -> {:?}", component.component.full_name, change.path, change.before, change.after);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MetadataSnapshot {
+    components: BTreeMap<ComponentKey, ComponentFiles>,
+}
+
+impl MetadataSnapshot {
+    /// Snapshot a retrieve's zip.
+    pub fn from_retrieve(package: &RetrievePackage) -> Result<Self> {
+        Ok(Self::from_files(package.package_entries()?))
+    }
+
+    /// Snapshot a directory in Metadata API format.
+    pub fn from_mdapi_dir(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        Ok(Self::from_files(read_files(path, &relative_files(path)?)?))
+    }
+
+    /// Snapshot a source-format project, found the same way as for
+    /// [`PackageManifest::from_source_dir`].
+    pub fn from_source_dir(path: impl AsRef<Path>) -> Result<Self> {
+        let package = FormatConverter::new().to_deploy_package(path)?;
+        Ok(Self::from_files(package.into_files()))
+    }
+
+    fn from_files(files: BTreeMap<String, Vec<u8>>) -> Self {
+        let registry = MetadataRegistry::default();
+        let mut components: BTreeMap<ComponentKey, ComponentFiles> = BTreeMap::new();
+        for (path, contents) in files {
+            if let Some(key) = component_key(&registry, &path) {
+                components.entry(key).or_default().insert(path, contents);
+            }
+        }
+        Self { components }
+    }
+
+    /// The components in the snapshot, sorted.
+    pub fn components(&self) -> impl Iterator<Item = &ComponentKey> {
+        self.components.keys()
+    }
+
+    /// Number of components in the snapshot.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Whether the snapshot has no components.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// The changes that turn this snapshot into `to`.
+    ///
+    /// XML is compared element by element, so formatting, comments, and
+    /// the order of named list items such as fields don't count as
+    /// changes. Other files are compared as text, ignoring line endings.
+    pub fn diff(&self, to: &MetadataSnapshot) -> MetadataDiff {
+        let registry = MetadataRegistry::default();
+        let mut diff = MetadataDiff::default();
+        for (key, before) in &self.components {
+            match to.components.get(key) {
+                Some(after) => {
+                    let registry_type = registry.by_name(&key.metadata_type);
+                    let changes = component_changes(registry_type, before, after);
+                    if !changes.is_empty() {
+                        diff.modified.push(ComponentDiff {
+                            component: key.clone(),
+                            changes,
+                        });
+                    }
+                }
+                None => diff.removed.push(key.clone()),
+            }
+        }
+        diff.added = to
+            .components
+            .keys()
+            .filter(|key| !self.components.contains_key(*key))
+            .cloned()
+            .collect();
+        diff
+    }
+}
+
+/// The differences between two [`MetadataSnapshot`]s.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetadataDiff {
+    /// Components only in the newer snapshot.
+    pub added: Vec<ComponentKey>,
+    /// Components only in the older snapshot.
+    pub removed: Vec<ComponentKey>,
+    /// Components in both that differ.
+    pub modified: Vec<ComponentDiff>,
+}
+
+impl MetadataDiff {
+    /// Whether the snapshots are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// A manifest of the added and modified components, to deploy them.
+    pub fn to_manifest(&self, version: impl Into<String>) -> PackageManifest {
+        let mut types: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        let changed = self
+            .added
+            .iter()
+            .chain(self.modified.iter().map(|m| &m.component));
+        for key in changed {
+            types
+                .entry(&key.metadata_type)
+                .or_default()
+                .push(key.full_name.clone());
+        }
+        types.into_iter().fold(
+            PackageManifest::new(version),
+            |manifest, (name, mut members)| {
+                members.sort();
+                manifest.add_type(name, members)
+            },
+        )
+    }
+
+    /// The removed components as destructive changes, deleted after the
+    /// rest of the deploy so nothing deployed still refers to them.
+    pub fn to_destructive_changes(&self) -> DestructiveChanges {
+        self.removed
+            .iter()
+            .fold(DestructiveChanges::new(), |changes, key| {
+                changes.delete_after(key.metadata_type.clone(), vec![key.full_name.clone()])
+            })
+    }
+}
+
+/// How a component differs between two snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentDiff {
+    pub component: ComponentKey,
+    pub changes: Vec<ElementChange>,
+}
+
+/// One changed value in a component's file.
+///
+/// For XML, `path` names the element, such as `fields[Amount__c].label`:
+/// list items with a `fullName` or `name` are named by it, and others by
+/// position after the first. An added or removed list item is reported
+/// once, with its name as the value, rather than element by element.
+///
+/// Other files are compared whole, with an empty `path` and the file's
+/// text as the values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElementChange {
+    /// Path of the file in the package, such as `objects/Invoice__c.object`.
+    pub file: String,
+    pub path: String,
+    /// The old value, or `None` if it was added.
+    pub before: Option<String>,
+    /// The new value, or `None` if it was removed.
+    pub after: Option<String>,
+}
+
+/// The component a file in Metadata API format belongs to.
+fn component_key(registry: &MetadataRegistry, path: &str) -> Option<ComponentKey> {
+    if !path.contains('/') {
+        // package.xml and destructive changes.
+        return None;
+    }
+    let (directory, rest) = path.split_once('/')?;
+    let Some(registry_type) = registry.by_directory(directory) else {
+        return Some(ComponentKey {
+            metadata_type: directory.to_string(),
+            full_name: rest.to_string(),
+        });
+    };
+
+    let unmeta = rest.strip_suffix("-meta.xml").unwrap_or(rest);
+    let unsuffixed = |name: &'_ str| -> String {
+        registry_type
+            .suffix
+            .as_ref()
+            .and_then(|suffix| name.strip_suffix(&format!(".{}", suffix)))
+            .unwrap_or(name)
+            .to_string()
+    };
+    let full_name = match &registry_type.strategy {
+        SourceStrategy::Bundle => rest.split('/').next().map(unsuffixed)?,
+        // Folders (`reports/Sales-meta.xml`) are members of the type of
+        // what they hold, like the reports in them (`reports/Sales/Pipeline.report`).
+        _ => unsuffixed(unmeta),
+    };
+    Some(ComponentKey {
+        metadata_type: registry_type.name.clone(),
+        full_name,
+    })
+}
+
+fn component_changes(
+    registry_type: Option<&RegistryType>,
+    before: &ComponentFiles,
+    after: &ComponentFiles,
+) -> Vec<ElementChange> {
+    let files: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    let mut changes = Vec::new();
+    for file in files {
+        let (old, new) = (before.get(file), after.get(file));
+        if old == new {
+            continue;
+        }
+        let xml = is_metadata_xml(registry_type, file, before, after);
+        let flattened = |contents: Option<&Vec<u8>>| -> Option<Option<BTreeMap<String, String>>> {
+            match contents {
+                None => Some(None),
+                Some(contents) => {
+                    let tree = parse_tree(std::str::from_utf8(contents).ok()?).ok()?;
+                    let mut flat = BTreeMap::new();
+                    flatten(&tree, String::new(), &mut flat);
+                    Some(Some(flat))
+                }
+            }
+        };
+        match (xml, flattened(old), flattened(new)) {
+            (true, Some(old), Some(new)) => {
+                changes.extend(element_changes(file, old.as_ref(), new.as_ref()));
+            }
+            _ => {
+                let (old, new) = (old.map(|c| text(c)), new.map(|c| text(c)));
+                if old != new {
+                    changes.push(ElementChange {
+                        file: file.clone(),
+                        path: String::new(),
+                        before: old,
+                        after: new,
+                    });
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// Whether a component's file is its metadata XML: a `-meta.xml` or other
+/// `.xml` file, or a file named with the type's suffix and no `-meta.xml`
+/// beside it.
+fn is_metadata_xml(
+    registry_type: Option<&RegistryType>,
+    file: &str,
+    before: &ComponentFiles,
+    after: &ComponentFiles,
+) -> bool {
+    if file.ends_with(".xml") {
+        return true;
+    }
+    let meta = format!("{}-meta.xml", file);
+    let suffixed = registry_type
+        .and_then(|t| t.suffix.as_ref())
+        .is_some_and(|suffix| file.ends_with(&format!(".{}", suffix)));
+    suffixed && !before.contains_key(&meta) && !after.contains_key(&meta)
+}
+
+/// A file as text, with line endings and trailing whitespace normalized.
+fn text(contents: &[u8]) -> String {
+    match std::str::from_utf8(contents) {
+        Ok(text) => text.replace("\r\n", "\n").trim_end().to_string(),
+        Err(_) => format!("<{} bytes>", contents.len()),
+    }
+}
+
+/// Record every value in an XML tree by element path.
+fn flatten(value: &Value, path: String, flat: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(children) => {
+            for (name, child) in children {
+                let child_path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                let items = match child {
+                    Value::Array(items) => items.iter().collect(),
+                    item => vec![item],
+                };
+                for (index, item) in items.into_iter().enumerate() {
+                    match item_name(item) {
+                        Some((key_element, key)) => {
+                            let item_path = format!("{}[{}]", child_path, key);
+                            flat.insert(item_path.clone(), key.to_string());
+                            if let Value::Object(fields) = item {
+                                for (field, value) in fields {
+                                    if field != key_element {
+                                        let mut single = serde_json::Map::new();
+                                        single.insert(field.clone(), value.clone());
+                                        flatten(&Value::Object(single), item_path.clone(), flat);
+                                    }
+                                }
+                            }
+                        }
+                        None if index == 0 => flatten(item, child_path.clone(), flat),
+                        None => flatten(item, format!("{}[{}]", child_path, index), flat),
+                    }
+                }
+            }
+        }
+        Value::String(s) => {
+            flat.insert(path, s.trim().to_string());
+        }
+        _ => {}
+    }
+}
+
+/// The element naming a list item, and its value.
+fn item_name(item: &Value) -> Option<(&'static str, &str)> {
+    ["fullName", "name"]
+        .into_iter()
+        .find_map(|element| match item.get(element) {
+            Some(Value::String(name)) if !name.is_empty() => Some((element, name.as_str())),
+            _ => None,
+        })
+}
+
+/// The differences between two flattened files, leaving out the contents
+/// of list items that were added or removed whole.
+fn element_changes(
+    file: &str,
+    before: Option<&BTreeMap<String, String>>,
+    after: Option<&BTreeMap<String, String>>,
+) -> Vec<ElementChange> {
+    let empty = BTreeMap::new();
+    let (before, after) = (before.unwrap_or(&empty), after.unwrap_or(&empty));
+    let paths: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+
+    let mut changes: Vec<ElementChange> = Vec::new();
+    let mut whole_items: Vec<&str> = Vec::new();
+    for path in paths {
+        let (old, new) = (before.get(path), after.get(path));
+        if old == new {
+            continue;
+        }
+        let within_whole_item = whole_items.iter().any(|item| {
+            path.strip_prefix(*item)
+                .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+        });
+        if within_whole_item {
+            continue;
+        }
+        if path.ends_with(']') && (old.is_none() || new.is_none()) {
+            whole_items.push(path);
+        }
+        changes.push(ElementChange {
+            file: file.to_string(),
+            path: path.clone(),
+            before: old.cloned(),
+            after: new.cloned(),
+        });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::DeployPackage;
+
+    const OBJECT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<CustomObject xmlns="http://soap.sforce.com/2006/04/metadata">
+    <fields>
+        <fullName>Amount__c</fullName>
+        <label>Amount</label>
+        <type>Currency</type>
+    </fields>
+    <fields>
+        <fullName>Due__c</fullName>
+        <label>Due</label>
+        <type>Date</type>
+    </fields>
+    <label>Invoice</label>
+    <sharingModel>ReadWrite</sharingModel>
+</CustomObject>
+"#;
+
+    fn snapshot(files: &[(&str, &str)]) -> MetadataSnapshot {
+        let package = files
+            .iter()
+            .fold(DeployPackage::default(), |package, (path, contents)| {
+                package.with_file(format!("unpackaged/{}", path), *contents)
+            })
+            .with_file("unpackaged/package.xml", "<Package/>");
+        MetadataSnapshot::from_retrieve(
+            &RetrievePackage::from_zip(package.to_zip().unwrap()).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_groups_files_into_components() {
+        let snapshot = snapshot(&[
+            ("classes/Invoices.cls", "public class Invoices {}"),
+            ("classes/Invoices.cls-meta.xml", "<ApexClass/>"),
+            ("reports/Sales-meta.xml", "<ReportFolder/>"),
+            ("reports/Sales/Pipeline.report", "<Report/>"),
+            ("lwc/invoiceList/invoiceList.js", ""),
+            (
+                "lwc/invoiceList/invoiceList.js-meta.xml",
+                "<LightningComponentBundle/>",
+            ),
+            ("widgets/Thing.widget", "<Widget/>"),
+        ]);
+        let components: Vec<String> = snapshot
+            .components()
+            .map(|c| format!("{}:{}", c.metadata_type, c.full_name))
+            .collect();
+        assert_eq!(
+            components,
+            [
+                "ApexClass:Invoices",
+                "LightningComponentBundle:invoiceList",
+                "Report:Sales",
+                "Report:Sales/Pipeline",
+                "widgets:Thing.widget",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_reports_element_changes() {
+        let reordered = OBJECT
+            .replace(
+                "        <label>Amount</label>",
+                "        <label>Total</label>\n        <!-- renamed -->",
+            )
+            .replace("<type>Date</type>", "<type>DateTime</type>")
+            .replace(
+                "    <label>Invoice</label>",
+                "    <fields><fullName>Paid__c</fullName><label>Paid</label></fields>\n    <label>Invoice</label>",
+            );
+        let before = snapshot(&[
+            ("objects/Invoice__c.object", OBJECT),
+            ("classes/Invoices.cls", "public class Invoices {}\r\n"),
+            (
+                "classes/Invoices.cls-meta.xml",
+                "<ApexClass><status>Active</status></ApexClass>",
+            ),
+            ("classes/Legacy.cls", "public class Legacy {}"),
+            ("classes/Legacy.cls-meta.xml", "<ApexClass/>"),
+        ]);
+        let after = snapshot(&[
+            ("objects/Invoice__c.object", &reordered),
+            ("classes/Invoices.cls", "public class Invoices {}\n"),
+            (
+                "classes/Invoices.cls-meta.xml",
+                "<ApexClass>\n  <status>Active</status>\n</ApexClass>",
+            ),
+            ("layouts/Invoice__c-Layout.layout", "<Layout/>"),
+        ]);
+
+        let diff = before.diff(&after);
+        let key = |t: &str, n: &str| ComponentKey {
+            metadata_type: t.to_string(),
+            full_name: n.to_string(),
+        };
+        assert_eq!(diff.added, [key("Layout", "Invoice__c-Layout")]);
+        assert_eq!(diff.removed, [key("ApexClass", "Legacy")]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(
+            diff.modified[0].component,
+            key("CustomObject", "Invoice__c")
+        );
+
+        let change = |path: &str, before: Option<&str>, after: Option<&str>| ElementChange {
+            file: "objects/Invoice__c.object".to_string(),
+            path: path.to_string(),
+            before: before.map(str::to_string),
+            after: after.map(str::to_string),
+        };
+        assert_eq!(
+            diff.modified[0].changes,
+            [
+                change("fields[Amount__c].label", Some("Amount"), Some("Total")),
+                change("fields[Due__c].type", Some("Date"), Some("DateTime")),
+                change("fields[Paid__c]", None, Some("Paid__c")),
+            ]
+        );
+
+        let manifest = diff.to_manifest("62.0").to_package_xml();
+        assert!(
+            manifest.contains("<members>Invoice__c</members>\n        <name>CustomObject</name>")
+        );
+        assert!(manifest.contains("<members>Invoice__c-Layout</members>"));
+        let destructive = diff.to_destructive_changes();
+        assert_eq!(destructive.post()[0].members, ["Legacy"]);
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_diff_compares_non_xml_files_as_text() {
+        let before = snapshot(&[
+            ("classes/Invoices.cls", "public class Invoices {}"),
+            ("classes/Invoices.cls-meta.xml", "<ApexClass/>"),
+        ]);
+        let after = snapshot(&[
+            ("classes/Invoices.cls", "public class Invoices { }"),
+            ("classes/Invoices.cls-meta.xml", "<ApexClass/>"),
+        ]);
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.modified[0].changes,
+            [ElementChange {
+                file: "classes/Invoices.cls".to_string(),
+                path: String::new(),
+                before: Some("public class Invoices {}".to_string()),
+                after: Some("public class Invoices { }".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_source_dir_matches_retrieve() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, contents: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        };
+        write("classes/Invoices.cls", "public class Invoices {}");
+        write("classes/Invoices.cls-meta.xml", "<ApexClass/>");
+        write(
+            "objects/Invoice__c/Invoice__c.object-meta.xml",
+            "<CustomObject><label>Invoice</label></CustomObject>",
+        );
+        write(
+            "objects/Invoice__c/fields/Amount__c.field-meta.xml",
+            "<CustomField><fullName>Amount__c</fullName><label>Amount</label></CustomField>",
+        );
+
+        let local = MetadataSnapshot::from_source_dir(root).unwrap();
+        let org = snapshot(&[
+            ("classes/Invoices.cls", "public class Invoices {}"),
+            ("classes/Invoices.cls-meta.xml", "<ApexClass/>"),
+            (
+                "objects/Invoice__c.object",
+                "<CustomObject><fields><fullName>Amount__c</fullName><label>Amount</label></fields><label>Invoice</label></CustomObject>",
+            ),
+        ]);
+        assert_eq!(local.len(), 2);
+        assert!(org.diff(&local).is_empty(), "{:?}", org.diff(&local));
+    }
+}
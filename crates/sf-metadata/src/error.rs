@@ -1,7 +1,7 @@
 //! Error types for sf-metadata.
 
 use crate::deploy::DeployResult;
-use crate::redact::redact_session_ids;
+use busbar_sf_client::trace::redact_session_ids;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -66,6 +66,8 @@ pub enum ErrorKind {
     Timeout,
     #[error("IO error: {0}")]
     Io(String),
+    #[error("Deploy history error: {0}")]
+    History(String),
     #[error("{0}")]
     Other(String),
 }
@@ -75,7 +77,8 @@ pub enum ErrorKind {
 /// component failures (each with its component type/name/problem), not just
 /// whatever Salesforce put in the top-level `errorMessage` (which is often
 /// empty when the real errors are per-component). Session ids/tokens are
-/// redacted defensively (see [`crate::redact`]) — Salesforce-generated
+/// redacted defensively (see
+/// [`busbar_sf_client::trace::redact_session_ids`]) — Salesforce-generated
 /// problem text shouldn't contain one, but a malformed/faulted response
 /// occasionally echoes request content back.
 fn fmt_deployment_failed(result: &DeployResult) -> String {
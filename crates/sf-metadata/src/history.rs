@@ -0,0 +1,386 @@
+//! Deploy history: a lasting record of what was deployed, when, by whom,
+//! and how it went.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::deploy::{DeployOptions, DeployResult};
+use crate::error::{Error, ErrorKind, Result};
+use crate::package::DeployPackage;
+
+/// One deploy, recorded when it starts and again when it finishes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployRecord {
+    /// The deploy's async process ID.
+    pub deploy_id: String,
+    /// SHA-256 of the package's `package.xml`, hex encoded, to tell which
+    /// deploys shipped the same components.
+    pub manifest_hash: String,
+    pub options: DeployOptions,
+    /// Who or what ran the deploy, such as a user or a plugin's name.
+    pub deployed_by: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// The final result, test results included. `None` until the deploy
+    /// finishes, and for deploys whose status was never checked to the end.
+    pub result: Option<DeployResult>,
+}
+
+impl DeployRecord {
+    /// A deploy of `package` started just now.
+    pub fn new(
+        deploy_id: impl Into<String>,
+        package: &DeployPackage,
+        options: DeployOptions,
+    ) -> Self {
+        Self {
+            deploy_id: deploy_id.into(),
+            manifest_hash: manifest_hash(package),
+            options,
+            deployed_by: None,
+            started_at: Utc::now(),
+            finished_at: None,
+            result: None,
+        }
+    }
+
+    /// Set who or what ran the deploy.
+    pub fn deployed_by(mut self, deployed_by: impl Into<String>) -> Self {
+        self.deployed_by = Some(deployed_by.into());
+        self
+    }
+
+    /// Record the deploy's final result.
+    pub fn finish(&mut self, result: DeployResult) {
+        self.finished_at = Some(Utc::now());
+        self.result = Some(result);
+    }
+}
+
+/// SHA-256 of a package's `package.xml`, hex encoded.
+pub fn manifest_hash(package: &DeployPackage) -> String {
+    Sha256::digest(package.file("package.xml").unwrap_or_default())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Somewhere to keep [`DeployRecord`]s.
+///
+/// [`MetadataClient::deploy_and_record`](crate::MetadataClient::deploy_and_record)
+/// records a deploy as it starts and again when it finishes, so a deploy
+/// interrupted partway still shows up, without a result.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_metadata::{DeployHistory, FileDeployHistory};
+///
+/// let history = FileDeployHistory::new(".busbar/deploys.jsonl");
+/// for record in history.since(last_release)? {
+///     let tests = record.result.as_ref().map(|r| (r.number_tests_completed, r.number_tests_errors));
+///     println!("{} {} by {:?}: {:?}", record.started_at, record.deploy_id, record.deployed_by, tests);
+/// }
+/// ```
+pub trait DeployHistory: Send + Sync {
+    /// Save `record`, replacing any earlier record of the same deploy.
+    fn record(&self, record: &DeployRecord) -> Result<()>;
+
+    /// Every deploy, oldest first.
+    fn records(&self) -> Result<Vec<DeployRecord>>;
+
+    /// The deploy with `deploy_id`, if it was recorded.
+    fn get(&self, deploy_id: &str) -> Result<Option<DeployRecord>> {
+        Ok(self
+            .records()?
+            .into_iter()
+            .find(|record| record.deploy_id == deploy_id))
+    }
+
+    /// Deploys started at or after `since`, oldest first.
+    fn since(&self, since: DateTime<Utc>) -> Result<Vec<DeployRecord>> {
+        Ok(self
+            .records()?
+            .into_iter()
+            .filter(|record| record.started_at >= since)
+            .collect())
+    }
+}
+
+/// Deploy history in a JSON Lines file, appended to for each record.
+///
+/// A deploy's later lines replace its earlier ones when the file is read.
+/// The file and its directory are created on the first record.
+#[derive(Debug)]
+pub struct FileDeployHistory {
+    path: PathBuf,
+    /// Keeps appends from this process whole.
+    lock: Mutex<()>,
+}
+
+impl FileDeployHistory {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// The file records are kept in.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl DeployHistory for FileDeployHistory {
+    fn record(&self, record: &DeployRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record).map_err(history_error)?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    fn records(&self) -> Result<Vec<DeployRecord>> {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut records: Vec<DeployRecord> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: DeployRecord = serde_json::from_str(&line).map_err(history_error)?;
+            match positions.get(&record.deploy_id) {
+                Some(&position) => records[position] = record,
+                None => {
+                    positions.insert(record.deploy_id.clone(), records.len());
+                    records.push(record);
+                }
+            }
+        }
+        records.sort_by_key(|record| record.started_at);
+        Ok(records)
+    }
+}
+
+/// Deploy history in a SQLite database, one row per deploy.
+///
+/// Besides the whole record as JSON, each row has the deploy's status and
+/// test counts in columns of their own, for reports written in SQL:
+///
+/// ```sql
+/// SELECT started_at, deploy_id, deployed_by, status, tests_errors
+/// FROM deploys WHERE status = 'Failed' ORDER BY started_at;
+/// ```
+#[cfg(feature = "history-sqlite")]
+#[derive(Debug)]
+pub struct SqliteDeployHistory {
+    connection: Mutex<rusqlite::Connection>,
+}
+
+#[cfg(feature = "history-sqlite")]
+impl SqliteDeployHistory {
+    /// Open the database at `path`, creating it and the `deploys` table
+    /// if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = rusqlite::Connection::open(path).map_err(history_error)?;
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS deploys (
+                    deploy_id TEXT PRIMARY KEY,
+                    manifest_hash TEXT NOT NULL,
+                    deployed_by TEXT,
+                    started_at TEXT NOT NULL,
+                    finished_at TEXT,
+                    status TEXT,
+                    success INTEGER,
+                    tests_completed INTEGER,
+                    tests_errors INTEGER,
+                    record TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS deploys_started_at ON deploys (started_at);",
+            )
+            .map_err(history_error)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Vec<DeployRecord>> {
+        let connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut statement = connection.prepare(sql).map_err(history_error)?;
+        let rows = statement
+            .query_map(params, |row| row.get::<_, String>(0))
+            .map_err(history_error)?;
+        rows.map(|json| serde_json::from_str(&json.map_err(history_error)?).map_err(history_error))
+            .collect()
+    }
+}
+
+/// Timestamps as stored: RFC 3339 in UTC with a fixed precision, so they
+/// sort as text.
+#[cfg(feature = "history-sqlite")]
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
+#[cfg(feature = "history-sqlite")]
+impl DeployHistory for SqliteDeployHistory {
+    fn record(&self, record: &DeployRecord) -> Result<()> {
+        let json = serde_json::to_string(record).map_err(history_error)?;
+        let result = record.result.as_ref();
+        let connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        connection
+            .execute(
+                "INSERT OR REPLACE INTO deploys (deploy_id, manifest_hash, deployed_by, started_at,
+                    finished_at, status, success, tests_completed, tests_errors, record)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                rusqlite::params![
+                    record.deploy_id,
+                    record.manifest_hash,
+                    record.deployed_by,
+                    timestamp(record.started_at),
+                    record.finished_at.map(timestamp),
+                    result.map(|r| format!("{:?}", r.status)),
+                    result.map(|r| r.success),
+                    result.map(|r| r.number_tests_completed),
+                    result.map(|r| r.number_tests_errors),
+                    json,
+                ],
+            )
+            .map_err(history_error)?;
+        Ok(())
+    }
+
+    fn records(&self) -> Result<Vec<DeployRecord>> {
+        self.query("SELECT record FROM deploys ORDER BY started_at, rowid", [])
+    }
+
+    fn get(&self, deploy_id: &str) -> Result<Option<DeployRecord>> {
+        Ok(self
+            .query(
+                "SELECT record FROM deploys WHERE deploy_id = ?1",
+                [deploy_id],
+            )?
+            .pop())
+    }
+
+    fn since(&self, since: DateTime<Utc>) -> Result<Vec<DeployRecord>> {
+        self.query(
+            "SELECT record FROM deploys WHERE started_at >= ?1 ORDER BY started_at, rowid",
+            [timestamp(since)],
+        )
+    }
+}
+
+fn history_error(e: impl std::error::Error + Send + Sync + 'static) -> Error {
+    Error::with_source(ErrorKind::History(e.to_string()), e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deploy::DeployStatus;
+    use crate::retrieve::PackageManifest;
+
+    fn result(id: &str, status: DeployStatus, tests_errors: u32) -> DeployResult {
+        DeployResult {
+            id: id.to_string(),
+            done: true,
+            status,
+            success: status == DeployStatus::Succeeded,
+            error_message: None,
+            number_components_deployed: 2,
+            number_components_errors: 0,
+            number_components_total: 2,
+            number_tests_completed: 10,
+            number_tests_errors: tests_errors,
+            number_tests_total: 10,
+            component_failures: Vec::new(),
+            component_successes: Vec::new(),
+            test_failures: Vec::new(),
+            code_coverage_warnings: Vec::new(),
+            state_detail: None,
+        }
+    }
+
+    fn exercise(history: &dyn DeployHistory) {
+        let package = DeployPackage::new(
+            &PackageManifest::new("62.0").add_type("ApexClass", vec!["Invoices".to_string()]),
+        );
+        assert!(history.records().unwrap().is_empty());
+
+        let mut first = DeployRecord::new("0Af1", &package, DeployOptions::default())
+            .deployed_by("release-plugin");
+        history.record(&first).unwrap();
+        let mut second = DeployRecord::new("0Af2", &package, DeployOptions::default());
+        second.started_at = first.started_at + chrono::Duration::seconds(1);
+        history.record(&second).unwrap();
+        first.finish(result("0Af1", DeployStatus::Failed, 3));
+        history.record(&first).unwrap();
+
+        let records = history.records().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].deploy_id, "0Af1");
+        assert_eq!(records[0].deployed_by.as_deref(), Some("release-plugin"));
+        assert_eq!(records[0].manifest_hash, records[1].manifest_hash);
+        assert_eq!(records[0].manifest_hash.len(), 64);
+        let finished = records[0].result.as_ref().unwrap();
+        assert_eq!(finished.status, DeployStatus::Failed);
+        assert_eq!(finished.number_tests_errors, 3);
+        assert!(records[1].result.is_none());
+
+        assert!(history.get("0Af2").unwrap().is_some());
+        assert!(history.get("0Af9").unwrap().is_none());
+        assert_eq!(history.since(second.started_at).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_file_deploy_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = FileDeployHistory::new(dir.path().join("history/deploys.jsonl"));
+        exercise(&history);
+        assert_eq!(
+            std::fs::read_to_string(history.path())
+                .unwrap()
+                .lines()
+                .count(),
+            3
+        );
+    }
+
+    #[cfg(feature = "history-sqlite")]
+    #[test]
+    fn test_sqlite_deploy_history() {
+        let dir = tempfile::tempdir().unwrap();
+        exercise(&SqliteDeployHistory::open(dir.path().join("deploys.db")).unwrap());
+    }
+}
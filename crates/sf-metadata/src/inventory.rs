@@ -0,0 +1,263 @@
+//! Cached `listMetadata` results with change detection between scans.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::list::MetadataComponent;
+
+/// The org's metadata components as of the last scan of each type.
+///
+/// Populate it with [`MetadataClient::refresh_inventory`](crate::MetadataClient::refresh_inventory).
+/// The inventory is serializable, so it can be saved between runs and a
+/// later scan will report only what changed since.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut inventory: OrgInventory = load_saved_inventory().unwrap_or_default();
+/// let changes = client
+///     .refresh_inventory(&mut inventory, &["ApexClass", "CustomObject", "Flow"])
+///     .await?;
+/// for component in &changes.modified {
+///     println!("changed: {} {}", component.metadata_type, component.full_name);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrgInventory {
+    types: BTreeMap<String, TypeSnapshot>,
+}
+
+/// One type's components as of a scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TypeSnapshot {
+    listed_at: DateTime<Utc>,
+    components: Vec<MetadataComponent>,
+}
+
+/// Components that differ between two scans of a type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InventoryChanges {
+    /// Components not present in the previous scan.
+    pub added: Vec<MetadataComponent>,
+    /// Components whose `last_modified_date` changed.
+    pub modified: Vec<MetadataComponent>,
+    /// Components present in the previous scan but not this one.
+    pub removed: Vec<MetadataComponent>,
+}
+
+impl InventoryChanges {
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+
+    fn extend(&mut self, other: InventoryChanges) {
+        self.added.extend(other.added);
+        self.modified.extend(other.modified);
+        self.removed.extend(other.removed);
+    }
+}
+
+impl OrgInventory {
+    /// An empty inventory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Metadata types that have been scanned.
+    pub fn types(&self) -> impl Iterator<Item = &str> {
+        self.types.keys().map(String::as_str)
+    }
+
+    /// Components of `metadata_type` from its last scan; empty if never scanned.
+    pub fn components(&self, metadata_type: &str) -> &[MetadataComponent] {
+        self.types
+            .get(metadata_type)
+            .map_or(&[], |snapshot| &snapshot.components)
+    }
+
+    /// When `metadata_type` was last scanned.
+    pub fn listed_at(&self, metadata_type: &str) -> Option<DateTime<Utc>> {
+        self.types.get(metadata_type).map(|s| s.listed_at)
+    }
+
+    /// Components of any scanned type last modified after `since`.
+    ///
+    /// Components without a parseable `last_modified_date` are skipped.
+    pub fn delta_since(&self, since: DateTime<Utc>) -> Vec<&MetadataComponent> {
+        self.types
+            .values()
+            .flat_map(|snapshot| &snapshot.components)
+            .filter(|component| last_modified(component).is_some_and(|date| date > since))
+            .collect()
+    }
+
+    /// Replace the cached components of `metadata_type` with a new scan and
+    /// return how they differ from the previous one.
+    ///
+    /// The first scan of a type reports every component as added.
+    pub fn record(
+        &mut self,
+        metadata_type: &str,
+        components: Vec<MetadataComponent>,
+        listed_at: DateTime<Utc>,
+    ) -> InventoryChanges {
+        let mut previous: HashMap<String, MetadataComponent> = self
+            .types
+            .remove(metadata_type)
+            .map(|snapshot| snapshot.components)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| (c.full_name.clone(), c))
+            .collect();
+
+        let mut changes = InventoryChanges::default();
+        for component in &components {
+            match previous.remove(&component.full_name) {
+                None => changes.added.push(component.clone()),
+                Some(old) if old.last_modified_date != component.last_modified_date => {
+                    changes.modified.push(component.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        changes.removed = previous.into_values().collect();
+        changes
+            .removed
+            .sort_by(|a, b| a.full_name.cmp(&b.full_name));
+
+        self.types.insert(
+            metadata_type.to_string(),
+            TypeSnapshot {
+                listed_at,
+                components,
+            },
+        );
+        changes
+    }
+
+    /// Record several types' scans, merging their changes.
+    pub(crate) fn record_all(
+        &mut self,
+        scans: Vec<(String, Vec<MetadataComponent>)>,
+        listed_at: DateTime<Utc>,
+    ) -> InventoryChanges {
+        let mut changes = InventoryChanges::default();
+        for (metadata_type, components) in scans {
+            changes.extend(self.record(&metadata_type, components, listed_at));
+        }
+        changes
+    }
+}
+
+fn last_modified(component: &MetadataComponent) -> Option<DateTime<Utc>> {
+    let date = component.last_modified_date.as_deref()?;
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(name: &str, modified: &str) -> MetadataComponent {
+        MetadataComponent {
+            full_name: name.to_string(),
+            file_name: Some(format!("classes/{name}.cls")),
+            id: None,
+            namespace_prefix: None,
+            metadata_type: "ApexClass".to_string(),
+            created_by_id: None,
+            created_by_name: None,
+            created_date: None,
+            last_modified_by_id: None,
+            last_modified_by_name: None,
+            last_modified_date: Some(modified.to_string()),
+            manageable_state: None,
+        }
+    }
+
+    fn at(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_record_reports_changes() {
+        let mut inventory = OrgInventory::new();
+        let first = inventory.record(
+            "ApexClass",
+            vec![
+                component("A", "2024-01-01T00:00:00.000Z"),
+                component("B", "2024-01-01T00:00:00.000Z"),
+            ],
+            at("2024-01-02T00:00:00Z"),
+        );
+        assert_eq!(first.added.len(), 2);
+
+        let second = inventory.record(
+            "ApexClass",
+            vec![
+                component("A", "2024-01-01T00:00:00.000Z"),
+                component("B", "2024-02-01T00:00:00.000Z"),
+                component("C", "2024-02-01T00:00:00.000Z"),
+            ],
+            at("2024-02-02T00:00:00Z"),
+        );
+        assert_eq!(second.added[0].full_name, "C");
+        assert_eq!(second.modified[0].full_name, "B");
+        assert!(second.removed.is_empty());
+
+        let third = inventory.record(
+            "ApexClass",
+            vec![component("C", "2024-02-01T00:00:00.000Z")],
+            at("2024-03-01T00:00:00Z"),
+        );
+        let removed: Vec<_> = third.removed.iter().map(|c| c.full_name.as_str()).collect();
+        assert_eq!(removed, ["A", "B"]);
+        assert_eq!(
+            inventory.listed_at("ApexClass"),
+            Some(at("2024-03-01T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_delta_since() {
+        let mut inventory = OrgInventory::new();
+        inventory.record(
+            "ApexClass",
+            vec![
+                component("Old", "2024-01-01T00:00:00.000Z"),
+                component("New", "2024-03-01T00:00:00.000Z"),
+                component("Unknown", "not a date"),
+            ],
+            at("2024-03-02T00:00:00Z"),
+        );
+        let delta = inventory.delta_since(at("2024-02-01T00:00:00Z"));
+        assert_eq!(delta.len(), 1);
+        assert_eq!(delta[0].full_name, "New");
+        assert!(inventory.components("Flow").is_empty());
+    }
+
+    #[test]
+    fn test_inventory_round_trips_through_json() {
+        let mut inventory = OrgInventory::new();
+        inventory.record(
+            "ApexClass",
+            vec![component("A", "2024-01-01T00:00:00.000Z")],
+            at("2024-01-02T00:00:00Z"),
+        );
+        let json = serde_json::to_string(&inventory).unwrap();
+        let mut restored: OrgInventory = serde_json::from_str(&json).unwrap();
+        let changes = restored.record(
+            "ApexClass",
+            vec![component("A", "2024-01-01T00:00:00.000Z")],
+            at("2024-01-03T00:00:00Z"),
+        );
+        assert!(changes.is_empty());
+    }
+}
@@ -6,10 +6,21 @@
 //!
 //! - **Deploy** - Deploy metadata packages via SOAP API
 //! - **Retrieve** - Retrieve metadata from an org
-//! - **List Metadata** - List metadata components by type
+//! - **List Metadata** - List metadata components by type, in batches, folder by folder, or the whole org
+//! - **CRUD Metadata** - Create, read, update, upsert, delete, and rename components
+//!   synchronously, without a file-based deploy
+//! - **Source Projects** - Build a package.xml from an SFDX source-format project
+//! - **Format Conversion** - Convert between SFDX source format and Metadata API format
+//! - **Destructive Changes** - Delete components before or after a deploy
+//! - **Metadata Diff** - Compare two orgs, or an org and a local project, element by element
+//! - **Packaging** - Zip a directory for deploy and extract retrieved zips to disk
+//! - **Org Inventory** - Cache listings and report components changed since the last scan
+//! - **Custom Metadata Records** - Upsert custom metadata type records from Rust structs
 //! - **Describe Metadata** - Get available metadata types
-//! - **Status Polling** - Automatic polling for async operations
-//! - **Typed Operations** (optional) - Type-safe deploy/retrieve with `busbar-sf-types`
+//! - **Status Polling** - Automatic polling for async operations, with deploy progress
+//!   through a callback or a stream
+//! - **Deploy History** (optional) - Record every deploy and its result in a file or SQLite
+//! - **Typed Operations** (optional) - Type-safe deploy, retrieve, and CRUD with `busbar-sf-types`
 //!
 //! ## Optional Features
 //!
@@ -26,7 +37,7 @@
 //! This enables the `TypedMetadataExt` trait for type-safe operations:
 //!
 //! ```rust,ignore
-//! use busbar_sf_metadata::{MetadataClient, TypedMetadataExt, DeployOptions};
+//! use busbar_sf_metadata::{MetadataClient, TypedComponent, TypedMetadataExt, DeployOptions};
 //! use busbar_sf_types::metadata::objects::CustomObject;
 //!
 //! let obj = CustomObject {
@@ -36,6 +47,36 @@
 //! };
 //!
 //! let async_id = client.deploy_typed(&obj, DeployOptions::default()).await?;
+//!
+//! // Components of different types can go in one package
+//! let components: [&dyn TypedComponent; 2] = [&obj, &layout];
+//! let async_id = client.deploy_typed_bundle(&components, DeployOptions::default()).await?;
+//!
+//! // Or save it directly through the CRUD calls
+//! let results = client.upsert_typed(&[obj]).await?;
+//!
+//! // Retrieve components back into typed structs, keyed by full name
+//! let objects = client.retrieve_typed::<CustomObject>(&["MyObject__c"]).await?;
+//! ```
+//!
+//! ### `history` / `history-sqlite` - Deploy History
+//!
+//! `history` adds the `DeployHistory` trait and `FileDeployHistory`, which
+//! keeps records in a JSON Lines file; `history-sqlite` adds
+//! `SqliteDeployHistory`. `MetadataClient::deploy_and_record` deploys and
+//! records each deploy's ID, manifest hash, options, and final result:
+//!
+//! ```rust,ignore
+//! use busbar_sf_metadata::{DeployHistory, FileDeployHistory};
+//!
+//! let history = FileDeployHistory::new(".busbar/deploys.jsonl");
+//! client
+//!     .deploy_and_record(&package, options, &history, Some("release"), timeout, poll_interval)
+//!     .await?;
+//!
+//! for record in history.records()? {
+//!     println!("{} {} {:?}", record.started_at, record.deploy_id, record.result.map(|r| r.status));
+//! }
 //! ```
 //!
 //! ## Example
@@ -80,35 +121,57 @@
 //! ```
 
 mod client;
+mod convert;
+mod custom_metadata;
 mod deploy;
 mod describe;
+mod destructive;
+mod diff;
 mod error;
+#[cfg(feature = "history")]
+mod history;
+mod inventory;
 mod list;
-mod redact;
+mod package;
+mod registry;
 mod retrieve;
+mod source;
 mod types;
 
 #[cfg(feature = "typed")]
 mod typed;
+mod xml_value;
 
 pub use client::MetadataClient;
+pub use convert::FormatConverter;
+pub use custom_metadata::{CustomMetadataRecord, CustomMetadataValue};
 pub use deploy::{CancelDeployResult, ComponentFailure, DeployOptions, DeployResult, DeployStatus};
 pub use describe::{
     DescribeMetadataResult, DescribeValueTypeResult, MetadataType, PicklistEntry, ValueTypeField,
 };
+pub use destructive::{DestructiveChanges, DESTRUCTIVE_CHANGES_POST, DESTRUCTIVE_CHANGES_PRE};
+pub use diff::{ComponentDiff, ComponentKey, ElementChange, MetadataDiff, MetadataSnapshot};
 pub use error::{Error, ErrorKind, Result};
+pub use inventory::{InventoryChanges, OrgInventory};
 pub use list::MetadataComponent;
+pub use package::{DeployPackage, RetrievePackage};
+pub use registry::{ChildType, MetadataRegistry, RegistryType, SourceStrategy};
 pub use retrieve::{
     PackageManifest, PackageTypeMembers, RetrieveMessage, RetrieveOptions, RetrieveResult,
     RetrieveStatus,
 };
 pub use types::{
-    ComponentSuccess, DeleteResult, FileProperties, MetadataError, ReadResult, SaveResult,
-    SoapFault, TestFailure, TestLevel, UpsertResult, DEFAULT_API_VERSION,
+    CodeCoverageWarning, ComponentSuccess, DeleteResult, FileProperties, MetadataError, ReadResult,
+    SaveResult, SoapFault, TestFailure, TestLevel, UpsertResult, DEFAULT_API_VERSION,
 };
 
+#[cfg(feature = "history-sqlite")]
+pub use history::SqliteDeployHistory;
+#[cfg(feature = "history")]
+pub use history::{manifest_hash, DeployHistory, DeployRecord, FileDeployHistory};
+
 #[cfg(feature = "typed")]
-pub use typed::TypedMetadataExt;
+pub use typed::{TypedComponent, TypedMetadataExt};
 
 #[cfg(feature = "typed")]
 pub use busbar_sf_types::traits::MetadataType as TypedMetadata;
@@ -0,0 +1,377 @@
+//! Deploy zips built from disk, and retrieve zips extracted to it.
+
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose, Engine as _};
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::destructive::{DestructiveChanges, DESTRUCTIVE_CHANGES_POST, DESTRUCTIVE_CHANGES_PRE};
+use crate::error::{Error, ErrorKind, Result};
+use crate::registry::MetadataRegistry;
+use crate::retrieve::{PackageManifest, RetrieveResult};
+use crate::source::{manifest_from_components, mdapi_components};
+use crate::types::DEFAULT_API_VERSION;
+
+/// A deploy package: `package.xml` and the component files, zipped on
+/// demand for [`MetadataClient::deploy`](crate::MetadataClient::deploy).
+///
+/// Files sit at the root of the zip, so deploy it with
+/// [`DeployOptions::single_package`](crate::DeployOptions::single_package)
+/// set, as it is by default.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_metadata::{DeployOptions, DeployPackage};
+///
+/// let package = DeployPackage::from_dir("mdapi-out")?;
+/// let async_id = client.deploy(&package.to_zip()?, DeployOptions::default()).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeployPackage {
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl DeployPackage {
+    /// A package with just `manifest` as its `package.xml`.
+    pub fn new(manifest: &PackageManifest) -> Self {
+        Self::default().with_file("package.xml", manifest.to_package_xml())
+    }
+
+    /// Package a directory in Metadata API format: `package.xml` at the
+    /// top, beside `classes`, `objects`, and the other type directories.
+    ///
+    /// Every file is included as it is, `-meta.xml` files too; hidden files
+    /// and directories are skipped. Without a `package.xml`, one listing
+    /// every component found is generated.
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Self> {
+        let root = path.as_ref();
+        let mut package = Self::default();
+        let mut pending = vec![(root.to_path_buf(), String::new())];
+        while let Some((dir, prefix)) = pending.pop() {
+            for entry in std::fs::read_dir(&dir)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name.starts_with('.') {
+                    continue;
+                }
+                let relative = format!("{}{}", prefix, name);
+                if entry.file_type()?.is_dir() {
+                    pending.push((entry.path(), format!("{}/", relative)));
+                } else {
+                    package.files.insert(relative, std::fs::read(entry.path())?);
+                }
+            }
+        }
+
+        if !package.files.contains_key("package.xml") {
+            let components = mdapi_components(root, &MetadataRegistry::default())?;
+            let manifest = manifest_from_components(components, DEFAULT_API_VERSION.to_string());
+            package = package.with_file("package.xml", manifest.to_package_xml());
+        }
+        Ok(package)
+    }
+
+    pub(crate) fn from_files(files: BTreeMap<String, Vec<u8>>) -> Self {
+        Self { files }
+    }
+
+    pub(crate) fn into_files(self) -> BTreeMap<String, Vec<u8>> {
+        self.files
+    }
+
+    /// Add a file at `path` in the zip, replacing any already there.
+    pub fn with_file(mut self, path: impl Into<String>, contents: impl Into<Vec<u8>>) -> Self {
+        self.files.insert(path.into(), contents.into());
+        self
+    }
+
+    /// Add `changes` as `destructiveChangesPre.xml` and
+    /// `destructiveChangesPost.xml`, replacing any already there.
+    pub fn with_destructive_changes(mut self, changes: &DestructiveChanges) -> Self {
+        for (path, xml) in [
+            (DESTRUCTIVE_CHANGES_PRE, changes.to_pre_xml()),
+            (DESTRUCTIVE_CHANGES_POST, changes.to_post_xml()),
+        ] {
+            match xml {
+                Some(xml) => self.files.insert(path.to_string(), xml.into_bytes()),
+                None => self.files.remove(path),
+            };
+        }
+        self
+    }
+
+    /// Paths of the files in the package, sorted.
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    /// The contents of the file at `path`.
+    pub fn file(&self, path: &str) -> Option<&[u8]> {
+        self.files.get(path).map(Vec::as_slice)
+    }
+
+    /// The zip to deploy.
+    pub fn to_zip(&self) -> Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, contents) in &self.files {
+            zip.start_file(path.as_str(), SimpleFileOptions::default())
+                .map_err(zip_error)?;
+            zip.write_all(contents)?;
+        }
+        Ok(zip.finish().map_err(zip_error)?.into_inner())
+    }
+}
+
+/// The zip a retrieve returns.
+///
+/// Unpackaged retrieves put everything under `unpackaged/`, and packaged
+/// ones under the package name; paths are kept as they are.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_metadata::RetrievePackage;
+///
+/// let result = client.retrieve_unpackaged_and_wait(&manifest, timeout, interval).await?;
+/// let written = RetrievePackage::from_result(&result)?.unzip_to("retrieved")?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetrievePackage {
+    zip: Vec<u8>,
+}
+
+impl RetrievePackage {
+    /// Wrap a retrieve's zip, checking that it is one.
+    pub fn from_zip(zip: Vec<u8>) -> Result<Self> {
+        ZipArchive::new(Cursor::new(zip.as_slice())).map_err(zip_error)?;
+        Ok(Self { zip })
+    }
+
+    /// The zip from a finished retrieve, whose status was checked with
+    /// `include_zip`.
+    pub fn from_result(result: &RetrieveResult) -> Result<Self> {
+        let encoded = result.zip_file.as_deref().ok_or_else(|| {
+            Error::new(ErrorKind::Retrieve(format!(
+                "retrieve {} has no zip file",
+                result.id
+            )))
+        })?;
+        let zip = general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| {
+                Error::with_source(
+                    ErrorKind::InvalidResponse(format!("retrieve zip is not base64: {}", e)),
+                    e,
+                )
+            })?;
+        Self::from_zip(zip)
+    }
+
+    /// The raw zip.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.zip
+    }
+
+    /// Paths of the files in the zip.
+    pub fn file_names(&self) -> Result<Vec<String>> {
+        let archive = self.archive()?;
+        Ok(archive
+            .file_names()
+            .filter(|name| !name.ends_with('/'))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// The contents of the file at `path`, if the zip has it.
+    pub fn read_file(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let mut archive = self.archive()?;
+        let mut file = match archive.by_name(path) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(e) => return Err(zip_error(e)),
+        };
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        Ok(Some(contents))
+    }
+
+    /// Extract every file under `dir`, creating directories as needed and
+    /// overwriting files already there. Returns the paths written.
+    ///
+    /// Fails without writing anything if an entry's path would land
+    /// outside `dir`.
+    pub fn unzip_to(&self, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        let mut written = Vec::new();
+        for (name, contents) in self.entries()? {
+            let path = dir.join(name);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, contents)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Every file in the zip by path, failing on paths that would escape
+    /// a directory they're extracted to.
+    pub(crate) fn entries(&self) -> Result<BTreeMap<String, Vec<u8>>> {
+        let mut archive = self.archive()?;
+        let mut entries = BTreeMap::new();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(zip_error)?;
+            if file.enclosed_name().is_none() {
+                return Err(Error::new(ErrorKind::Retrieve(format!(
+                    "retrieve zip entry {} escapes the target directory",
+                    file.name()
+                ))));
+            }
+            if file.is_dir() {
+                continue;
+            }
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents)?;
+            entries.insert(file.name().to_string(), contents);
+        }
+        Ok(entries)
+    }
+
+    /// Like [`entries`](Self::entries), with paths relative to the
+    /// package: the `unpackaged/` or package name directory a retrieve
+    /// puts everything under is dropped.
+    pub(crate) fn package_entries(&self) -> Result<BTreeMap<String, Vec<u8>>> {
+        let entries = self.entries()?;
+        let root = entries
+            .keys()
+            .find_map(|name| name.strip_suffix("/package.xml"))
+            .filter(|root| !root.contains('/'))
+            .map(|root| format!("{}/", root));
+        Ok(match root {
+            Some(root) => entries
+                .into_iter()
+                .filter_map(|(name, contents)| {
+                    name.strip_prefix(&root)
+                        .map(|name| (name.to_string(), contents))
+                })
+                .collect(),
+            None => entries,
+        })
+    }
+
+    fn archive(&self) -> Result<ZipArchive<Cursor<&[u8]>>> {
+        ZipArchive::new(Cursor::new(self.zip.as_slice())).map_err(zip_error)
+    }
+}
+
+pub(crate) fn zip_error(e: ZipError) -> Error {
+    Error::with_source(ErrorKind::Io(format!("zip: {}", e)), e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_deploy_package_from_dir_generates_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "classes/Invoices.cls", "public class Invoices {}");
+        write(root, "classes/Invoices.cls-meta.xml", "<ApexClass/>");
+        write(root, "objects/Invoice__c.object", "<CustomObject/>");
+        write(root, "reports/Sales-meta.xml", "<ReportFolder/>");
+        write(root, "reports/Sales/Pipeline.report", "<Report/>");
+        write(root, "documents/Shared/logo.png", "png");
+        write(root, "documents/Shared/logo.png-meta.xml", "<Document/>");
+        write(root, "lwc/invoiceList/invoiceList.js", "export default {}");
+        write(root, ".DS_Store", "");
+
+        let package = DeployPackage::from_dir(root).unwrap();
+        assert!(package.file("classes/Invoices.cls-meta.xml").is_some());
+        assert!(package.file(".DS_Store").is_none());
+
+        let package_xml = String::from_utf8(package.file("package.xml").unwrap().to_vec()).unwrap();
+        for expected in [
+            "<members>Invoices</members>",
+            "<members>Invoice__c</members>",
+            "<members>Sales</members>",
+            "<members>Sales/Pipeline</members>",
+            "<members>Shared/logo.png</members>",
+            "<members>invoiceList</members>",
+            "<name>LightningComponentBundle</name>",
+        ] {
+            assert!(package_xml.contains(expected), "missing {}", expected);
+        }
+        assert!(!package_xml.contains("-meta.xml"));
+    }
+
+    #[test]
+    fn test_deploy_package_zip_round_trips_through_unzip() {
+        let manifest = PackageManifest::new("62.0").add_type("ApexClass", vec!["A".to_string()]);
+        let package = DeployPackage::new(&manifest)
+            .with_file("classes/A.cls", "public class A {}")
+            .with_file("classes/A.cls-meta.xml", "<ApexClass/>");
+
+        let retrieved = RetrievePackage::from_zip(package.to_zip().unwrap()).unwrap();
+        assert_eq!(
+            retrieved.file_names().unwrap(),
+            ["classes/A.cls", "classes/A.cls-meta.xml", "package.xml"]
+        );
+        assert_eq!(
+            retrieved.read_file("classes/A.cls").unwrap().as_deref(),
+            Some(&b"public class A {}"[..])
+        );
+        assert_eq!(retrieved.read_file("classes/B.cls").unwrap(), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let written = retrieved.unzip_to(dir.path()).unwrap();
+        assert_eq!(written.len(), 3);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("classes/A.cls")).unwrap(),
+            "public class A {}"
+        );
+    }
+
+    #[test]
+    fn test_deploy_package_with_destructive_changes() {
+        let package = DeployPackage::new(&PackageManifest::new("62.0"))
+            .with_file(DESTRUCTIVE_CHANGES_PRE, "<Package/>")
+            .with_destructive_changes(
+                &DestructiveChanges::new().delete_after("ApexClass", vec!["Legacy".to_string()]),
+            );
+
+        assert_eq!(
+            package.file_names().collect::<Vec<_>>(),
+            [DESTRUCTIVE_CHANGES_POST, "package.xml"]
+        );
+        let post =
+            String::from_utf8(package.file(DESTRUCTIVE_CHANGES_POST).unwrap().to_vec()).unwrap();
+        assert!(post.contains("<members>Legacy</members>"));
+        assert!(!post.contains("<version>"));
+    }
+
+    #[test]
+    fn test_retrieve_package_rejects_escaping_paths() {
+        let package = DeployPackage::default()
+            .with_file("unpackaged/package.xml", "<Package/>")
+            .with_file("../outside.txt", "nope");
+        let retrieved = RetrievePackage::from_zip(package.to_zip().unwrap()).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("out");
+        assert!(retrieved.unzip_to(&target).is_err());
+        assert!(!target.exists());
+        assert!(RetrievePackage::from_zip(b"not a zip".to_vec()).is_err());
+    }
+}
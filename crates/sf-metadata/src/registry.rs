@@ -0,0 +1,403 @@
+//! Where each metadata type lives in an SFDX source-format project.
+//!
+//! Source format keeps one directory per type (`classes`, `objects`,
+//! `lwc`, ...) under a package directory, and every component has a
+//! `<name>.<suffix>-meta.xml` file. How the files map to components
+//! depends on the type: most are a file per component, some sit in
+//! folders, some are a directory per component, and custom objects are
+//! split into a file per field, record type, list view, and so on.
+
+use crate::describe::DescribeMetadataResult;
+
+/// How a type's components are laid out in source format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceStrategy {
+    /// A file per component: `classes/Foo.cls` and `classes/Foo.cls-meta.xml`.
+    File,
+    /// Components inside folders, which are members of the type too:
+    /// `reports/Sales.reportFolder-meta.xml` and
+    /// `reports/Sales/Pipeline.report-meta.xml`.
+    InFolder {
+        /// Suffix of a folder's own `-meta.xml` file, e.g. `reportFolder`.
+        folder_suffix: String,
+    },
+    /// A directory per component: `lwc/invoiceList/...`.
+    Bundle,
+    /// A directory per component holding the parent's `-meta.xml` and a
+    /// subdirectory per child type: `objects/Invoice__c/fields/Amount__c.field-meta.xml`.
+    Decomposed {
+        /// Child types split out into their own files.
+        children: Vec<ChildType>,
+    },
+}
+
+/// A child type split out of a decomposed parent, such as `CustomField`
+/// out of `CustomObject`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChildType {
+    /// Metadata API type name, e.g. `CustomField`.
+    pub name: String,
+    /// Subdirectory of the parent's directory, e.g. `fields`.
+    pub directory_name: String,
+    /// File suffix, e.g. `field`.
+    pub suffix: String,
+    /// Element the children are listed under in the parent's Metadata
+    /// API format file, e.g. `fields`.
+    pub xml_element: String,
+    /// Whether the children can be listed in a manifest on their own.
+    /// Field translations, for one, only deploy with their parent.
+    pub addressable: bool,
+}
+
+impl ChildType {
+    /// An addressable child type listed under an element named after its
+    /// directory.
+    pub fn new(name: &str, directory_name: &str, suffix: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            directory_name: directory_name.to_string(),
+            suffix: suffix.to_string(),
+            xml_element: directory_name.to_string(),
+            addressable: true,
+        }
+    }
+}
+
+/// A metadata type's place in a source-format project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryType {
+    /// Metadata API type name, e.g. `ApexClass`.
+    pub name: String,
+    /// Directory the type's components live in, e.g. `classes`.
+    pub directory_name: String,
+    /// File suffix, e.g. `cls`. Bundles whose top-level files aren't
+    /// components have none.
+    pub suffix: Option<String>,
+    /// How components are laid out.
+    pub strategy: SourceStrategy,
+}
+
+/// Metadata types by source directory.
+///
+/// [`MetadataRegistry::default`] has the types Salesforce projects
+/// commonly use. [`MetadataRegistry::from_describe`] adds whatever else
+/// an org's `describeMetadata` reports.
+#[derive(Debug, Clone)]
+pub struct MetadataRegistry {
+    types: Vec<RegistryType>,
+}
+
+/// `(type, directory, suffix)` for types with a file per component.
+const FILE_TYPES: &[(&str, &str, &str)] = &[
+    ("AnalyticSnapshot", "analyticSnapshots", "snapshot"),
+    ("ApexClass", "classes", "cls"),
+    ("ApexComponent", "components", "component"),
+    ("ApexPage", "pages", "page"),
+    ("ApexTrigger", "triggers", "trigger"),
+    ("AssignmentRules", "assignmentRules", "assignmentRules"),
+    ("AuthProvider", "authproviders", "authprovider"),
+    (
+        "AutoResponseRules",
+        "autoResponseRules",
+        "autoResponseRules",
+    ),
+    ("Certificate", "certs", "crt"),
+    ("ConnectedApp", "connectedApps", "connectedApp"),
+    ("ContentAsset", "contentassets", "asset"),
+    (
+        "CorsWhitelistOrigin",
+        "corsWhitelistOrigins",
+        "corsWhitelistOrigin",
+    ),
+    ("CspTrustedSite", "cspTrustedSites", "cspTrustedSite"),
+    ("CustomApplication", "applications", "app"),
+    ("CustomLabels", "labels", "labels"),
+    ("CustomMetadata", "customMetadata", "md"),
+    ("CustomNotificationType", "notificationtypes", "notiftype"),
+    ("CustomPageWebLink", "weblinks", "weblink"),
+    ("CustomPermission", "customPermissions", "customPermission"),
+    ("CustomSite", "sites", "site"),
+    ("CustomTab", "tabs", "tab"),
+    ("DelegateGroup", "delegateGroups", "delegateGroup"),
+    ("DuplicateRule", "duplicateRules", "duplicateRule"),
+    ("EmailServicesFunction", "emailservices", "xml"),
+    ("EscalationRules", "escalationRules", "escalationRules"),
+    (
+        "ExternalCredential",
+        "externalCredentials",
+        "externalCredential",
+    ),
+    ("FlexiPage", "flexipages", "flexipage"),
+    ("Flow", "flows", "flow"),
+    ("FlowDefinition", "flowDefinitions", "flowDefinition"),
+    ("GlobalValueSet", "globalValueSets", "globalValueSet"),
+    (
+        "GlobalValueSetTranslation",
+        "globalValueSetTranslations",
+        "globalValueSetTranslation",
+    ),
+    ("Group", "groups", "group"),
+    ("HomePageLayout", "homePageLayouts", "homePageLayout"),
+    ("InstalledPackage", "installedPackages", "installedPackage"),
+    ("Layout", "layouts", "layout"),
+    ("Letterhead", "letterhead", "letter"),
+    (
+        "LightningMessageChannel",
+        "messageChannels",
+        "messageChannel",
+    ),
+    ("MatchingRules", "matchingRules", "matchingRule"),
+    ("NamedCredential", "namedCredentials", "namedCredential"),
+    ("Network", "networks", "network"),
+    ("PathAssistant", "pathAssistants", "pathAssistant"),
+    ("PermissionSet", "permissionsets", "permissionset"),
+    (
+        "PermissionSetGroup",
+        "permissionsetgroups",
+        "permissionsetgroup",
+    ),
+    (
+        "PlatformCachePartition",
+        "cachePartitions",
+        "cachePartition",
+    ),
+    ("Profile", "profiles", "profile"),
+    ("Queue", "queues", "queue"),
+    ("QuickAction", "quickActions", "quickAction"),
+    ("RemoteSiteSetting", "remoteSiteSettings", "remoteSite"),
+    ("ReportType", "reportTypes", "reportType"),
+    ("Role", "roles", "role"),
+    ("Settings", "settings", "settings"),
+    ("SharingRules", "sharingRules", "sharingRules"),
+    ("SharingSet", "sharingSets", "sharingSet"),
+    ("StandardValueSet", "standardValueSets", "standardValueSet"),
+    (
+        "StandardValueSetTranslation",
+        "standardValueSetTranslations",
+        "standardValueSetTranslation",
+    ),
+    ("StaticResource", "staticresources", "resource"),
+    ("TopicsForObjects", "topicsForObjects", "topicsForObjects"),
+    ("Translations", "translations", "translation"),
+    ("Workflow", "workflows", "workflow"),
+];
+
+/// `(type, directory, suffix)` for types kept in folders.
+const FOLDER_TYPES: &[(&str, &str, &str)] = &[
+    ("Dashboard", "dashboards", "dashboard"),
+    ("Document", "documents", "document"),
+    ("EmailTemplate", "email", "email"),
+    ("Report", "reports", "report"),
+];
+
+/// `(type, directory, suffix)` for types with a directory per component.
+const BUNDLE_TYPES: &[(&str, &str, Option<&str>)] = &[
+    ("AuraDefinitionBundle", "aura", None),
+    ("ExperienceBundle", "experiences", Some("site")),
+    ("LightningComponentBundle", "lwc", None),
+];
+
+/// `(type, directory, suffix)` for the children split out of a custom
+/// object.
+const CUSTOM_OBJECT_CHILDREN: &[(&str, &str, &str)] = &[
+    ("BusinessProcess", "businessProcesses", "businessProcess"),
+    ("CompactLayout", "compactLayouts", "compactLayout"),
+    ("CustomField", "fields", "field"),
+    ("FieldSet", "fieldSets", "fieldSet"),
+    ("Index", "indexes", "index"),
+    ("ListView", "listViews", "listView"),
+    ("RecordType", "recordTypes", "recordType"),
+    ("SharingReason", "sharingReasons", "sharingReason"),
+    ("ValidationRule", "validationRules", "validationRule"),
+    ("WebLink", "webLinks", "webLink"),
+];
+
+impl Default for MetadataRegistry {
+    fn default() -> Self {
+        let mut types = Vec::new();
+        for &(name, directory, suffix) in FILE_TYPES {
+            types.push(RegistryType::new(
+                name,
+                directory,
+                Some(suffix),
+                SourceStrategy::File,
+            ));
+        }
+        for &(name, directory, suffix) in FOLDER_TYPES {
+            types.push(RegistryType::new(
+                name,
+                directory,
+                Some(suffix),
+                SourceStrategy::InFolder {
+                    folder_suffix: folder_suffix(suffix),
+                },
+            ));
+        }
+        for &(name, directory, suffix) in BUNDLE_TYPES {
+            types.push(RegistryType::new(
+                name,
+                directory,
+                suffix,
+                SourceStrategy::Bundle,
+            ));
+        }
+        types.push(RegistryType::new(
+            "CustomObject",
+            "objects",
+            Some("object"),
+            SourceStrategy::Decomposed {
+                children: CUSTOM_OBJECT_CHILDREN
+                    .iter()
+                    .map(|&(name, directory, suffix)| ChildType::new(name, directory, suffix))
+                    .collect(),
+            },
+        ));
+        types.push(RegistryType::new(
+            "CustomObjectTranslation",
+            "objectTranslations",
+            Some("objectTranslation"),
+            SourceStrategy::Decomposed {
+                children: vec![ChildType {
+                    xml_element: "fields".to_string(),
+                    addressable: false,
+                    ..ChildType::new(
+                        "CustomFieldTranslation",
+                        "fieldTranslations",
+                        "fieldTranslation",
+                    )
+                }],
+            },
+        ));
+        Self { types }
+    }
+}
+
+impl MetadataRegistry {
+    /// The bundled registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bundled registry plus any type in `describe` it doesn't have.
+    ///
+    /// Added types are laid out a file per component, or in folders if
+    /// `describeMetadata` says so; types without a suffix are taken to be
+    /// bundles.
+    pub fn from_describe(describe: &DescribeMetadataResult) -> Self {
+        let mut registry = Self::default();
+        for metadata_type in &describe.metadata_objects {
+            let Some(directory) = &metadata_type.directory_name else {
+                continue;
+            };
+            if registry.by_name(&metadata_type.xml_name).is_some()
+                || registry.by_directory(directory).is_some()
+            {
+                continue;
+            }
+            let suffix = metadata_type.suffix.as_deref();
+            let strategy = match suffix {
+                Some(suffix) if metadata_type.in_folder => SourceStrategy::InFolder {
+                    folder_suffix: folder_suffix(suffix),
+                },
+                Some(_) => SourceStrategy::File,
+                None => SourceStrategy::Bundle,
+            };
+            registry.types.push(RegistryType::new(
+                &metadata_type.xml_name,
+                directory,
+                suffix,
+                strategy,
+            ));
+        }
+        registry
+    }
+
+    /// Add a type, replacing any with the same name.
+    pub fn with_type(mut self, registry_type: RegistryType) -> Self {
+        self.types.retain(|t| t.name != registry_type.name);
+        self.types.push(registry_type);
+        self
+    }
+
+    /// Every registered type.
+    pub fn types(&self) -> &[RegistryType] {
+        &self.types
+    }
+
+    /// The type by Metadata API name, e.g. `ApexClass`.
+    pub fn by_name(&self, name: &str) -> Option<&RegistryType> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    /// The type stored in a source directory, e.g. `classes`.
+    pub fn by_directory(&self, directory: &str) -> Option<&RegistryType> {
+        self.types.iter().find(|t| t.directory_name == directory)
+    }
+}
+
+impl RegistryType {
+    /// A type stored in `directory_name`.
+    pub fn new(
+        name: &str,
+        directory_name: &str,
+        suffix: Option<&str>,
+        strategy: SourceStrategy,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            directory_name: directory_name.to_string(),
+            suffix: suffix.map(str::to_string),
+            strategy,
+        }
+    }
+}
+
+/// Folders of `report`s are `reportFolder`s, and so on.
+fn folder_suffix(suffix: &str) -> String {
+    format!("{}Folder", suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::describe::MetadataType;
+
+    #[test]
+    fn test_from_describe_adds_unknown_types() {
+        let metadata_type =
+            |name: &str, directory: &str, suffix: Option<&str>, in_folder| MetadataType {
+                xml_name: name.to_string(),
+                directory_name: Some(directory.to_string()),
+                suffix: suffix.map(str::to_string),
+                meta_file: false,
+                in_folder,
+                child_xml_names: Vec::new(),
+            };
+        let describe = DescribeMetadataResult {
+            metadata_objects: vec![
+                metadata_type("ApexClass", "classes", Some("cls"), false),
+                metadata_type("Prompt", "prompts", Some("prompt"), false),
+                metadata_type("Survey", "surveys", Some("survey"), true),
+            ],
+            organization_namespace: None,
+            partial_save_allowed: true,
+            test_required: false,
+        };
+
+        let registry = MetadataRegistry::from_describe(&describe);
+        assert_eq!(
+            registry.types().len(),
+            MetadataRegistry::new().types().len() + 2
+        );
+        assert_eq!(
+            registry.by_directory("prompts").map(|t| &t.strategy),
+            Some(&SourceStrategy::File)
+        );
+        assert_eq!(
+            registry.by_name("Survey").map(|t| &t.strategy),
+            Some(&SourceStrategy::InFolder {
+                folder_suffix: "surveyFolder".to_string()
+            })
+        );
+    }
+}
@@ -65,6 +65,41 @@ impl PackageManifest {
 
         xml_parts.join("\n        ")
     }
+
+    /// The manifest as a `package.xml` file.
+    pub fn to_package_xml(&self) -> String {
+        package_xml(&self.types, Some(&self.version))
+    }
+}
+
+/// A `package.xml` style file listing `types`, as manifests and
+/// destructive changes are written.
+pub(crate) fn package_xml(types: &[PackageTypeMembers], version: Option<&str>) -> String {
+    let mut package_xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <Package xmlns=\"http://soap.sforce.com/2006/04/metadata\">\n",
+    );
+    for type_members in types {
+        package_xml.push_str("    <types>\n");
+        for member in &type_members.members {
+            package_xml.push_str(&format!(
+                "        <members>{}</members>\n",
+                xml::escape(member)
+            ));
+        }
+        package_xml.push_str(&format!(
+            "        <name>{}</name>\n    </types>\n",
+            xml::escape(&type_members.name)
+        ));
+    }
+    if let Some(version) = version {
+        package_xml.push_str(&format!(
+            "    <version>{}</version>\n",
+            xml::escape(version)
+        ));
+    }
+    package_xml.push_str("</Package>\n");
+    package_xml
 }
 
 /// Type members in a package manifest.
@@ -169,6 +204,23 @@ mod tests {
         assert!(xml.contains("<version>62.0</version>"));
     }
 
+    #[test]
+    fn test_package_manifest_to_package_xml() {
+        let manifest = PackageManifest::new("62.0").add_type("ApexClass", vec!["A&B".to_string()]);
+
+        assert_eq!(
+            manifest.to_package_xml(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <Package xmlns=\"http://soap.sforce.com/2006/04/metadata\">\n\
+             \x20   <types>\n\
+             \x20       <members>A&amp;B</members>\n\
+             \x20       <name>ApexClass</name>\n\
+             \x20   </types>\n\
+             \x20   <version>62.0</version>\n\
+             </Package>\n"
+        );
+    }
+
     #[test]
     fn test_package_manifest_escapes_xml_injection() {
         // Attempt XML injection via member name
@@ -0,0 +1,425 @@
+//! Reading SFDX source-format projects from disk.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::registry::{MetadataRegistry, RegistryType, SourceStrategy};
+use crate::retrieve::{PackageManifest, PackageTypeMembers};
+use crate::types::DEFAULT_API_VERSION;
+
+/// A component found in a source directory.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct SourceComponent {
+    pub metadata_type: String,
+    pub full_name: String,
+}
+
+/// The parts of `sfdx-project.json` that say where source lives.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SfdxProject {
+    #[serde(default)]
+    package_directories: Vec<PackageDirectory>,
+    source_api_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageDirectory {
+    path: String,
+}
+
+impl PackageManifest {
+    /// Build a manifest of every component in a source-format project,
+    /// using the bundled [`MetadataRegistry`].
+    ///
+    /// `path` is either a project root with an `sfdx-project.json`, whose
+    /// package directories are read and whose `sourceApiVersion` becomes
+    /// the manifest version, or any directory holding source, such as
+    /// `force-app`. Type directories (`classes`, `objects`, ...) are found
+    /// at any depth. Folders of reports, dashboards, documents, and email
+    /// templates are listed with their contents, and the fields, record
+    /// types, and other children of custom objects are listed under their
+    /// own types.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let manifest = PackageManifest::from_source_dir("my-project")?;
+    /// let retrieve_id = client.retrieve_unpackaged(&manifest).await?;
+    /// ```
+    pub fn from_source_dir(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_source_dir_with_registry(path, &MetadataRegistry::default())
+    }
+
+    /// Like [`from_source_dir`](Self::from_source_dir), with the type
+    /// layouts in `registry`; see [`MetadataRegistry::from_describe`].
+    pub fn from_source_dir_with_registry(
+        path: impl AsRef<Path>,
+        registry: &MetadataRegistry,
+    ) -> Result<Self> {
+        let (roots, version) = project_roots(path.as_ref())?;
+        let mut components = Vec::new();
+        for root in &roots {
+            components.extend(source_components(root, registry)?);
+        }
+
+        Ok(manifest_from_components(
+            components,
+            version.unwrap_or_else(|| DEFAULT_API_VERSION.to_string()),
+        ))
+    }
+}
+
+/// A manifest listing `components`, with types and members sorted.
+pub(crate) fn manifest_from_components(
+    components: impl IntoIterator<Item = SourceComponent>,
+    version: String,
+) -> PackageManifest {
+    let mut types: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for component in components {
+        types
+            .entry(component.metadata_type)
+            .or_default()
+            .insert(component.full_name);
+    }
+    PackageManifest {
+        types: types
+            .into_iter()
+            .map(|(name, members)| PackageTypeMembers {
+                name,
+                members: members.into_iter().collect(),
+            })
+            .collect(),
+        version,
+    }
+}
+
+/// The directories holding a project's source, and its source API
+/// version. Without an `sfdx-project.json`, `path` itself holds the
+/// source.
+pub(crate) fn project_roots(path: &Path) -> Result<(Vec<PathBuf>, Option<String>)> {
+    Ok(match read_sfdx_project(path)? {
+        Some(project) => (
+            project
+                .package_directories
+                .iter()
+                .map(|dir| path.join(&dir.path))
+                .collect(),
+            project.source_api_version,
+        ),
+        None => (vec![path.to_path_buf()], None),
+    })
+}
+
+fn read_sfdx_project(path: &Path) -> Result<Option<SfdxProject>> {
+    let file = path.join("sfdx-project.json");
+    if !file.is_file() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(&file)?;
+    serde_json::from_str(&contents).map(Some).map_err(|e| {
+        Error::with_source(
+            ErrorKind::Other(format!("Invalid {}: {}", file.display(), e)),
+            e,
+        )
+    })
+}
+
+/// Every component under `root`, in no particular order.
+pub(crate) fn source_components(
+    root: &Path,
+    registry: &MetadataRegistry,
+) -> Result<Vec<SourceComponent>> {
+    let mut components = Vec::new();
+    for (registry_type, dir) in source_type_dirs(root, registry)? {
+        components.extend(type_components(registry_type, &relative_files(&dir)?));
+    }
+    Ok(components)
+}
+
+/// The type directories under `root`, at any depth.
+pub(crate) fn source_type_dirs<'r>(
+    root: &Path,
+    registry: &'r MetadataRegistry,
+) -> Result<Vec<(&'r RegistryType, PathBuf)>> {
+    let mut found = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') || name == "node_modules" {
+                continue;
+            }
+            match registry.by_directory(&name) {
+                Some(registry_type) => found.push((registry_type, entry.path())),
+                None => pending.push(entry.path()),
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Every component in a Metadata API format directory, whose type
+/// directories sit directly under `root`.
+pub(crate) fn mdapi_components(
+    root: &Path,
+    registry: &MetadataRegistry,
+) -> Result<Vec<SourceComponent>> {
+    let mut components = Vec::new();
+    for entry in std::fs::read_dir(root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if let Some(registry_type) = registry.by_directory(&name) {
+            let files = relative_files(&entry.path())?;
+            components.extend(mdapi_type_components(registry_type, &files));
+        }
+    }
+    Ok(components)
+}
+
+/// Files under `dir`, as `/`-separated paths relative to it.
+pub(crate) fn relative_files(dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    let mut pending: Vec<(PathBuf, String)> = vec![(dir.to_path_buf(), String::new())];
+    while let Some((path, prefix)) = pending.pop() {
+        for entry in std::fs::read_dir(&path)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let relative = format!("{}{}", prefix, name);
+            if entry.file_type()?.is_dir() {
+                pending.push((entry.path(), format!("{}/", relative)));
+            } else {
+                files.push(relative);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The components of one type, from its directory's files.
+fn type_components(registry_type: &RegistryType, files: &[String]) -> Vec<SourceComponent> {
+    let component = |metadata_type: &str, full_name: &str| SourceComponent {
+        metadata_type: metadata_type.to_string(),
+        full_name: full_name.to_string(),
+    };
+    let meta_suffix = registry_type
+        .suffix
+        .as_ref()
+        .map(|suffix| format!(".{}-meta.xml", suffix));
+    let mut components = Vec::new();
+
+    for file in files {
+        let segments: Vec<&str> = file.split('/').collect();
+        let own_meta = meta_suffix
+            .as_deref()
+            .and_then(|meta_suffix| file.strip_suffix(meta_suffix));
+
+        match &registry_type.strategy {
+            SourceStrategy::File => {
+                if let Some(name) = own_meta.filter(|name| !name.contains('/')) {
+                    components.push(component(&registry_type.name, name));
+                }
+            }
+            SourceStrategy::InFolder { folder_suffix } => {
+                if let Some(folder) = file.strip_suffix(&format!(".{}-meta.xml", folder_suffix)) {
+                    components.push(component(&registry_type.name, folder));
+                } else if let Some(name) = own_meta.filter(|name| name.contains('/')) {
+                    // Documents keep their own extension, which is part of
+                    // the member name.
+                    let suffix = registry_type.suffix.as_deref().unwrap_or_default();
+                    let content = files.iter().find(|other| {
+                        other
+                            .strip_prefix(name)
+                            .and_then(|rest| rest.strip_prefix('.'))
+                            .is_some_and(|ext| {
+                                !ext.contains('/') && !ext.ends_with("-meta.xml") && ext != suffix
+                            })
+                    });
+                    components.push(component(
+                        &registry_type.name,
+                        content.map_or(name, String::as_str),
+                    ));
+                }
+            }
+            SourceStrategy::Bundle => match segments.as_slice() {
+                [_] => components.extend(own_meta.map(|name| component(&registry_type.name, name))),
+                [bundle, _, ..] if !bundle.starts_with('.') => {
+                    components.push(component(&registry_type.name, bundle));
+                }
+                _ => {}
+            },
+            SourceStrategy::Decomposed { children } => match segments.as_slice() {
+                [parent, _]
+                    if own_meta.is_some_and(|name| name == format!("{}/{}", parent, parent)) =>
+                {
+                    components.push(component(&registry_type.name, parent));
+                }
+                [parent, directory, child_file] => {
+                    let child = children
+                        .iter()
+                        .find(|c| c.directory_name == *directory && c.addressable);
+                    if let Some(child) = child {
+                        if let Some(name) =
+                            child_file.strip_suffix(&format!(".{}-meta.xml", child.suffix))
+                        {
+                            components
+                                .push(component(&child.name, &format!("{}.{}", parent, name)));
+                        }
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    components.sort();
+    components.dedup();
+    components
+}
+
+/// The components of one type in Metadata API format, where a component
+/// is a `<name>.<suffix>` file, possibly with a `-meta.xml` beside it, and
+/// decomposed types are a single file again.
+fn mdapi_type_components(registry_type: &RegistryType, files: &[String]) -> Vec<SourceComponent> {
+    let component = |full_name: &str| SourceComponent {
+        metadata_type: registry_type.name.clone(),
+        full_name: full_name.to_string(),
+    };
+    let suffix = registry_type
+        .suffix
+        .as_ref()
+        .map(|suffix| format!(".{}", suffix));
+    let mut components = Vec::new();
+
+    for file in files {
+        let segments: Vec<&str> = file.split('/').collect();
+        let named = suffix
+            .as_deref()
+            .and_then(|suffix| file.strip_suffix(suffix));
+
+        match (&registry_type.strategy, segments.as_slice()) {
+            (SourceStrategy::InFolder { .. }, [folder_meta]) => {
+                components.extend(folder_meta.strip_suffix("-meta.xml").map(component));
+            }
+            (SourceStrategy::InFolder { .. }, [.., _]) if !file.ends_with("-meta.xml") => {
+                // Documents keep their own extension in the member name.
+                components.push(component(named.unwrap_or(file)));
+            }
+            (SourceStrategy::Bundle, [bundle, _, ..]) => components.push(component(bundle)),
+            (SourceStrategy::File | SourceStrategy::Decomposed { .. }, [_])
+            | (SourceStrategy::Bundle, [_]) => {
+                components.extend(named.map(component));
+            }
+            _ => {}
+        }
+    }
+
+    components.sort();
+    components.dedup();
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "<xml/>").unwrap();
+    }
+
+    fn members<'a>(manifest: &'a PackageManifest, name: &str) -> Vec<&'a str> {
+        manifest
+            .types
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| t.members.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_from_source_dir_maps_every_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("sfdx-project.json"),
+            r#"{"packageDirectories": [{"path": "force-app", "default": true}], "sourceApiVersion": "61.0"}"#,
+        )
+        .unwrap();
+        for file in [
+            "force-app/main/default/classes/Invoices.cls",
+            "force-app/main/default/classes/Invoices.cls-meta.xml",
+            "force-app/main/default/customMetadata/Flag.Beta.md-meta.xml",
+            "force-app/main/default/staticresources/brand.resource-meta.xml",
+            "force-app/main/default/staticresources/brand/logo.svg",
+            "force-app/main/default/lwc/invoiceList/invoiceList.js",
+            "force-app/main/default/lwc/invoiceList/invoiceList.js-meta.xml",
+            "force-app/main/default/lwc/jsconfig.json",
+            "force-app/main/default/reports/Sales.reportFolder-meta.xml",
+            "force-app/main/default/reports/Sales/Pipeline.report-meta.xml",
+            "force-app/main/default/documents/Shared.documentFolder-meta.xml",
+            "force-app/main/default/documents/Shared/logo.png",
+            "force-app/main/default/documents/Shared/logo.document-meta.xml",
+            "force-app/main/default/email/Notices/Welcome.email",
+            "force-app/main/default/email/Notices/Welcome.email-meta.xml",
+            "force-app/main/default/objects/Invoice__c/Invoice__c.object-meta.xml",
+            "force-app/main/default/objects/Invoice__c/fields/Amount__c.field-meta.xml",
+            "force-app/main/default/objects/Invoice__c/listViews/All.listView-meta.xml",
+            "force-app/main/default/objects/Account/fields/Tier__c.field-meta.xml",
+            "force-app/main/default/unknownThings/Thing.thing-meta.xml",
+            "other/classes/NotPackaged.cls-meta.xml",
+        ] {
+            write(root, file);
+        }
+
+        let manifest = PackageManifest::from_source_dir(root).unwrap();
+        assert_eq!(manifest.version, "61.0");
+        assert_eq!(members(&manifest, "ApexClass"), ["Invoices"]);
+        assert_eq!(members(&manifest, "CustomMetadata"), ["Flag.Beta"]);
+        assert_eq!(members(&manifest, "StaticResource"), ["brand"]);
+        assert_eq!(
+            members(&manifest, "LightningComponentBundle"),
+            ["invoiceList"]
+        );
+        assert_eq!(members(&manifest, "Report"), ["Sales", "Sales/Pipeline"]);
+        assert_eq!(
+            members(&manifest, "Document"),
+            ["Shared", "Shared/logo.png"]
+        );
+        assert_eq!(members(&manifest, "EmailTemplate"), ["Notices/Welcome"]);
+        assert_eq!(members(&manifest, "CustomObject"), ["Invoice__c"]);
+        assert_eq!(
+            members(&manifest, "CustomField"),
+            ["Account.Tier__c", "Invoice__c.Amount__c"]
+        );
+        assert_eq!(members(&manifest, "ListView"), ["Invoice__c.All"]);
+
+        let names: Vec<_> = manifest.types.iter().map(|t| t.name.as_str()).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+    }
+
+    #[test]
+    fn test_from_source_dir_without_project_file() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "triggers/InvoiceTrigger.trigger-meta.xml");
+
+        let manifest = PackageManifest::from_source_dir(dir.path()).unwrap();
+        assert_eq!(manifest.version, DEFAULT_API_VERSION);
+        assert_eq!(members(&manifest, "ApexTrigger"), ["InvoiceTrigger"]);
+        assert!(PackageManifest::from_source_dir(dir.path().join("missing")).is_err());
+    }
+}
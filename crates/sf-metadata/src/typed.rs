@@ -6,7 +6,8 @@
 //!
 //! # ⚠️ Current Limitations
 //!
-//! This is a **proof-of-concept implementation**. The current XML serialization is simplified
+//! Retrieved XML is parsed into the typed structures properly, but deploys are a
+//! **proof-of-concept implementation**: the current XML serialization is simplified
 //! and wraps JSON in XML tags rather than producing proper Salesforce Metadata API XML.
 //! This may not work correctly with all metadata types in production.
 //!
@@ -43,10 +44,19 @@
 use crate::client::MetadataClient;
 use crate::deploy::DeployOptions;
 use crate::error::{Error, ErrorKind, Result};
-use crate::retrieve::PackageManifest;
+use crate::package::{DeployPackage, RetrievePackage};
+use crate::retrieve::{PackageManifest, RetrieveResult};
+use crate::types::{DeleteResult, SaveResult, UpsertResult};
+use crate::xml_value::from_metadata_xml;
 use busbar_sf_types::traits::MetadataType;
-use std::io::{Cursor, Write};
-use zip::write::{FileOptions, ZipWriter};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// How long [`TypedMetadataExt::retrieve_typed`] waits for its retrieve.
+const RETRIEVE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// How often [`TypedMetadataExt::retrieve_typed`] checks on its retrieve.
+const RETRIEVE_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 /// Extension trait for typed metadata operations.
 ///
@@ -101,6 +111,77 @@ pub trait TypedMetadataExt {
         metadata_items: &[T],
         options: DeployOptions,
     ) -> Result<String>;
+
+    /// Deploy components of any mix of types in one package.
+    ///
+    /// Each component is written to its type's directory, and the
+    /// package.xml lists every type and member.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use busbar_sf_metadata::TypedComponent;
+    ///
+    /// let components: [&dyn TypedComponent; 3] = [&invoice_object, &invoice_layout, &sales_permissions];
+    /// let async_id = client.deploy_typed_bundle(&components, DeployOptions::default()).await?;
+    /// ```
+    async fn deploy_typed_bundle(
+        &self,
+        components: &[&dyn TypedComponent],
+        options: DeployOptions,
+    ) -> Result<String>;
+
+    /// Retrieve components of type `T` and parse each one's XML, keyed by
+    /// full name.
+    ///
+    /// Runs an unpackaged retrieve of `members` (`"*"` for every component
+    /// the type allows a wildcard for), waits up to ten minutes for it, and
+    /// parses each retrieved file. For types whose content sits in a
+    /// separate file, such as `ApexClass`, that's the `-meta.xml` file.
+    /// Members the org doesn't have are missing from the map.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use busbar_sf_types::metadata::objects::CustomObject;
+    ///
+    /// let objects = client
+    ///     .retrieve_typed::<CustomObject>(&["Invoice__c", "Account"])
+    ///     .await?;
+    /// for field in &objects["Invoice__c"].fields {
+    ///     println!("{}", field.label);
+    /// }
+    /// ```
+    async fn retrieve_typed<T: MetadataType>(&self, members: &[&str])
+        -> Result<HashMap<String, T>>;
+
+    /// Create typed components with the synchronous `createMetadata` call.
+    ///
+    /// Unset fields (null, empty strings, lists, and objects) are left out
+    /// of the request. Up to 10 components per call.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let results = client.create_typed(&[custom_object]).await?;
+    /// ```
+    async fn create_typed<T: MetadataType>(&self, items: &[T]) -> Result<Vec<SaveResult>>;
+
+    /// Update typed components with the synchronous `updateMetadata` call.
+    ///
+    /// Like [`create_typed`](Self::create_typed), unset fields are left
+    /// out. Fields that are set, including `false` booleans, replace what
+    /// the org has.
+    async fn update_typed<T: MetadataType>(&self, items: &[T]) -> Result<Vec<SaveResult>>;
+
+    /// Create or update typed components with the synchronous
+    /// `upsertMetadata` call.
+    async fn upsert_typed<T: MetadataType>(&self, items: &[T]) -> Result<Vec<UpsertResult>>;
+
+    /// Delete components of type `T` by full name with the synchronous
+    /// `deleteMetadata` call.
+    async fn delete_typed<T: MetadataType>(&self, full_names: &[&str])
+        -> Result<Vec<DeleteResult>>;
 }
 
 impl TypedMetadataExt for MetadataClient {
@@ -118,75 +199,231 @@ impl TypedMetadataExt for MetadataClient {
         metadata_items: &[T],
         options: DeployOptions,
     ) -> Result<String> {
-        if metadata_items.is_empty() {
-            return Err(Error::new(ErrorKind::Other(
-                "Cannot deploy empty metadata batch".to_string(),
-            )));
-        }
+        let components: Vec<&dyn TypedComponent> = metadata_items
+            .iter()
+            .map(|item| item as &dyn TypedComponent)
+            .collect();
+        self.deploy_typed_bundle(&components, options).await
+    }
 
-        // Create zip in memory
-        let mut zip_buffer = Cursor::new(Vec::new());
-        let mut zip = ZipWriter::new(&mut zip_buffer);
-
-        // Collect member names for package.xml
-        let mut members = Vec::new();
-
-        // Add each metadata item to the zip
-        for (idx, item) in metadata_items.iter().enumerate() {
-            // Get the API name
-            let api_name = item
-                .api_name()
-                .ok_or_else(|| {
-                    Error::new(ErrorKind::Other(format!(
-                        "Metadata item at index {} missing api_name",
-                        idx
-                    )))
-                })?
-                .to_string();
-
-            members.push(api_name.clone());
-
-            // Determine the file path based on metadata type
-            let file_path = format!(
-                "{}/{}.{}",
-                get_directory_name(T::METADATA_TYPE_NAME),
-                api_name,
-                get_file_extension(T::METADATA_TYPE_NAME)
-            );
+    async fn deploy_typed_bundle(
+        &self,
+        components: &[&dyn TypedComponent],
+        options: DeployOptions,
+    ) -> Result<String> {
+        let package = bundle_package(components, self.api_version())?;
+        self.deploy(&package.to_zip()?, options).await
+    }
 
-            // Serialize to XML
-            let xml = serialize_to_metadata_xml(item)?;
+    async fn retrieve_typed<T: MetadataType>(
+        &self,
+        members: &[&str],
+    ) -> Result<HashMap<String, T>> {
+        let manifest = PackageManifest::new(self.api_version()).add_type(
+            T::METADATA_TYPE_NAME,
+            members.iter().map(|m| m.to_string()).collect(),
+        );
+        let result = self
+            .retrieve_unpackaged_and_wait(&manifest, RETRIEVE_TIMEOUT, RETRIEVE_POLL_INTERVAL)
+            .await?;
+        parse_retrieved(&result)
+    }
 
-            // Add to zip
-            zip.start_file::<_, ()>(file_path, FileOptions::default())
-                .map_err(|e| Error::new(ErrorKind::Io(e.to_string())))?;
-            zip.write_all(xml.as_bytes())?;
-        }
+    async fn create_typed<T: MetadataType>(&self, items: &[T]) -> Result<Vec<SaveResult>> {
+        let values = crud_values(items)?;
+        self.create_metadata(T::METADATA_TYPE_NAME, &values).await
+    }
 
-        // Create package.xml
-        let manifest =
-            PackageManifest::new(self.api_version()).add_type(T::METADATA_TYPE_NAME, members);
+    async fn update_typed<T: MetadataType>(&self, items: &[T]) -> Result<Vec<SaveResult>> {
+        let values = crud_values(items)?;
+        self.update_metadata(T::METADATA_TYPE_NAME, &values).await
+    }
+
+    async fn upsert_typed<T: MetadataType>(&self, items: &[T]) -> Result<Vec<UpsertResult>> {
+        let values = crud_values(items)?;
+        self.upsert_metadata(T::METADATA_TYPE_NAME, &values).await
+    }
+
+    async fn delete_typed<T: MetadataType>(
+        &self,
+        full_names: &[&str],
+    ) -> Result<Vec<DeleteResult>> {
+        self.delete_metadata(T::METADATA_TYPE_NAME, full_names)
+            .await
+    }
+}
+
+/// A typed component of any metadata type, for deploying several types
+/// together with [`TypedMetadataExt::deploy_typed_bundle`].
+///
+/// [`TypedMetadata`](crate::TypedMetadata) can't be used as a trait object,
+/// so this is implemented for every type that implements it.
+pub trait TypedComponent {
+    /// The Metadata API type name, such as `CustomObject`.
+    fn metadata_type_name(&self) -> &'static str;
+
+    /// The name the component is listed under in package.xml, including
+    /// its folder for folder-based types.
+    fn component_name(&self) -> Option<String>;
+
+    /// The component's file contents.
+    fn to_metadata_xml(&self) -> Result<String>;
+}
+
+impl<T: MetadataType> TypedComponent for T {
+    fn metadata_type_name(&self) -> &'static str {
+        T::METADATA_TYPE_NAME
+    }
+
+    fn component_name(&self) -> Option<String> {
+        self.full_name()
+    }
 
-        let package_xml = format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?>
-<Package xmlns="http://soap.sforce.com/2006/04/metadata">
-    {}
-</Package>"#,
-            manifest.to_xml()
+    fn to_metadata_xml(&self) -> Result<String> {
+        serialize_to_metadata_xml(self)
+    }
+}
+
+/// Build the deploy package for `components`, with types listed in
+/// package.xml in the order they first appear.
+fn bundle_package(components: &[&dyn TypedComponent], api_version: &str) -> Result<DeployPackage> {
+    if components.is_empty() {
+        return Err(Error::new(ErrorKind::Other(
+            "Cannot deploy empty metadata batch".to_string(),
+        )));
+    }
+
+    let mut types: Vec<(&str, Vec<String>)> = Vec::new();
+    let mut files = Vec::with_capacity(components.len());
+    for (idx, component) in components.iter().enumerate() {
+        let type_name = component.metadata_type_name();
+        let name = component.component_name().ok_or_else(|| {
+            Error::new(ErrorKind::Other(format!(
+                "Metadata item at index {} missing api_name",
+                idx
+            )))
+        })?;
+
+        let members = match types.iter_mut().find(|(t, _)| *t == type_name) {
+            Some((_, members)) => members,
+            None => {
+                types.push((type_name, Vec::new()));
+                &mut types.last_mut().expect("just pushed").1
+            }
+        };
+        if members.contains(&name) {
+            return Err(Error::new(ErrorKind::Other(format!(
+                "{} {} appears more than once",
+                type_name, name
+            ))));
+        }
+        members.push(name.clone());
+
+        let file_path = format!(
+            "{}/{}.{}",
+            get_directory_name(type_name),
+            name,
+            get_file_extension(type_name)
         );
+        files.push((file_path, component.to_metadata_xml()?));
+    }
 
-        zip.start_file::<_, ()>("package.xml", FileOptions::default())
-            .map_err(|e| Error::new(ErrorKind::Io(e.to_string())))?;
-        zip.write_all(package_xml.as_bytes())?;
+    let manifest = types.into_iter().fold(
+        PackageManifest::new(api_version),
+        |manifest, (type_name, members)| manifest.add_type(type_name, members),
+    );
+    Ok(files
+        .into_iter()
+        .fold(DeployPackage::new(&manifest), |package, (path, xml)| {
+            package.with_file(path, xml)
+        }))
+}
 
-        // Finish the zip
-        zip.finish()
-            .map_err(|e| Error::new(ErrorKind::Io(e.to_string())))?;
+/// Parse the components of type `T` in a finished retrieve, using its file
+/// properties to find each one's file.
+fn parse_retrieved<T: MetadataType>(result: &RetrieveResult) -> Result<HashMap<String, T>> {
+    let files = RetrievePackage::from_result(result)?.entries()?;
+    let mut components = HashMap::new();
+    for properties in &result.file_properties {
+        if properties.component_type != T::METADATA_TYPE_NAME {
+            continue;
+        }
+        let meta_file = format!("{}-meta.xml", properties.file_name);
+        let Some(contents) = files
+            .get(&meta_file)
+            .or_else(|| files.get(&properties.file_name))
+        else {
+            continue;
+        };
+        let xml = std::str::from_utf8(contents).map_err(|e| {
+            Error::with_source(
+                ErrorKind::Parse(format!("{} is not UTF-8: {}", properties.file_name, e)),
+                e,
+            )
+        })?;
+        let component = from_metadata_xml(xml).map_err(|e| {
+            Error::with_source(
+                ErrorKind::Parse(format!(
+                    "Failed to parse {} as {}: {}",
+                    properties.file_name,
+                    T::METADATA_TYPE_NAME,
+                    e
+                )),
+                e,
+            )
+        })?;
+        components.insert(properties.full_name.clone(), component);
+    }
+    Ok(components)
+}
 
-        let zip_bytes = zip_buffer.into_inner();
+/// Convert typed components to the JSON the CRUD calls take.
+///
+/// Generated types serialize every field, so unset ones are pruned rather
+/// than sent as empty elements that would blank the org's values.
+fn crud_values<T: MetadataType>(items: &[T]) -> Result<Vec<serde_json::Value>> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let full_name = item.full_name().ok_or_else(|| {
+                Error::new(ErrorKind::Other(format!(
+                    "Metadata item at index {} missing api_name",
+                    idx
+                )))
+            })?;
+            let mut value = serde_json::to_value(item)
+                .map_err(|e| Error::new(ErrorKind::Parse(e.to_string())))?;
+            prune_unset(&mut value);
+            if let serde_json::Value::Object(fields) = &mut value {
+                fields.insert("fullName".to_string(), full_name.into());
+            }
+            Ok(value)
+        })
+        .collect()
+}
 
-        // Deploy using the standard method
-        self.deploy(&zip_bytes, options).await
+/// Drop nulls, empty strings, and empty lists and objects, innermost first.
+fn prune_unset(value: &mut serde_json::Value) {
+    fn is_unset(value: &serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Null => true,
+            serde_json::Value::String(s) => s.is_empty(),
+            serde_json::Value::Array(items) => items.is_empty(),
+            serde_json::Value::Object(fields) => fields.is_empty(),
+            _ => false,
+        }
+    }
+    match value {
+        serde_json::Value::Object(fields) => {
+            fields.values_mut().for_each(prune_unset);
+            fields.retain(|_, v| !is_unset(v));
+        }
+        serde_json::Value::Array(items) => {
+            items.iter_mut().for_each(prune_unset);
+            items.retain(|v| !is_unset(v));
+        }
+        _ => {}
     }
 }
 
@@ -260,6 +497,8 @@ fn get_file_extension(metadata_type: &str) -> &str {
 mod tests {
     use super::*;
     use serde::{Deserialize, Serialize};
+    use std::io::{Cursor, Write};
+    use zip::write::{FileOptions, ZipWriter};
 
     // Mock metadata type for testing
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -578,4 +817,182 @@ mod tests {
             );
         }
     }
+
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase", default)]
+    struct MockObject {
+        full_name: Option<String>,
+        label: String,
+        description: String,
+        enable_history: bool,
+        fields: Vec<serde_json::Value>,
+        sharing_model: serde_json::Value,
+        search_layouts: serde_json::Value,
+    }
+
+    impl busbar_sf_types::traits::MetadataType for MockObject {
+        const METADATA_TYPE_NAME: &'static str = "CustomObject";
+        const XML_ROOT_ELEMENT: &'static str = "CustomObject";
+
+        fn api_name(&self) -> Option<&str> {
+            self.full_name.as_deref()
+        }
+    }
+
+    #[test]
+    fn test_crud_values_prune_unset_fields() {
+        let object = MockObject {
+            full_name: Some("Invoice__c".to_string()),
+            label: "Invoice".to_string(),
+            description: String::new(),
+            enable_history: false,
+            fields: Vec::new(),
+            sharing_model: serde_json::Value::Null,
+            search_layouts: serde_json::json!({"customTabListAdditionalFields": [""]}),
+        };
+        let values = crud_values(&[object]).unwrap();
+        assert_eq!(
+            values,
+            vec![serde_json::json!({
+                "fullName": "Invoice__c",
+                "label": "Invoice",
+                "enableHistory": false
+            })]
+        );
+
+        let unnamed = MockObject {
+            full_name: None,
+            label: "Nameless".to_string(),
+            description: String::new(),
+            enable_history: false,
+            fields: Vec::new(),
+            sharing_model: serde_json::Value::Null,
+            search_layouts: serde_json::Value::Null,
+        };
+        assert!(crud_values(&[unnamed]).is_err());
+    }
+
+    #[test]
+    fn test_parse_retrieved_custom_objects() {
+        use crate::package::DeployPackage;
+        use crate::retrieve::RetrieveStatus;
+        use crate::types::FileProperties;
+        use base64::{engine::general_purpose, Engine as _};
+
+        let zip = DeployPackage::default()
+            .with_file("unpackaged/package.xml", "<Package/>")
+            .with_file(
+                "unpackaged/objects/Invoice__c.object",
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<CustomObject xmlns="http://soap.sforce.com/2006/04/metadata">
+    <enableHistory>true</enableHistory>
+    <fields>
+        <fullName>Amount__c</fullName>
+        <label>Amount</label>
+    </fields>
+    <fields>
+        <fullName>Due__c</fullName>
+        <label>Due</label>
+    </fields>
+    <label>Invoice</label>
+    <sharingModel>ReadWrite</sharingModel>
+</CustomObject>
+"#,
+            )
+            .with_file(
+                "unpackaged/classes/Invoices.cls",
+                "public class Invoices {}",
+            )
+            .to_zip()
+            .unwrap();
+        let properties = |component_type: &str, full_name: &str, file_name: &str| FileProperties {
+            created_by_id: String::new(),
+            created_by_name: String::new(),
+            created_date: String::new(),
+            file_name: file_name.to_string(),
+            full_name: full_name.to_string(),
+            id: String::new(),
+            last_modified_by_id: String::new(),
+            last_modified_by_name: String::new(),
+            last_modified_date: String::new(),
+            manageable_state: None,
+            namespace_prefix: None,
+            component_type: component_type.to_string(),
+        };
+        let result = RetrieveResult {
+            id: "09S000000000001".to_string(),
+            done: true,
+            status: RetrieveStatus::Succeeded,
+            success: true,
+            error_message: None,
+            error_status_code: None,
+            zip_file: Some(general_purpose::STANDARD.encode(zip)),
+            file_properties: vec![
+                properties(
+                    "CustomObject",
+                    "Invoice__c",
+                    "unpackaged/objects/Invoice__c.object",
+                ),
+                properties("ApexClass", "Invoices", "unpackaged/classes/Invoices.cls"),
+                properties(
+                    "Package",
+                    "unpackaged/package.xml",
+                    "unpackaged/package.xml",
+                ),
+            ],
+            messages: Vec::new(),
+        };
+
+        let objects = parse_retrieved::<MockObject>(&result).unwrap();
+        assert_eq!(objects.len(), 1);
+        let invoice = &objects["Invoice__c"];
+        assert_eq!(invoice.label, "Invoice");
+        assert!(invoice.enable_history);
+        assert_eq!(invoice.fields.len(), 2);
+        assert_eq!(invoice.fields[1]["label"], "Due");
+        assert_eq!(invoice.sharing_model, serde_json::json!("ReadWrite"));
+    }
+
+    #[test]
+    fn test_bundle_package_mixes_types() {
+        let class = MockMetadata {
+            full_name: Some("Invoices".to_string()),
+            label: None,
+        };
+        let object = MockObject {
+            full_name: Some("Invoice__c".to_string()),
+            label: "Invoice".to_string(),
+            ..Default::default()
+        };
+        let other_class = MockMetadata {
+            full_name: Some("Payments".to_string()),
+            label: None,
+        };
+
+        let components: [&dyn TypedComponent; 3] = [&class, &object, &other_class];
+        let package = bundle_package(&components, "62.0").unwrap();
+        assert_eq!(
+            package.file_names().collect::<Vec<_>>(),
+            [
+                "classes/Invoices.cls",
+                "classes/Payments.cls",
+                "objects/Invoice__c.object",
+                "package.xml"
+            ]
+        );
+        let package_xml = String::from_utf8(package.file("package.xml").unwrap().to_vec()).unwrap();
+        assert!(package_xml.contains(
+            "<members>Invoices</members>\n        <members>Payments</members>\n        <name>ApexClass</name>"
+        ));
+        assert!(package_xml
+            .contains("<members>Invoice__c</members>\n        <name>CustomObject</name>"));
+
+        assert!(bundle_package(&[], "62.0").is_err());
+        assert!(bundle_package(&[&class, &class], "62.0").is_err());
+        let unnamed = MockMetadata {
+            full_name: None,
+            label: None,
+        };
+        assert!(bundle_package(&[&unnamed], "62.0").is_err());
+    }
 }
@@ -65,6 +65,15 @@ pub struct TestFailure {
     pub namespace: Option<String>,
 }
 
+/// A code coverage warning from the tests run by a deployment, such as a
+/// class below the required coverage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeCoverageWarning {
+    pub name: Option<String>,
+    pub message: String,
+    pub namespace: Option<String>,
+}
+
 /// Properties of a file in a retrieve result.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileProperties {
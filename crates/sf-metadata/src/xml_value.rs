@@ -0,0 +1,318 @@
+//! Metadata XML as a JSON tree, for comparing files and deserializing
+//! them into `busbar-sf-types` structs.
+//!
+//! Metadata XML doesn't say which elements are lists or what type a value
+//! has, so a file is read into a tree of strings, objects, and (for
+//! repeated elements) arrays. When deserializing, the target type decides
+//! the rest: a string becomes a bool or number where one is wanted, and a
+//! lone element becomes a one-item list.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+#[cfg(feature = "typed")]
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde_json::{Map, Value};
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Deserialize a metadata file, such as a retrieved `Invoice__c.object`,
+/// into `T`. The root element's name isn't checked.
+#[cfg(feature = "typed")]
+pub(crate) fn from_metadata_xml<T: DeserializeOwned>(xml: &str) -> Result<T> {
+    let value = parse_tree(xml)?;
+    T::deserialize(Lenient(value))
+        .map_err(|e| Error::with_source(ErrorKind::Parse(e.to_string()), e))
+}
+
+/// The root element's contents as a JSON tree. Attributes, comments, and
+/// processing instructions are dropped.
+pub(crate) fn parse_tree(xml: &str) -> Result<Value> {
+    let parse_error = |e: quick_xml::Error| {
+        Error::with_source(ErrorKind::Parse(format!("Invalid XML: {}", e)), e)
+    };
+
+    let mut reader = Reader::from_str(xml);
+    // Each open element's name, children, and text so far.
+    let mut stack: Vec<(String, Map<String, Value>, String)> = Vec::new();
+    loop {
+        match reader.read_event().map_err(parse_error)? {
+            Event::Start(start) => {
+                let name = String::from_utf8_lossy(start.local_name().as_ref()).into_owned();
+                stack.push((name, Map::new(), String::new()));
+            }
+            Event::Empty(empty) => {
+                let name = String::from_utf8_lossy(empty.local_name().as_ref()).into_owned();
+                match stack.last_mut() {
+                    Some((_, children, _)) => {
+                        add_child(children, name, Value::String(String::new()))
+                    }
+                    None => return Ok(Value::Object(Map::new())),
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, _, buffer)) = stack.last_mut() {
+                    buffer.push_str(&text.unescape().map_err(parse_error)?);
+                }
+            }
+            Event::CData(cdata) => {
+                if let Some((_, _, buffer)) = stack.last_mut() {
+                    buffer.push_str(&String::from_utf8_lossy(&cdata.into_inner()));
+                }
+            }
+            Event::End(_) => {
+                let Some((name, children, text)) = stack.pop() else {
+                    break;
+                };
+                let value = if !children.is_empty() {
+                    Value::Object(children)
+                } else if text.trim().is_empty() {
+                    Value::String(String::new())
+                } else {
+                    Value::String(text)
+                };
+                match stack.last_mut() {
+                    Some((_, parent, _)) => add_child(parent, name, value),
+                    None => return Ok(value),
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Err(Error::new(ErrorKind::Parse(
+        "Invalid XML: no complete root element".to_string(),
+    )))
+}
+
+/// Add a child element, turning repeated elements into an array.
+fn add_child(children: &mut Map<String, Value>, name: String, value: Value) {
+    match children.get_mut(&name) {
+        Some(Value::Array(items)) => items.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            children.insert(name, value);
+        }
+    }
+}
+
+/// A JSON tree read from XML, deserialized by what the target asks for.
+#[cfg(feature = "typed")]
+struct Lenient(Value);
+
+#[cfg(feature = "typed")]
+impl Lenient {
+    fn text(&self) -> Option<&str> {
+        match &self.0 {
+            Value::String(s) => Some(s.trim()),
+            _ => None,
+        }
+    }
+
+    fn invalid<E: de::Error>(&self, expected: &str) -> E {
+        E::custom(format!("expected {}, found {}", expected, self.0))
+    }
+}
+
+#[cfg(feature = "typed")]
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> std::result::Result<V::Value, Self::Error> {
+                match self.text().map(str::parse) {
+                    Some(Ok(parsed)) => visitor.$visit(parsed),
+                    _ => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "typed")]
+impl<'de> de::Deserializer<'de> for Lenient {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Object(map) => visitor.visit_map(de::value::MapDeserializer::new(
+                map.into_iter().map(|(k, v)| (k, Lenient(v))),
+            )),
+            Value::Array(items) => visitor.visit_seq(de::value::SeqDeserializer::new(
+                items.into_iter().map(Lenient),
+            )),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Array(_) => self.deserialize_any(visitor),
+            Value::Null => visitor.visit_seq(de::value::SeqDeserializer::new(std::iter::empty::<
+                Lenient,
+            >())),
+            single => visitor.visit_seq(de::value::SeqDeserializer::new(std::iter::once(Lenient(
+                single,
+            )))),
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match &self.0 {
+            Value::Object(_) => self.deserialize_any(visitor),
+            // An empty element, such as `<searchLayouts></searchLayouts>`.
+            Value::String(s) if s.trim().is_empty() => visitor.visit_map(
+                de::value::MapDeserializer::new(std::iter::empty::<(String, Lenient)>()),
+            ),
+            _ => Err(self.invalid("an element with children")),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.text() {
+            Some(variant) => visitor.visit_enum(variant.to_string().into_deserializer()),
+            None => Err(self.invalid("an enum value")),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf unit unit_struct tuple
+        tuple_struct identifier ignored_any
+    }
+}
+
+#[cfg(feature = "typed")]
+impl<'de> IntoDeserializer<'de, serde_json::Error> for Lenient {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+#[cfg(all(test, feature = "typed"))]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(rename_all = "camelCase", default)]
+    struct Object {
+        label: String,
+        enable_history: bool,
+        fields: Vec<Field>,
+        list_views: Vec<Field>,
+        sharing_model: Value,
+        search_layouts: Value,
+        nested: Option<Field>,
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(rename_all = "camelCase", default)]
+    struct Field {
+        full_name: String,
+        length: i32,
+        picklist: Vec<String>,
+    }
+
+    #[test]
+    fn test_from_metadata_xml_follows_target_types() {
+        let object: Object = from_metadata_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<CustomObject xmlns="http://soap.sforce.com/2006/04/metadata">
+    <!-- generated -->
+    <enableHistory>true</enableHistory>
+    <fields>
+        <fullName>Amount__c</fullName>
+        <length>18</length>
+    </fields>
+    <fields>
+        <fullName>Stage__c</fullName>
+        <picklist>Open</picklist>
+        <picklist>Closed &amp; Won</picklist>
+    </fields>
+    <label><![CDATA[Invoice <Main>]]></label>
+    <listViews>
+        <fullName>All</fullName>
+        <picklist>Only</picklist>
+    </listViews>
+    <searchLayouts></searchLayouts>
+    <sharingModel>ReadWrite</sharingModel>
+</CustomObject>
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(object.label, "Invoice <Main>");
+        assert!(object.enable_history);
+        assert_eq!(object.fields.len(), 2);
+        assert_eq!(object.fields[0].length, 18);
+        assert_eq!(object.fields[1].picklist, ["Open", "Closed & Won"]);
+        assert_eq!(object.list_views[0].picklist, ["Only"]);
+        assert_eq!(object.sharing_model, Value::String("ReadWrite".into()));
+        assert_eq!(object.search_layouts, Value::String(String::new()));
+        assert!(object.nested.is_none());
+    }
+
+    #[test]
+    fn test_from_metadata_xml_rejects_broken_xml() {
+        assert!(from_metadata_xml::<Object>("<CustomObject><label>x</CustomObject>").is_err());
+        assert!(from_metadata_xml::<Object>("<CustomObject>").is_err());
+    }
+}
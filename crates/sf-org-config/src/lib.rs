@@ -0,0 +1,836 @@
+//! # busbar-sf-org-config
+//!
+//! Declarative org configuration.
+//!
+//! Describe the remote site settings, named credentials, custom metadata
+//! records, and custom setting values an org should have in an
+//! [`OrgConfig`] (it deserializes from JSON, YAML, TOML, ...). Then
+//! [`OrgConfig::plan`] reads the org's current values and lists what
+//! differs, and [`OrgConfigPlan::apply`] writes only those differences.
+//!
+//! Only the items listed in the config are managed. Nothing is deleted,
+//! and fields the config leaves out keep their current values.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use busbar_sf_org_config::OrgConfig;
+//!
+//! let config: OrgConfig = serde_json::from_str(&std::fs::read_to_string("org.json")?)?;
+//! let plan = config.plan(&rest, &metadata).await?;
+//! print!("{plan}");
+//! if confirm() {
+//!     let report = plan.apply(&rest, &metadata).await?;
+//!     for failure in &report.failed {
+//!         eprintln!("{}: {}", failure.name, failure.message);
+//!     }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use busbar_sf_metadata::{CustomMetadataRecord, CustomMetadataValue, MetadataClient, UpsertResult};
+use busbar_sf_rest::{SalesforceRestClient, Soql, SoqlValue};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+/// Errors from planning or applying an [`OrgConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A REST call failed.
+    #[error("REST API error: {0}")]
+    Rest(#[from] busbar_sf_rest::Error),
+    /// A Metadata API call failed.
+    #[error("Metadata API error: {0}")]
+    Metadata(#[from] busbar_sf_metadata::Error),
+    /// An entry in the config can't be turned into a write.
+    #[error("invalid config for {name}: {message}")]
+    InvalidConfig { name: String, message: String },
+}
+
+/// Result type for org configuration.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The desired state of an org's configuration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrgConfig {
+    /// Remote site settings.
+    pub remote_site_settings: Vec<RemoteSiteSetting>,
+    /// Named credentials.
+    pub named_credentials: Vec<NamedCredential>,
+    /// Custom metadata type records.
+    pub custom_metadata: Vec<CustomMetadataEntry>,
+    /// Custom setting values.
+    pub custom_settings: Vec<CustomSettingEntry>,
+}
+
+/// A remote site setting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSiteSetting {
+    /// API name.
+    pub full_name: String,
+    /// Allowed URL, e.g. `https://api.example.com`.
+    pub url: String,
+    /// Description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Whether callouts to the site are allowed.
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    /// Allow plain HTTP even when the org requires HTTPS.
+    #[serde(default)]
+    pub disable_protocol_security: bool,
+}
+
+impl RemoteSiteSetting {
+    fn to_metadata(&self) -> Map<String, Value> {
+        let mut fields = json!({
+            "fullName": self.full_name,
+            "url": self.url,
+            "isActive": self.is_active,
+            "disableProtocolSecurity": self.disable_protocol_security,
+        });
+        if let Some(ref description) = self.description {
+            fields["description"] = description.as_str().into();
+        }
+        into_map(fields)
+    }
+}
+
+/// A named credential.
+///
+/// Passwords and OAuth secrets are not managed here; set them in the org.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedCredential {
+    /// API name.
+    pub full_name: String,
+    /// Label.
+    pub label: String,
+    /// Callout endpoint URL.
+    pub endpoint: String,
+    /// `Anonymous`, `NamedUser`, or `PerUser`.
+    #[serde(default = "default_principal_type")]
+    pub principal_type: String,
+    /// Authentication protocol, e.g. `NoAuthentication`, `Password`, `Oauth`.
+    #[serde(default = "default_protocol")]
+    pub protocol: String,
+    /// Allow merge fields in the request body.
+    #[serde(default)]
+    pub allow_merge_fields_in_body: bool,
+    /// Allow merge fields in request headers.
+    #[serde(default)]
+    pub allow_merge_fields_in_header: bool,
+    /// Send an `Authorization` header built from the credential.
+    #[serde(default = "default_true")]
+    pub generate_authorization_header: bool,
+}
+
+impl NamedCredential {
+    fn to_metadata(&self) -> Map<String, Value> {
+        into_map(json!({
+            "fullName": self.full_name,
+            "label": self.label,
+            "endpoint": self.endpoint,
+            "principalType": self.principal_type,
+            "protocol": self.protocol,
+            "allowMergeFieldsInBody": self.allow_merge_fields_in_body,
+            "allowMergeFieldsInHeader": self.allow_merge_fields_in_header,
+            "generateAuthorizationHeader": self.generate_authorization_header,
+        }))
+    }
+}
+
+/// A custom metadata type record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomMetadataEntry {
+    /// Type API name, with or without `__mdt`.
+    pub type_name: String,
+    /// Record developer name.
+    pub developer_name: String,
+    /// Record label.
+    pub label: String,
+    /// Whether the record is protected.
+    #[serde(default)]
+    pub protected: bool,
+    /// Field values by API name. Scalars only; `null` clears a field.
+    #[serde(default)]
+    pub values: BTreeMap<String, Value>,
+}
+
+impl CustomMetadataEntry {
+    fn to_record(&self) -> Result<CustomMetadataRecord> {
+        CustomMetadataRecord::from_serialize(
+            &self.type_name,
+            &self.developer_name,
+            &self.label,
+            &self.values,
+        )
+        .map(|record| record.protected(self.protected))
+        .map_err(|e| Error::InvalidConfig {
+            name: format!("{}.{}", self.type_name, self.developer_name),
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Values for one custom setting record.
+///
+/// Identify a list setting's record by `name`. For a hierarchy setting,
+/// give the profile or user ID in `setup_owner_id`, or leave both unset
+/// for the org-wide default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomSettingEntry {
+    /// Custom setting API name, e.g. `App_Settings__c`.
+    pub sobject: String,
+    /// Profile or user ID for a hierarchy setting.
+    #[serde(default)]
+    pub setup_owner_id: Option<String>,
+    /// Record name for a list setting.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Field values by API name.
+    #[serde(default)]
+    pub values: BTreeMap<String, Value>,
+}
+
+impl CustomSettingEntry {
+    fn display_name(&self) -> String {
+        match (&self.name, &self.setup_owner_id) {
+            (Some(name), _) => format!("{}.{}", self.sobject, name),
+            (None, Some(owner)) => format!("{} ({})", self.sobject, owner),
+            (None, None) => format!("{} (org default)", self.sobject),
+        }
+    }
+}
+
+/// What a [`ConfigChange`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKind {
+    RemoteSiteSetting,
+    NamedCredential,
+    CustomMetadata,
+    CustomSetting,
+}
+
+impl fmt::Display for ConfigKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ConfigKind::RemoteSiteSetting => "RemoteSiteSetting",
+            ConfigKind::NamedCredential => "NamedCredential",
+            ConfigKind::CustomMetadata => "CustomMetadata",
+            ConfigKind::CustomSetting => "CustomSetting",
+        })
+    }
+}
+
+/// Whether a change creates an item or updates an existing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeAction {
+    Create,
+    Update,
+}
+
+/// One field that differs from the desired value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// Field name as the org knows it.
+    pub field: String,
+    /// The org's current value, `None` when unset or the item is new.
+    pub current: Option<String>,
+    /// The desired value, `None` to clear the field.
+    pub desired: Option<String>,
+}
+
+/// One item that will be created or updated.
+#[derive(Debug, Clone)]
+pub struct ConfigChange {
+    /// What kind of item this is.
+    pub kind: ConfigKind,
+    /// The item's name, e.g. `Flag.Checkout`.
+    pub name: String,
+    /// Create or update.
+    pub action: ChangeAction,
+    /// The fields that differ.
+    pub fields: Vec<FieldChange>,
+    write: Write,
+}
+
+#[derive(Debug, Clone)]
+enum Write {
+    Metadata(Map<String, Value>),
+    CustomMetadata(CustomMetadataRecord),
+    CreateSetting {
+        sobject: String,
+        record: Value,
+    },
+    UpdateSetting {
+        sobject: String,
+        id: String,
+        record: Value,
+    },
+}
+
+/// The differences between an [`OrgConfig`] and an org.
+#[derive(Debug, Clone, Default)]
+pub struct OrgConfigPlan {
+    /// Changes in the order they will be applied.
+    pub changes: Vec<ConfigChange>,
+}
+
+impl OrgConfigPlan {
+    /// Whether the org already matches the config.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for OrgConfigPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+        for change in &self.changes {
+            let marker = match change.action {
+                ChangeAction::Create => '+',
+                ChangeAction::Update => '~',
+            };
+            writeln!(f, "{} {} {}", marker, change.kind, change.name)?;
+            for field in &change.fields {
+                let show = |v: &Option<String>| v.clone().unwrap_or_else(|| "(none)".to_string());
+                match change.action {
+                    ChangeAction::Create => {
+                        writeln!(f, "    {}: {}", field.field, show(&field.desired))?
+                    }
+                    ChangeAction::Update => writeln!(
+                        f,
+                        "    {}: {} -> {}",
+                        field.field,
+                        show(&field.current),
+                        show(&field.desired)
+                    )?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A change that Salesforce rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyFailure {
+    /// The item's name.
+    pub name: String,
+    /// Why it failed.
+    pub message: String,
+}
+
+/// The outcome of [`OrgConfigPlan::apply`].
+#[derive(Debug, Clone, Default)]
+pub struct ApplyReport {
+    /// Names of the items written.
+    pub applied: Vec<String>,
+    /// Items Salesforce rejected.
+    pub failed: Vec<ApplyFailure>,
+}
+
+impl ApplyReport {
+    /// Whether every change was applied.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl OrgConfig {
+    /// Compare the config with the org and list what needs to change.
+    ///
+    /// Reads metadata with `readMetadata` and custom settings with SOQL;
+    /// nothing is written.
+    pub async fn plan(
+        &self,
+        rest: &SalesforceRestClient,
+        metadata: &MetadataClient,
+    ) -> Result<OrgConfigPlan> {
+        let mut plan = OrgConfigPlan::default();
+
+        for (kind, metadata_type, items) in [
+            (
+                ConfigKind::RemoteSiteSetting,
+                "RemoteSiteSetting",
+                self.remote_site_settings
+                    .iter()
+                    .map(RemoteSiteSetting::to_metadata)
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                ConfigKind::NamedCredential,
+                "NamedCredential",
+                self.named_credentials
+                    .iter()
+                    .map(NamedCredential::to_metadata)
+                    .collect(),
+            ),
+        ] {
+            let names: Vec<&str> = items
+                .iter()
+                .filter_map(|item| item["fullName"].as_str())
+                .collect();
+            let current = read_by_name(metadata, metadata_type, &names).await?;
+            for desired in items {
+                let name = desired["fullName"].as_str().unwrap_or_default().to_string();
+                let existing = current.get(&name).and_then(Value::as_object);
+                let fields = diff_metadata(&desired, existing);
+                if !fields.is_empty() || existing.is_none() {
+                    plan.changes.push(ConfigChange {
+                        kind,
+                        name,
+                        action: action_for(existing.is_some()),
+                        fields,
+                        write: Write::Metadata(desired),
+                    });
+                }
+            }
+        }
+
+        let records = self
+            .custom_metadata
+            .iter()
+            .map(CustomMetadataEntry::to_record)
+            .collect::<Result<Vec<_>>>()?;
+        let names: Vec<String> = records.iter().map(|r| r.full_name()).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let current = read_by_name(metadata, "CustomMetadata", &names).await?;
+        for record in records {
+            let existing = current
+                .get(&record.full_name())
+                .and_then(CustomMetadataRecord::from_read_record);
+            let fields = diff_custom_metadata(&record, existing.as_ref());
+            if !fields.is_empty() || existing.is_none() {
+                plan.changes.push(ConfigChange {
+                    kind: ConfigKind::CustomMetadata,
+                    name: record.full_name(),
+                    action: action_for(existing.is_some()),
+                    fields,
+                    write: Write::CustomMetadata(record),
+                });
+            }
+        }
+
+        let mut org_id = None;
+        for entry in &self.custom_settings {
+            plan.changes
+                .extend(plan_custom_setting(rest, entry, &mut org_id).await?);
+        }
+
+        Ok(plan)
+    }
+}
+
+impl OrgConfigPlan {
+    /// Write every change in the plan.
+    ///
+    /// Items Salesforce rejects are reported in [`ApplyReport::failed`]
+    /// and don't stop the rest. A failed Metadata API call returns an
+    /// error; changes before it stay applied.
+    pub async fn apply(
+        &self,
+        rest: &SalesforceRestClient,
+        metadata: &MetadataClient,
+    ) -> Result<ApplyReport> {
+        let mut report = ApplyReport::default();
+
+        for kind in [ConfigKind::RemoteSiteSetting, ConfigKind::NamedCredential] {
+            let changes: Vec<(&ConfigChange, Value)> = self
+                .changes
+                .iter()
+                .filter_map(|c| match c.write {
+                    Write::Metadata(ref fields) if c.kind == kind => {
+                        Some((c, Value::Object(fields.clone())))
+                    }
+                    _ => None,
+                })
+                .collect();
+            for batch in changes.chunks(10) {
+                let objects: Vec<Value> = batch.iter().map(|(_, v)| v.clone()).collect();
+                let results = metadata
+                    .upsert_metadata(&kind.to_string(), &objects)
+                    .await?;
+                record_upserts(&mut report, batch.iter().map(|(c, _)| *c), results);
+            }
+        }
+
+        let (changes, records): (Vec<&ConfigChange>, Vec<CustomMetadataRecord>) = self
+            .changes
+            .iter()
+            .filter_map(|c| match c.write {
+                Write::CustomMetadata(ref record) => Some((c, record.clone())),
+                _ => None,
+            })
+            .unzip();
+        if !records.is_empty() {
+            let results = metadata.upsert_custom_metadata(&records).await?;
+            record_upserts(&mut report, changes.into_iter(), results);
+        }
+
+        for change in &self.changes {
+            let result = match change.write {
+                Write::CreateSetting {
+                    ref sobject,
+                    ref record,
+                } => rest.create(sobject, record).await.map(|_| ()),
+                Write::UpdateSetting {
+                    ref sobject,
+                    ref id,
+                    ref record,
+                } => rest.update(sobject, id, record).await,
+                _ => continue,
+            };
+            match result {
+                Ok(()) => report.applied.push(change.name.clone()),
+                Err(e) => report.failed.push(ApplyFailure {
+                    name: change.name.clone(),
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+fn record_upserts<'a>(
+    report: &mut ApplyReport,
+    changes: impl Iterator<Item = &'a ConfigChange>,
+    results: Vec<UpsertResult>,
+) {
+    for (change, result) in changes.zip(results) {
+        if result.success {
+            report.applied.push(change.name.clone());
+        } else {
+            let message = result
+                .errors
+                .iter()
+                .map(|e| e.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ");
+            report.failed.push(ApplyFailure {
+                name: change.name.clone(),
+                message,
+            });
+        }
+    }
+}
+
+/// Read metadata components, keyed by full name. Missing names are absent.
+async fn read_by_name(
+    metadata: &MetadataClient,
+    metadata_type: &str,
+    names: &[&str],
+) -> Result<BTreeMap<String, Value>> {
+    let mut found = BTreeMap::new();
+    for batch in names.chunks(10) {
+        for record in metadata.read_metadata(metadata_type, batch).await?.records {
+            if let Some(name) = record["fullName"].as_str() {
+                found.insert(name.to_string(), record);
+            }
+        }
+    }
+    Ok(found)
+}
+
+async fn plan_custom_setting(
+    rest: &SalesforceRestClient,
+    entry: &CustomSettingEntry,
+    org_id: &mut Option<String>,
+) -> Result<Option<ConfigChange>> {
+    let (key_field, key) = match (&entry.name, &entry.setup_owner_id) {
+        (Some(name), _) => ("Name", SoqlValue::from(name)),
+        (None, Some(owner)) => ("SetupOwnerId", SoqlValue::id(owner)),
+        (None, None) => {
+            if org_id.is_none() {
+                let orgs: Vec<Value> = rest.query_all("SELECT Id FROM Organization").await?;
+                *org_id = orgs
+                    .first()
+                    .and_then(|org| org["Id"].as_str())
+                    .map(str::to_string);
+            }
+            let id = org_id.clone().ok_or_else(|| Error::InvalidConfig {
+                name: entry.display_name(),
+                message: "could not read the organization ID".to_string(),
+            })?;
+            ("SetupOwnerId", SoqlValue::Id(id))
+        }
+    };
+    let soql = Soql::select(std::iter::once("Id").chain(entry.values.keys().map(String::as_str)))
+        .from(&entry.sobject)
+        .and_where(format!("{} = :key", key_field))
+        .bind("key", key.clone())
+        .limit(1)
+        .build()?;
+    let current: Vec<Map<String, Value>> = rest.query_all(&soql).await?;
+    let existing = current.into_iter().next();
+
+    let fields = diff_setting(&entry.values, existing.as_ref());
+    if fields.is_empty() && existing.is_some() {
+        return Ok(None);
+    }
+    let write = match existing {
+        Some(ref record) => Write::UpdateSetting {
+            sobject: entry.sobject.clone(),
+            id: record["Id"].as_str().unwrap_or_default().to_string(),
+            record: Value::Object(
+                fields
+                    .iter()
+                    .map(|f| (f.field.clone(), entry.values[&f.field].clone()))
+                    .collect(),
+            ),
+        },
+        None => {
+            let mut record: Map<String, Value> = entry
+                .values
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+            if let SoqlValue::String(key) | SoqlValue::Id(key) = key {
+                record.insert(key_field.to_string(), key.into());
+            }
+            Write::CreateSetting {
+                sobject: entry.sobject.clone(),
+                record: Value::Object(record),
+            }
+        }
+    };
+    Ok(Some(ConfigChange {
+        kind: ConfigKind::CustomSetting,
+        name: entry.display_name(),
+        action: action_for(existing.is_some()),
+        fields,
+        write,
+    }))
+}
+
+fn action_for(exists: bool) -> ChangeAction {
+    if exists {
+        ChangeAction::Update
+    } else {
+        ChangeAction::Create
+    }
+}
+
+/// Metadata read back from the org has every scalar as a string.
+fn diff_metadata(
+    desired: &Map<String, Value>,
+    current: Option<&Map<String, Value>>,
+) -> Vec<FieldChange> {
+    desired
+        .iter()
+        .filter(|(field, _)| field.as_str() != "fullName")
+        .filter_map(|(field, value)| {
+            let current = current.and_then(|c| c.get(field)).and_then(scalar_text);
+            let desired = scalar_text(value);
+            (!same_scalar(desired.as_deref(), current.as_deref())).then(|| FieldChange {
+                field: field.clone(),
+                current,
+                desired,
+            })
+        })
+        .collect()
+}
+
+fn diff_custom_metadata(
+    desired: &CustomMetadataRecord,
+    current: Option<&CustomMetadataRecord>,
+) -> Vec<FieldChange> {
+    let mut fields = Vec::new();
+    let mut compare = |field: &str, desired: Option<String>, current: Option<String>| {
+        if !same_scalar(desired.as_deref(), current.as_deref()) {
+            fields.push(FieldChange {
+                field: field.to_string(),
+                current,
+                desired,
+            });
+        }
+    };
+    compare(
+        "label",
+        Some(desired.label.clone()),
+        current.map(|c| c.label.clone()),
+    );
+    compare(
+        "protected",
+        Some(desired.protected.to_string()),
+        current.map(|c| c.protected.to_string()),
+    );
+    for (field, value) in &desired.values {
+        let current = current
+            .and_then(|c| c.values.get(field))
+            .and_then(Option::as_ref);
+        compare(
+            field,
+            value.as_ref().map(CustomMetadataValue::to_string),
+            current.map(CustomMetadataValue::to_string),
+        );
+    }
+    fields
+}
+
+fn diff_setting(
+    desired: &BTreeMap<String, Value>,
+    current: Option<&Map<String, Value>>,
+) -> Vec<FieldChange> {
+    desired
+        .iter()
+        .filter_map(|(field, value)| {
+            let current = current.and_then(|c| c.get(field)).and_then(scalar_text);
+            let desired = scalar_text(value);
+            (!same_scalar(desired.as_deref(), current.as_deref())).then(|| FieldChange {
+                field: field.clone(),
+                current,
+                desired,
+            })
+        })
+        .collect()
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Compare two scalar renderings, treating numbers by value (`25` vs `25.0`).
+fn same_scalar(desired: Option<&str>, current: Option<&str>) -> bool {
+    match (desired, current) {
+        (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => a == b,
+        },
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn into_map(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_principal_type() -> String {
+    "Anonymous".to_string()
+}
+
+fn default_protocol() -> String {
+    "NoAuthentication".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults() {
+        let config: OrgConfig = serde_json::from_value(json!({
+            "remote_site_settings": [{"full_name": "Acme_API", "url": "https://api.acme.com"}],
+            "custom_settings": [{"sobject": "App_Settings__c", "values": {"Timeout__c": 30}}]
+        }))
+        .unwrap();
+        assert!(config.remote_site_settings[0].is_active);
+        assert!(config.named_credentials.is_empty());
+        assert_eq!(
+            config.custom_settings[0].display_name(),
+            "App_Settings__c (org default)"
+        );
+    }
+
+    #[test]
+    fn test_diff_metadata_compares_read_strings() {
+        let desired = RemoteSiteSetting {
+            full_name: "Acme_API".to_string(),
+            url: "https://api.acme.com".to_string(),
+            description: None,
+            is_active: true,
+            disable_protocol_security: false,
+        }
+        .to_metadata();
+        let current = into_map(json!({
+            "fullName": "Acme_API",
+            "url": "https://old.acme.com",
+            "isActive": "true",
+            "disableProtocolSecurity": "false",
+            "_rawXml": "<records/>"
+        }));
+        assert_eq!(
+            diff_metadata(&desired, Some(&current)),
+            vec![FieldChange {
+                field: "url".to_string(),
+                current: Some("https://old.acme.com".to_string()),
+                desired: Some("https://api.acme.com".to_string()),
+            }]
+        );
+        assert_eq!(diff_metadata(&desired, None).len(), 3);
+    }
+
+    #[test]
+    fn test_diff_custom_metadata_and_settings() {
+        let desired = CustomMetadataEntry {
+            type_name: "Flag__mdt".to_string(),
+            developer_name: "Checkout".to_string(),
+            label: "Checkout".to_string(),
+            protected: false,
+            values: [
+                ("Enabled__c".to_string(), json!(true)),
+                ("Percent__c".to_string(), json!(25)),
+            ]
+            .into(),
+        }
+        .to_record()
+        .unwrap();
+        let current = CustomMetadataRecord::new("Flag", "Checkout", "Checkout")
+            .with_value("Enabled__c", false)
+            .with_value("Percent__c", 25.0);
+        let changes = diff_custom_metadata(&desired, Some(&current));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "Enabled__c");
+        assert_eq!(changes[0].current.as_deref(), Some("false"));
+
+        let desired = [
+            ("Timeout__c".to_string(), json!(30)),
+            ("Endpoint__c".to_string(), Value::Null),
+        ]
+        .into();
+        let current =
+            into_map(json!({"Id": "a00000000000001AAA", "Timeout__c": 30.0, "Endpoint__c": null}));
+        assert!(diff_setting(&desired, Some(&current)).is_empty());
+    }
+
+    #[test]
+    fn test_plan_display() {
+        let plan = OrgConfigPlan {
+            changes: vec![ConfigChange {
+                kind: ConfigKind::RemoteSiteSetting,
+                name: "Acme_API".to_string(),
+                action: ChangeAction::Update,
+                fields: vec![FieldChange {
+                    field: "isActive".to_string(),
+                    current: Some("false".to_string()),
+                    desired: Some("true".to_string()),
+                }],
+                write: Write::Metadata(Map::new()),
+            }],
+        };
+        assert_eq!(
+            plan.to_string(),
+            "~ RemoteSiteSetting Acme_API\n    isActive: false -> true\n"
+        );
+        assert_eq!(OrgConfigPlan::default().to_string(), "No changes.\n");
+    }
+}
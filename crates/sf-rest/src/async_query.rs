@@ -0,0 +1,204 @@
+//! Async SOQL types.
+//!
+//! Async SOQL runs a query in the background and writes the results into a
+//! target object instead of returning them. It is how Big Objects are
+//! queried at scale, since the synchronous query endpoint can only filter
+//! them on their index fields.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// How an async query writes its results to the target object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AsyncQueryOperation {
+    /// Insert a record per result row.
+    #[default]
+    Insert,
+    /// Upsert on the target's external ID field.
+    Upsert,
+}
+
+/// A request to start an async SOQL job.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_rest::AsyncQueryRequest;
+///
+/// let request = AsyncQueryRequest::new(
+///     "SELECT Account__c, Amount__c FROM Sale__b WHERE Year__c = 2024",
+///     "SaleSummary__c",
+/// )
+/// .map_field("Account__c", "Account__c")
+/// .map_field("Amount__c", "Amount__c")
+/// .with_value("Source__c", "'archive'");
+/// let job = client.create_async_query(&request).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncQueryRequest {
+    /// The SOQL query to run.
+    pub query: String,
+    /// Insert or upsert.
+    pub operation: AsyncQueryOperation,
+    /// API name of the object the results are written to.
+    pub target_object: String,
+    /// Query field (or alias) to target field.
+    pub target_field_map: BTreeMap<String, String>,
+    /// Target field to a constant written on every result record. String
+    /// values must be quoted, e.g. `'archive'`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub target_value_map: BTreeMap<String, String>,
+    /// External ID field matched on when upserting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_external_id_field: Option<String>,
+}
+
+impl AsyncQueryRequest {
+    /// Insert the results of `query` into `target_object`.
+    pub fn new(query: impl Into<String>, target_object: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            target_object: target_object.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Write query field `source` into target field `target`.
+    pub fn map_field(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+        self.target_field_map.insert(source.into(), target.into());
+        self
+    }
+
+    /// Set target field `field` to the constant `value` on every result.
+    pub fn with_value(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        self.target_value_map.insert(field.into(), value.into());
+        self
+    }
+
+    /// Upsert on `external_id_field` instead of inserting.
+    pub fn upsert(mut self, external_id_field: impl Into<String>) -> Self {
+        self.operation = AsyncQueryOperation::Upsert;
+        self.target_external_id_field = Some(external_id_field.into());
+        self
+    }
+}
+
+/// Status of an async SOQL job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AsyncQueryStatus {
+    New,
+    Running,
+    Complete,
+    Failed,
+    Canceled,
+    /// A status this crate doesn't know about.
+    #[serde(other)]
+    Unknown,
+}
+
+impl AsyncQueryStatus {
+    /// Whether the job has finished, successfully or not.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            AsyncQueryStatus::Complete | AsyncQueryStatus::Failed | AsyncQueryStatus::Canceled
+        )
+    }
+
+    /// The status as Salesforce reports it.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AsyncQueryStatus::New => "New",
+            AsyncQueryStatus::Running => "Running",
+            AsyncQueryStatus::Complete => "Complete",
+            AsyncQueryStatus::Failed => "Failed",
+            AsyncQueryStatus::Canceled => "Canceled",
+            AsyncQueryStatus::Unknown => "Unknown",
+        }
+    }
+}
+
+/// An async SOQL job and its current status.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncQueryJob {
+    pub job_id: String,
+    pub status: AsyncQueryStatus,
+    /// Progress or failure details.
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub operation: AsyncQueryOperation,
+    #[serde(default)]
+    pub query: String,
+    #[serde(default)]
+    pub target_object: String,
+    #[serde(default)]
+    pub target_field_map: BTreeMap<String, String>,
+    #[serde(default)]
+    pub target_value_map: BTreeMap<String, String>,
+    #[serde(default)]
+    pub target_external_id_field: Option<String>,
+}
+
+impl AsyncQueryJob {
+    /// Every target field the job writes to.
+    pub fn target_fields(&self) -> impl Iterator<Item = &str> {
+        self.target_field_map
+            .values()
+            .chain(self.target_value_map.keys())
+            .map(String::as_str)
+    }
+}
+
+/// Response from listing async SOQL jobs.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AsyncQueryList {
+    #[serde(default)]
+    pub async_queries: Vec<AsyncQueryJob>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_async_query_request_serialization() {
+        let request = AsyncQueryRequest::new("SELECT Amount__c FROM Sale__b", "Summary__c")
+            .map_field("Amount__c", "Total__c")
+            .upsert("Key__c");
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "query": "SELECT Amount__c FROM Sale__b",
+                "operation": "upsert",
+                "targetObject": "Summary__c",
+                "targetFieldMap": {"Amount__c": "Total__c"},
+                "targetExternalIdField": "Key__c"
+            })
+        );
+    }
+
+    #[test]
+    fn test_async_query_job_deserialization() {
+        let job: AsyncQueryJob = serde_json::from_value(serde_json::json!({
+            "jobId": "08PD000000003kiMAA",
+            "message": "",
+            "operation": "insert",
+            "query": "SELECT Amount__c FROM Sale__b",
+            "status": "Running",
+            "targetFieldMap": {"Amount__c": "Total__c"},
+            "targetObject": "Summary__c",
+            "targetValueMap": {}
+        }))
+        .unwrap();
+        assert_eq!(job.status, AsyncQueryStatus::Running);
+        assert!(!job.status.is_terminal());
+        assert_eq!(job.target_field_map["Amount__c"], "Total__c");
+    }
+}
@@ -0,0 +1,184 @@
+use tracing::instrument;
+
+use busbar_sf_client::{RequestBuilder, RequestMethod};
+
+use crate::error::{Error, ErrorKind, Result};
+
+impl super::SalesforceRestClient {
+    /// Call a custom Apex REST endpoint (`@RestResource`).
+    ///
+    /// `path` is relative to `/services/apexrest/`, e.g. `MyService/v1/orders`,
+    /// and may include a query string. The response is parsed as JSON when
+    /// possible; other bodies are returned as a JSON string and an empty body
+    /// as `null`.
+    ///
+    /// Extra `headers` are added to the request; `Authorization` cannot be
+    /// overridden.
+    #[instrument(skip(self, body, headers))]
+    pub async fn apex_rest(
+        &self,
+        method: RequestMethod,
+        path: &str,
+        body: Option<&serde_json::Value>,
+        headers: &[(&str, &str)],
+    ) -> Result<serde_json::Value> {
+        let path = validate_apex_rest_path(path)?;
+        let url = self.client.url(&format!("/services/apexrest/{}", path));
+
+        let mut request = RequestBuilder::new(method, url)
+            .bearer_auth(self.client.access_token())
+            .header("Accept", "application/json");
+        for (name, value) in headers {
+            if name.eq_ignore_ascii_case("authorization") {
+                return Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "INVALID_HEADER".to_string(),
+                    message: "The Authorization header cannot be overridden".to_string(),
+                }));
+            }
+            request = request.header(*name, *value);
+        }
+        if let Some(body) = body {
+            request = request.json(body)?;
+        }
+
+        let text = self.client.execute(request).await?.text().await?;
+        if text.trim().is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+        Ok(serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text)))
+    }
+}
+
+/// Normalize an Apex REST path and reject anything that could escape
+/// `/services/apexrest/`.
+pub(crate) fn validate_apex_rest_path(path: &str) -> Result<&str> {
+    let path = path.trim_start_matches('/');
+    let route = path.split('?').next().unwrap_or_default();
+    let invalid = route.is_empty()
+        || route
+            .split('/')
+            .any(|segment| segment == "." || segment == "..")
+        // Encoded dots and slashes could be decoded into traversal later.
+        || ["%2e", "%2f", "%5c"]
+            .iter()
+            .any(|enc| route.to_ascii_lowercase().contains(enc))
+        || path.contains('\\')
+        || path.contains('#')
+        || path.chars().any(|c| c.is_whitespace() || c.is_control());
+    if invalid {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_PATH".to_string(),
+            message: "Invalid Apex REST path".to_string(),
+        }));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use super::*;
+
+    #[test]
+    fn test_validate_apex_rest_path() {
+        assert_eq!(
+            validate_apex_rest_path("/MyService/v1").unwrap(),
+            "MyService/v1"
+        );
+        assert_eq!(
+            validate_apex_rest_path("MyService?id=1").unwrap(),
+            "MyService?id=1"
+        );
+        for bad in [
+            "",
+            "/",
+            "../data/v62.0/sobjects",
+            "a/../b",
+            "a b",
+            "a\\b",
+            "a#b",
+            "a/%2E%2E/b",
+        ] {
+            assert!(
+                validate_apex_rest_path(bad).is_err(),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apex_rest_post_wiremock() {
+        use wiremock::matchers::{body_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/services/apexrest/Orders/v1"))
+            .and(header("Authorization", "Bearer test-token"))
+            .and(header("X-Request-Id", "abc"))
+            .and(body_json(serde_json::json!({"orderId": "42"})))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"status": "queued"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let result = client
+            .apex_rest(
+                RequestMethod::Post,
+                "/Orders/v1",
+                Some(&serde_json::json!({"orderId": "42"})),
+                &[("X-Request-Id", "abc")],
+            )
+            .await
+            .expect("apex_rest should succeed");
+        assert_eq!(result["status"], "queued");
+    }
+
+    #[tokio::test]
+    async fn test_apex_rest_text_and_empty_bodies() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/apexrest/Ping"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("pong"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/services/apexrest/Orders/42"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let pong = client
+            .apex_rest(RequestMethod::Get, "Ping", None, &[])
+            .await
+            .unwrap();
+        assert_eq!(pong, serde_json::Value::String("pong".to_string()));
+        let deleted = client
+            .apex_rest(RequestMethod::Delete, "Orders/42", None, &[])
+            .await
+            .unwrap();
+        assert!(deleted.is_null());
+    }
+
+    #[tokio::test]
+    async fn test_apex_rest_rejects_authorization_header() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client
+            .apex_rest(
+                RequestMethod::Get,
+                "Ping",
+                None,
+                &[("authorization", "Bearer other")],
+            )
+            .await;
+        assert!(result.unwrap_err().to_string().contains("INVALID_HEADER"));
+    }
+}
@@ -0,0 +1,250 @@
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use tracing::instrument;
+
+use busbar_sf_client::security::{soql, url as url_security};
+
+use crate::async_query::{AsyncQueryJob, AsyncQueryList, AsyncQueryRequest, AsyncQueryStatus};
+use crate::error::{Error, ErrorKind, Result};
+
+impl super::SalesforceRestClient {
+    /// Start an async SOQL job.
+    ///
+    /// The job runs in the background and writes its results into the
+    /// request's target object. Poll it with [`async_query`](Self::async_query)
+    /// or [`wait_for_async_query`](Self::wait_for_async_query).
+    #[instrument(skip(self, request))]
+    pub async fn create_async_query(&self, request: &AsyncQueryRequest) -> Result<AsyncQueryJob> {
+        if !soql::is_safe_sobject_name(&request.target_object) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_SOBJECT".to_string(),
+                message: format!("Invalid SObject name: {}", request.target_object),
+            }));
+        }
+        if request.target_field_map.is_empty() {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_REQUEST".to_string(),
+                message: "Async query needs at least one target field mapping".to_string(),
+            }));
+        }
+        self.client
+            .rest_post("async-queries", request)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Get the status of an async SOQL job.
+    #[instrument(skip(self))]
+    pub async fn async_query(&self, job_id: &str) -> Result<AsyncQueryJob> {
+        validate_job_id(job_id)?;
+        self.client
+            .rest_get(&format!("async-queries/{}", job_id))
+            .await
+            .map_err(Into::into)
+    }
+
+    /// List the org's async SOQL jobs.
+    #[instrument(skip(self))]
+    pub async fn list_async_queries(&self) -> Result<Vec<AsyncQueryJob>> {
+        let list: AsyncQueryList = self.client.rest_get("async-queries").await?;
+        Ok(list.async_queries)
+    }
+
+    /// Cancel a running async SOQL job.
+    #[instrument(skip(self))]
+    pub async fn cancel_async_query(&self, job_id: &str) -> Result<()> {
+        validate_job_id(job_id)?;
+        self.client
+            .rest_delete(&format!("async-queries/{}", job_id))
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Poll an async SOQL job until it completes, fails, or is canceled.
+    ///
+    /// Fails if the job is still running after `timeout`.
+    #[instrument(skip(self))]
+    pub async fn wait_for_async_query(
+        &self,
+        job_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<AsyncQueryJob> {
+        let started = Instant::now();
+        loop {
+            let job = self.async_query(job_id).await?;
+            if job.status.is_terminal() {
+                return Ok(job);
+            }
+            if started.elapsed() >= timeout {
+                return Err(Error::new(ErrorKind::Other(format!(
+                    "Async query {} still {} after {:?}",
+                    job_id,
+                    job.status.as_str(),
+                    timeout
+                ))));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Read back the records a completed job wrote to its target object.
+    ///
+    /// Selects every mapped target field. The target object may hold
+    /// records from earlier jobs too; pass `filter` (a SOQL condition,
+    /// e.g. a constant set through the job's value map) to narrow them.
+    ///
+    /// # Security
+    ///
+    /// **IMPORTANT**: `filter` is inserted into the query as-is. Escape
+    /// user-provided values with `busbar_sf_client::security::soql::escape_string()`.
+    #[instrument(skip(self, job), fields(job_id = %job.job_id))]
+    pub async fn async_query_results<T: DeserializeOwned>(
+        &self,
+        job: &AsyncQueryJob,
+        filter: Option<&str>,
+    ) -> Result<Vec<T>> {
+        if job.status != AsyncQueryStatus::Complete {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "JOB_NOT_COMPLETE".to_string(),
+                message: format!("Async query {} is {}", job.job_id, job.status.as_str()),
+            }));
+        }
+        if !soql::is_safe_sobject_name(&job.target_object) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_SOBJECT".to_string(),
+                message: format!("Invalid SObject name: {}", job.target_object),
+            }));
+        }
+        let mut fields: Vec<&str> = job.target_fields().collect();
+        fields.sort_unstable();
+        fields.dedup();
+        if let Some(field) = fields.iter().find(|f| !soql::is_safe_field_name(f)) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_FIELD".to_string(),
+                message: format!("Invalid field name: {}", field),
+            }));
+        }
+        if fields.is_empty() {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_FIELD".to_string(),
+                message: "Async query has no target fields".to_string(),
+            }));
+        }
+
+        let mut query = format!("SELECT {} FROM {}", fields.join(", "), job.target_object);
+        if let Some(filter) = filter {
+            query.push_str(&format!(" WHERE {}", filter));
+        }
+        self.query_as(&query).await
+    }
+}
+
+fn validate_job_id(job_id: &str) -> Result<()> {
+    if !url_security::is_valid_salesforce_id(job_id) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_ID".to_string(),
+            message: "Invalid Salesforce ID format".to_string(),
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::AsyncQueryRequest;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_create_async_query_rejects_invalid_target() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let request = AsyncQueryRequest::new("SELECT Id FROM Sale__b", "Bad'; DROP--")
+            .map_field("Id", "Sale__c");
+        let err = client.create_async_query(&request).await.unwrap_err();
+        assert!(err.to_string().contains("INVALID_SOBJECT"));
+
+        let request = AsyncQueryRequest::new("SELECT Id FROM Sale__b", "Summary__c");
+        let err = client.create_async_query(&request).await.unwrap_err();
+        assert!(err.to_string().contains("INVALID_REQUEST"));
+    }
+
+    #[tokio::test]
+    async fn test_async_query_lifecycle_wiremock() {
+        use wiremock::matchers::{body_partial_json, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let job = serde_json::json!({
+            "jobId": "08PD000000003kiMAA",
+            "operation": "insert",
+            "query": "SELECT Amount__c FROM Sale__b",
+            "status": "New",
+            "targetFieldMap": {"Amount__c": "Total__c"},
+            "targetObject": "Summary__c",
+            "targetValueMap": {"Batch__c": "'b1'"}
+        });
+        let mut complete = job.clone();
+        complete["status"] = "Complete".into();
+
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/async-queries"))
+            .and(body_partial_json(serde_json::json!({
+                "targetObject": "Summary__c",
+                "operation": "insert"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&job))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/services/data/v62.0/async-queries/08PD000000003kiMAA",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&complete))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .and(query_param(
+                "q",
+                "SELECT Batch__c, Total__c FROM Summary__c WHERE Batch__c = 'b1'",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [{
+                    "attributes": {"type": "Summary__c"},
+                    "Batch__c": "b1",
+                    "Total__c": 1250.0
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let request = AsyncQueryRequest::new("SELECT Amount__c FROM Sale__b", "Summary__c")
+            .map_field("Amount__c", "Total__c")
+            .with_value("Batch__c", "'b1'");
+        let created = client.create_async_query(&request).await.unwrap();
+        let done = client
+            .wait_for_async_query(
+                &created.job_id,
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+
+        #[derive(serde::Deserialize)]
+        struct Summary {
+            #[serde(rename = "Total__c")]
+            total: f64,
+        }
+        let rows: Vec<Summary> = client
+            .async_query_results(&done, Some("Batch__c = 'b1'"))
+            .await
+            .unwrap();
+        assert_eq!(rows[0].total, 1250.0);
+    }
+}
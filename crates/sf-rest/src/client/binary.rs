@@ -1,4 +1,9 @@
+use std::path::Path;
+
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
 use serde::de::DeserializeOwned;
+use tokio::io::AsyncWriteExt;
 use tracing::instrument;
 
 use busbar_sf_client::security::{soql, url as url_security};
@@ -7,27 +12,12 @@ use crate::error::{Error, ErrorKind, Result};
 
 impl super::SalesforceRestClient {
     /// Get binary blob content from an SObject field (e.g., Attachment body, Document body).
+    ///
+    /// The whole body is buffered in memory. For large files use
+    /// [`get_blob_stream`](Self::get_blob_stream) or [`save_blob`](Self::save_blob).
     #[instrument(skip(self))]
     pub async fn get_blob(&self, sobject: &str, id: &str, blob_field: &str) -> Result<Vec<u8>> {
-        if !soql::is_safe_sobject_name(sobject) {
-            return Err(Error::new(ErrorKind::Salesforce {
-                error_code: "INVALID_SOBJECT".to_string(),
-                message: "Invalid SObject name".to_string(),
-            }));
-        }
-        if !url_security::is_valid_salesforce_id(id) {
-            return Err(Error::new(ErrorKind::Salesforce {
-                error_code: "INVALID_ID".to_string(),
-                message: "Invalid Salesforce ID format".to_string(),
-            }));
-        }
-        if !soql::is_safe_field_name(blob_field) {
-            return Err(Error::new(ErrorKind::Salesforce {
-                error_code: "INVALID_FIELD".to_string(),
-                message: "Invalid field name".to_string(),
-            }));
-        }
-        let path = format!("sobjects/{}/{}/{}", sobject, id, blob_field);
+        let path = blob_path(sobject, id, blob_field)?;
         let url = self.client.rest_url(&path);
         let request = self.client.get(&url);
         let response = self.client.execute(request).await?;
@@ -35,6 +25,66 @@ impl super::SalesforceRestClient {
         Ok(bytes.to_vec())
     }
 
+    /// Stream binary blob content from an SObject field as it downloads.
+    ///
+    /// Suitable for ContentVersion bodies of hundreds of megabytes, which
+    /// would otherwise have to fit in memory.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut stream = client
+    ///     .get_blob_stream("ContentVersion", &id, "VersionData")
+    ///     .await?;
+    /// while let Some(chunk) = stream.try_next().await? {
+    ///     sink.write_all(&chunk).await?;
+    /// }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn get_blob_stream(
+        &self,
+        sobject: &str,
+        id: &str,
+        blob_field: &str,
+    ) -> Result<impl Stream<Item = Result<Bytes>> + Send> {
+        let path = blob_path(sobject, id, blob_field)?;
+        let url = self.client.rest_url(&path);
+        let request = self.client.get(&url);
+        let response = self.client.execute(request).await?;
+        Ok(response.bytes_stream().map_err(Error::from))
+    }
+
+    /// Download binary blob content from an SObject field straight to a file.
+    ///
+    /// Returns the number of bytes written.
+    #[instrument(skip(self, dest), fields(dest = %dest.as_ref().display()))]
+    pub async fn save_blob(
+        &self,
+        sobject: &str,
+        id: &str,
+        blob_field: &str,
+        dest: impl AsRef<Path>,
+    ) -> Result<u64> {
+        let dest = dest.as_ref();
+        let io_err = |e: std::io::Error| Error {
+            kind: ErrorKind::Other(format!("Failed to write {}: {}", dest.display(), e)),
+            source: Some(Box::new(e)),
+        };
+
+        let stream = self.get_blob_stream(sobject, id, blob_field).await?;
+        futures::pin_mut!(stream);
+        let mut file = tokio::fs::File::create(dest).await.map_err(io_err)?;
+        let mut written = 0u64;
+        while let Some(chunk) = stream.try_next().await? {
+            file.write_all(&chunk).await.map_err(io_err)?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await.map_err(io_err)?;
+        Ok(written)
+    }
+
     /// Get a rich text image from an SObject field.
     #[instrument(skip(self))]
     pub async fn get_rich_text_image(
@@ -123,6 +173,29 @@ impl super::SalesforceRestClient {
     }
 }
 
+/// Validate the parts of a blob field path and build it.
+fn blob_path(sobject: &str, id: &str, blob_field: &str) -> Result<String> {
+    if !soql::is_safe_sobject_name(sobject) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_SOBJECT".to_string(),
+            message: "Invalid SObject name".to_string(),
+        }));
+    }
+    if !url_security::is_valid_salesforce_id(id) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_ID".to_string(),
+            message: "Invalid Salesforce ID format".to_string(),
+        }));
+    }
+    if !soql::is_safe_field_name(blob_field) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_FIELD".to_string(),
+            message: "Invalid field name".to_string(),
+        }));
+    }
+    Ok(format!("sobjects/{}/{}/{}", sobject, id, blob_field))
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::SalesforceRestClient;
@@ -155,6 +228,50 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("INVALID_FIELD"));
     }
 
+    #[tokio::test]
+    async fn test_get_blob_stream_invalid_id() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client
+            .get_blob_stream("ContentVersion", "bad-id", "VersionData")
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_blob_stream_wiremock() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let content: Vec<u8> = (0..=255u8).cycle().take(256 * 1024).collect();
+
+        Mock::given(method("GET"))
+            .and(path_regex(
+                ".*/sobjects/ContentVersion/068xx0000000001AAA/VersionData$",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(content.clone()))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let stream = client
+            .get_blob_stream("ContentVersion", "068xx0000000001AAA", "VersionData")
+            .await
+            .expect("get_blob_stream should succeed");
+        let chunks: Vec<_> = stream.try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), content);
+
+        let dest = std::env::temp_dir().join(format!("busbar-blob-{}", std::process::id()));
+        let written = client
+            .save_blob("ContentVersion", "068xx0000000001AAA", "VersionData", &dest)
+            .await
+            .expect("save_blob should succeed");
+        assert_eq!(written, content.len() as u64);
+        assert_eq!(std::fs::read(&dest).unwrap(), content);
+        std::fs::remove_file(&dest).ok();
+    }
+
     #[tokio::test]
     async fn test_get_rich_text_image_invalid_sobject() {
         let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
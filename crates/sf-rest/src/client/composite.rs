@@ -1,10 +1,12 @@
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use tracing::instrument;
 
 use busbar_sf_client::security::soql;
 
 use crate::composite::{
     CompositeBatchRequest, CompositeBatchResponse, CompositeGraphRequest, CompositeGraphResponse,
-    CompositeRequest, CompositeResponse, CompositeTreeRequest, CompositeTreeResponse,
+    CompositeRequest, CompositeResponse, CompositeSubresponse, CompositeTreeRequest,
+    CompositeTreeResponse,
 };
 use crate::error::{Error, ErrorKind, Result};
 
@@ -23,6 +25,68 @@ impl super::SalesforceRestClient {
             .map_err(Into::into)
     }
 
+    /// Execute a composite request of any size.
+    ///
+    /// The request is [split](CompositeRequest::split) into calls of at
+    /// most 25 subrequests, run one after another, and the subresponses are
+    /// returned in order. `all_or_none` applies to each call separately:
+    /// once a call fails, the calls after it are not made, so the response
+    /// holds fewer subresponses than the request had subrequests, and the
+    /// work of earlier calls is not rolled back.
+    #[instrument(skip(self, request), fields(subrequests = request.subrequests.len()))]
+    pub async fn composite_chunked(&self, request: &CompositeRequest) -> Result<CompositeResponse> {
+        let responses = self.composite_stream(request)?.try_collect().await?;
+        Ok(CompositeResponse { responses })
+    }
+
+    /// Execute a composite request of any size, yielding subresponses as
+    /// each call completes.
+    ///
+    /// Behaves like [`composite_chunked`](Self::composite_chunked). The
+    /// request is split up front, so a reference that can't be kept within
+    /// one call fails here, before anything is sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut responses = client.composite_stream(&request)?;
+    /// while let Some(response) = responses.try_next().await? {
+    ///     println!("{}: {}", response.reference_id, response.http_status_code);
+    /// }
+    /// ```
+    pub fn composite_stream<'a>(
+        &'a self,
+        request: &CompositeRequest,
+    ) -> Result<impl Stream<Item = Result<CompositeSubresponse>> + Send + 'a> {
+        let chunks = request.split()?;
+        let all_or_none = request.all_or_none;
+        let calls = stream::unfold(
+            (chunks.into_iter(), false),
+            move |(mut chunks, stopped)| async move {
+                if stopped {
+                    return None;
+                }
+                let chunk = chunks.next()?;
+                match self.composite(&chunk).await {
+                    Ok(response) => {
+                        let failed = all_or_none
+                            && response.responses.iter().any(|r| r.http_status_code >= 400);
+                        Some((Ok(response.responses), (chunks, failed)))
+                    }
+                    Err(e) => Some((Err(e), (chunks, true))),
+                }
+            },
+        );
+        Ok(calls.flat_map(|call| {
+            stream::iter(match call {
+                Ok(responses) => responses.into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            })
+        }))
+    }
+
     /// Execute a composite batch request with multiple independent subrequests.
     ///
     /// The composite batch API executes up to 25 subrequests independently.
@@ -86,3 +150,88 @@ impl super::SalesforceRestClient {
             .map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::composite::{CompositeRequest, CompositeSubrequest};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+    /// Answers each composite call with one response per subrequest,
+    /// failing the subrequest named `fail`.
+    struct EchoComposite {
+        fail: &'static str,
+    }
+
+    impl Respond for EchoComposite {
+        fn respond(&self, request: &Request) -> ResponseTemplate {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let responses: Vec<_> = body["compositeRequest"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|sub| {
+                    let reference_id = sub["referenceId"].as_str().unwrap();
+                    let status = if reference_id == self.fail { 400 } else { 201 };
+                    serde_json::json!({
+                        "body": {},
+                        "httpHeaders": {},
+                        "httpStatusCode": status,
+                        "referenceId": reference_id
+                    })
+                })
+                .collect();
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"compositeResponse": responses}))
+        }
+    }
+
+    fn request(count: usize, all_or_none: bool) -> CompositeRequest {
+        CompositeRequest {
+            all_or_none,
+            collate_subrequests: false,
+            subrequests: (0..count)
+                .map(|i| CompositeSubrequest {
+                    method: "POST".to_string(),
+                    url: "/services/data/v62.0/sobjects/Contact".to_string(),
+                    reference_id: format!("c{i}"),
+                    body: Some(serde_json::json!({"LastName": "x"})),
+                    http_headers: Default::default(),
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_composite_chunked_wiremock() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/composite"))
+            .respond_with(EchoComposite { fail: "c30" })
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let client = SalesforceRestClient::new(server.uri(), "token").unwrap();
+        let response = client.composite_chunked(&request(60, false)).await.unwrap();
+        assert_eq!(response.responses.len(), 60);
+        assert_eq!(response.responses[59].reference_id, "c59");
+        assert_eq!(response.responses[30].http_status_code, 400);
+    }
+
+    #[tokio::test]
+    async fn test_composite_chunked_stops_after_failed_all_or_none_chunk() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/composite"))
+            .respond_with(EchoComposite { fail: "c30" })
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = SalesforceRestClient::new(server.uri(), "token").unwrap();
+        let response = client.composite_chunked(&request(60, true)).await.unwrap();
+        assert_eq!(response.responses.len(), 50);
+    }
+}
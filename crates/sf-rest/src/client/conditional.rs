@@ -0,0 +1,318 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::instrument;
+
+use busbar_sf_client::RequestBuilder;
+
+use crate::conditional::{CacheValidators, Conditional, WritePrecondition};
+use crate::describe::{DescribeGlobalResult, DescribeSObjectResult};
+use crate::error::Result;
+
+use super::crud::record_path;
+use super::describe::describe_path;
+
+impl super::SalesforceRestClient {
+    /// Describe all SObjects unless unchanged since `validators`.
+    ///
+    /// Long-lived callers can cache the describe with its validators and
+    /// skip re-downloading it while the org's schema is unchanged.
+    #[instrument(skip(self))]
+    pub async fn describe_global_if_modified(
+        &self,
+        validators: &CacheValidators,
+    ) -> Result<Conditional<DescribeGlobalResult>> {
+        self.get_conditional("sobjects", validators).await
+    }
+
+    /// Describe an SObject unless unchanged since `validators`.
+    #[instrument(skip(self))]
+    pub async fn describe_sobject_if_modified(
+        &self,
+        sobject: &str,
+        validators: &CacheValidators,
+    ) -> Result<Conditional<DescribeSObjectResult>> {
+        let path = describe_path(sobject)?;
+        self.get_conditional(&path, validators).await
+    }
+
+    /// Get a record unless unchanged since `validators`.
+    ///
+    /// Takes the same arguments as [`get`](Self::get).
+    #[instrument(skip(self))]
+    pub async fn get_if_modified<T: DeserializeOwned>(
+        &self,
+        sobject: &str,
+        id: &str,
+        fields: Option<&[&str]>,
+        validators: &CacheValidators,
+    ) -> Result<Conditional<T>> {
+        let path = record_path(sobject, id, fields)?;
+        self.get_conditional(&path, validators).await
+    }
+
+    /// Update a record only if it is unchanged since it was read.
+    ///
+    /// If the record no longer meets `precondition` the update is refused
+    /// and the error's
+    /// [`is_precondition_failed`](crate::Error::is_precondition_failed) is
+    /// true; re-read the record and retry.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let Conditional::Modified { value, validators } =
+    ///     client.get_if_modified::<Account>("Account", &id, None, &Default::default()).await?
+    /// else { unreachable!() };
+    /// let precondition = WritePrecondition::from_validators(&validators)
+    ///     .expect("Salesforce returns Last-Modified for records");
+    /// client
+    ///     .update_if_unchanged("Account", &id, &json!({"Name": "Renamed"}), &precondition)
+    ///     .await?;
+    /// ```
+    #[instrument(skip(self, record))]
+    pub async fn update_if_unchanged<T: Serialize>(
+        &self,
+        sobject: &str,
+        id: &str,
+        record: &T,
+        precondition: &WritePrecondition,
+    ) -> Result<()> {
+        let path = record_path(sobject, id, None)?;
+        let request = self
+            .client
+            .patch(&self.client.rest_url(&path))
+            .json(record)?;
+        self.client
+            .execute(with_precondition(request, precondition))
+            .await?;
+        Ok(())
+    }
+
+    /// Delete a record only if it is unchanged since it was read.
+    ///
+    /// Fails like [`update_if_unchanged`](Self::update_if_unchanged).
+    #[instrument(skip(self))]
+    pub async fn delete_if_unchanged(
+        &self,
+        sobject: &str,
+        id: &str,
+        precondition: &WritePrecondition,
+    ) -> Result<()> {
+        let path = record_path(sobject, id, None)?;
+        let request = self.client.delete(&self.client.rest_url(&path));
+        self.client
+            .execute(with_precondition(request, precondition))
+            .await?;
+        Ok(())
+    }
+
+    async fn get_conditional<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        validators: &CacheValidators,
+    ) -> Result<Conditional<T>> {
+        let mut request = self.client.get(&self.client.rest_url(path));
+        if let Some(ref etag) = validators.etag {
+            request = request.if_none_match(etag);
+        }
+        if let Some(ref timestamp) = validators.last_modified {
+            request = request.if_modified_since(timestamp);
+        }
+
+        let response = self.client.execute(request).await?;
+        if response.is_not_modified() {
+            return Ok(Conditional::NotModified);
+        }
+        let validators = CacheValidators {
+            etag: response.etag().map(str::to_string),
+            last_modified: response.last_modified().map(str::to_string),
+        };
+        let value = response.json().await?;
+        Ok(Conditional::Modified { value, validators })
+    }
+}
+
+fn with_precondition(request: RequestBuilder, precondition: &WritePrecondition) -> RequestBuilder {
+    match precondition {
+        WritePrecondition::IfMatch(etag) => request.if_match(etag),
+        WritePrecondition::IfUnmodifiedSince(timestamp) => request.if_unmodified_since(timestamp),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::{CacheValidators, Conditional};
+
+    #[tokio::test]
+    async fn test_describe_sobject_if_modified() {
+        use wiremock::matchers::{header_exists, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/sobjects/Account/describe"))
+            .and(header_exists("If-Modified-Since"))
+            .respond_with(ResponseTemplate::new(304))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let result = client
+            .describe_sobject_if_modified(
+                "Account",
+                &CacheValidators::modified_since("Wed, 21 Oct 2015 07:28:00 GMT"),
+            )
+            .await
+            .expect("conditional describe should succeed");
+        assert!(result.is_not_modified());
+
+        // HTTP dates contain a comma, which wiremock's header matcher splits on.
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].headers["If-Modified-Since"],
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+
+        assert!(client
+            .describe_sobject_if_modified("Bad Name", &CacheValidators::default())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_if_modified_returns_validators() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(
+                "/services/data/v62.0/sobjects/Account/001000000000001AAA",
+            ))
+            .and(header("If-None-Match", "\"old\""))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"new\"")
+                    .insert_header("Last-Modified", "Thu, 22 Oct 2015 07:28:00 GMT")
+                    .set_body_json(serde_json::json!({"Id": "001000000000001AAA"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let result: Conditional<serde_json::Value> = client
+            .get_if_modified(
+                "Account",
+                "001000000000001AAA",
+                None,
+                &CacheValidators::etag("\"old\""),
+            )
+            .await
+            .expect("conditional get should succeed");
+        match result {
+            Conditional::Modified { value, validators } => {
+                assert_eq!(value["Id"], "001000000000001AAA");
+                assert_eq!(validators.etag.as_deref(), Some("\"new\""));
+                assert_eq!(
+                    validators.last_modified.as_deref(),
+                    Some("Thu, 22 Oct 2015 07:28:00 GMT")
+                );
+            }
+            Conditional::NotModified => panic!("expected a fresh record"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_if_unchanged_detects_concurrent_change() {
+        use crate::WritePrecondition;
+        use wiremock::matchers::{body_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PATCH"))
+            .and(path(
+                "/services/data/v62.0/sobjects/Account/001000000000001AAA",
+            ))
+            .and(header("If-Match", "\"v1\""))
+            .and(body_json(serde_json::json!({"Name": "Renamed"})))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path(
+                "/services/data/v62.0/sobjects/Account/001000000000001AAA",
+            ))
+            .and(header("If-Match", "\"stale\""))
+            .respond_with(
+                ResponseTemplate::new(412).set_body_json(serde_json::json!([{
+                    "errorCode": "PRECONDITION_FAILED",
+                    "message": "The entity has been modified"
+                }])),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let record = serde_json::json!({"Name": "Renamed"});
+        client
+            .update_if_unchanged(
+                "Account",
+                "001000000000001AAA",
+                &record,
+                &WritePrecondition::IfMatch("\"v1\"".to_string()),
+            )
+            .await
+            .expect("matching ETag should update");
+
+        let err = client
+            .update_if_unchanged(
+                "Account",
+                "001000000000001AAA",
+                &record,
+                &WritePrecondition::IfMatch("\"stale\"".to_string()),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.is_precondition_failed(), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_delete_if_unchanged_sends_if_unmodified_since() {
+        use crate::WritePrecondition;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path(
+                "/services/data/v62.0/sobjects/Account/001000000000001AAA",
+            ))
+            .respond_with(ResponseTemplate::new(412))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let time = "2015-10-21T07:28:00Z".parse().unwrap();
+        let err = client
+            .delete_if_unchanged(
+                "Account",
+                "001000000000001AAA",
+                &WritePrecondition::unmodified_since(time),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.is_precondition_failed());
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].headers["If-Unmodified-Since"],
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+    }
+}
@@ -5,13 +5,14 @@ use busbar_sf_client::security::{soql, url as url_security};
 
 use crate::error::{Error, ErrorKind, Result};
 use crate::sobject::{CreateResult, UpsertResult};
+use crate::types::SalesforceId;
 
 impl super::SalesforceRestClient {
     /// Create a new record.
     ///
     /// Returns the ID of the created record.
     #[instrument(skip(self, record))]
-    pub async fn create<T: Serialize>(&self, sobject: &str, record: &T) -> Result<String> {
+    pub async fn create<T: Serialize>(&self, sobject: &str, record: &T) -> Result<SalesforceId> {
         if !soql::is_safe_sobject_name(sobject) {
             return Err(Error::new(ErrorKind::Salesforce {
                 error_code: "INVALID_SOBJECT".to_string(),
@@ -21,14 +22,15 @@ impl super::SalesforceRestClient {
         let path = format!("sobjects/{}", sobject);
         let result: CreateResult = self.client.rest_post(&path, record).await?;
 
-        if result.success {
-            Ok(result.id)
-        } else {
-            let errors: Vec<String> = result.errors.iter().map(|e| e.message.clone()).collect();
-            Err(Error::new(ErrorKind::Salesforce {
-                error_code: "CREATE_FAILED".to_string(),
-                message: errors.join("; "),
-            }))
+        match result.id {
+            Some(id) if result.success => Ok(id),
+            _ => {
+                let errors: Vec<String> = result.errors.iter().map(|e| e.message.clone()).collect();
+                Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "CREATE_FAILED".to_string(),
+                    message: errors.join("; "),
+                }))
+            }
         }
     }
 
@@ -42,36 +44,7 @@ impl super::SalesforceRestClient {
         id: &str,
         fields: Option<&[&str]>,
     ) -> Result<T> {
-        if !soql::is_safe_sobject_name(sobject) {
-            return Err(Error::new(ErrorKind::Salesforce {
-                error_code: "INVALID_SOBJECT".to_string(),
-                message: "Invalid SObject name".to_string(),
-            }));
-        }
-        if !url_security::is_valid_salesforce_id(id) {
-            return Err(Error::new(ErrorKind::Salesforce {
-                error_code: "INVALID_ID".to_string(),
-                message: "Invalid Salesforce ID format".to_string(),
-            }));
-        }
-        let path = if let Some(fields) = fields {
-            // Validate and filter field names for safety
-            let safe_fields: Vec<&str> = soql::filter_safe_fields(fields.iter().copied()).collect();
-            if safe_fields.is_empty() {
-                return Err(Error::new(ErrorKind::Salesforce {
-                    error_code: "INVALID_FIELDS".to_string(),
-                    message: "No valid field names provided".to_string(),
-                }));
-            }
-            format!(
-                "sobjects/{}/{}?fields={}",
-                sobject,
-                id,
-                safe_fields.join(",")
-            )
-        } else {
-            format!("sobjects/{}/{}", sobject, id)
-        };
+        let path = record_path(sobject, id, fields)?;
         self.client.rest_get(&path).await.map_err(Into::into)
     }
 
@@ -127,24 +100,7 @@ impl super::SalesforceRestClient {
         external_id_value: &str,
         record: &T,
     ) -> Result<UpsertResult> {
-        if !soql::is_safe_sobject_name(sobject) {
-            return Err(Error::new(ErrorKind::Salesforce {
-                error_code: "INVALID_SOBJECT".to_string(),
-                message: "Invalid SObject name".to_string(),
-            }));
-        }
-        if !soql::is_safe_field_name(external_id_field) {
-            return Err(Error::new(ErrorKind::Salesforce {
-                error_code: "INVALID_FIELD".to_string(),
-                message: "Invalid external ID field name".to_string(),
-            }));
-        }
-        // URL-encode the external ID value to handle special characters
-        let encoded_value = url_security::encode_param(external_id_value);
-        let path = format!(
-            "sobjects/{}/{}/{}",
-            sobject, external_id_field, encoded_value
-        );
+        let path = external_id_path(sobject, external_id_field, external_id_value)?;
         let url = self.client.rest_url(&path);
         let request = self.client.patch(&url).json(record)?;
         let response = self.client.execute(request).await?;
@@ -170,6 +126,93 @@ impl super::SalesforceRestClient {
             }))
         }
     }
+
+    /// Get a record by an external ID field instead of its Salesforce ID.
+    ///
+    /// Fails with a `300 Multiple Choices` error if the field is not unique
+    /// and more than one record matches.
+    #[instrument(skip(self))]
+    pub async fn get_by_external_id<T: DeserializeOwned>(
+        &self,
+        sobject: &str,
+        external_id_field: &str,
+        external_id_value: &str,
+    ) -> Result<T> {
+        let path = external_id_path(sobject, external_id_field, external_id_value)?;
+        self.client.rest_get(&path).await.map_err(Into::into)
+    }
+
+    /// Delete a record by an external ID field instead of its Salesforce ID.
+    #[instrument(skip(self))]
+    pub async fn delete_by_external_id(
+        &self,
+        sobject: &str,
+        external_id_field: &str,
+        external_id_value: &str,
+    ) -> Result<()> {
+        let path = external_id_path(sobject, external_id_field, external_id_value)?;
+        self.client.rest_delete(&path).await.map_err(Into::into)
+    }
+}
+
+/// Build `sobjects/{type}/{field}/{value}` after validating the names.
+fn external_id_path(
+    sobject: &str,
+    external_id_field: &str,
+    external_id_value: &str,
+) -> Result<String> {
+    if !soql::is_safe_sobject_name(sobject) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_SOBJECT".to_string(),
+            message: "Invalid SObject name".to_string(),
+        }));
+    }
+    if !soql::is_safe_field_name(external_id_field) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_FIELD".to_string(),
+            message: "Invalid external ID field name".to_string(),
+        }));
+    }
+    // URL-encode the external ID value to handle special characters
+    let encoded_value = url_security::encode_param(external_id_value);
+    Ok(format!(
+        "sobjects/{}/{}/{}",
+        sobject, external_id_field, encoded_value
+    ))
+}
+
+/// Build the `sobjects/{sobject}/{id}` path, validating its parts.
+pub(super) fn record_path(sobject: &str, id: &str, fields: Option<&[&str]>) -> Result<String> {
+    if !soql::is_safe_sobject_name(sobject) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_SOBJECT".to_string(),
+            message: "Invalid SObject name".to_string(),
+        }));
+    }
+    if !url_security::is_valid_salesforce_id(id) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_ID".to_string(),
+            message: "Invalid Salesforce ID format".to_string(),
+        }));
+    }
+    Ok(if let Some(fields) = fields {
+        // Validate and filter field names for safety
+        let safe_fields: Vec<&str> = soql::filter_safe_fields(fields.iter().copied()).collect();
+        if safe_fields.is_empty() {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_FIELDS".to_string(),
+                message: "No valid field names provided".to_string(),
+            }));
+        }
+        format!(
+            "sobjects/{}/{}?fields={}",
+            sobject,
+            id,
+            safe_fields.join(",")
+        )
+    } else {
+        format!("sobjects/{}/{}", sobject, id)
+    })
 }
 
 #[cfg(test)]
@@ -289,4 +332,63 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("INVALID_FIELD"));
     }
+
+    #[tokio::test]
+    async fn test_get_by_external_id_wiremock() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(
+                "/services/data/v62.0/sobjects/Account/ExtId__c/ext%2F123",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "attributes": {"type": "Account"},
+                "Id": "001xx000003DgAAAS",
+                "ExtId__c": "ext/123"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let record: serde_json::Value = client
+            .get_by_external_id("Account", "ExtId__c", "ext/123")
+            .await
+            .expect("get_by_external_id should succeed");
+        assert_eq!(record["Id"], "001xx000003DgAAAS");
+    }
+
+    #[tokio::test]
+    async fn test_delete_by_external_id_wiremock() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path(
+                "/services/data/v62.0/sobjects/Account/ExtId__c/ext-123",
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        client
+            .delete_by_external_id("Account", "ExtId__c", "ext-123")
+            .await
+            .expect("delete_by_external_id should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_delete_by_external_id_invalid_field() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client
+            .delete_by_external_id("Account", "Bad'; DROP--", "123")
+            .await;
+        assert!(result.unwrap_err().to_string().contains("INVALID_FIELD"));
+    }
 }
@@ -2,7 +2,7 @@ use tracing::instrument;
 
 use busbar_sf_client::security::soql;
 
-use crate::describe::{DescribeGlobalResult, DescribeSObjectResult};
+use crate::describe::{DescribeGlobalResult, DescribeSObjectResult, DescribeWithLayouts};
 use crate::error::{Error, ErrorKind, Result};
 
 impl super::SalesforceRestClient {
@@ -17,15 +17,87 @@ impl super::SalesforceRestClient {
     /// Get detailed metadata for a specific SObject.
     ///
     /// This is equivalent to calling `/services/data/vXX.0/sobjects/{sobject}/describe`.
+    /// With a [`DescribeCache`](crate::DescribeCache) attached, the describe
+    /// is served from the cache when it is fresh.
     #[instrument(skip(self))]
     pub async fn describe_sobject(&self, sobject: &str) -> Result<DescribeSObjectResult> {
-        if !soql::is_safe_sobject_name(sobject) {
-            return Err(Error::new(ErrorKind::Salesforce {
-                error_code: "INVALID_SOBJECT".to_string(),
-                message: "Invalid SObject name".to_string(),
-            }));
+        if let Some(cache) = &self.describe_cache {
+            return cache.describe(self, sobject).await;
         }
-        let path = format!("sobjects/{}/describe", sobject);
+        let path = describe_path(sobject)?;
         self.client.rest_get(&path).await.map_err(Into::into)
     }
+
+    /// Describe an SObject and its page layouts in one call.
+    ///
+    /// Both requests are sent concurrently. Labels and picklist values in
+    /// each are translated when the client's
+    /// [`CallOptions`](crate::CallOptions) set an `Accept-Language`.
+    #[instrument(skip(self))]
+    pub async fn describe_sobject_with_layouts(
+        &self,
+        sobject: &str,
+    ) -> Result<DescribeWithLayouts> {
+        let (describe, layouts) = futures::try_join!(
+            self.describe_sobject(sobject),
+            self.describe_layouts(sobject)
+        )?;
+        Ok(DescribeWithLayouts { describe, layouts })
+    }
+}
+
+/// Build the `sobjects/{sobject}/describe` path, validating the name.
+pub(super) fn describe_path(sobject: &str) -> Result<String> {
+    if !soql::is_safe_sobject_name(sobject) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_SOBJECT".to_string(),
+            message: "Invalid SObject name".to_string(),
+        }));
+    }
+    Ok(format!("sobjects/{}/describe", sobject))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::CallOptions;
+
+    #[tokio::test]
+    async fn test_localized_describe_with_layouts() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/sobjects/Account/describe"))
+            .and(header("Accept-Language", "de"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "Account",
+                "label": "Firma",
+                "custom": false,
+                "fields": []
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/services/data/v62.0/sobjects/Account/describe/layouts",
+            ))
+            .and(header("Accept-Language", "de"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "layouts": [{"id": "00hxx0000000001"}]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token")
+            .unwrap()
+            .with_call_options(CallOptions::new().with_accept_language("de"));
+        let result = client
+            .describe_sobject_with_layouts("Account")
+            .await
+            .unwrap();
+        assert_eq!(result.describe.label, "Firma");
+        assert_eq!(result.layouts["layouts"][0]["id"], "00hxx0000000001");
+    }
 }
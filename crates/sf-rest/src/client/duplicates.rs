@@ -0,0 +1,229 @@
+use serde::Serialize;
+use tracing::instrument;
+
+use busbar_sf_client::security::{soql, url as url_security};
+
+use crate::duplicates::{
+    DuplicateDetectResponse, DuplicateResult, DuplicateRuleHeader, RecordDuplicatesResponse,
+    DUPLICATE_RULE_HEADER,
+};
+use crate::error::{Error, ErrorKind, Result};
+use crate::sobject::CreateResult;
+use crate::types::SalesforceId;
+
+impl super::SalesforceRestClient {
+    /// Get the duplicates that active duplicate rules find for an existing record.
+    #[instrument(skip(self))]
+    pub async fn record_duplicates(&self, sobject: &str, id: &str) -> Result<Vec<DuplicateResult>> {
+        validate_sobject(sobject)?;
+        validate_id(id)?;
+        let path = format!("sobjects/{}/{}/duplicates", sobject, id);
+        let response: RecordDuplicatesResponse = self.client.rest_get(&path).await?;
+        Ok(response.duplicate_results)
+    }
+
+    /// Run duplicate rules against unsaved records without saving them.
+    ///
+    /// Each record is serialized as-is; the `attributes.type` of every
+    /// record is set to `sobject`.
+    #[instrument(skip(self, records))]
+    pub async fn detect_duplicates<T: Serialize>(
+        &self,
+        sobject: &str,
+        records: &[T],
+    ) -> Result<DuplicateDetectResponse> {
+        validate_sobject(sobject)?;
+        let records = records
+            .iter()
+            .map(|r| {
+                let mut value = serde_json::to_value(r).map_err(|e| Error {
+                    kind: ErrorKind::Other(format!("Failed to serialize record: {}", e)),
+                    source: Some(Box::new(e)),
+                })?;
+                if let Some(obj) = value.as_object_mut() {
+                    obj.insert(
+                        "attributes".to_string(),
+                        serde_json::json!({ "type": sobject }),
+                    );
+                }
+                Ok(value)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let body = serde_json::json!({ "records": records });
+        self.client
+            .rest_post("duplicates/detect", &body)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Create a record with a `Sforce-Duplicate-Rule-Header`.
+    ///
+    /// When a blocking rule fires, the error code is `DUPLICATES_DETECTED`;
+    /// use [`detect_duplicates`](Self::detect_duplicates) to inspect matches.
+    #[instrument(skip(self, record))]
+    pub async fn create_with_duplicate_rule<T: Serialize>(
+        &self,
+        sobject: &str,
+        record: &T,
+        header: &DuplicateRuleHeader,
+    ) -> Result<SalesforceId> {
+        validate_sobject(sobject)?;
+        let url = self.client.rest_url(&format!("sobjects/{}", sobject));
+        let request = self
+            .client
+            .post(&url)
+            .header(DUPLICATE_RULE_HEADER, header.header_value())
+            .header("Accept", "application/json")
+            .json(record)?;
+        let result: CreateResult = self.client.execute(request).await?.json().await?;
+
+        match result.id {
+            Some(id) if result.success => Ok(id),
+            _ => {
+                let errors: Vec<String> = result.errors.iter().map(|e| e.message.clone()).collect();
+                Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "CREATE_FAILED".to_string(),
+                    message: errors.join("; "),
+                }))
+            }
+        }
+    }
+
+    /// Update a record with a `Sforce-Duplicate-Rule-Header`.
+    #[instrument(skip(self, record))]
+    pub async fn update_with_duplicate_rule<T: Serialize>(
+        &self,
+        sobject: &str,
+        id: &str,
+        record: &T,
+        header: &DuplicateRuleHeader,
+    ) -> Result<()> {
+        validate_sobject(sobject)?;
+        validate_id(id)?;
+        let url = self
+            .client
+            .rest_url(&format!("sobjects/{}/{}", sobject, id));
+        let request = self
+            .client
+            .patch(&url)
+            .header(DUPLICATE_RULE_HEADER, header.header_value())
+            .json(record)?;
+        self.client.execute(request).await?;
+        Ok(())
+    }
+}
+
+fn validate_sobject(sobject: &str) -> Result<()> {
+    if soql::is_safe_sobject_name(sobject) {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_SOBJECT".to_string(),
+            message: "Invalid SObject name".to_string(),
+        }))
+    }
+}
+
+fn validate_id(id: &str) -> Result<()> {
+    if url_security::is_valid_salesforce_id(id) {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_ID".to_string(),
+            message: "Invalid Salesforce ID format".to_string(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::duplicates::DuplicateRuleHeader;
+
+    #[tokio::test]
+    async fn test_record_duplicates_invalid_id() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client.record_duplicates("Contact", "bad").await;
+        assert!(result.unwrap_err().to_string().contains("INVALID_ID"));
+    }
+
+    #[tokio::test]
+    async fn test_create_with_duplicate_rule_wiremock() {
+        use wiremock::matchers::{header, method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path_regex(".*/sobjects/Lead$"))
+            .and(header(
+                "Sforce-Duplicate-Rule-Header",
+                "allowSave=true; includeRecordDuplicates=false; runAsCurrentUser=true",
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "00Qxx0000000001EAA",
+                "success": true,
+                "errors": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let id = client
+            .create_with_duplicate_rule(
+                "Lead",
+                &serde_json::json!({"LastName": "Smith", "Company": "Acme"}),
+                &DuplicateRuleHeader::allow_save(),
+            )
+            .await
+            .expect("create should succeed");
+        assert_eq!(id, "00Qxx0000000001EAA");
+    }
+
+    #[tokio::test]
+    async fn test_detect_duplicates_wiremock() {
+        use wiremock::matchers::{body_partial_json, method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path_regex(".*/duplicates/detect$"))
+            .and(body_partial_json(serde_json::json!({
+                "records": [{"attributes": {"type": "Contact"}, "Email": "a@example.com"}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{
+                    "duplicateResults": [{
+                        "allowSave": false,
+                        "duplicateRule": "Block_Contacts",
+                        "duplicateRuleEntityType": "Contact",
+                        "matchResults": [{
+                            "entityType": "Contact",
+                            "rule": "Email_Match",
+                            "size": 1,
+                            "success": true,
+                            "matchRecords": [{
+                                "matchConfidence": 100.0,
+                                "record": {"Id": "003xx0000000001AAA"}
+                            }]
+                        }]
+                    }]
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let response = client
+            .detect_duplicates("Contact", &[serde_json::json!({"Email": "a@example.com"})])
+            .await
+            .expect("detect_duplicates should succeed");
+        let dup = &response.results[0].duplicate_results[0];
+        assert_eq!(dup.duplicate_rule, "Block_Contacts");
+        assert_eq!(
+            dup.match_results[0].match_records[0].record["Id"],
+            "003xx0000000001AAA"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::path::Path;
+
+use bytes::Bytes;
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use tracing::instrument;
+
+use busbar_sf_client::security::soql;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::event_log::{parse_event_log, EventLogFile, EventLogFileFilter};
+
+impl super::SalesforceRestClient {
+    /// List `EventLogFile` records matching a filter, newest first.
+    ///
+    /// Requires the "View Event Log Files" permission. Orgs without Event
+    /// Monitoring only get `Login`, `Logout`, and `ApiTotalUsage` logs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use sf_rest::EventLogFileFilter;
+    ///
+    /// let files = client
+    ///     .event_log_files(
+    ///         &EventLogFileFilter::new()
+    ///             .event_type("Login")
+    ///             .since(Utc::now() - chrono::Duration::days(7)),
+    ///     )
+    ///     .await?;
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn event_log_files(&self, filter: &EventLogFileFilter) -> Result<Vec<EventLogFile>> {
+        let mut conditions = Vec::new();
+        if let Some(event_type) = &filter.event_type {
+            if !soql::is_safe_field_name(event_type) {
+                return Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "INVALID_EVENT_TYPE".to_string(),
+                    message: format!("Invalid event type: {}", event_type),
+                }));
+            }
+            conditions.push(format!("EventType = '{}'", event_type));
+        }
+        if let Some(interval) = &filter.interval {
+            conditions.push(format!("Interval = '{}'", soql::escape_string(interval)));
+        }
+        if let Some(since) = &filter.since {
+            conditions.push(format!("LogDate >= {}", since.format("%Y-%m-%dT%H:%M:%SZ")));
+        }
+
+        let mut query = String::from(
+            "SELECT Id, EventType, LogDate, LogFileLength, Interval, Sequence, CreatedDate \
+             FROM EventLogFile",
+        );
+        if !conditions.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&conditions.join(" AND "));
+        }
+        query.push_str(" ORDER BY LogDate DESC, Sequence DESC");
+
+        self.query_all(&query).await
+    }
+
+    /// Stream the CSV content of an event log file as it downloads.
+    ///
+    /// Daily log files for busy orgs can run to hundreds of megabytes.
+    #[instrument(skip(self))]
+    pub async fn event_log_file_stream(
+        &self,
+        log_file_id: &str,
+    ) -> Result<impl Stream<Item = Result<Bytes>> + Send> {
+        self.get_blob_stream("EventLogFile", log_file_id, "LogFile")
+            .await
+    }
+
+    /// Download the CSV content of an event log file to disk.
+    ///
+    /// Returns the number of bytes written.
+    #[instrument(skip(self, dest))]
+    pub async fn save_event_log_file(
+        &self,
+        log_file_id: &str,
+        dest: impl AsRef<Path>,
+    ) -> Result<u64> {
+        self.save_blob("EventLogFile", log_file_id, "LogFile", dest)
+            .await
+    }
+
+    /// Download an event log file and decode its rows.
+    ///
+    /// Use [`LoginEvent`](crate::LoginEvent), [`ApiEvent`](crate::ApiEvent),
+    /// [`RestApiEvent`](crate::RestApiEvent), or your own struct keyed by the
+    /// CSV column names. The whole file is buffered in memory.
+    #[instrument(skip(self))]
+    pub async fn event_log_records<T: DeserializeOwned>(
+        &self,
+        log_file_id: &str,
+    ) -> Result<Vec<T>> {
+        let csv_data = self
+            .get_blob("EventLogFile", log_file_id, "LogFile")
+            .await?;
+        parse_event_log(&csv_data).map_err(|e| Error {
+            kind: ErrorKind::Other(format!("Failed to parse event log: {}", e)),
+            source: Some(Box::new(e)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::event_log::{EventLogFileFilter, LoginEvent};
+
+    #[tokio::test]
+    async fn test_event_log_files_invalid_type() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client
+            .event_log_files(&EventLogFileFilter::new().event_type("Login' OR Id != '"))
+            .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("INVALID_EVENT_TYPE"));
+    }
+
+    #[tokio::test]
+    async fn test_event_log_files_wiremock() {
+        use wiremock::matchers::{method, path_regex, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/query$"))
+            .and(query_param(
+                "q",
+                "SELECT Id, EventType, LogDate, LogFileLength, Interval, Sequence, CreatedDate \
+                 FROM EventLogFile WHERE EventType = 'Login' AND LogDate >= 2024-01-01T00:00:00Z \
+                 ORDER BY LogDate DESC, Sequence DESC",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [{
+                    "attributes": {"type": "EventLogFile"},
+                    "Id": "0ATxx0000000001AAA",
+                    "EventType": "Login",
+                    "LogDate": "2024-01-04T00:00:00.000+0000",
+                    "LogFileLength": 120.0,
+                    "Interval": "Daily",
+                    "Sequence": 0,
+                    "CreatedDate": "2024-01-05T03:12:00.000+0000"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let since = "2024-01-01T00:00:00Z".parse().unwrap();
+        let files = client
+            .event_log_files(&EventLogFileFilter::new().event_type("Login").since(since))
+            .await
+            .expect("event_log_files should succeed");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].id, "0ATxx0000000001AAA");
+    }
+
+    #[tokio::test]
+    async fn test_event_log_records_wiremock() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(
+                ".*/sobjects/EventLogFile/0ATxx0000000001AAA/LogFile$",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "\"EVENT_TYPE\",\"TIMESTAMP\",\"USER_ID\",\"LOGIN_STATUS\"\n\
+                 \"Login\",\"20240104100000.123\",\"005xx000001Sv6e\",\"LOGIN_NO_ERROR\"\n",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let events: Vec<LoginEvent> = client
+            .event_log_records("0ATxx0000000001AAA")
+            .await
+            .expect("event_log_records should succeed");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].login_status.as_deref(), Some("LOGIN_NO_ERROR"));
+    }
+}
@@ -1,12 +1,14 @@
 use tracing::instrument;
 
-use busbar_sf_client::security::soql;
+use busbar_sf_client::security::{soql, url as url_security};
 
 use crate::error::{Error, ErrorKind, Result};
+use crate::invocable_actions::{InvocableActionRequest, InvocableActionResult};
 use crate::knowledge::{
-    DataCategoriesResponse, DataCategoryGroupsResponse, KnowledgeArticlesResponse,
-    KnowledgeSettings,
+    DataCategoriesResponse, DataCategoryGroupsResponse, KnowledgeArticleDetail,
+    KnowledgeArticleDraft, KnowledgeArticlesResponse, KnowledgeSettings,
 };
+use crate::types::SalesforceId;
 
 impl super::SalesforceRestClient {
     /// Get knowledge management settings.
@@ -40,6 +42,145 @@ impl super::SalesforceRestClient {
         self.client.rest_get(&path).await.map_err(Into::into)
     }
 
+    /// Get the full content of a published knowledge article.
+    ///
+    /// `article` is the article ID (`kA0...`) or its URL name.
+    #[instrument(skip(self))]
+    pub async fn knowledge_article(&self, article: &str) -> Result<KnowledgeArticleDetail> {
+        if article.is_empty() {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ARTICLE".to_string(),
+                message: "Article ID or URL name is required".to_string(),
+            }));
+        }
+        let path = format!("support/knowledgeArticles/{}", urlencoding::encode(article));
+        self.client.rest_get(&path).await.map_err(Into::into)
+    }
+
+    /// Create a draft knowledge article.
+    ///
+    /// `article_type` is the org's knowledge article version object, usually
+    /// `Knowledge__kav`. Returns the ID of the new article version.
+    #[instrument(skip(self, draft))]
+    pub async fn create_knowledge_draft(
+        &self,
+        article_type: &str,
+        draft: &KnowledgeArticleDraft,
+    ) -> Result<SalesforceId> {
+        if !article_type.ends_with("__kav") || !soql::is_safe_sobject_name(article_type) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ARTICLE_TYPE".to_string(),
+                message: "Article type must be a knowledge article version object (__kav)"
+                    .to_string(),
+            }));
+        }
+        self.create(article_type, draft).await
+    }
+
+    /// Publish draft article versions.
+    ///
+    /// With `new_version`, each draft is published as a new major version of
+    /// its article; otherwise it replaces the current version in place.
+    #[instrument(skip(self))]
+    pub async fn publish_knowledge_articles(
+        &self,
+        version_ids: &[&str],
+        new_version: bool,
+    ) -> Result<()> {
+        let pub_action = if new_version {
+            "PUBLISH_ARTICLE_NEW_VERSION"
+        } else {
+            "PUBLISH_ARTICLE"
+        };
+        let input = serde_json::json!({
+            "articleVersionIdList": validate_version_ids(version_ids)?,
+            "pubAction": pub_action,
+        });
+        self.run_knowledge_action("publishKnowledgeArticles", input)
+            .await
+            .map(drop)
+    }
+
+    /// Archive published article versions.
+    #[instrument(skip(self))]
+    pub async fn archive_knowledge_articles(&self, version_ids: &[&str]) -> Result<()> {
+        let input = serde_json::json!({
+            "articleVersionIdList": validate_version_ids(version_ids)?,
+        });
+        self.run_knowledge_action("archiveKnowledgeArticles", input)
+            .await
+            .map(drop)
+    }
+
+    /// Create an editable draft from a published article.
+    ///
+    /// `knowledge_article_id` is the article's `KnowledgeArticleId` (`kA0...`),
+    /// not a version ID. With `unpublish`, the online version is taken down
+    /// until the draft is published. Returns the ID of the draft version.
+    #[instrument(skip(self))]
+    pub async fn create_knowledge_draft_from_online(
+        &self,
+        knowledge_article_id: &str,
+        unpublish: bool,
+    ) -> Result<String> {
+        if !url_security::is_valid_salesforce_id(knowledge_article_id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        let input = serde_json::json!({
+            "action": "EDIT_AS_DRAFT_ARTICLE",
+            "unpublish": unpublish,
+            "articleId": knowledge_article_id,
+        });
+        let result = self
+            .run_knowledge_action("createDraftFromOnlineKnowledgeArticle", input)
+            .await?;
+        result
+            .output_values
+            .as_ref()
+            .and_then(|values| values.get("draftId"))
+            .and_then(|id| id.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Salesforce {
+                    error_code: "KNOWLEDGE_ACTION_FAILED".to_string(),
+                    message: "Response did not include a draft ID".to_string(),
+                })
+            })
+    }
+
+    /// Invoke a single-input knowledge standard action and fail if it did.
+    async fn run_knowledge_action(
+        &self,
+        action: &str,
+        input: serde_json::Value,
+    ) -> Result<InvocableActionResult> {
+        let request = InvocableActionRequest {
+            inputs: vec![input],
+        };
+        let result = self
+            .invoke_standard_action(action, &request)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Salesforce {
+                    error_code: "KNOWLEDGE_ACTION_FAILED".to_string(),
+                    message: format!("{action} returned no result"),
+                })
+            })?;
+        if !result.is_success {
+            let errors: Vec<String> = result.errors.iter().map(|e| e.message.clone()).collect();
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "KNOWLEDGE_ACTION_FAILED".to_string(),
+                message: errors.join("; "),
+            }));
+        }
+        Ok(result)
+    }
+
     /// Get data category groups, optionally filtered by SObject type.
     #[instrument(skip(self))]
     pub async fn data_category_groups(
@@ -93,6 +234,25 @@ impl super::SalesforceRestClient {
     }
 }
 
+fn validate_version_ids<'a>(version_ids: &[&'a str]) -> Result<Vec<&'a str>> {
+    if version_ids.is_empty() {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_ID".to_string(),
+            message: "At least one article version ID is required".to_string(),
+        }));
+    }
+    if !version_ids
+        .iter()
+        .all(|id| url_security::is_valid_salesforce_id(id))
+    {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_ID".to_string(),
+            message: "Invalid Salesforce ID format".to_string(),
+        }));
+    }
+    Ok(version_ids.to_vec())
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::SalesforceRestClient;
@@ -235,4 +395,139 @@ mod tests {
             .expect("data_categories should succeed");
         assert_eq!(result.categories.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_knowledge_article_detail_wiremock() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(
+                "/services/data/v62.0/support/knowledgeArticles/reset-password",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "kA0xx0000000001",
+                "articleNumber": "000001",
+                "title": "Reset",
+                "urlName": "reset-password",
+                "summary": null,
+                "layoutItems": [{"name": "Body__c", "type": "RICH_TEXT_AREA", "value": "<p>Steps</p>"}]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let detail = client
+            .knowledge_article("reset-password")
+            .await
+            .expect("knowledge_article should succeed");
+        assert_eq!(detail.field("Body__c"), Some("<p>Steps</p>"));
+    }
+
+    #[tokio::test]
+    async fn test_create_knowledge_draft_wiremock() {
+        use crate::KnowledgeArticleDraft;
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/sobjects/Knowledge__kav"))
+            .and(body_json(
+                serde_json::json!({"Title": "Reset", "UrlName": "reset"}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "ka0xx0000000001AAA",
+                "success": true,
+                "errors": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let id = client
+            .create_knowledge_draft(
+                "Knowledge__kav",
+                &KnowledgeArticleDraft::new("Reset", "reset"),
+            )
+            .await
+            .expect("create_knowledge_draft should succeed");
+        assert_eq!(id, "ka0xx0000000001AAA");
+
+        let err = client
+            .create_knowledge_draft("Account", &KnowledgeArticleDraft::new("Reset", "reset"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("INVALID_ARTICLE_TYPE"));
+    }
+
+    #[tokio::test]
+    async fn test_publish_knowledge_articles_wiremock() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path(
+                "/services/data/v62.0/actions/standard/publishKnowledgeArticles",
+            ))
+            .and(body_json(serde_json::json!({"inputs": [{
+                "articleVersionIdList": ["ka0xx0000000001AAA"],
+                "pubAction": "PUBLISH_ARTICLE_NEW_VERSION"
+            }]})))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "actionName": "publishKnowledgeArticles",
+                    "errors": null,
+                    "isSuccess": true,
+                    "outputValues": null
+                }])),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        client
+            .publish_knowledge_articles(&["ka0xx0000000001AAA"], true)
+            .await
+            .expect("publish_knowledge_articles should succeed");
+
+        let err = client
+            .publish_knowledge_articles(&[], false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("INVALID_ID"));
+    }
+
+    #[tokio::test]
+    async fn test_create_knowledge_draft_from_online_wiremock() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path(
+                "/services/data/v62.0/actions/standard/createDraftFromOnlineKnowledgeArticle",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "actionName": "createDraftFromOnlineKnowledgeArticle",
+                "errors": [{"statusCode": "INVALID_STATUS", "message": "Article already has a draft", "fields": []}],
+                "isSuccess": false,
+                "outputValues": null
+            }])))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let err = client
+            .create_knowledge_draft_from_online("kA0xx0000000001AAA", false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Article already has a draft"));
+    }
 }
@@ -0,0 +1,330 @@
+use std::collections::{HashMap, VecDeque};
+
+use tracing::{instrument, warn};
+
+use busbar_sf_client::security::soql;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::mass_delete::{
+    AdaptiveChunk, MassDeleteFailure, MassDeleteOptions, MassDeleteResult, LOCK_ERROR,
+};
+use crate::types::SalesforceId;
+
+/// Largest `LIMIT` a single range query may use without paging.
+const MAX_PAGE_SIZE: usize = 2000;
+
+impl super::SalesforceRestClient {
+    /// Delete every record of `sobject` matching `filter`, in `Id` order.
+    ///
+    /// Records are selected a page at a time with
+    /// `WHERE Id > :last AND (filter) ORDER BY Id`, so each page is an
+    /// indexed range query regardless of how many rows have been deleted
+    /// so far. Each page is deleted through SObject Collections in chunks
+    /// that halve when Salesforce reports `UNABLE_TO_LOCK_ROW` and grow
+    /// back once chunks go through cleanly; locked records are retried
+    /// after a backoff. Records that fail for any other reason are skipped
+    /// and listed in the result.
+    ///
+    /// Before deleting anything, the filter's query plan is checked with
+    /// [`explain`](Self::explain). A non-selective filter is logged, or
+    /// refused when [`MassDeleteOptions::require_selective`] is set.
+    ///
+    /// # Security
+    ///
+    /// **IMPORTANT**: `filter` is inserted into the SOQL as-is. Escape
+    /// user-provided values with `busbar_sf_client::security::soql::escape_string()`.
+    #[instrument(skip(self, options))]
+    pub async fn mass_delete(
+        &self,
+        sobject: &str,
+        filter: Option<&str>,
+        options: MassDeleteOptions,
+    ) -> Result<MassDeleteResult> {
+        if !soql::is_safe_sobject_name(sobject) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_SOBJECT".to_string(),
+                message: "Invalid SObject name".to_string(),
+            }));
+        }
+
+        if let Some(filter) = filter {
+            let plan = self
+                .explain(&format!("SELECT Id FROM {sobject} WHERE {filter}"))
+                .await?;
+            if !plan.is_selective() {
+                if options.require_selective {
+                    return Err(Error::new(ErrorKind::Other(format!(
+                        "Mass delete filter on {sobject} is not selective; \
+                         add a condition on an indexed field"
+                    ))));
+                }
+                warn!(
+                    sobject,
+                    relative_cost = plan.best_plan().map(|p| p.relative_cost),
+                    "Mass delete filter is not selective"
+                );
+            }
+        }
+
+        let page_size = options.page_size.clamp(1, MAX_PAGE_SIZE);
+        let mut chunk = AdaptiveChunk::new(&options);
+        let mut result = MassDeleteResult::default();
+        let mut last_id: Option<SalesforceId> = None;
+
+        'pages: loop {
+            let mut conditions = Vec::new();
+            if let Some(ref last) = last_id {
+                conditions.push(format!("Id > '{}'", soql::escape_string(last)));
+            }
+            if let Some(filter) = filter {
+                conditions.push(format!("({filter})"));
+            }
+            let where_clause = if conditions.is_empty() {
+                String::new()
+            } else {
+                format!(" WHERE {}", conditions.join(" AND "))
+            };
+            let page: Vec<serde_json::Value> = self
+                .query(&format!(
+                    "SELECT Id FROM {sobject}{where_clause} ORDER BY Id LIMIT {page_size}"
+                ))
+                .await?
+                .records;
+            let ids: Vec<SalesforceId> = page
+                .iter()
+                .filter_map(|r| r.get("Id").and_then(|v| v.as_str())?.parse().ok())
+                .collect();
+            let Some(last) = ids.last().cloned() else {
+                break;
+            };
+
+            let mut queue: VecDeque<SalesforceId> = ids.into();
+            let mut lock_attempts: HashMap<SalesforceId, u32> = HashMap::new();
+            while !queue.is_empty() {
+                let mut size = chunk.size().min(queue.len());
+                if let Some(max) = options.max_records {
+                    let remaining = max.saturating_sub(result.deleted);
+                    if remaining == 0 {
+                        result.truncated = true;
+                        break 'pages;
+                    }
+                    size = size.min(remaining as usize);
+                }
+                let batch: Vec<SalesforceId> = queue.drain(..size).collect();
+                let refs: Vec<&str> = batch.iter().map(SalesforceId::as_str).collect();
+
+                result.delete_calls += 1;
+                let outcomes: Vec<Option<(String, String)>> =
+                    match self.delete_multiple(&refs, false).await {
+                        Ok(results) => batch
+                            .iter()
+                            .enumerate()
+                            .map(|(i, _)| match results.get(i) {
+                                Some(r) if r.success => None,
+                                Some(r) => Some(r.errors.first().map_or_else(
+                                    || ("UNKNOWN_EXCEPTION".to_string(), String::new()),
+                                    |e| (e.status_code.clone(), e.message.clone()),
+                                )),
+                                None => Some((
+                                    "UNKNOWN_EXCEPTION".to_string(),
+                                    "No result returned for record".to_string(),
+                                )),
+                            })
+                            .collect(),
+                        Err(e) if is_lock_error(&e) => batch
+                            .iter()
+                            .map(|_| Some((LOCK_ERROR.to_string(), e.to_string())))
+                            .collect(),
+                        Err(e) => return Err(e),
+                    };
+
+                let mut contended = false;
+                for (id, outcome) in batch.into_iter().zip(outcomes) {
+                    match outcome {
+                        None => result.deleted += 1,
+                        Some((status_code, message)) if status_code == LOCK_ERROR => {
+                            contended = true;
+                            let attempts = lock_attempts.entry(id.clone()).or_insert(0);
+                            *attempts += 1;
+                            if *attempts > options.max_lock_retries {
+                                result.failures.push(MassDeleteFailure {
+                                    id,
+                                    status_code,
+                                    message,
+                                });
+                            } else {
+                                queue.push_back(id);
+                            }
+                        }
+                        Some((status_code, message)) => {
+                            result.failures.push(MassDeleteFailure {
+                                id,
+                                status_code,
+                                message,
+                            });
+                        }
+                    }
+                }
+
+                if contended {
+                    result.contended_calls += 1;
+                    let backoff = chunk.contended(options.lock_backoff);
+                    warn!(
+                        sobject,
+                        chunk_size = chunk.size(),
+                        backoff_ms = backoff.as_millis() as u64,
+                        "Lock contention during mass delete"
+                    );
+                    tokio::time::sleep(backoff).await;
+                } else {
+                    chunk.clean();
+                }
+            }
+
+            if page.len() < page_size {
+                break;
+            }
+            last_id = Some(last);
+        }
+
+        result.final_chunk_size = chunk.size();
+        Ok(result)
+    }
+}
+
+/// Whether a whole delete call failed because a record was locked.
+fn is_lock_error(err: &Error) -> bool {
+    match &err.kind {
+        ErrorKind::Salesforce { error_code, .. } => error_code == LOCK_ERROR,
+        _ => err
+            .source
+            .as_ref()
+            .and_then(|s| s.downcast_ref::<busbar_sf_client::Error>())
+            .is_some_and(|e| {
+                matches!(
+                    &e.kind,
+                    busbar_sf_client::ErrorKind::SalesforceApi { error_code, .. }
+                        if error_code == LOCK_ERROR
+                )
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::MassDeleteOptions;
+    use std::time::Duration;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_mass_delete_retries_locked_rows_with_smaller_chunks() {
+        let server = MockServer::start().await;
+        let ids = [
+            "001000000000001AAA",
+            "001000000000002AAA",
+            "001000000000003AAA",
+            "001000000000004AAA",
+        ];
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .and(query_param(
+                "q",
+                "SELECT Id FROM Account ORDER BY Id LIMIT 2000",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 4,
+                "done": true,
+                "records": ids.iter().map(|id| serde_json::json!({"Id": id})).collect::<Vec<_>>()
+            })))
+            .mount(&server)
+            .await;
+
+        // First call: rows 2 and 4 are locked, row 3 is already deleted.
+        Mock::given(method("DELETE"))
+            .and(path("/services/data/v62.0/composite/sobjects"))
+            .and(query_param("ids", ids.join(",").as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": ids[0], "success": true, "errors": []},
+                {"id": ids[1], "success": false, "errors": [
+                    {"statusCode": "UNABLE_TO_LOCK_ROW", "message": "unable to obtain exclusive access", "fields": []}
+                ]},
+                {"id": ids[2], "success": false, "errors": [
+                    {"statusCode": "ENTITY_IS_DELETED", "message": "entity is deleted", "fields": []}
+                ]},
+                {"id": ids[3], "success": false, "errors": [
+                    {"statusCode": "UNABLE_TO_LOCK_ROW", "message": "unable to obtain exclusive access", "fields": []}
+                ]}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // Retry of the locked rows, in a halved chunk.
+        Mock::given(method("DELETE"))
+            .and(path("/services/data/v62.0/composite/sobjects"))
+            .and(query_param(
+                "ids",
+                format!("{},{}", ids[1], ids[3]).as_str(),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": ids[1], "success": true, "errors": []},
+                {"id": ids[3], "success": true, "errors": []}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = SalesforceRestClient::new(server.uri(), "token").unwrap();
+        let options = MassDeleteOptions::default()
+            .with_chunk_size(4)
+            .with_min_chunk_size(2)
+            .with_lock_backoff(Duration::from_millis(1));
+        let result = client.mass_delete("Account", None, options).await.unwrap();
+
+        assert_eq!(result.deleted, 3);
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].id, ids[2]);
+        assert_eq!(result.failures[0].status_code, "ENTITY_IS_DELETED");
+        assert_eq!(result.delete_calls, 2);
+        assert_eq!(result.contended_calls, 1);
+        assert_eq!(result.final_chunk_size, 3);
+        assert!(!result.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_mass_delete_refuses_non_selective_filter() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .and(query_param(
+                "explain",
+                "SELECT Id FROM Task WHERE Subject LIKE '%call%'",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "plans": [{
+                    "cardinality": 900000,
+                    "fields": [],
+                    "leadingOperationType": "TableScan",
+                    "relativeCost": 2.4,
+                    "sobjectCardinality": 1000000,
+                    "sobjectType": "Task"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = SalesforceRestClient::new(server.uri(), "token").unwrap();
+        let err = client
+            .mass_delete(
+                "Task",
+                Some("Subject LIKE '%call%'"),
+                MassDeleteOptions::default().require_selective(true),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not selective"));
+    }
+}
@@ -4,30 +4,43 @@
 //! typed methods for REST API operations including CRUD, Query, Describe,
 //! Composite, and Collections.
 
-use busbar_sf_client::{ClientConfig, SalesforceClient};
+use busbar_sf_client::{CallOptions, ClientConfig, SalesforceClient};
 
+use crate::describe_cache::DescribeCache;
 use crate::error::Result;
+use crate::types::SalesforceId;
 
+mod apex_rest;
+mod async_query;
 mod binary;
 mod collections;
 mod composite;
+mod conditional;
 mod consent;
 mod crud;
 mod describe;
+mod duplicates;
 mod embedded_service;
+mod event_log;
 mod invocable_actions;
 mod knowledge;
 mod layout;
 mod limits;
 mod list_views;
+mod mass_delete;
+mod platform_events;
 mod process;
+mod profile;
 mod query;
 mod quick_actions;
+mod recycle_bin;
 mod scheduler;
 mod search;
 mod standalone;
 mod sync;
+mod upload;
 mod user_password;
+mod wave;
 
 /// Salesforce REST API client.
 ///
@@ -64,28 +77,48 @@ mod user_password;
 #[derive(Debug, Clone)]
 pub struct SalesforceRestClient {
     client: SalesforceClient,
+    describe_cache: Option<DescribeCache>,
 }
 
 impl SalesforceRestClient {
     /// Create a new REST client with the given instance URL and access token.
     pub fn new(instance_url: impl Into<String>, access_token: impl Into<String>) -> Result<Self> {
         let client = SalesforceClient::new(instance_url, access_token)?;
-        Ok(Self { client })
+        Ok(Self::from_client(client))
     }
 
     /// Create a new REST client with custom HTTP configuration.
+    ///
+    /// Use this to tune retries; the default retries every [`RetryOn`](crate::RetryOn)
+    /// class up to three times:
+    ///
+    /// ```rust,ignore
+    /// use sf_rest::{ClientConfig, RetryConfig, RetryOn, SalesforceRestClient};
+    ///
+    /// let config = ClientConfig::builder()
+    ///     .with_retry(
+    ///         RetryConfig::default()
+    ///             .with_max_attempts(5)
+    ///             .with_retry_on([RetryOn::RateLimited, RetryOn::ServerError]),
+    ///     )
+    ///     .build();
+    /// let client = SalesforceRestClient::with_config(instance_url, token, config)?;
+    /// ```
     pub fn with_config(
         instance_url: impl Into<String>,
         access_token: impl Into<String>,
         config: ClientConfig,
     ) -> Result<Self> {
         let client = SalesforceClient::with_config(instance_url, access_token, config)?;
-        Ok(Self { client })
+        Ok(Self::from_client(client))
     }
 
     /// Create a REST client from an existing SalesforceClient.
     pub fn from_client(client: SalesforceClient) -> Self {
-        Self { client }
+        Self {
+            client,
+            describe_cache: None,
+        }
     }
 
     /// Get the underlying SalesforceClient.
@@ -108,6 +141,39 @@ impl SalesforceRestClient {
         self.client = self.client.with_api_version(version);
         self
     }
+
+    /// Serve `describe_sobject` from `cache`.
+    ///
+    /// The cache can be shared with other clients, including clients for
+    /// other orgs and users; entries are keyed by instance URL, user, and
+    /// API version.
+    pub fn with_describe_cache(mut self, cache: DescribeCache) -> Self {
+        self.describe_cache = Some(cache);
+        self
+    }
+
+    /// The describe cache attached with [`with_describe_cache`](Self::with_describe_cache).
+    pub fn describe_cache(&self) -> Option<&DescribeCache> {
+        self.describe_cache.as_ref()
+    }
+
+    /// Send `options` as call option headers with every request.
+    ///
+    /// To apply options to a single call, use them on a clone:
+    ///
+    /// ```rust,ignore
+    /// use busbar_sf_rest::{AutoAssign, CallOptions};
+    ///
+    /// let id = client
+    ///     .clone()
+    ///     .with_call_options(CallOptions::new().with_auto_assign(AutoAssign::Enabled))
+    ///     .create("Lead", &lead)
+    ///     .await?;
+    /// ```
+    pub fn with_call_options(mut self, options: CallOptions) -> Self {
+        self.client = self.client.with_call_options(options);
+        self
+    }
 }
 
 /// Result of a SOSL search.
@@ -139,7 +205,7 @@ pub struct GetDeletedResult {
 /// A deleted record.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct DeletedRecord {
-    pub id: String,
+    pub id: SalesforceId,
     #[serde(rename = "deletedDate")]
     pub deleted_date: String,
 }
@@ -147,11 +213,38 @@ pub struct DeletedRecord {
 /// Result of a getUpdated request.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct GetUpdatedResult {
-    pub ids: Vec<String>,
+    pub ids: Vec<SalesforceId>,
     #[serde(rename = "latestDateCovered")]
     pub latest_date_covered: String,
 }
 
+/// What happened to a record in a [`ChangeJournal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Created or updated.
+    Updated,
+    Deleted,
+}
+
+/// A single record change.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct RecordChange {
+    pub id: SalesforceId,
+    pub kind: ChangeKind,
+    /// When the record was deleted; `None` for updates.
+    #[serde(rename = "deletedDate", skip_serializing_if = "Option::is_none")]
+    pub deleted_date: Option<String>,
+}
+
+/// Updated and deleted records for an SObject since a watermark.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ChangeJournal {
+    pub changes: Vec<RecordChange>,
+    /// Pass this to the next `changes_since` call.
+    pub watermark: String,
+}
+
 /// Basic information about an SObject.
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct SObjectInfo {
@@ -202,21 +295,21 @@ mod tests {
     fn test_get_deleted_result_deserialize() {
         let json = serde_json::json!({
             "deletedRecords": [
-                {"id": "001xx000003DgAAAS", "deletedDate": "2024-01-15T10:30:00.000Z"}
+                {"id": "001xx000003DgAAAA0", "deletedDate": "2024-01-15T10:30:00.000Z"}
             ],
             "earliestDateAvailable": "2024-01-01T00:00:00.000Z",
             "latestDateCovered": "2024-01-15T23:59:59.000Z"
         });
         let result: GetDeletedResult = serde_json::from_value(json).unwrap();
         assert_eq!(result.deleted_records.len(), 1);
-        assert_eq!(result.deleted_records[0].id, "001xx000003DgAAAS");
+        assert_eq!(result.deleted_records[0].id, "001xx000003DgAAAA0");
         assert_eq!(result.earliest_date_available, "2024-01-01T00:00:00.000Z");
     }
 
     #[test]
     fn test_get_updated_result_deserialize() {
         let json = serde_json::json!({
-            "ids": ["001xx000003DgAAAS", "001xx000003DgBBAS"],
+            "ids": ["001xx000003DgAAAA0", "001xx000003DgBBAA0"],
             "latestDateCovered": "2024-01-15T23:59:59.000Z"
         });
         let result: GetUpdatedResult = serde_json::from_value(json).unwrap();
@@ -243,7 +336,7 @@ mod tests {
                 "searchable": true
             },
             "recentItems": [
-                {"Id": "001xx000003DgAAAS", "Name": "Acme Corp"}
+                {"Id": "001xx000003DgAAAA0", "Name": "Acme Corp"}
             ]
         });
         let info: SObjectInfo = serde_json::from_value(json).unwrap();
@@ -0,0 +1,153 @@
+use serde::Serialize;
+use tracing::instrument;
+
+use busbar_sf_client::security::soql;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::platform_events::PublishResult;
+use crate::sobject::CreateResult;
+
+impl super::SalesforceRestClient {
+    /// Publish a platform event.
+    ///
+    /// `event` is the event object's API name (`Order_Shipped__e`) and
+    /// `payload` its field values. A rejected event is returned with
+    /// `success` unset and the reasons in `errors`, not as an `Err`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let result = client
+    ///     .publish_event("Order_Shipped__e", &serde_json::json!({"Order_Number__c": "A-1"}))
+    ///     .await?;
+    /// assert!(result.success);
+    /// ```
+    #[instrument(skip(self, payload))]
+    pub async fn publish_event<T: Serialize>(
+        &self,
+        event: &str,
+        payload: &T,
+    ) -> Result<PublishResult> {
+        validate_event_name(event)?;
+        let path = format!("sobjects/{}", event);
+        let result: CreateResult = self.client.rest_post(&path, payload).await?;
+        Ok(result.into())
+    }
+
+    /// Publish up to 200 events of one type in a single request.
+    ///
+    /// Events are published independently, so one rejected event doesn't
+    /// hold back the others. Results are in the order of `payloads`.
+    #[instrument(skip(self, payloads))]
+    pub async fn publish_events<T: Serialize>(
+        &self,
+        event: &str,
+        payloads: &[T],
+    ) -> Result<Vec<PublishResult>> {
+        validate_event_name(event)?;
+        let results = self.create_multiple(event, payloads, false).await?;
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Event objects are the only SObjects whose names end in `__e`.
+fn validate_event_name(event: &str) -> Result<()> {
+    if !soql::is_safe_sobject_name(event) || !event.ends_with("__e") {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_EVENT_NAME".to_string(),
+            message: "Invalid platform event name".to_string(),
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_publish_event_wiremock() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/sobjects/Order_Shipped__e"))
+            .and(body_json(serde_json::json!({"Order_Number__c": "A-1"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "e00xx0000000001AAA",
+                "success": true,
+                "errors": [{
+                    "statusCode": "OPERATION_ENQUEUED",
+                    "message": "3d9b4b5e-6b2a-4a4f-8c87-3f0f5d8f2e11",
+                    "fields": []
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = SalesforceRestClient::new(server.uri(), "token").unwrap();
+        let result = client
+            .publish_event(
+                "Order_Shipped__e",
+                &serde_json::json!({"Order_Number__c": "A-1"}),
+            )
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(
+            result.event_uuid.as_deref(),
+            Some("3d9b4b5e-6b2a-4a4f-8c87-3f0f5d8f2e11")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_events_wiremock() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/composite/sobjects"))
+            .and(body_json(serde_json::json!({
+                "allOrNone": false,
+                "records": [
+                    {"attributes": {"type": "Order_Shipped__e"}, "Order_Number__c": "A-1"},
+                    {"attributes": {"type": "Order_Shipped__e"}}
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": "e00xx0000000001AAA", "success": true, "errors": []},
+                {"id": null, "success": false, "errors": [{
+                    "statusCode": "REQUIRED_FIELD_MISSING",
+                    "message": "Required fields are missing: [Order_Number__c]",
+                    "fields": ["Order_Number__c"]
+                }]}
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = SalesforceRestClient::new(server.uri(), "token").unwrap();
+        let results = client
+            .publish_events(
+                "Order_Shipped__e",
+                &[
+                    serde_json::json!({"Order_Number__c": "A-1"}),
+                    serde_json::json!({}),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert_eq!(results[1].errors[0].fields, ["Order_Number__c"]);
+    }
+
+    #[tokio::test]
+    async fn test_publish_event_rejects_non_event_objects() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let err = client
+            .publish_event("Account", &serde_json::json!({"Name": "Acme"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("INVALID_EVENT_NAME"));
+    }
+}
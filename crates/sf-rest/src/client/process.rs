@@ -178,7 +178,7 @@ mod tests {
 
         let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
         let request = crate::process::ProcessRuleRequest {
-            context_ids: vec!["001xx000003DgAAAS".to_string()],
+            context_ids: vec!["001xx000003DgAA".parse().unwrap()],
         };
         let result = client
             .trigger_process_rules(&request)
@@ -246,7 +246,7 @@ mod tests {
         let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
         let request = crate::process::ApprovalRequest {
             action_type: crate::process::ApprovalActionType::Submit,
-            context_id: "001xx000003DgAAAS".to_string(),
+            context_id: "001xx000003DgAA".parse().unwrap(),
             context_actor_id: None,
             comments: Some("Please approve".to_string()),
             next_approver_ids: None,
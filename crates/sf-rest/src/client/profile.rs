@@ -0,0 +1,66 @@
+use tracing::instrument;
+
+use crate::error::Result;
+use crate::profile::QueryProfile;
+
+impl super::SalesforceRestClient {
+    /// Profile the results of a SOQL query.
+    ///
+    /// Only the first page of results is fetched (up to 2,000 records by
+    /// default), so add a `LIMIT` to keep the sample small. Field types and
+    /// nullability are inferred from the values in that page.
+    ///
+    /// # Security
+    ///
+    /// **IMPORTANT**: Escape user-provided values with `busbar_sf_client::security::soql::escape_string()`
+    /// to prevent SOQL injection attacks.
+    #[instrument(skip(self))]
+    pub async fn profile_query(&self, soql: &str) -> Result<QueryProfile> {
+        let result = self.query::<serde_json::Value>(soql).await?;
+        Ok(QueryProfile::from_records(
+            &result.records,
+            result.total_size,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::profile::InferredType;
+
+    #[tokio::test]
+    async fn test_profile_query_wiremock() {
+        use wiremock::matchers::{method, path_regex, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/query$"))
+            .and(query_param("q", "SELECT Id, AnnualRevenue FROM Account LIMIT 2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 250,
+                "done": false,
+                "nextRecordsUrl": "/services/data/v62.0/query/01gxx-2000",
+                "records": [
+                    {"attributes": {"type": "Account"}, "Id": "001xx000003DGb2AAG", "AnnualRevenue": 1000},
+                    {"attributes": {"type": "Account"}, "Id": "001xx000003DGb3AAG", "AnnualRevenue": null}
+                ]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let profile = client
+            .profile_query("SELECT Id, AnnualRevenue FROM Account LIMIT 2")
+            .await
+            .expect("profile_query should succeed");
+        assert_eq!(profile.total_size, 250);
+        assert_eq!(profile.sample_size, 2);
+        let revenue = profile.field("AnnualRevenue").unwrap();
+        assert_eq!(revenue.inferred_type, InferredType::Integer);
+        assert!(revenue.is_nullable());
+    }
+}
@@ -1,8 +1,8 @@
 use serde::de::DeserializeOwned;
 use tracing::instrument;
 
-use crate::error::Result;
-use crate::query::QueryResult;
+use crate::error::{Error, ErrorKind, Result};
+use crate::query::{strip_record_attributes, QueryExplain, QueryOptions, QueryResult};
 
 impl super::SalesforceRestClient {
     /// Execute a SOQL query.
@@ -40,6 +40,71 @@ impl super::SalesforceRestClient {
         self.client.query_all(soql).await.map_err(Into::into)
     }
 
+    /// Execute a SOQL query and collect every page into a Polars DataFrame.
+    ///
+    /// Column types come from a describe of the queried object (served
+    /// from the describe cache when one is attached). Aggregate queries
+    /// have no describe, so their column types are inferred from the values.
+    ///
+    /// # Security
+    ///
+    /// **IMPORTANT**: Escape user-provided values with `busbar_sf_client::security::soql::escape_string()`
+    /// to prevent SOQL injection attacks. See `query()` for examples.
+    #[cfg(feature = "polars")]
+    #[instrument(skip(self))]
+    pub async fn query_dataframe(&self, soql: &str) -> Result<polars::prelude::DataFrame> {
+        let records: Vec<serde_json::Value> = self.client.query_all(soql).await?;
+        let sobject = records
+            .first()
+            .and_then(|r| r.pointer("/attributes/type"))
+            .and_then(|t| t.as_str())
+            .filter(|t| *t != "AggregateResult");
+        let describe = match sobject {
+            Some(sobject) => Some(self.describe_sobject(sobject).await?),
+            None => None,
+        };
+        crate::dataframe::records_to_dataframe(&records, describe.as_ref())
+    }
+
+    /// Execute a SOQL query and deserialize every record into `T`, following
+    /// all pages.
+    ///
+    /// The `attributes` object on each record and related record is removed
+    /// before deserializing, so `T` can use `#[serde(deny_unknown_fields)]`
+    /// or a `#[serde(flatten)]` map without picking it up.
+    ///
+    /// ```rust,ignore
+    /// #[derive(serde::Deserialize)]
+    /// struct Account {
+    ///     #[serde(rename = "Id")]
+    ///     id: String,
+    ///     #[serde(rename = "Name")]
+    ///     name: String,
+    /// }
+    ///
+    /// let accounts: Vec<Account> = client.query_as("SELECT Id, Name FROM Account").await?;
+    /// ```
+    ///
+    /// # Security
+    ///
+    /// **IMPORTANT**: Escape user-provided values with `busbar_sf_client::security::soql::escape_string()`
+    /// to prevent SOQL injection attacks. See `query()` for examples.
+    #[instrument(skip(self))]
+    pub async fn query_as<T: DeserializeOwned>(&self, soql: &str) -> Result<Vec<T>> {
+        let records: Vec<serde_json::Value> = self.client.query_all(soql).await?;
+        records
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut record)| {
+                strip_record_attributes(&mut record);
+                serde_json::from_value(record).map_err(|e| Error {
+                    kind: ErrorKind::Other(format!("failed to deserialize record {index}: {e}")),
+                    source: Some(Box::new(e)),
+                })
+            })
+            .collect()
+    }
+
     /// Execute a SOQL query including deleted/archived records.
     ///
     /// # Security
@@ -61,6 +126,53 @@ impl super::SalesforceRestClient {
         self.client.get_json(&url).await.map_err(Into::into)
     }
 
+    /// Execute a SOQL query with per-request options.
+    ///
+    /// `batch_size` is sent as `Sforce-Query-Options` and applies to this
+    /// query only; `include_deleted` uses the `queryAll` endpoint. Returns
+    /// the first page of results.
+    ///
+    /// # Security
+    ///
+    /// **IMPORTANT**: Escape user-provided values with `busbar_sf_client::security::soql::escape_string()`
+    /// to prevent SOQL injection attacks. See `query()` for examples.
+    #[instrument(skip(self))]
+    pub async fn query_with_options<T: DeserializeOwned>(
+        &self,
+        soql: &str,
+        options: &QueryOptions,
+    ) -> Result<QueryResult<T>> {
+        let resource = if options.include_deleted {
+            "queryAll"
+        } else {
+            "query"
+        };
+        let mut request = self
+            .client
+            .get(&self.client.rest_url(resource))
+            .query("q", soql);
+        if let Some(batch_size) = options.batch_size {
+            request = request.sforce_query_options(batch_size);
+        }
+        let response = self.client.execute(request).await?;
+        response.json().await.map_err(Into::into)
+    }
+
+    /// Get the execution plans Salesforce would consider for `soql`,
+    /// without running it.
+    ///
+    /// Use [`QueryExplain::is_selective`] to catch queries that would scan
+    /// the whole table before handing them to a long-running job.
+    #[instrument(skip(self))]
+    pub async fn explain(&self, soql: &str) -> Result<QueryExplain> {
+        let request = self
+            .client
+            .get(&self.client.rest_url("query"))
+            .query("explain", soql);
+        let response = self.client.execute(request).await?;
+        response.json().await.map_err(Into::into)
+    }
+
     /// Fetch the next page of query results.
     #[instrument(skip(self))]
     pub async fn query_more<T: DeserializeOwned>(
@@ -73,3 +185,157 @@ impl super::SalesforceRestClient {
             .map_err(Into::into)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use crate::{AutoAssign, CallOptions, QueryOptions};
+
+    #[tokio::test]
+    async fn test_query_as_strips_attributes_across_pages() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        #[derive(serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Contact {
+            #[serde(rename = "LastName")]
+            last_name: String,
+            #[serde(rename = "Account")]
+            account: Option<Account>,
+        }
+        #[derive(Debug, serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Account {
+            #[serde(rename = "Name")]
+            name: String,
+        }
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 2,
+                "done": false,
+                "nextRecordsUrl": "/services/data/v62.0/query/01gxx-2000",
+                "records": [{
+                    "attributes": {"type": "Contact"},
+                    "LastName": "Doe",
+                    "Account": {"attributes": {"type": "Account"}, "Name": "Acme"}
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query/01gxx-2000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 2,
+                "done": true,
+                "records": [{"attributes": {"type": "Contact"}, "LastName": "Roe", "Account": null}]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let contacts: Vec<Contact> = client
+            .query_as("SELECT LastName, Account.Name FROM Contact")
+            .await
+            .expect("query_as should succeed");
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[0].account.as_ref().unwrap().name, "Acme");
+        assert_eq!(contacts[1].last_name, "Roe");
+
+        let err = client
+            .query_as::<Account>("SELECT LastName, Account.Name FROM Contact")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("record 0"));
+    }
+
+    #[tokio::test]
+    async fn test_query_with_options_overrides_client_call_options() {
+        use wiremock::matchers::{header, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/queryAll"))
+            .and(query_param("q", "SELECT Id FROM Lead"))
+            .and(header("Sforce-Auto-Assign", "FALSE"))
+            .and(header("Sforce-Query-Options", "batchSize=200"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 0,
+                "done": true,
+                "records": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token")
+            .unwrap()
+            .with_call_options(
+                CallOptions::new()
+                    .with_client("acme")
+                    .with_default_namespace("ns")
+                    .with_auto_assign(AutoAssign::Disabled)
+                    .with_query_batch_size(2000),
+            );
+        let options = QueryOptions {
+            batch_size: Some(200),
+            include_deleted: true,
+        };
+        let result: crate::QueryResult<serde_json::Value> = client
+            .query_with_options("SELECT Id FROM Lead", &options)
+            .await
+            .expect("query_with_options should succeed");
+        assert!(result.done);
+
+        // wiremock's header matcher splits on commas, so check this one directly.
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].headers["Sforce-Call-Options"],
+            "client=acme, defaultNamespace=ns"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_explain_wiremock() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let soql = "SELECT Id FROM Account WHERE Description = 'x'";
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .and(query_param("explain", soql))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "plans": [{
+                    "cardinality": 120000,
+                    "fields": [],
+                    "leadingOperationType": "TableScan",
+                    "notes": [{
+                        "description": "Not considering filter for optimization because unindexed",
+                        "fields": ["Description"],
+                        "tableEnumOrId": "Account"
+                    }],
+                    "relativeCost": 2.8,
+                    "sobjectCardinality": 120000,
+                    "sobjectType": "Account"
+                }],
+                "sourceQuery": soql
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let explain = client.explain(soql).await.expect("explain should succeed");
+
+        let plan = explain.best_plan().unwrap();
+        assert!(plan.is_table_scan());
+        assert_eq!(plan.notes[0].fields, vec!["Description"]);
+        assert!(!explain.is_selective());
+    }
+}
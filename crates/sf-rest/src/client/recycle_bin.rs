@@ -0,0 +1,288 @@
+use tracing::instrument;
+
+use busbar_sf_client::security::url as url_security;
+
+use crate::collections::CollectionResult;
+use crate::error::{Error, ErrorKind, Result};
+use crate::sobject::SalesforceError;
+
+/// Maximum IDs per `emptyRecycleBin` or `undelete` call.
+const MAX_RECYCLE_BIN_IDS: usize = 200;
+
+impl super::SalesforceRestClient {
+    /// Permanently delete records from the Recycle Bin (up to 200).
+    ///
+    /// The records must already be deleted. There is no REST resource for
+    /// this, so the call goes through the SOAP Partner API using the same
+    /// session.
+    #[instrument(skip(self))]
+    pub async fn empty_recycle_bin(&self, ids: &[&str]) -> Result<Vec<CollectionResult>> {
+        self.partner_id_call("emptyRecycleBin", ids).await
+    }
+
+    /// Restore deleted records from the Recycle Bin (up to 200).
+    ///
+    /// Like [`empty_recycle_bin`](Self::empty_recycle_bin), this uses the
+    /// SOAP Partner API.
+    #[instrument(skip(self))]
+    pub async fn undelete(&self, ids: &[&str]) -> Result<Vec<CollectionResult>> {
+        self.partner_id_call("undelete", ids).await
+    }
+
+    /// Invoke a Partner API call that takes a list of `ids` and returns one
+    /// result per ID.
+    async fn partner_id_call(
+        &self,
+        operation: &str,
+        ids: &[&str],
+    ) -> Result<Vec<CollectionResult>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        if ids.len() > MAX_RECYCLE_BIN_IDS {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "LIMIT_EXCEEDED".to_string(),
+                message: format!("Maximum {} IDs per {} call", MAX_RECYCLE_BIN_IDS, operation),
+            }));
+        }
+        for id in ids {
+            if !url_security::is_valid_salesforce_id(id) {
+                return Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "INVALID_ID".to_string(),
+                    message: "Invalid Salesforce ID format".to_string(),
+                }));
+            }
+        }
+
+        let id_elements: String = ids
+            .iter()
+            .map(|id| format!("      <urn:ids>{}</urn:ids>\n", id))
+            .collect();
+        let envelope = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<soapenv:Envelope xmlns:soapenv="http://schemas.xmlsoap.org/soap/envelope/" xmlns:urn="urn:partner.soap.sforce.com">
+  <soapenv:Header>
+    <urn:SessionHeader>
+      <urn:sessionId>{session_id}</urn:sessionId>
+    </urn:SessionHeader>
+  </soapenv:Header>
+  <soapenv:Body>
+    <urn:{operation}>
+{id_elements}    </urn:{operation}>
+  </soapenv:Body>
+</soapenv:Envelope>"#,
+            session_id = busbar_sf_client::security::xml::escape(self.client.access_token()),
+        );
+
+        let url = self
+            .client
+            .url(&format!("/services/Soap/u/{}", self.client.api_version()));
+        let request = self
+            .client
+            .post(&url)
+            .header("SOAPAction", "\"\"")
+            .xml(envelope)
+            .soap();
+        let response = self.client.execute(request).await?;
+        let status = response.status();
+        let body = response.text().await?;
+
+        if let Some(fault_code) = extract_element(&body, "faultcode") {
+            let message = extract_element(&body, "faultstring")
+                .unwrap_or_else(|| "Unknown SOAP fault".to_string());
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: fault_code,
+                message,
+            }));
+        }
+        if status == 500 {
+            return Err(
+                busbar_sf_client::Error::new(busbar_sf_client::ErrorKind::Http {
+                    status,
+                    message: format!("Server error: {}", status),
+                })
+                .into(),
+            );
+        }
+        Ok(parse_partner_results(&body))
+    }
+}
+
+/// Parse the `<result>` blocks of a Partner API response.
+fn parse_partner_results(xml: &str) -> Vec<CollectionResult> {
+    extract_blocks(xml, "result")
+        .into_iter()
+        .map(|block| CollectionResult {
+            id: extract_element(block, "id").and_then(|id| id.parse().ok()),
+            success: extract_element(block, "success").as_deref() == Some("true"),
+            errors: extract_blocks(block, "errors")
+                .into_iter()
+                .map(|err| SalesforceError {
+                    status_code: extract_element(err, "statusCode").unwrap_or_default(),
+                    message: extract_element(err, "message").unwrap_or_default(),
+                    fields: extract_blocks(err, "fields")
+                        .into_iter()
+                        .map(str::to_string)
+                        .collect(),
+                })
+                .collect(),
+            created: None,
+        })
+        .collect()
+}
+
+/// Return the contents of every `<tag>...</tag>` (or `<tag attr>...</tag>`)
+/// element. Partner API responses use the default namespace, so tags are
+/// matched without a prefix.
+fn extract_blocks<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut blocks = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        // Skip longer tag names that share the prefix (e.g. <resultSet>).
+        if !after.starts_with(['>', ' ', '/']) {
+            rest = after;
+            continue;
+        }
+        let Some(gt) = after.find('>') else { break };
+        if after[..gt].ends_with('/') {
+            blocks.push("");
+            rest = &after[gt + 1..];
+            continue;
+        }
+        let content = &after[gt + 1..];
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+        blocks.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+    blocks
+}
+
+/// Extract the unescaped text of the first `<tag>` element.
+fn extract_element(xml: &str, tag: &str) -> Option<String> {
+    extract_blocks(xml, tag).first().map(|s| unescape_xml(s))
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use super::*;
+
+    const UNDELETE_RESPONSE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<soapenv:Envelope xmlns:soapenv="http://schemas.xmlsoap.org/soap/envelope/" xmlns="urn:partner.soap.sforce.com">
+  <soapenv:Body>
+    <undeleteResponse>
+      <result>
+        <id>001xx000003DGb2AAG</id>
+        <success>true</success>
+      </result>
+      <result>
+        <errors>
+          <message>Entity is not in the recycle bin &amp; cannot be restored</message>
+          <statusCode>UNDELETE_FAILED</statusCode>
+        </errors>
+        <id>001xx000003DGb3AAG</id>
+        <success>false</success>
+      </result>
+    </undeleteResponse>
+  </soapenv:Body>
+</soapenv:Envelope>"#;
+
+    #[test]
+    fn test_parse_partner_results() {
+        let results = parse_partner_results(UNDELETE_RESPONSE);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert_eq!(results[0].id.as_deref(), Some("001xx000003DGb2AAG"));
+        assert!(results[0].errors.is_empty());
+        assert!(!results[1].success);
+        assert_eq!(results[1].errors[0].status_code, "UNDELETE_FAILED");
+        assert_eq!(
+            results[1].errors[0].message,
+            "Entity is not in the recycle bin & cannot be restored"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undelete_invalid_id() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client.undelete(&["not-an-id"]).await;
+        assert!(result.unwrap_err().to_string().contains("INVALID_ID"));
+    }
+
+    #[tokio::test]
+    async fn test_empty_recycle_bin_too_many_ids() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let ids = vec!["001xx000003DGb2AAG"; 201];
+        let result = client.empty_recycle_bin(&ids).await;
+        assert!(result.unwrap_err().to_string().contains("LIMIT_EXCEEDED"));
+    }
+
+    #[tokio::test]
+    async fn test_undelete_wiremock() {
+        use wiremock::matchers::{body_string_contains, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/u/62.0"))
+            .and(body_string_contains("<urn:undelete>"))
+            .and(body_string_contains(
+                "<urn:ids>001xx000003DGb2AAG</urn:ids>",
+            ))
+            .and(body_string_contains(
+                "<urn:sessionId>test-token</urn:sessionId>",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(UNDELETE_RESPONSE))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let results = client
+            .undelete(&["001xx000003DGb2AAG", "001xx000003DGb3AAG"])
+            .await
+            .expect("undelete should succeed");
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+    }
+
+    #[tokio::test]
+    async fn test_empty_recycle_bin_fault() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/services/Soap/u/62.0"))
+            .respond_with(ResponseTemplate::new(500).set_body_string(
+                r#"<soapenv:Envelope xmlns:soapenv="http://schemas.xmlsoap.org/soap/envelope/"><soapenv:Body><soapenv:Fault><faultcode>sf:INVALID_SESSION_ID</faultcode><faultstring>Invalid Session ID</faultstring></soapenv:Fault></soapenv:Body></soapenv:Envelope>"#,
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let err = client
+            .empty_recycle_bin(&["001xx000003DGb2AAG"])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.kind,
+            ErrorKind::Salesforce { ref error_code, .. } if error_code == "sf:INVALID_SESSION_ID"
+        ));
+    }
+}
@@ -8,6 +8,7 @@ use crate::search::{
     ParameterizedSearchRequest, ParameterizedSearchResponse, ScopeEntity, SearchLayoutInfo,
     SearchSuggestionResult,
 };
+use crate::sosl::SoslResult;
 
 impl super::SalesforceRestClient {
     /// Execute a SOSL search.
@@ -29,6 +30,16 @@ impl super::SalesforceRestClient {
         self.client.get_json(&url).await.map_err(Into::into)
     }
 
+    /// Execute a SOSL search and group the records by SObject type.
+    ///
+    /// Build `sosl` with [`Sosl`](crate::Sosl) to have the search term
+    /// escaped for you.
+    #[instrument(skip(self))]
+    pub async fn search_grouped(&self, sosl: &str) -> Result<SoslResult> {
+        let result: super::SearchResult<serde_json::Value> = self.search(sosl).await?;
+        Ok(SoslResult::from_records(result.search_records))
+    }
+
     /// Execute a parameterized search request.
     ///
     /// This provides a structured alternative to raw SOSL queries,
@@ -296,4 +307,43 @@ mod tests {
         assert_eq!(result[0].label, "Accounts");
         assert_eq!(result[0].columns.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_search_grouped_wiremock() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        let sosl = crate::Sosl::find("Acme & Co")
+            .returning(crate::SoslReturning::new("Account").fields(["Id", "Name"]))
+            .returning(crate::SoslReturning::new("Contact").fields(["Id"]))
+            .build()
+            .unwrap();
+
+        let body = serde_json::json!({
+            "searchRecords": [
+                {"attributes": {"type": "Account"}, "Id": "001xx000003Dgb2AAC", "Name": "Acme & Co"},
+                {"attributes": {"type": "Contact"}, "Id": "003xx000004TmiQAAS"},
+                {"attributes": {"type": "Account"}, "Id": "001xx000003Dgb3AAC", "Name": "Acme"}
+            ]
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/search"))
+            .and(query_param("q", sosl.as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let result = client
+            .search_grouped(&sosl)
+            .await
+            .expect("search_grouped should succeed");
+
+        assert_eq!(result.groups.len(), 2);
+        assert_eq!(result.records("Account").len(), 2);
+        assert_eq!(result.records("Contact")[0]["Id"], "003xx000004TmiQAAS");
+    }
 }
@@ -1,9 +1,22 @@
+use std::collections::HashSet;
+use std::future::Future;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
 use tracing::instrument;
 
 use busbar_sf_client::security::soql;
 
 use crate::error::{Error, ErrorKind, Result};
 
+use super::{ChangeJournal, ChangeKind, GetDeletedResult, GetUpdatedResult, RecordChange};
+
+/// How far back Salesforce keeps replication history.
+const REPLICATION_RETENTION_DAYS: i64 = 30;
+
+/// Error Salesforce returns when a window holds more records than one
+/// getUpdated/getDeleted call may return.
+const EXCEEDED_ID_LIMIT: &str = "EXCEEDED_ID_LIMIT";
+
 impl super::SalesforceRestClient {
     /// Get deleted records for an SObject type within a date range.
     ///
@@ -56,11 +69,282 @@ impl super::SalesforceRestClient {
         );
         self.client.rest_get(&path).await.map_err(Into::into)
     }
+
+    /// Get deleted records for any window, following Salesforce's
+    /// replication rules for the caller.
+    ///
+    /// The window is clamped to the last 30 days and to the current time,
+    /// and rounded to whole minutes. A window holding more records than
+    /// one call may return is split in half until each part fits, and the
+    /// results are merged. Deletions before the clamped start are not
+    /// reported; `earliest_date_available` says how far back the result
+    /// reaches.
+    #[instrument(skip(self))]
+    pub async fn get_deleted_window(
+        &self,
+        sobject: &str,
+        start: &str,
+        end: &str,
+    ) -> Result<GetDeletedResult> {
+        let (start, end) = parse_window(start, end)?;
+        self.deleted_between(sobject, start, end, Utc::now()).await
+    }
+
+    /// Get updated record IDs for any window, following Salesforce's
+    /// replication rules for the caller.
+    ///
+    /// Clamps, splits, and merges like
+    /// [`get_deleted_window`](Self::get_deleted_window). An ID updated in
+    /// more than one part of the window appears once.
+    #[instrument(skip(self))]
+    pub async fn get_updated_window(
+        &self,
+        sobject: &str,
+        start: &str,
+        end: &str,
+    ) -> Result<GetUpdatedResult> {
+        let (start, end) = parse_window(start, end)?;
+        self.updated_between(sobject, start, end, Utc::now()).await
+    }
+
+    async fn deleted_between(
+        &self,
+        sobject: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<GetDeletedResult> {
+        let Some((start, end)) = clamp_window(start, end, now) else {
+            let covered = format_replication_date(end.min(now));
+            return Ok(GetDeletedResult {
+                deleted_records: Vec::new(),
+                earliest_date_available: covered.clone(),
+                latest_date_covered: covered,
+            });
+        };
+        let parts = split_on_id_limit(start, end, |start, end| async move {
+            self.get_deleted(sobject, &start, &end).await
+        })
+        .await?;
+
+        let mut merged = GetDeletedResult {
+            deleted_records: Vec::new(),
+            earliest_date_available: parts[0].earliest_date_available.clone(),
+            latest_date_covered: String::new(),
+        };
+        for part in parts {
+            merged.deleted_records.extend(part.deleted_records);
+            merged.latest_date_covered = part.latest_date_covered;
+        }
+        Ok(merged)
+    }
+
+    async fn updated_between(
+        &self,
+        sobject: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<GetUpdatedResult> {
+        let Some((start, end)) = clamp_window(start, end, now) else {
+            return Ok(GetUpdatedResult {
+                ids: Vec::new(),
+                latest_date_covered: format_replication_date(end.min(now)),
+            });
+        };
+        let parts = split_on_id_limit(start, end, |start, end| async move {
+            self.get_updated(sobject, &start, &end).await
+        })
+        .await?;
+
+        let mut seen = HashSet::new();
+        let mut merged = GetUpdatedResult {
+            ids: Vec::new(),
+            latest_date_covered: String::new(),
+        };
+        for part in parts {
+            merged
+                .ids
+                .extend(part.ids.into_iter().filter(|id| seen.insert(id.clone())));
+            merged.latest_date_covered = part.latest_date_covered;
+        }
+        Ok(merged)
+    }
+
+    /// Get every record of an SObject type updated or deleted since `watermark`.
+    ///
+    /// Combines [`get_updated`](Self::get_updated) and
+    /// [`get_deleted`](Self::get_deleted) over the window from `watermark`
+    /// to now. A record that was updated and then deleted appears once, as a
+    /// deletion. The returned watermark is the earlier of the two
+    /// `latestDateCovered` values, so feeding it back into the next call
+    /// never skips a change. Salesforce only keeps 30 days of replication
+    /// history.
+    #[instrument(skip(self))]
+    pub async fn changes_since(&self, sobject: &str, watermark: &str) -> Result<ChangeJournal> {
+        self.changes_between(sobject, watermark, Utc::now()).await
+    }
+
+    async fn changes_between(
+        &self,
+        sobject: &str,
+        watermark: &str,
+        end: DateTime<Utc>,
+    ) -> Result<ChangeJournal> {
+        if !soql::is_safe_sobject_name(sobject) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_SOBJECT".to_string(),
+                message: "Invalid SObject name".to_string(),
+            }));
+        }
+        let start = parse_replication_date(watermark).ok_or_else(|| {
+            Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_WATERMARK".to_string(),
+                message: format!("Invalid watermark date-time: {}", watermark),
+            })
+        })?;
+        // The replication API works at minute granularity and rejects
+        // windows shorter than that.
+        if end - start < chrono::Duration::minutes(1) {
+            return Ok(ChangeJournal {
+                changes: Vec::new(),
+                watermark: watermark.to_string(),
+            });
+        }
+
+        let start = format_replication_date(start);
+        let end = format_replication_date(end);
+        let (updated, deleted) = futures::try_join!(
+            self.get_updated(sobject, &start, &end),
+            self.get_deleted(sobject, &start, &end)
+        )?;
+
+        let deleted_ids: HashSet<&str> = deleted
+            .deleted_records
+            .iter()
+            .map(|r| r.id.as_str())
+            .collect();
+        let mut changes: Vec<RecordChange> = updated
+            .ids
+            .iter()
+            .filter(|id| !deleted_ids.contains(id.as_str()))
+            .map(|id| RecordChange {
+                id: id.clone(),
+                kind: ChangeKind::Updated,
+                deleted_date: None,
+            })
+            .collect();
+        changes.extend(deleted.deleted_records.iter().map(|r| RecordChange {
+            id: r.id.clone(),
+            kind: ChangeKind::Deleted,
+            deleted_date: Some(r.deleted_date.clone()),
+        }));
+
+        let watermark = match (
+            parse_replication_date(&updated.latest_date_covered),
+            parse_replication_date(&deleted.latest_date_covered),
+        ) {
+            (Some(u), Some(d)) if d < u => deleted.latest_date_covered,
+            (Some(_), Some(_)) => updated.latest_date_covered,
+            _ => start,
+        };
+
+        Ok(ChangeJournal { changes, watermark })
+    }
+}
+
+/// Parse a replication date, accepting both RFC 3339 and the
+/// `2024-01-15T10:30:00.000+0000` form Salesforce returns.
+fn parse_replication_date(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .or_else(|_| DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn parse_window(start: &str, end: &str) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let parse = |s: &str| {
+        parse_replication_date(s).ok_or_else(|| {
+            Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_REPLICATION_DATE".to_string(),
+                message: format!("Invalid replication date-time: {}", s),
+            })
+        })
+    };
+    Ok((parse(start)?, parse(end)?))
+}
+
+fn format_replication_date(date: DateTime<Utc>) -> String {
+    date.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn truncate_to_minute(date: DateTime<Utc>) -> DateTime<Utc> {
+    date.duration_trunc(Duration::minutes(1)).unwrap_or(date)
+}
+
+/// Clamp a window to the part Salesforce will answer for: whole minutes,
+/// no earlier than the retention period, and no later than `now`. `None`
+/// if less than a minute is left.
+fn clamp_window(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    // Round the retention boundary up so the start is never a few seconds
+    // too old.
+    let oldest = now - Duration::days(REPLICATION_RETENTION_DAYS);
+    let mut floor = truncate_to_minute(oldest);
+    if floor < oldest {
+        floor += Duration::minutes(1);
+    }
+    let start = truncate_to_minute(start).max(floor);
+    let end = truncate_to_minute(end.min(now));
+    (end - start >= Duration::minutes(1)).then_some((start, end))
+}
+
+/// Call `fetch` for the window, halving any part Salesforce rejects as
+/// holding too many records. Results are in chronological order.
+async fn split_on_id_limit<T, F, Fut>(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    mut fetch: F,
+) -> Result<Vec<T>>
+where
+    F: FnMut(String, String) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut pending = vec![(start, end)];
+    let mut parts = Vec::new();
+    while let Some((start, end)) = pending.pop() {
+        match fetch(format_replication_date(start), format_replication_date(end)).await {
+            Ok(part) => parts.push(part),
+            Err(e) if is_id_limit_error(&e) && end - start >= Duration::minutes(2) => {
+                let middle = truncate_to_minute(start + (end - start) / 2);
+                pending.push((middle, end));
+                pending.push((start, middle));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(parts)
+}
+
+fn is_id_limit_error(err: &Error) -> bool {
+    err.source
+        .as_ref()
+        .and_then(|s| s.downcast_ref::<busbar_sf_client::Error>())
+        .is_some_and(|e| {
+            matches!(
+                &e.kind,
+                busbar_sf_client::ErrorKind::SalesforceApi { error_code, .. }
+                    if error_code == EXCEEDED_ID_LIMIT
+            )
+        })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::super::SalesforceRestClient;
+    use super::super::{ChangeKind, SalesforceRestClient};
 
     #[tokio::test]
     async fn test_get_deleted_invalid_sobject() {
@@ -99,7 +383,7 @@ mod tests {
 
         let body = serde_json::json!({
             "deletedRecords": [
-                {"id": "001xx000003DgAAAS", "deletedDate": "2024-01-15T10:30:00.000Z"}
+                {"id": "001xx000003DgAAAA0", "deletedDate": "2024-01-15T10:30:00.000Z"}
             ],
             "earliestDateAvailable": "2024-01-01T00:00:00.000Z",
             "latestDateCovered": "2024-01-15T23:59:59.000Z"
@@ -117,7 +401,7 @@ mod tests {
             .await
             .expect("get_deleted should succeed");
         assert_eq!(result.deleted_records.len(), 1);
-        assert_eq!(result.deleted_records[0].id, "001xx000003DgAAAS");
+        assert_eq!(result.deleted_records[0].id, "001xx000003DgAAAA0");
     }
 
     #[tokio::test]
@@ -128,7 +412,7 @@ mod tests {
         let mock_server = MockServer::start().await;
 
         let body = serde_json::json!({
-            "ids": ["001xx000003DgAAAS", "001xx000003DgBBAS"],
+            "ids": ["001xx000003DgAAAA0", "001xx000003DgBBAA0"],
             "latestDateCovered": "2024-01-15T23:59:59.000Z"
         });
 
@@ -145,4 +429,210 @@ mod tests {
             .expect("get_updated should succeed");
         assert_eq!(result.ids.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_changes_since_merges_updated_and_deleted() {
+        use wiremock::matchers::{method, path_regex, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/sobjects/Account/updated/.*"))
+            .and(query_param("start", "2024-01-01T00:00:00Z"))
+            .and(query_param("end", "2024-01-15T00:00:00Z"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ids": ["001xx000003DgAAAA0", "001xx000003DgBBAA0"],
+                "latestDateCovered": "2024-01-15T00:00:00.000+0000"
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(".*/sobjects/Account/deleted/.*"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "deletedRecords": [
+                    {"id": "001xx000003DgBBAA0", "deletedDate": "2024-01-10T10:30:00.000+0000"}
+                ],
+                "earliestDateAvailable": "2023-12-20T00:00:00.000+0000",
+                "latestDateCovered": "2024-01-14T23:59:00.000+0000"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let end = "2024-01-15T00:00:00Z".parse().unwrap();
+        let journal = client
+            .changes_between("Account", "2024-01-01T00:00:00Z", end)
+            .await
+            .expect("changes_since should succeed");
+
+        assert_eq!(journal.changes.len(), 2);
+        assert_eq!(journal.changes[0].id, "001xx000003DgAAAA0");
+        assert_eq!(journal.changes[0].kind, ChangeKind::Updated);
+        assert_eq!(journal.changes[1].id, "001xx000003DgBBAA0");
+        assert_eq!(journal.changes[1].kind, ChangeKind::Deleted);
+        assert_eq!(journal.watermark, "2024-01-14T23:59:00.000+0000");
+    }
+
+    #[tokio::test]
+    async fn test_changes_since_short_window() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let end = "2024-01-15T00:00:30Z".parse().unwrap();
+        let journal = client
+            .changes_between("Account", "2024-01-15T00:00:00.000+0000", end)
+            .await
+            .expect("short window should not call the API");
+        assert!(journal.changes.is_empty());
+        assert_eq!(journal.watermark, "2024-01-15T00:00:00.000+0000");
+    }
+
+    #[tokio::test]
+    async fn test_changes_since_invalid_watermark() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client.changes_since("Account", "yesterday").await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("INVALID_WATERMARK"));
+    }
+
+    #[test]
+    fn test_clamp_window() {
+        use super::clamp_window;
+        let at = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let now = at("2024-03-31T12:00:30Z");
+
+        let (start, end) =
+            clamp_window(at("2024-01-01T00:00:00Z"), at("2024-04-30T00:00:00Z"), now).unwrap();
+        assert_eq!(start, at("2024-03-01T12:01:00Z"));
+        assert_eq!(end, at("2024-03-31T12:00:00Z"));
+
+        let (start, end) =
+            clamp_window(at("2024-03-10T08:15:45Z"), at("2024-03-11T08:15:45Z"), now).unwrap();
+        assert_eq!(start, at("2024-03-10T08:15:00Z"));
+        assert_eq!(end, at("2024-03-11T08:15:00Z"));
+
+        assert!(
+            clamp_window(at("2024-03-10T08:15:10Z"), at("2024-03-10T08:15:50Z"), now).is_none()
+        );
+        assert!(
+            clamp_window(at("2024-01-01T00:00:00Z"), at("2024-02-01T00:00:00Z"), now).is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_updated_window_splits_on_id_limit() {
+        use wiremock::matchers::{method, path_regex, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/sobjects/Account/updated/.*"))
+            .and(query_param("start", "2024-03-10T00:00:00Z"))
+            .and(query_param("end", "2024-03-12T00:00:00Z"))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_json(serde_json::json!([{
+                    "errorCode": "EXCEEDED_ID_LIMIT",
+                    "message": "ID limit exceeded"
+                }])),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(".*/sobjects/Account/updated/.*"))
+            .and(query_param("start", "2024-03-10T00:00:00Z"))
+            .and(query_param("end", "2024-03-11T00:00:00Z"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ids": ["001xx000003DgAAAA0", "001xx000003DgBBAA0"],
+                "latestDateCovered": "2024-03-11T00:00:00.000+0000"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(".*/sobjects/Account/updated/.*"))
+            .and(query_param("start", "2024-03-11T00:00:00Z"))
+            .and(query_param("end", "2024-03-12T00:00:00Z"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ids": ["001xx000003DgBBAA0", "001xx000003DgCCAA0"],
+                "latestDateCovered": "2024-03-12T00:00:00.000+0000"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let at = |s: &str| s.parse().unwrap();
+        let result = client
+            .updated_between(
+                "Account",
+                at("2024-03-10T00:00:00Z"),
+                at("2024-03-12T00:00:00Z"),
+                at("2024-03-31T00:00:00Z"),
+            )
+            .await
+            .expect("split window should succeed");
+
+        assert_eq!(
+            result.ids,
+            [
+                "001xx000003DgAAAA0",
+                "001xx000003DgBBAA0",
+                "001xx000003DgCCAA0"
+            ]
+        );
+        assert_eq!(result.latest_date_covered, "2024-03-12T00:00:00.000+0000");
+    }
+
+    #[tokio::test]
+    async fn test_get_deleted_window_clamps_to_retention() {
+        use wiremock::matchers::{method, path_regex, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/sobjects/Account/deleted/.*"))
+            .and(query_param("start", "2024-03-01T00:00:00Z"))
+            .and(query_param("end", "2024-03-31T00:00:00Z"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "deletedRecords": [
+                    {"id": "001xx000003DgAAAA0", "deletedDate": "2024-03-15T10:30:00.000+0000"}
+                ],
+                "earliestDateAvailable": "2024-03-01T00:00:00.000+0000",
+                "latestDateCovered": "2024-03-31T00:00:00.000+0000"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let at = |s: &str| s.parse().unwrap();
+        let result = client
+            .deleted_between(
+                "Account",
+                at("2023-01-01T00:00:00Z"),
+                at("2025-01-01T00:00:00Z"),
+                at("2024-03-31T00:00:00Z"),
+            )
+            .await
+            .expect("clamped window should succeed");
+        assert_eq!(result.deleted_records.len(), 1);
+        assert_eq!(
+            result.earliest_date_available,
+            "2024-03-01T00:00:00.000+0000"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_updated_window_invalid_date() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let err = client
+            .get_updated_window("Account", "last week", "2024-03-12T00:00:00Z")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("INVALID_REPLICATION_DATE"));
+    }
 }
@@ -0,0 +1,343 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use serde::Serialize;
+use tokio::io::AsyncReadExt;
+use tracing::instrument;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::sobject::CreateResult;
+use crate::types::SalesforceId;
+use crate::upload::{FileSource, UploadTarget};
+
+impl super::SalesforceRestClient {
+    /// Upload a file as a new ContentVersion, Attachment, or Document record.
+    ///
+    /// The record fields in `metadata` and the file content are sent as a
+    /// single `multipart/form-data` request, so the file is never
+    /// base64-encoded and is not subject to the JSON body size limit.
+    /// A file given by path is streamed from disk rather than read into
+    /// memory. For ContentVersion, `metadata` should include `PathOnClient`.
+    ///
+    /// Returns the ID of the created record.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::path::Path;
+    /// use sf_rest::UploadTarget;
+    ///
+    /// let id = client
+    ///     .upload_file(
+    ///         UploadTarget::ContentVersion,
+    ///         &json!({"Title": "Q3 Report", "PathOnClient": "q3.pdf"}),
+    ///         "q3.pdf",
+    ///         Path::new("/tmp/q3.pdf"),
+    ///     )
+    ///     .await?;
+    /// ```
+    #[instrument(skip(self, metadata, source), fields(sobject = target.sobject()))]
+    pub async fn upload_file<T: Serialize>(
+        &self,
+        target: UploadTarget,
+        metadata: &T,
+        file_name: &str,
+        source: impl Into<FileSource>,
+    ) -> Result<SalesforceId> {
+        if file_name.is_empty() || file_name.contains(['"', '\r', '\n']) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_FILE_NAME".to_string(),
+                message: "File name must be non-empty and must not contain quotes or newlines"
+                    .to_string(),
+            }));
+        }
+
+        let entity = serde_json::to_vec(metadata).map_err(|e| Error {
+            kind: ErrorKind::Other(format!("Failed to serialize record fields: {}", e)),
+            source: Some(Box::new(e)),
+        })?;
+
+        let boundary = multipart_boundary();
+        let (head, tail) = multipart_frame(&boundary, target, &entity, file_name);
+
+        let url = self
+            .client
+            .rest_url(&format!("sobjects/{}", target.sobject()));
+        let request = self
+            .client
+            .post(&url)
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={}", boundary),
+            )
+            .header("Accept", "application/json");
+        let request = match source.into() {
+            FileSource::Bytes(content) => request.bytes([head, content, tail].concat()),
+            FileSource::Path(path) => {
+                let len = tokio::fs::metadata(&path)
+                    .await
+                    .map_err(|e| read_error(&path, e))?
+                    .len();
+                let (head, tail) = (Bytes::from(head), Bytes::from(tail));
+                let body_len = head.len() as u64 + len + tail.len() as u64;
+                request.stream(body_len, move || {
+                    file_stream(head.clone(), path.clone(), tail.clone())
+                })
+            }
+        };
+        let result: CreateResult = self.client.execute(request).await?.json().await?;
+
+        match result.id {
+            Some(id) if result.success => Ok(id),
+            _ => {
+                let errors: Vec<String> = result.errors.iter().map(|e| e.message.clone()).collect();
+                Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "CREATE_FAILED".to_string(),
+                    message: errors.join("; "),
+                }))
+            }
+        }
+    }
+}
+
+/// Generate a boundary string that is unique per request.
+fn multipart_boundary() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    format!(
+        "busbar-boundary-{:x}-{:x}",
+        nanos,
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// The parts of a Salesforce multipart upload that go before and after the
+/// file content: the JSON entity part and the binary part's headers, then
+/// the closing boundary.
+fn multipart_frame(
+    boundary: &str,
+    target: UploadTarget,
+    entity: &[u8],
+    file_name: &str,
+) -> (Vec<u8>, Vec<u8>) {
+    let mut head = Vec::with_capacity(entity.len() + 512);
+    head.extend_from_slice(
+        format!(
+            "--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"{}\"\r\n\
+             Content-Type: application/json\r\n\r\n",
+            target.entity_part()
+        )
+        .as_bytes(),
+    );
+    head.extend_from_slice(entity);
+    head.extend_from_slice(
+        format!(
+            "\r\n--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n",
+            target.binary_field(),
+            file_name
+        )
+        .as_bytes(),
+    );
+    let tail = format!("\r\n--{boundary}--\r\n").into_bytes();
+    (head, tail)
+}
+
+/// Size of each read when streaming a file into an upload.
+const UPLOAD_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Stream `head`, then the file at `path` in chunks, then `tail`.
+fn file_stream(
+    head: Bytes,
+    path: PathBuf,
+    tail: Bytes,
+) -> BoxStream<'static, std::io::Result<Bytes>> {
+    let file = futures::stream::once(tokio::fs::File::open(path))
+        .map_ok(|file| {
+            futures::stream::try_unfold(file, |mut file| async move {
+                let mut chunk = vec![0; UPLOAD_CHUNK_BYTES];
+                let read = file.read(&mut chunk).await?;
+                if read == 0 {
+                    return Ok(None);
+                }
+                chunk.truncate(read);
+                Ok(Some((Bytes::from(chunk), file)))
+            })
+        })
+        .try_flatten();
+    futures::stream::once(async { Ok(head) })
+        .chain(file)
+        .chain(futures::stream::once(async { Ok(tail) }))
+        .boxed()
+}
+
+fn read_error(path: &Path, e: std::io::Error) -> Error {
+    Error {
+        kind: ErrorKind::Other(format!("Failed to read {}: {}", path.display(), e)),
+        source: Some(Box::new(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+    use super::*;
+
+    #[test]
+    fn test_multipart_frame() {
+        let (head, tail) = multipart_frame(
+            "XYZ",
+            UploadTarget::ContentVersion,
+            br#"{"Title":"t"}"#,
+            "a.bin",
+        );
+        let body = [head, vec![0, 1, 2], tail].concat();
+        let mut expected = b"--XYZ\r\n\
+            Content-Disposition: form-data; name=\"entity_content\"\r\n\
+            Content-Type: application/json\r\n\r\n\
+            {\"Title\":\"t\"}\r\n\
+            --XYZ\r\n\
+            Content-Disposition: form-data; name=\"VersionData\"; filename=\"a.bin\"\r\n\
+            Content-Type: application/octet-stream\r\n\r\n"
+            .to_vec();
+        expected.extend_from_slice(&[0, 1, 2]);
+        expected.extend_from_slice(b"\r\n--XYZ--\r\n");
+        assert_eq!(body, expected);
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_invalid_name() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client
+            .upload_file(
+                UploadTarget::Attachment,
+                &serde_json::json!({"Name": "x"}),
+                "bad\"name",
+                b"data".as_slice(),
+            )
+            .await;
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("INVALID_FILE_NAME"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_wiremock() {
+        use wiremock::matchers::{header_regex, method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path_regex(".*/sobjects/ContentVersion$"))
+            .and(header_regex(
+                "content-type",
+                "^multipart/form-data; boundary=busbar-boundary-",
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "068xx0000000001AAA",
+                "success": true,
+                "errors": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let id = client
+            .upload_file(
+                UploadTarget::ContentVersion,
+                &serde_json::json!({"Title": "Report", "PathOnClient": "report.pdf"}),
+                "report.pdf",
+                vec![0x25, 0x50, 0x44, 0x46],
+            )
+            .await
+            .expect("upload_file should succeed");
+        assert_eq!(id, "068xx0000000001AAA");
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("name=\"entity_content\""));
+        assert!(body.contains("\"PathOnClient\":\"report.pdf\""));
+        assert!(body.contains("name=\"VersionData\"; filename=\"report.pdf\""));
+        assert!(body.contains("%PDF"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_missing_path() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client
+            .upload_file(
+                UploadTarget::Document,
+                &serde_json::json!({"Name": "x"}),
+                "x.txt",
+                std::path::Path::new("/nonexistent/busbar/x.txt"),
+            )
+            .await;
+        assert!(result.unwrap_err().to_string().contains("Failed to read"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_file_streams_path() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("big.bin");
+        let content: Vec<u8> = (0..3 * UPLOAD_CHUNK_BYTES + 7).map(|i| i as u8).collect();
+        std::fs::write(&file, &content).unwrap();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex(".*/sobjects/ContentVersion$"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "068xx0000000001AAA",
+                "success": true,
+                "errors": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let metadata = serde_json::json!({"Title": "big", "PathOnClient": "big.bin"});
+        let id = client
+            .upload_file(
+                UploadTarget::ContentVersion,
+                &metadata,
+                "big.bin",
+                file.as_path(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(id, "068xx0000000001AAA");
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let boundary = requests[0].headers["content-type"]
+            .to_str()
+            .unwrap()
+            .split("boundary=")
+            .nth(1)
+            .unwrap()
+            .to_string();
+        let (head, tail) = multipart_frame(
+            &boundary,
+            UploadTarget::ContentVersion,
+            &serde_json::to_vec(&metadata).unwrap(),
+            "big.bin",
+        );
+        assert_eq!(requests[0].body, [head, content, tail].concat());
+        assert_eq!(
+            requests[0].headers["content-length"],
+            requests[0].body.len().to_string().as_str()
+        );
+    }
+}
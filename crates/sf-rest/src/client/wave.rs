@@ -0,0 +1,236 @@
+use tracing::instrument;
+
+use busbar_sf_client::security::url as url_security;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::wave::{
+    DataflowJob, DataflowJobCollection, DataflowJobRequest, SaqlQueryRequest, SaqlQueryResponse,
+    WaveDataset, WaveDatasetCollection,
+};
+
+impl super::SalesforceRestClient {
+    /// List CRM Analytics datasets, optionally filtered by a search string.
+    #[instrument(skip(self))]
+    pub async fn wave_datasets(&self, search: Option<&str>) -> Result<WaveDatasetCollection> {
+        let path = match search {
+            Some(q) => format!("wave/datasets?q={}", urlencoding::encode(q)),
+            None => "wave/datasets".to_string(),
+        };
+        self.client.rest_get(&path).await.map_err(Into::into)
+    }
+
+    /// Get a single CRM Analytics dataset by ID.
+    #[instrument(skip(self))]
+    pub async fn wave_dataset(&self, dataset_id: &str) -> Result<WaveDataset> {
+        if !url_security::is_valid_salesforce_id(dataset_id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        let path = format!("wave/datasets/{}", dataset_id);
+        self.client.rest_get(&path).await.map_err(Into::into)
+    }
+
+    /// Execute a SAQL query against CRM Analytics.
+    ///
+    /// Datasets must be referenced in the query as `"<datasetId>/<versionId>"`
+    /// or by dataset name.
+    #[instrument(skip(self, saql))]
+    pub async fn wave_query(&self, saql: &str) -> Result<SaqlQueryResponse> {
+        let request = SaqlQueryRequest {
+            query: saql.to_string(),
+        };
+        self.client
+            .rest_post("wave/query", &request)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Start a dataflow, returning the queued job.
+    #[instrument(skip(self))]
+    pub async fn start_dataflow(&self, dataflow_id: &str) -> Result<DataflowJob> {
+        if !url_security::is_valid_salesforce_id(dataflow_id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        let request = DataflowJobRequest {
+            dataflow_id: dataflow_id.to_string(),
+            command: "Start".to_string(),
+        };
+        self.client
+            .rest_post("wave/dataflowjobs", &request)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Get the status of a dataflow job.
+    #[instrument(skip(self))]
+    pub async fn dataflow_job(&self, job_id: &str) -> Result<DataflowJob> {
+        if !url_security::is_valid_salesforce_id(job_id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        let path = format!("wave/dataflowjobs/{}", job_id);
+        self.client.rest_get(&path).await.map_err(Into::into)
+    }
+
+    /// List recent dataflow jobs.
+    #[instrument(skip(self))]
+    pub async fn dataflow_jobs(&self) -> Result<DataflowJobCollection> {
+        self.client
+            .rest_get("wave/dataflowjobs")
+            .await
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SalesforceRestClient;
+
+    #[tokio::test]
+    async fn test_wave_dataset_invalid_id() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client.wave_dataset("../../etc").await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("INVALID_ID"));
+    }
+
+    #[tokio::test]
+    async fn test_start_dataflow_invalid_id() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let result = client.start_dataflow("bad id").await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("INVALID_ID"));
+    }
+
+    #[tokio::test]
+    async fn test_wave_datasets_wiremock() {
+        use wiremock::matchers::{method, path_regex, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        let body = serde_json::json!({
+            "datasets": [{
+                "id": "0Fbxx0000000001CAA",
+                "name": "opportunities",
+                "label": "Opportunities"
+            }],
+            "nextPageUrl": null,
+            "totalSize": 1
+        });
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/wave/datasets$"))
+            .and(query_param("q", "opp"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let result = client
+            .wave_datasets(Some("opp"))
+            .await
+            .expect("wave_datasets should succeed");
+        assert_eq!(result.datasets.len(), 1);
+        assert_eq!(result.datasets[0].name, "opportunities");
+    }
+
+    #[tokio::test]
+    async fn test_wave_query_wiremock() {
+        use wiremock::matchers::{body_partial_json, method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        let saql = "q = load \"opportunities\"; q = limit q 10;";
+        let body = serde_json::json!({
+            "action": "query",
+            "responseId": "4abc",
+            "results": {"records": [{"Name": "Acme"}, {"Name": "Globex"}]},
+            "query": saql,
+            "responseTime": 12
+        });
+
+        Mock::given(method("POST"))
+            .and(path_regex(".*/wave/query$"))
+            .and(body_partial_json(serde_json::json!({"query": saql})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let result = client
+            .wave_query(saql)
+            .await
+            .expect("wave_query should succeed");
+        assert_eq!(result.results.records.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_start_dataflow_wiremock() {
+        use wiremock::matchers::{body_partial_json, method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        let body = serde_json::json!({
+            "id": "03Cxx0000000001AAA",
+            "label": "Default Salesforce Dataflow",
+            "status": "Queued",
+            "jobType": "dataflow"
+        });
+
+        Mock::given(method("POST"))
+            .and(path_regex(".*/wave/dataflowjobs$"))
+            .and(body_partial_json(serde_json::json!({
+                "dataflowId": "02Kxx0000000001EAA",
+                "command": "Start"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&body))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let job = client
+            .start_dataflow("02Kxx0000000001EAA")
+            .await
+            .expect("start_dataflow should succeed");
+        assert_eq!(job.status, "Queued");
+        assert!(!job.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_dataflow_job_wiremock() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        let body = serde_json::json!({
+            "id": "03Cxx0000000001AAA",
+            "status": "Success",
+            "duration": 57
+        });
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/wave/dataflowjobs/03Cxx0000000001AAA$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+            .mount(&mock_server)
+            .await;
+
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let job = client
+            .dataflow_job("03Cxx0000000001AAA")
+            .await
+            .expect("dataflow_job should succeed");
+        assert!(job.is_finished());
+        assert_eq!(job.duration, Some(57));
+    }
+}
@@ -1,6 +1,7 @@
 //! SObject Collections for batch operations.
 
-use crate::sobject::SalesforceError;
+use crate::sobject::{empty_id_as_none, SalesforceError};
+use crate::types::SalesforceId;
 use serde::{Deserialize, Serialize};
 
 /// Request for SObject Collections operations.
@@ -14,7 +15,8 @@ pub struct CollectionRequest {
 /// Result of a collection operation.
 #[derive(Debug, Clone, Deserialize)]
 pub struct CollectionResult {
-    pub id: Option<String>,
+    #[serde(default, deserialize_with = "empty_id_as_none")]
+    pub id: Option<SalesforceId>,
     pub success: bool,
     #[serde(default)]
     pub errors: Vec<SalesforceError>,
@@ -44,9 +46,9 @@ mod tests {
     #[test]
     fn test_collection_result_success() {
         let json =
-            json!({"id": "001xx000003DgAAAS", "success": true, "errors": [], "created": true});
+            json!({"id": "001xx000003DgAAAA0", "success": true, "errors": [], "created": true});
         let result: CollectionResult = serde_json::from_value(json).unwrap();
-        assert_eq!(result.id, Some("001xx000003DgAAAS".to_string()));
+        assert_eq!(result.id.unwrap(), "001xx000003DgAA");
         assert!(result.success);
         assert_eq!(result.created, Some(true));
         assert!(result.errors.is_empty());
@@ -71,9 +73,9 @@ mod tests {
     fn test_collection_result_batch_response() {
         // Salesforce returns an array of CollectionResults for batch ops
         let json = json!([
-            {"id": "001xx000003Dg1", "success": true, "errors": [], "created": true},
+            {"id": "001xx000003Dg01AAC", "success": true, "errors": [], "created": true},
             {"id": null, "success": false, "errors": [{"statusCode": "INVALID_FIELD", "message": "bad field", "fields": ["Foo"]}], "created": null},
-            {"id": "001xx000003Dg3", "success": true, "errors": [], "created": true}
+            {"id": "001xx000003Dg03AAC", "success": true, "errors": [], "created": true}
         ]);
         let results: Vec<CollectionResult> = serde_json::from_value(json).unwrap();
         assert_eq!(results.len(), 3);
@@ -81,4 +83,17 @@ mod tests {
         assert!(!results[1].success);
         assert!(results[2].success);
     }
+
+    #[test]
+    fn test_collection_result_empty_id_on_failure() {
+        // A failed subresult can report `"id": ""`; it must not fail the batch.
+        let json = json!([
+            {"id": "001xx000003Dg01AAC", "success": true, "errors": [], "created": true},
+            {"id": "", "success": false, "errors": [{"statusCode": "REQUIRED_FIELD_MISSING", "message": "Required fields are missing: [Name]", "fields": ["Name"]}], "created": false}
+        ]);
+        let results: Vec<CollectionResult> = serde_json::from_value(json).unwrap();
+        assert!(results[0].id.is_some());
+        assert!(results[1].id.is_none());
+        assert_eq!(results[1].errors[0].status_code, "REQUIRED_FIELD_MISSING");
+    }
 }
@@ -1,7 +1,14 @@
 //! Composite API operations.
 
+use std::collections::{BTreeMap, HashMap};
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::{Error, ErrorKind, Result};
+
+/// Most subrequests the composite API accepts in one call.
+pub const MAX_COMPOSITE_SUBREQUESTS: usize = 25;
+
 /// A composite request containing multiple subrequests.
 #[derive(Debug, Clone, Serialize)]
 pub struct CompositeRequest {
@@ -22,6 +29,141 @@ pub struct CompositeSubrequest {
     pub reference_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<serde_json::Value>,
+    /// Headers for this subrequest only, e.g. `Sforce-Auto-Assign` or
+    /// `If-Match`.
+    #[serde(
+        rename = "httpHeaders",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub http_headers: BTreeMap<String, String>,
+}
+
+impl CompositeSubrequest {
+    /// Add a header to this subrequest.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.http_headers.insert(name.into(), value.into());
+        self
+    }
+}
+
+impl CompositeRequest {
+    /// Split into requests of at most [`MAX_COMPOSITE_SUBREQUESTS`]
+    /// subrequests each, keeping the subrequests in order.
+    ///
+    /// The composite API only resolves `@{referenceId}` references within
+    /// one call, so a chunk is ended early rather than separate a
+    /// subrequest from one it references. Fails if references chain
+    /// together more subrequests than fit in one call.
+    pub fn split(&self) -> Result<Vec<CompositeRequest>> {
+        let positions: HashMap<&str, usize> = self
+            .subrequests
+            .iter()
+            .enumerate()
+            .map(|(i, sub)| (sub.reference_id.as_str(), i))
+            .collect();
+
+        // For each subrequest, the earliest subrequest it references.
+        let earliest: Vec<(usize, Option<&str>)> = self
+            .subrequests
+            .iter()
+            .enumerate()
+            .map(|(i, sub)| {
+                sub.references()
+                    .into_iter()
+                    .filter_map(|r| positions.get(r).map(|&j| (j, r)))
+                    .filter(|&(j, _)| j < i)
+                    .min_by_key(|&(j, _)| j)
+                    .map_or((i, None), |(j, r)| (j, Some(r)))
+            })
+            .collect();
+
+        // A chunk may end before `b` only if nothing from `b` on
+        // references a subrequest before `b`.
+        let mut lowest_after = vec![usize::MAX; self.subrequests.len() + 1];
+        for i in (0..self.subrequests.len()).rev() {
+            lowest_after[i] = lowest_after[i + 1].min(earliest[i].0);
+        }
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while start < self.subrequests.len() {
+            let limit = (start + MAX_COMPOSITE_SUBREQUESTS).min(self.subrequests.len());
+            let Some(end) = (start + 1..=limit).rev().find(|&b| lowest_after[b] >= b) else {
+                let mut message = format!(
+                    "Composite references cannot span more than \
+                     {MAX_COMPOSITE_SUBREQUESTS} subrequests"
+                );
+                if let Some((i, &(j, Some(reference)))) = earliest
+                    .iter()
+                    .enumerate()
+                    .skip(limit)
+                    .find(|(_, &(j, _))| j < limit)
+                {
+                    message = format!(
+                        "Composite subrequest '{}' references '{reference}' {} subrequests \
+                         earlier, but references cannot span more than \
+                         {MAX_COMPOSITE_SUBREQUESTS} subrequests",
+                        self.subrequests[i].reference_id,
+                        i - j
+                    );
+                }
+                return Err(Error::new(ErrorKind::Other(message)));
+            };
+            chunks.push(CompositeRequest {
+                all_or_none: self.all_or_none,
+                collate_subrequests: self.collate_subrequests,
+                subrequests: self.subrequests[start..end].to_vec(),
+            });
+            start = end;
+        }
+        Ok(chunks)
+    }
+}
+
+impl CompositeSubrequest {
+    /// Reference IDs this subrequest uses as `@{referenceId...}` in its URL,
+    /// body, or headers.
+    fn references(&self) -> Vec<&str> {
+        let mut refs = reference_ids(&self.url);
+        for value in self.http_headers.values() {
+            refs.extend(reference_ids(value));
+        }
+        if let Some(body) = &self.body {
+            collect_body_references(body, &mut refs);
+        }
+        refs
+    }
+}
+
+fn collect_body_references<'a>(value: &'a serde_json::Value, refs: &mut Vec<&'a str>) {
+    match value {
+        serde_json::Value::String(s) => refs.extend(reference_ids(s)),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_body_references(item, refs);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values() {
+                collect_body_references(item, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The `referenceId` of every `@{referenceId.path}` in `s`.
+fn reference_ids(s: &str) -> Vec<&str> {
+    s.match_indices("@{")
+        .filter_map(|(at, _)| {
+            let rest = &s[at + 2..];
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (len > 0).then(|| &rest[..len])
+        })
+        .collect()
 }
 
 /// Response from a composite request.
@@ -69,6 +211,21 @@ pub struct CompositeBatchSubrequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "binaryPartNameAlias")]
     pub binary_part_name_alias: Option<String>,
+    /// Headers for this subrequest only.
+    #[serde(
+        rename = "httpHeaders",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub http_headers: BTreeMap<String, String>,
+}
+
+impl CompositeBatchSubrequest {
+    /// Add a header to this subrequest.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.http_headers.insert(name.into(), value.into());
+        self
+    }
 }
 
 /// Response from a composite batch request.
@@ -186,6 +343,254 @@ pub struct GraphResponseBody {
     pub responses: Vec<CompositeSubresponse>,
 }
 
+/// Most nodes (subrequests) the composite graph API accepts in one graph.
+pub const MAX_GRAPH_NODES: usize = 500;
+
+impl CompositeGraphRequest {
+    /// Create an empty request; add graphs with [`graph`](Self::graph).
+    pub fn new() -> Self {
+        Self { graphs: Vec::new() }
+    }
+
+    /// Add a graph built with [`GraphBuilder`].
+    pub fn graph(mut self, graph: GraphRequest) -> Self {
+        self.graphs.push(graph);
+        self
+    }
+}
+
+impl Default for CompositeGraphRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphResponse {
+    /// The response to the node with `reference_id`.
+    pub fn response(&self, reference_id: &str) -> Option<&CompositeSubresponse> {
+        self.graph_response
+            .responses
+            .iter()
+            .find(|r| r.reference_id == reference_id)
+    }
+
+    /// The ID of the record created or upserted by the node with
+    /// `reference_id`.
+    pub fn created_id(&self, reference_id: &str) -> Option<&str> {
+        self.response(reference_id)?.body.get("id")?.as_str()
+    }
+}
+
+/// A node added to a [`GraphBuilder`], used to reference its result from
+/// later nodes.
+///
+/// Each reference is an edge in the graph: the referencing node runs after
+/// the node it references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRef {
+    reference_id: String,
+}
+
+impl NodeRef {
+    /// The node's `referenceId`.
+    pub fn reference_id(&self) -> &str {
+        &self.reference_id
+    }
+
+    /// `@{referenceId.id}`: the ID of the record the node created.
+    pub fn id(&self) -> String {
+        self.field("id")
+    }
+
+    /// `@{referenceId.path}` for any path into the node's response body,
+    /// e.g. `records[0].Id`.
+    pub fn field(&self, path: &str) -> String {
+        format!("@{{{}.{}}}", self.reference_id, path)
+    }
+}
+
+/// Builds one graph of a [`CompositeGraphRequest`].
+///
+/// Nodes are added in execution order and reference earlier nodes through
+/// the [`NodeRef`] each one returns. [`build`](Self::build) checks the
+/// references, so a misspelled or forward reference fails before the
+/// request is sent.
+///
+/// # Example
+///
+/// ```
+/// use busbar_sf_rest::{CompositeGraphRequest, GraphBuilder};
+/// use serde_json::json;
+///
+/// let mut graph = GraphBuilder::new("onboarding");
+/// let account = graph.create("acct", "Account", json!({"Name": "Acme"}));
+/// graph.create(
+///     "contact",
+///     "Contact",
+///     json!({"LastName": "Smith", "AccountId": account.id()}),
+/// );
+///
+/// let request = CompositeGraphRequest::new().graph(graph.build().unwrap());
+/// assert_eq!(request.graphs[0].composite_request.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraphBuilder {
+    graph_id: String,
+    api_version: String,
+    nodes: Vec<CompositeSubrequest>,
+}
+
+impl GraphBuilder {
+    /// Start a graph with the given `graphId`.
+    pub fn new(graph_id: impl Into<String>) -> Self {
+        Self {
+            graph_id: graph_id.into(),
+            api_version: busbar_sf_client::DEFAULT_API_VERSION.to_string(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// API version used in the URLs of nodes added with the SObject
+    /// helpers. Defaults to the client default.
+    pub fn api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = version.into();
+        self
+    }
+
+    /// Add an arbitrary subrequest as a node.
+    pub fn node(&mut self, subrequest: CompositeSubrequest) -> NodeRef {
+        let node = NodeRef {
+            reference_id: subrequest.reference_id.clone(),
+        };
+        self.nodes.push(subrequest);
+        node
+    }
+
+    /// Add a node that creates an `sobject` record.
+    pub fn create(
+        &mut self,
+        reference_id: impl Into<String>,
+        sobject: &str,
+        record: serde_json::Value,
+    ) -> NodeRef {
+        let url = self.sobject_url(sobject, &[]);
+        self.push(reference_id, "POST", url, Some(record))
+    }
+
+    /// Add a node that updates the `sobject` record `id`, which may be a
+    /// reference such as [`NodeRef::id`].
+    pub fn update(
+        &mut self,
+        reference_id: impl Into<String>,
+        sobject: &str,
+        id: &str,
+        record: serde_json::Value,
+    ) -> NodeRef {
+        let url = self.sobject_url(sobject, &[id]);
+        self.push(reference_id, "PATCH", url, Some(record))
+    }
+
+    /// Add a node that upserts an `sobject` record on an external ID field.
+    pub fn upsert(
+        &mut self,
+        reference_id: impl Into<String>,
+        sobject: &str,
+        external_id_field: &str,
+        external_id: &str,
+        record: serde_json::Value,
+    ) -> NodeRef {
+        let url = self.sobject_url(sobject, &[external_id_field, external_id]);
+        self.push(reference_id, "PATCH", url, Some(record))
+    }
+
+    /// Add a node that deletes the `sobject` record `id`.
+    pub fn delete(&mut self, reference_id: impl Into<String>, sobject: &str, id: &str) -> NodeRef {
+        let url = self.sobject_url(sobject, &[id]);
+        self.push(reference_id, "DELETE", url, None)
+    }
+
+    /// Add a node that reads the `sobject` record `id`.
+    pub fn get(&mut self, reference_id: impl Into<String>, sobject: &str, id: &str) -> NodeRef {
+        let url = self.sobject_url(sobject, &[id]);
+        self.push(reference_id, "GET", url, None)
+    }
+
+    /// Finish the graph.
+    ///
+    /// Fails if the graph is empty or has more than [`MAX_GRAPH_NODES`]
+    /// nodes, if a `referenceId` is not a valid identifier or is used
+    /// twice, or if a node references one that is not earlier in the graph.
+    pub fn build(self) -> Result<GraphRequest> {
+        let invalid = |message: String| {
+            Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_GRAPH".to_string(),
+                message,
+            }))
+        };
+        if self.nodes.is_empty() || self.nodes.len() > MAX_GRAPH_NODES {
+            return invalid(format!(
+                "Graph '{}' must have between 1 and {MAX_GRAPH_NODES} nodes",
+                self.graph_id
+            ));
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for node in &self.nodes {
+            for reference in node.references() {
+                if !seen.contains(reference) {
+                    return invalid(format!(
+                        "Node '{}' references '{reference}', which is not an earlier node",
+                        node.reference_id
+                    ));
+                }
+            }
+            if !is_reference_id(&node.reference_id) {
+                return invalid(format!("Invalid referenceId '{}'", node.reference_id));
+            }
+            if !seen.insert(node.reference_id.as_str()) {
+                return invalid(format!("Duplicate referenceId '{}'", node.reference_id));
+            }
+        }
+
+        Ok(GraphRequest {
+            graph_id: self.graph_id,
+            composite_request: self.nodes,
+        })
+    }
+
+    fn sobject_url(&self, sobject: &str, segments: &[&str]) -> String {
+        let mut url = format!("/services/data/v{}/sobjects/{}", self.api_version, sobject);
+        for segment in segments {
+            url.push('/');
+            url.push_str(segment);
+        }
+        url
+    }
+
+    fn push(
+        &mut self,
+        reference_id: impl Into<String>,
+        method: &str,
+        url: String,
+        body: Option<serde_json::Value>,
+    ) -> NodeRef {
+        self.node(CompositeSubrequest {
+            method: method.to_string(),
+            url,
+            reference_id: reference_id.into(),
+            body,
+            http_headers: BTreeMap::new(),
+        })
+    }
+}
+
+/// Reference IDs start with a letter and contain only letters, digits,
+/// and underscores.
+fn is_reference_id(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic())
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,12 +607,15 @@ mod tests {
                     url: "/services/data/v62.0/sobjects/Account".to_string(),
                     reference_id: "NewAccount".to_string(),
                     body: Some(json!({"Name": "Test Corp"})),
-                },
+                    http_headers: Default::default(),
+                }
+                .with_header("Sforce-Auto-Assign", "FALSE"),
                 CompositeSubrequest {
                     method: "GET".to_string(),
                     url: "/services/data/v62.0/sobjects/Account/@{NewAccount.id}".to_string(),
                     reference_id: "GetAccount".to_string(),
                     body: None,
+                    http_headers: Default::default(),
                 },
             ],
         };
@@ -221,11 +629,13 @@ mod tests {
         assert_eq!(first["method"], "POST");
         assert_eq!(first["referenceId"], "NewAccount");
         assert!(first["body"].is_object());
+        assert_eq!(first["httpHeaders"]["Sforce-Auto-Assign"], "FALSE");
 
         // GET subrequest should omit null body
         let second = &json["compositeRequest"][1];
         assert_eq!(second["method"], "GET");
         assert!(second.get("body").is_none());
+        assert!(second.get("httpHeaders").is_none());
     }
 
     #[test]
@@ -263,6 +673,7 @@ mod tests {
                 rich_input: None,
                 binary_part_name: None,
                 binary_part_name_alias: None,
+                http_headers: Default::default(),
             }],
             halt_on_error: true,
         };
@@ -371,6 +782,7 @@ mod tests {
                     url: "/services/data/v62.0/sobjects/Account".to_string(),
                     reference_id: "Account1".to_string(),
                     body: Some(json!({"Name": "Test"})),
+                    http_headers: Default::default(),
                 }],
             }],
         };
@@ -411,4 +823,131 @@ mod tests {
             201
         );
     }
+
+    fn subrequest(reference_id: &str, body: serde_json::Value) -> CompositeSubrequest {
+        CompositeSubrequest {
+            method: "POST".to_string(),
+            url: "/services/data/v62.0/sobjects/Contact".to_string(),
+            reference_id: reference_id.to_string(),
+            body: Some(body),
+            http_headers: BTreeMap::new(),
+        }
+    }
+
+    fn request(subrequests: Vec<CompositeSubrequest>) -> CompositeRequest {
+        CompositeRequest {
+            all_or_none: true,
+            collate_subrequests: false,
+            subrequests,
+        }
+    }
+
+    fn sizes(chunks: &[CompositeRequest]) -> Vec<usize> {
+        chunks.iter().map(|c| c.subrequests.len()).collect()
+    }
+
+    #[test]
+    fn test_split_fills_chunks() {
+        let subrequests = (0..60)
+            .map(|i| subrequest(&format!("c{i}"), json!({"LastName": "x"})))
+            .collect();
+        let chunks = request(subrequests).split().unwrap();
+        assert_eq!(sizes(&chunks), [25, 25, 10]);
+        assert!(chunks.iter().all(|c| c.all_or_none));
+        assert_eq!(chunks[1].subrequests[0].reference_id, "c25");
+    }
+
+    #[test]
+    fn test_split_keeps_references_in_one_chunk() {
+        // c20 is created, then referenced from c26 and through a header
+        // on c27, so c20..=c27 must travel together.
+        let mut subrequests: Vec<_> = (0..40)
+            .map(|i| subrequest(&format!("c{i}"), json!({"LastName": "x"})))
+            .collect();
+        subrequests[26].body = Some(json!({"Notes": [{"ParentId": "@{c20.id}"}]}));
+        subrequests[27] = subrequests[27]
+            .clone()
+            .with_header("If-Match", "@{c22.body.etag}");
+        subrequests[30].url = "/services/data/v62.0/sobjects/Contact/@{c29.id}".to_string();
+
+        let chunks = request(subrequests).split().unwrap();
+        assert_eq!(sizes(&chunks), [20, 20]);
+        assert_eq!(chunks[1].subrequests[0].reference_id, "c20");
+    }
+
+    #[test]
+    fn test_split_rejects_references_across_the_limit() {
+        let mut subrequests: Vec<_> = (0..30)
+            .map(|i| subrequest(&format!("c{i}"), json!({"LastName": "x"})))
+            .collect();
+        subrequests[28].body = Some(json!({"AccountId": "@{c1.id}"}));
+
+        let err = request(subrequests).split().unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("'c28' references 'c1'"), "{message}");
+        assert!(message.contains("27 subrequests earlier"), "{message}");
+    }
+
+    #[test]
+    fn test_graph_builder_links_nodes() {
+        let mut graph = GraphBuilder::new("onboarding").api_version("60.0");
+        let account = graph.create("acct", "Account", json!({"Name": "Acme"}));
+        let contact = graph.create(
+            "contact",
+            "Contact",
+            json!({"LastName": "Smith", "AccountId": account.id()}),
+        );
+        graph.update(
+            "rename",
+            "Account",
+            &account.id(),
+            json!({"Description": contact.field("id")}),
+        );
+
+        let request = CompositeGraphRequest::new().graph(graph.build().unwrap());
+        let json = serde_json::to_value(&request).unwrap();
+        let nodes = &json["graphs"][0]["compositeRequest"];
+        assert_eq!(json["graphs"][0]["graphId"], "onboarding");
+        assert_eq!(nodes[0]["url"], "/services/data/v60.0/sobjects/Account");
+        assert_eq!(nodes[1]["body"]["AccountId"], "@{acct.id}");
+        assert_eq!(nodes[2]["method"], "PATCH");
+        assert_eq!(
+            nodes[2]["url"],
+            "/services/data/v60.0/sobjects/Account/@{acct.id}"
+        );
+    }
+
+    #[test]
+    fn test_graph_builder_rejects_bad_references() {
+        let mut graph = GraphBuilder::new("g");
+        graph.create("contact", "Contact", json!({"AccountId": "@{acct.id}"}));
+        graph.create("acct", "Account", json!({"Name": "Acme"}));
+        let err = graph.build().unwrap_err().to_string();
+        assert!(err.contains("'contact' references 'acct'"), "{err}");
+
+        let mut graph = GraphBuilder::new("g");
+        graph.create("acct", "Account", json!({}));
+        graph.create("acct", "Account", json!({}));
+        let err = graph.build().unwrap_err().to_string();
+        assert!(err.contains("Duplicate referenceId 'acct'"), "{err}");
+
+        assert!(GraphBuilder::new("empty").build().is_err());
+    }
+
+    #[test]
+    fn test_graph_response_lookup() {
+        let response: GraphResponse = serde_json::from_value(json!({
+            "graphId": "g",
+            "isSuccessful": true,
+            "graphResponse": {"compositeResponse": [{
+                "body": {"id": "001xx000003DGb2AAG", "success": true, "errors": []},
+                "httpHeaders": {},
+                "httpStatusCode": 201,
+                "referenceId": "acct"
+            }]}
+        }))
+        .unwrap();
+        assert_eq!(response.created_id("acct"), Some("001xx000003DGb2AAG"));
+        assert!(response.response("missing").is_none());
+    }
 }
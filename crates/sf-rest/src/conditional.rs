@@ -0,0 +1,145 @@
+//! Conditional request types: cached describes and records, and
+//! optimistic-concurrency writes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Cache validators from a previous response, sent back as `If-None-Match`
+/// and `If-Modified-Since`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheValidators {
+    /// The `ETag` response header.
+    pub etag: Option<String>,
+    /// The `Last-Modified` response header, in HTTP date format.
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    /// Validators with only an ETag.
+    pub fn etag(etag: impl Into<String>) -> Self {
+        Self {
+            etag: Some(etag.into()),
+            last_modified: None,
+        }
+    }
+
+    /// Validators with only a timestamp, e.g. `Wed, 21 Oct 2015 07:28:00 GMT`.
+    pub fn modified_since(timestamp: impl Into<String>) -> Self {
+        Self {
+            etag: None,
+            last_modified: Some(timestamp.into()),
+        }
+    }
+
+    /// Whether there is nothing to validate against.
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Result of a conditional GET.
+#[derive(Debug, Clone)]
+pub enum Conditional<T> {
+    /// The resource changed (or there were no validators); keep `validators`
+    /// for the next request.
+    Modified {
+        /// The fresh resource.
+        value: T,
+        /// Validators from this response.
+        validators: CacheValidators,
+    },
+    /// The resource is unchanged (HTTP 304); reuse the cached copy.
+    NotModified,
+}
+
+impl<T> Conditional<T> {
+    /// Whether the server answered 304 Not Modified.
+    pub fn is_not_modified(&self) -> bool {
+        matches!(self, Conditional::NotModified)
+    }
+
+    /// The fresh value, if the resource changed.
+    pub fn into_modified(self) -> Option<T> {
+        match self {
+            Conditional::Modified { value, .. } => Some(value),
+            Conditional::NotModified => None,
+        }
+    }
+}
+
+/// Condition a record must meet for an update or delete to go ahead.
+///
+/// Read the record, then write with the precondition: if someone else
+/// changed it in between, Salesforce refuses the write with HTTP 412
+/// (see [`Error::is_precondition_failed`](crate::Error::is_precondition_failed))
+/// instead of overwriting their change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WritePrecondition {
+    /// `If-Match`: the record's ETag must be unchanged. Only objects that
+    /// return an `ETag` support this.
+    IfMatch(String),
+    /// `If-Unmodified-Since`: the record must not have been modified after
+    /// this HTTP date. Works for every object.
+    IfUnmodifiedSince(String),
+}
+
+impl WritePrecondition {
+    /// `If-Unmodified-Since` the given time, typically the record's
+    /// `LastModifiedDate` as read.
+    pub fn unmodified_since(time: DateTime<Utc>) -> Self {
+        Self::IfUnmodifiedSince(time.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+    }
+
+    /// The precondition matching validators from an earlier read,
+    /// preferring the ETag.
+    pub fn from_validators(validators: &CacheValidators) -> Option<Self> {
+        match validators {
+            CacheValidators {
+                etag: Some(etag), ..
+            } => Some(Self::IfMatch(etag.clone())),
+            CacheValidators {
+                last_modified: Some(timestamp),
+                ..
+            } => Some(Self::IfUnmodifiedSince(timestamp.clone())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_precondition_from_validators() {
+        let both = CacheValidators {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+        };
+        assert_eq!(
+            WritePrecondition::from_validators(&both),
+            Some(WritePrecondition::IfMatch("\"abc\"".to_string()))
+        );
+        assert_eq!(
+            WritePrecondition::from_validators(&CacheValidators::modified_since(
+                "Wed, 21 Oct 2015 07:28:00 GMT"
+            )),
+            Some(WritePrecondition::IfUnmodifiedSince(
+                "Wed, 21 Oct 2015 07:28:00 GMT".to_string()
+            ))
+        );
+        assert_eq!(
+            WritePrecondition::from_validators(&CacheValidators::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unmodified_since_formats_http_date() {
+        let time = "2015-10-21T07:28:00Z".parse().unwrap();
+        assert_eq!(
+            WritePrecondition::unmodified_since(time),
+            WritePrecondition::IfUnmodifiedSince("Wed, 21 Oct 2015 07:28:00 GMT".to_string())
+        );
+    }
+}
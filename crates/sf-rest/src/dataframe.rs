@@ -0,0 +1,283 @@
+//! Polars DataFrames from query results.
+//!
+//! Column types come from the object's describe when it is available, so a
+//! `currency` field becomes `Float64` and a `date` field becomes `Date` even
+//! when every sampled value is null. Columns the describe doesn't cover
+//! (relationship paths, aggregate aliases) fall back to the type inferred
+//! from the values, as in [`QueryProfile`](crate::QueryProfile).
+
+use std::collections::HashMap;
+
+use polars::prelude::{Column, DataFrame, DataType, NamedFrom, Series, TimeUnit};
+
+use crate::describe::DescribeSObjectResult;
+use crate::error::{Error, ErrorKind, Result};
+use crate::profile::{flatten, InferredType, QueryProfile};
+
+/// How a column's values are converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Boolean,
+    Int,
+    Float,
+    Date,
+    DateTime,
+    Time,
+    String,
+}
+
+impl ColumnType {
+    fn from_describe(field_type: &str) -> Self {
+        match field_type {
+            "boolean" => ColumnType::Boolean,
+            "int" => ColumnType::Int,
+            "double" | "currency" | "percent" => ColumnType::Float,
+            "date" => ColumnType::Date,
+            "datetime" => ColumnType::DateTime,
+            "time" => ColumnType::Time,
+            _ => ColumnType::String,
+        }
+    }
+
+    fn from_inferred(inferred: InferredType) -> Self {
+        match inferred {
+            InferredType::Boolean => ColumnType::Boolean,
+            InferredType::Integer => ColumnType::Int,
+            InferredType::Double => ColumnType::Float,
+            InferredType::Date => ColumnType::Date,
+            InferredType::DateTime => ColumnType::DateTime,
+            InferredType::Time => ColumnType::Time,
+            _ => ColumnType::String,
+        }
+    }
+}
+
+/// Convert query records into a DataFrame.
+///
+/// Parent relationship fields become dotted columns (`Owner.Name`); child
+/// subqueries and compound fields are kept as JSON strings. Datetimes are
+/// UTC, in milliseconds. Values that don't parse as their column's type are
+/// null.
+///
+/// Records are the raw JSON returned by a query, including Bulk API CSV
+/// rows turned into string-valued objects.
+pub fn records_to_dataframe(
+    records: &[serde_json::Value],
+    describe: Option<&DescribeSObjectResult>,
+) -> Result<DataFrame> {
+    let profile = QueryProfile::from_records(records, records.len() as u64);
+    let rows: Vec<HashMap<String, &serde_json::Value>> = records
+        .iter()
+        .map(|record| {
+            let mut flat = Vec::new();
+            flatten("", record, &mut flat);
+            flat.into_iter().collect()
+        })
+        .collect();
+
+    let columns = profile
+        .fields
+        .iter()
+        .map(|field| {
+            let column_type = describe
+                .and_then(|d| d.fields.iter().find(|f| f.name == field.name))
+                .map_or_else(
+                    || ColumnType::from_inferred(field.inferred_type),
+                    |f| ColumnType::from_describe(&f.field_type),
+                );
+            let values = rows.iter().map(|row| {
+                row.get(&field.name)
+                    .copied()
+                    .filter(|value| !value.is_null())
+            });
+            build_column(&field.name, column_type, values)
+        })
+        .collect::<Result<Vec<Column>>>()?;
+
+    DataFrame::new(records.len(), columns).map_err(polars_error)
+}
+
+fn build_column<'a>(
+    name: &str,
+    column_type: ColumnType,
+    values: impl Iterator<Item = Option<&'a serde_json::Value>>,
+) -> Result<Column> {
+    let series = match column_type {
+        ColumnType::Boolean => {
+            let values: Vec<Option<bool>> = values.map(|v| v.and_then(as_bool)).collect();
+            Series::new(name.into(), values)
+        }
+        ColumnType::Int => {
+            let values: Vec<Option<i64>> = values.map(|v| v.and_then(as_i64)).collect();
+            Series::new(name.into(), values)
+        }
+        ColumnType::Float => {
+            let values: Vec<Option<f64>> = values.map(|v| v.and_then(as_f64)).collect();
+            Series::new(name.into(), values)
+        }
+        ColumnType::Date => {
+            let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+            let values: Vec<Option<i32>> = values
+                .map(|v| {
+                    let date = chrono::NaiveDate::parse_from_str(v?.as_str()?, "%Y-%m-%d").ok()?;
+                    i32::try_from((date - epoch).num_days()).ok()
+                })
+                .collect();
+            Series::new(name.into(), values)
+                .cast(&DataType::Date)
+                .map_err(polars_error)?
+        }
+        ColumnType::DateTime => {
+            let values: Vec<Option<i64>> = values
+                .map(|v| parse_datetime_millis(v?.as_str()?))
+                .collect();
+            Series::new(name.into(), values)
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+                .map_err(polars_error)?
+        }
+        ColumnType::Time => {
+            let values: Vec<Option<i64>> = values
+                .map(|v| {
+                    let s = v?.as_str()?;
+                    let time = chrono::NaiveTime::parse_from_str(
+                        s.strip_suffix('Z').unwrap_or(s),
+                        "%H:%M:%S%.f",
+                    )
+                    .ok()?;
+                    let since_midnight = time - chrono::NaiveTime::MIN;
+                    since_midnight.num_nanoseconds()
+                })
+                .collect();
+            Series::new(name.into(), values)
+                .cast(&DataType::Time)
+                .map_err(polars_error)?
+        }
+        ColumnType::String => {
+            let values: Vec<Option<String>> = values
+                .map(|v| {
+                    v.map(|v| match v.as_str() {
+                        Some(s) => s.to_string(),
+                        None => v.to_string(),
+                    })
+                })
+                .collect();
+            Series::new(name.into(), values)
+        }
+    };
+    Ok(series.into())
+}
+
+fn as_bool(value: &serde_json::Value) -> Option<bool> {
+    match value {
+        serde_json::Value::Bool(b) => Some(*b),
+        serde_json::Value::String(s) if s.eq_ignore_ascii_case("true") => Some(true),
+        serde_json::Value::String(s) if s.eq_ignore_ascii_case("false") => Some(false),
+        _ => None,
+    }
+}
+
+fn as_i64(value: &serde_json::Value) -> Option<i64> {
+    match value {
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64)),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_f64(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Salesforce returns `2024-01-04T10:00:00.000+0000`; Bulk API CSVs use
+/// `2024-01-04T10:00:00.000Z`.
+fn parse_datetime_millis(s: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .or_else(|_| chrono::DateTime::parse_from_rfc3339(s))
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+fn polars_error(e: polars::prelude::PolarsError) -> Error {
+    Error::new(ErrorKind::Other(format!(
+        "DataFrame conversion failed: {}",
+        e
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_records_to_dataframe_with_describe() {
+        let describe: DescribeSObjectResult = serde_json::from_value(json!({
+            "name": "Opportunity",
+            "label": "Opportunity",
+            "custom": false,
+            "fields": [
+                {"name": "Name", "label": "Name", "type": "string"},
+                {"name": "Amount", "label": "Amount", "type": "currency"},
+                {"name": "CloseDate", "label": "Close Date", "type": "date"},
+                {"name": "IsWon", "label": "Won", "type": "boolean"},
+                {"name": "CreatedDate", "label": "Created", "type": "datetime"}
+            ]
+        }))
+        .unwrap();
+        let records = vec![
+            json!({
+                "attributes": {"type": "Opportunity"},
+                "Name": "Big Deal",
+                "Amount": 5000,
+                "CloseDate": "2024-03-01",
+                "IsWon": true,
+                "CreatedDate": "2024-01-04T10:00:00.000+0000",
+                "Account": {"attributes": {"type": "Account"}, "Name": "Acme"}
+            }),
+            json!({
+                "attributes": {"type": "Opportunity"},
+                "Name": "Small Deal",
+                "Amount": null,
+                "CloseDate": null,
+                "IsWon": false,
+                "CreatedDate": "2024-01-05T10:00:00.000Z",
+                "Account": null
+            }),
+        ];
+
+        let df = records_to_dataframe(&records, Some(&describe)).unwrap();
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.column("Amount").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("CloseDate").unwrap().dtype(), &DataType::Date);
+        assert_eq!(df.column("IsWon").unwrap().dtype(), &DataType::Boolean);
+        assert_eq!(
+            df.column("CreatedDate").unwrap().dtype(),
+            &DataType::Datetime(TimeUnit::Milliseconds, None)
+        );
+        assert_eq!(
+            df.column("Account.Name").unwrap().dtype(),
+            &DataType::String
+        );
+        assert_eq!(df.column("Amount").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn test_records_to_dataframe_inferred() {
+        let records = vec![
+            json!({"attributes": {"type": "AggregateResult"}, "StageName": "Won", "expr0": 3}),
+            json!({"attributes": {"type": "AggregateResult"}, "StageName": "Lost", "expr0": 7}),
+        ];
+        let df = records_to_dataframe(&records, None).unwrap();
+        assert_eq!(df.get_column_names(), vec!["StageName", "expr0"]);
+        assert_eq!(df.column("expr0").unwrap().dtype(), &DataType::Int64);
+
+        let empty = records_to_dataframe(&[], None).unwrap();
+        assert_eq!(empty.height(), 0);
+    }
+}
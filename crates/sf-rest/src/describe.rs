@@ -166,6 +166,16 @@ pub struct DescribeSObjectResult {
     pub action_overrides: Vec<ActionOverride>,
 }
 
+/// An SObject describe together with the object's page layouts.
+///
+/// Returned by
+/// [`describe_sobject_with_layouts`](crate::SalesforceRestClient::describe_sobject_with_layouts).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DescribeWithLayouts {
+    pub describe: DescribeSObjectResult,
+    pub layouts: crate::layout::DescribeLayoutsResult,
+}
+
 /// Child relationship metadata for an SObject.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChildRelationship {
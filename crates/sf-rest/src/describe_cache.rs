@@ -0,0 +1,510 @@
+//! Shared cache for SObject describes.
+//!
+//! A [`DescribeCache`] keeps describes in memory, and optionally on disk,
+//! keyed by org, user, API version, `Accept-Language`, and SObject. Entries younger than the cache's
+//! max age are served without a request. Older entries are revalidated with
+//! a conditional GET, so an unchanged schema costs a 304 rather than a full
+//! download.
+//!
+//! The cache is cheap to clone; clones share entries. Attach one to any
+//! number of clients with
+//! [`SalesforceRestClient::with_describe_cache`](crate::SalesforceRestClient::with_describe_cache).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::conditional::{CacheValidators, Conditional};
+use crate::describe::DescribeSObjectResult;
+use crate::error::{Error, ErrorKind, Result};
+
+/// How long an entry is served without revalidation by default.
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    instance_url: String,
+    /// Who the describe was fetched as, since field visibility differs
+    /// between users. See [`user_key`].
+    user: String,
+    api_version: String,
+    /// The client's `Accept-Language`; translated labels differ per language.
+    language: Option<String>,
+    sobject: String,
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    describe: Arc<DescribeSObjectResult>,
+    validators: CacheValidators,
+    /// When the entry was last fetched or revalidated; `None` for entries
+    /// loaded from disk, which are always revalidated before use.
+    checked_at: Option<Instant>,
+}
+
+/// The on-disk form of an entry.
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    validators: CacheValidators,
+    describe: DescribeSObjectResult,
+}
+
+/// Counters describing how a [`DescribeCache`] has been used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DescribeCacheStats {
+    /// Describes served without any request.
+    pub hits: u64,
+    /// Revalidations answered with 304 Not Modified.
+    pub revalidated: u64,
+    /// Full describes downloaded.
+    pub fetched: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    revalidated: AtomicU64,
+    fetched: AtomicU64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    entries: Mutex<HashMap<CacheKey, CacheEntry>>,
+    dir: Option<PathBuf>,
+    max_age: Duration,
+    counters: Counters,
+}
+
+/// An opt-in cache for `describe_sobject`, shared by every client it is
+/// attached to.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_rest::{DescribeCache, SalesforceRestClient};
+///
+/// let cache = DescribeCache::new().with_disk(".cache/describes");
+/// let client = SalesforceRestClient::new(url, token)?.with_describe_cache(cache.clone());
+///
+/// client.describe_sobject("Account").await?; // downloaded
+/// client.describe_sobject("Account").await?; // served from memory
+/// ```
+#[derive(Debug, Clone)]
+pub struct DescribeCache {
+    inner: Arc<Inner>,
+}
+
+impl Default for DescribeCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DescribeCache {
+    /// An in-memory cache that revalidates entries older than five minutes.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                entries: Mutex::new(HashMap::new()),
+                dir: None,
+                max_age: DEFAULT_MAX_AGE,
+                counters: Counters::default(),
+            }),
+        }
+    }
+
+    /// Also persist describes under `dir`, so they survive restarts.
+    ///
+    /// Entries read back from disk are revalidated before first use.
+    pub fn with_disk(self, dir: impl Into<PathBuf>) -> Self {
+        self.rebuild(|inner| inner.dir = Some(dir.into()))
+    }
+
+    /// How long an entry is served before it is revalidated. Zero
+    /// revalidates on every call.
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        self.rebuild(|inner| inner.max_age = max_age)
+    }
+
+    fn rebuild(self, configure: impl FnOnce(&mut Inner)) -> Self {
+        let mut inner = Inner {
+            entries: Mutex::new(self.inner.entries.lock().unwrap().clone()),
+            dir: self.inner.dir.clone(),
+            max_age: self.inner.max_age,
+            counters: Counters::default(),
+        };
+        configure(&mut inner);
+        Self {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Drop the cached describe of `sobject` for every org and version.
+    pub fn invalidate(&self, sobject: &str) {
+        self.inner
+            .entries
+            .lock()
+            .unwrap()
+            .retain(|key, _| !key.sobject.eq_ignore_ascii_case(sobject));
+    }
+
+    /// Drop every in-memory entry. Files on disk are kept, and are
+    /// revalidated before they are used again.
+    pub fn clear(&self) {
+        self.inner.entries.lock().unwrap().clear();
+    }
+
+    /// How the cache has been used so far.
+    pub fn stats(&self) -> DescribeCacheStats {
+        let counters = &self.inner.counters;
+        DescribeCacheStats {
+            hits: counters.hits.load(Ordering::Relaxed),
+            revalidated: counters.revalidated.load(Ordering::Relaxed),
+            fetched: counters.fetched.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Return the describe of `sobject`, using `client` to fetch or
+    /// revalidate it as needed.
+    pub(crate) async fn describe(
+        &self,
+        client: &crate::SalesforceRestClient,
+        sobject: &str,
+    ) -> Result<DescribeSObjectResult> {
+        let key = CacheKey {
+            instance_url: client.instance_url().to_string(),
+            user: user_key(client.inner().access_token()),
+            api_version: client.api_version().to_string(),
+            language: client.inner().call_options().accept_language.clone(),
+            sobject: sobject.to_ascii_lowercase(),
+        };
+
+        let cached = self.inner.entries.lock().unwrap().get(&key).cloned();
+        let cached = match cached {
+            Some(entry) => Some(entry),
+            None => self.load(&key).await,
+        };
+
+        if let Some(entry) = &cached {
+            if entry
+                .checked_at
+                .is_some_and(|at| at.elapsed() < self.inner.max_age)
+            {
+                self.inner.counters.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok((*entry.describe).clone());
+            }
+        }
+
+        let validators = cached
+            .as_ref()
+            .map(|entry| entry.validators.clone())
+            .unwrap_or_default();
+        let entry = match client
+            .describe_sobject_if_modified(sobject, &validators)
+            .await?
+        {
+            Conditional::NotModified => {
+                let Some(mut entry) = cached else {
+                    return Err(Error::new(ErrorKind::Other(format!(
+                        "Unexpected 304 for uncached describe of {}",
+                        sobject
+                    ))));
+                };
+                self.inner
+                    .counters
+                    .revalidated
+                    .fetch_add(1, Ordering::Relaxed);
+                entry.checked_at = Some(Instant::now());
+                entry
+            }
+            Conditional::Modified {
+                value,
+                mut validators,
+            } => {
+                self.inner.counters.fetched.fetch_add(1, Ordering::Relaxed);
+                if validators.is_empty() {
+                    // Describes don't always carry validators; fall back to
+                    // the fetch time for If-Modified-Since.
+                    validators = CacheValidators::modified_since(
+                        chrono::Utc::now()
+                            .format("%a, %d %b %Y %H:%M:%S GMT")
+                            .to_string(),
+                    );
+                }
+                let entry = CacheEntry {
+                    describe: Arc::new(value),
+                    validators,
+                    checked_at: Some(Instant::now()),
+                };
+                self.store(&key, &entry).await;
+                entry
+            }
+        };
+
+        let describe = (*entry.describe).clone();
+        self.inner.entries.lock().unwrap().insert(key, entry);
+        Ok(describe)
+    }
+
+    fn path(&self, key: &CacheKey) -> Option<PathBuf> {
+        let dir = self.inner.dir.as_deref()?;
+        let host = url::Url::parse(&key.instance_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| "default".to_string());
+        let file = match &key.language {
+            Some(language) => {
+                let language: String = language
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || c == '-' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                format!("{}.{}.json", key.sobject, language)
+            }
+            None => format!("{}.json", key.sobject),
+        };
+        Some(
+            dir.join(host)
+                .join(&key.user)
+                .join(format!("v{}", key.api_version))
+                .join(file),
+        )
+    }
+
+    async fn load(&self, key: &CacheKey) -> Option<CacheEntry> {
+        let path = self.path(key)?;
+        let bytes = tokio::fs::read(&path).await.ok()?;
+        match serde_json::from_slice::<DiskEntry>(&bytes) {
+            Ok(disk) => Some(CacheEntry {
+                describe: Arc::new(disk.describe),
+                validators: disk.validators,
+                checked_at: None,
+            }),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Ignoring unreadable cached describe");
+                None
+            }
+        }
+    }
+
+    /// Write `entry` to disk. Failures are logged and otherwise ignored;
+    /// the in-memory entry still works.
+    async fn store(&self, key: &CacheKey, entry: &CacheEntry) {
+        let Some(path) = self.path(key) else {
+            return;
+        };
+        let disk = DiskEntry {
+            validators: entry.validators.clone(),
+            describe: (*entry.describe).clone(),
+        };
+        if let Err(e) = write_json(&path, &disk).await {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to persist describe");
+        }
+    }
+}
+
+/// Identify the user behind `access_token` without keeping the token: the
+/// first 16 bytes of its SHA-256 digest, in hex.
+///
+/// A refreshed token gets a new key, so its first describes are downloaded
+/// again rather than served from another session's entries.
+fn user_key(access_token: &str) -> String {
+    Sha256::digest(access_token.as_bytes())[..16].iter().fold(
+        String::with_capacity(32),
+        |mut hex, b| {
+            hex.push_str(&format!("{:02x}", b));
+            hex
+        },
+    )
+}
+
+async fn write_json(path: &Path, value: &DiskEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let bytes = serde_json::to_vec(value)?;
+    tokio::fs::write(path, bytes).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SalesforceRestClient;
+    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const DESCRIBE_PATH: &str = "/services/data/v62.0/sobjects/Account/describe";
+
+    fn account_describe() -> serde_json::Value {
+        serde_json::json!({"name": "Account", "label": "Account", "custom": false, "fields": []})
+    }
+
+    #[tokio::test]
+    async fn test_describe_cache_serves_and_revalidates() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(DESCRIBE_PATH))
+            .and(header_exists("If-Modified-Since"))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(DESCRIBE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(account_describe()))
+            .mount(&mock_server)
+            .await;
+
+        let cache = DescribeCache::new();
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token")
+            .unwrap()
+            .with_describe_cache(cache.clone());
+        client.describe_sobject("Account").await.unwrap();
+        client.describe_sobject("account").await.unwrap();
+
+        // A second client shares the entries.
+        let other = SalesforceRestClient::new(mock_server.uri(), "test-token")
+            .unwrap()
+            .with_describe_cache(cache.clone());
+        other.describe_sobject("Account").await.unwrap();
+        assert_eq!(
+            cache.stats(),
+            DescribeCacheStats {
+                hits: 2,
+                revalidated: 0,
+                fetched: 1
+            }
+        );
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 1);
+
+        let always_check = DescribeCache::new().with_max_age(Duration::ZERO);
+        let client = client.with_describe_cache(always_check.clone());
+        client.describe_sobject("Account").await.unwrap();
+        let describe = client.describe_sobject("Account").await.unwrap();
+        assert_eq!(describe.name, "Account");
+        assert_eq!(always_check.stats().fetched, 1);
+        assert_eq!(always_check.stats().revalidated, 1);
+    }
+
+    #[tokio::test]
+    async fn test_describe_cache_keys_on_user() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(DESCRIBE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(account_describe()))
+            .mount(&mock_server)
+            .await;
+
+        let cache = DescribeCache::new();
+        for token in ["admin-token", "integration-token", "admin-token"] {
+            SalesforceRestClient::new(mock_server.uri(), token)
+                .unwrap()
+                .with_describe_cache(cache.clone())
+                .describe_sobject("Account")
+                .await
+                .unwrap();
+        }
+
+        // Each user's describe is downloaded once; the other's is never
+        // served in its place.
+        assert_eq!(cache.stats().fetched, 2);
+        assert_eq!(cache.stats().hits, 1);
+        assert_ne!(user_key("admin-token"), user_key("integration-token"));
+        assert!(!user_key("admin-token").contains("admin"));
+    }
+
+    #[tokio::test]
+    async fn test_describe_cache_keys_on_language() {
+        use wiremock::matchers::header;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(DESCRIBE_PATH))
+            .and(header("Accept-Language", "fr"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"name": "Account", "label": "Compte", "custom": false, "fields": []}),
+            ))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(DESCRIBE_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(account_describe()))
+            .mount(&mock_server)
+            .await;
+
+        let cache = DescribeCache::new();
+        let english = SalesforceRestClient::new(mock_server.uri(), "test-token")
+            .unwrap()
+            .with_describe_cache(cache.clone());
+        let french = english
+            .clone()
+            .with_call_options(crate::CallOptions::new().with_accept_language("fr"));
+
+        assert_eq!(
+            english.describe_sobject("Account").await.unwrap().label,
+            "Account"
+        );
+        assert_eq!(
+            french.describe_sobject("Account").await.unwrap().label,
+            "Compte"
+        );
+        assert_eq!(
+            english.describe_sobject("Account").await.unwrap().label,
+            "Account"
+        );
+        assert_eq!(cache.stats().fetched, 2);
+    }
+
+    #[tokio::test]
+    async fn test_describe_cache_persists_to_disk() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(DESCRIBE_PATH))
+            .and(header_exists("If-Modified-Since"))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(DESCRIBE_PATH))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(account_describe())
+                    .insert_header("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let client = SalesforceRestClient::new(mock_server.uri(), "test-token").unwrap();
+        let first = client
+            .clone()
+            .with_describe_cache(DescribeCache::new().with_disk(dir.path()));
+        first.describe_sobject("Account").await.unwrap();
+
+        // A fresh cache over the same directory revalidates instead of
+        // downloading.
+        let restarted = DescribeCache::new().with_disk(dir.path());
+        let second = client.with_describe_cache(restarted.clone());
+        second.describe_sobject("Account").await.unwrap();
+        assert_eq!(restarted.stats().revalidated, 1);
+        assert_eq!(restarted.stats().fetched, 0);
+
+        let requests = mock_server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[1].headers["If-Modified-Since"],
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Duplicate management types.
+//!
+//! Duplicate rules and matching rules are configured in Setup; these types
+//! describe the matches they produce and the `Sforce-Duplicate-Rule-Header`
+//! that controls how saves behave when a rule fires.
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the request header that controls duplicate rule behavior.
+pub(crate) const DUPLICATE_RULE_HEADER: &str = "Sforce-Duplicate-Rule-Header";
+
+/// Options sent in the `Sforce-Duplicate-Rule-Header` on create and update.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use sf_rest::DuplicateRuleHeader;
+///
+/// // Save even when an "Alert" duplicate rule fires.
+/// let id = client
+///     .create_with_duplicate_rule("Lead", &lead, &DuplicateRuleHeader::allow_save())
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateRuleHeader {
+    /// Save the record even if duplicate rules with an "Alert" action match.
+    pub allow_save: bool,
+    /// Also check the record against duplicates in the same request.
+    pub include_record_duplicates: bool,
+    /// Apply sharing rules of the running user when looking for duplicates.
+    pub run_as_current_user: bool,
+}
+
+impl Default for DuplicateRuleHeader {
+    fn default() -> Self {
+        Self {
+            allow_save: false,
+            include_record_duplicates: false,
+            run_as_current_user: true,
+        }
+    }
+}
+
+impl DuplicateRuleHeader {
+    /// Save records even when alerting duplicate rules match.
+    pub fn allow_save() -> Self {
+        Self {
+            allow_save: true,
+            ..Self::default()
+        }
+    }
+
+    /// The header value, e.g. `allowSave=true; includeRecordDuplicates=false; runAsCurrentUser=true`.
+    pub fn header_value(&self) -> String {
+        format!(
+            "allowSave={}; includeRecordDuplicates={}; runAsCurrentUser={}",
+            self.allow_save, self.include_record_duplicates, self.run_as_current_user
+        )
+    }
+}
+
+/// Duplicates found for a record by one duplicate rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DuplicateResult {
+    #[serde(rename = "allowSave", default)]
+    pub allow_save: bool,
+    #[serde(rename = "duplicateRule")]
+    pub duplicate_rule: String,
+    #[serde(rename = "duplicateRuleEntityType")]
+    pub duplicate_rule_entity_type: String,
+    #[serde(rename = "errorMessage")]
+    pub error_message: Option<String>,
+    #[serde(rename = "matchResults", default)]
+    pub match_results: Vec<MatchResult>,
+}
+
+/// Matches produced by one matching rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MatchResult {
+    #[serde(rename = "entityType")]
+    pub entity_type: String,
+    #[serde(rename = "matchEngine")]
+    pub match_engine: Option<String>,
+    /// Matching rule developer name.
+    pub rule: String,
+    #[serde(default)]
+    pub size: u32,
+    #[serde(default)]
+    pub success: bool,
+    #[serde(rename = "matchRecords", default)]
+    pub match_records: Vec<MatchRecord>,
+    #[serde(default)]
+    pub errors: Vec<serde_json::Value>,
+}
+
+/// An existing record that matched.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MatchRecord {
+    /// Confidence from 0 to 100.
+    #[serde(rename = "matchConfidence")]
+    pub match_confidence: f64,
+    /// The matching record's fields (always includes `Id`).
+    pub record: serde_json::Value,
+    #[serde(rename = "fieldDiffs", default)]
+    pub field_diffs: Vec<FieldDiff>,
+    #[serde(rename = "additionalInformation", default)]
+    pub additional_information: Vec<serde_json::Value>,
+}
+
+/// How a single matching-rule field compares between the two records.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FieldDiff {
+    pub name: String,
+    /// `Same`, `Different`, or `Null`.
+    pub difference: String,
+}
+
+/// Response from `POST /duplicates/detect`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DuplicateDetectResponse {
+    /// One entry per input record, in request order.
+    #[serde(default)]
+    pub results: Vec<DuplicateDetectResult>,
+}
+
+/// Duplicate detection results for one input record.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DuplicateDetectResult {
+    #[serde(rename = "duplicateResults", default)]
+    pub duplicate_results: Vec<DuplicateResult>,
+}
+
+/// Response from `GET /sobjects/{type}/{id}/duplicates`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecordDuplicatesResponse {
+    #[serde(rename = "duplicateResults", default)]
+    pub duplicate_results: Vec<DuplicateResult>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_value() {
+        assert_eq!(
+            DuplicateRuleHeader::default().header_value(),
+            "allowSave=false; includeRecordDuplicates=false; runAsCurrentUser=true"
+        );
+        let header = DuplicateRuleHeader {
+            include_record_duplicates: true,
+            ..DuplicateRuleHeader::allow_save()
+        };
+        assert_eq!(
+            header.header_value(),
+            "allowSave=true; includeRecordDuplicates=true; runAsCurrentUser=true"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_result_deserialize() {
+        let json = serde_json::json!({
+            "allowSave": true,
+            "duplicateRule": "Standard_Contact_Duplicate_Rule",
+            "duplicateRuleEntityType": "Contact",
+            "errorMessage": "You're creating a duplicate record.",
+            "matchResults": [{
+                "entityType": "Contact",
+                "errors": [],
+                "matchEngine": "FuzzyMatchEngine",
+                "matchRecords": [{
+                    "additionalInformation": [],
+                    "fieldDiffs": [
+                        {"difference": "Same", "name": "Email"},
+                        {"difference": "Different", "name": "Phone"}
+                    ],
+                    "matchConfidence": 87.5,
+                    "record": {"attributes": {"type": "Contact"}, "Id": "003xx0000000001AAA"}
+                }],
+                "rule": "Standard_Contact_Match_Rule_v1_1",
+                "size": 1,
+                "success": true
+            }]
+        });
+        let result: DuplicateResult = serde_json::from_value(json).unwrap();
+        assert!(result.allow_save);
+        let record = &result.match_results[0].match_records[0];
+        assert_eq!(record.match_confidence, 87.5);
+        assert_eq!(record.record["Id"], "003xx0000000001AAA");
+        assert_eq!(record.field_diffs[1].difference, "Different");
+    }
+}
@@ -14,6 +14,15 @@ impl Error {
     pub fn new(kind: ErrorKind) -> Self {
         Self { kind, source: None }
     }
+
+    /// Whether a conditional write was refused because the record changed
+    /// since it was read (HTTP 412).
+    pub fn is_precondition_failed(&self) -> bool {
+        self.source
+            .as_ref()
+            .and_then(|s| s.downcast_ref::<busbar_sf_client::Error>())
+            .is_some_and(|e| matches!(e.kind, busbar_sf_client::ErrorKind::PreconditionFailed(_)))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
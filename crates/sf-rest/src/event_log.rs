@@ -0,0 +1,252 @@
+//! Event Monitoring (`EventLogFile`) types.
+//!
+//! Each `EventLogFile` record holds one day (or hour, for hourly logs) of a
+//! single event type as a CSV blob in its `LogFile` field. The typed event
+//! structs here cover the most common audit event types; any other type can
+//! be decoded into a custom struct or `HashMap<String, String>`.
+
+use serde::{Deserialize, Serialize};
+
+/// An `EventLogFile` record (without the `LogFile` blob itself).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventLogFile {
+    #[serde(rename = "Id")]
+    pub id: String,
+    /// e.g. `Login`, `API`, `RestApi`, `Report`.
+    #[serde(rename = "EventType")]
+    pub event_type: String,
+    #[serde(rename = "LogDate")]
+    pub log_date: String,
+    /// Size of the log file in bytes.
+    #[serde(rename = "LogFileLength")]
+    pub log_file_length: Option<f64>,
+    /// `Daily` or `Hourly`.
+    #[serde(rename = "Interval")]
+    pub interval: Option<String>,
+    #[serde(rename = "Sequence")]
+    pub sequence: Option<i64>,
+    #[serde(rename = "CreatedDate")]
+    pub created_date: Option<String>,
+}
+
+/// Filter for listing `EventLogFile` records.
+#[derive(Debug, Clone, Default)]
+pub struct EventLogFileFilter {
+    pub(crate) event_type: Option<String>,
+    pub(crate) since: Option<chrono::DateTime<chrono::Utc>>,
+    pub(crate) interval: Option<String>,
+}
+
+impl EventLogFileFilter {
+    /// Match all log files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only log files for this event type (e.g. `Login`).
+    pub fn event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_type = Some(event_type.into());
+        self
+    }
+
+    /// Only log files with a `LogDate` at or after `since`.
+    pub fn since(mut self, since: chrono::DateTime<chrono::Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only `Daily` or `Hourly` log files.
+    pub fn interval(mut self, interval: impl Into<String>) -> Self {
+        self.interval = Some(interval.into());
+        self
+    }
+}
+
+/// A row of a `Login` event log file.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LoginEvent {
+    #[serde(rename = "EVENT_TYPE")]
+    pub event_type: String,
+    /// Compact timestamp, e.g. `20240104100000.123`.
+    #[serde(rename = "TIMESTAMP")]
+    pub timestamp: String,
+    /// ISO 8601 timestamp, e.g. `2024-01-04T10:00:00.123Z`.
+    #[serde(rename = "TIMESTAMP_DERIVED")]
+    pub timestamp_derived: Option<String>,
+    #[serde(rename = "REQUEST_ID")]
+    pub request_id: Option<String>,
+    #[serde(rename = "ORGANIZATION_ID")]
+    pub organization_id: Option<String>,
+    #[serde(rename = "USER_ID")]
+    pub user_id: Option<String>,
+    #[serde(rename = "USER_ID_DERIVED")]
+    pub user_id_derived: Option<String>,
+    #[serde(rename = "USER_NAME")]
+    pub user_name: Option<String>,
+    #[serde(rename = "SOURCE_IP")]
+    pub source_ip: Option<String>,
+    /// e.g. `LOGIN_NO_ERROR`, `LOGIN_ERROR_INVALID_PASSWORD`.
+    #[serde(rename = "LOGIN_STATUS")]
+    pub login_status: Option<String>,
+    #[serde(rename = "LOGIN_KEY")]
+    pub login_key: Option<String>,
+    #[serde(rename = "URI")]
+    pub uri: Option<String>,
+    #[serde(rename = "BROWSER_TYPE")]
+    pub browser_type: Option<String>,
+    #[serde(rename = "API_TYPE")]
+    pub api_type: Option<String>,
+    #[serde(rename = "API_VERSION")]
+    pub api_version: Option<String>,
+    #[serde(rename = "TLS_PROTOCOL")]
+    pub tls_protocol: Option<String>,
+    #[serde(rename = "RUN_TIME")]
+    pub run_time: Option<u64>,
+    #[serde(rename = "CPU_TIME")]
+    pub cpu_time: Option<u64>,
+}
+
+/// A row of an `API` (SOAP) event log file.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ApiEvent {
+    #[serde(rename = "EVENT_TYPE")]
+    pub event_type: String,
+    #[serde(rename = "TIMESTAMP")]
+    pub timestamp: String,
+    #[serde(rename = "TIMESTAMP_DERIVED")]
+    pub timestamp_derived: Option<String>,
+    #[serde(rename = "REQUEST_ID")]
+    pub request_id: Option<String>,
+    #[serde(rename = "ORGANIZATION_ID")]
+    pub organization_id: Option<String>,
+    #[serde(rename = "USER_ID")]
+    pub user_id: Option<String>,
+    #[serde(rename = "USER_ID_DERIVED")]
+    pub user_id_derived: Option<String>,
+    #[serde(rename = "CLIENT_IP")]
+    pub client_ip: Option<String>,
+    #[serde(rename = "SESSION_KEY")]
+    pub session_key: Option<String>,
+    #[serde(rename = "LOGIN_KEY")]
+    pub login_key: Option<String>,
+    /// e.g. `E` (enterprise), `P` (partner), `M` (metadata).
+    #[serde(rename = "API_TYPE")]
+    pub api_type: Option<String>,
+    #[serde(rename = "API_VERSION")]
+    pub api_version: Option<String>,
+    #[serde(rename = "METHOD_NAME")]
+    pub method_name: Option<String>,
+    #[serde(rename = "ENTITY_NAME")]
+    pub entity_name: Option<String>,
+    #[serde(rename = "ROWS_PROCESSED")]
+    pub rows_processed: Option<u64>,
+    #[serde(rename = "REQUEST_STATUS")]
+    pub request_status: Option<String>,
+    #[serde(rename = "RUN_TIME")]
+    pub run_time: Option<u64>,
+    #[serde(rename = "CPU_TIME")]
+    pub cpu_time: Option<u64>,
+}
+
+/// A row of a `RestApi` event log file.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RestApiEvent {
+    #[serde(rename = "EVENT_TYPE")]
+    pub event_type: String,
+    #[serde(rename = "TIMESTAMP")]
+    pub timestamp: String,
+    #[serde(rename = "TIMESTAMP_DERIVED")]
+    pub timestamp_derived: Option<String>,
+    #[serde(rename = "REQUEST_ID")]
+    pub request_id: Option<String>,
+    #[serde(rename = "ORGANIZATION_ID")]
+    pub organization_id: Option<String>,
+    #[serde(rename = "USER_ID")]
+    pub user_id: Option<String>,
+    #[serde(rename = "USER_ID_DERIVED")]
+    pub user_id_derived: Option<String>,
+    #[serde(rename = "CLIENT_IP")]
+    pub client_ip: Option<String>,
+    #[serde(rename = "SESSION_KEY")]
+    pub session_key: Option<String>,
+    #[serde(rename = "LOGIN_KEY")]
+    pub login_key: Option<String>,
+    #[serde(rename = "METHOD")]
+    pub method: Option<String>,
+    #[serde(rename = "URI")]
+    pub uri: Option<String>,
+    #[serde(rename = "ENTITY_NAME")]
+    pub entity_name: Option<String>,
+    #[serde(rename = "STATUS_CODE")]
+    pub status_code: Option<u16>,
+    #[serde(rename = "ROWS_PROCESSED")]
+    pub rows_processed: Option<u64>,
+    #[serde(rename = "REQUEST_SIZE")]
+    pub request_size: Option<u64>,
+    #[serde(rename = "RESPONSE_SIZE")]
+    pub response_size: Option<u64>,
+    #[serde(rename = "RUN_TIME")]
+    pub run_time: Option<u64>,
+    #[serde(rename = "CPU_TIME")]
+    pub cpu_time: Option<u64>,
+}
+
+/// Decode the CSV content of a log file into typed rows.
+pub fn parse_event_log<T: serde::de::DeserializeOwned>(csv_data: &[u8]) -> csv::Result<Vec<T>> {
+    csv::Reader::from_reader(csv_data)
+        .into_deserialize()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_log_file_deserialize() {
+        let json = r#"{
+            "attributes": {"type": "EventLogFile"},
+            "Id": "0ATxx0000000001AAA",
+            "EventType": "Login",
+            "LogDate": "2024-01-04T00:00:00.000+0000",
+            "LogFileLength": 5199.0,
+            "Interval": "Daily",
+            "Sequence": 0,
+            "CreatedDate": "2024-01-05T03:12:00.000+0000"
+        }"#;
+        let file: EventLogFile = serde_json::from_str(json).unwrap();
+        assert_eq!(file.event_type, "Login");
+        assert_eq!(file.log_file_length, Some(5199.0));
+        assert_eq!(file.interval.as_deref(), Some("Daily"));
+    }
+
+    #[test]
+    fn test_parse_login_events() {
+        let csv_data = b"\"EVENT_TYPE\",\"TIMESTAMP\",\"USER_ID\",\"SOURCE_IP\",\"LOGIN_STATUS\",\"RUN_TIME\",\"UNKNOWN_COLUMN\"\n\
+            \"Login\",\"20240104100000.123\",\"005xx000001Sv6e\",\"203.0.113.7\",\"LOGIN_NO_ERROR\",\"84\",\"x\"\n\
+            \"Login\",\"20240104110000.456\",\"005xx000001Sv6e\",\"203.0.113.8\",\"LOGIN_ERROR_INVALID_PASSWORD\",\"\",\"y\"\n";
+        let events: Vec<LoginEvent> = parse_event_log(csv_data).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].source_ip.as_deref(), Some("203.0.113.7"));
+        assert_eq!(events[0].run_time, Some(84));
+        assert_eq!(events[1].run_time, None);
+        assert_eq!(
+            events[1].login_status.as_deref(),
+            Some("LOGIN_ERROR_INVALID_PASSWORD")
+        );
+        assert!(events[0].user_name.is_none());
+    }
+
+    #[test]
+    fn test_parse_rest_api_events() {
+        let csv_data = b"EVENT_TYPE,TIMESTAMP,METHOD,URI,STATUS_CODE,ROWS_PROCESSED\n\
+            RestApi,20240104100000.123,GET,/services/data/v62.0/query,200,42\n";
+        let events: Vec<RestApiEvent> = parse_event_log(csv_data).unwrap();
+        assert_eq!(events[0].method.as_deref(), Some("GET"));
+        assert_eq!(events[0].status_code, Some(200));
+        assert_eq!(events[0].rows_processed, Some(42));
+    }
+}
@@ -19,7 +19,15 @@ pub struct KnowledgeSettings {
     #[serde(rename = "knowledgeEnabled", default)]
     pub knowledge_enabled: bool,
     #[serde(default, deserialize_with = "null_as_default")]
-    pub languages: Vec<serde_json::Value>,
+    pub languages: Vec<KnowledgeLanguage>,
+}
+
+/// A language enabled for knowledge articles.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KnowledgeLanguage {
+    pub name: String,
+    #[serde(default)]
+    pub active: bool,
 }
 
 /// Response from knowledge articles endpoint.
@@ -35,7 +43,7 @@ pub struct KnowledgeArticlesResponse {
     pub page_number: i32,
 }
 
-/// A knowledge article.
+/// Summary of a published knowledge article, as returned by article lists.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct KnowledgeArticle {
     pub id: String,
@@ -45,6 +53,172 @@ pub struct KnowledgeArticle {
     #[serde(rename = "urlName")]
     pub url_name: Option<String>,
     pub summary: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(rename = "lastPublishedDate", default)]
+    pub last_published_date: Option<String>,
+    #[serde(rename = "viewCount", default)]
+    pub view_count: i64,
+    #[serde(rename = "viewScore", default)]
+    pub view_score: f64,
+    #[serde(rename = "upVoteCount", default)]
+    pub up_vote_count: i64,
+    #[serde(rename = "downVoteCount", default)]
+    pub down_vote_count: i64,
+    #[serde(
+        rename = "categoryGroups",
+        default,
+        deserialize_with = "null_as_default"
+    )]
+    pub category_groups: Vec<ArticleCategoryGroup>,
+}
+
+/// Full content of a published knowledge article.
+///
+/// Custom fields on the article type appear in `layout_items`; use
+/// [`KnowledgeArticleDetail::field`] to look one up by API name.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KnowledgeArticleDetail {
+    pub id: String,
+    #[serde(rename = "articleNumber", default)]
+    pub article_number: String,
+    pub title: Option<String>,
+    #[serde(rename = "urlName")]
+    pub url_name: Option<String>,
+    pub summary: Option<String>,
+    #[serde(rename = "versionNumber", default)]
+    pub version_number: Option<i32>,
+    #[serde(rename = "createdDate", default)]
+    pub created_date: Option<String>,
+    #[serde(rename = "lastModifiedDate", default)]
+    pub last_modified_date: Option<String>,
+    #[serde(rename = "firstPublishedDate", default)]
+    pub first_published_date: Option<String>,
+    #[serde(rename = "lastPublishedDate", default)]
+    pub last_published_date: Option<String>,
+    #[serde(rename = "allViewCount", default)]
+    pub all_view_count: i64,
+    #[serde(rename = "allViewScore", default)]
+    pub all_view_score: f64,
+    #[serde(
+        rename = "categoryGroups",
+        default,
+        deserialize_with = "null_as_default"
+    )]
+    pub category_groups: Vec<ArticleCategoryGroup>,
+    #[serde(rename = "layoutItems", default, deserialize_with = "null_as_default")]
+    pub layout_items: Vec<KnowledgeLayoutItem>,
+}
+
+impl KnowledgeArticleDetail {
+    /// The value of the layout field with API name `name`, if present.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.layout_items
+            .iter()
+            .find(|item| item.name == name)
+            .and_then(|item| item.value.as_deref())
+    }
+}
+
+/// A field shown on a knowledge article's layout.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KnowledgeLayoutItem {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Field type, e.g. `TEXT`, `RICH_TEXT_AREA`, `URL`.
+    #[serde(rename = "type", default)]
+    pub item_type: Option<String>,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// Data categories an article is classified under within one group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArticleCategoryGroup {
+    #[serde(rename = "groupName")]
+    pub group_name: String,
+    #[serde(rename = "groupLabel", default)]
+    pub group_label: Option<String>,
+    #[serde(
+        rename = "selectedCategories",
+        default,
+        deserialize_with = "null_as_default"
+    )]
+    pub selected_categories: Vec<ArticleCategory>,
+}
+
+/// A data category selected on an article.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArticleCategory {
+    #[serde(rename = "categoryName")]
+    pub category_name: String,
+    #[serde(rename = "categoryLabel", default)]
+    pub category_label: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Fields for a new draft article, created as a record of the org's
+/// knowledge article version object (e.g. `Knowledge__kav`).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let draft = KnowledgeArticleDraft::new("Reset your password", "reset-password")
+///     .with_summary("Steps to reset a forgotten password")
+///     .with_field("Body__c", "<p>Click <b>Forgot password</b>...</p>");
+/// let version_id = client.create_knowledge_draft("Knowledge__kav", &draft).await?;
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KnowledgeArticleDraft {
+    #[serde(rename = "Title")]
+    pub title: String,
+    #[serde(rename = "UrlName")]
+    pub url_name: String,
+    #[serde(rename = "Summary", skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Article language (e.g. `en_US`); defaults to the org's knowledge language.
+    #[serde(rename = "Language", skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Additional fields on the article type, keyed by API name.
+    #[serde(flatten)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl KnowledgeArticleDraft {
+    /// A draft with the required title and URL name.
+    pub fn new(title: impl Into<String>, url_name: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            url_name: url_name.into(),
+            summary: None,
+            language: None,
+            fields: serde_json::Map::new(),
+        }
+    }
+
+    /// Set the article summary.
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Set the article language.
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Set a custom field on the article type.
+    pub fn with_field(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        self.fields.insert(name.into(), value.into());
+        self
+    }
 }
 
 /// Response from data category groups endpoint.
@@ -128,6 +302,61 @@ mod tests {
         let response: KnowledgeArticlesResponse = serde_json::from_value(json).unwrap();
         assert_eq!(response.articles.len(), 1);
         assert_eq!(response.articles[0].article_number, "000001");
+        assert!(response.articles[0].category_groups.is_empty());
+    }
+
+    #[test]
+    fn test_knowledge_article_detail_deserialize() {
+        let json = json!({
+            "id": "kA0xx0000000001",
+            "articleNumber": "000001",
+            "title": "How to Reset Password",
+            "urlName": "how-to-reset-password",
+            "summary": null,
+            "versionNumber": 3,
+            "lastPublishedDate": "2024-05-01T12:00:00Z",
+            "allViewCount": 42,
+            "allViewScore": 87.5,
+            "categoryGroups": [{
+                "groupName": "Products",
+                "groupLabel": "Products",
+                "selectedCategories": [{
+                    "categoryName": "CRM",
+                    "categoryLabel": "CRM",
+                    "url": "/support/dataCategoryGroups/Products/dataCategories/CRM"
+                }]
+            }],
+            "layoutItems": [
+                {"name": "Body__c", "label": "Body", "type": "RICH_TEXT_AREA", "value": "<p>Hi</p>"},
+                {"name": "Notes__c", "label": "Notes", "type": "TEXT", "value": null}
+            ]
+        });
+        let detail: KnowledgeArticleDetail = serde_json::from_value(json).unwrap();
+        assert_eq!(detail.version_number, Some(3));
+        assert_eq!(detail.all_view_count, 42);
+        assert_eq!(detail.field("Body__c"), Some("<p>Hi</p>"));
+        assert_eq!(detail.field("Notes__c"), None);
+        assert_eq!(
+            detail.category_groups[0].selected_categories[0].category_name,
+            "CRM"
+        );
+    }
+
+    #[test]
+    fn test_knowledge_article_draft_serialize() {
+        let draft = KnowledgeArticleDraft::new("Reset", "reset")
+            .with_summary("How to reset")
+            .with_field("Body__c", "<p>Steps</p>");
+        let value = serde_json::to_value(&draft).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "Title": "Reset",
+                "UrlName": "reset",
+                "Summary": "How to reset",
+                "Body__c": "<p>Steps</p>"
+            })
+        );
     }
 
     #[test]
@@ -4,13 +4,36 @@
 //!
 //! ## Features
 //!
-//! - **SObject CRUD** - Create, Read, Update, Delete individual records
+//! - **SObject CRUD** - Create, Read, Update, Delete individual records; `UpdateBuilder` clears
+//!   only the fields you name, leaving other nulls out of the update
 //! - **SObject Collections** - Batch operations for up to 200 records
-//! - **SOQL Query** - Execute queries with automatic pagination
-//! - **SOSL Search** - Full-text search across objects
-//! - **Describe** - Get object and field metadata
-//! - **Composite API** - Execute multiple operations in a single request
+//! - **SOQL Query** - Execute queries with automatic pagination; `query_as` decodes into your own structs
+//! - **SOQL Builder** - `Soql` takes values only through typed binds, so queries are injection-safe
+//! - **SOSL Search** - Full-text search across objects; the `Sosl` builder escapes the search term and `search_grouped` groups results by object
+//! - **Describe** - Get object and field metadata, optionally through a shared `DescribeCache`
+//! - **Conditional Requests** - ETag/If-Modified-Since describes and records that return
+//!   [`Conditional::NotModified`] when unchanged, and updates/deletes guarded by a
+//!   [`WritePrecondition`] so concurrent writers don't overwrite each other
+//! - **Composite API** - Execute multiple operations in a single request; `composite_chunked`
+//!   and `composite_stream` split requests past the 25-subrequest limit, and `GraphBuilder`
+//!   assembles composite graphs with checked node references
 //! - **Limits** - Check API usage and limits
+//! - **File Upload** - Multipart ContentVersion/Attachment/Document uploads
+//! - **CRM Analytics** - Datasets, SAQL queries, and dataflow jobs
+//! - **Event Monitoring** - List and download EventLogFile CSVs as typed events
+//! - **Recycle Bin** - Restore or permanently purge deleted records
+//! - **Duplicate Management** - Detect duplicates and control duplicate rules on save
+//! - **Query Profiling** - Infer field types and nullability from a sample of query results
+//! - **Change Detection** - `RecordHasher` hashes a canonical form of selected fields so syncs skip unchanged records
+//! - **Polars DataFrames** - `query_dataframe` returns results with column types from describe (`polars` feature)
+//! - **Async SOQL** - Run background queries into a target object, for querying Big Objects at scale
+//! - **Query Plans** - `explain` returns the optimizer's plans so non-selective queries can be caught before they run
+//! - **Apex REST** - Call custom `@RestResource` endpoints
+//! - **Platform Events** - `publish_event` and `publish_events` with typed publish results
+//! - **Retries** - Exponential backoff with jitter for rate limits, 5xx,
+//!   dropped connections, and `REQUEST_LIMIT_EXCEEDED` (see [`RetryConfig`])
+//! - **Call Options** - `Sforce-Call-Options`, `Sforce-Auto-Assign`, query batch size, and
+//!   `Accept-Language` for translated describes, per client or per call (see [`CallOptions`])
 //!
 //! ## Example
 //!
@@ -46,35 +69,53 @@
 //! }
 //! ```
 
+mod async_query;
 mod client;
 mod collections;
 mod composite;
+mod conditional;
 mod consent;
+#[cfg(feature = "polars")]
+mod dataframe;
 mod describe;
+mod describe_cache;
+mod duplicates;
 mod embedded_service;
 mod error;
+mod event_log;
 mod invocable_actions;
 mod knowledge;
 mod layout;
 mod list_views;
+mod mass_delete;
+mod platform_events;
 mod process;
+mod profile;
 mod query;
 mod query_builder;
 mod quick_actions;
+mod record_hash;
 mod scheduler;
 mod search;
 mod sobject;
+mod soql;
+mod sosl;
 mod types;
+mod update_builder;
+mod upload;
 mod user_password;
+mod wave;
 
 // Main client
 pub use client::{
-    ApiVersion, DeletedRecord, GetDeletedResult, GetUpdatedResult, SObjectInfo,
-    SObjectInfoDescribe, SalesforceRestClient, SearchResult,
+    ApiVersion, ChangeJournal, ChangeKind, DeletedRecord, GetDeletedResult, GetUpdatedResult,
+    RecordChange, SObjectInfo, SObjectInfoDescribe, SalesforceRestClient, SearchResult,
 };
 
 // Collection operations
 pub use collections::{CollectionRequest, CollectionResult};
+pub use mass_delete::{MassDeleteFailure, MassDeleteOptions, MassDeleteResult};
+pub use platform_events::PublishResult;
 
 // Composite API
 pub use composite::{
@@ -82,7 +123,8 @@ pub use composite::{
     CompositeBatchSubresponse, CompositeGraphRequest, CompositeGraphResponse, CompositeRequest,
     CompositeResponse, CompositeSubrequest, CompositeSubresponse, CompositeTreeAttributes,
     CompositeTreeError, CompositeTreeRecord, CompositeTreeRequest, CompositeTreeResponse,
-    CompositeTreeResult, GraphRequest, GraphResponse, GraphResponseBody,
+    CompositeTreeResult, GraphBuilder, GraphRequest, GraphResponse, GraphResponseBody, NodeRef,
+    MAX_COMPOSITE_SUBREQUESTS, MAX_GRAPH_NODES,
 };
 
 // Convenience aliases for SObject Tree types
@@ -91,13 +133,19 @@ pub use composite::CompositeTreeAttributes as SObjectTreeAttributes;
 pub use composite::CompositeTreeRecord as SObjectTreeRecord;
 pub use composite::CompositeTreeRequest as SObjectTreeRequest;
 
+// Conditional GET types
+pub use conditional::{CacheValidators, Conditional, WritePrecondition};
+
 // Describe types
 pub use describe::{
-    ActionOverride, ChildRelationship, DescribeGlobalResult, DescribeSObjectResult, FieldDescribe,
-    FilteredLookupInfo, NamedLayoutInfo, PicklistValue, RecordTypeInfo, SObjectBasicInfo,
-    ScopeInfo,
+    ActionOverride, ChildRelationship, DescribeGlobalResult, DescribeSObjectResult,
+    DescribeWithLayouts, FieldDescribe, FilteredLookupInfo, NamedLayoutInfo, PicklistValue,
+    RecordTypeInfo, SObjectBasicInfo, ScopeInfo,
 };
 
+// Describe cache
+pub use describe_cache::{DescribeCache, DescribeCacheStats};
+
 // Layout types
 pub use layout::{
     ApprovalLayoutsResult, CompactLayoutsResult, DescribeLayoutsResult,
@@ -111,14 +159,20 @@ pub use search::{
     SearchSuggestionResult, Suggestion, SuggestionAttributes,
 };
 
+// Async SOQL types
+pub use async_query::{AsyncQueryJob, AsyncQueryOperation, AsyncQueryRequest, AsyncQueryStatus};
+
 // Error types
 pub use error::{Error, ErrorKind, Result};
 
 // Query types
-pub use query::{QueryOptions, QueryResult};
+pub use query::{QueryExplain, QueryOptions, QueryPlan, QueryPlanNote, QueryResult};
 
 // Query builder (safe by default)
 pub use query_builder::QueryBuilder;
+pub use soql::{bind_soql, Soql, SoqlValue};
+pub use sosl::{SearchGroup, SearchScope, Sosl, SoslResult, SoslReturning};
+pub use update_builder::UpdateBuilder;
 
 // SObject CRUD types
 pub use sobject::{CreateResult, DeleteResult, SalesforceError, UpdateResult, UpsertResult};
@@ -154,8 +208,9 @@ pub use consent::{
 
 // PR #54: Knowledge types
 pub use knowledge::{
-    DataCategoriesResponse, DataCategory, DataCategoryGroup, DataCategoryGroupsResponse,
-    KnowledgeArticle, KnowledgeArticlesResponse, KnowledgeSettings,
+    ArticleCategory, ArticleCategoryGroup, DataCategoriesResponse, DataCategory, DataCategoryGroup,
+    DataCategoryGroupsResponse, KnowledgeArticle, KnowledgeArticleDetail, KnowledgeArticleDraft,
+    KnowledgeArticlesResponse, KnowledgeLanguage, KnowledgeLayoutItem, KnowledgeSettings,
 };
 
 // PR #54: User Password types
@@ -169,5 +224,40 @@ pub use scheduler::{
 // PR #54: Embedded Service types
 pub use embedded_service::EmbeddedServiceConfig;
 
+// Multipart file upload types
+pub use upload::{FileSource, UploadTarget};
+
+// CRM Analytics (Wave) types
+pub use wave::{
+    DataflowJob, DataflowJobCollection, DataflowJobRequest, SaqlQueryRequest, SaqlQueryResponse,
+    SaqlQueryResults, WaveDataset, WaveDatasetCollection, WaveFolder,
+};
+
+// Duplicate management types
+pub use duplicates::{
+    DuplicateDetectResponse, DuplicateDetectResult, DuplicateResult, DuplicateRuleHeader,
+    FieldDiff, MatchRecord, MatchResult, RecordDuplicatesResponse,
+};
+
+// Event Monitoring types
+pub use event_log::{
+    parse_event_log, ApiEvent, EventLogFile, EventLogFileFilter, LoginEvent, RestApiEvent,
+};
+
+// Query profiling types
+pub use profile::{FieldProfile, InferredType, QueryProfile};
+
+// Change detection
+pub use record_hash::RecordHasher;
+
+// Polars DataFrame conversion
+#[cfg(feature = "polars")]
+pub use dataframe::records_to_dataframe;
+#[cfg(feature = "polars")]
+pub use polars::prelude::DataFrame;
+
 // Re-export sf-client types that users might need
-pub use busbar_sf_client::{ClientConfig, ClientConfigBuilder};
+pub use busbar_sf_client::{
+    AutoAssign, BackoffStrategy, CallOptions, ClientConfig, ClientConfigBuilder, RequestMethod,
+    RetryConfig, RetryOn,
+};
@@ -0,0 +1,218 @@
+//! Guarded mass deletes.
+//!
+//! Deleting millions of rows with a plain loop over `delete_multiple`
+//! tends to fail in two ways: the selection query isn't selective and
+//! times out, or cascade deletes and sharing recalculation contend for the
+//! same parent rows and the deletes fail with `UNABLE_TO_LOCK_ROW`.
+//! [`SalesforceRestClient::mass_delete`](crate::SalesforceRestClient::mass_delete)
+//! walks the object in `Id` order, so every page is an indexed range
+//! query, and shrinks its delete chunks while lock errors persist.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::SalesforceId;
+
+/// Status code Salesforce returns when a record is locked by another
+/// transaction.
+pub(crate) const LOCK_ERROR: &str = "UNABLE_TO_LOCK_ROW";
+
+/// Largest number of IDs a single collections delete accepts.
+pub(crate) const MAX_DELETE_CHUNK: usize = 200;
+
+/// Tuning for [`mass_delete`](crate::SalesforceRestClient::mass_delete).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use busbar_sf_rest::MassDeleteOptions;
+///
+/// let options = MassDeleteOptions::default()
+///     .with_chunk_size(100)
+///     .require_selective(true);
+/// let result = client
+///     .mass_delete("Task", Some("CreatedDate < LAST_N_YEARS:3"), options)
+///     .await?;
+/// println!("deleted {}, {} failed", result.deleted, result.failures.len());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MassDeleteOptions {
+    /// IDs fetched per range query. At most 2000.
+    pub page_size: usize,
+    /// Records per delete call at the start. At most 200.
+    pub chunk_size: usize,
+    /// Smallest chunk the delete shrinks to under lock contention.
+    pub min_chunk_size: usize,
+    /// Pause after a chunk hits lock errors. Doubles with each consecutive
+    /// contended chunk, up to 32 times this value.
+    pub lock_backoff: Duration,
+    /// How many times a locked record is retried before it is reported as
+    /// a failure.
+    pub max_lock_retries: u32,
+    /// Fail before deleting anything if the query plan for the filter is
+    /// not selective. When false, a non-selective filter is only logged.
+    pub require_selective: bool,
+    /// Stop after this many records have been deleted.
+    pub max_records: Option<u64>,
+}
+
+impl Default for MassDeleteOptions {
+    fn default() -> Self {
+        Self {
+            page_size: 2000,
+            chunk_size: MAX_DELETE_CHUNK,
+            min_chunk_size: 10,
+            lock_backoff: Duration::from_secs(1),
+            max_lock_retries: 5,
+            require_selective: false,
+            max_records: None,
+        }
+    }
+}
+
+impl MassDeleteOptions {
+    /// Set the number of records per delete call at the start.
+    pub fn with_chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = size;
+        self
+    }
+
+    /// Set the smallest chunk size used under lock contention.
+    pub fn with_min_chunk_size(mut self, size: usize) -> Self {
+        self.min_chunk_size = size;
+        self
+    }
+
+    /// Set the number of IDs fetched per range query.
+    pub fn with_page_size(mut self, size: usize) -> Self {
+        self.page_size = size;
+        self
+    }
+
+    /// Set the pause after a chunk hits lock errors.
+    pub fn with_lock_backoff(mut self, backoff: Duration) -> Self {
+        self.lock_backoff = backoff;
+        self
+    }
+
+    /// Set how many times a locked record is retried.
+    pub fn with_max_lock_retries(mut self, retries: u32) -> Self {
+        self.max_lock_retries = retries;
+        self
+    }
+
+    /// Refuse to start when the filter is not selective.
+    pub fn require_selective(mut self, require: bool) -> Self {
+        self.require_selective = require;
+        self
+    }
+
+    /// Stop after deleting `max` records.
+    pub fn with_max_records(mut self, max: u64) -> Self {
+        self.max_records = Some(max);
+        self
+    }
+}
+
+/// A record the mass delete could not remove.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MassDeleteFailure {
+    /// The record ID.
+    pub id: SalesforceId,
+    /// Salesforce status code, e.g. `ENTITY_IS_DELETED` or
+    /// `UNABLE_TO_LOCK_ROW` once retries ran out.
+    pub status_code: String,
+    /// Salesforce's error message.
+    pub message: String,
+}
+
+/// Outcome of a mass delete.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MassDeleteResult {
+    /// Records deleted.
+    pub deleted: u64,
+    /// Records that could not be deleted.
+    pub failures: Vec<MassDeleteFailure>,
+    /// Delete calls made.
+    pub delete_calls: u64,
+    /// Delete calls that hit at least one lock error.
+    pub contended_calls: u64,
+    /// Chunk size in use when the delete finished.
+    pub final_chunk_size: usize,
+    /// Whether the delete stopped at [`MassDeleteOptions::max_records`]
+    /// with records possibly left to delete.
+    pub truncated: bool,
+}
+
+/// Chunk size controller: halves on contention, grows back by half again
+/// after each clean chunk.
+#[derive(Debug, Clone)]
+pub(crate) struct AdaptiveChunk {
+    size: usize,
+    min: usize,
+    max: usize,
+    contended_streak: u32,
+}
+
+impl AdaptiveChunk {
+    pub(crate) fn new(options: &MassDeleteOptions) -> Self {
+        let max = options.chunk_size.clamp(1, MAX_DELETE_CHUNK);
+        Self {
+            size: max,
+            min: options.min_chunk_size.clamp(1, max),
+            max,
+            contended_streak: 0,
+        }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Record a contended chunk and return how long to back off.
+    pub(crate) fn contended(&mut self, base: Duration) -> Duration {
+        self.size = (self.size / 2).max(self.min);
+        let backoff = base * 2u32.pow(self.contended_streak.min(5));
+        self.contended_streak += 1;
+        backoff
+    }
+
+    pub(crate) fn clean(&mut self) {
+        self.contended_streak = 0;
+        self.size = (self.size + self.size.div_ceil(2)).min(self.max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptive_chunk() {
+        let options = MassDeleteOptions::default()
+            .with_chunk_size(500)
+            .with_min_chunk_size(20);
+        let mut chunk = AdaptiveChunk::new(&options);
+        assert_eq!(chunk.size(), MAX_DELETE_CHUNK);
+
+        let base = Duration::from_millis(100);
+        assert_eq!(chunk.contended(base), base);
+        assert_eq!(chunk.size(), 100);
+        assert_eq!(chunk.contended(base), base * 2);
+        assert_eq!(chunk.size(), 50);
+        chunk.contended(base);
+        chunk.contended(base);
+        assert_eq!(chunk.size(), 20);
+
+        chunk.clean();
+        assert_eq!(chunk.size(), 30);
+        for _ in 0..10 {
+            chunk.clean();
+        }
+        assert_eq!(chunk.size(), MAX_DELETE_CHUNK);
+        assert_eq!(chunk.contended(base), base);
+    }
+}
@@ -0,0 +1,106 @@
+//! Platform Event publish results.
+//!
+//! Platform events are published over REST by creating a "record" of the
+//! event object (`Order_Shipped__e`). The response has the shape of a
+//! create result, but the event is not stored: `id` identifies the
+//! publish, and for events queued for publishing Salesforce reports the
+//! event's `EventUuid` as an `OPERATION_ENQUEUED` entry in `errors`, even
+//! though the publish succeeded. [`PublishResult`] separates the two.
+
+use serde::{Deserialize, Serialize};
+
+use crate::collections::CollectionResult;
+use crate::sobject::{empty_id_as_none, CreateResult, SalesforceError};
+use crate::types::SalesforceId;
+
+/// Status code Salesforce uses to report the `EventUuid` of a queued event.
+const OPERATION_ENQUEUED: &str = "OPERATION_ENQUEUED";
+
+/// Outcome of publishing one platform event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishResult {
+    /// Whether the event was accepted for publishing.
+    pub success: bool,
+    /// Publish ID assigned by Salesforce, when accepted.
+    #[serde(default, deserialize_with = "empty_id_as_none")]
+    pub id: Option<SalesforceId>,
+    /// The event's `EventUuid`, which subscribers see on the delivered
+    /// event, when Salesforce reports it.
+    pub event_uuid: Option<String>,
+    /// Why the event was not published. Never contains the
+    /// `OPERATION_ENQUEUED` notice carrying the UUID.
+    pub errors: Vec<SalesforceError>,
+}
+
+impl PublishResult {
+    fn new(id: Option<SalesforceId>, success: bool, errors: Vec<SalesforceError>) -> Self {
+        let (enqueued, errors): (Vec<_>, Vec<_>) = errors
+            .into_iter()
+            .partition(|e| e.status_code == OPERATION_ENQUEUED);
+        Self {
+            success,
+            id,
+            event_uuid: enqueued.into_iter().next().map(|e| e.message),
+            errors,
+        }
+    }
+}
+
+impl From<CreateResult> for PublishResult {
+    fn from(result: CreateResult) -> Self {
+        Self::new(result.id, result.success, result.errors)
+    }
+}
+
+impl From<CollectionResult> for PublishResult {
+    fn from(result: CollectionResult) -> Self {
+        Self::new(result.id, result.success, result.errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_publish_result_extracts_event_uuid() {
+        let created: CreateResult = serde_json::from_value(json!({
+            "id": "e00xx0000000001AAA",
+            "success": true,
+            "errors": [{
+                "statusCode": "OPERATION_ENQUEUED",
+                "message": "3d9b4b5e-6b2a-4a4f-8c87-3f0f5d8f2e11",
+                "fields": []
+            }]
+        }))
+        .unwrap();
+        let result = PublishResult::from(created);
+        assert!(result.success);
+        assert_eq!(result.id.unwrap(), "e00xx0000000001AAA");
+        assert_eq!(
+            result.event_uuid.as_deref(),
+            Some("3d9b4b5e-6b2a-4a4f-8c87-3f0f5d8f2e11")
+        );
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn test_publish_result_keeps_real_errors() {
+        let created: CollectionResult = serde_json::from_value(json!({
+            "id": null,
+            "success": false,
+            "errors": [{
+                "statusCode": "REQUIRED_FIELD_MISSING",
+                "message": "Required fields are missing: [Order_Number__c]",
+                "fields": ["Order_Number__c"]
+            }]
+        }))
+        .unwrap();
+        let result = PublishResult::from(created);
+        assert!(!result.success);
+        assert!(result.id.is_none());
+        assert!(result.event_uuid.is_none());
+        assert_eq!(result.errors[0].status_code, "REQUIRED_FIELD_MISSING");
+    }
+}
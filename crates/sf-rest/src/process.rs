@@ -4,6 +4,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::types::SalesforceId;
+
 /// Deserialize `null` as the default value for the type (e.g., empty Vec).
 /// Salesforce APIs often return `null` instead of `[]` for empty arrays.
 fn null_as_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
@@ -37,7 +39,7 @@ pub struct ProcessRuleCollection {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ProcessRuleRequest {
     #[serde(rename = "contextIds")]
-    pub context_ids: Vec<String>,
+    pub context_ids: Vec<SalesforceId>,
 }
 
 /// Result of triggering a process rule.
@@ -75,13 +77,13 @@ pub struct ApprovalRequest {
     #[serde(rename = "actionType")]
     pub action_type: ApprovalActionType,
     #[serde(rename = "contextId")]
-    pub context_id: String,
+    pub context_id: SalesforceId,
     #[serde(rename = "contextActorId", skip_serializing_if = "Option::is_none")]
-    pub context_actor_id: Option<String>,
+    pub context_actor_id: Option<SalesforceId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comments: Option<String>,
     #[serde(rename = "nextApproverIds", skip_serializing_if = "Option::is_none")]
-    pub next_approver_ids: Option<Vec<String>>,
+    pub next_approver_ids: Option<Vec<SalesforceId>>,
     #[serde(
         rename = "processDefinitionNameOrId",
         skip_serializing_if = "Option::is_none"
@@ -171,10 +173,10 @@ mod tests {
     #[test]
     fn test_process_rule_request_serialize() {
         let request = ProcessRuleRequest {
-            context_ids: vec!["001xx000003DgAAAS".to_string()],
+            context_ids: vec!["001xx000003DgAA".parse().unwrap()],
         };
         let json = serde_json::to_value(&request).unwrap();
-        assert_eq!(json["contextIds"][0], "001xx000003DgAAAS");
+        assert_eq!(json["contextIds"][0], "001xx000003DgAAAA0");
     }
 
     #[test]
@@ -219,7 +221,7 @@ mod tests {
     fn test_approval_request_serialize() {
         let request = ApprovalRequest {
             action_type: ApprovalActionType::Submit,
-            context_id: "001xx000003DgAAAS".to_string(),
+            context_id: "001xx000003DgAA".parse().unwrap(),
             context_actor_id: None,
             comments: Some("Approved".to_string()),
             next_approver_ids: None,
@@ -228,7 +230,7 @@ mod tests {
         };
         let json = serde_json::to_value(&request).unwrap();
         assert_eq!(json["actionType"], "Submit");
-        assert_eq!(json["contextId"], "001xx000003DgAAAS");
+        assert_eq!(json["contextId"], "001xx000003DgAAAA0");
         assert!(json.get("contextActorId").is_none());
         assert_eq!(json["comments"], "Approved");
     }
@@ -0,0 +1,316 @@
+//! Query result profiling.
+//!
+//! A [`QueryProfile`] summarizes a sample of query results: the type each
+//! field appears to have, whether it was ever null, and how many distinct
+//! values it took. Exporters use it to pick column types and code
+//! generators use it to choose between `String` and `Option<String>`.
+
+use std::collections::HashSet;
+
+use busbar_sf_client::security::url as url_security;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of example values kept per field.
+const MAX_EXAMPLES: usize = 3;
+
+/// The type a field's values appear to have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InferredType {
+    /// Every sampled value was null.
+    Null,
+    Boolean,
+    Integer,
+    Double,
+    /// A 15 or 18 character record ID.
+    Id,
+    /// `YYYY-MM-DD`.
+    Date,
+    /// ISO 8601 date-time, e.g. `2024-01-04T10:00:00.000+0000`.
+    DateTime,
+    /// `HH:MM:SS.sssZ`.
+    Time,
+    String,
+    /// A child relationship subquery (`{"totalSize": .., "records": [..]}`)
+    /// or a JSON array.
+    Array,
+    /// A compound field (e.g. an address or geolocation).
+    Object,
+}
+
+impl InferredType {
+    fn of(field: &str, value: &serde_json::Value) -> Self {
+        use serde_json::Value;
+        match value {
+            Value::Null => InferredType::Null,
+            Value::Bool(_) => InferredType::Boolean,
+            Value::Number(n) if n.is_i64() || n.is_u64() => InferredType::Integer,
+            Value::Number(_) => InferredType::Double,
+            Value::String(s) => infer_string(field, s),
+            Value::Array(_) => InferredType::Array,
+            Value::Object(obj) if obj.contains_key("records") => InferredType::Array,
+            Value::Object(_) => InferredType::Object,
+        }
+    }
+
+    /// Combine the types of two samples of the same field.
+    fn merge(self, other: Self) -> Self {
+        use InferredType::*;
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Null, t) | (t, Null) => t,
+            (Integer, Double) | (Double, Integer) => Double,
+            _ => String,
+        }
+    }
+}
+
+fn infer_string(field: &str, s: &str) -> InferredType {
+    let name = field.rsplit('.').next().unwrap_or(field);
+    if (name == "Id" || name.ends_with("Id")) && url_security::is_valid_salesforce_id(s) {
+        InferredType::Id
+    } else if chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+        InferredType::Date
+    } else if chrono::DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z").is_ok()
+        || chrono::DateTime::parse_from_rfc3339(s).is_ok()
+    {
+        InferredType::DateTime
+    } else if s.ends_with('Z')
+        && chrono::NaiveTime::parse_from_str(&s[..s.len() - 1], "%H:%M:%S%.f").is_ok()
+    {
+        InferredType::Time
+    } else {
+        InferredType::String
+    }
+}
+
+/// Profile of a single field across the sampled records.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldProfile {
+    /// Field path; relationship fields are dotted (`Owner.Name`).
+    pub name: String,
+    pub inferred_type: InferredType,
+    /// Number of records where the field was null or absent.
+    pub null_count: usize,
+    /// Number of distinct non-null values.
+    pub distinct_count: usize,
+    /// Longest string value, for sizing text columns.
+    pub max_length: Option<usize>,
+    /// A few non-null values, in the order seen.
+    pub examples: Vec<serde_json::Value>,
+}
+
+impl FieldProfile {
+    /// Whether any sampled record had no value for this field.
+    pub fn is_nullable(&self) -> bool {
+        self.null_count > 0
+    }
+}
+
+/// Profile of a query's results, built from a sample page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryProfile {
+    /// Total number of records the query matches.
+    pub total_size: u64,
+    /// Number of records the profile was built from.
+    pub sample_size: usize,
+    /// Fields in the order they first appeared.
+    pub fields: Vec<FieldProfile>,
+}
+
+impl QueryProfile {
+    /// Build a profile from records returned by a query.
+    pub fn from_records(records: &[serde_json::Value], total_size: u64) -> Self {
+        // (profile, distinct values, non-null count) per field
+        let mut builders: Vec<(FieldProfile, HashSet<String>, usize)> = Vec::new();
+
+        for record in records {
+            let mut flat = Vec::new();
+            flatten("", record, &mut flat);
+            for (name, value) in flat {
+                let idx = match builders.iter().position(|(f, _, _)| f.name == name) {
+                    Some(idx) => idx,
+                    None => {
+                        builders.push((
+                            FieldProfile {
+                                name: name.clone(),
+                                inferred_type: InferredType::Null,
+                                null_count: 0,
+                                distinct_count: 0,
+                                max_length: None,
+                                examples: Vec::new(),
+                            },
+                            HashSet::new(),
+                            0,
+                        ));
+                        builders.len() - 1
+                    }
+                };
+                let (field, distinct, non_null) = &mut builders[idx];
+                field.inferred_type = field.inferred_type.merge(InferredType::of(&name, value));
+                if value.is_null() {
+                    continue;
+                }
+                *non_null += 1;
+                if let Some(s) = value.as_str() {
+                    let len = s.chars().count();
+                    field.max_length = Some(field.max_length.map_or(len, |m| m.max(len)));
+                }
+                if distinct.insert(value.to_string()) && field.examples.len() < MAX_EXAMPLES {
+                    field.examples.push(value.clone());
+                }
+            }
+        }
+
+        let sample_size = records.len();
+        let fields = builders
+            .into_iter()
+            .map(|(mut field, distinct, non_null)| {
+                field.distinct_count = distinct.len();
+                // Explicit nulls and records where the field was absent.
+                field.null_count = sample_size - non_null;
+                field
+            })
+            .collect();
+
+        QueryProfile {
+            total_size,
+            sample_size,
+            fields,
+        }
+    }
+
+    /// Look up a field profile by name.
+    pub fn field(&self, name: &str) -> Option<&FieldProfile> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+/// Flatten a record into `(path, value)` pairs, descending into parent
+/// relationships but not child subqueries or compound fields.
+pub(crate) fn flatten<'a>(
+    prefix: &str,
+    value: &'a serde_json::Value,
+    out: &mut Vec<(String, &'a serde_json::Value)>,
+) {
+    let Some(obj) = value.as_object() else {
+        return;
+    };
+    for (key, child) in obj {
+        if key == "attributes" {
+            continue;
+        }
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        // Parent relationships carry their own `attributes` block.
+        match child.as_object() {
+            Some(nested) if nested.contains_key("attributes") => flatten(&path, child, out),
+            _ => out.push((path, child)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_infer_scalar_types() {
+        assert_eq!(
+            InferredType::of("Id", &json!("001xx000003DGb2AAG")),
+            InferredType::Id
+        );
+        assert_eq!(
+            InferredType::of("Name", &json!("001xx000003DGb2AAG")),
+            InferredType::String
+        );
+        assert_eq!(
+            InferredType::of("CloseDate", &json!("2024-01-04")),
+            InferredType::Date
+        );
+        assert_eq!(
+            InferredType::of("CreatedDate", &json!("2024-01-04T10:00:00.000+0000")),
+            InferredType::DateTime
+        );
+        assert_eq!(
+            InferredType::of("Start__c", &json!("09:30:00.000Z")),
+            InferredType::Time
+        );
+        assert_eq!(
+            InferredType::of("Amount", &json!(12.5)),
+            InferredType::Double
+        );
+        assert_eq!(
+            InferredType::of("NumberOfEmployees", &json!(12)),
+            InferredType::Integer
+        );
+        assert_eq!(
+            InferredType::Integer.merge(InferredType::Double),
+            InferredType::Double
+        );
+        assert_eq!(
+            InferredType::Null.merge(InferredType::Date),
+            InferredType::Date
+        );
+        assert_eq!(
+            InferredType::Date.merge(InferredType::Boolean),
+            InferredType::String
+        );
+    }
+
+    #[test]
+    fn test_profile_records() {
+        let records = vec![
+            json!({
+                "attributes": {"type": "Account"},
+                "Id": "001xx000003DGb2AAG",
+                "Name": "Acme",
+                "Industry": "Technology",
+                "Owner": {"attributes": {"type": "User"}, "Name": "Ada"},
+                "Contacts": {"totalSize": 1, "done": true, "records": []}
+            }),
+            json!({
+                "attributes": {"type": "Account"},
+                "Id": "001xx000003DGb3AAG",
+                "Name": "Globex Corporation",
+                "Industry": null,
+                "Owner": {"attributes": {"type": "User"}, "Name": "Ada"},
+                "Contacts": null
+            }),
+            json!({
+                "attributes": {"type": "Account"},
+                "Id": "001xx000003DGb4AAG",
+                "Name": "Initech",
+                "Owner": null,
+                "Contacts": null
+            }),
+        ];
+        let profile = QueryProfile::from_records(&records, 42);
+        assert_eq!(profile.total_size, 42);
+        assert_eq!(profile.sample_size, 3);
+
+        let id = profile.field("Id").unwrap();
+        assert_eq!(id.inferred_type, InferredType::Id);
+        assert!(!id.is_nullable());
+        assert_eq!(id.distinct_count, 3);
+
+        let name = profile.field("Name").unwrap();
+        assert_eq!(name.max_length, Some(18));
+
+        // Null in one record and absent in another.
+        let industry = profile.field("Industry").unwrap();
+        assert_eq!(industry.null_count, 2);
+        assert_eq!(industry.inferred_type, InferredType::String);
+
+        let owner = profile.field("Owner.Name").unwrap();
+        assert_eq!(owner.distinct_count, 1);
+        assert_eq!(owner.null_count, 1);
+
+        let contacts = profile.field("Contacts").unwrap();
+        assert_eq!(contacts.inferred_type, InferredType::Array);
+    }
+}
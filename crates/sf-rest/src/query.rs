@@ -6,6 +6,8 @@
 // Re-export QueryResult from busbar_sf_client to ensure type compatibility
 pub use busbar_sf_client::QueryResult;
 
+use serde::{Deserialize, Serialize};
+
 /// Options for query execution.
 #[derive(Debug, Clone, Default)]
 pub struct QueryOptions {
@@ -14,3 +16,102 @@ pub struct QueryOptions {
     /// Include deleted records (QueryAll endpoint).
     pub include_deleted: bool,
 }
+
+/// Execution plans Salesforce would consider for a query, from
+/// `GET /query?explain=`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryExplain {
+    /// Candidate plans, cheapest first.
+    #[serde(default)]
+    pub plans: Vec<QueryPlan>,
+    /// The query that was explained.
+    #[serde(default)]
+    pub source_query: Option<String>,
+}
+
+impl QueryExplain {
+    /// The plan the optimizer would choose.
+    pub fn best_plan(&self) -> Option<&QueryPlan> {
+        self.plans
+            .iter()
+            .min_by(|a, b| a.relative_cost.total_cmp(&b.relative_cost))
+    }
+
+    /// Whether the chosen plan is selective, i.e. has a relative cost
+    /// below 1. Non-selective queries scan the whole table and are likely
+    /// to time out on large objects.
+    pub fn is_selective(&self) -> bool {
+        self.best_plan().is_some_and(QueryPlan::is_selective)
+    }
+}
+
+/// One candidate execution plan for a query.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlan {
+    /// Estimated number of records the leading operation returns.
+    #[serde(default)]
+    pub cardinality: u64,
+    /// Indexed fields used by the leading operation.
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// `Index`, `Other`, `Sharing`, or `TableScan`.
+    #[serde(default)]
+    pub leading_operation_type: String,
+    /// Cost relative to the selectivity threshold. Above 1 means the
+    /// query is not selective.
+    #[serde(default)]
+    pub relative_cost: f64,
+    /// Approximate record count of the queried object.
+    #[serde(default)]
+    pub sobject_cardinality: u64,
+    /// The queried object.
+    #[serde(default)]
+    pub sobject_type: String,
+    /// Why fields could not be used by this plan.
+    #[serde(default)]
+    pub notes: Vec<QueryPlanNote>,
+}
+
+impl QueryPlan {
+    /// Whether this plan's relative cost is below 1.
+    pub fn is_selective(&self) -> bool {
+        self.relative_cost < 1.0
+    }
+
+    /// Whether this plan reads the entire table.
+    pub fn is_table_scan(&self) -> bool {
+        self.leading_operation_type == "TableScan"
+    }
+}
+
+/// Performance feedback attached to a query plan.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlanNote {
+    /// Explanation, e.g. "Not considering filter for optimization because unindexed".
+    #[serde(default)]
+    pub description: String,
+    /// Fields the note applies to.
+    #[serde(default)]
+    pub fields: Vec<String>,
+    /// The object the fields belong to.
+    #[serde(default)]
+    pub table_enum_or_id: String,
+}
+
+/// Remove the `attributes` objects Salesforce adds to query records,
+/// including those on relationship fields and subquery results.
+pub(crate) fn strip_record_attributes(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            if map.get("attributes").is_some_and(|a| a.is_object()) {
+                map.remove("attributes");
+            }
+            map.values_mut().for_each(strip_record_attributes);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_record_attributes),
+        _ => {}
+    }
+}
@@ -61,6 +61,11 @@ impl<T: DeserializeOwned + Clone> QueryBuilder<T> {
         })
     }
 
+    /// The SObject being queried.
+    pub fn sobject(&self) -> &str {
+        &self.sobject
+    }
+
     /// Select fields to retrieve.
     ///
     /// Validates field names for safety. Invalid field names are silently ignored.
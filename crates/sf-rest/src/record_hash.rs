@@ -0,0 +1,285 @@
+//! Canonical record hashing for change detection.
+//!
+//! The same record can come back from Salesforce in slightly different
+//! shapes: date-times as `2024-01-15T10:30:00.000+0000` from REST and
+//! `2024-01-15T10:30:00.000Z` from Bulk, fields in any order, padded
+//! strings, `""` for a cleared text field where REST returns `null`. A
+//! [`RecordHasher`] hashes a canonical form of the record so only real
+//! changes produce a different hash, and a sync can skip records whose
+//! hash matches the one it stored last time.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+
+/// Separates a field name from its value.
+const VALUE_SEPARATOR: u8 = 0x1f;
+/// Ends each field, so `("ab", "c")` and `("a", "bc")` differ.
+const FIELD_SEPARATOR: u8 = 0x1e;
+
+/// Computes a stable hash of selected fields of a record.
+///
+/// Field names are matched case-insensitively and may be relationship
+/// paths such as `Account.Name`. Before hashing:
+///
+/// - fields are sorted by name and the `attributes` entry is ignored,
+/// - strings are trimmed, and an empty string is the same as `null` or a
+///   missing field,
+/// - date-times are converted to UTC with millisecond precision,
+/// - numbers, booleans, and strings are compared by their text, so `1.0`
+///   and `1` hash the same,
+/// - compound fields (addresses, geolocations) are hashed the same way.
+///
+/// # Example
+///
+/// ```
+/// use busbar_sf_rest::RecordHasher;
+/// use serde_json::json;
+///
+/// let hasher = RecordHasher::new(&["Name", "LastModifiedDate"]);
+/// let rest = json!({"Name": "Acme ", "LastModifiedDate": "2024-01-15T10:30:00.000+0000"});
+/// let bulk = json!({"LastModifiedDate": "2024-01-15T10:30:00.000Z", "Name": "Acme"});
+/// assert_eq!(hasher.hash(&rest), hasher.hash(&bulk));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecordHasher {
+    /// Lowercased field paths, or `None` to hash every field.
+    fields: Option<Vec<String>>,
+    /// Lowercased top-level fields skipped when hashing every field.
+    excluded: Vec<String>,
+}
+
+impl RecordHasher {
+    /// Hash only `fields`.
+    pub fn new(fields: &[&str]) -> Self {
+        let mut fields: Vec<String> = fields.iter().map(|f| f.to_lowercase()).collect();
+        fields.sort();
+        fields.dedup();
+        Self {
+            fields: Some(fields),
+            excluded: Vec::new(),
+        }
+    }
+
+    /// Hash every field present in the record.
+    ///
+    /// Usually combined with [`excluding`](Self::excluding) to leave out
+    /// audit fields such as `SystemModstamp` that change on every save.
+    pub fn all_fields() -> Self {
+        Self {
+            fields: None,
+            excluded: Vec::new(),
+        }
+    }
+
+    /// Leave these top-level fields out of the hash.
+    pub fn excluding(mut self, fields: &[&str]) -> Self {
+        self.excluded
+            .extend(fields.iter().map(|f| f.to_lowercase()));
+        if let Some(selected) = &mut self.fields {
+            selected.retain(|f| !self.excluded.contains(f));
+        }
+        self
+    }
+
+    /// The record's hash as 64 lowercase hex characters.
+    pub fn hash(&self, record: &serde_json::Value) -> String {
+        let mut hasher = Sha256::new();
+        for (name, value) in self.canonical_fields(record) {
+            hasher.update(name.as_bytes());
+            hasher.update([VALUE_SEPARATOR]);
+            hasher.update(value.as_bytes());
+            hasher.update([FIELD_SEPARATOR]);
+        }
+        hasher
+            .finalize()
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, b| {
+                hex.push_str(&format!("{:02x}", b));
+                hex
+            })
+    }
+
+    /// Whether the selected fields differ between two versions of a record.
+    pub fn changed(&self, before: &serde_json::Value, after: &serde_json::Value) -> bool {
+        self.canonical_fields(before) != self.canonical_fields(after)
+    }
+
+    /// Selected fields as sorted `(name, canonical value)` pairs, leaving
+    /// out empty ones.
+    fn canonical_fields(&self, record: &serde_json::Value) -> BTreeMap<String, String> {
+        let mut fields = BTreeMap::new();
+        match &self.fields {
+            Some(selected) => {
+                for path in selected {
+                    if let Some(value) = lookup(record, path).and_then(canonical_value) {
+                        fields.insert(path.clone(), value);
+                    }
+                }
+            }
+            None => {
+                if let Some(object) = record.as_object() {
+                    for (name, value) in object {
+                        let name = name.to_lowercase();
+                        if name == "attributes" || self.excluded.contains(&name) {
+                            continue;
+                        }
+                        if let Some(value) = canonical_value(value) {
+                            fields.insert(name, value);
+                        }
+                    }
+                }
+            }
+        }
+        fields
+    }
+}
+
+/// Follow a lowercased dotted path through a record, matching keys
+/// case-insensitively.
+fn lookup<'a>(record: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.').try_fold(record, |value, segment| {
+        value
+            .as_object()?
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(segment))
+            .map(|(_, v)| v)
+    })
+}
+
+/// The text hashed for a value, or `None` for an empty one.
+fn canonical_value(value: &serde_json::Value) -> Option<String> {
+    use serde_json::Value;
+    match value {
+        Value::Null => None,
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => i.to_string(),
+            (None, Some(f)) => f.to_string(),
+            (None, None) => n.to_string(),
+        }),
+        Value::String(s) => {
+            let s = s.trim();
+            if s.is_empty() {
+                None
+            } else {
+                Some(canonical_date_time(s).unwrap_or_else(|| s.to_string()))
+            }
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| canonical_value(item).unwrap_or_default())
+                .collect();
+            Some(format!("[{}]", items.join("\u{1f}")))
+        }
+        Value::Object(object) => {
+            let fields: BTreeMap<String, String> = object
+                .iter()
+                .filter(|(key, _)| key.as_str() != "attributes")
+                .filter_map(|(key, value)| Some((key.to_lowercase(), canonical_value(value)?)))
+                .collect();
+            if fields.is_empty() {
+                return None;
+            }
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect();
+            Some(format!("{{{}}}", fields.join("\u{1f}")))
+        }
+    }
+}
+
+/// A date-time in UTC with millisecond precision, if `s` is one.
+///
+/// Accepts RFC 3339 and Salesforce's `+0000` offset form. Dates without a
+/// time are left as they are.
+fn canonical_date_time(s: &str) -> Option<String> {
+    if !s.contains('T') {
+        return None;
+    }
+    DateTime::parse_from_rfc3339(s)
+        .or_else(|_| DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|dt| {
+            dt.with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Millis, true)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hash_ignores_formatting_differences() {
+        let hasher = RecordHasher::new(&["Name", "Amount", "CloseDate", "LastModifiedDate"]);
+        let rest = json!({
+            "attributes": {"type": "Opportunity"},
+            "Name": "  Big Deal",
+            "Amount": 1000.0,
+            "CloseDate": "2024-02-01",
+            "LastModifiedDate": "2024-01-15T12:30:00.000+0200"
+        });
+        let bulk = json!({
+            "lastmodifieddate": "2024-01-15T10:30:00Z",
+            "CloseDate": "2024-02-01",
+            "Amount": 1000,
+            "Name": "Big Deal"
+        });
+        assert_eq!(hasher.hash(&rest), hasher.hash(&bulk));
+        assert_eq!(hasher.hash(&rest).len(), 64);
+        assert!(!hasher.changed(&rest, &bulk));
+    }
+
+    #[test]
+    fn test_hash_detects_real_changes() {
+        let hasher = RecordHasher::new(&["Name", "Account.Name"]);
+        let before = json!({"Name": "Jo", "Account": {"Name": "Acme"}});
+        let after = json!({"Name": "Jo", "Account": {"Name": "Acme Corp"}});
+        assert_ne!(hasher.hash(&before), hasher.hash(&after));
+        assert!(hasher.changed(&before, &after));
+
+        // Values are not confused with field boundaries.
+        let hasher = RecordHasher::new(&["A", "B"]);
+        assert_ne!(
+            hasher.hash(&json!({"A": "ab", "B": "c"})),
+            hasher.hash(&json!({"A": "a", "B": "bc"}))
+        );
+    }
+
+    #[test]
+    fn test_empty_values_match_missing_fields() {
+        let hasher = RecordHasher::new(&["Name", "Description"]);
+        let a = json!({"Name": "Acme", "Description": null});
+        let b = json!({"Name": "Acme", "Description": "  "});
+        let c = json!({"Name": "Acme"});
+        assert_eq!(hasher.hash(&a), hasher.hash(&b));
+        assert_eq!(hasher.hash(&a), hasher.hash(&c));
+    }
+
+    #[test]
+    fn test_all_fields_excluding_audit_fields() {
+        let hasher = RecordHasher::all_fields().excluding(&["SystemModstamp"]);
+        let before = json!({
+            "Id": "001xx000003DGb2AAG",
+            "BillingAddress": {"city": "Paris", "country": "FR"},
+            "SystemModstamp": "2024-01-15T10:30:00.000+0000"
+        });
+        let after = json!({
+            "Id": "001xx000003DGb2AAG",
+            "BillingAddress": {"country": "FR", "city": "Paris"},
+            "SystemModstamp": "2024-01-16T08:00:00.000+0000"
+        });
+        assert!(!hasher.changed(&before, &after));
+
+        let moved = json!({
+            "Id": "001xx000003DGb2AAG",
+            "BillingAddress": {"city": "Lyon", "country": "FR"}
+        });
+        assert!(hasher.changed(&before, &moved));
+    }
+}
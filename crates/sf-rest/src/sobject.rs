@@ -1,11 +1,15 @@
 //! SObject CRUD operations.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::types::SalesforceId;
 
 /// Result of a create operation.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CreateResult {
-    pub id: String,
+    /// The new record's ID; `None` when the create failed.
+    #[serde(default, deserialize_with = "empty_id_as_none")]
+    pub id: Option<SalesforceId>,
     pub success: bool,
     #[serde(default)]
     pub errors: Vec<SalesforceError>,
@@ -47,6 +51,16 @@ pub struct SalesforceError {
     pub fields: Vec<String>,
 }
 
+/// Failed creates report `"id": ""` or `"id": null`.
+pub(crate) fn empty_id_as_none<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<SalesforceId>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(id) if !id.is_empty() => id.parse().map(Some).map_err(serde::de::Error::custom),
+        _ => Ok(None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,9 +68,9 @@ mod tests {
 
     #[test]
     fn test_create_result_success() {
-        let json = json!({"id": "001xx000003DgAAAS", "success": true, "errors": []});
+        let json = json!({"id": "001xx000003DgAAAA0", "success": true, "errors": []});
         let result: CreateResult = serde_json::from_value(json).unwrap();
-        assert_eq!(result.id, "001xx000003DgAAAS");
+        assert_eq!(result.id.unwrap(), "001xx000003DgAAAA0");
         assert!(result.success);
         assert!(result.errors.is_empty());
     }
@@ -74,6 +88,7 @@ mod tests {
         });
         let result: CreateResult = serde_json::from_value(json).unwrap();
         assert!(!result.success);
+        assert!(result.id.is_none());
         assert_eq!(result.errors.len(), 1);
         assert_eq!(result.errors[0].status_code, "REQUIRED_FIELD_MISSING");
         assert_eq!(result.errors[0].fields, vec!["Name"]);
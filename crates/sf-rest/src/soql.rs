@@ -0,0 +1,457 @@
+//! SOQL builder with bound parameters.
+//!
+//! Conditions are written as templates with `:name` placeholders, and every
+//! value is supplied with [`Soql::bind`]. Templates may not contain string
+//! literals, so text can only reach the query through a bind, where it is
+//! escaped according to its type.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use busbar_sf_rest::Soql;
+//!
+//! let soql = Soql::select(["Id", "Name", "Owner.Name"])
+//!     .from("Account")
+//!     .and_where("Name = :name AND CreatedDate > :since")
+//!     .and_where("Id NOT IN :excluded")
+//!     .bind("name", user_input)
+//!     .bind("since", since)
+//!     .bind("excluded", SoqlValue::ids(excluded_ids))
+//!     .order_by("Name", true)
+//!     .limit(50)
+//!     .build()?;
+//! let accounts: Vec<Account> = client.query_all(&soql).await?;
+//! ```
+
+use std::collections::BTreeMap;
+
+use busbar_sf_client::security::{soql, url};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// A value bound to a `:name` placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoqlValue {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Boolean(bool),
+    /// An integer literal.
+    Integer(i64),
+    /// A decimal literal. Must be finite.
+    Double(f64),
+    /// A quoted, escaped string literal.
+    String(String),
+    /// A quoted record ID, checked to be a valid 15- or 18-character ID.
+    Id(String),
+    /// A date literal, `YYYY-MM-DD`.
+    Date(NaiveDate),
+    /// A UTC date/time literal.
+    DateTime(DateTime<Utc>),
+    /// A parenthesized list for `IN`/`NOT IN`. Must be non-empty and flat.
+    List(Vec<SoqlValue>),
+}
+
+impl SoqlValue {
+    /// A record ID.
+    pub fn id(id: impl Into<String>) -> Self {
+        SoqlValue::Id(id.into())
+    }
+
+    /// A list of record IDs.
+    pub fn ids<I, S>(ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        SoqlValue::List(ids.into_iter().map(|id| SoqlValue::Id(id.into())).collect())
+    }
+
+    fn render(&self, name: &str, out: &mut String) -> Result<()> {
+        match self {
+            SoqlValue::Null => out.push_str("null"),
+            SoqlValue::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+            SoqlValue::Integer(n) => out.push_str(&n.to_string()),
+            SoqlValue::Double(n) if n.is_finite() => out.push_str(&n.to_string()),
+            SoqlValue::Double(_) => return Err(invalid_bind(name, "number is not finite")),
+            SoqlValue::String(s) => {
+                out.push('\'');
+                out.push_str(&soql::escape_string(s));
+                out.push('\'');
+            }
+            SoqlValue::Id(id) if url::is_valid_salesforce_id(id) => {
+                out.push('\'');
+                out.push_str(id);
+                out.push('\'');
+            }
+            SoqlValue::Id(_) => return Err(invalid_bind(name, "invalid Salesforce ID")),
+            SoqlValue::Date(d) => out.push_str(&d.format("%Y-%m-%d").to_string()),
+            SoqlValue::DateTime(t) => out.push_str(&t.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            SoqlValue::List(values) => {
+                if values.is_empty() {
+                    return Err(invalid_bind(name, "list is empty"));
+                }
+                out.push('(');
+                for (i, value) in values.iter().enumerate() {
+                    if matches!(value, SoqlValue::List(_)) {
+                        return Err(invalid_bind(name, "lists cannot be nested"));
+                    }
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    value.render(name, out)?;
+                }
+                out.push(')');
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for SoqlValue {
+    fn from(value: &str) -> Self {
+        SoqlValue::String(value.to_string())
+    }
+}
+
+impl From<String> for SoqlValue {
+    fn from(value: String) -> Self {
+        SoqlValue::String(value)
+    }
+}
+
+impl From<&String> for SoqlValue {
+    fn from(value: &String) -> Self {
+        SoqlValue::String(value.clone())
+    }
+}
+
+impl From<bool> for SoqlValue {
+    fn from(value: bool) -> Self {
+        SoqlValue::Boolean(value)
+    }
+}
+
+impl From<i32> for SoqlValue {
+    fn from(value: i32) -> Self {
+        SoqlValue::Integer(value.into())
+    }
+}
+
+impl From<i64> for SoqlValue {
+    fn from(value: i64) -> Self {
+        SoqlValue::Integer(value)
+    }
+}
+
+impl From<f64> for SoqlValue {
+    fn from(value: f64) -> Self {
+        SoqlValue::Double(value)
+    }
+}
+
+impl From<NaiveDate> for SoqlValue {
+    fn from(value: NaiveDate) -> Self {
+        SoqlValue::Date(value)
+    }
+}
+
+impl From<DateTime<Utc>> for SoqlValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        SoqlValue::DateTime(value)
+    }
+}
+
+impl<T: Into<SoqlValue>> From<Option<T>> for SoqlValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(SoqlValue::Null, Into::into)
+    }
+}
+
+impl<T: Into<SoqlValue>> From<Vec<T>> for SoqlValue {
+    fn from(values: Vec<T>) -> Self {
+        SoqlValue::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+/// SOQL builder whose conditions only take values through binds.
+///
+/// Names are checked and placeholders filled in by [`build`](Self::build),
+/// so the chain itself never fails.
+#[derive(Debug, Clone, Default)]
+pub struct Soql {
+    fields: Vec<String>,
+    sobject: String,
+    conditions: Vec<String>,
+    binds: BTreeMap<String, SoqlValue>,
+    order_by: Vec<(String, bool)>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+impl Soql {
+    /// Start a query selecting `fields`. Relationship paths such as
+    /// `Owner.Name` are allowed.
+    pub fn select<I, S>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            fields: fields.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the SObject to query.
+    pub fn from(mut self, sobject: impl Into<String>) -> Self {
+        self.sobject = sobject.into();
+        self
+    }
+
+    /// Add a condition template, ANDed with any others.
+    ///
+    /// Reference values as `:name` and supply them with [`bind`](Self::bind).
+    /// Date literals such as `LAST_N_DAYS:30` are left alone.
+    pub fn and_where(mut self, condition: impl Into<String>) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    /// Bind a value to the `:name` placeholder.
+    pub fn bind(mut self, name: impl Into<String>, value: impl Into<SoqlValue>) -> Self {
+        self.binds.insert(name.into(), value.into());
+        self
+    }
+
+    /// Add an ORDER BY field.
+    pub fn order_by(mut self, field: impl Into<String>, ascending: bool) -> Self {
+        self.order_by.push((field.into(), ascending));
+        self
+    }
+
+    /// Set the LIMIT clause.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the OFFSET clause.
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Build the query string.
+    ///
+    /// Fails on an unsafe SObject or field name, a string literal in a
+    /// condition, a placeholder without a bind, a bind no condition uses,
+    /// or a bind value that can't be rendered.
+    pub fn build(&self) -> Result<String> {
+        if !soql::is_safe_sobject_name(&self.sobject) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_SOBJECT".to_string(),
+                message: format!("Invalid SObject name: {}", self.sobject),
+            }));
+        }
+        if self.fields.is_empty() {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "NO_FIELDS".to_string(),
+                message: "No fields selected for query".to_string(),
+            }));
+        }
+        for field in self
+            .fields
+            .iter()
+            .chain(self.order_by.iter().map(|(f, _)| f))
+        {
+            if !is_safe_field_path(field) {
+                return Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "INVALID_FIELD".to_string(),
+                    message: format!("Invalid field name: {}", field),
+                }));
+            }
+        }
+
+        let mut query = format!("SELECT {} FROM {}", self.fields.join(", "), self.sobject);
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|c| format!("({})", c))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        if !conditions.is_empty() {
+            query.push_str(" WHERE ");
+        }
+        query.push_str(&bind_soql(&conditions, &self.binds)?);
+
+        if !self.order_by.is_empty() {
+            let order_by: Vec<String> = self
+                .order_by
+                .iter()
+                .map(|(field, ascending)| {
+                    format!("{} {}", field, if *ascending { "ASC" } else { "DESC" })
+                })
+                .collect();
+            query.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
+        }
+        if let Some(limit) = self.limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+        if let Some(offset) = self.offset {
+            query.push_str(&format!(" OFFSET {}", offset));
+        }
+        Ok(query)
+    }
+}
+
+/// Fill the `:name` placeholders in a SOQL template.
+///
+/// The template may not contain string literals (`'...'`), every
+/// placeholder must have a bind, and every bind must be used. This is what
+/// [`Soql::build`] runs on its conditions; call it directly to bind a
+/// whole hand-written query.
+pub fn bind_soql(template: &str, binds: &BTreeMap<String, SoqlValue>) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut used = std::collections::BTreeSet::new();
+    let mut chars = template.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                return Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "INLINE_LITERAL".to_string(),
+                    message: "String literals must be passed as bound parameters".to_string(),
+                }));
+            }
+            ':' if chars
+                .peek()
+                .is_some_and(|&(_, next)| next.is_ascii_alphabetic() || next == '_') =>
+            {
+                let start = i + 1;
+                let mut end = start;
+                while let Some(&(j, next)) = chars.peek() {
+                    if !(next.is_ascii_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    end = j + next.len_utf8();
+                    chars.next();
+                }
+                let name = &template[start..end];
+                let value = binds.get(name).ok_or_else(|| {
+                    Error::new(ErrorKind::Salesforce {
+                        error_code: "UNBOUND_PARAMETER".to_string(),
+                        message: format!("No value bound for :{}", name),
+                    })
+                })?;
+                value.render(name, &mut out)?;
+                used.insert(name);
+            }
+            _ => out.push(c),
+        }
+    }
+    if let Some(unused) = binds.keys().find(|k| !used.contains(k.as_str())) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "UNUSED_PARAMETER".to_string(),
+            message: format!("Bound parameter :{} is not used", unused),
+        }));
+    }
+    Ok(out)
+}
+
+fn is_safe_field_path(path: &str) -> bool {
+    path.split('.').all(soql::is_safe_field_name)
+}
+
+fn invalid_bind(name: &str, reason: &str) -> Error {
+    Error::new(ErrorKind::Salesforce {
+        error_code: "INVALID_BIND".to_string(),
+        message: format!("Invalid value for :{}: {}", name, reason),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_code(err: Error) -> String {
+        match err.kind {
+            ErrorKind::Salesforce { error_code, .. } => error_code,
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_build_binds_typed_values() {
+        let since = DateTime::parse_from_rfc3339("2024-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let query = Soql::select(["Id", "Name", "Owner.Name"])
+            .from("Account")
+            .and_where("Name = :name OR Name LIKE :pattern")
+            .and_where("CreatedDate > :since AND CreatedDate = LAST_N_DAYS:30")
+            .and_where("Id NOT IN :excluded AND Active__c = :active")
+            .bind("name", "O'Brien")
+            .bind("pattern", "50%\\")
+            .bind("since", since)
+            .bind(
+                "excluded",
+                SoqlValue::ids(["001000000000001AAA", "001000000000002"]),
+            )
+            .bind("active", true)
+            .order_by("Name", true)
+            .limit(50)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT Id, Name, Owner.Name FROM Account \
+             WHERE (Name = 'O\\'Brien' OR Name LIKE '50%\\\\') \
+             AND (CreatedDate > 2024-01-02T03:04:05Z AND CreatedDate = LAST_N_DAYS:30) \
+             AND (Id NOT IN ('001000000000001AAA', '001000000000002') AND Active__c = true) \
+             ORDER BY Name ASC LIMIT 50"
+        );
+    }
+
+    #[test]
+    fn test_build_rejects_unsafe_queries() {
+        let base = || Soql::select(["Id"]).from("Account");
+
+        let err = base().and_where("Name = 'x' OR Id != null").build();
+        assert_eq!(error_code(err.unwrap_err()), "INLINE_LITERAL");
+        let err = base().and_where("Name = :name").build();
+        assert_eq!(error_code(err.unwrap_err()), "UNBOUND_PARAMETER");
+        let err = base().bind("name", "x").build();
+        assert_eq!(error_code(err.unwrap_err()), "UNUSED_PARAMETER");
+        let err = base()
+            .and_where("Id = :id")
+            .bind("id", SoqlValue::id("001' OR Id != '"))
+            .build();
+        assert_eq!(error_code(err.unwrap_err()), "INVALID_BIND");
+        let err = base()
+            .and_where("Id IN :ids")
+            .bind("ids", Vec::<String>::new())
+            .build();
+        assert_eq!(error_code(err.unwrap_err()), "INVALID_BIND");
+        let err = Soql::select(["Id"]).from("Account; DELETE").build();
+        assert_eq!(error_code(err.unwrap_err()), "INVALID_SOBJECT");
+        let err = base().order_by("Name DESC, Id", true).build();
+        assert_eq!(error_code(err.unwrap_err()), "INVALID_FIELD");
+    }
+
+    #[test]
+    fn test_bind_soql_full_template() {
+        let binds = BTreeMap::from([
+            ("limit".to_string(), SoqlValue::Integer(5)),
+            ("owner".to_string(), SoqlValue::Null),
+        ]);
+        assert_eq!(
+            bind_soql(
+                "SELECT Id FROM Case WHERE OwnerId != :owner LIMIT :limit",
+                &binds
+            )
+            .unwrap(),
+            "SELECT Id FROM Case WHERE OwnerId != null LIMIT 5"
+        );
+    }
+}
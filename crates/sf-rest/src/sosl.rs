@@ -0,0 +1,418 @@
+//! SOSL builder and search results grouped by SObject.
+//!
+//! [`Sosl`] mirrors [`Soql`](crate::Soql): the search term is escaped for
+//! the FIND clause, and RETURNING conditions take values only through
+//! `:name` binds.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use busbar_sf_rest::{SearchScope, Sosl, SoslReturning};
+//!
+//! let sosl = Sosl::find(user_input)
+//!     .in_scope(SearchScope::Name)
+//!     .returning(SoslReturning::new("Account").fields(["Id", "Name"]).limit(5))
+//!     .returning(
+//!         SoslReturning::new("Contact")
+//!             .fields(["Id", "Name", "Email"])
+//!             .and_where("AccountId != :none"),
+//!     )
+//!     .bind("none", SoqlValue::Null)
+//!     .build()?;
+//! let results = client.search_grouped(&sosl).await?;
+//! let contacts: Vec<Contact> = results.records_as("Contact")?;
+//! ```
+
+use std::collections::BTreeMap;
+
+use busbar_sf_client::security::soql;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::query::strip_record_attributes;
+use crate::soql::{bind_soql, SoqlValue};
+
+/// Which fields the FIND term is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    /// All searchable fields (the default).
+    #[default]
+    All,
+    /// Name fields.
+    Name,
+    /// Email fields.
+    Email,
+    /// Phone fields.
+    Phone,
+    /// The fields searched by the sidebar.
+    Sidebar,
+}
+
+impl SearchScope {
+    fn as_str(&self) -> &'static str {
+        match self {
+            SearchScope::All => "ALL",
+            SearchScope::Name => "NAME",
+            SearchScope::Email => "EMAIL",
+            SearchScope::Phone => "PHONE",
+            SearchScope::Sidebar => "SIDEBAR",
+        }
+    }
+}
+
+/// One object in a RETURNING clause, e.g. `Account(Id, Name LIMIT 5)`.
+#[derive(Debug, Clone, Default)]
+pub struct SoslReturning {
+    sobject: String,
+    fields: Vec<String>,
+    conditions: Vec<String>,
+    order_by: Vec<(String, bool)>,
+    limit: Option<u32>,
+}
+
+impl SoslReturning {
+    /// Return records of `sobject`. With no fields, only IDs come back.
+    pub fn new(sobject: impl Into<String>) -> Self {
+        Self {
+            sobject: sobject.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Fields to return.
+    pub fn fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields.extend(fields.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add a condition template, ANDed with any others. Values are bound
+    /// on the [`Sosl`] with [`Sosl::bind`].
+    pub fn and_where(mut self, condition: impl Into<String>) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    /// Add an ORDER BY field.
+    pub fn order_by(mut self, field: impl Into<String>, ascending: bool) -> Self {
+        self.order_by.push((field.into(), ascending));
+        self
+    }
+
+    /// Limit the records returned for this object.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn template(&self) -> Result<String> {
+        if !soql::is_safe_sobject_name(&self.sobject) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_SOBJECT".to_string(),
+                message: format!("Invalid SObject name: {}", self.sobject),
+            }));
+        }
+        if let Some(field) = self
+            .fields
+            .iter()
+            .chain(self.order_by.iter().map(|(f, _)| f))
+            .find(|f| !f.split('.').all(soql::is_safe_field_name))
+        {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_FIELD".to_string(),
+                message: format!("Invalid field name: {}", field),
+            }));
+        }
+
+        let mut clauses = Vec::new();
+        if !self.fields.is_empty() {
+            clauses.push(self.fields.join(", "));
+        }
+        if !self.conditions.is_empty() {
+            let conditions: Vec<String> =
+                self.conditions.iter().map(|c| format!("({})", c)).collect();
+            clauses.push(format!("WHERE {}", conditions.join(" AND ")));
+        }
+        if !self.order_by.is_empty() {
+            let order_by: Vec<String> = self
+                .order_by
+                .iter()
+                .map(|(field, ascending)| {
+                    format!("{} {}", field, if *ascending { "ASC" } else { "DESC" })
+                })
+                .collect();
+            clauses.push(format!("ORDER BY {}", order_by.join(", ")));
+        }
+        if let Some(limit) = self.limit {
+            clauses.push(format!("LIMIT {}", limit));
+        }
+
+        if clauses.is_empty() {
+            Ok(self.sobject.clone())
+        } else {
+            Ok(format!("{}({})", self.sobject, clauses.join(" ")))
+        }
+    }
+}
+
+/// SOSL builder with an escaped search term and bound RETURNING conditions.
+#[derive(Debug, Clone, Default)]
+pub struct Sosl {
+    term: String,
+    scope: SearchScope,
+    returning: Vec<SoslReturning>,
+    binds: BTreeMap<String, SoqlValue>,
+    limit: Option<u32>,
+}
+
+impl Sosl {
+    /// Search for `term`. Every SOSL operator and wildcard in it is
+    /// escaped, so it matches literally.
+    pub fn find(term: impl AsRef<str>) -> Self {
+        Self {
+            term: escape_term(term.as_ref()),
+            ..Default::default()
+        }
+    }
+
+    /// Search for words starting with `prefix` (`prefix*`).
+    pub fn find_prefix(prefix: impl AsRef<str>) -> Self {
+        let mut sosl = Self::find(prefix);
+        sosl.term.push('*');
+        sosl
+    }
+
+    /// Set which fields are searched.
+    pub fn in_scope(mut self, scope: SearchScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Add an object to the RETURNING clause.
+    pub fn returning(mut self, returning: SoslReturning) -> Self {
+        self.returning.push(returning);
+        self
+    }
+
+    /// Bind a value to a `:name` placeholder in a RETURNING condition.
+    pub fn bind(mut self, name: impl Into<String>, value: impl Into<SoqlValue>) -> Self {
+        self.binds.insert(name.into(), value.into());
+        self
+    }
+
+    /// Limit the total records returned.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Build the search string.
+    ///
+    /// Fails on an empty term and otherwise for the same reasons as
+    /// [`Soql::build`](crate::Soql::build).
+    pub fn build(&self) -> Result<String> {
+        if self.term.trim().is_empty() {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "EMPTY_SEARCH".to_string(),
+                message: "Search term is empty".to_string(),
+            }));
+        }
+        let returning = self
+            .returning
+            .iter()
+            .map(SoslReturning::template)
+            .collect::<Result<Vec<_>>>()?
+            .join(", ");
+        let returning = bind_soql(&returning, &self.binds)?;
+
+        let mut sosl = format!("FIND {{{}}} IN {} FIELDS", self.term, self.scope.as_str());
+        if !returning.is_empty() {
+            sosl.push_str(&format!(" RETURNING {}", returning));
+        }
+        if let Some(limit) = self.limit {
+            sosl.push_str(&format!(" LIMIT {}", limit));
+        }
+        Ok(sosl)
+    }
+}
+
+/// Escape the characters SOSL treats as operators or delimiters in a FIND term.
+pub(crate) fn escape_term(term: &str) -> String {
+    let mut escaped = String::with_capacity(term.len());
+    for c in term.chars() {
+        if matches!(
+            c,
+            '?' | '&'
+                | '|'
+                | '!'
+                | '{'
+                | '}'
+                | '['
+                | ']'
+                | '('
+                | ')'
+                | '^'
+                | '~'
+                | '*'
+                | ':'
+                | '\\'
+                | '"'
+                | '\''
+                | '+'
+                | '-'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Records of one SObject type in a search result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchGroup {
+    /// The SObject type.
+    pub sobject: String,
+    /// The matching records, in relevance order.
+    pub records: Vec<serde_json::Value>,
+}
+
+/// SOSL results grouped by SObject type.
+///
+/// Groups appear in the order their first record was returned.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SoslResult {
+    /// One group per SObject type that had matches.
+    pub groups: Vec<SearchGroup>,
+}
+
+impl SoslResult {
+    pub(crate) fn from_records(records: Vec<serde_json::Value>) -> Self {
+        let mut result = SoslResult::default();
+        for record in records {
+            let sobject = record["attributes"]["type"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            match result.groups.iter_mut().find(|g| g.sobject == sobject) {
+                Some(group) => group.records.push(record),
+                None => result.groups.push(SearchGroup {
+                    sobject,
+                    records: vec![record],
+                }),
+            }
+        }
+        result
+    }
+
+    /// The records of `sobject`, empty if none matched.
+    pub fn records(&self, sobject: &str) -> &[serde_json::Value] {
+        self.groups
+            .iter()
+            .find(|g| g.sobject.eq_ignore_ascii_case(sobject))
+            .map_or(&[], |g| &g.records)
+    }
+
+    /// Deserialize the records of `sobject` into `T`, dropping their
+    /// `attributes` first.
+    pub fn records_as<T: DeserializeOwned>(&self, sobject: &str) -> Result<Vec<T>> {
+        self.records(sobject)
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let mut record = record.clone();
+                strip_record_attributes(&mut record);
+                serde_json::from_value(record).map_err(|e| Error {
+                    kind: ErrorKind::Other(format!(
+                        "failed to deserialize {sobject} record {i}: {e}"
+                    )),
+                    source: Some(Box::new(e)),
+                })
+            })
+            .collect()
+    }
+
+    /// Total records across all groups.
+    pub fn len(&self) -> usize {
+        self.groups.iter().map(|g| g.records.len()).sum()
+    }
+
+    /// Whether nothing matched.
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_escapes_term_and_binds_returning() {
+        let sosl = Sosl::find("O'Brien & {Sons} - 50%")
+            .in_scope(SearchScope::Name)
+            .returning(
+                SoslReturning::new("Account")
+                    .fields(["Id", "Name"])
+                    .and_where("Industry = :industry")
+                    .order_by("Name", true)
+                    .limit(5),
+            )
+            .returning(SoslReturning::new("Contact"))
+            .bind("industry", "Tech")
+            .limit(20)
+            .build()
+            .unwrap();
+        assert_eq!(
+            sosl,
+            "FIND {O\\'Brien \\& \\{Sons\\} \\- 50%} IN NAME FIELDS \
+             RETURNING Account(Id, Name WHERE (Industry = 'Tech') ORDER BY Name ASC LIMIT 5), \
+             Contact LIMIT 20"
+        );
+
+        assert_eq!(
+            Sosl::find_prefix("acme*").build().unwrap(),
+            "FIND {acme\\**} IN ALL FIELDS"
+        );
+    }
+
+    #[test]
+    fn test_build_rejects_unsafe_parts() {
+        assert!(Sosl::find("  ").build().is_err());
+        assert!(Sosl::find("x")
+            .returning(SoslReturning::new("Account) RETURNING User"))
+            .build()
+            .is_err());
+        assert!(Sosl::find("x")
+            .returning(SoslReturning::new("Account").and_where("Name = 'x'"))
+            .build()
+            .is_err());
+        assert!(Sosl::find("x").bind("unused", 1).build().is_err());
+    }
+
+    #[test]
+    fn test_sosl_result_groups_records() {
+        #[derive(Debug, Deserialize)]
+        struct Contact {
+            #[serde(rename = "Name")]
+            name: String,
+        }
+
+        let result = SoslResult::from_records(vec![
+            serde_json::json!({"attributes": {"type": "Contact"}, "Id": "003A", "Name": "Ann"}),
+            serde_json::json!({"attributes": {"type": "Account"}, "Id": "001A"}),
+            serde_json::json!({"attributes": {"type": "Contact"}, "Id": "003B", "Name": "Bo"}),
+        ]);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.groups[0].sobject, "Contact");
+        assert_eq!(result.records("account").len(), 1);
+        assert!(result.records("Lead").is_empty());
+        let contacts: Vec<Contact> = result.records_as("Contact").unwrap();
+        assert_eq!(contacts[1].name, "Bo");
+        assert!(result.records_as::<Contact>("Account").is_err());
+    }
+}
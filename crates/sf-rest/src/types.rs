@@ -1,4 +1,3 @@
 //! Common types for sf-rest.
 
-/// Salesforce record ID (18-character or 15-character).
-pub type SalesforceId = String;
+pub use busbar_sf_client::{ParseIdError, SalesforceId};
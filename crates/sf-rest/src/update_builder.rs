@@ -0,0 +1,177 @@
+//! Record updates that say which fields to clear.
+//!
+//! In an update body, a missing field is left alone and a `null` field is
+//! cleared. A typed struct with `Option` fields can't tell the two apart:
+//! serialized as-is every `None` clears a field, and with
+//! `skip_serializing_if` no field can ever be cleared. [`UpdateBuilder`]
+//! omits nulls by default and sends one only for fields named with
+//! [`clear`](UpdateBuilder::clear).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use busbar_sf_rest::UpdateBuilder;
+//!
+//! let update = UpdateBuilder::from_record(&contact)?   // `None` fields are left alone
+//!     .set("Title", "CTO")
+//!     .clear("AssistantName");                          // sent as null
+//! client.update("Contact", &id, &update).await?;
+//! ```
+
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// The body of a record update, distinguishing omitted fields from fields
+/// explicitly set to null.
+///
+/// Implements [`Serialize`], so it can be passed anywhere a record is
+/// accepted, such as [`update`](crate::SalesforceRestClient::update).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateBuilder {
+    fields: Map<String, Value>,
+}
+
+impl UpdateBuilder {
+    /// An empty update.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from a record's non-null fields.
+    ///
+    /// Null fields (such as `None` options) and the `attributes` entry of
+    /// a queried record are omitted, so they are left unchanged. Fails if
+    /// the record does not serialize to a JSON object.
+    pub fn from_record<T: Serialize>(record: &T) -> Result<Self> {
+        let value = serde_json::to_value(record).map_err(|e| {
+            Error::new(ErrorKind::Other(format!(
+                "Failed to serialize update: {}",
+                e
+            )))
+        })?;
+        let Value::Object(mut fields) = value else {
+            return Err(Error::new(ErrorKind::Other(
+                "An update must serialize to a JSON object".to_string(),
+            )));
+        };
+        fields.remove("attributes");
+        fields.retain(|_, value| !value.is_null());
+        Ok(Self { fields })
+    }
+
+    /// Set a field. A null value omits the field rather than clearing it;
+    /// use [`clear`](Self::clear) for that.
+    pub fn set(mut self, field: impl Into<String>, value: impl Into<Value>) -> Self {
+        let field = field.into();
+        match value.into() {
+            Value::Null => {
+                self.fields.remove(&field);
+            }
+            value => {
+                self.fields.insert(field, value);
+            }
+        }
+        self
+    }
+
+    /// Clear a field by sending it as null.
+    pub fn clear(mut self, field: impl Into<String>) -> Self {
+        self.fields.insert(field.into(), Value::Null);
+        self
+    }
+
+    /// Leave a field out of the update.
+    pub fn omit(mut self, field: &str) -> Self {
+        self.fields.remove(field);
+        self
+    }
+
+    /// Fields that will be cleared.
+    pub fn fields_to_null(&self) -> impl Iterator<Item = &str> {
+        self.fields
+            .iter()
+            .filter(|(_, value)| value.is_null())
+            .map(|(field, _)| field.as_str())
+    }
+
+    /// Whether the update changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// The update body as a JSON object.
+    pub fn into_value(self) -> Value {
+        Value::Object(self.fields)
+    }
+}
+
+impl Serialize for UpdateBuilder {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.fields.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Contact {
+        last_name: String,
+        title: Option<String>,
+        assistant_name: Option<String>,
+    }
+
+    #[test]
+    fn test_from_record_omits_nulls() {
+        let contact = Contact {
+            last_name: "Smith".to_string(),
+            title: None,
+            assistant_name: None,
+        };
+        let update = UpdateBuilder::from_record(&contact).unwrap();
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({"LastName": "Smith"})
+        );
+        assert_eq!(update.fields_to_null().count(), 0);
+    }
+
+    #[test]
+    fn test_clear_sends_explicit_null() {
+        let update = UpdateBuilder::from_record(&json!({
+            "attributes": {"type": "Contact"},
+            "LastName": "Smith",
+            "Phone": null
+        }))
+        .unwrap()
+        .set("Title", "CTO")
+        .set("Department", Value::Null)
+        .clear("AssistantName");
+
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({"LastName": "Smith", "Title": "CTO", "AssistantName": null})
+        );
+        assert_eq!(
+            update.fields_to_null().collect::<Vec<_>>(),
+            ["AssistantName"]
+        );
+
+        let update = update.set("AssistantName", "Jo").omit("Title");
+        assert_eq!(
+            update.into_value(),
+            json!({"LastName": "Smith", "AssistantName": "Jo"})
+        );
+    }
+
+    #[test]
+    fn test_from_record_rejects_non_objects() {
+        assert!(UpdateBuilder::from_record(&"Smith").is_err());
+        assert!(UpdateBuilder::new().is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+//! Multipart file upload types for the Salesforce REST API.
+
+use std::path::{Path, PathBuf};
+
+/// The SObject a multipart file upload creates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadTarget {
+    /// `ContentVersion` (Files). Up to 2 GB per file.
+    ContentVersion,
+    /// Legacy `Attachment` records. Up to 500 MB per file.
+    Attachment,
+    /// `Document` records. Up to 500 MB per file.
+    Document,
+}
+
+impl UploadTarget {
+    /// The SObject API name.
+    pub fn sobject(&self) -> &'static str {
+        match self {
+            UploadTarget::ContentVersion => "ContentVersion",
+            UploadTarget::Attachment => "Attachment",
+            UploadTarget::Document => "Document",
+        }
+    }
+
+    /// Name of the multipart part carrying the record's JSON fields.
+    pub(crate) fn entity_part(&self) -> &'static str {
+        match self {
+            UploadTarget::ContentVersion => "entity_content",
+            UploadTarget::Attachment => "entity_attachment",
+            UploadTarget::Document => "entity_document",
+        }
+    }
+
+    /// The blob field (and multipart part name) the file content goes into.
+    pub(crate) fn binary_field(&self) -> &'static str {
+        match self {
+            UploadTarget::ContentVersion => "VersionData",
+            UploadTarget::Attachment | UploadTarget::Document => "Body",
+        }
+    }
+}
+
+/// File content for a multipart upload: either a path to read or raw bytes.
+///
+/// A file at a path is streamed from disk as it is sent, so its size is not
+/// limited by memory.
+#[derive(Debug, Clone)]
+pub enum FileSource {
+    Path(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+impl From<PathBuf> for FileSource {
+    fn from(path: PathBuf) -> Self {
+        FileSource::Path(path)
+    }
+}
+
+impl From<&Path> for FileSource {
+    fn from(path: &Path) -> Self {
+        FileSource::Path(path.to_path_buf())
+    }
+}
+
+impl From<Vec<u8>> for FileSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        FileSource::Bytes(bytes)
+    }
+}
+
+impl From<&[u8]> for FileSource {
+    fn from(bytes: &[u8]) -> Self {
+        FileSource::Bytes(bytes.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_target_parts() {
+        assert_eq!(UploadTarget::ContentVersion.sobject(), "ContentVersion");
+        assert_eq!(UploadTarget::ContentVersion.entity_part(), "entity_content");
+        assert_eq!(UploadTarget::ContentVersion.binary_field(), "VersionData");
+        assert_eq!(UploadTarget::Attachment.entity_part(), "entity_attachment");
+        assert_eq!(UploadTarget::Document.binary_field(), "Body");
+    }
+
+    #[test]
+    fn test_file_source_from() {
+        assert!(matches!(
+            FileSource::from(Path::new("report.pdf")),
+            FileSource::Path(_)
+        ));
+        assert!(matches!(
+            FileSource::from(b"abc".as_slice()),
+            FileSource::Bytes(ref b) if b == b"abc"
+        ));
+    }
+}
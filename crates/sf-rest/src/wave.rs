@@ -0,0 +1,180 @@
+//! CRM Analytics (Wave) types for the Salesforce REST API.
+
+use serde::{Deserialize, Serialize};
+
+/// A page of CRM Analytics datasets.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaveDatasetCollection {
+    #[serde(default)]
+    pub datasets: Vec<WaveDataset>,
+    #[serde(rename = "nextPageUrl")]
+    pub next_page_url: Option<String>,
+    #[serde(rename = "totalSize", default)]
+    pub total_size: u64,
+    pub url: Option<String>,
+}
+
+/// A CRM Analytics dataset.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaveDataset {
+    pub id: String,
+    pub name: String,
+    pub label: Option<String>,
+    #[serde(rename = "currentVersionId")]
+    pub current_version_id: Option<String>,
+    #[serde(rename = "currentVersionUrl")]
+    pub current_version_url: Option<String>,
+    #[serde(rename = "createdDate")]
+    pub created_date: Option<String>,
+    #[serde(rename = "lastModifiedDate")]
+    pub last_modified_date: Option<String>,
+    pub folder: Option<WaveFolder>,
+    pub url: Option<String>,
+}
+
+/// The app (folder) a CRM Analytics asset lives in.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WaveFolder {
+    pub id: String,
+    pub name: Option<String>,
+    pub label: Option<String>,
+}
+
+/// Request body for executing a SAQL query.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SaqlQueryRequest {
+    pub query: String,
+}
+
+/// Response from the `/wave/query` endpoint.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SaqlQueryResponse {
+    pub action: Option<String>,
+    #[serde(rename = "responseId")]
+    pub response_id: Option<String>,
+    pub results: SaqlQueryResults,
+    pub query: Option<String>,
+    #[serde(rename = "responseTime")]
+    pub response_time: Option<u64>,
+}
+
+/// Rows returned by a SAQL query.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SaqlQueryResults {
+    #[serde(default)]
+    pub records: Vec<serde_json::Value>,
+}
+
+/// Request body for starting a dataflow job.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataflowJobRequest {
+    #[serde(rename = "dataflowId")]
+    pub dataflow_id: String,
+    pub command: String,
+}
+
+/// A CRM Analytics dataflow job.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataflowJob {
+    pub id: String,
+    pub label: Option<String>,
+    /// One of `Queued`, `Running`, `Success`, `Warning`, or `Failure`.
+    pub status: String,
+    #[serde(rename = "jobType")]
+    pub job_type: Option<String>,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<String>,
+    #[serde(rename = "executedDate")]
+    pub executed_date: Option<String>,
+    /// Run time in seconds.
+    pub duration: Option<u64>,
+    pub message: Option<String>,
+    pub url: Option<String>,
+}
+
+impl DataflowJob {
+    /// Whether the job has reached a terminal state.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "Success" | "Warning" | "Failure")
+    }
+}
+
+/// A page of CRM Analytics dataflow jobs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DataflowJobCollection {
+    #[serde(rename = "dataflowJobs", default)]
+    pub dataflow_jobs: Vec<DataflowJob>,
+    #[serde(rename = "nextPageUrl")]
+    pub next_page_url: Option<String>,
+    pub url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dataset_collection_deserialize() {
+        let json = json!({
+            "datasets": [{
+                "id": "0Fbxx0000000001CAA",
+                "name": "opportunities",
+                "label": "Opportunities",
+                "currentVersionId": "0Fcxx0000000001CAA",
+                "currentVersionUrl": "/services/data/v62.0/wave/datasets/0Fbxx0000000001CAA/versions/0Fcxx0000000001CAA",
+                "folder": {"id": "00lxx0000000001AAA", "name": "SharedApp", "label": "Shared App"},
+                "type": "dataset"
+            }],
+            "nextPageUrl": null,
+            "totalSize": 1,
+            "url": "/services/data/v62.0/wave/datasets"
+        });
+        let page: WaveDatasetCollection = serde_json::from_value(json).unwrap();
+        assert_eq!(page.total_size, 1);
+        assert_eq!(page.datasets[0].name, "opportunities");
+        assert_eq!(
+            page.datasets[0].folder.as_ref().unwrap().name.as_deref(),
+            Some("SharedApp")
+        );
+    }
+
+    #[test]
+    fn test_saql_query_response_deserialize() {
+        let json = json!({
+            "action": "query",
+            "responseId": "4abc",
+            "results": {"records": [{"count": 42}]},
+            "query": "q = load \"opportunities\"; q = group q by all; q = foreach q generate count() as 'count';",
+            "responseTime": 35
+        });
+        let response: SaqlQueryResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(response.results.records.len(), 1);
+        assert_eq!(response.results.records[0]["count"], 42);
+    }
+
+    #[test]
+    fn test_dataflow_job_request_serialize() {
+        let request = DataflowJobRequest {
+            dataflow_id: "02Kxx0000000001EAA".to_string(),
+            command: "Start".to_string(),
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["dataflowId"], "02Kxx0000000001EAA");
+        assert_eq!(json["command"], "Start");
+    }
+
+    #[test]
+    fn test_dataflow_job_is_finished() {
+        let json = json!({
+            "id": "03CAxx000000001",
+            "label": "Default Salesforce Dataflow",
+            "status": "Running",
+            "jobType": "dataflow"
+        });
+        let mut job: DataflowJob = serde_json::from_value(json).unwrap();
+        assert!(!job.is_finished());
+        job.status = "Warning".to_string();
+        assert!(job.is_finished());
+    }
+}
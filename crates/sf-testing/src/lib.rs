@@ -0,0 +1,479 @@
+//! # busbar-sf-testing
+//!
+//! Test data for integration tests that run against a real Salesforce org.
+//!
+//! Tests that share an org step on each other: two CI runs create an
+//! Account with the same name, or a failed test never reaches its cleanup
+//! code and leaves records behind for the next run to trip over.
+//! [`TestOrgFactory`] gives every record a name unique to the run and
+//! returns a [`TestRecord`] guard that deletes the record when it goes out
+//! of scope, including when the test panics.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use busbar_sf_testing::TestOrgFactory;
+//! use serde_json::json;
+//!
+//! let factory = TestOrgFactory::new(client.clone());
+//! let account = factory
+//!     .create_named("Account", "Name", "Widget", json!({"Industry": "Technology"}))
+//!     .await?;
+//! let contact = factory
+//!     .create("Contact", json!({"LastName": "Smith", "AccountId": account.id()}))
+//!     .await?;
+//!
+//! // ... exercise the code under test ...
+//!
+//! // Guards drop in reverse order: the contact is deleted, then the account.
+//! ```
+//!
+//! Deleting in `Drop` blocks the test while the request runs. Tests that
+//! want to see cleanup errors can call [`TestRecord::delete`] instead, and
+//! a record that must outlive the test can be kept with
+//! [`TestRecord::keep`]. Runs killed before their guards ran are cleaned up
+//! by [`TestOrgFactory::sweep`].
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use busbar_sf_client::security::soql;
+use busbar_sf_client::SalesforceClient;
+use busbar_sf_rest::{Error, ErrorKind, Result, SalesforceId, SalesforceRestClient};
+use serde_json::Value;
+
+/// Name prefix used unless [`TestOrgFactory::with_prefix`] sets another.
+pub const DEFAULT_PREFIX: &str = "BusbarTest";
+
+/// Records deleted per request by [`TestOrgFactory::sweep`].
+const SWEEP_BATCH_SIZE: usize = 200;
+
+/// Creates uniquely named test records that delete themselves.
+///
+/// Names have the form `{prefix}_{base}_{run}_{n}`, where `run` identifies
+/// the factory (time and process) and `n` counts records it has named, so
+/// concurrent runs against the same org never collide.
+#[derive(Debug)]
+pub struct TestOrgFactory {
+    client: SalesforceRestClient,
+    prefix: String,
+    run_id: String,
+    counter: AtomicU64,
+}
+
+impl TestOrgFactory {
+    /// A factory creating records through `client`.
+    pub fn new(client: SalesforceRestClient) -> Self {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        Self {
+            client,
+            prefix: DEFAULT_PREFIX.to_string(),
+            run_id: format!("{:x}{:x}", millis, std::process::id()),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Use `prefix` instead of [`DEFAULT_PREFIX`] for names.
+    ///
+    /// # Panics
+    ///
+    /// If `prefix` is empty or contains anything other than ASCII letters,
+    /// digits, and underscores.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        assert!(
+            !prefix.is_empty()
+                && prefix
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_'),
+            "test record prefix must be letters, digits, and underscores: {prefix:?}"
+        );
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// The client records are created with.
+    pub fn client(&self) -> &SalesforceRestClient {
+        &self.client
+    }
+
+    /// The name prefix.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The part of every name that identifies this factory.
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// A name no other call, factory, or run will produce.
+    pub fn unique_name(&self, base: &str) -> String {
+        let n = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
+        format!("{}_{}_{}_{}", self.prefix, base, self.run_id, n)
+    }
+
+    /// Create a record and guard it.
+    ///
+    /// `fields` is used as given; use [`create_named`](Self::create_named)
+    /// for a unique name.
+    pub async fn create(&self, sobject: &str, fields: Value) -> Result<TestRecord> {
+        let id = self.client.create(sobject, &fields).await?;
+        tracing::debug!(sobject, id = %id, "created test record");
+        Ok(TestRecord {
+            client: self.client.clone(),
+            sobject: sobject.to_string(),
+            id,
+            name: None,
+            armed: true,
+        })
+    }
+
+    /// Create a record with `name_field` set to a
+    /// [`unique_name`](Self::unique_name) built from `base`, and guard it.
+    pub async fn create_named(
+        &self,
+        sobject: &str,
+        name_field: &str,
+        base: &str,
+        fields: Value,
+    ) -> Result<TestRecord> {
+        let mut fields = match fields {
+            Value::Object(fields) => fields,
+            Value::Null => Default::default(),
+            _ => {
+                return Err(Error::new(ErrorKind::Other(
+                    "Test record fields must be a JSON object".to_string(),
+                )))
+            }
+        };
+        let name = self.unique_name(base);
+        fields.insert(name_field.to_string(), Value::String(name.clone()));
+        let mut record = self.create(sobject, Value::Object(fields)).await?;
+        record.name = Some(name);
+        Ok(record)
+    }
+
+    /// Delete records left behind by earlier runs.
+    ///
+    /// Deletes `sobject` records whose `name_field` starts with this
+    /// factory's prefix and that were created more than `older_than` ago,
+    /// so records of runs still in progress are left alone. Returns how
+    /// many were deleted; records that fail to delete (because another
+    /// record still points at them, say) are logged and skipped.
+    pub async fn sweep(
+        &self,
+        sobject: &str,
+        name_field: &str,
+        older_than: chrono::Duration,
+    ) -> Result<usize> {
+        if !soql::is_safe_sobject_name(sobject) || !soql::is_safe_field_name(name_field) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_FIELD".to_string(),
+                message: "Invalid SObject or field name".to_string(),
+            }));
+        }
+        let cutoff = (chrono::Utc::now() - older_than).format("%Y-%m-%dT%H:%M:%SZ");
+        // `_` is a LIKE wildcard; the prefix is letters, digits, and `_`.
+        let pattern = format!("{}\\_%", self.prefix.replace('_', "\\_"));
+        let soql = format!(
+            "SELECT Id FROM {sobject} WHERE {name_field} LIKE '{pattern}' AND CreatedDate < {cutoff}"
+        );
+        let records: Vec<Value> = self.client.query_all(&soql).await?;
+        let ids: Vec<&str> = records
+            .iter()
+            .filter_map(|r| r.get("Id").and_then(Value::as_str))
+            .collect();
+
+        let mut deleted = 0;
+        for batch in ids.chunks(SWEEP_BATCH_SIZE) {
+            for result in self.client.delete_multiple(batch, false).await? {
+                if result.success {
+                    deleted += 1;
+                } else {
+                    tracing::warn!(
+                        sobject,
+                        id = ?result.id,
+                        errors = ?result.errors,
+                        "failed to sweep test record"
+                    );
+                }
+            }
+        }
+        Ok(deleted)
+    }
+}
+
+/// A record created by a [`TestOrgFactory`], deleted when dropped.
+///
+/// The delete in `Drop` runs on its own thread and runtime, with a new
+/// client for the same org and access token, so it works from any test
+/// runtime, including `#[tokio::test]`'s single thread. Interceptors and
+/// custom transports on the factory's client are not used for it. A failed
+/// delete is logged, never raised: `Drop` may be running because the test
+/// already panicked.
+#[derive(Debug)]
+pub struct TestRecord {
+    client: SalesforceRestClient,
+    sobject: String,
+    id: SalesforceId,
+    name: Option<String>,
+    armed: bool,
+}
+
+impl TestRecord {
+    /// The record ID.
+    pub fn id(&self) -> &SalesforceId {
+        &self.id
+    }
+
+    /// The record's SObject type.
+    pub fn sobject(&self) -> &str {
+        &self.sobject
+    }
+
+    /// The unique name given by [`TestOrgFactory::create_named`].
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Keep the record after the test and return its ID.
+    pub fn keep(mut self) -> SalesforceId {
+        self.armed = false;
+        self.id.clone()
+    }
+
+    /// Delete the record now, returning any error.
+    ///
+    /// A record that is already gone counts as deleted.
+    pub async fn delete(mut self) -> Result<()> {
+        self.armed = false;
+        match self.client.delete(&self.sobject, self.id.as_str()).await {
+            Err(e) if is_not_found(&e) => Ok(()),
+            result => result,
+        }
+    }
+}
+
+impl Drop for TestRecord {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let inner = self.client.inner();
+        let client = SalesforceClient::new(inner.instance_url(), inner.access_token())
+            .map(|c| SalesforceRestClient::from_client(c.with_api_version(inner.api_version())));
+        let sobject = self.sobject.clone();
+        let id = self.id.clone();
+
+        // The test's runtime may be a single thread that is blocked right
+        // here, so the delete can't be spawned onto it, and connections
+        // pooled by the original client are driven by that runtime.
+        let outcome = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| Error::new(ErrorKind::Other(e.to_string())))?;
+            let client = client.map_err(Error::from)?;
+            runtime.block_on(client.delete(&sobject, id.as_str()))
+        })
+        .join();
+
+        match outcome {
+            Ok(Ok(())) => {
+                tracing::debug!(sobject = %self.sobject, id = %self.id, "deleted test record")
+            }
+            Ok(Err(e)) if is_not_found(&e) => {}
+            Ok(Err(e)) => tracing::warn!(
+                sobject = %self.sobject,
+                id = %self.id,
+                error = %e,
+                "failed to delete test record"
+            ),
+            Err(_) => tracing::warn!(
+                sobject = %self.sobject,
+                id = %self.id,
+                "test record cleanup panicked"
+            ),
+        }
+    }
+}
+
+/// Whether a delete failed because the record no longer exists.
+fn is_not_found(error: &Error) -> bool {
+    error
+        .source
+        .as_ref()
+        .and_then(|s| s.downcast_ref::<busbar_sf_client::Error>())
+        .is_some_and(|e| match &e.kind {
+            busbar_sf_client::ErrorKind::NotFound(_) => true,
+            busbar_sf_client::ErrorKind::SalesforceApi { error_code, .. } => {
+                error_code == "NOT_FOUND" || error_code == "ENTITY_IS_DELETED"
+            }
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ACCOUNT_ID: &str = "001xx000003DGb2AAG";
+
+    async fn mock_create(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/sobjects/Account"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "id": ACCOUNT_ID,
+                "success": true,
+                "errors": []
+            })))
+            .mount(server)
+            .await;
+    }
+
+    fn factory(server: &MockServer) -> TestOrgFactory {
+        TestOrgFactory::new(SalesforceRestClient::new(server.uri(), "token").unwrap())
+    }
+
+    #[test]
+    fn test_unique_names() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let a = TestOrgFactory::new(client.clone()).with_prefix("CI_Run");
+        let first = a.unique_name("Widget");
+        assert!(first.starts_with("CI_Run_Widget_"));
+        assert!(first.ends_with("_1"));
+        assert_ne!(first, a.unique_name("Widget"));
+
+        let b = TestOrgFactory::new(client);
+        assert!(b.unique_name("Widget").starts_with("BusbarTest_Widget_"));
+    }
+
+    #[test]
+    #[should_panic(expected = "prefix")]
+    fn test_prefix_rejects_soql_metacharacters() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let _ = TestOrgFactory::new(client).with_prefix("x' OR Name != '");
+    }
+
+    #[tokio::test]
+    async fn test_record_deleted_on_drop() {
+        let server = MockServer::start().await;
+        mock_create(&server).await;
+        Mock::given(method("DELETE"))
+            .and(path(format!(
+                "/services/data/v62.0/sobjects/Account/{ACCOUNT_ID}"
+            )))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let factory = factory(&server);
+        let record = factory
+            .create_named("Account", "Name", "Widget", json!({"Industry": "Energy"}))
+            .await
+            .unwrap();
+        assert_eq!(record.id(), ACCOUNT_ID);
+        assert!(record.name().unwrap().starts_with("BusbarTest_Widget_"));
+        drop(record);
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_kept_record_not_deleted() {
+        let server = MockServer::start().await;
+        mock_create(&server).await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let record = factory(&server)
+            .create("Account", json!({"Name": "Keep me"}))
+            .await
+            .unwrap();
+        assert_eq!(record.keep(), ACCOUNT_ID);
+    }
+
+    #[tokio::test]
+    async fn test_explicit_delete_tolerates_missing_record() {
+        let server = MockServer::start().await;
+        mock_create(&server).await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!([{
+                "errorCode": "NOT_FOUND",
+                "message": "The requested resource does not exist"
+            }])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let record = factory(&server)
+            .create("Account", json!({"Name": "Gone"}))
+            .await
+            .unwrap();
+        record.delete().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sweep_deletes_old_records() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "totalSize": 2,
+                "done": true,
+                "records": [
+                    {"attributes": {"type": "Account"}, "Id": ACCOUNT_ID},
+                    {"attributes": {"type": "Account"}, "Id": "001xx000003DGb3AAG"}
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/services/data/v62.0/composite/sobjects"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"id": ACCOUNT_ID, "success": true, "errors": []},
+                {"id": "001xx000003DGb3AAG", "success": false, "errors": [{
+                    "statusCode": "DELETE_FAILED",
+                    "message": "Your attempt to delete Acme could not be completed",
+                    "fields": []
+                }]}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let deleted = factory(&server)
+            .sweep("Account", "Name", chrono::Duration::hours(6))
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+
+        let requests = server.received_requests().await.unwrap();
+        let (_, soql) = requests[0]
+            .url
+            .query_pairs()
+            .find(|(k, _)| k == "q")
+            .unwrap();
+        assert!(soql.contains("Name LIKE 'BusbarTest\\_%'"), "{soql}");
+        assert!(soql.contains("CreatedDate < "), "{soql}");
+    }
+
+    #[tokio::test]
+    async fn test_sweep_rejects_unsafe_names() {
+        let client = SalesforceRestClient::new("https://test.salesforce.com", "token").unwrap();
+        let err = TestOrgFactory::new(client)
+            .sweep("Account", "Name FROM User--", chrono::Duration::hours(1))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("INVALID_FIELD"));
+    }
+}
@@ -0,0 +1,267 @@
+use std::time::{Duration, Instant};
+
+use busbar_sf_client::security::{soql, url as url_security};
+use tracing::instrument;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::*;
+
+const ASYNC_APEX_JOB_FIELDS: &str = "Id, JobType, Status, ApexClassId, ApexClass.Name, MethodName, JobItemsProcessed, TotalJobItems, NumberOfErrors, ExtendedStatus, ParentJobId, CronTriggerId, CreatedById, CreatedDate, CompletedDate";
+
+const CRON_TRIGGER_FIELDS: &str = "Id, CronExpression, CronJobDetail.Name, CronJobDetail.JobType, State, NextFireTime, PreviousFireTime, StartTime, EndTime, TimesTriggered, TimeZoneSidKey, OwnerId";
+
+impl super::ToolingClient {
+    // =========================================================================
+    // AsyncApexJob Monitoring
+    // =========================================================================
+    //
+    // AsyncApexJob and CronTrigger are data objects, so these go through the
+    // REST query endpoint rather than Tooling's.
+
+    /// List async Apex jobs (batch, queueable, future, scheduled) matching `filter`.
+    ///
+    /// ```rust,ignore
+    /// use busbar_sf_tooling::{AsyncApexJobFilter, AsyncApexJobType};
+    ///
+    /// // Queueables still running after an hour.
+    /// let stuck = client
+    ///     .async_apex_jobs(
+    ///         &AsyncApexJobFilter::active()
+    ///             .job_types([AsyncApexJobType::Queueable])
+    ///             .older_than(Duration::from_secs(60 * 60)),
+    ///     )
+    ///     .await?;
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn async_apex_jobs(&self, filter: &AsyncApexJobFilter) -> Result<Vec<AsyncApexJob>> {
+        self.client
+            .query_all(&async_apex_job_soql(filter))
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Get an async Apex job by ID.
+    #[instrument(skip(self))]
+    pub async fn async_apex_job(&self, id: &str) -> Result<Option<AsyncApexJob>> {
+        validate_id(id)?;
+        let soql = format!(
+            "SELECT {} FROM AsyncApexJob WHERE Id = '{}'",
+            ASYNC_APEX_JOB_FIELDS, id
+        );
+        let mut jobs: Vec<AsyncApexJob> = self.client.query_all(&soql).await?;
+        Ok(jobs.pop())
+    }
+
+    /// Poll an async Apex job until it completes, fails, or is aborted.
+    ///
+    /// Fails if the job doesn't exist or is still running after `timeout`.
+    #[instrument(skip(self))]
+    pub async fn wait_for_async_apex_job(
+        &self,
+        id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<AsyncApexJob> {
+        let started = Instant::now();
+        loop {
+            let job = self.async_apex_job(id).await?.ok_or_else(|| {
+                Error::new(ErrorKind::Salesforce {
+                    error_code: "NOT_FOUND".to_string(),
+                    message: format!("AsyncApexJob {} not found", id),
+                })
+            })?;
+            if job.status.is_terminal() {
+                return Ok(job);
+            }
+            if started.elapsed() >= timeout {
+                return Err(Error::new(ErrorKind::Other(format!(
+                    "AsyncApexJob {} still {} after {:?}",
+                    id,
+                    job.status.as_str(),
+                    timeout
+                ))));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    // =========================================================================
+    // Scheduled Jobs
+    // =========================================================================
+
+    /// List scheduled jobs, optionally only those in `state` (e.g. `WAITING`).
+    #[instrument(skip(self))]
+    pub async fn cron_triggers(&self, state: Option<&str>) -> Result<Vec<CronTrigger>> {
+        let mut soql = format!("SELECT {} FROM CronTrigger", CRON_TRIGGER_FIELDS);
+        if let Some(state) = state {
+            soql.push_str(&format!(" WHERE State = '{}'", soql::escape_string(state)));
+        }
+        soql.push_str(" ORDER BY NextFireTime");
+        self.client.query_all(&soql).await.map_err(Into::into)
+    }
+
+    /// Get a scheduled job by its `CronJobDetail.Name` (the name given to
+    /// `System.schedule`).
+    #[instrument(skip(self))]
+    pub async fn cron_trigger_by_name(&self, name: &str) -> Result<Option<CronTrigger>> {
+        let soql = format!(
+            "SELECT {} FROM CronTrigger WHERE CronJobDetail.Name = '{}'",
+            CRON_TRIGGER_FIELDS,
+            soql::escape_string(name)
+        );
+        let mut triggers: Vec<CronTrigger> = self.client.query_all(&soql).await?;
+        Ok(triggers.pop())
+    }
+
+    // =========================================================================
+    // Abort
+    // =========================================================================
+
+    /// Abort an async Apex job or scheduled job with `System.abortJob`.
+    ///
+    /// Accepts an `AsyncApexJob` ID or a `CronTrigger` ID. Aborting a job
+    /// that already finished fails with an Apex execution error.
+    #[instrument(skip(self))]
+    pub async fn abort_job(&self, id: &str) -> Result<()> {
+        validate_id(id)?;
+        self.execute_anonymous(&format!("System.abortJob('{}');", id))
+            .await?;
+        Ok(())
+    }
+}
+
+fn validate_id(id: &str) -> Result<()> {
+    if !url_security::is_valid_salesforce_id(id) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_ID".to_string(),
+            message: "Invalid Salesforce ID format".to_string(),
+        }));
+    }
+    Ok(())
+}
+
+fn async_apex_job_soql(filter: &AsyncApexJobFilter) -> String {
+    let quoted = |values: Vec<&str>| {
+        values
+            .iter()
+            .map(|v| format!("'{}'", v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut conditions = Vec::new();
+    if !filter.statuses.is_empty() {
+        let statuses = filter.statuses.iter().map(|s| s.as_str()).collect();
+        conditions.push(format!("Status IN ({})", quoted(statuses)));
+    }
+    if !filter.job_types.is_empty() {
+        let job_types = filter.job_types.iter().map(|t| t.as_str()).collect();
+        conditions.push(format!("JobType IN ({})", quoted(job_types)));
+    }
+    if let Some(ref name) = filter.apex_class {
+        conditions.push(format!("ApexClass.Name = '{}'", soql::escape_string(name)));
+    }
+    let format_time = |time: &chrono::DateTime<chrono::Utc>| {
+        time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    };
+    if let Some(ref before) = filter.created_before {
+        conditions.push(format!("CreatedDate < {}", format_time(before)));
+    }
+    if let Some(ref after) = filter.created_after {
+        conditions.push(format!("CreatedDate > {}", format_time(after)));
+    }
+
+    let mut soql = format!("SELECT {} FROM AsyncApexJob", ASYNC_APEX_JOB_FIELDS);
+    if !conditions.is_empty() {
+        soql.push_str(" WHERE ");
+        soql.push_str(&conditions.join(" AND "));
+    }
+    soql.push_str(" ORDER BY CreatedDate DESC");
+    if let Some(limit) = filter.limit {
+        soql.push_str(&format!(" LIMIT {}", limit));
+    }
+    soql
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ToolingClient;
+    use super::*;
+
+    #[test]
+    fn test_async_apex_job_soql() {
+        let created = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let soql = async_apex_job_soql(
+            &AsyncApexJobFilter::active()
+                .job_types([AsyncApexJobType::Queueable])
+                .apex_class("O'Brien")
+                .created_before(created)
+                .limit(10),
+        );
+        assert!(soql.ends_with(
+            "FROM AsyncApexJob WHERE Status IN ('Holding', 'Queued', 'Preparing', 'Processing') \
+             AND JobType IN ('Queueable') AND ApexClass.Name = 'O\\'Brien' \
+             AND CreatedDate < 2024-01-01T00:00:00Z ORDER BY CreatedDate DESC LIMIT 10"
+        ));
+        assert!(!async_apex_job_soql(&AsyncApexJobFilter::new()).contains("WHERE"));
+    }
+
+    #[test]
+    fn test_async_apex_job_deser() {
+        let job: AsyncApexJob = serde_json::from_value(serde_json::json!({
+            "Id": "707000000000001AAA",
+            "JobType": "BatchApex",
+            "Status": "Processing",
+            "ApexClass": {"Name": "NightlyCleanup"},
+            "JobItemsProcessed": 3,
+            "TotalJobItems": 10,
+            "NumberOfErrors": 0
+        }))
+        .unwrap();
+        assert_eq!(job.job_type, AsyncApexJobType::BatchApex);
+        assert!(!job.status.is_terminal());
+        assert_eq!(
+            job.apex_class.and_then(|c| c.name).as_deref(),
+            Some("NightlyCleanup")
+        );
+
+        let job: AsyncApexJob = serde_json::from_value(serde_json::json!({
+            "Id": "707000000000001AAA",
+            "JobType": "SomethingNew",
+            "Status": "SomethingElse"
+        }))
+        .unwrap();
+        assert_eq!(job.job_type, AsyncApexJobType::Other);
+        assert_eq!(job.status, AsyncApexJobStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn test_abort_job_wiremock() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/executeAnonymous/"))
+            .and(query_param(
+                "anonymousBody",
+                "System.abortJob('707000000000001AAA');",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "compiled": true,
+                "success": true
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        client
+            .abort_job("707000000000001AAA")
+            .await
+            .expect("abort should succeed");
+        assert!(client.abort_job("'); delete x; //").await.is_err());
+    }
+}
@@ -0,0 +1,723 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use busbar_sf_client::security::{soql, url as url_security};
+use tracing::instrument;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::*;
+
+impl super::ToolingClient {
+    // =========================================================================
+    // Lightning Web Component Bundles
+    // =========================================================================
+
+    /// Get an LWC bundle and all of its files by developer name.
+    #[instrument(skip(self))]
+    pub async fn get_lwc_bundle(&self, name: &str) -> Result<Option<LwcBundle>> {
+        let soql = format!(
+            "SELECT Id, DeveloperName, MasterLabel, ApiVersion, Description, IsExposed, NamespacePrefix, LastModifiedDate FROM LightningComponentBundle WHERE DeveloperName = '{}'",
+            soql::escape_string(name)
+        );
+        let mut bundles: Vec<LightningComponentBundle> = self.query_all(&soql).await?;
+        let Some(bundle) = bundles.pop() else {
+            return Ok(None);
+        };
+        let soql = format!(
+            "SELECT Id, LightningComponentBundleId, FilePath, Format, Source, LastModifiedDate FROM LightningComponentResource WHERE LightningComponentBundleId = '{}'",
+            soql::escape_string(&bundle.id)
+        );
+        let resources = self.query_all(&soql).await?;
+        Ok(Some(LwcBundle { bundle, resources }))
+    }
+
+    /// Create an empty LWC bundle. Returns its ID.
+    #[instrument(skip(self))]
+    pub async fn create_lwc_bundle(&self, bundle: &NewLwcBundle) -> Result<String> {
+        let mut metadata = serde_json::json!({
+            "masterLabel": bundle.master_label.as_deref().unwrap_or(&bundle.developer_name),
+            "apiVersion": self.bundle_api_version(bundle.api_version),
+            "isExposed": bundle.is_exposed,
+        });
+        if let Some(description) = &bundle.description {
+            metadata["description"] = description.as_str().into();
+        }
+        self.create(
+            "LightningComponentBundle",
+            &serde_json::json!({
+                "FullName": bundle.developer_name,
+                "Metadata": metadata,
+            }),
+        )
+        .await
+    }
+
+    /// Add a file to an LWC bundle. Returns the new resource's ID.
+    ///
+    /// `file_path` is the path within the project, e.g.
+    /// `lwc/hello/hello.js`; the format is taken from its extension.
+    #[instrument(skip(self, source))]
+    pub async fn create_lwc_resource(
+        &self,
+        bundle_id: &str,
+        file_path: &str,
+        source: &str,
+    ) -> Result<String> {
+        validate_id(bundle_id)?;
+        self.create(
+            "LightningComponentResource",
+            &serde_json::json!({
+                "LightningComponentBundleId": bundle_id,
+                "FilePath": file_path,
+                "Format": lwc_resource_format(file_path),
+                "Source": source,
+            }),
+        )
+        .await
+    }
+
+    /// Replace the source of one LWC file.
+    #[instrument(skip(self, source))]
+    pub async fn update_lwc_resource(&self, id: &str, source: &str) -> Result<()> {
+        self.update(
+            "LightningComponentResource",
+            id,
+            &serde_json::json!({ "Source": source }),
+        )
+        .await
+    }
+
+    /// Write `files` (path, source) into an existing LWC bundle.
+    ///
+    /// Paths match a resource's `FilePath` or its file name alone. Files
+    /// whose source is unchanged are skipped. Every path must already
+    /// exist in the bundle; if any doesn't, nothing is written.
+    ///
+    /// Returns the `FilePath`s that were updated.
+    #[instrument(skip(self, files))]
+    pub async fn sync_lwc_bundle(&self, name: &str, files: &[(&str, &str)]) -> Result<Vec<String>> {
+        let bundle = self
+            .get_lwc_bundle(name)
+            .await?
+            .ok_or_else(|| bundle_not_found("LightningComponentBundle", name))?;
+        let updates = plan_updates(files, |path| {
+            bundle
+                .resource(path)
+                .map(|r| (r.file_path.as_str(), r.id.as_str(), r.source.as_deref()))
+        })?;
+        for (key, id, source) in &updates {
+            tracing::debug!(file = %key, "updating LWC resource");
+            self.update_lwc_resource(id, source).await?;
+        }
+        Ok(updates.into_iter().map(|(key, _, _)| key).collect())
+    }
+
+    /// Write `files` (path, source) into an LWC bundle, creating the bundle
+    /// and any files it doesn't have yet.
+    ///
+    /// Paths are matched as in [`sync_lwc_bundle`](Self::sync_lwc_bundle);
+    /// a new file given by name alone goes in `lwc/<bundle>/`. Unchanged
+    /// files are skipped.
+    #[instrument(skip(self, files))]
+    pub async fn push_lwc_bundle(
+        &self,
+        bundle: &NewLwcBundle,
+        files: &[(&str, &str)],
+    ) -> Result<BundlePush> {
+        let (existing, mut push) = match self.get_lwc_bundle(&bundle.developer_name).await? {
+            Some(existing) => {
+                let push = BundlePush {
+                    bundle_id: existing.bundle.id.clone(),
+                    ..Default::default()
+                };
+                (Some(existing), push)
+            }
+            None => {
+                let push = BundlePush {
+                    bundle_id: self.create_lwc_bundle(bundle).await?,
+                    bundle_created: true,
+                    ..Default::default()
+                };
+                (None, push)
+            }
+        };
+        for &(path, source) in files {
+            match existing.as_ref().and_then(|b| b.resource(path)) {
+                Some(r) if r.source.as_deref() == Some(source) => {}
+                Some(r) => {
+                    self.update_lwc_resource(&r.id, source).await?;
+                    push.updated.push(r.file_path.clone());
+                }
+                None => {
+                    let file_path = if path.contains('/') {
+                        path.to_string()
+                    } else {
+                        format!("lwc/{}/{}", bundle.developer_name, path)
+                    };
+                    self.create_lwc_resource(&push.bundle_id, &file_path, source)
+                        .await?;
+                    push.created.push(file_path);
+                }
+            }
+        }
+        Ok(push)
+    }
+
+    // =========================================================================
+    // Aura Bundles
+    // =========================================================================
+
+    /// Get an Aura bundle and all of its definitions by developer name.
+    #[instrument(skip(self))]
+    pub async fn get_aura_bundle(&self, name: &str) -> Result<Option<AuraBundle>> {
+        let soql = format!(
+            "SELECT Id, DeveloperName, MasterLabel, ApiVersion, Description, NamespacePrefix, LastModifiedDate FROM AuraDefinitionBundle WHERE DeveloperName = '{}'",
+            soql::escape_string(name)
+        );
+        let mut bundles: Vec<AuraDefinitionBundle> = self.query_all(&soql).await?;
+        let Some(bundle) = bundles.pop() else {
+            return Ok(None);
+        };
+        let soql = format!(
+            "SELECT Id, AuraDefinitionBundleId, DefType, Format, Source, LastModifiedDate FROM AuraDefinition WHERE AuraDefinitionBundleId = '{}'",
+            soql::escape_string(&bundle.id)
+        );
+        let definitions = self.query_all(&soql).await?;
+        Ok(Some(AuraBundle {
+            bundle,
+            definitions,
+        }))
+    }
+
+    /// Create an empty Aura bundle. Returns its ID.
+    #[instrument(skip(self))]
+    pub async fn create_aura_bundle(&self, bundle: &NewAuraBundle) -> Result<String> {
+        let mut record = serde_json::json!({
+            "DeveloperName": bundle.developer_name,
+            "MasterLabel": bundle.master_label.as_deref().unwrap_or(&bundle.developer_name),
+            "ApiVersion": self.bundle_api_version(bundle.api_version),
+        });
+        if let Some(description) = &bundle.description {
+            record["Description"] = description.as_str().into();
+        }
+        self.create("AuraDefinitionBundle", &record).await
+    }
+
+    /// Add a definition (e.g. `CONTROLLER`) to an Aura bundle. Returns the
+    /// new definition's ID.
+    #[instrument(skip(self, source))]
+    pub async fn create_aura_definition(
+        &self,
+        bundle_id: &str,
+        def_type: &str,
+        source: &str,
+    ) -> Result<String> {
+        validate_id(bundle_id)?;
+        let def_type = def_type.to_ascii_uppercase();
+        self.create(
+            "AuraDefinition",
+            &serde_json::json!({
+                "AuraDefinitionBundleId": bundle_id,
+                "Format": aura_definition_format(&def_type),
+                "DefType": def_type,
+                "Source": source,
+            }),
+        )
+        .await
+    }
+
+    /// Replace the source of one Aura definition.
+    #[instrument(skip(self, source))]
+    pub async fn update_aura_definition(&self, id: &str, source: &str) -> Result<()> {
+        self.update(
+            "AuraDefinition",
+            id,
+            &serde_json::json!({ "Source": source }),
+        )
+        .await
+    }
+
+    /// Write `definitions` (DefType, source) into an existing Aura bundle.
+    ///
+    /// Follows the same rules as [`sync_lwc_bundle`](Self::sync_lwc_bundle),
+    /// keyed by `DefType` (e.g. `CONTROLLER`) instead of file path.
+    ///
+    /// Returns the `DefType`s that were updated.
+    #[instrument(skip(self, definitions))]
+    pub async fn sync_aura_bundle(
+        &self,
+        name: &str,
+        definitions: &[(&str, &str)],
+    ) -> Result<Vec<String>> {
+        let bundle = self
+            .get_aura_bundle(name)
+            .await?
+            .ok_or_else(|| bundle_not_found("AuraDefinitionBundle", name))?;
+        let updates = plan_updates(definitions, |def_type| {
+            bundle
+                .definition(def_type)
+                .map(|d| (d.def_type.as_str(), d.id.as_str(), d.source.as_deref()))
+        })?;
+        for (key, id, source) in &updates {
+            tracing::debug!(def_type = %key, "updating Aura definition");
+            self.update_aura_definition(id, source).await?;
+        }
+        Ok(updates.into_iter().map(|(key, _, _)| key).collect())
+    }
+
+    /// Write `definitions` (DefType, source) into an Aura bundle, creating
+    /// the bundle and any definitions it doesn't have yet.
+    ///
+    /// Unchanged definitions are skipped.
+    #[instrument(skip(self, definitions))]
+    pub async fn push_aura_bundle(
+        &self,
+        bundle: &NewAuraBundle,
+        definitions: &[(&str, &str)],
+    ) -> Result<BundlePush> {
+        let (existing, mut push) = match self.get_aura_bundle(&bundle.developer_name).await? {
+            Some(existing) => {
+                let push = BundlePush {
+                    bundle_id: existing.bundle.id.clone(),
+                    ..Default::default()
+                };
+                (Some(existing), push)
+            }
+            None => {
+                let push = BundlePush {
+                    bundle_id: self.create_aura_bundle(bundle).await?,
+                    bundle_created: true,
+                    ..Default::default()
+                };
+                (None, push)
+            }
+        };
+        for &(def_type, source) in definitions {
+            match existing.as_ref().and_then(|b| b.definition(def_type)) {
+                Some(d) if d.source.as_deref() == Some(source) => {}
+                Some(d) => {
+                    self.update_aura_definition(&d.id, source).await?;
+                    push.updated.push(d.def_type.clone());
+                }
+                None => {
+                    self.create_aura_definition(&push.bundle_id, def_type, source)
+                        .await?;
+                    push.created.push(def_type.to_ascii_uppercase());
+                }
+            }
+        }
+        Ok(push)
+    }
+
+    // =========================================================================
+    // Static Resources
+    // =========================================================================
+
+    /// Get a static resource's record (without its body) by name.
+    #[instrument(skip(self))]
+    pub async fn get_static_resource(&self, name: &str) -> Result<Option<StaticResource>> {
+        let soql = format!(
+            "SELECT Id, Name, ContentType, CacheControl, BodyLength, Description, NamespacePrefix, LastModifiedDate FROM StaticResource WHERE Name = '{}'",
+            soql::escape_string(name)
+        );
+        let mut resources: Vec<StaticResource> = self.query_all(&soql).await?;
+        Ok(resources.pop())
+    }
+
+    /// Download a static resource's body.
+    ///
+    /// Multi-file resources come back as the zip archive they were uploaded as.
+    #[instrument(skip(self))]
+    pub async fn get_static_resource_body(&self, id: &str) -> Result<Vec<u8>> {
+        validate_id(id)?;
+        let url = self
+            .client
+            .tooling_url(&format!("sobjects/StaticResource/{}/Body", id));
+        let response = self.client.execute(self.client.get(&url)).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Replace a static resource's body, optionally changing its content type.
+    #[instrument(skip(self, body))]
+    pub async fn update_static_resource_body(
+        &self,
+        id: &str,
+        body: &[u8],
+        content_type: Option<&str>,
+    ) -> Result<()> {
+        let mut record = serde_json::json!({ "Body": STANDARD.encode(body) });
+        if let Some(content_type) = content_type {
+            record["ContentType"] = content_type.into();
+        }
+        self.update("StaticResource", id, &record).await
+    }
+
+    /// Create a static resource, or replace the body and settings of the
+    /// one with the same name. Returns its ID.
+    ///
+    /// Fails before uploading if the (possibly gzipped) body is over
+    /// Salesforce's 5 MB limit.
+    #[instrument(skip(self, upload), fields(name = %upload.name))]
+    pub async fn save_static_resource(&self, upload: &StaticResourceUpload) -> Result<String> {
+        let (body, content_type) = if upload.gzip {
+            (gzip(&upload.body)?, "application/gzip")
+        } else {
+            (upload.body.clone(), upload.content_type.as_str())
+        };
+        if body.len() > MAX_STATIC_RESOURCE_BYTES {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "STATIC_RESOURCE_TOO_LARGE".to_string(),
+                message: format!(
+                    "{} is {} bytes; static resources are limited to {} bytes",
+                    upload.name,
+                    body.len(),
+                    MAX_STATIC_RESOURCE_BYTES
+                ),
+            }));
+        }
+        let mut record = serde_json::json!({
+            "Body": STANDARD.encode(&body),
+            "ContentType": content_type,
+            "CacheControl": upload.cache_control.as_str(),
+        });
+        if let Some(description) = &upload.description {
+            record["Description"] = description.as_str().into();
+        }
+
+        match self.get_static_resource(&upload.name).await? {
+            Some(existing) => {
+                self.update("StaticResource", &existing.id, &record).await?;
+                Ok(existing.id)
+            }
+            None => {
+                record["Name"] = upload.name.as_str().into();
+                self.create("StaticResource", &record).await
+            }
+        }
+    }
+}
+
+/// Largest static resource body Salesforce accepts.
+const MAX_STATIC_RESOURCE_BYTES: usize = 5 * 1024 * 1024;
+
+fn gzip(body: &[u8]) -> Result<Vec<u8>> {
+    use std::io::Write as _;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder
+        .write_all(body)
+        .and_then(|()| encoder.finish())
+        .map_err(|e| Error {
+            kind: ErrorKind::Other(format!("Failed to gzip static resource: {}", e)),
+            source: Some(Box::new(e)),
+        })
+}
+
+impl super::ToolingClient {
+    fn bundle_api_version(&self, version: Option<f64>) -> f64 {
+        version.unwrap_or_else(|| self.client.api_version().parse().unwrap_or(62.0))
+    }
+}
+
+/// The `Format` of an LWC file, from its extension.
+fn lwc_resource_format(file_path: &str) -> &str {
+    file_path.rsplit('.').next().unwrap_or("js")
+}
+
+/// The `Format` Salesforce expects for an Aura `DefType`.
+fn aura_definition_format(def_type: &str) -> &'static str {
+    match def_type {
+        "CONTROLLER" | "HELPER" | "RENDERER" | "PROVIDER" | "MODEL" => "JS",
+        "STYLE" => "CSS",
+        "SVG" => "SVG",
+        _ => "XML",
+    }
+}
+
+fn validate_id(id: &str) -> Result<()> {
+    if !url_security::is_valid_salesforce_id(id) {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "INVALID_ID".to_string(),
+            message: "Invalid Salesforce ID format".to_string(),
+        }));
+    }
+    Ok(())
+}
+
+fn bundle_not_found(sobject: &str, name: &str) -> Error {
+    Error::new(ErrorKind::Salesforce {
+        error_code: "BUNDLE_NOT_FOUND".to_string(),
+        message: format!("{} '{}' not found", sobject, name),
+    })
+}
+
+/// Match each `(key, source)` to an existing bundle member and keep the
+/// ones whose source changed, as `(member key, member id, new source)`.
+fn plan_updates<'a, 'b>(
+    files: &[(&str, &'a str)],
+    lookup: impl Fn(&str) -> Option<(&'b str, &'b str, Option<&'b str>)>,
+) -> Result<Vec<(String, String, &'a str)>> {
+    let mut updates = Vec::new();
+    let mut missing = Vec::new();
+    for &(key, source) in files {
+        match lookup(key) {
+            None => missing.push(key),
+            Some((_, _, Some(current))) if current == source => {}
+            Some((member, id, _)) => updates.push((member.to_string(), id.to_string(), source)),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(Error::new(ErrorKind::Salesforce {
+            error_code: "BUNDLE_MEMBER_NOT_FOUND".to_string(),
+            message: format!("not in bundle: {}", missing.join(", ")),
+        }));
+    }
+    Ok(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ToolingClient;
+    use super::*;
+
+    fn resource(id: &str, path: &str, source: &str) -> LightningComponentResource {
+        LightningComponentResource {
+            id: id.to_string(),
+            bundle_id: "0Rb000000000001AAA".to_string(),
+            file_path: path.to_string(),
+            format: None,
+            source: Some(source.to_string()),
+            last_modified_date: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_updates_skips_unchanged_and_rejects_missing() {
+        let bundle = LwcBundle {
+            bundle: serde_json::from_value(
+                serde_json::json!({"Id": "0Rb000000000001AAA", "DeveloperName": "hello"}),
+            )
+            .unwrap(),
+            resources: vec![
+                resource("0Rd000000000001AAA", "lwc/hello/hello.js", "old js"),
+                resource("0Rd000000000002AAA", "lwc/hello/hello.html", "<template/>"),
+            ],
+        };
+        let lookup = |path: &str| {
+            bundle
+                .resource(path)
+                .map(|r| (r.file_path.as_str(), r.id.as_str(), r.source.as_deref()))
+        };
+
+        let updates = plan_updates(
+            &[("hello.js", "new js"), ("hello.html", "<template/>")],
+            lookup,
+        )
+        .unwrap();
+        assert_eq!(
+            updates,
+            vec![(
+                "lwc/hello/hello.js".to_string(),
+                "0Rd000000000001AAA".to_string(),
+                "new js"
+            )]
+        );
+
+        let err = plan_updates(&[("hello.js", "x"), ("hello.css", "y")], lookup).unwrap_err();
+        assert!(err.to_string().contains("hello.css"));
+    }
+
+    #[test]
+    fn test_bundle_member_formats() {
+        assert_eq!(lwc_resource_format("lwc/hello/hello.html"), "html");
+        assert_eq!(lwc_resource_format("lwc/hello/hello.js-meta.xml"), "xml");
+        assert_eq!(aura_definition_format("HELPER"), "JS");
+        assert_eq!(aura_definition_format("STYLE"), "CSS");
+        assert_eq!(aura_definition_format("COMPONENT"), "XML");
+    }
+
+    #[tokio::test]
+    async fn test_push_lwc_bundle_creates_bundle_and_files_wiremock() {
+        use wiremock::matchers::{body_json, method, path, path_regex, query_param_contains};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let sobjects = "/services/data/v62.0/tooling/sobjects";
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/tooling/query/?"))
+            .and(query_param_contains("q", "FROM LightningComponentBundle"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 0,
+                "done": true,
+                "records": []
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/LightningComponentBundle", sobjects)))
+            .and(body_json(serde_json::json!({
+                "FullName": "hello",
+                "Metadata": {"masterLabel": "Hello", "apiVersion": 62.0, "isExposed": true}
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "0Rb000000000001AAA", "success": true, "errors": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/LightningComponentResource", sobjects)))
+            .and(body_json(serde_json::json!({
+                "LightningComponentBundleId": "0Rb000000000001AAA",
+                "FilePath": "lwc/hello/hello.js",
+                "Format": "js",
+                "Source": "export default class Hello {}"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "0Rd000000000001AAA", "success": true, "errors": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let push = client
+            .push_lwc_bundle(
+                &NewLwcBundle::new("hello")
+                    .master_label("Hello")
+                    .exposed(true),
+                &[("hello.js", "export default class Hello {}")],
+            )
+            .await
+            .expect("push should succeed");
+        assert!(push.bundle_created);
+        assert_eq!(push.bundle_id, "0Rb000000000001AAA");
+        assert_eq!(push.created, vec!["lwc/hello/hello.js".to_string()]);
+        assert!(push.updated.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_save_static_resource_gzip_wiremock() {
+        use std::io::Read as _;
+        use wiremock::matchers::{body_partial_json, method, path, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/tooling/query/?"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 0,
+                "done": true,
+                "records": []
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/tooling/sobjects/StaticResource"))
+            .and(body_partial_json(serde_json::json!({
+                "Name": "countries",
+                "ContentType": "application/gzip",
+                "CacheControl": "Public"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "081000000000001AAA", "success": true, "errors": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let data = "[\"Andorra\",\"Angola\"]".repeat(100);
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let id = client
+            .save_static_resource(
+                &StaticResourceUpload::new("countries", data.as_bytes(), "application/json")
+                    .cache_control(CacheControl::Public)
+                    .gzip(true),
+            )
+            .await
+            .expect("save should succeed");
+        assert_eq!(id, "081000000000001AAA");
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let post = requests
+            .iter()
+            .find(|r| r.method.as_str() == "POST")
+            .unwrap();
+        let record: serde_json::Value = serde_json::from_slice(&post.body).unwrap();
+        let body = STANDARD.decode(record["Body"].as_str().unwrap()).unwrap();
+        assert!(body.len() < data.len());
+        let mut inflated = String::new();
+        flate2::read::GzDecoder::new(body.as_slice())
+            .read_to_string(&mut inflated)
+            .unwrap();
+        assert_eq!(inflated, data);
+
+        let too_big = StaticResourceUpload::new(
+            "huge",
+            vec![0u8; MAX_STATIC_RESOURCE_BYTES + 1],
+            "application/octet-stream",
+        );
+        assert!(client.save_static_resource(&too_big).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sync_lwc_bundle_wiremock() {
+        use wiremock::matchers::{body_json, method, path, path_regex, query_param_contains};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/tooling/query/?"))
+            .and(query_param_contains("q", "FROM LightningComponentBundle"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [{"Id": "0Rb000000000001AAA", "DeveloperName": "hello"}]
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(".*/tooling/query/?"))
+            .and(query_param_contains("q", "FROM LightningComponentResource"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 2,
+                "done": true,
+                "records": [
+                    {
+                        "Id": "0Rd000000000001AAA",
+                        "LightningComponentBundleId": "0Rb000000000001AAA",
+                        "FilePath": "lwc/hello/hello.js",
+                        "Source": "old js"
+                    },
+                    {
+                        "Id": "0Rd000000000002AAA",
+                        "LightningComponentBundleId": "0Rb000000000001AAA",
+                        "FilePath": "lwc/hello/hello.html",
+                        "Source": "<template/>"
+                    }
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path(
+                "/services/data/v62.0/tooling/sobjects/LightningComponentResource/0Rd000000000001AAA",
+            ))
+            .and(body_json(serde_json::json!({"Source": "new js"})))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let updated = client
+            .sync_lwc_bundle(
+                "hello",
+                &[("hello.js", "new js"), ("hello.html", "<template/>")],
+            )
+            .await
+            .expect("sync should succeed");
+        assert_eq!(updated, vec!["lwc/hello/hello.js".to_string()]);
+    }
+}
@@ -1,6 +1,14 @@
+use busbar_sf_client::security::{soql, url as url_security};
 use tracing::instrument;
 
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::{ApexCompletions, SymbolTable};
+
+#[derive(Clone, serde::Deserialize)]
+struct SymbolTableRecord {
+    #[serde(rename = "SymbolTable")]
+    symbol_table: Option<SymbolTable>,
+}
 
 impl super::ToolingClient {
     /// Get code completions for Apex system symbols.
@@ -28,6 +36,54 @@ impl super::ToolingClient {
         let url = self.client.tooling_url("completions?type=visualforce");
         self.client.get_json(&url).await.map_err(Into::into)
     }
+
+    /// Get Apex completions as typed declarations, keyed by namespace and
+    /// type name.
+    ///
+    /// The response is large (every system type in the org's API version),
+    /// so callers serving completions should fetch it once and keep it.
+    #[instrument(skip(self))]
+    pub async fn apex_completions(&self) -> Result<ApexCompletions> {
+        let url = self.client.tooling_url("completions?type=apex");
+        self.client.get_json(&url).await.map_err(Into::into)
+    }
+
+    /// Get the symbol table of an Apex class by ID.
+    ///
+    /// Returns `None` if the class doesn't exist or has no symbol table
+    /// because it doesn't compile.
+    #[instrument(skip(self))]
+    pub async fn get_apex_class_symbol_table(&self, id: &str) -> Result<Option<SymbolTable>> {
+        if !url_security::is_valid_salesforce_id(id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        // SymbolTable can only be selected for one class per query.
+        let soql = format!("SELECT SymbolTable FROM ApexClass WHERE Id = '{}'", id);
+        self.symbol_table(&soql).await
+    }
+
+    /// Get the symbol table of an Apex class by name.
+    ///
+    /// Returns `None` if the class doesn't exist or doesn't compile.
+    #[instrument(skip(self))]
+    pub async fn get_apex_class_symbol_table_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<SymbolTable>> {
+        let soql = format!(
+            "SELECT SymbolTable FROM ApexClass WHERE Name = '{}' LIMIT 1",
+            soql::escape_string(name)
+        );
+        self.symbol_table(&soql).await
+    }
+
+    async fn symbol_table(&self, soql: &str) -> Result<Option<SymbolTable>> {
+        let mut records: Vec<SymbolTableRecord> = self.query_all(soql).await?;
+        Ok(records.pop().and_then(|r| r.symbol_table))
+    }
 }
 
 #[cfg(test)]
@@ -106,4 +162,133 @@ mod tests {
             .expect("should have publicDeclarations object");
         assert!(pd.contains_key("apex"));
     }
+
+    #[tokio::test]
+    async fn test_apex_completions_typed_wiremock() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/completions"))
+            .and(query_param("type", "apex"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "publicDeclarations": {
+                    "System": {
+                        "String": {
+                            "constructors": [],
+                            "methods": [{
+                                "argTypes": ["String", "String"],
+                                "isStatic": true,
+                                "methodDoc": null,
+                                "name": "join",
+                                "parameters": [
+                                    {"name": "iterableObj", "type": "Object"},
+                                    {"name": "separator", "type": "String"}
+                                ],
+                                "references": [],
+                                "returnType": "String"
+                            }],
+                            "properties": null
+                        }
+                    }
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let completions = client.apex_completions().await.unwrap();
+        let string = completions
+            .type_declaration("system", "string")
+            .expect("System.String should be declared");
+        assert_eq!(string.methods[0].name, "join");
+        assert_eq!(string.methods[0].arg_types, ["String", "String"]);
+        assert_eq!(string.methods[0].return_type.as_deref(), Some("String"));
+        assert!(string.properties.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_apex_class_symbol_table_by_name_wiremock() {
+        use wiremock::matchers::query_param_contains;
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/query"))
+            .and(query_param_contains(
+                "q",
+                "SELECT SymbolTable FROM ApexClass WHERE Name = 'Greeter'",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [{
+                    "attributes": {"type": "ApexClass"},
+                    "SymbolTable": {
+                        "constructors": [],
+                        "externalReferences": [{
+                            "name": "System",
+                            "namespace": "System",
+                            "references": [],
+                            "methods": [{
+                                "argTypes": ["Object"],
+                                "isStatic": true,
+                                "name": "debug",
+                                "references": [{"line": 3, "column": 16}],
+                                "returnType": "void"
+                            }],
+                            "variables": []
+                        }],
+                        "id": "01p000000000001AAA",
+                        "innerClasses": [],
+                        "interfaces": [],
+                        "key": "01p000000000001AAA",
+                        "methods": [{
+                            "annotations": [{"name": "AuraEnabled"}],
+                            "location": {"line": 2, "column": 26},
+                            "modifiers": ["public", "static"],
+                            "name": "greet",
+                            "parameters": [{"name": "who", "type": "String"}],
+                            "references": [],
+                            "returnType": "String",
+                            "type": null
+                        }],
+                        "name": "Greeter",
+                        "namespace": null,
+                        "parentClass": "",
+                        "properties": [],
+                        "tableDeclaration": {
+                            "annotations": [],
+                            "location": {"line": 1, "column": 14},
+                            "modifiers": ["public"],
+                            "name": "Greeter",
+                            "references": [],
+                            "type": "Greeter"
+                        },
+                        "variables": null
+                    }
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let table = client
+            .get_apex_class_symbol_table_by_name("Greeter")
+            .await
+            .unwrap()
+            .expect("symbol table");
+        assert_eq!(table.name, "Greeter");
+        let greet = table.methods_named("GREET").next().expect("greet method");
+        assert_eq!(greet.location.map(|l| l.line), Some(2));
+        assert_eq!(greet.annotations[0].name, "AuraEnabled");
+        assert_eq!(greet.parameters[0].param_type.as_deref(), Some("String"));
+        assert_eq!(
+            table.external_references[0].methods[0].references[0].column,
+            16
+        );
+        assert!(client
+            .get_apex_class_symbol_table("not-an-id")
+            .await
+            .is_err());
+    }
 }
@@ -25,6 +25,7 @@ impl super::ToolingClient {
     ///             url: "/services/data/v62.0/tooling/sobjects/ApexClass/01p...".to_string(),
     ///             reference_id: "refApexClass".to_string(),
     ///             body: None,
+    ///             http_headers: Default::default(),
     ///         },
     ///     ],
     /// };
@@ -131,4 +132,71 @@ mod tests {
             "https://na1.salesforce.com/services/data/v62.0/tooling/composite/tree/ApexClass"
         );
     }
+
+    #[tokio::test]
+    async fn test_composite_wiremock() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/tooling/composite"))
+            .and(body_partial_json(serde_json::json!({
+                "allOrNone": true,
+                "compositeRequest": [
+                    {"referenceId": "container"},
+                    {"referenceId": "member", "body": {"MetadataContainerId": "@{container.id}"}}
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "compositeResponse": [
+                    {
+                        "body": {"id": "1dc000000000001AAA", "success": true, "errors": []},
+                        "httpHeaders": {},
+                        "httpStatusCode": 201,
+                        "referenceId": "container"
+                    },
+                    {
+                        "body": {"id": "400000000000001AAA", "success": true, "errors": []},
+                        "httpHeaders": {},
+                        "httpStatusCode": 201,
+                        "referenceId": "member"
+                    }
+                ]
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let subrequest =
+            |url: &str, reference_id: &str, body| busbar_sf_rest::CompositeSubrequest {
+                method: "POST".to_string(),
+                url: format!("/services/data/v62.0/tooling/sobjects/{url}"),
+                reference_id: reference_id.to_string(),
+                body: Some(body),
+                http_headers: Default::default(),
+            };
+        let response = client
+            .composite(&busbar_sf_rest::CompositeRequest {
+                all_or_none: true,
+                collate_subrequests: false,
+                subrequests: vec![
+                    subrequest(
+                        "MetadataContainer",
+                        "container",
+                        serde_json::json!({"Name": "HotFix"}),
+                    ),
+                    subrequest(
+                        "ApexClassMember",
+                        "member",
+                        serde_json::json!({"MetadataContainerId": "@{container.id}"}),
+                    ),
+                ],
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.responses.len(), 2);
+        assert_eq!(response.responses[1].http_status_code, 201);
+    }
 }
@@ -1,7 +1,8 @@
+use busbar_sf_client::security::url as url_security;
 use tracing::instrument;
 
-use crate::error::Result;
-use crate::types::ApexCodeCoverageAggregate;
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::{ApexCodeCoverage, ApexCodeCoverageAggregate, CoverageReport};
 
 impl super::ToolingClient {
     /// Get code coverage for all Apex classes and triggers.
@@ -12,24 +13,134 @@ impl super::ToolingClient {
         ).await
     }
 
+    /// Get the coverage each test method gave, optionally only for tests in
+    /// the given test classes.
+    ///
+    /// Salesforce keeps these records from the most recent test runs; pass
+    /// them to [`CoverageReport::from_coverage`] to combine them per class.
+    #[instrument(skip(self))]
+    pub async fn get_test_code_coverage(
+        &self,
+        test_class_ids: &[&str],
+    ) -> Result<Vec<ApexCodeCoverage>> {
+        let mut soql = "SELECT Id, ApexClassOrTriggerId, ApexClassOrTrigger.Name, ApexTestClassId, TestMethodName, NumLinesCovered, NumLinesUncovered, Coverage FROM ApexCodeCoverage".to_string();
+        if !test_class_ids.is_empty() {
+            if !test_class_ids
+                .iter()
+                .all(|id| url_security::is_valid_salesforce_id(id))
+            {
+                return Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "INVALID_ID".to_string(),
+                    message: "Invalid Salesforce ID format".to_string(),
+                }));
+            }
+            let ids: Vec<String> = test_class_ids
+                .iter()
+                .map(|id| format!("'{}'", id))
+                .collect();
+            soql.push_str(&format!(" WHERE ApexTestClassId IN ({})", ids.join(", ")));
+        }
+        self.query_all(&soql).await
+    }
+
+    /// Get per-class and org-wide coverage from the aggregate records.
+    #[instrument(skip(self))]
+    pub async fn get_coverage_report(&self) -> Result<CoverageReport> {
+        let coverage = self.get_code_coverage().await?;
+        Ok(CoverageReport::from_aggregates(&coverage))
+    }
+
     /// Get overall org-wide code coverage percentage.
     #[instrument(skip(self))]
     pub async fn get_org_wide_coverage(&self) -> Result<f64> {
-        let coverage = self.get_code_coverage().await?;
+        Ok(self.get_coverage_report().await?.percent())
+    }
+}
 
-        let mut total_covered = 0i64;
-        let mut total_uncovered = 0i64;
+#[cfg(test)]
+mod tests {
+    use super::super::ToolingClient;
+    use crate::types::CoverageReport;
 
-        for item in coverage {
-            total_covered += item.num_lines_covered as i64;
-            total_uncovered += item.num_lines_uncovered as i64;
-        }
+    #[tokio::test]
+    async fn test_get_coverage_report_wiremock() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
 
-        let total_lines = total_covered + total_uncovered;
-        if total_lines == 0 {
-            return Ok(0.0);
-        }
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 2,
+                "done": true,
+                "records": [
+                    {
+                        "Id": "715000000000001AAA",
+                        "ApexClassOrTriggerId": "01q000000000001AAA",
+                        "ApexClassOrTrigger": {"Name": "AccountTrigger"},
+                        "NumLinesCovered": 1,
+                        "NumLinesUncovered": 3,
+                        "Coverage": {"coveredLines": [2], "uncoveredLines": [3, 4, 5]}
+                    },
+                    {
+                        "Id": "715000000000002AAA",
+                        "ApexClassOrTriggerId": "01p000000000001AAA",
+                        "ApexClassOrTrigger": {"Name": "AccountService"},
+                        "NumLinesCovered": 5,
+                        "NumLinesUncovered": 0
+                    }
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let report = client.get_coverage_report().await.unwrap();
+
+        assert_eq!(report.classes[0].name.as_deref(), Some("AccountService"));
+        assert_eq!(report.percent(), 6.0 / 9.0 * 100.0);
+        let below: Vec<_> = report.below(75.0).map(|c| c.id.as_str()).collect();
+        assert_eq!(below, ["01q000000000001AAA"]);
+        assert_eq!(
+            report.to_lcov(),
+            "TN:\nSF:classes/AccountService.cls\nLF:5\nLH:5\nend_of_record\n\
+             TN:\nSF:triggers/AccountTrigger.trigger\nDA:2,1\nDA:3,0\nDA:4,0\nDA:5,0\n\
+             LF:4\nLH:1\nend_of_record\n"
+        );
+    }
+
+    #[test]
+    fn test_coverage_report_from_coverage_merges_tests() {
+        let records: Vec<crate::types::ApexCodeCoverage> =
+            serde_json::from_value(serde_json::json!([
+                {
+                    "Id": "714000000000001AAA",
+                    "ApexClassOrTriggerId": "01p000000000001AAA",
+                    "ApexClassOrTrigger": {"Name": "AccountService"},
+                    "TestMethodName": "createsAccount",
+                    "NumLinesCovered": 2,
+                    "NumLinesUncovered": 2,
+                    "Coverage": {"coveredLines": [1, 2], "uncoveredLines": [3, 4]}
+                },
+                {
+                    "Id": "714000000000002AAA",
+                    "ApexClassOrTriggerId": "01p000000000001AAA",
+                    "ApexClassOrTrigger": {"Name": "AccountService"},
+                    "TestMethodName": "rejectsDuplicate",
+                    "NumLinesCovered": 2,
+                    "NumLinesUncovered": 2,
+                    "Coverage": {"coveredLines": [1, 3], "uncoveredLines": [2, 4]}
+                }
+            ]))
+            .unwrap();
 
-        Ok((total_covered as f64 / total_lines as f64) * 100.0)
+        let report = CoverageReport::from_coverage(&records);
+        assert_eq!(report.classes.len(), 1);
+        let class = &report.classes[0];
+        assert_eq!(class.covered_lines, [1, 2, 3]);
+        assert_eq!(class.uncovered_lines, [4]);
+        assert_eq!(class.percent(), 75.0);
+        assert_eq!(report.below(75.0).count(), 0);
     }
 }
@@ -1,7 +1,16 @@
+use std::time::Duration;
+
 use tracing::instrument;
 
 use crate::error::{Error, ErrorKind, Result};
-use crate::types::ExecuteAnonymousResult;
+use crate::types::{ApexLogFilter, ExecuteAnonymousResult, ExecuteAnonymousWithLog};
+
+/// How long the trace flag set by `execute_anonymous_with_log` lasts if it
+/// can't be deleted afterwards.
+const LOG_CAPTURE_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// Times to look for the execution's log, a second apart, before giving up.
+const LOG_LOOKUPS: u32 = 3;
 
 impl super::ToolingClient {
     /// Execute anonymous Apex code.
@@ -18,15 +27,7 @@ impl super::ToolingClient {
     /// ```
     #[instrument(skip(self))]
     pub async fn execute_anonymous(&self, apex_code: &str) -> Result<ExecuteAnonymousResult> {
-        let encoded = urlencoding::encode(apex_code);
-        let url = format!(
-            "{}/services/data/v{}/tooling/executeAnonymous/?anonymousBody={}",
-            self.client.instance_url(),
-            self.client.api_version(),
-            encoded
-        );
-
-        let result: ExecuteAnonymousResult = self.client.get_json(&url).await?;
+        let result = self.execute_anonymous_unchecked(apex_code).await?;
 
         // Check for compilation or execution errors
         if !result.compiled {
@@ -43,4 +44,224 @@ impl super::ToolingClient {
 
         Ok(result)
     }
+
+    /// Execute anonymous Apex and return its debug log with the result.
+    ///
+    /// Unlike [`execute_anonymous`](Self::execute_anonymous), compile and
+    /// runtime failures are returned in the result rather than as errors,
+    /// so their log isn't lost. Unless the running user is already being
+    /// traced, a temporary `USER_DEBUG` trace flag is set for the call (see
+    /// [`capture_logs_for`](Self::capture_logs_for)) and deleted after.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let run = client.execute_anonymous_with_log("Integer i = 1 / 0;").await?;
+    /// if !run.result.success {
+    ///     eprintln!("{}", run.log.unwrap_or_default());
+    /// }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn execute_anonymous_with_log(
+        &self,
+        apex_code: &str,
+    ) -> Result<ExecuteAnonymousWithLog> {
+        let user_id = self.current_user_id().await?;
+        let now = chrono::Utc::now();
+        let traced = self.get_trace_flags().await?.into_iter().any(|flag| {
+            flag.traced_entity_id == user_id
+                && flag.log_type == "USER_DEBUG"
+                && flag
+                    .expiration_date
+                    .as_deref()
+                    .and_then(parse_datetime)
+                    .is_some_and(|expires| expires > now)
+        });
+        let capture = if traced {
+            None
+        } else {
+            Some(self.capture_logs_for(&user_id, LOG_CAPTURE_WINDOW).await?)
+        };
+        let started_at = capture.as_ref().map_or(now, |c| c.started_at());
+
+        let outcome = self
+            .execute_and_fetch_log(apex_code, &user_id, started_at)
+            .await;
+        let finished = match capture {
+            Some(capture) => capture.finish().await,
+            None => Ok(()),
+        };
+        let run = outcome?;
+        finished?;
+        Ok(run)
+    }
+
+    async fn execute_and_fetch_log(
+        &self,
+        apex_code: &str,
+        user_id: &str,
+        started_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<ExecuteAnonymousWithLog> {
+        let result = self.execute_anonymous_unchecked(apex_code).await?;
+
+        let filter = ApexLogFilter::new()
+            .user(user_id)
+            .started_after(started_at)
+            .limit(1);
+        let mut log_id = None;
+        for lookup in 1..=LOG_LOOKUPS {
+            log_id = self
+                .list_apex_logs(&filter)
+                .await?
+                .into_iter()
+                .next()
+                .map(|log| log.id);
+            if log_id.is_some() || lookup == LOG_LOOKUPS {
+                break;
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        let log = match log_id {
+            Some(ref id) => Some(self.get_apex_log_body(id).await?),
+            None => None,
+        };
+        Ok(ExecuteAnonymousWithLog {
+            result,
+            log_id,
+            log,
+        })
+    }
+
+    async fn execute_anonymous_unchecked(&self, apex_code: &str) -> Result<ExecuteAnonymousResult> {
+        let encoded = urlencoding::encode(apex_code);
+        let url = format!(
+            "{}/services/data/v{}/tooling/executeAnonymous/?anonymousBody={}",
+            self.client.instance_url(),
+            self.client.api_version(),
+            encoded
+        );
+
+        self.client.get_json(&url).await.map_err(Into::into)
+    }
+
+    /// The ID of the user the access token belongs to.
+    async fn current_user_id(&self) -> Result<String> {
+        let info: serde_json::Value = self
+            .client
+            .get_json(&self.client.url("/services/oauth2/userinfo"))
+            .await?;
+        info.get("user_id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Other(
+                    "userinfo response has no user_id".to_string(),
+                ))
+            })
+    }
+}
+
+/// Parse a Salesforce datetime, e.g. `2024-01-01T00:00:00.000+0000`.
+fn parse_datetime(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .or_else(|_| chrono::DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ToolingClient;
+    use wiremock::matchers::{method, path, query_param_contains};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_query(server: &MockServer, from: &str, records: serde_json::Value) {
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/query"))
+            .and(query_param_contains("q", from))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": records.as_array().map_or(0, Vec::len),
+                "done": true,
+                "records": records
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_execute_anonymous_with_log_wiremock() {
+        let server = MockServer::start().await;
+        let user_id = "005000000000001AAA";
+
+        Mock::given(method("GET"))
+            .and(path("/services/oauth2/userinfo"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "user_id": user_id })),
+            )
+            .mount(&server)
+            .await;
+        mock_query(&server, "FROM TraceFlag", serde_json::json!([])).await;
+        mock_query(
+            &server,
+            "FROM DebugLevel",
+            serde_json::json!([{
+                "Id": "7dl000000000001AAA",
+                "DeveloperName": "Busbar_Log_Capture",
+                "MasterLabel": "Busbar_Log_Capture"
+            }]),
+        )
+        .await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/tooling/sobjects/TraceFlag"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "7tf000000000001AAA",
+                "success": true,
+                "errors": []
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/executeAnonymous/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "compiled": true,
+                "success": false,
+                "exceptionMessage": "System.MathException: Divide by 0",
+                "line": 1,
+                "column": 1
+            })))
+            .mount(&server)
+            .await;
+        mock_query(
+            &server,
+            "FROM ApexLog",
+            serde_json::json!([{ "Id": "07L000000000001AAA" }]),
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/services/data/v62.0/tooling/sobjects/ApexLog/07L000000000001AAA/Body",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string("FATAL_ERROR Divide by 0"))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path(
+                "/services/data/v62.0/tooling/sobjects/TraceFlag/7tf000000000001AAA",
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ToolingClient::new(server.uri(), "test-token").unwrap();
+        let run = client
+            .execute_anonymous_with_log("Integer i = 1 / 0;")
+            .await
+            .expect("a runtime failure is still a result");
+        assert!(!run.result.success);
+        assert_eq!(run.log_id.as_deref(), Some("07L000000000001AAA"));
+        assert_eq!(run.log.as_deref(), Some("FATAL_ERROR Divide by 0"));
+        server.verify().await;
+    }
 }
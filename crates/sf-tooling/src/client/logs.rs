@@ -1,8 +1,12 @@
-use busbar_sf_client::security::url as url_security;
+use busbar_sf_client::security::{soql, url as url_security};
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
 use tracing::instrument;
 
 use crate::error::{Error, ErrorKind, Result};
-use crate::types::ApexLog;
+use crate::types::{ApexLog, ApexLogFilter};
+
+const APEX_LOG_FIELDS: &str = "Id, LogUserId, LogUser.Name, LogLength, LastModifiedDate, StartTime, Status, Operation, Request, Application, DurationMilliseconds, Location";
 
 impl super::ToolingClient {
     /// Get recent Apex logs.
@@ -13,12 +17,19 @@ impl super::ToolingClient {
     pub async fn get_apex_logs(&self, limit: Option<u32>) -> Result<Vec<ApexLog>> {
         let limit = limit.unwrap_or(20);
         let soql = format!(
-            "SELECT Id, LogUserId, LogUser.Name, LogLength, LastModifiedDate, StartTime, Status, Operation, Request, Application, DurationMilliseconds, Location FROM ApexLog ORDER BY LastModifiedDate DESC LIMIT {}",
-            limit
+            "SELECT {} FROM ApexLog ORDER BY LastModifiedDate DESC LIMIT {}",
+            APEX_LOG_FIELDS, limit
         );
         self.query_all(&soql).await
     }
 
+    /// List Apex logs matching `filter`, newest first.
+    #[instrument(skip(self))]
+    pub async fn list_apex_logs(&self, filter: &ApexLogFilter) -> Result<Vec<ApexLog>> {
+        let soql = apex_log_soql(filter)?;
+        self.query_all(&soql).await
+    }
+
     /// Get the body of a specific Apex log.
     #[instrument(skip(self))]
     pub async fn get_apex_log_body(&self, log_id: &str) -> Result<String> {
@@ -40,6 +51,32 @@ impl super::ToolingClient {
         response.text().await.map_err(Into::into)
     }
 
+    /// Stream the body of an Apex log as it downloads.
+    ///
+    /// Logs can run to 20 MB; this avoids holding a whole one in memory.
+    #[instrument(skip(self))]
+    pub async fn get_apex_log_body_stream(
+        &self,
+        log_id: &str,
+    ) -> Result<impl Stream<Item = Result<Bytes>> + Send> {
+        if !url_security::is_valid_salesforce_id(log_id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        let url = format!(
+            "{}/services/data/v{}/tooling/sobjects/ApexLog/{}/Body",
+            self.client.instance_url(),
+            self.client.api_version(),
+            log_id
+        );
+
+        let request = self.client.get(&url);
+        let response = self.client.execute(request).await?;
+        Ok(response.bytes_stream().map_err(Error::from))
+    }
+
     /// Delete an Apex log.
     #[instrument(skip(self))]
     pub async fn delete_apex_log(&self, log_id: &str) -> Result<()> {
@@ -82,3 +119,62 @@ impl super::ToolingClient {
         Ok(count)
     }
 }
+
+fn apex_log_soql(filter: &ApexLogFilter) -> Result<String> {
+    let mut conditions = Vec::new();
+    if let Some(ref user_id) = filter.user_id {
+        if !url_security::is_valid_salesforce_id(user_id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        conditions.push(format!("LogUserId = '{}'", user_id));
+    }
+    if let Some(ref operation) = filter.operation {
+        conditions.push(format!("Operation = '{}'", soql::escape_string(operation)));
+    }
+    if let Some(ref after) = filter.started_after {
+        conditions.push(format!(
+            "StartTime > {}",
+            after.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        ));
+    }
+
+    let mut soql = format!("SELECT {} FROM ApexLog", APEX_LOG_FIELDS);
+    if !conditions.is_empty() {
+        soql.push_str(" WHERE ");
+        soql.push_str(&conditions.join(" AND "));
+    }
+    soql.push_str(" ORDER BY StartTime DESC");
+    if let Some(limit) = filter.limit {
+        soql.push_str(&format!(" LIMIT {}", limit));
+    }
+    Ok(soql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apex_log_soql() {
+        let started = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let soql = apex_log_soql(
+            &ApexLogFilter::new()
+                .user("005000000000001AAA")
+                .operation("O'Brien")
+                .started_after(started)
+                .limit(5),
+        )
+        .unwrap();
+        assert!(soql.ends_with(
+            "FROM ApexLog WHERE LogUserId = '005000000000001AAA' \
+             AND Operation = 'O\\'Brien' AND StartTime > 2024-01-01T00:00:00Z \
+             ORDER BY StartTime DESC LIMIT 5"
+        ));
+        assert!(apex_log_soql(&ApexLogFilter::new().user("x' OR Id != '")).is_err());
+    }
+}
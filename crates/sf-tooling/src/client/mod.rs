@@ -3,14 +3,17 @@
 //! This client wraps `SalesforceClient` from `sf-client` and provides
 //! typed methods for Tooling API operations.
 
-use busbar_sf_client::{ClientConfig, SalesforceClient};
+use busbar_sf_client::{CallOptions, ClientConfig, SalesforceClient};
 
 use crate::error::Result;
 
 mod apex;
+mod async_jobs;
+mod bundles;
 mod code_intelligence;
 mod collections;
 mod composite;
+mod containers;
 mod coverage;
 #[cfg(feature = "dependencies")]
 mod dependencies;
@@ -18,6 +21,7 @@ mod describe;
 mod execute;
 mod logs;
 mod query;
+mod schema;
 mod sobject;
 mod test_execution;
 mod trace_flags;
@@ -95,6 +99,15 @@ impl ToolingClient {
         self.client = self.client.with_api_version(version);
         self
     }
+
+    /// Send `options` as call option headers with every request.
+    ///
+    /// Set [`CallOptions::with_accept_language`] to get Tooling describes
+    /// with translated labels.
+    pub fn with_call_options(mut self, options: CallOptions) -> Self {
+        self.client = self.client.with_call_options(options);
+        self
+    }
 }
 
 #[cfg(test)]
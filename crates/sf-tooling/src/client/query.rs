@@ -3,7 +3,7 @@ use tracing::instrument;
 
 use busbar_sf_client::QueryResult;
 
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, Result};
 use crate::types::SearchResult;
 
 impl super::ToolingClient {
@@ -42,6 +42,30 @@ impl super::ToolingClient {
             .map_err(Into::into)
     }
 
+    /// Fetch the next page of a Tooling query.
+    ///
+    /// Pass the `next_records_url` from the previous page. Only Tooling
+    /// query locators (`/services/data/vXX.X/tooling/query/...`) on this
+    /// org are accepted.
+    #[instrument(skip(self))]
+    pub async fn query_more<T: DeserializeOwned>(
+        &self,
+        next_records_url: &str,
+    ) -> Result<QueryResult<T>> {
+        if !next_records_url.starts_with("/services/data/")
+            || !next_records_url.contains("/tooling/query/")
+        {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_URL".to_string(),
+                message: "Not a Tooling query locator URL".to_string(),
+            }));
+        }
+        self.client
+            .get_json(next_records_url)
+            .await
+            .map_err(Into::into)
+    }
+
     /// Execute a SOQL query including deleted and archived records.
     ///
     /// Note: The Tooling API does not expose a `/queryAll` endpoint.
@@ -111,6 +135,40 @@ mod tests {
         assert!(result.done);
     }
 
+    #[tokio::test]
+    async fn test_query_more_wiremock() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/services/data/v62.0/tooling/query/01gxx0000000001AAA-2000",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 2001,
+                "done": true,
+                "records": [{"Id": "01p000000000001AAA"}]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let page: busbar_sf_client::QueryResult<serde_json::Value> = client
+            .query_more("/services/data/v62.0/tooling/query/01gxx0000000001AAA-2000")
+            .await
+            .expect("should succeed");
+        assert!(page.done);
+        assert_eq!(page.records.len(), 1);
+
+        for url in [
+            "https://evil.example.com/services/data/v62.0/tooling/query/x",
+            "/services/data/v62.0/query/01gxx0000000001AAA-2000",
+        ] {
+            assert!(client.query_more::<serde_json::Value>(url).await.is_err());
+        }
+    }
+
     #[tokio::test]
     async fn test_search_wiremock() {
         use wiremock::matchers::{method, path_regex};
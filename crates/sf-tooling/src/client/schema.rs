@@ -0,0 +1,238 @@
+use busbar_sf_client::security::{soql, url as url_security};
+use tracing::instrument;
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::types::*;
+
+const ENTITY_DEFINITION_FIELDS: &str = "DurableId, QualifiedApiName, DeveloperName, Label, PluralLabel, KeyPrefix, NamespacePrefix, IsCustomizable, IsCustomSetting, IsQueryable, IsApexTriggerable, LastModifiedDate";
+
+const FIELD_DEFINITION_FIELDS: &str = "DurableId, QualifiedApiName, Label, DataType, Length, Precision, Scale, IsNillable, IsIndexed, IsCalculated, IsFieldHistoryTracked, Description, NamespacePrefix, LastModifiedDate";
+
+const CUSTOM_FIELD_FIELDS: &str = "Id, DeveloperName, TableEnumOrId, NamespacePrefix, ManageableState, CreatedDate, LastModifiedDate";
+
+impl super::ToolingClient {
+    /// Get an object's EntityDefinition by API name, e.g. `Account` or
+    /// `Invoice__c`.
+    #[instrument(skip(self))]
+    pub async fn entity_definition(&self, api_name: &str) -> Result<Option<EntityDefinition>> {
+        let soql = format!(
+            "SELECT {} FROM EntityDefinition WHERE QualifiedApiName = '{}'",
+            ENTITY_DEFINITION_FIELDS,
+            soql::escape_string(api_name)
+        );
+        let mut entities: Vec<EntityDefinition> = self.query_all(&soql).await?;
+        Ok(entities.pop())
+    }
+
+    /// List the FieldDefinitions of an object, standard and custom.
+    #[instrument(skip(self))]
+    pub async fn field_definitions(&self, entity: &str) -> Result<Vec<FieldDefinition>> {
+        let soql = format!(
+            "SELECT {} FROM FieldDefinition WHERE EntityDefinition.QualifiedApiName = '{}' ORDER BY QualifiedApiName",
+            FIELD_DEFINITION_FIELDS,
+            soql::escape_string(entity)
+        );
+        self.query_all(&soql).await
+    }
+
+    /// Get one field's FieldDefinition, e.g. (`Account`, `Industry`).
+    #[instrument(skip(self))]
+    pub async fn field_definition(
+        &self,
+        entity: &str,
+        field: &str,
+    ) -> Result<Option<FieldDefinition>> {
+        let soql = format!(
+            "SELECT {} FROM FieldDefinition WHERE EntityDefinition.QualifiedApiName = '{}' AND QualifiedApiName = '{}'",
+            FIELD_DEFINITION_FIELDS,
+            soql::escape_string(entity),
+            soql::escape_string(field)
+        );
+        let mut fields: Vec<FieldDefinition> = self.query_all(&soql).await?;
+        Ok(fields.pop())
+    }
+
+    /// List the custom fields on an object, without their metadata.
+    ///
+    /// Returns an empty list if the object doesn't exist.
+    #[instrument(skip(self))]
+    pub async fn custom_fields(&self, entity: &str) -> Result<Vec<CustomField>> {
+        // CustomField is keyed by the object's DurableId, which for custom
+        // objects is an ID rather than the API name.
+        let Some(entity) = self.entity_definition(entity).await? else {
+            return Ok(Vec::new());
+        };
+        let soql = format!(
+            "SELECT {} FROM CustomField WHERE TableEnumOrId = '{}' ORDER BY DeveloperName",
+            CUSTOM_FIELD_FIELDS,
+            soql::escape_string(&entity.durable_id)
+        );
+        self.query_all(&soql).await
+    }
+
+    /// Get a custom field with its `FullName` and `Metadata`.
+    #[instrument(skip(self))]
+    pub async fn custom_field(&self, id: &str) -> Result<Option<CustomField>> {
+        if !url_security::is_valid_salesforce_id(id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        // Metadata and FullName can only be selected for one record per query.
+        let soql = format!(
+            "SELECT {}, FullName, Metadata FROM CustomField WHERE Id = '{}'",
+            CUSTOM_FIELD_FIELDS, id
+        );
+        let mut fields: Vec<CustomField> = self.query_all(&soql).await?;
+        Ok(fields.pop())
+    }
+
+    /// List the custom fields on an object with their metadata.
+    ///
+    /// Salesforce only returns metadata one field at a time, so this makes a
+    /// query per field after listing them.
+    #[instrument(skip(self))]
+    pub async fn custom_fields_with_metadata(&self, entity: &str) -> Result<Vec<CustomField>> {
+        let mut fields = Vec::new();
+        for field in self.custom_fields(entity).await? {
+            if let Some(field) = self.custom_field(&field.id).await? {
+                fields.push(field);
+            }
+        }
+        Ok(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ToolingClient;
+
+    #[tokio::test]
+    async fn test_custom_fields_with_metadata_wiremock() {
+        use wiremock::matchers::{method, path, query_param_contains};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let query = "/services/data/v62.0/tooling/query";
+
+        Mock::given(method("GET"))
+            .and(path(query))
+            .and(query_param_contains(
+                "q",
+                "FROM EntityDefinition WHERE QualifiedApiName = 'Invoice__c'",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [{
+                    "DurableId": "01I000000000001AAA",
+                    "QualifiedApiName": "Invoice__c",
+                    "Label": "Invoice"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(query))
+            .and(query_param_contains(
+                "q",
+                "FROM CustomField WHERE TableEnumOrId = '01I000000000001AAA'",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [{
+                    "Id": "00N000000000001AAA",
+                    "DeveloperName": "Amount",
+                    "TableEnumOrId": "01I000000000001AAA",
+                    "NamespacePrefix": null
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(query))
+            .and(query_param_contains(
+                "q",
+                "FullName, Metadata FROM CustomField WHERE Id = '00N000000000001AAA'",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [{
+                    "Id": "00N000000000001AAA",
+                    "DeveloperName": "Amount",
+                    "FullName": "Invoice__c.Amount__c",
+                    "Metadata": {
+                        "type": "Currency",
+                        "label": "Amount",
+                        "precision": 18,
+                        "scale": 2,
+                        "required": true,
+                        "inlineHelpText": null,
+                        "trackHistory": false
+                    }
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let fields = client
+            .custom_fields_with_metadata("Invoice__c")
+            .await
+            .expect("should succeed");
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].api_name(), "Amount__c");
+        assert_eq!(fields[0].full_name.as_deref(), Some("Invoice__c.Amount__c"));
+        let metadata = fields[0].metadata.as_ref().expect("metadata");
+        assert_eq!(metadata.field_type.as_deref(), Some("Currency"));
+        assert_eq!(metadata.scale, Some(2));
+        assert_eq!(metadata.other["trackHistory"], false);
+    }
+
+    #[tokio::test]
+    async fn test_field_definitions_wiremock() {
+        use wiremock::matchers::{method, path, query_param_contains};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/query"))
+            .and(query_param_contains(
+                "q",
+                "FROM FieldDefinition WHERE EntityDefinition.QualifiedApiName = 'Account'",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 2,
+                "done": true,
+                "records": [
+                    {
+                        "DurableId": "Account.Industry",
+                        "QualifiedApiName": "Industry",
+                        "DataType": "Picklist",
+                        "IsNillable": true
+                    },
+                    {
+                        "DurableId": "Account.00N000000000001",
+                        "QualifiedApiName": "Tier__c",
+                        "DataType": "Text(20)",
+                        "Length": 20
+                    }
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let fields = client.field_definitions("Account").await.unwrap();
+        let custom: Vec<_> = fields.iter().filter(|f| f.is_custom()).collect();
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[0].length, Some(20));
+        assert!(client.custom_field("bad id").await.is_err());
+    }
+}
@@ -101,6 +101,59 @@ impl super::ToolingClient {
             .map_err(Into::into)
     }
 
+    /// Upsert a Tooling API SObject by an external ID field.
+    ///
+    /// Only Tooling objects with an external ID or idLookup field support
+    /// this; others fail with `NOT_FOUND` or `INVALID_FIELD`.
+    #[instrument(skip(self, record))]
+    pub async fn upsert<T: serde::Serialize>(
+        &self,
+        sobject: &str,
+        external_id_field: &str,
+        external_id_value: &str,
+        record: &T,
+    ) -> Result<busbar_sf_rest::UpsertResult> {
+        if !soql::is_safe_sobject_name(sobject) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_SOBJECT".to_string(),
+                message: "Invalid SObject name".to_string(),
+            }));
+        }
+        if !soql::is_safe_field_name(external_id_field) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_FIELD".to_string(),
+                message: "Invalid external ID field name".to_string(),
+            }));
+        }
+        let url = format!(
+            "{}/services/data/v{}/tooling/sobjects/{}/{}/{}",
+            self.client.instance_url(),
+            self.client.api_version(),
+            sobject,
+            external_id_field,
+            url_security::encode_param(external_id_value)
+        );
+
+        let request = self.client.patch(&url).json(record)?;
+        let response = self.client.execute(request).await?;
+
+        // 201 Created or 200 Updated carry a result; older API versions
+        // answer an update with 204 and no body.
+        match response.status() {
+            200 | 201 => Ok(response.json().await?),
+            204 => Ok(busbar_sf_rest::UpsertResult {
+                id: external_id_value.to_string(),
+                success: true,
+                created: false,
+                errors: vec![],
+            }),
+            status => Err(Error::new(ErrorKind::Salesforce {
+                error_code: "UPSERT_FAILED".to_string(),
+                message: format!("Unexpected status: {}", status),
+            })),
+        }
+    }
+
     /// Delete a Tooling API SObject.
     #[instrument(skip(self))]
     pub async fn delete(&self, sobject: &str, id: &str) -> Result<()> {
@@ -167,6 +220,47 @@ mod tests {
         assert!(result.is_ok(), "update should succeed: {:?}", result.err());
     }
 
+    #[tokio::test]
+    async fn test_upsert_wiremock() {
+        use wiremock::matchers::{body_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("PATCH"))
+            .and(path(
+                "/services/data/v62.0/tooling/sobjects/DebugLevel/DeveloperName/Verbose%20Apex",
+            ))
+            .and(body_json(serde_json::json!({"ApexCode": "FINEST"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "7dl000000000001AAA",
+                "success": true,
+                "created": true,
+                "errors": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let result = client
+            .upsert(
+                "DebugLevel",
+                "DeveloperName",
+                "Verbose Apex",
+                &serde_json::json!({"ApexCode": "FINEST"}),
+            )
+            .await
+            .unwrap();
+        assert!(result.created);
+        assert_eq!(result.id, "7dl000000000001AAA");
+
+        let err = client
+            .upsert("DebugLevel", "Name'--", "x", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("INVALID_FIELD"));
+    }
+
     #[tokio::test]
     async fn test_update_invalid_sobject() {
         let client = ToolingClient::new("https://na1.salesforce.com", "token").unwrap();
@@ -1,11 +1,19 @@
+use std::time::{Duration, Instant};
+
+use busbar_sf_client::security::url as url_security;
 use tracing::instrument;
 
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, Result};
 use crate::types::{
-    RunTestsAsyncRequest, RunTestsRequest, RunTestsResponse, RunTestsSyncRequest,
-    RunTestsSyncResult, TestDiscoveryResult,
+    ApexTestQueueItem, ApexTestResult, RunTestsAsyncRequest, RunTestsRequest, RunTestsResponse,
+    RunTestsSyncRequest, RunTestsSyncResult, TestDiscoveryResult, TestRunSummary,
 };
 
+const QUEUE_ITEM_FIELDS: &str =
+    "Id, ApexClassId, ApexClass.Name, Status, ExtendedStatus, ParentJobId";
+
+const TEST_RESULT_FIELDS: &str = "Id, QueueItemId, AsyncApexJobId, ApexClass.Name, MethodName, Outcome, RunTime, Message, StackTrace, TestTimestamp";
+
 impl super::ToolingClient {
     /// Run tests asynchronously.
     ///
@@ -23,6 +31,94 @@ impl super::ToolingClient {
         Ok(job_id)
     }
 
+    /// Enqueue tests with `runTestsAsynchronous` and wait for them to finish.
+    ///
+    /// Fails if the run is still going after `timeout`; the tests keep
+    /// running and [`test_run_summary`](Self::test_run_summary) can pick
+    /// them up again.
+    #[instrument(skip(self, request))]
+    pub async fn run_apex_tests(
+        &self,
+        request: &RunTestsAsyncRequest,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<TestRunSummary> {
+        let test_run_id = self.run_tests_async(request).await?;
+        self.wait_for_test_run(&test_run_id, poll_interval, timeout)
+            .await
+    }
+
+    /// Get the queue items and method results of an asynchronous test run.
+    ///
+    /// ApexTestQueueItem and ApexTestResult are data objects, so they are
+    /// queried through the REST query endpoint.
+    #[instrument(skip(self))]
+    pub async fn test_run_summary(&self, test_run_id: &str) -> Result<TestRunSummary> {
+        if !url_security::is_valid_salesforce_id(test_run_id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        let queue_items: Vec<ApexTestQueueItem> = self
+            .client
+            .query_all(&format!(
+                "SELECT {} FROM ApexTestQueueItem WHERE ParentJobId = '{}'",
+                QUEUE_ITEM_FIELDS, test_run_id
+            ))
+            .await?;
+        let results: Vec<ApexTestResult> = self
+            .client
+            .query_all(&format!(
+                "SELECT {} FROM ApexTestResult WHERE AsyncApexJobId = '{}' \
+                 ORDER BY ApexClass.Name, MethodName",
+                TEST_RESULT_FIELDS, test_run_id
+            ))
+            .await?;
+        Ok(TestRunSummary {
+            test_run_id: test_run_id.to_string(),
+            queue_items,
+            results,
+        })
+    }
+
+    /// Poll a test run until every enqueued class has finished.
+    ///
+    /// Fails if the run has no queue items, or is still going after `timeout`.
+    #[instrument(skip(self))]
+    pub async fn wait_for_test_run(
+        &self,
+        test_run_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<TestRunSummary> {
+        let started = Instant::now();
+        loop {
+            let summary = self.test_run_summary(test_run_id).await?;
+            if summary.queue_items.is_empty() {
+                return Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "NOT_FOUND".to_string(),
+                    message: format!("Test run {} has no queued tests", test_run_id),
+                }));
+            }
+            if summary.is_complete() {
+                return Ok(summary);
+            }
+            if started.elapsed() >= timeout {
+                let pending = summary
+                    .queue_items
+                    .iter()
+                    .filter(|item| !item.status.is_terminal())
+                    .count();
+                return Err(Error::new(ErrorKind::Other(format!(
+                    "Test run {} still has {} classes running after {:?}",
+                    test_run_id, pending, timeout
+                ))));
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
     /// Run tests synchronously.
     ///
     /// Blocks until completion. Returns full results including successes and failures.
@@ -142,4 +238,113 @@ mod tests {
         );
         assert_eq!(actual, expected);
     }
+
+    #[tokio::test]
+    async fn test_run_apex_tests_wiremock() {
+        use std::time::Duration;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        let run_id = "707000000000001AAA";
+
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/tooling/runTestsAsynchronous/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(run_id))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let queue_soql = format!(
+            "SELECT {} FROM ApexTestQueueItem WHERE ParentJobId = '{}'",
+            super::QUEUE_ITEM_FIELDS,
+            run_id
+        );
+        let item = |status: &str| {
+            serde_json::json!({
+                "Id": "709000000000001AAA",
+                "ApexClass": {"Name": "AccountServiceTest"},
+                "Status": status,
+                "ParentJobId": run_id
+            })
+        };
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .and(query_param("q", queue_soql.as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [item("Processing")]
+            })))
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .and(query_param("q", queue_soql.as_str()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 1,
+                "done": true,
+                "records": [item("Completed")]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/query"))
+            .and(query_param(
+                "q",
+                format!(
+                    "SELECT {} FROM ApexTestResult WHERE AsyncApexJobId = '{}' \
+                     ORDER BY ApexClass.Name, MethodName",
+                    super::TEST_RESULT_FIELDS,
+                    run_id
+                )
+                .as_str(),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 2,
+                "done": true,
+                "records": [
+                    {
+                        "Id": "07M000000000001AAA",
+                        "ApexClass": {"Name": "AccountServiceTest"},
+                        "MethodName": "createsAccount",
+                        "Outcome": "Pass",
+                        "RunTime": 120
+                    },
+                    {
+                        "Id": "07M000000000002AAA",
+                        "ApexClass": {"Name": "AccountServiceTest"},
+                        "MethodName": "rejectsDuplicate",
+                        "Outcome": "Fail",
+                        "RunTime": 80,
+                        "Message": "System.AssertException: Assertion Failed",
+                        "StackTrace": "Class.AccountServiceTest.rejectsDuplicate: line 12, column 1"
+                    }
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ToolingClient::new(mock_server.uri(), "test-token").unwrap();
+        let request = crate::types::RunTestsAsyncRequest {
+            class_names: Some("AccountServiceTest".to_string()),
+            ..Default::default()
+        };
+        let summary = client
+            .run_apex_tests(&request, Duration::ZERO, Duration::from_secs(5))
+            .await
+            .expect("test run should finish");
+
+        assert!(summary.is_complete());
+        assert!(!summary.is_success());
+        assert_eq!((summary.passed(), summary.failed()), (1, 1));
+        assert_eq!(summary.total_run_time(), 200);
+        let failure = summary.failures().next().unwrap();
+        assert_eq!(failure.class_name(), Some("AccountServiceTest"));
+        assert!(failure.stack_trace.as_deref().unwrap().contains("line 12"));
+
+        assert!(client.test_run_summary("'; DELETE").await.is_err());
+    }
 }
@@ -1,6 +1,10 @@
+use std::time::Duration;
+
+use busbar_sf_client::security::{soql, url as url_security};
 use tracing::instrument;
 
-use crate::error::Result;
+use crate::error::{Error, ErrorKind, Result};
+use crate::log_capture::{LogCapture, LOG_CAPTURE_DEBUG_LEVEL};
 use crate::types::*;
 
 impl super::ToolingClient {
@@ -19,4 +23,247 @@ impl super::ToolingClient {
             "SELECT Id, DeveloperName, MasterLabel, ApexCode, ApexProfiling, Callout, Database, System, Validation, Visualforce, Workflow FROM DebugLevel"
         ).await
     }
+
+    /// Get a debug level by its `DeveloperName`.
+    #[instrument(skip(self))]
+    pub async fn get_debug_level_by_name(
+        &self,
+        developer_name: &str,
+    ) -> Result<Option<DebugLevel>> {
+        let soql = format!(
+            "SELECT Id, DeveloperName, MasterLabel, ApexCode, ApexProfiling, Callout, Database, System, Validation, Visualforce, Workflow FROM DebugLevel WHERE DeveloperName = '{}'",
+            soql::escape_string(developer_name)
+        );
+        let mut levels: Vec<DebugLevel> = self.query_all(&soql).await?;
+        Ok(levels.pop())
+    }
+
+    /// Create a debug level, returning its ID.
+    #[instrument(skip(self, level))]
+    pub async fn create_debug_level(&self, level: &DebugLevel) -> Result<String> {
+        self.create("DebugLevel", level).await
+    }
+
+    /// Update a debug level's name and log levels.
+    #[instrument(skip(self, level))]
+    pub async fn update_debug_level(&self, id: &str, level: &DebugLevel) -> Result<()> {
+        let level = DebugLevel {
+            id: None,
+            ..level.clone()
+        };
+        self.update("DebugLevel", id, &level).await
+    }
+
+    /// Create a trace flag, returning its ID.
+    ///
+    /// Salesforce rejects a trace flag that overlaps an existing one for the
+    /// same entity and log type, or that lasts more than 24 hours.
+    #[instrument(skip(self, flag))]
+    pub async fn create_trace_flag(&self, flag: &TraceFlag) -> Result<String> {
+        let flag = TraceFlag {
+            id: None,
+            ..flag.clone()
+        };
+        self.create("TraceFlag", &flag).await
+    }
+
+    /// Point a trace flag at another debug level, move its expiration, or
+    /// both.
+    #[instrument(skip(self))]
+    pub async fn update_trace_flag(
+        &self,
+        id: &str,
+        debug_level_id: Option<&str>,
+        expiration: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<()> {
+        let mut fields = serde_json::Map::new();
+        if let Some(debug_level_id) = debug_level_id {
+            if !url_security::is_valid_salesforce_id(debug_level_id) {
+                return Err(Error::new(ErrorKind::Salesforce {
+                    error_code: "INVALID_ID".to_string(),
+                    message: "Invalid Salesforce ID format".to_string(),
+                }));
+            }
+            fields.insert("DebugLevelId".to_string(), debug_level_id.into());
+        }
+        if let Some(expiration) = expiration {
+            fields.insert(
+                "ExpirationDate".to_string(),
+                format_datetime(expiration).into(),
+            );
+        }
+        self.update("TraceFlag", id, &fields).await
+    }
+
+    /// Delete a trace flag.
+    #[instrument(skip(self))]
+    pub async fn delete_trace_flag(&self, id: &str) -> Result<()> {
+        self.delete("TraceFlag", id).await
+    }
+
+    /// Log `user_id`'s activity at `FINEST` Apex code level for `duration`.
+    ///
+    /// Creates a `USER_DEBUG` trace flag using the [`LOG_CAPTURE_DEBUG_LEVEL`](crate::LOG_CAPTURE_DEBUG_LEVEL)
+    /// debug level, which is created the first time and left in place. The
+    /// returned guard lists the logs written since, and deletes the trace
+    /// flag when finished or dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let capture = client
+    ///     .capture_logs_for("005xx000001Sv6AAAS", Duration::from_secs(600))
+    ///     .await?;
+    /// run_integration_scenario().await?;
+    /// for log in capture.logs().await? {
+    ///     let body = client.get_apex_log_body(&log.id).await?;
+    ///     // ...
+    /// }
+    /// capture.finish().await?;
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn capture_logs_for(&self, user_id: &str, duration: Duration) -> Result<LogCapture> {
+        if !url_security::is_valid_salesforce_id(user_id) {
+            return Err(Error::new(ErrorKind::Salesforce {
+                error_code: "INVALID_ID".to_string(),
+                message: "Invalid Salesforce ID format".to_string(),
+            }));
+        }
+        let debug_level_id = match self
+            .get_debug_level_by_name(LOG_CAPTURE_DEBUG_LEVEL)
+            .await?
+        {
+            Some(DebugLevel { id: Some(id), .. }) => id,
+            _ => {
+                self.create_debug_level(&DebugLevel::new(LOG_CAPTURE_DEBUG_LEVEL, LogLevel::Finest))
+                    .await?
+            }
+        };
+
+        let started_at = chrono::Utc::now();
+        let expires_at = chrono::Duration::from_std(duration)
+            .ok()
+            .and_then(|d| started_at.checked_add_signed(d))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Other(format!(
+                    "Log capture duration {:?} is too long",
+                    duration
+                )))
+            })?;
+        let trace_flag_id = self
+            .create_trace_flag(&TraceFlag {
+                id: None,
+                traced_entity_id: user_id.to_string(),
+                log_type: "USER_DEBUG".to_string(),
+                debug_level_id,
+                start_date: Some(format_datetime(started_at)),
+                expiration_date: Some(format_datetime(expires_at)),
+            })
+            .await?;
+
+        Ok(LogCapture::new(
+            self.clone(),
+            user_id.to_string(),
+            trace_flag_id,
+            started_at,
+        ))
+    }
+}
+
+fn format_datetime(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ToolingClient;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const USER_ID: &str = "005000000000001AAA";
+    const TRACE_FLAG_ID: &str = "7tf000000000001AAA";
+
+    async fn mock_capture(server: &MockServer, deletes: u64) {
+        Mock::given(method("GET"))
+            .and(path("/services/data/v62.0/tooling/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "totalSize": 0,
+                "done": true,
+                "records": []
+            })))
+            .mount(server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/tooling/sobjects/DebugLevel"))
+            .and(body_partial_json(serde_json::json!({
+                "DeveloperName": "Busbar_Log_Capture",
+                "ApexCode": "FINEST"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": "7dl000000000001AAA",
+                "success": true,
+                "errors": []
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/services/data/v62.0/tooling/sobjects/TraceFlag"))
+            .and(body_partial_json(serde_json::json!({
+                "TracedEntityId": USER_ID,
+                "LogType": "USER_DEBUG",
+                "DebugLevelId": "7dl000000000001AAA"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "id": TRACE_FLAG_ID,
+                "success": true,
+                "errors": []
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path(format!(
+                "/services/data/v62.0/tooling/sobjects/TraceFlag/{TRACE_FLAG_ID}"
+            )))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(deletes)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_capture_logs_for_finish_deletes_trace_flag() {
+        let server = MockServer::start().await;
+        mock_capture(&server, 1).await;
+
+        let client = ToolingClient::new(server.uri(), "test-token").unwrap();
+        let capture = client
+            .capture_logs_for(USER_ID, std::time::Duration::from_secs(600))
+            .await
+            .unwrap();
+        assert_eq!(capture.trace_flag_id(), TRACE_FLAG_ID);
+        assert!(capture.logs().await.unwrap().is_empty());
+        capture.finish().await.unwrap();
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn test_capture_logs_for_drop_deletes_trace_flag() {
+        let server = MockServer::start().await;
+        mock_capture(&server, 1).await;
+
+        let client = ToolingClient::new(server.uri(), "test-token").unwrap();
+        let capture = client
+            .capture_logs_for(USER_ID, std::time::Duration::from_secs(600))
+            .await
+            .unwrap();
+        drop(capture);
+        server.verify().await;
+
+        assert!(client
+            .capture_logs_for("not-an-id", std::time::Duration::from_secs(1))
+            .await
+            .is_err());
+    }
 }
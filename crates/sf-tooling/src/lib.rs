@@ -6,11 +6,20 @@
 //!
 //! - **Apex Operations** - Execute anonymous Apex, query Apex logs
 //! - **Metadata Query** - Query ApexClass, ApexTrigger, and other tooling objects
-//! - **Debug Logs** - Retrieve and manage debug logs
-//! - **Trace Flags** - Manage debug trace flags
-//! - **Test Execution** - Run Apex and Flow tests (async/sync, discovery, v65.0+ unified API)
-//! - **Code Coverage** - Get code coverage information
-//! - **Describe** - Get tooling object metadata
+//! - **Metadata Containers** - Save Apex classes and triggers, compiling them in the org
+//!   and reporting compile errors with line numbers
+//! - **Debug Logs** - List, stream, and delete debug logs
+//! - **Trace Flags** - Manage trace flags and debug levels, or capture a user's logs
+//!   for a while with a guard that removes its trace flag
+//! - **Test Execution** - Run Apex and Flow tests (async/sync, discovery, v65.0+ unified API),
+//!   and poll asynchronous runs for per-method results
+//! - **Code Coverage** - Per-class and org-wide coverage, with LCOV and JSON reports
+//! - **Async Jobs** - Monitor AsyncApexJob and CronTrigger records and abort stuck jobs
+//! - **Code Intelligence** - Apex class symbol tables and typed Apex completions
+//! - **Describe** - Get tooling object metadata, and introspect schema through
+//!   EntityDefinition, FieldDefinition, and CustomField
+//! - **Bundles** - Create, fetch, and hot-edit LWC, Aura, and static resource bundles
+//!   file by file
 //!
 //! ## Example
 //!
@@ -47,14 +56,16 @@
 
 mod client;
 mod error;
+mod log_capture;
 mod types;
 
 pub use client::ToolingClient;
 pub use error::{Error, ErrorKind, Result};
+pub use log_capture::{LogCapture, LOG_CAPTURE_DEBUG_LEVEL};
 pub use types::*;
 
 // Re-export busbar-sf-client types that users might need
-pub use busbar_sf_client::{ClientConfig, ClientConfigBuilder, QueryResult};
+pub use busbar_sf_client::{CallOptions, ClientConfig, ClientConfigBuilder, QueryResult};
 
 // Re-export composite and collection types from sf-rest for Tooling API usage
 pub use busbar_sf_rest::{
@@ -62,5 +73,5 @@ pub use busbar_sf_rest::{
     CompositeBatchSubrequest, CompositeBatchSubresponse, CompositeRequest, CompositeResponse,
     CompositeSubrequest, CompositeSubresponse, CompositeTreeAttributes, CompositeTreeError,
     CompositeTreeRecord, CompositeTreeRequest, CompositeTreeResponse, CompositeTreeResult,
-    DescribeGlobalResult, DescribeSObjectResult,
+    DescribeGlobalResult, DescribeSObjectResult, UpsertResult,
 };
@@ -0,0 +1,133 @@
+//! Scoped debug log capture.
+
+use crate::client::ToolingClient;
+use crate::error::{Error, Result};
+use crate::types::{ApexLog, ApexLogFilter};
+
+/// The debug level [`ToolingClient::capture_logs_for`] creates and reuses.
+pub const LOG_CAPTURE_DEBUG_LEVEL: &str = "Busbar_Log_Capture";
+
+/// A trace flag capturing a user's debug logs, deleted when dropped.
+///
+/// Returned by [`ToolingClient::capture_logs_for`]. Prefer
+/// [`finish`](Self::finish), which reports errors. The delete in `Drop` runs
+/// on its own thread and runtime with a new client for the same org and
+/// access token, so it works from any runtime; a failure there is only
+/// logged. A trace flag left behind still stops logging when it expires.
+#[derive(Debug)]
+pub struct LogCapture {
+    client: ToolingClient,
+    user_id: String,
+    trace_flag_id: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    armed: bool,
+}
+
+impl LogCapture {
+    pub(crate) fn new(
+        client: ToolingClient,
+        user_id: String,
+        trace_flag_id: String,
+        started_at: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        Self {
+            client,
+            user_id,
+            trace_flag_id,
+            started_at,
+            armed: true,
+        }
+    }
+
+    /// The user being logged.
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// The trace flag's ID.
+    pub fn trace_flag_id(&self) -> &str {
+        &self.trace_flag_id
+    }
+
+    /// When the trace flag started.
+    pub fn started_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.started_at
+    }
+
+    /// The user's logs written since the capture started, newest first.
+    pub async fn logs(&self) -> Result<Vec<ApexLog>> {
+        self.client
+            .list_apex_logs(
+                &ApexLogFilter::new()
+                    .user(self.user_id.clone())
+                    .started_after(self.started_at),
+            )
+            .await
+    }
+
+    /// Delete the trace flag now, returning any error.
+    ///
+    /// A trace flag that is already gone counts as deleted.
+    pub async fn finish(mut self) -> Result<()> {
+        self.armed = false;
+        match self.client.delete_trace_flag(&self.trace_flag_id).await {
+            Err(e) if is_not_found(&e) => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Leave the trace flag in place until it expires and return its ID.
+    pub fn keep(mut self) -> String {
+        self.armed = false;
+        self.trace_flag_id.clone()
+    }
+}
+
+impl Drop for LogCapture {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let inner = self.client.inner();
+        let client = ToolingClient::new(inner.instance_url(), inner.access_token())
+            .map(|c| c.with_api_version(inner.api_version()));
+        let id = self.trace_flag_id.clone();
+
+        // The caller's runtime may be a single thread blocked right here,
+        // so the delete gets a runtime of its own.
+        let outcome = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| Error::new(crate::ErrorKind::Other(e.to_string())))?;
+            runtime.block_on(client?.delete_trace_flag(&id))
+        })
+        .join();
+
+        match outcome {
+            Ok(Ok(())) => tracing::debug!(id = %self.trace_flag_id, "deleted trace flag"),
+            Ok(Err(e)) if is_not_found(&e) => {}
+            Ok(Err(e)) => tracing::warn!(
+                id = %self.trace_flag_id,
+                error = %e,
+                "failed to delete trace flag"
+            ),
+            Err(_) => tracing::warn!(id = %self.trace_flag_id, "trace flag cleanup panicked"),
+        }
+    }
+}
+
+/// Whether a delete failed because the record no longer exists.
+fn is_not_found(error: &Error) -> bool {
+    error
+        .source
+        .as_ref()
+        .and_then(|s| s.downcast_ref::<busbar_sf_client::Error>())
+        .is_some_and(|e| match &e.kind {
+            busbar_sf_client::ErrorKind::NotFound(_) => true,
+            busbar_sf_client::ErrorKind::SalesforceApi { error_code, .. } => {
+                error_code == "NOT_FOUND" || error_code == "ENTITY_IS_DELETED"
+            }
+            _ => false,
+        })
+}
@@ -61,6 +61,21 @@ pub struct ExecuteAnonymousResult {
     pub line: Option<i32>,
 }
 
+/// Result of executing anonymous Apex with its debug log captured.
+///
+/// Returned by [`ToolingClient::execute_anonymous_with_log`](crate::ToolingClient::execute_anonymous_with_log).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecuteAnonymousWithLog {
+    /// The execution result, including compile and runtime failures.
+    pub result: ExecuteAnonymousResult,
+
+    /// The ApexLog written by the execution, if one was found.
+    pub log_id: Option<String>,
+
+    /// The log body.
+    pub log: Option<String>,
+}
+
 // ============================================================================
 // Apex Class Types
 // ============================================================================
@@ -196,6 +211,46 @@ pub struct LogUser {
     pub name: Option<String>,
 }
 
+/// Filter for [`ToolingClient::list_apex_logs`](crate::ToolingClient::list_apex_logs).
+#[derive(Debug, Clone, Default)]
+pub struct ApexLogFilter {
+    pub(crate) user_id: Option<String>,
+    pub(crate) operation: Option<String>,
+    pub(crate) started_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub(crate) limit: Option<u32>,
+}
+
+impl ApexLogFilter {
+    /// Match all logs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only logs of the user with this ID.
+    pub fn user(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Only logs of this operation, e.g. `/services/data/v62.0/sobjects/Account`.
+    pub fn operation(mut self, operation: impl Into<String>) -> Self {
+        self.operation = Some(operation.into());
+        self
+    }
+
+    /// Only logs started after `time`.
+    pub fn started_after(mut self, time: chrono::DateTime<chrono::Utc>) -> Self {
+        self.started_after = Some(time);
+        self
+    }
+
+    /// Return at most `limit` logs, newest first.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
 // ============================================================================
 // Trace Flag Types
 // ============================================================================
@@ -203,6 +258,7 @@ pub struct LogUser {
 /// TraceFlag record from Tooling API.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TraceFlag {
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "Id")]
     pub id: Option<String>,
 
@@ -215,9 +271,11 @@ pub struct TraceFlag {
     #[serde(rename = "DebugLevelId")]
     pub debug_level_id: String,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "StartDate")]
     pub start_date: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "ExpirationDate")]
     pub expiration_date: Option<String>,
 }
@@ -225,6 +283,7 @@ pub struct TraceFlag {
 /// DebugLevel record from Tooling API.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DebugLevel {
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "Id")]
     pub id: Option<String>,
 
@@ -234,31 +293,60 @@ pub struct DebugLevel {
     #[serde(rename = "MasterLabel")]
     pub master_label: String,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "ApexCode")]
     pub apex_code: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "ApexProfiling")]
     pub apex_profiling: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "Callout")]
     pub callout: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "Database")]
     pub database: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "System")]
     pub system: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "Validation")]
     pub validation: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "Visualforce")]
     pub visualforce: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "Workflow")]
     pub workflow: Option<String>,
 }
 
+impl DebugLevel {
+    /// A debug level logging Apex code at `apex_code` and every other
+    /// category at Salesforce's defaults.
+    pub fn new(developer_name: impl Into<String>, apex_code: LogLevel) -> Self {
+        let developer_name = developer_name.into();
+        Self {
+            id: None,
+            master_label: developer_name.clone(),
+            developer_name,
+            apex_code: Some(apex_code.to_string()),
+            apex_profiling: None,
+            callout: None,
+            database: None,
+            system: None,
+            validation: None,
+            visualforce: None,
+            workflow: None,
+        }
+    }
+}
+
 /// Debug log level options.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
@@ -303,6 +391,9 @@ pub struct ApexCodeCoverage {
     #[serde(rename = "ApexClassOrTrigger")]
     pub apex_class_or_trigger: Option<ApexClassOrTriggerRef>,
 
+    #[serde(rename = "ApexTestClassId")]
+    pub apex_test_class_id: Option<String>,
+
     #[serde(rename = "TestMethodName")]
     pub test_method_name: Option<String>,
 
@@ -355,6 +446,210 @@ pub struct ApexCodeCoverageAggregate {
     pub coverage: Option<CoverageDetail>,
 }
 
+/// Line coverage of one Apex class or trigger.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ClassCoverage {
+    /// The ApexClass (`01p`) or ApexTrigger (`01q`) ID.
+    pub id: String,
+    pub name: Option<String>,
+    pub lines_covered: u32,
+    pub lines_uncovered: u32,
+    /// Covered line numbers, ascending. Empty when Salesforce didn't
+    /// return line detail.
+    pub covered_lines: Vec<i32>,
+    /// Uncovered line numbers, ascending.
+    pub uncovered_lines: Vec<i32>,
+}
+
+impl ClassCoverage {
+    /// Whether this is a trigger rather than a class.
+    pub fn is_trigger(&self) -> bool {
+        self.id.starts_with("01q")
+    }
+
+    /// Lines that can be covered.
+    pub fn lines_total(&self) -> u32 {
+        self.lines_covered + self.lines_uncovered
+    }
+
+    /// Percentage of lines covered, or 0 when there are none.
+    pub fn percent(&self) -> f64 {
+        percent(u64::from(self.lines_covered), u64::from(self.lines_total()))
+    }
+}
+
+/// Per-class and org-wide code coverage.
+///
+/// Serializes to JSON as-is; [`to_lcov`](Self::to_lcov) gives an LCOV
+/// tracefile for coverage tools.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct CoverageReport {
+    /// One entry per class or trigger, by name.
+    pub classes: Vec<ClassCoverage>,
+}
+
+impl CoverageReport {
+    /// A report from `ApexCodeCoverageAggregate` records, which already
+    /// combine every test's coverage.
+    pub fn from_aggregates(records: &[ApexCodeCoverageAggregate]) -> Self {
+        Self::new(
+            records
+                .iter()
+                .map(|record| {
+                    let (covered_lines, uncovered_lines) = line_numbers(record.coverage.as_ref());
+                    ClassCoverage {
+                        id: record.apex_class_or_trigger_id.clone(),
+                        name: record
+                            .apex_class_or_trigger
+                            .as_ref()
+                            .and_then(|r| r.name.clone()),
+                        lines_covered: record.num_lines_covered.max(0) as u32,
+                        lines_uncovered: record.num_lines_uncovered.max(0) as u32,
+                        covered_lines,
+                        uncovered_lines,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// A report from per-test `ApexCodeCoverage` records, such as those of
+    /// a single test run.
+    ///
+    /// A line is covered if any test covered it.
+    pub fn from_coverage(records: &[ApexCodeCoverage]) -> Self {
+        let mut classes: Vec<ClassCoverage> = Vec::new();
+        for record in records {
+            let (covered, uncovered) = line_numbers(record.coverage.as_ref());
+            let class = match classes
+                .iter_mut()
+                .position(|c| c.id == record.apex_class_or_trigger_id)
+            {
+                Some(i) => &mut classes[i],
+                None => {
+                    classes.push(ClassCoverage {
+                        id: record.apex_class_or_trigger_id.clone(),
+                        name: record
+                            .apex_class_or_trigger
+                            .as_ref()
+                            .and_then(|r| r.name.clone()),
+                        lines_covered: 0,
+                        lines_uncovered: 0,
+                        covered_lines: Vec::new(),
+                        uncovered_lines: Vec::new(),
+                    });
+                    classes.last_mut().expect("just pushed")
+                }
+            };
+            if covered.is_empty() && uncovered.is_empty() {
+                // No line detail: keep the best single test's counts.
+                let lines_covered = record.num_lines_covered.unwrap_or(0).max(0) as u32;
+                if lines_covered >= class.lines_covered {
+                    class.lines_covered = lines_covered;
+                    class.lines_uncovered = record.num_lines_uncovered.unwrap_or(0).max(0) as u32;
+                }
+                continue;
+            }
+            class.covered_lines.extend(covered);
+            class.uncovered_lines.extend(uncovered);
+            class.covered_lines.sort_unstable();
+            class.covered_lines.dedup();
+            let covered_lines = &class.covered_lines;
+            class
+                .uncovered_lines
+                .retain(|line| covered_lines.binary_search(line).is_err());
+            class.uncovered_lines.sort_unstable();
+            class.uncovered_lines.dedup();
+            class.lines_covered = class.covered_lines.len() as u32;
+            class.lines_uncovered = class.uncovered_lines.len() as u32;
+        }
+        Self::new(classes)
+    }
+
+    fn new(mut classes: Vec<ClassCoverage>) -> Self {
+        classes.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+        Self { classes }
+    }
+
+    /// Covered lines across all classes and triggers.
+    pub fn lines_covered(&self) -> u64 {
+        self.classes
+            .iter()
+            .map(|c| u64::from(c.lines_covered))
+            .sum()
+    }
+
+    /// Coverable lines across all classes and triggers.
+    pub fn lines_total(&self) -> u64 {
+        self.classes
+            .iter()
+            .map(|c| u64::from(c.lines_total()))
+            .sum()
+    }
+
+    /// Org-wide percentage of lines covered, or 0 when there are none.
+    pub fn percent(&self) -> f64 {
+        percent(self.lines_covered(), self.lines_total())
+    }
+
+    /// Classes and triggers with coverable lines below `percent` coverage,
+    /// the check Salesforce applies per trigger on production deploys.
+    pub fn below(&self, percent: f64) -> impl Iterator<Item = &ClassCoverage> {
+        self.classes
+            .iter()
+            .filter(move |c| c.lines_total() > 0 && c.percent() < percent)
+    }
+
+    /// The report as an LCOV tracefile.
+    ///
+    /// Source files are named `classes/<Name>.cls` and
+    /// `triggers/<Name>.trigger`, relative to the package directory.
+    /// Classes without line detail only get their totals.
+    pub fn to_lcov(&self) -> String {
+        use std::fmt::Write;
+
+        let mut lcov = String::new();
+        for class in &self.classes {
+            let name = class.name.as_deref().unwrap_or(&class.id);
+            let path = if class.is_trigger() {
+                format!("triggers/{}.trigger", name)
+            } else {
+                format!("classes/{}.cls", name)
+            };
+            let mut lines: Vec<(i32, u8)> = class
+                .covered_lines
+                .iter()
+                .map(|&line| (line, 1))
+                .chain(class.uncovered_lines.iter().map(|&line| (line, 0)))
+                .collect();
+            lines.sort_unstable();
+
+            let _ = writeln!(lcov, "TN:");
+            let _ = writeln!(lcov, "SF:{}", path);
+            for (line, hits) in lines {
+                let _ = writeln!(lcov, "DA:{},{}", line, hits);
+            }
+            let _ = writeln!(lcov, "LF:{}", class.lines_total());
+            let _ = writeln!(lcov, "LH:{}", class.lines_covered);
+            let _ = writeln!(lcov, "end_of_record");
+        }
+        lcov
+    }
+}
+
+fn line_numbers(detail: Option<&CoverageDetail>) -> (Vec<i32>, Vec<i32>) {
+    detail.map_or_else(Default::default, |d| {
+        (d.covered_lines.clone(), d.uncovered_lines.clone())
+    })
+}
+
+fn percent(covered: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (covered as f64 / total as f64) * 100.0
+}
+
 // ============================================================================
 // Test Execution Types
 // ============================================================================
@@ -565,6 +860,170 @@ pub struct CodeCoverageWarning {
     pub namespace: Option<String>,
 }
 
+// ============================================================================
+// Asynchronous Test Run Types
+// ============================================================================
+
+/// ApexTestQueueItem record: one test class enqueued by a test run.
+///
+/// Its `Status` uses the same picklist as `AsyncApexJob`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApexTestQueueItem {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "ApexClassId")]
+    pub apex_class_id: Option<String>,
+
+    #[serde(rename = "ApexClass")]
+    pub apex_class: Option<ApexClassOrTriggerRef>,
+
+    #[serde(rename = "Status")]
+    pub status: AsyncApexJobStatus,
+
+    /// Why the class didn't run, e.g. a compile error.
+    #[serde(rename = "ExtendedStatus")]
+    pub extended_status: Option<String>,
+
+    /// The test run (`AsyncApexJob`) this item belongs to.
+    #[serde(rename = "ParentJobId")]
+    pub parent_job_id: Option<String>,
+}
+
+/// Outcome of a single test method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ApexTestOutcome {
+    Pass,
+    Fail,
+    CompileFail,
+    Skip,
+    /// An outcome this crate doesn't know about.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ApexTestOutcome {
+    /// The picklist value used in SOQL.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApexTestOutcome::Pass => "Pass",
+            ApexTestOutcome::Fail => "Fail",
+            ApexTestOutcome::CompileFail => "CompileFail",
+            ApexTestOutcome::Skip => "Skip",
+            ApexTestOutcome::Unknown => "Unknown",
+        }
+    }
+}
+
+/// ApexTestResult record: the outcome of one test method.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApexTestResult {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "QueueItemId")]
+    pub queue_item_id: Option<String>,
+
+    #[serde(rename = "AsyncApexJobId")]
+    pub async_apex_job_id: Option<String>,
+
+    #[serde(rename = "ApexClass")]
+    pub apex_class: Option<ApexClassOrTriggerRef>,
+
+    #[serde(rename = "MethodName")]
+    pub method_name: Option<String>,
+
+    #[serde(rename = "Outcome")]
+    pub outcome: ApexTestOutcome,
+
+    /// Run time in milliseconds.
+    #[serde(rename = "RunTime")]
+    pub run_time: Option<i64>,
+
+    #[serde(rename = "Message")]
+    pub message: Option<String>,
+
+    #[serde(rename = "StackTrace")]
+    pub stack_trace: Option<String>,
+
+    #[serde(rename = "TestTimestamp")]
+    pub test_timestamp: Option<String>,
+}
+
+impl ApexTestResult {
+    /// The test class name, if Salesforce returned it.
+    pub fn class_name(&self) -> Option<&str> {
+        self.apex_class.as_ref()?.name.as_deref()
+    }
+
+    /// Whether the method failed or its class didn't compile.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self.outcome,
+            ApexTestOutcome::Fail | ApexTestOutcome::CompileFail
+        )
+    }
+}
+
+/// The state of an asynchronous test run, from its queue items and the
+/// results recorded so far.
+///
+/// Returned by [`ToolingClient::test_run_summary`](crate::ToolingClient::test_run_summary).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TestRunSummary {
+    /// The run's `AsyncApexJob` ID, as returned by `runTestsAsynchronous`.
+    pub test_run_id: String,
+
+    /// One item per enqueued test class.
+    pub queue_items: Vec<ApexTestQueueItem>,
+
+    /// One result per test method that has finished, by class and method.
+    pub results: Vec<ApexTestResult>,
+}
+
+impl TestRunSummary {
+    /// Whether every enqueued class has finished running.
+    pub fn is_complete(&self) -> bool {
+        self.queue_items
+            .iter()
+            .all(|item| item.status.is_terminal())
+    }
+
+    /// Whether the run is complete, no class failed to run, and no test
+    /// failed.
+    pub fn is_success(&self) -> bool {
+        self.is_complete()
+            && self
+                .queue_items
+                .iter()
+                .all(|item| item.status == AsyncApexJobStatus::Completed)
+            && self.failures().next().is_none()
+    }
+
+    /// Methods that passed.
+    pub fn passed(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.outcome == ApexTestOutcome::Pass)
+            .count()
+    }
+
+    /// Methods that failed or didn't compile.
+    pub fn failed(&self) -> usize {
+        self.failures().count()
+    }
+
+    /// Results of the methods that failed or didn't compile.
+    pub fn failures(&self) -> impl Iterator<Item = &ApexTestResult> {
+        self.results.iter().filter(|r| r.is_failure())
+    }
+
+    /// Sum of the methods' run times, in milliseconds.
+    pub fn total_run_time(&self) -> i64 {
+        self.results.iter().filter_map(|r| r.run_time).sum()
+    }
+}
+
 // ============================================================================
 // Test Discovery Types (v65.0+)
 // ============================================================================
@@ -681,12 +1140,1142 @@ pub struct Reference {
     pub ref_type: Option<String>,
 }
 
+/// Typed response of the Apex completions endpoint.
+///
+/// Declarations are keyed by namespace, then by type name within it, e.g.
+/// `public_declarations["System"]["String"]`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ApexCompletions {
+    #[serde(rename = "publicDeclarations", default)]
+    pub public_declarations:
+        std::collections::HashMap<String, std::collections::HashMap<String, ApexTypeDeclaration>>,
+}
+
+impl ApexCompletions {
+    /// Look up a type, ignoring case as Apex does.
+    pub fn type_declaration(&self, namespace: &str, name: &str) -> Option<&ApexTypeDeclaration> {
+        self.public_declarations
+            .iter()
+            .find(|(ns, _)| ns.eq_ignore_ascii_case(namespace))
+            .and_then(|(_, types)| {
+                types
+                    .iter()
+                    .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                    .map(|(_, decl)| decl)
+            })
+    }
+}
+
+/// The public members of a system or managed-package type.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ApexTypeDeclaration {
+    #[serde(deserialize_with = "null_as_default")]
+    pub constructors: Vec<ApexMemberDeclaration>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub methods: Vec<ApexMemberDeclaration>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub properties: Vec<ApexMemberDeclaration>,
+}
+
+/// A constructor, method, or property offered as a completion.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ApexMemberDeclaration {
+    pub name: String,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub arg_types: Vec<String>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub parameters: Vec<Parameter>,
+
+    pub return_type: Option<String>,
+
+    pub is_static: Option<bool>,
+
+    pub method_doc: Option<String>,
+}
+
+/// The `SymbolTable` of a compiled Apex class: its declarations, their
+/// source positions, and what it references in other types.
+///
+/// Salesforce only builds a symbol table for a class that compiles.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SymbolTable {
+    pub id: Option<String>,
+
+    pub key: Option<String>,
+
+    pub name: String,
+
+    pub namespace: Option<String>,
+
+    pub parent_class: Option<String>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub interfaces: Vec<String>,
+
+    /// The class declaration itself.
+    pub table_declaration: Option<ApexSymbol>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub constructors: Vec<ApexSymbol>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub methods: Vec<ApexSymbol>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub properties: Vec<ApexSymbol>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub variables: Vec<ApexSymbol>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub inner_classes: Vec<SymbolTable>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub external_references: Vec<ExternalReference>,
+}
+
+impl SymbolTable {
+    /// Methods called `name`, overloads included, ignoring case.
+    pub fn methods_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ApexSymbol> {
+        self.methods
+            .iter()
+            .filter(move |m| m.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A declaration in a symbol table.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ApexSymbol {
+    pub name: String,
+
+    /// Declared type; `None` for constructors and methods.
+    #[serde(rename = "type")]
+    pub symbol_type: Option<String>,
+
+    pub location: Option<SymbolPosition>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub modifiers: Vec<String>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub annotations: Vec<SymbolAnnotation>,
+
+    /// Where the symbol is used within the class.
+    #[serde(deserialize_with = "null_as_default")]
+    pub references: Vec<SymbolPosition>,
+
+    /// Constructor and method parameters.
+    #[serde(deserialize_with = "null_as_default")]
+    pub parameters: Vec<Parameter>,
+
+    pub return_type: Option<String>,
+}
+
+/// A 1-based line and column in the class source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SymbolPosition {
+    pub line: i32,
+    pub column: i32,
+}
+
+/// An annotation on a declaration, e.g. `IsTest`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SymbolAnnotation {
+    pub name: String,
+}
+
+/// A type the class refers to, with the members it uses.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ExternalReference {
+    pub name: String,
+
+    pub namespace: Option<String>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub references: Vec<SymbolPosition>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub methods: Vec<ExternalMethod>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub variables: Vec<ApexSymbol>,
+}
+
+/// A method of another type called from the class.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ExternalMethod {
+    pub name: String,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub arg_types: Vec<String>,
+
+    pub return_type: Option<String>,
+
+    pub is_static: Option<bool>,
+
+    #[serde(deserialize_with = "null_as_default")]
+    pub references: Vec<SymbolPosition>,
+}
+
 // ============================================================================
-// Metadata Component Dependency (Beta)
+// Async Apex Job Types
 // ============================================================================
 
-#[cfg(feature = "dependencies")]
-pub use busbar_sf_client::MetadataComponentDependency;
+/// Status of an `AsyncApexJob`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum AsyncApexJobStatus {
+    Holding,
+    Queued,
+    Preparing,
+    Processing,
+    Aborted,
+    Completed,
+    Failed,
+    /// A status this crate doesn't know about.
+    #[serde(other)]
+    Unknown,
+}
+
+impl AsyncApexJobStatus {
+    /// Statuses of jobs that have not finished yet.
+    pub const ACTIVE: [AsyncApexJobStatus; 4] = [
+        AsyncApexJobStatus::Holding,
+        AsyncApexJobStatus::Queued,
+        AsyncApexJobStatus::Preparing,
+        AsyncApexJobStatus::Processing,
+    ];
+
+    /// Whether the job has finished, successfully or not.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            AsyncApexJobStatus::Aborted
+                | AsyncApexJobStatus::Completed
+                | AsyncApexJobStatus::Failed
+        )
+    }
+
+    /// The picklist value used in SOQL.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AsyncApexJobStatus::Holding => "Holding",
+            AsyncApexJobStatus::Queued => "Queued",
+            AsyncApexJobStatus::Preparing => "Preparing",
+            AsyncApexJobStatus::Processing => "Processing",
+            AsyncApexJobStatus::Aborted => "Aborted",
+            AsyncApexJobStatus::Completed => "Completed",
+            AsyncApexJobStatus::Failed => "Failed",
+            AsyncApexJobStatus::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Kind of an `AsyncApexJob`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum AsyncApexJobType {
+    Future,
+    Queueable,
+    BatchApex,
+    BatchApexWorker,
+    ScheduledApex,
+    SharingRecalculation,
+    TestRequest,
+    TestWorker,
+    ApexToken,
+    /// A job type this crate doesn't know about.
+    #[serde(other)]
+    Other,
+}
+
+impl AsyncApexJobType {
+    /// The picklist value used in SOQL.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AsyncApexJobType::Future => "Future",
+            AsyncApexJobType::Queueable => "Queueable",
+            AsyncApexJobType::BatchApex => "BatchApex",
+            AsyncApexJobType::BatchApexWorker => "BatchApexWorker",
+            AsyncApexJobType::ScheduledApex => "ScheduledApex",
+            AsyncApexJobType::SharingRecalculation => "SharingRecalculation",
+            AsyncApexJobType::TestRequest => "TestRequest",
+            AsyncApexJobType::TestWorker => "TestWorker",
+            AsyncApexJobType::ApexToken => "ApexToken",
+            AsyncApexJobType::Other => "Other",
+        }
+    }
+}
+
+/// AsyncApexJob record: a batch, queueable, future, or scheduled Apex run.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AsyncApexJob {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "JobType")]
+    pub job_type: AsyncApexJobType,
+
+    #[serde(rename = "Status")]
+    pub status: AsyncApexJobStatus,
+
+    #[serde(rename = "ApexClassId")]
+    pub apex_class_id: Option<String>,
+
+    #[serde(rename = "ApexClass")]
+    pub apex_class: Option<ApexClassOrTriggerRef>,
+
+    #[serde(rename = "MethodName")]
+    pub method_name: Option<String>,
+
+    #[serde(rename = "JobItemsProcessed")]
+    pub job_items_processed: Option<i64>,
+
+    #[serde(rename = "TotalJobItems")]
+    pub total_job_items: Option<i64>,
+
+    #[serde(rename = "NumberOfErrors")]
+    pub number_of_errors: Option<i64>,
+
+    /// First error message, for failed batches.
+    #[serde(rename = "ExtendedStatus")]
+    pub extended_status: Option<String>,
+
+    #[serde(rename = "ParentJobId")]
+    pub parent_job_id: Option<String>,
+
+    #[serde(rename = "CronTriggerId")]
+    pub cron_trigger_id: Option<String>,
+
+    #[serde(rename = "CreatedById")]
+    pub created_by_id: Option<String>,
+
+    #[serde(rename = "CreatedDate")]
+    pub created_date: Option<String>,
+
+    #[serde(rename = "CompletedDate")]
+    pub completed_date: Option<String>,
+}
+
+/// Job details referenced from a `CronTrigger`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CronJobDetail {
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+
+    /// `7` for scheduled Apex; other values are internal job kinds.
+    #[serde(rename = "JobType")]
+    pub job_type: Option<String>,
+}
+
+/// CronTrigger record: a scheduled job.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CronTrigger {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "CronExpression")]
+    pub cron_expression: Option<String>,
+
+    #[serde(rename = "CronJobDetail")]
+    pub cron_job_detail: Option<CronJobDetail>,
+
+    /// `WAITING`, `ACQUIRED`, `EXECUTING`, `COMPLETE`, `ERROR`, `DELETED`,
+    /// `PAUSED`, `BLOCKED`, or `PAUSED_BLOCKED`.
+    #[serde(rename = "State")]
+    pub state: Option<String>,
+
+    #[serde(rename = "NextFireTime")]
+    pub next_fire_time: Option<String>,
+
+    #[serde(rename = "PreviousFireTime")]
+    pub previous_fire_time: Option<String>,
+
+    #[serde(rename = "StartTime")]
+    pub start_time: Option<String>,
+
+    #[serde(rename = "EndTime")]
+    pub end_time: Option<String>,
+
+    #[serde(rename = "TimesTriggered")]
+    pub times_triggered: Option<i64>,
+
+    #[serde(rename = "TimeZoneSidKey")]
+    pub time_zone_sid_key: Option<String>,
+
+    #[serde(rename = "OwnerId")]
+    pub owner_id: Option<String>,
+}
+
+/// Filter for [`ToolingClient::async_apex_jobs`](crate::ToolingClient::async_apex_jobs).
+#[derive(Debug, Clone, Default)]
+pub struct AsyncApexJobFilter {
+    pub(crate) statuses: Vec<AsyncApexJobStatus>,
+    pub(crate) job_types: Vec<AsyncApexJobType>,
+    pub(crate) apex_class: Option<String>,
+    pub(crate) created_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub(crate) created_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub(crate) limit: Option<u32>,
+}
+
+impl AsyncApexJobFilter {
+    /// Match all jobs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only jobs that haven't finished.
+    pub fn active() -> Self {
+        Self::new().statuses(AsyncApexJobStatus::ACTIVE)
+    }
+
+    /// Only jobs in one of `statuses`.
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = AsyncApexJobStatus>) -> Self {
+        self.statuses.extend(statuses);
+        self
+    }
+
+    /// Only jobs of one of `job_types`.
+    pub fn job_types(mut self, job_types: impl IntoIterator<Item = AsyncApexJobType>) -> Self {
+        self.job_types.extend(job_types);
+        self
+    }
+
+    /// Only jobs running this Apex class.
+    pub fn apex_class(mut self, name: impl Into<String>) -> Self {
+        self.apex_class = Some(name.into());
+        self
+    }
+
+    /// Only jobs created before `time`.
+    pub fn created_before(mut self, time: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_before = Some(time);
+        self
+    }
+
+    /// Only jobs created more than `age` ago.
+    pub fn older_than(self, age: std::time::Duration) -> Self {
+        let age = chrono::Duration::from_std(age).unwrap_or(chrono::Duration::MAX);
+        let now = chrono::Utc::now();
+        self.created_before(
+            now.checked_sub_signed(age)
+                .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC),
+        )
+    }
+
+    /// Only jobs created after `time`.
+    pub fn created_after(mut self, time: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_after = Some(time);
+        self
+    }
+
+    /// Return at most `limit` jobs, newest first.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+// ============================================================================
+// Metadata Component Dependency (Beta)
+// ============================================================================
+
+#[cfg(feature = "dependencies")]
+pub use busbar_sf_client::MetadataComponentDependency;
+
+// ============================================================================
+// Bundle Types (Lightning Web Components, Aura, Static Resources)
+// ============================================================================
+
+/// LightningComponentBundle record from Tooling API.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LightningComponentBundle {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "DeveloperName")]
+    pub developer_name: String,
+
+    #[serde(rename = "MasterLabel")]
+    pub master_label: Option<String>,
+
+    #[serde(rename = "ApiVersion")]
+    pub api_version: Option<f64>,
+
+    #[serde(rename = "Description")]
+    pub description: Option<String>,
+
+    #[serde(rename = "IsExposed")]
+    pub is_exposed: Option<bool>,
+
+    #[serde(rename = "NamespacePrefix")]
+    pub namespace_prefix: Option<String>,
+
+    #[serde(rename = "LastModifiedDate")]
+    pub last_modified_date: Option<String>,
+}
+
+/// LightningComponentResource record: one file of an LWC bundle.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LightningComponentResource {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "LightningComponentBundleId")]
+    pub bundle_id: String,
+
+    /// Path within the project, e.g. `lwc/myCmp/myCmp.js`.
+    #[serde(rename = "FilePath")]
+    pub file_path: String,
+
+    /// `js`, `html`, `css`, `xml`, or `svg`.
+    #[serde(rename = "Format")]
+    pub format: Option<String>,
+
+    #[serde(rename = "Source")]
+    pub source: Option<String>,
+
+    #[serde(rename = "LastModifiedDate")]
+    pub last_modified_date: Option<String>,
+}
+
+/// An LWC bundle with its files.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LwcBundle {
+    pub bundle: LightningComponentBundle,
+    pub resources: Vec<LightningComponentResource>,
+}
+
+impl LwcBundle {
+    /// Find a file by its `FilePath`, or by the path's file name alone.
+    pub fn resource(&self, path: &str) -> Option<&LightningComponentResource> {
+        self.resources
+            .iter()
+            .find(|r| r.file_path == path || r.file_path.rsplit('/').next() == Some(path))
+    }
+}
+
+/// AuraDefinitionBundle record from Tooling API.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AuraDefinitionBundle {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "DeveloperName")]
+    pub developer_name: String,
+
+    #[serde(rename = "MasterLabel")]
+    pub master_label: Option<String>,
+
+    #[serde(rename = "ApiVersion")]
+    pub api_version: Option<f64>,
+
+    #[serde(rename = "Description")]
+    pub description: Option<String>,
+
+    #[serde(rename = "NamespacePrefix")]
+    pub namespace_prefix: Option<String>,
+
+    #[serde(rename = "LastModifiedDate")]
+    pub last_modified_date: Option<String>,
+}
+
+/// AuraDefinition record: one file of an Aura bundle.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AuraDefinition {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "AuraDefinitionBundleId")]
+    pub bundle_id: String,
+
+    /// `COMPONENT`, `CONTROLLER`, `HELPER`, `STYLE`, `RENDERER`, `DOCUMENTATION`, etc.
+    #[serde(rename = "DefType")]
+    pub def_type: String,
+
+    /// `XML`, `JS`, or `CSS`.
+    #[serde(rename = "Format")]
+    pub format: Option<String>,
+
+    #[serde(rename = "Source")]
+    pub source: Option<String>,
+
+    #[serde(rename = "LastModifiedDate")]
+    pub last_modified_date: Option<String>,
+}
+
+/// An Aura bundle with its definitions.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AuraBundle {
+    pub bundle: AuraDefinitionBundle,
+    pub definitions: Vec<AuraDefinition>,
+}
+
+impl AuraBundle {
+    /// Find a definition by `DefType` (case-insensitive).
+    pub fn definition(&self, def_type: &str) -> Option<&AuraDefinition> {
+        self.definitions
+            .iter()
+            .find(|d| d.def_type.eq_ignore_ascii_case(def_type))
+    }
+}
+
+/// StaticResource record from Tooling API (without its body).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StaticResource {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "Name")]
+    pub name: String,
+
+    /// MIME type; `application/zip` for multi-file archives.
+    #[serde(rename = "ContentType")]
+    pub content_type: Option<String>,
+
+    /// `Private` or `Public`.
+    #[serde(rename = "CacheControl")]
+    pub cache_control: Option<String>,
+
+    #[serde(rename = "BodyLength")]
+    pub body_length: Option<i64>,
+
+    #[serde(rename = "Description")]
+    pub description: Option<String>,
+
+    #[serde(rename = "NamespacePrefix")]
+    pub namespace_prefix: Option<String>,
+
+    #[serde(rename = "LastModifiedDate")]
+    pub last_modified_date: Option<String>,
+}
+
+/// How browsers and proxies may cache a static resource.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum CacheControl {
+    /// Cached per user only.
+    #[default]
+    Private,
+    /// Cached by shared caches too, including for guest users.
+    Public,
+}
+
+impl CacheControl {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheControl::Private => "Private",
+            CacheControl::Public => "Public",
+        }
+    }
+}
+
+/// A static resource to create or replace, for
+/// [`save_static_resource`](crate::ToolingClient::save_static_resource).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticResourceUpload {
+    pub name: String,
+    pub body: Vec<u8>,
+    /// MIME type, e.g. `application/javascript` or `application/zip`.
+    pub content_type: String,
+    pub cache_control: CacheControl,
+    pub description: Option<String>,
+    /// Gzip the body before uploading.
+    pub gzip: bool,
+}
+
+impl StaticResourceUpload {
+    pub fn new(
+        name: impl Into<String>,
+        body: impl Into<Vec<u8>>,
+        content_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            body: body.into(),
+            content_type: content_type.into(),
+            cache_control: CacheControl::default(),
+            description: None,
+            gzip: false,
+        }
+    }
+
+    pub fn cache_control(mut self, cache_control: CacheControl) -> Self {
+        self.cache_control = cache_control;
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Store the body gzipped, as `application/gzip`.
+    ///
+    /// Salesforce serves the compressed bytes as they are and never
+    /// inflates them, so this suits large data files that the code loading
+    /// them decompresses itself, not scripts or stylesheets a page
+    /// references directly.
+    pub fn gzip(mut self, gzip: bool) -> Self {
+        self.gzip = gzip;
+        self
+    }
+}
+
+/// A new LWC bundle, for
+/// [`create_lwc_bundle`](crate::ToolingClient::create_lwc_bundle) and
+/// [`push_lwc_bundle`](crate::ToolingClient::push_lwc_bundle).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewLwcBundle {
+    pub developer_name: String,
+    /// Defaults to the developer name.
+    pub master_label: Option<String>,
+    /// Defaults to the client's API version.
+    pub api_version: Option<f64>,
+    pub description: Option<String>,
+    /// Whether the component is available in Lightning App Builder.
+    pub is_exposed: bool,
+}
+
+impl NewLwcBundle {
+    pub fn new(developer_name: impl Into<String>) -> Self {
+        Self {
+            developer_name: developer_name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn master_label(mut self, label: impl Into<String>) -> Self {
+        self.master_label = Some(label.into());
+        self
+    }
+
+    pub fn api_version(mut self, version: f64) -> Self {
+        self.api_version = Some(version);
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn exposed(mut self, is_exposed: bool) -> Self {
+        self.is_exposed = is_exposed;
+        self
+    }
+}
+
+/// A new Aura bundle, for
+/// [`create_aura_bundle`](crate::ToolingClient::create_aura_bundle) and
+/// [`push_aura_bundle`](crate::ToolingClient::push_aura_bundle).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewAuraBundle {
+    pub developer_name: String,
+    /// Defaults to the developer name.
+    pub master_label: Option<String>,
+    /// Defaults to the client's API version.
+    pub api_version: Option<f64>,
+    pub description: Option<String>,
+}
+
+impl NewAuraBundle {
+    pub fn new(developer_name: impl Into<String>) -> Self {
+        Self {
+            developer_name: developer_name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn master_label(mut self, label: impl Into<String>) -> Self {
+        self.master_label = Some(label.into());
+        self
+    }
+
+    pub fn api_version(mut self, version: f64) -> Self {
+        self.api_version = Some(version);
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// What a bundle push changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundlePush {
+    pub bundle_id: String,
+    /// Whether the bundle itself had to be created.
+    pub bundle_created: bool,
+    /// File paths (LWC) or `DefType`s (Aura) that were added.
+    pub created: Vec<String>,
+    /// File paths or `DefType`s whose source was replaced.
+    pub updated: Vec<String>,
+}
+
+// ============================================================================
+// Schema Introspection Types
+// ============================================================================
+
+/// EntityDefinition record: an object's schema-level properties.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EntityDefinition {
+    /// The object's name for a standard object (`Account`) or its ID for
+    /// a custom one (`01I...`); this is what `CustomField.TableEnumOrId`
+    /// holds.
+    #[serde(rename = "DurableId")]
+    pub durable_id: String,
+
+    #[serde(rename = "QualifiedApiName")]
+    pub qualified_api_name: String,
+
+    #[serde(rename = "DeveloperName")]
+    pub developer_name: Option<String>,
+
+    #[serde(rename = "Label")]
+    pub label: Option<String>,
+
+    #[serde(rename = "PluralLabel")]
+    pub plural_label: Option<String>,
+
+    #[serde(rename = "KeyPrefix")]
+    pub key_prefix: Option<String>,
+
+    #[serde(rename = "NamespacePrefix")]
+    pub namespace_prefix: Option<String>,
+
+    #[serde(rename = "IsCustomizable")]
+    pub is_customizable: Option<bool>,
+
+    #[serde(rename = "IsCustomSetting")]
+    pub is_custom_setting: Option<bool>,
+
+    #[serde(rename = "IsQueryable")]
+    pub is_queryable: Option<bool>,
+
+    #[serde(rename = "IsApexTriggerable")]
+    pub is_apex_triggerable: Option<bool>,
+
+    #[serde(rename = "LastModifiedDate")]
+    pub last_modified_date: Option<String>,
+}
+
+/// FieldDefinition record: a field's schema-level properties.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FieldDefinition {
+    /// `Object.Field` for standard fields; `ObjectId.FieldId` for custom ones.
+    #[serde(rename = "DurableId")]
+    pub durable_id: String,
+
+    #[serde(rename = "QualifiedApiName")]
+    pub qualified_api_name: String,
+
+    #[serde(rename = "Label")]
+    pub label: Option<String>,
+
+    /// Display type with size, e.g. `Text(80)` or `Lookup(Account)`.
+    #[serde(rename = "DataType")]
+    pub data_type: Option<String>,
+
+    #[serde(rename = "Length")]
+    pub length: Option<i32>,
+
+    #[serde(rename = "Precision")]
+    pub precision: Option<i32>,
+
+    #[serde(rename = "Scale")]
+    pub scale: Option<i32>,
+
+    #[serde(rename = "IsNillable")]
+    pub is_nillable: Option<bool>,
+
+    #[serde(rename = "IsIndexed")]
+    pub is_indexed: Option<bool>,
+
+    #[serde(rename = "IsCalculated")]
+    pub is_calculated: Option<bool>,
+
+    #[serde(rename = "IsFieldHistoryTracked")]
+    pub is_field_history_tracked: Option<bool>,
+
+    #[serde(rename = "Description")]
+    pub description: Option<String>,
+
+    #[serde(rename = "NamespacePrefix")]
+    pub namespace_prefix: Option<String>,
+
+    #[serde(rename = "LastModifiedDate")]
+    pub last_modified_date: Option<String>,
+}
+
+impl FieldDefinition {
+    /// Whether this is a custom field.
+    pub fn is_custom(&self) -> bool {
+        self.qualified_api_name.ends_with("__c")
+    }
+}
+
+/// CustomField record from Tooling API.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CustomField {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    /// The field name without namespace or `__c`.
+    #[serde(rename = "DeveloperName")]
+    pub developer_name: String,
+
+    /// See [`EntityDefinition::durable_id`].
+    #[serde(rename = "TableEnumOrId")]
+    pub table_enum_or_id: Option<String>,
+
+    #[serde(rename = "NamespacePrefix")]
+    pub namespace_prefix: Option<String>,
+
+    /// `unmanaged`, `installed`, `released`, etc.
+    #[serde(rename = "ManageableState")]
+    pub manageable_state: Option<String>,
+
+    /// `Object.Field__c`; only returned when querying a single field.
+    #[serde(rename = "FullName")]
+    pub full_name: Option<String>,
+
+    /// Only returned when querying a single field.
+    #[serde(rename = "Metadata")]
+    pub metadata: Option<CustomFieldMetadata>,
+
+    #[serde(rename = "CreatedDate")]
+    pub created_date: Option<String>,
+
+    #[serde(rename = "LastModifiedDate")]
+    pub last_modified_date: Option<String>,
+}
+
+impl CustomField {
+    /// The field's API name, e.g. `ns__Score__c`.
+    pub fn api_name(&self) -> String {
+        match self.namespace_prefix.as_deref() {
+            Some(ns) if !ns.is_empty() => format!("{}__{}__c", ns, self.developer_name),
+            _ => format!("{}__c", self.developer_name),
+        }
+    }
+}
+
+/// The commonly used parts of a custom field's metadata.
+///
+/// Everything else (picklist values, lookup filters, and so on) is kept in
+/// `other` as returned.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomFieldMetadata {
+    /// Metadata API field type, e.g. `Text`, `Number`, `Lookup`.
+    #[serde(rename = "type")]
+    pub field_type: Option<String>,
+    pub label: Option<String>,
+    pub description: Option<String>,
+    pub inline_help_text: Option<String>,
+    pub length: Option<i32>,
+    pub precision: Option<i32>,
+    pub scale: Option<i32>,
+    pub required: Option<bool>,
+    pub unique: Option<bool>,
+    pub external_id: Option<bool>,
+    pub default_value: Option<String>,
+    pub formula: Option<String>,
+    pub reference_to: Option<String>,
+    pub relationship_name: Option<String>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+// ============================================================================
+// Metadata Container Types
+// ============================================================================
+
+/// A source file saved through a `MetadataContainer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerMemberKind {
+    ApexClass,
+    ApexTrigger,
+    ApexPage,
+    ApexComponent,
+}
+
+impl ContainerMemberKind {
+    /// The Tooling object holding the saved source, e.g. `ApexClass`.
+    pub fn entity_sobject(&self) -> &'static str {
+        match self {
+            ContainerMemberKind::ApexClass => "ApexClass",
+            ContainerMemberKind::ApexTrigger => "ApexTrigger",
+            ContainerMemberKind::ApexPage => "ApexPage",
+            ContainerMemberKind::ApexComponent => "ApexComponent",
+        }
+    }
+
+    /// The member object added to the container, e.g. `ApexClassMember`.
+    pub fn member_sobject(&self) -> &'static str {
+        match self {
+            ContainerMemberKind::ApexClass => "ApexClassMember",
+            ContainerMemberKind::ApexTrigger => "ApexTriggerMember",
+            ContainerMemberKind::ApexPage => "ApexPageMember",
+            ContainerMemberKind::ApexComponent => "ApexComponentMember",
+        }
+    }
+}
+
+/// New source for an existing class, trigger, page, or component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerMember {
+    pub kind: ContainerMemberKind,
+    /// ID of the record being changed (`ContentEntityId`).
+    pub entity_id: String,
+    /// The full new source.
+    pub body: String,
+}
+
+impl ContainerMember {
+    pub fn new(
+        kind: ContainerMemberKind,
+        entity_id: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            entity_id: entity_id.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// State of a `ContainerAsyncRequest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ContainerAsyncRequestState {
+    Queued,
+    /// A newer save of the same container superseded this one.
+    Invalidated,
+    Completed,
+    /// The source did not compile; see the request's component failures.
+    Failed,
+    /// Salesforce hit an internal error; see `ErrorMsg`.
+    Error,
+    Aborted,
+    /// A state this crate doesn't know about.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ContainerAsyncRequestState {
+    /// Whether the request has finished, successfully or not.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, ContainerAsyncRequestState::Queued)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContainerAsyncRequestState::Queued => "Queued",
+            ContainerAsyncRequestState::Invalidated => "Invalidated",
+            ContainerAsyncRequestState::Completed => "Completed",
+            ContainerAsyncRequestState::Failed => "Failed",
+            ContainerAsyncRequestState::Error => "Error",
+            ContainerAsyncRequestState::Aborted => "Aborted",
+            ContainerAsyncRequestState::Unknown => "Unknown",
+        }
+    }
+}
+
+/// ContainerAsyncRequest record: the compile and save of a `MetadataContainer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContainerAsyncRequest {
+    #[serde(rename = "Id")]
+    pub id: String,
+
+    #[serde(rename = "MetadataContainerId")]
+    pub metadata_container_id: Option<String>,
+
+    #[serde(rename = "State")]
+    pub state: ContainerAsyncRequestState,
+
+    #[serde(rename = "IsCheckOnly")]
+    pub is_check_only: Option<bool>,
+
+    #[serde(rename = "ErrorMsg")]
+    pub error_msg: Option<String>,
+
+    #[serde(rename = "DeployDetails")]
+    pub deploy_details: Option<ContainerDeployDetails>,
+}
+
+impl ContainerAsyncRequest {
+    /// Whether every member compiled and was saved (or, for a check-only
+    /// request, would have been).
+    pub fn is_success(&self) -> bool {
+        self.state == ContainerAsyncRequestState::Completed
+    }
+
+    /// Compile errors, in the order Salesforce reported them.
+    pub fn compile_errors(&self) -> &[ContainerComponentMessage] {
+        self.deploy_details
+            .as_ref()
+            .map_or(&[], |d| d.component_failures.as_slice())
+    }
+}
+
+/// Per-component outcome of a `ContainerAsyncRequest`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerDeployDetails {
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub component_failures: Vec<ContainerComponentMessage>,
+
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub component_successes: Vec<ContainerComponentMessage>,
+}
+
+/// A compile error or success message for one saved file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerComponentMessage {
+    pub component_type: Option<String>,
+    pub full_name: Option<String>,
+    pub file_name: Option<String>,
+    pub line_number: Option<i32>,
+    pub column_number: Option<i32>,
+    pub problem: Option<String>,
+    /// `Error` or `Warning`.
+    pub problem_type: Option<String>,
+}
+
+impl std::fmt::Display for ContainerComponentMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self
+            .file_name
+            .as_deref()
+            .or(self.full_name.as_deref())
+            .unwrap_or("<unknown>");
+        write!(f, "{}", name)?;
+        if let Some(line) = self.line_number {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column_number {
+                write!(f, ":{}", column)?;
+            }
+        }
+        write!(
+            f,
+            ": {}",
+            self.problem.as_deref().unwrap_or("unknown problem")
+        )
+    }
+}
 
 #[cfg(test)]
 mod tests {
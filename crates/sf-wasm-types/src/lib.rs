@@ -134,11 +134,59 @@ pub struct SalesforceApiError {
 /// Request for SOQL query operations.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryRequest {
-    /// SOQL query string.
+    /// SOQL query string. With `binds`, a template whose `:name`
+    /// placeholders the host fills in.
     pub soql: String,
     /// If true, include deleted/archived records (queryAll endpoint).
     #[serde(default)]
     pub include_deleted: bool,
+    /// Values for the `:name` placeholders in `soql`. When present, `soql`
+    /// may not contain string literals; the host escapes each value.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub binds: std::collections::BTreeMap<String, SoqlBind>,
+}
+
+impl QueryRequest {
+    /// A query with no binds.
+    pub fn new(soql: impl Into<String>) -> Self {
+        Self {
+            soql: soql.into(),
+            include_deleted: false,
+            binds: Default::default(),
+        }
+    }
+
+    /// Bind a value to the `:name` placeholder.
+    pub fn bind(mut self, name: impl Into<String>, value: SoqlBind) -> Self {
+        self.binds.insert(name.into(), value);
+        self
+    }
+}
+
+/// A value bound to a `:name` placeholder in a [`QueryRequest`].
+///
+/// Serialized as `{"type": "string", "value": "Acme"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum SoqlBind {
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Boolean(bool),
+    /// An integer.
+    Integer(i64),
+    /// A decimal number.
+    Double(f64),
+    /// Text, quoted and escaped by the host.
+    String(String),
+    /// A 15- or 18-character record ID.
+    Id(String),
+    /// A date, `YYYY-MM-DD`.
+    Date(String),
+    /// A date/time in RFC 3339 form.
+    DateTime(String),
+    /// A list for `IN`/`NOT IN`.
+    List(Vec<SoqlBind>),
 }
 
 /// Response from a SOQL query.
@@ -153,6 +201,50 @@ pub struct QueryResponse {
     /// URL for the next page (if `done` is false).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_records_url: Option<String>,
+    /// Set when the host's query memory budget for this invocation ran
+    /// out. The remaining records are held host-side; read them with
+    /// `sf_query_cursor_next`. `next_records_url` is then `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl QueryResponse {
+    /// Deserialize this page's records into `T`.
+    ///
+    /// Each record's `attributes` object, and those of related and child
+    /// records, is dropped first, so `T` only needs fields for the queried
+    /// columns.
+    pub fn records_as<T: serde::de::DeserializeOwned>(&self) -> Result<Vec<T>, serde_json::Error> {
+        records_as(&self.records)
+    }
+}
+
+fn records_as<T: serde::de::DeserializeOwned>(
+    records: &[serde_json::Value],
+) -> Result<Vec<T>, serde_json::Error> {
+    records
+        .iter()
+        .map(|record| {
+            let mut record = record.clone();
+            strip_record_attributes(&mut record);
+            serde_json::from_value(record)
+        })
+        .collect()
+}
+
+/// Remove the `attributes` objects Salesforce adds to query records,
+/// recursing into relationship fields and subquery results.
+pub fn strip_record_attributes(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            if map.get("attributes").is_some_and(|a| a.is_object()) {
+                map.remove("attributes");
+            }
+            map.values_mut().for_each(strip_record_attributes);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_record_attributes),
+        _ => {}
+    }
 }
 
 /// Request to fetch the next page of query results.
@@ -162,6 +254,32 @@ pub struct QueryMoreRequest {
     pub next_records_url: String,
 }
 
+/// Request for the next records held behind a query cursor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryCursorRequest {
+    /// The `cursor` from a [`QueryResponse`].
+    pub cursor: String,
+    /// Most records to return. The host applies its own limit when unset
+    /// or larger.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_records: Option<u32>,
+}
+
+/// Records read from a query cursor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryCursorPage {
+    pub records: Vec<serde_json::Value>,
+    /// Whether the cursor is exhausted. The host forgets it once it is.
+    pub done: bool,
+}
+
+impl QueryCursorPage {
+    /// Deserialize these records into `T`, as [`QueryResponse::records_as`].
+    pub fn records_as<T: serde::de::DeserializeOwned>(&self) -> Result<Vec<T>, serde_json::Error> {
+        records_as(&self.records)
+    }
+}
+
 // =============================================================================
 // REST API: CRUD Operations
 // =============================================================================
@@ -205,6 +323,9 @@ pub struct UpdateRequest {
     pub id: String,
     /// Fields to update.
     pub record: serde_json::Value,
+    /// Only update if the record still meets this condition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precondition: Option<WritePrecondition>,
 }
 
 /// Request to delete a record.
@@ -214,6 +335,24 @@ pub struct DeleteRequest {
     pub sobject: String,
     /// Record ID.
     pub id: String,
+    /// Only delete if the record still meets this condition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precondition: Option<WritePrecondition>,
+}
+
+/// Condition a record must meet for an update or delete to go ahead.
+///
+/// A write whose condition no longer holds, because someone else changed
+/// the record since it was read, fails with `PRECONDITION_FAILED`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WritePrecondition {
+    /// The record's ETag must be unchanged. Only objects that return an
+    /// ETag support this.
+    IfMatch(String),
+    /// The record must not have been modified after this HTTP date, e.g.
+    /// `Wed, 21 Oct 2015 07:28:00 GMT`.
+    IfUnmodifiedSince(String),
 }
 
 /// Request that identifies a resource by ID only (used for various single-ID operations).
@@ -236,6 +375,17 @@ pub struct UpsertRequest {
     pub record: serde_json::Value,
 }
 
+/// Request that identifies a record by an external ID field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalIdRequest {
+    /// SObject type.
+    pub sobject: String,
+    /// External ID field name.
+    pub external_id_field: String,
+    /// External ID value.
+    pub external_id_value: String,
+}
+
 /// Response from an upsert operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpsertResponse {
@@ -257,6 +407,175 @@ pub struct DescribeSObjectRequest {
     pub sobject: String,
 }
 
+/// Describe of an SObject, as returned by `sf_describe_sobject`.
+///
+/// Covers the parts of the describe guests use; read the untyped response
+/// for anything else.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SObjectDescribe {
+    pub name: String,
+    pub label: String,
+    #[serde(default)]
+    pub label_plural: Option<String>,
+    /// Three-character ID prefix of the object's records.
+    #[serde(default)]
+    pub key_prefix: Option<String>,
+    #[serde(default)]
+    pub custom: bool,
+    #[serde(default)]
+    pub createable: bool,
+    #[serde(default)]
+    pub updateable: bool,
+    #[serde(default)]
+    pub deletable: bool,
+    #[serde(default)]
+    pub queryable: bool,
+    #[serde(default)]
+    pub searchable: bool,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub fields: Vec<FieldDescribe>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub child_relationships: Vec<ChildRelationship>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub record_type_infos: Vec<RecordTypeInfo>,
+}
+
+impl SObjectDescribe {
+    /// Look up a field by API name, ignoring case.
+    pub fn field(&self, name: &str) -> Option<&FieldDescribe> {
+        self.fields
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The record type used when none is given.
+    pub fn default_record_type(&self) -> Option<&RecordTypeInfo> {
+        self.record_type_infos
+            .iter()
+            .find(|rt| rt.default_record_type_mapping)
+    }
+}
+
+/// A field in an [`SObjectDescribe`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDescribe {
+    pub name: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub label: String,
+    /// Salesforce field type, e.g. `string`, `reference`, `picklist`.
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub custom: Option<bool>,
+    /// Maximum length for text fields.
+    #[serde(default)]
+    pub length: Option<i32>,
+    #[serde(default)]
+    pub precision: Option<i32>,
+    #[serde(default)]
+    pub scale: Option<i32>,
+    #[serde(default)]
+    pub nillable: bool,
+    #[serde(default)]
+    pub createable: bool,
+    #[serde(default)]
+    pub updateable: bool,
+    #[serde(default)]
+    pub filterable: bool,
+    #[serde(default)]
+    pub unique: bool,
+    #[serde(default)]
+    pub external_id: bool,
+    #[serde(default)]
+    pub calculated: bool,
+    #[serde(default)]
+    pub name_field: Option<bool>,
+    #[serde(default)]
+    pub defaulted_on_create: Option<bool>,
+    /// Target objects of a lookup or master-detail field.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub reference_to: Vec<String>,
+    #[serde(default)]
+    pub relationship_name: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub picklist_values: Vec<PicklistEntry>,
+    #[serde(default)]
+    pub dependent_picklist: Option<bool>,
+    #[serde(default)]
+    pub restricted_picklist: Option<bool>,
+}
+
+impl FieldDescribe {
+    /// Whether a value must be supplied when creating a record.
+    pub fn is_required_on_create(&self) -> bool {
+        self.createable && !self.nillable && self.defaulted_on_create != Some(true)
+    }
+
+    /// Active picklist values.
+    pub fn active_picklist_values(&self) -> impl Iterator<Item = &PicklistEntry> {
+        self.picklist_values.iter().filter(|p| p.active)
+    }
+}
+
+/// A picklist value in a [`FieldDescribe`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PicklistEntry {
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub value: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub label: String,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub default_value: bool,
+}
+
+/// A relationship from child records to an [`SObjectDescribe`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildRelationship {
+    #[serde(rename = "childSObject")]
+    pub child_sobject: String,
+    /// Lookup field on the child.
+    pub field: String,
+    /// Name for subqueries, e.g. `Contacts`.
+    #[serde(default)]
+    pub relationship_name: Option<String>,
+    #[serde(default)]
+    pub cascade_delete: Option<bool>,
+}
+
+/// A record type available on an [`SObjectDescribe`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordTypeInfo {
+    pub name: String,
+    pub record_type_id: String,
+    #[serde(default)]
+    pub developer_name: Option<String>,
+    #[serde(default)]
+    pub active: bool,
+    /// Whether the running user can assign it.
+    #[serde(default)]
+    pub available: bool,
+    #[serde(default)]
+    pub default_record_type_mapping: bool,
+    #[serde(default)]
+    pub master: Option<bool>,
+}
+
+/// Treat an explicit `null` like a missing value.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 // =============================================================================
 // REST API: Search (SOSL)
 // =============================================================================
@@ -299,6 +618,17 @@ pub struct CompositeSubrequest {
     /// Optional request body.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<serde_json::Value>,
+    /// Headers for this subrequest, e.g. `Sforce-Auto-Assign` or `If-Match`.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub http_headers: std::collections::BTreeMap<String, String>,
+}
+
+impl CompositeSubrequest {
+    /// Add a header to this subrequest.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.http_headers.insert(name.into(), value.into());
+        self
+    }
 }
 
 /// Response from a composite API call.
@@ -334,6 +664,17 @@ pub struct CompositeBatchSubrequest {
     /// Optional request body.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rich_input: Option<serde_json::Value>,
+    /// Headers for this subrequest.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub http_headers: std::collections::BTreeMap<String, String>,
+}
+
+impl CompositeBatchSubrequest {
+    /// Add a header to this subrequest.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.http_headers.insert(name.into(), value.into());
+        self
+    }
 }
 
 /// Response from a composite batch API call.
@@ -375,6 +716,54 @@ pub struct CompositeTreeResult {
     pub errors: Vec<SalesforceApiError>,
 }
 
+/// Request for a composite graph API call.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompositeGraphRequest {
+    /// Independent graphs; each succeeds or rolls back as a whole.
+    pub graphs: Vec<GraphRequest>,
+}
+
+/// One graph in a composite graph call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphRequest {
+    /// Identifies the graph in the response.
+    pub graph_id: String,
+    /// The graph's nodes, which may reference earlier nodes as
+    /// `@{referenceId.field}`.
+    pub subrequests: Vec<CompositeSubrequest>,
+}
+
+/// Response from a composite graph API call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositeGraphResponse {
+    pub graphs: Vec<GraphResponse>,
+}
+
+/// Outcome of one graph in a composite graph call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphResponse {
+    pub graph_id: String,
+    /// False if any node failed, in which case the whole graph was rolled
+    /// back.
+    pub is_successful: bool,
+    pub responses: Vec<CompositeSubresponse>,
+}
+
+impl GraphResponse {
+    /// The response to the node with `reference_id`.
+    pub fn response(&self, reference_id: &str) -> Option<&CompositeSubresponse> {
+        self.responses
+            .iter()
+            .find(|r| r.reference_id == reference_id)
+    }
+
+    /// The ID of the record created or upserted by the node with
+    /// `reference_id`.
+    pub fn created_id(&self, reference_id: &str) -> Option<&str> {
+        self.response(reference_id)?.body.get("id")?.as_str()
+    }
+}
+
 // =============================================================================
 // REST API: Collections (Batch CRUD)
 // =============================================================================
@@ -430,6 +819,20 @@ pub struct DeleteMultipleRequest {
     pub all_or_none: bool,
 }
 
+/// Request to permanently delete records from the Recycle Bin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmptyRecycleBinRequest {
+    /// IDs of deleted records (up to 200).
+    pub ids: Vec<String>,
+}
+
+/// Request to restore deleted records from the Recycle Bin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndeleteRequest {
+    /// IDs of deleted records (up to 200).
+    pub ids: Vec<String>,
+}
+
 /// Result of a single record in a collection operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionResult {
@@ -462,30 +865,56 @@ pub struct ApiVersion {
 // Bulk API 2.0
 // =============================================================================
 
+/// Bulk ingest operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkOperation {
+    Insert,
+    Update,
+    /// Match records on [`BulkCreateIngestJobRequest::external_id_field`].
+    Upsert,
+    /// Move records to the recycle bin.
+    Delete,
+    /// Delete records permanently.
+    #[serde(rename = "hardDelete", alias = "harddelete")]
+    HardDelete,
+}
+
+/// Column delimiter of ingest CSV data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ColumnDelimiter {
+    #[default]
+    Comma,
+    Tab,
+    Semicolon,
+    Pipe,
+    Backquote,
+    Caret,
+}
+
+/// Line ending of ingest CSV data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
 /// Request to create a bulk ingest job.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkCreateIngestJobRequest {
     /// SObject API name.
     pub sobject: String,
-    /// Operation: insert, update, upsert, delete, hardDelete.
-    pub operation: String,
+    pub operation: BulkOperation,
     /// External ID field name (required for upsert).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub external_id_field: Option<String>,
-    /// Column delimiter (COMMA, TAB, SEMICOLON, PIPE, BACKQUOTE, CARET).
-    #[serde(default = "default_column_delimiter")]
-    pub column_delimiter: String,
-    /// Line ending (LF, CRLF).
-    #[serde(default = "default_line_ending")]
-    pub line_ending: String,
-}
-
-fn default_column_delimiter() -> String {
-    "COMMA".to_string()
-}
-
-fn default_line_ending() -> String {
-    "LF".to_string()
+    #[serde(default)]
+    pub column_delimiter: ColumnDelimiter,
+    #[serde(default)]
+    pub line_ending: LineEnding,
 }
 
 /// Response from bulk job operations (create, close, abort, get).
@@ -505,6 +934,12 @@ pub struct BulkJobResponse {
     pub system_modstamp: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// Parallel or Serial.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency_mode: Option<String>,
+    /// V2Ingest, Classic, or BigObjectIngest. Only set for ingest jobs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_type: Option<String>,
 }
 
 /// Request to upload CSV data to a bulk ingest job.
@@ -534,7 +969,25 @@ pub struct BulkJobResultsResponse {
     pub csv_data: String,
 }
 
+/// Request to list ingest jobs. Every filter is optional; an empty request
+/// lists all ingest jobs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkGetAllIngestJobsRequest {
+    /// Only jobs with PK chunking enabled (`true`) or disabled (`false`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_pk_chunking_enabled: Option<bool>,
+    /// Only jobs of this type: V2Ingest, Classic, or BigObjectIngest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_type: Option<String>,
+    /// Only jobs with this concurrency mode: Parallel or Serial.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency_mode: Option<String>,
+}
+
 /// Response from listing all ingest jobs.
+///
+/// The host follows `nextRecordsUrl` itself, so `records` holds every
+/// matching job and `done` is always true.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkJobListResponse {
     pub records: Vec<BulkJobResponse>,
@@ -543,6 +996,30 @@ pub struct BulkJobListResponse {
     pub next_records_url: Option<String>,
 }
 
+/// Bulk query operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryOperation {
+    #[default]
+    Query,
+    /// Include deleted and archived records.
+    #[serde(alias = "queryall")]
+    QueryAll,
+}
+
+/// Request to create a bulk query job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkCreateQueryJobRequest {
+    /// SOQL query. With `binds`, a template whose `:name` placeholders the
+    /// host fills in, as for [`QueryRequest`].
+    pub soql: String,
+    #[serde(default)]
+    pub operation: QueryOperation,
+    /// Values for the `:name` placeholders in `soql`.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub binds: std::collections::BTreeMap<String, SoqlBind>,
+}
+
 /// Request to get query job results with optional pagination.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkQueryResultsRequest {
@@ -572,10 +1049,21 @@ pub struct ToolingQueryRequest {
     pub soql: String,
 }
 
+/// Request to fetch the next page of Tooling query results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolingQueryMoreRequest {
+    /// The `next_records_url` from the previous Tooling query response.
+    pub next_records_url: String,
+}
+
 /// Request to execute anonymous Apex code.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecuteAnonymousRequest {
     pub apex_code: String,
+    /// Trace the run and return its debug log. Compile and runtime
+    /// failures then come back in the response instead of as errors.
+    #[serde(default)]
+    pub capture_log: bool,
 }
 
 /// Response from executing anonymous Apex code.
@@ -593,6 +1081,9 @@ pub struct ExecuteAnonymousResponse {
     pub line: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column: Option<i32>,
+    /// The run's debug log, when `capture_log` was set and one was written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug_log: Option<String>,
 }
 
 /// Request to get a Tooling API record.
@@ -609,6 +1100,27 @@ pub struct ToolingCreateRequest {
     pub record: serde_json::Value,
 }
 
+/// Request to update fields on a Tooling API record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolingUpdateRequest {
+    pub sobject: String,
+    pub id: String,
+    /// Fields to change; fields not given keep their values.
+    pub record: serde_json::Value,
+}
+
+/// Request to upsert a Tooling API record by an external ID field.
+///
+/// Only Tooling objects with an external ID or idLookup field, such as
+/// `DeveloperName` on `DebugLevel`, can be upserted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolingUpsertRequest {
+    pub sobject: String,
+    pub external_id_field: String,
+    pub external_id_value: String,
+    pub record: serde_json::Value,
+}
+
 /// Request to delete a Tooling API record.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolingDeleteRequest {
@@ -616,6 +1128,116 @@ pub struct ToolingDeleteRequest {
     pub id: String,
 }
 
+/// Request to list async Apex jobs (batch, queueable, future, scheduled).
+///
+/// Empty lists and `None` fields don't filter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AsyncApexJobsRequest {
+    /// Job statuses, e.g. `Queued` or `Processing`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<String>,
+    /// Job types, e.g. `BatchApex` or `Queueable`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub job_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apex_class: Option<String>,
+    /// Only jobs created at least this many minutes ago.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub older_than_minutes: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Request to abort an async Apex job or scheduled job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbortJobRequest {
+    /// AsyncApexJob or CronTrigger ID.
+    pub id: String,
+}
+
+/// Request to enqueue Apex tests with `runTestsAsynchronous`.
+///
+/// Give classes or suites by name or ID, or only a `test_level` such as
+/// `RunLocalTests`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolingRunTestsRequest {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub class_names: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub class_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suite_names: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suite_ids: Vec<String>,
+    /// `RunSpecifiedTests`, `RunLocalTests`, or `RunAllTestsInOrg`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_level: Option<String>,
+    /// Stop after this many failures; -1 for no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_failed_tests: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_code_coverage: Option<bool>,
+}
+
+/// Response from `sf_tooling_run_tests`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolingRunTestsResponse {
+    /// The run's AsyncApexJob ID, for `sf_tooling_test_run_summary`.
+    pub test_run_id: String,
+}
+
+/// Request for the state of an asynchronous test run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestRunSummaryRequest {
+    pub test_run_id: String,
+}
+
+/// One enqueued test class in a test run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestClassStatus {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+    /// `Queued`, `Processing`, `Completed`, `Failed`, and so on.
+    pub status: String,
+    /// Why the class didn't run, e.g. a compile error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_status: Option<String>,
+}
+
+/// The outcome of one test method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestMethodResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method_name: Option<String>,
+    /// `Pass`, `Fail`, `CompileFail`, or `Skip`.
+    pub outcome: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_time_ms: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_trace: Option<String>,
+}
+
+/// State of an asynchronous test run, from `sf_tooling_test_run_summary`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestRunSummary {
+    pub test_run_id: String,
+    /// Whether every enqueued class has finished.
+    pub complete: bool,
+    /// Whether the run is complete with every class run and no failures.
+    pub success: bool,
+    pub passed: u32,
+    pub failed: u32,
+    #[serde(default)]
+    pub classes: Vec<TestClassStatus>,
+    /// Finished methods, by class and method name.
+    #[serde(default)]
+    pub results: Vec<TestMethodResult>,
+}
+
 // =============================================================================
 // Metadata API
 // =============================================================================
@@ -645,6 +1267,11 @@ pub struct MetadataDeployOptions {
     /// If true, roll back on error.
     #[serde(default = "default_true")]
     pub rollback_on_error: bool,
+    /// If true, components deleted by the package's destructive changes
+    /// are hard deleted instead of going to the recycle bin. Not allowed
+    /// in production orgs.
+    #[serde(default)]
+    pub purge_on_delete: bool,
 }
 
 fn default_true() -> bool {
@@ -686,6 +1313,65 @@ pub struct MetadataDeployResult {
     pub number_tests_completed: i32,
     #[serde(default)]
     pub number_tests_total: i32,
+    /// What failed, present when the status was checked with
+    /// `include_details`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<DeployDetails>,
+}
+
+/// Component failures and test results of a deployment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeployDetails {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub component_failures: Vec<DeployComponentFailure>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub test_failures: Vec<DeployTestFailure>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub code_coverage_warnings: Vec<CodeCoverageWarning>,
+}
+
+/// A component that failed to deploy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployComponentFailure {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_number: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_number: Option<u32>,
+    pub problem: String,
+    /// "Error" or "Warning".
+    pub problem_type: String,
+}
+
+/// An Apex test that failed during a deployment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployTestFailure {
+    /// The test class.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_trace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+/// A class or trigger short of the required test coverage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeCoverageWarning {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
 }
 
 /// Request to retrieve metadata as a zip package.
@@ -745,6 +1431,39 @@ pub struct MetadataRetrieveResult {
     pub zip_base64: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// Each file in the retrieved package, once the retrieve is done.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_properties: Vec<FileProperties>,
+    /// Components that couldn't be retrieved, such as members the org
+    /// doesn't have.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<RetrieveMessage>,
+}
+
+/// A file in a retrieved package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileProperties {
+    pub full_name: String,
+    /// Path in the zip, such as `unpackaged/classes/MyClass.cls`.
+    pub file_name: String,
+    pub component_type: String,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_prefix: Option<String>,
+    /// "unmanaged", "installed", and so on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manageable_state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified_by_name: Option<String>,
+}
+
+/// A problem with one file of a retrieve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetrieveMessage {
+    pub file_name: String,
+    pub problem: String,
 }
 
 /// Request to list metadata components of a given type.
@@ -1032,6 +1751,9 @@ pub struct InvokeQuickActionRequest {
 // =============================================================================
 
 /// Request to get deleted records.
+///
+/// The host clamps `start` to Salesforce's 30-day replication history and
+/// `end` to the current time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetDeletedRequest {
     pub sobject: String,
@@ -1058,7 +1780,7 @@ pub struct DeletedRecord {
     pub deleted_date: String,
 }
 
-/// Request to get updated records.
+/// Request to get updated records. Clamped like [`GetDeletedRequest`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetUpdatedRequest {
     pub sobject: String,
@@ -1074,6 +1796,115 @@ pub struct GetUpdatedResult {
     pub latest_date_covered: String,
 }
 
+/// Request for all changes to an SObject type since a watermark.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangesSinceRequest {
+    pub sobject: String,
+    /// ISO 8601 date-time; the watermark returned by the previous call.
+    pub watermark: String,
+}
+
+/// What happened to a record in a [`ChangeJournal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Created or updated.
+    Updated,
+    Deleted,
+}
+
+/// A single record change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordChange {
+    pub id: String,
+    pub kind: ChangeKind,
+    #[serde(
+        rename = "deletedDate",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deleted_date: Option<String>,
+}
+
+/// Response from changes_since.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeJournal {
+    pub changes: Vec<RecordChange>,
+    /// Watermark to pass to the next call.
+    pub watermark: String,
+}
+
+/// Request for a compact schema digest of the given SObjects.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaSnapshotRequest {
+    /// SObject API names, e.g. `["Account", "Invoice__c"]`.
+    pub sobjects: Vec<String>,
+}
+
+/// Compact schema of a set of SObjects, built host-side from describes.
+///
+/// `digest` is a hash of `objects`; guests that cache a snapshot can
+/// compare digests to tell whether the schema changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaSnapshot {
+    /// Layout version of this struct. Bumped when fields change meaning.
+    pub format_version: u32,
+    /// API version the describes were taken at.
+    pub api_version: String,
+    /// Lowercase hex SHA-256 of the serialized `objects`.
+    pub digest: String,
+    /// Objects in the order they were requested.
+    pub objects: Vec<SchemaObject>,
+}
+
+impl SchemaSnapshot {
+    /// Current value of `format_version`.
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// Look up an object by API name, ignoring case.
+    pub fn object(&self, name: &str) -> Option<&SchemaObject> {
+        self.objects
+            .iter()
+            .find(|o| o.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// An SObject in a [`SchemaSnapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaObject {
+    pub name: String,
+    pub label: String,
+    pub custom: bool,
+    pub fields: Vec<SchemaField>,
+}
+
+impl SchemaObject {
+    /// Look up a field by API name, ignoring case.
+    pub fn field(&self, name: &str) -> Option<&SchemaField> {
+        self.fields
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A field in a [`SchemaObject`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaField {
+    pub name: String,
+    /// Salesforce field type, e.g. `string`, `reference`, `picklist`.
+    #[serde(rename = "type")]
+    pub field_type: String,
+    pub nillable: bool,
+    pub createable: bool,
+    pub updateable: bool,
+    /// Maximum length for text fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<i32>,
+    /// Target objects of a lookup or master-detail field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reference_to: Vec<String>,
+}
+
 // =============================================================================
 // Priority 2: Invocable Actions, Layouts, Knowledge, Standalone, etc.
 // =============================================================================
@@ -1137,107 +1968,614 @@ pub struct DataCategoriesRequest {
     pub sobject: Option<String>,
 }
 
-/// Request for app menu.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AppMenuRequest {
-    pub app_menu_type: String,
+/// Request for a published knowledge article's full content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeArticleRequest {
+    /// Article ID or URL name.
+    pub article: String,
+}
+
+/// Request to create a draft knowledge article.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateKnowledgeDraftRequest {
+    /// Knowledge article version object, e.g. `Knowledge__kav`.
+    pub article_type: String,
+    pub title: String,
+    pub url_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Additional fields on the article type, keyed by API name.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Request to publish draft knowledge article versions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishKnowledgeArticlesRequest {
+    pub version_ids: Vec<String>,
+    /// Publish as a new major version instead of replacing the current one.
+    #[serde(default)]
+    pub new_version: bool,
+}
+
+/// Request to archive published knowledge article versions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveKnowledgeArticlesRequest {
+    pub version_ids: Vec<String>,
+}
+
+/// Request to create an editable draft from a published knowledge article.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeDraftFromOnlineRequest {
+    /// The article's `KnowledgeArticleId`, not a version ID.
+    pub knowledge_article_id: String,
+    /// Take the online version down until the draft is published.
+    #[serde(default)]
+    pub unpublish: bool,
+}
+
+/// Request for app menu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppMenuRequest {
+    pub app_menu_type: String,
+}
+
+/// Request for compact layouts (multi-sobject).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactLayoutsMultiRequest {
+    pub sobject_list: String,
+}
+
+/// Request for platform event schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformEventSchemaRequest {
+    pub event_name: String,
+}
+
+/// Request to publish a platform event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishEventRequest {
+    /// Event object API name (e.g., "Order_Shipped__e").
+    pub event: String,
+    /// The event's field values.
+    pub payload: serde_json::Value,
+}
+
+/// Outcome of publishing a platform event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishEventResponse {
+    /// Whether the event was accepted for publishing.
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The event's `EventUuid`, as subscribers will see it, when reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_uuid: Option<String>,
+    /// Why the event was rejected.
+    #[serde(default)]
+    pub errors: Vec<SalesforceApiError>,
+}
+
+/// Request for set user password.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetUserPasswordRequest {
+    pub user_id: String,
+    pub password: String,
+}
+
+/// Request for read consent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadConsentRequest {
+    pub action: String,
+    pub ids: Vec<String>,
+}
+
+/// Request for write consent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteConsentRequest {
+    pub action: String,
+    pub records: Vec<ConsentWriteRecord>,
+}
+
+/// Individual consent record for write operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentWriteRecord {
+    pub id: String,
+    pub result: String,
+}
+
+/// Request for read multi consent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadMultiConsentRequest {
+    pub actions: Vec<String>,
+    pub ids: Vec<String>,
+}
+
+/// Request for get blob.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBlobRequest {
+    pub sobject: String,
+    pub id: String,
+    pub field: String,
+}
+
+/// Response for get blob (base64-encoded).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBlobResponse {
+    pub data_base64: String,
+}
+
+/// Request for get rich text image.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRichTextImageRequest {
+    pub sobject: String,
+    pub id: String,
+    pub field: String,
+    pub content_reference_id: String,
+}
+
+/// Response for get rich text image (base64-encoded).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRichTextImageResponse {
+    pub data_base64: String,
+}
+
+/// Request for get relationship.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRelationshipRequest {
+    pub sobject: String,
+    pub id: String,
+    pub relationship_name: String,
+}
+
+/// Request for search suggestions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchSuggestionsRequest {
+    pub query: String,
+    pub sobject: String,
+}
+
+/// Request for search result layouts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResultLayoutsRequest {
+    pub sobjects: Vec<String>,
+}
+
+// =============================================================================
+// Apex REST
+// =============================================================================
+
+/// Request to call a custom Apex REST endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApexRestRequest {
+    /// HTTP method (`GET`, `POST`, `PUT`, `PATCH`, `DELETE`).
+    pub method: String,
+    /// Path relative to `/services/apexrest/`, optionally with a query string.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+    #[serde(default)]
+    pub headers: std::collections::BTreeMap<String, String>,
+}
+
+// =============================================================================
+// Outbound HTTP
+// =============================================================================
+
+/// Request for an outbound HTTP call to a third-party API.
+///
+/// Header values may reference secrets registered on the host with
+/// `{{secret:NAME}}`. The host substitutes the value before sending, so the
+/// secret never enters guest memory, and only does so for hosts the secret
+/// is bound to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboundHttpRequest {
+    /// HTTP method (`GET`, `POST`, `PUT`, `PATCH`, `DELETE`).
+    pub method: String,
+    /// Absolute `https://` URL.
+    pub url: String,
+    #[serde(default)]
+    pub headers: std::collections::BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Response from an outbound HTTP call.
+///
+/// Non-2xx statuses are returned as-is rather than as errors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboundHttpResponse {
+    pub status: u16,
+    pub headers: std::collections::BTreeMap<String, String>,
+    pub body: String,
+}
+
+// =============================================================================
+// Long-running Operations
+// =============================================================================
+
+/// Kind of a long-running operation tracked by the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    /// Started by `sf_metadata_deploy`.
+    Deploy,
+    /// Started by `sf_metadata_retrieve`.
+    Retrieve,
+    /// Started by `sf_bulk_create_ingest_job`.
+    BulkIngest,
+    /// Started by `sf_bulk_create_query_job`.
+    BulkQuery,
+}
+
+/// Where a long-running operation is, across all operation kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationState {
+    /// Queued, running, or (for bulk jobs) waiting for data.
+    InProgress,
+    Succeeded,
+    Failed,
+    Canceled,
+}
+
+impl OperationState {
+    /// Whether the operation has finished.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, OperationState::InProgress)
+    }
+}
+
+/// Request for the status of a tracked operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationStatusRequest {
+    /// The ID returned when the operation was started: a deploy or
+    /// retrieve `async_process_id`, or a bulk job `id`.
+    pub handle: String,
+}
+
+/// Status of a tracked operation, from `sf_operation_status`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationStatus {
+    pub handle: String,
+    pub kind: OperationKind,
+    pub state: OperationState,
+    /// Salesforce's own status, e.g. `InProgress` or `JobComplete`.
+    pub status: String,
+    /// Components deployed or records processed so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processed: Option<i64>,
+    /// Components or tests in the deploy, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+// =============================================================================
+// Invocation Context
+// =============================================================================
+
+/// Business context the host hands to a guest invocation.
+///
+/// Modeled on the Salesforce Functions context: who the invocation runs
+/// for, plus whatever payload the host wants the plugin to act on. Guests
+/// read it with `sf_invocation_context`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InvocationContext {
+    /// Unique ID of this invocation, for correlating logs.
+    pub invocation_id: String,
+    /// 18-character ID of the org the invocation runs against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    /// 18-character ID of the user the invocation runs as.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// BCP 47 language tag of the end user, e.g. `fr-CA`. The bridge uses
+    /// it to pick translated error messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Host-defined data, such as the record or event that triggered the
+    /// invocation.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub payload: serde_json::Value,
+}
+
+impl InvocationContext {
+    /// A context with only an invocation ID.
+    pub fn new(invocation_id: impl Into<String>) -> Self {
+        Self {
+            invocation_id: invocation_id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the org the invocation runs against.
+    pub fn with_org_id(mut self, org_id: impl Into<String>) -> Self {
+        self.org_id = Some(org_id.into());
+        self
+    }
+
+    /// Set the user the invocation runs as.
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Set the end user's locale.
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Attach host-defined data for the guest.
+    pub fn with_payload(mut self, payload: serde_json::Value) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    /// Deserialize the payload into `T`.
+    pub fn payload_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(&self.payload)
+    }
 }
 
-/// Request for compact layouts (multi-sobject).
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CompactLayoutsMultiRequest {
-    pub sobject_list: String,
-}
+// =============================================================================
+// Capabilities
+// =============================================================================
 
-/// Request for platform event schema.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PlatformEventSchemaRequest {
-    pub event_name: String,
+/// What the host offers the current plugin, returned by `sf_capabilities`.
+///
+/// Guests use it to feature-detect instead of failing at call time: a host
+/// function missing from `host_functions` was compiled out of this bridge
+/// or is newer than it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// [`ABI_VERSION`] of the host.
+    pub abi_version: u32,
+    /// Version of the sf-bridge crate serving the plugin.
+    pub bridge_version: String,
+    /// Every host function registered for this plugin, by
+    /// [`host_fn_names`] name.
+    pub host_functions: Vec<String>,
+    /// Registered host functions that are scheduled for removal.
+    #[serde(default)]
+    pub deprecated: Vec<HostFunctionDeprecation>,
+    /// Policies the host enforces on this plugin.
+    pub policies: CapabilityPolicies,
+    /// Size and time limits for this plugin.
+    pub limits: CapabilityLimits,
 }
 
-/// Request for set user password.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SetUserPasswordRequest {
-    pub user_id: String,
-    pub password: String,
-}
+impl Capabilities {
+    /// Whether the host function `name` is available.
+    pub fn has(&self, name: &str) -> bool {
+        self.host_functions.iter().any(|f| f == name)
+    }
 
-/// Request for read consent.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ReadConsentRequest {
-    pub action: String,
-    pub ids: Vec<String>,
+    /// The deprecation notice for `name`, if it has one.
+    pub fn deprecation(&self, name: &str) -> Option<&HostFunctionDeprecation> {
+        self.deprecated.iter().find(|d| d.name == name)
+    }
 }
 
-/// Request for write consent.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WriteConsentRequest {
-    pub action: String,
-    pub records: Vec<ConsentWriteRecord>,
+/// A host function that still works but will be removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostFunctionDeprecation {
+    /// The deprecated host function.
+    pub name: String,
+    /// Bridge version that deprecated it.
+    pub since: String,
+    /// Host function to call instead, if there is one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+    /// Why it is going away.
+    pub note: String,
 }
 
-/// Individual consent record for write operation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConsentWriteRecord {
-    pub id: String,
-    pub result: String,
+/// Policies the host applies to the current plugin.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityPolicies {
+    /// Whether WASI imports are provided.
+    pub wasi: bool,
+    /// Hosts `sf_http_request` may reach, as configured (`*.` prefixes
+    /// match subdomains). Hosts bound to a secret are included.
+    pub http_hosts: Vec<String>,
+    /// Names of secrets the plugin may reference in `sf_http_request`.
+    pub secrets: Vec<String>,
+    /// Apex REST paths `sf_apex_rest` may call.
+    pub apex_rest_paths: Vec<String>,
+    /// Whether SOQL string values must be passed as binds.
+    pub bound_queries_only: bool,
+    /// Locale error messages are translated into, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
-/// Request for read multi consent.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ReadMultiConsentRequest {
-    pub actions: Vec<String>,
-    pub ids: Vec<String>,
+/// Limits the host applies to the current plugin.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityLimits {
+    /// Time a host function may run before it fails with `TIMEOUT`.
+    pub host_call_timeout_ms: u64,
+    /// Time allowed for deploys, retrieves, bulk data transfers, and
+    /// anonymous Apex.
+    pub long_running_timeout_ms: u64,
+    /// Host functions with their own time limit, by name.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub function_timeouts_ms: std::collections::BTreeMap<String, u64>,
+    /// Largest request body `sf_http_request` sends.
+    pub http_max_request_bytes: u64,
+    /// Largest response body `sf_http_request` returns.
+    pub http_max_response_bytes: u64,
+    /// Time allowed for each `sf_http_request`.
+    pub http_timeout_ms: u64,
+    /// Query result bytes returned before further records are held behind
+    /// a cursor. `None` when unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_memory_budget: Option<u64>,
 }
 
-/// Request for get blob.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GetBlobRequest {
-    pub sobject: String,
-    pub id: String,
-    pub field: String,
+// =============================================================================
+// Host Call Traces
+// =============================================================================
+
+/// Version of the [`HostCallTrace`] file format.
+pub const TRACE_FORMAT_VERSION: u32 = 1;
+
+/// Every host function call made during one guest invocation, in order.
+///
+/// Hosts record traces with `SfBridge::call_with_trace` and save them with
+/// [`to_json`](Self::to_json). Requests and responses are stored as JSON
+/// with secrets and policy-selected fields already redacted, so a trace
+/// taken in production can be handed to a plugin developer, who replays it
+/// with [`TraceReplay`] to reproduce the invocation without an org.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostCallTrace {
+    /// [`TRACE_FORMAT_VERSION`] of the file.
+    pub version: u32,
+    /// The guest export that was called.
+    pub function: String,
+    /// [`InvocationContext::invocation_id`] of the traced call.
+    pub invocation_id: String,
+    /// Host function calls in the order the guest made them.
+    pub calls: Vec<HostCallRecord>,
 }
 
-/// Response for get blob (base64-encoded).
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GetBlobResponse {
-    pub data_base64: String,
+impl HostCallTrace {
+    /// An empty trace for a call to the guest export `function`.
+    pub fn new(function: impl Into<String>, invocation_id: impl Into<String>) -> Self {
+        Self {
+            version: TRACE_FORMAT_VERSION,
+            function: function.into(),
+            invocation_id: invocation_id.into(),
+            calls: Vec::new(),
+        }
+    }
+
+    /// The trace as pretty-printed JSON, ready to write to a file.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a trace always serializes")
+    }
+
+    /// Load a trace written by [`to_json`](Self::to_json).
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
 }
 
-/// Request for get rich text image.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GetRichTextImageRequest {
-    pub sobject: String,
-    pub id: String,
-    pub field: String,
-    pub content_reference_id: String,
+/// One host function call in a [`HostCallTrace`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostCallRecord {
+    /// The host function, by [`host_fn_names`] name.
+    pub host_function: String,
+    /// The request the guest sent. `null` for functions without input.
+    pub request: serde_json::Value,
+    /// The [`BridgeResult`] returned to the guest, or `null` if the call
+    /// trapped.
+    pub response: serde_json::Value,
+    /// Why the call trapped instead of returning a response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trap: Option<String>,
+    /// Time the host spent on the call.
+    pub duration_ms: u64,
 }
 
-/// Response for get rich text image (base64-encoded).
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GetRichTextImageResponse {
-    pub data_base64: String,
+/// Serves the responses recorded in a [`HostCallTrace`] back in order.
+///
+/// This is the backend for replaying a trace against a guest, natively or
+/// in a test harness: each [`respond`](Self::respond) returns the next
+/// recorded response of that host function. Requests are compared with
+/// the recorded ones, so a guest that has diverged from the traced run is
+/// reported rather than fed responses meant for other calls.
+#[derive(Debug, Clone, Default)]
+pub struct TraceReplay {
+    calls: std::collections::HashMap<String, std::collections::VecDeque<HostCallRecord>>,
 }
 
-/// Request for get relationship.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GetRelationshipRequest {
-    pub sobject: String,
-    pub id: String,
-    pub relationship_name: String,
+impl TraceReplay {
+    /// A replay of `trace`.
+    pub fn new(trace: HostCallTrace) -> Self {
+        let mut calls: std::collections::HashMap<_, std::collections::VecDeque<_>> =
+            Default::default();
+        for call in trace.calls {
+            calls
+                .entry(call.host_function.clone())
+                .or_default()
+                .push_back(call);
+        }
+        Self { calls }
+    }
+
+    /// The recorded response to the next call of `host_function`.
+    ///
+    /// Fails with `REPLAY_EXHAUSTED` once every recorded call of the
+    /// function has been replayed, and with `REPLAY_MISMATCH` if `request`
+    /// differs from the recorded one. Redacted values in the recording
+    /// match anything.
+    pub fn respond(
+        &mut self,
+        host_function: &str,
+        request: &serde_json::Value,
+    ) -> Result<serde_json::Value, BridgeError> {
+        let call = self
+            .calls
+            .get_mut(host_function)
+            .and_then(|calls| calls.pop_front())
+            .ok_or_else(|| {
+                replay_error(
+                    "REPLAY_EXHAUSTED",
+                    format!("the trace has no further calls to {host_function}"),
+                )
+            })?;
+        if !replay_matches(&call.request, request) {
+            return Err(replay_error(
+                "REPLAY_MISMATCH",
+                format!("{host_function} was called with a different request than recorded"),
+            ));
+        }
+        match call.trap {
+            Some(trap) => Err(replay_error("REPLAY_TRAP", trap)),
+            None => Ok(call.response),
+        }
+    }
+
+    /// Recorded calls that have not been replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.calls.values().map(|calls| calls.len()).sum()
+    }
 }
 
-/// Request for search suggestions.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SearchSuggestionsRequest {
-    pub query: String,
-    pub sobject: String,
+fn replay_error(code: &str, message: String) -> BridgeError {
+    BridgeError {
+        code: code.to_string(),
+        message,
+        fields: Vec::new(),
+    }
 }
 
-/// Request for search result layouts.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SearchResultLayoutsRequest {
-    pub sobjects: Vec<String>,
+/// Placeholder a trace stores in place of a redacted value.
+pub const TRACE_REDACTED: &str = "[REDACTED]";
+
+/// Whether `actual` matches `recorded`, treating redacted values in the
+/// recording as wildcards.
+fn replay_matches(recorded: &serde_json::Value, actual: &serde_json::Value) -> bool {
+    use serde_json::Value;
+    match (recorded, actual) {
+        (Value::String(s), _) if s == TRACE_REDACTED => true,
+        // A secret redacted inside a longer string, such as a header.
+        (Value::String(s), Value::String(_)) => s.contains(TRACE_REDACTED) || recorded == actual,
+        (Value::Array(r), Value::Array(a)) => {
+            r.len() == a.len() && r.iter().zip(a).all(|(r, a)| replay_matches(r, a))
+        }
+        (Value::Object(r), Value::Object(a)) => {
+            r.len() == a.len()
+                && r.iter()
+                    .all(|(k, r)| a.get(k).is_some_and(|a| replay_matches(r, a)))
+        }
+        _ => recorded == actual,
+    }
 }
 
 // =============================================================================
@@ -1252,11 +2590,14 @@ pub mod host_fn_names {
     // REST API
     pub const QUERY: &str = "sf_query";
     pub const QUERY_MORE: &str = "sf_query_more";
+    pub const QUERY_CURSOR_NEXT: &str = "sf_query_cursor_next";
     pub const CREATE: &str = "sf_create";
     pub const GET: &str = "sf_get";
     pub const UPDATE: &str = "sf_update";
     pub const DELETE: &str = "sf_delete";
     pub const UPSERT: &str = "sf_upsert";
+    pub const GET_BY_EXTERNAL_ID: &str = "sf_get_by_external_id";
+    pub const DELETE_BY_EXTERNAL_ID: &str = "sf_delete_by_external_id";
     pub const DESCRIBE_GLOBAL: &str = "sf_describe_global";
     pub const DESCRIBE_SOBJECT: &str = "sf_describe_sobject";
     pub const SEARCH: &str = "sf_search";
@@ -1267,6 +2608,8 @@ pub mod host_fn_names {
     pub const UPDATE_MULTIPLE: &str = "sf_update_multiple";
     pub const GET_MULTIPLE: &str = "sf_get_multiple";
     pub const DELETE_MULTIPLE: &str = "sf_delete_multiple";
+    pub const EMPTY_RECYCLE_BIN: &str = "sf_empty_recycle_bin";
+    pub const UNDELETE: &str = "sf_undelete";
     pub const LIMITS: &str = "sf_limits";
     pub const VERSIONS: &str = "sf_versions";
 
@@ -1293,6 +2636,7 @@ pub mod host_fn_names {
     // REST API: Sync
     pub const GET_DELETED: &str = "sf_get_deleted";
     pub const GET_UPDATED: &str = "sf_get_updated";
+    pub const CHANGES_SINCE: &str = "sf_changes_since";
 
     // Bulk API
     pub const BULK_CREATE_INGEST_JOB: &str = "sf_bulk_create_ingest_job";
@@ -1303,15 +2647,25 @@ pub mod host_fn_names {
     pub const BULK_GET_JOB_RESULTS: &str = "sf_bulk_get_job_results";
     pub const BULK_DELETE_INGEST_JOB: &str = "sf_bulk_delete_ingest_job";
     pub const BULK_GET_ALL_INGEST_JOBS: &str = "sf_bulk_get_all_ingest_jobs";
+    pub const BULK_CREATE_QUERY_JOB: &str = "sf_bulk_create_query_job";
+    pub const BULK_GET_QUERY_JOB: &str = "sf_bulk_get_query_job";
     pub const BULK_ABORT_QUERY_JOB: &str = "sf_bulk_abort_query_job";
     pub const BULK_GET_QUERY_RESULTS: &str = "sf_bulk_get_query_results";
 
     // Tooling API
     pub const TOOLING_QUERY: &str = "sf_tooling_query";
+    pub const TOOLING_QUERY_MORE: &str = "sf_tooling_query_more";
     pub const TOOLING_EXECUTE_ANONYMOUS: &str = "sf_tooling_execute_anonymous";
     pub const TOOLING_GET: &str = "sf_tooling_get";
     pub const TOOLING_CREATE: &str = "sf_tooling_create";
+    pub const TOOLING_COMPOSITE: &str = "sf_tooling_composite";
+    pub const TOOLING_UPDATE: &str = "sf_tooling_update";
+    pub const TOOLING_UPSERT: &str = "sf_tooling_upsert";
     pub const TOOLING_DELETE: &str = "sf_tooling_delete";
+    pub const TOOLING_ASYNC_APEX_JOBS: &str = "sf_tooling_async_apex_jobs";
+    pub const TOOLING_ABORT_JOB: &str = "sf_tooling_abort_job";
+    pub const TOOLING_RUN_TESTS: &str = "sf_tooling_run_tests";
+    pub const TOOLING_TEST_RUN_SUMMARY: &str = "sf_tooling_test_run_summary";
 
     // Metadata API
     pub const METADATA_DEPLOY: &str = "sf_metadata_deploy";
@@ -1342,6 +2696,11 @@ pub mod host_fn_names {
     pub const KNOWLEDGE_ARTICLES: &str = "sf_knowledge_articles";
     pub const DATA_CATEGORY_GROUPS: &str = "sf_data_category_groups";
     pub const DATA_CATEGORIES: &str = "sf_data_categories";
+    pub const KNOWLEDGE_ARTICLE: &str = "sf_knowledge_article";
+    pub const CREATE_KNOWLEDGE_DRAFT: &str = "sf_create_knowledge_draft";
+    pub const PUBLISH_KNOWLEDGE_ARTICLES: &str = "sf_publish_knowledge_articles";
+    pub const ARCHIVE_KNOWLEDGE_ARTICLES: &str = "sf_archive_knowledge_articles";
+    pub const KNOWLEDGE_DRAFT_FROM_ONLINE: &str = "sf_knowledge_draft_from_online";
 
     // REST API: Standalone
     pub const TABS: &str = "sf_tabs";
@@ -1351,6 +2710,7 @@ pub mod host_fn_names {
     pub const RELEVANT_ITEMS: &str = "sf_relevant_items";
     pub const COMPACT_LAYOUTS_MULTI: &str = "sf_compact_layouts_multi";
     pub const PLATFORM_EVENT_SCHEMA: &str = "sf_platform_event_schema";
+    pub const PUBLISH_EVENT: &str = "sf_publish_event";
     pub const LIGHTNING_TOGGLE_METRICS: &str = "sf_lightning_toggle_metrics";
     pub const LIGHTNING_USAGE: &str = "sf_lightning_usage";
 
@@ -1384,11 +2744,36 @@ pub mod host_fn_names {
 
     // REST API: Composite Enhancement
     pub const COMPOSITE_GRAPH: &str = "sf_composite_graph";
+
+    // Outbound HTTP
+    pub const HTTP_REQUEST: &str = "sf_http_request";
+
+    // Apex REST
+    pub const APEX_REST: &str = "sf_apex_rest";
+
+    // Schema
+    pub const SCHEMA_SNAPSHOT: &str = "sf_schema_snapshot";
+
+    // Invocation context
+    pub const INVOCATION_CONTEXT: &str = "sf_invocation_context";
+
+    // Long-running operations
+    pub const OPERATION_STATUS: &str = "sf_operation_status";
+
+    // Capability discovery
+    pub const CAPABILITIES: &str = "sf_capabilities";
 }
 
 /// The Extism namespace used for all bridge host functions.
 pub const BRIDGE_NAMESPACE: &str = "busbar";
 
+/// Version of the host function ABI.
+///
+/// Bumped when an existing host function changes incompatibly. New host
+/// functions don't bump it; guests detect those through
+/// [`Capabilities::has`].
+pub const ABI_VERSION: u32 = 1;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1536,13 +2921,34 @@ mod tests {
 
     #[test]
     fn test_query_request_serialization() {
-        let req = QueryRequest {
-            soql: "SELECT Id, Name FROM Account".to_string(),
-            include_deleted: false,
-        };
+        let req = QueryRequest::new("SELECT Id, Name FROM Account");
         let json = serde_json::to_value(&req).unwrap();
         assert_eq!(json["soql"], "SELECT Id, Name FROM Account");
         assert_eq!(json["include_deleted"], false);
+        assert!(json.get("binds").is_none());
+    }
+
+    #[test]
+    fn test_query_request_binds_serialization() {
+        let req = QueryRequest::new("SELECT Id FROM Account WHERE Name = :name AND Id IN :ids")
+            .bind("name", SoqlBind::String("O'Brien".to_string()))
+            .bind(
+                "ids",
+                SoqlBind::List(vec![SoqlBind::Id("001000000000001AAA".to_string())]),
+            );
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(
+            json["binds"]["name"],
+            serde_json::json!({"type": "string", "value": "O'Brien"})
+        );
+        assert_eq!(
+            json["binds"]["ids"],
+            serde_json::json!({"type": "list", "value": [{"type": "id", "value": "001000000000001AAA"}]})
+        );
+        let back: QueryRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(back.binds, req.binds);
+        let null: SoqlBind = serde_json::from_value(serde_json::json!({"type": "null"})).unwrap();
+        assert_eq!(null, SoqlBind::Null);
     }
 
     #[test]
@@ -1562,6 +2968,7 @@ mod tests {
                 serde_json::json!({"Id": "001xx2", "Name": "Widget Co"}),
             ],
             next_records_url: None,
+            cursor: None,
         };
         let json = serde_json::to_value(&resp).unwrap();
         assert_eq!(json["total_size"], 2);
@@ -1577,6 +2984,7 @@ mod tests {
             done: false,
             records: vec![serde_json::json!({"Id": "001xx1"})],
             next_records_url: Some("/services/data/v62.0/query/01gxx-2000".to_string()),
+            cursor: None,
         };
         let json = serde_json::to_value(&resp).unwrap();
         assert!(!json["done"].as_bool().unwrap());
@@ -1593,6 +3001,24 @@ mod tests {
         assert_eq!(d.next_records_url, req.next_records_url);
     }
 
+    #[test]
+    fn test_query_response_cursor_defaults() {
+        let resp: QueryResponse = serde_json::from_value(serde_json::json!({
+            "total_size": 1,
+            "done": true,
+            "records": []
+        }))
+        .unwrap();
+        assert!(resp.cursor.is_none());
+
+        let req = QueryCursorRequest {
+            cursor: "c1".to_string(),
+            max_records: None,
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert!(json.get("max_records").is_none());
+    }
+
     // =========================================================================
     // REST API: CRUD
     // =========================================================================
@@ -1671,8 +3097,10 @@ mod tests {
             sobject: "Account".to_string(),
             id: "001xx000003DgAAAS".to_string(),
             record: serde_json::json!({"Name": "Updated"}),
+            precondition: None,
         };
         let json = serde_json::to_string(&req).unwrap();
+        assert!(!json.contains("precondition"));
         let d: UpdateRequest = serde_json::from_str(&json).unwrap();
         assert_eq!(d.sobject, "Account");
         assert_eq!(d.id, "001xx000003DgAAAS");
@@ -1683,10 +3111,18 @@ mod tests {
         let req = DeleteRequest {
             sobject: "Account".to_string(),
             id: "001xx000003DgAAAS".to_string(),
+            precondition: Some(WritePrecondition::IfUnmodifiedSince(
+                "Wed, 21 Oct 2015 07:28:00 GMT".to_string(),
+            )),
         };
-        let json = serde_json::to_string(&req).unwrap();
-        let d: DeleteRequest = serde_json::from_str(&json).unwrap();
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(
+            json["precondition"]["if_unmodified_since"],
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+        let d: DeleteRequest = serde_json::from_value(json).unwrap();
         assert_eq!(d.sobject, "Account");
+        assert_eq!(d.precondition, req.precondition);
     }
 
     #[test]
@@ -1703,6 +3139,19 @@ mod tests {
         assert_eq!(deserialized.external_id_value, "EXT-001");
     }
 
+    #[test]
+    fn test_external_id_request_roundtrip() {
+        let req = ExternalIdRequest {
+            sobject: "Account".to_string(),
+            external_id_field: "External_Id__c".to_string(),
+            external_id_value: "EXT-001".to_string(),
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["external_id_field"], "External_Id__c");
+        let d: ExternalIdRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(d.external_id_value, "EXT-001");
+    }
+
     #[test]
     fn test_upsert_response_created() {
         let resp = UpsertResponse {
@@ -1729,6 +3178,69 @@ mod tests {
         assert_eq!(d.sobject, "Account");
     }
 
+    #[test]
+    fn test_sobject_describe_from_rest_json() {
+        let describe: SObjectDescribe = serde_json::from_value(serde_json::json!({
+            "name": "Contact",
+            "label": "Contact",
+            "labelPlural": "Contacts",
+            "keyPrefix": "003",
+            "custom": false,
+            "createable": true,
+            "urls": {"sobject": "/services/data/v62.0/sobjects/Contact"},
+            "fields": [
+                {"name": "LastName", "label": "Last Name", "type": "string",
+                 "length": 80, "nillable": false, "createable": true,
+                 "defaultedOnCreate": false, "referenceTo": [], "picklistValues": []},
+                {"name": "AccountId", "label": "Account ID", "type": "reference",
+                 "nillable": true, "createable": true, "referenceTo": ["Account"],
+                 "relationshipName": "Account", "picklistValues": null},
+                {"name": "LeadSource", "label": "Lead Source", "type": "picklist",
+                 "nillable": true, "referenceTo": null, "picklistValues": [
+                    {"value": "Web", "label": "Web", "active": true, "defaultValue": false},
+                    {"value": "Fax", "label": "Fax", "active": false, "defaultValue": false}
+                 ]}
+            ],
+            "childRelationships": [
+                {"childSObject": "Case", "field": "ContactId", "relationshipName": "Cases"}
+            ],
+            "recordTypeInfos": [
+                {"name": "Master", "recordTypeId": "012000000000000AAA",
+                 "active": true, "available": true, "defaultRecordTypeMapping": true,
+                 "master": true}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(describe.key_prefix.as_deref(), Some("003"));
+        assert!(describe.field("lastname").unwrap().is_required_on_create());
+        assert_eq!(
+            describe.field("AccountId").unwrap().reference_to,
+            ["Account"]
+        );
+        assert!(describe
+            .field("AccountId")
+            .unwrap()
+            .picklist_values
+            .is_empty());
+        assert_eq!(
+            describe
+                .field("LeadSource")
+                .unwrap()
+                .active_picklist_values()
+                .count(),
+            1
+        );
+        assert_eq!(
+            describe.child_relationships[0].relationship_name.as_deref(),
+            Some("Cases")
+        );
+        assert_eq!(
+            describe.default_record_type().unwrap().record_type_id,
+            "012000000000000AAA"
+        );
+    }
+
     #[test]
     fn test_search_request_roundtrip() {
         let req = SearchRequest {
@@ -1763,12 +3275,15 @@ mod tests {
                     url: "/services/data/v62.0/sobjects/Account".to_string(),
                     reference_id: "NewAccount".to_string(),
                     body: Some(serde_json::json!({"Name": "Test"})),
-                },
+                    http_headers: Default::default(),
+                }
+                .with_header("Sforce-Auto-Assign", "FALSE"),
                 CompositeSubrequest {
                     method: "GET".to_string(),
                     url: "/services/data/v62.0/sobjects/Account/@{NewAccount.id}".to_string(),
                     reference_id: "GetAccount".to_string(),
                     body: None,
+                    http_headers: Default::default(),
                 },
             ],
         };
@@ -1776,6 +3291,11 @@ mod tests {
         assert!(json["all_or_none"].as_bool().unwrap());
         assert_eq!(json["subrequests"].as_array().unwrap().len(), 2);
         assert!(json["subrequests"][1].get("body").is_none());
+        assert_eq!(
+            json["subrequests"][0]["http_headers"]["Sforce-Auto-Assign"],
+            "FALSE"
+        );
+        assert!(json["subrequests"][1].get("http_headers").is_none());
     }
 
     #[test]
@@ -1792,6 +3312,25 @@ mod tests {
         assert_eq!(resp.responses[0].http_status_code, 201);
     }
 
+    #[test]
+    fn test_composite_graph_response_lookup() {
+        let json = serde_json::json!({
+            "graphs": [{
+                "graph_id": "onboarding",
+                "is_successful": true,
+                "responses": [{
+                    "body": {"id": "001xx000003DGb2AAG", "success": true},
+                    "http_status_code": 201,
+                    "reference_id": "acct"
+                }]
+            }]
+        });
+        let resp: CompositeGraphResponse = serde_json::from_value(json).unwrap();
+        let graph = &resp.graphs[0];
+        assert_eq!(graph.created_id("acct"), Some("001xx000003DGb2AAG"));
+        assert!(graph.response("contact").is_none());
+    }
+
     #[test]
     fn test_composite_batch_request_roundtrip() {
         let req = CompositeBatchRequest {
@@ -1800,6 +3339,7 @@ mod tests {
                 method: "GET".to_string(),
                 url: "/services/data/v62.0/sobjects/Account/001xx".to_string(),
                 rich_input: None,
+                http_headers: Default::default(),
             }],
         };
         let json = serde_json::to_string(&req).unwrap();
@@ -1926,6 +3466,256 @@ mod tests {
         assert_eq!(json["ids"].as_array().unwrap().len(), 2);
     }
 
+    #[test]
+    fn test_change_journal_roundtrip() {
+        let json = serde_json::json!({
+            "changes": [
+                {"id": "001xx000003DgAAAS", "kind": "updated"},
+                {"id": "001xx000003DgBBAS", "kind": "deleted", "deletedDate": "2024-01-10T10:30:00.000+0000"}
+            ],
+            "watermark": "2024-01-14T23:59:00.000+0000"
+        });
+        let journal: ChangeJournal = serde_json::from_value(json).unwrap();
+        assert_eq!(journal.changes[0].kind, ChangeKind::Updated);
+        assert!(journal.changes[0].deleted_date.is_none());
+        assert_eq!(journal.changes[1].kind, ChangeKind::Deleted);
+        let back = serde_json::to_value(&journal).unwrap();
+        assert!(back["changes"][0].get("deletedDate").is_none());
+    }
+
+    #[test]
+    fn test_schema_snapshot_lookup() {
+        let snapshot: SchemaSnapshot = serde_json::from_value(serde_json::json!({
+            "format_version": 1,
+            "api_version": "62.0",
+            "digest": "ab12",
+            "objects": [{
+                "name": "Contact",
+                "label": "Contact",
+                "custom": false,
+                "fields": [
+                    {"name": "LastName", "type": "string", "nillable": false,
+                     "createable": true, "updateable": true, "length": 80},
+                    {"name": "AccountId", "type": "reference", "nillable": true,
+                     "createable": true, "updateable": true, "reference_to": ["Account"]}
+                ]
+            }]
+        }))
+        .unwrap();
+        let contact = snapshot.object("contact").unwrap();
+        assert_eq!(contact.field("lastname").unwrap().length, Some(80));
+        assert_eq!(
+            contact.field("AccountId").unwrap().reference_to,
+            ["Account"]
+        );
+        assert!(snapshot.object("Account").is_none());
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert!(json["objects"][0]["fields"][0]
+            .get("reference_to")
+            .is_none());
+    }
+
+    #[test]
+    fn test_operation_status_serialization() {
+        let status = OperationStatus {
+            handle: "0Afxx00000000001".to_string(),
+            kind: OperationKind::BulkIngest,
+            state: OperationState::InProgress,
+            status: "UploadComplete".to_string(),
+            processed: Some(0),
+            total: None,
+            error_message: None,
+        };
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["kind"], "bulk_ingest");
+        assert_eq!(json["state"], "in_progress");
+        assert!(json.get("total").is_none());
+        assert!(!status.state.is_terminal());
+        assert!(OperationState::Canceled.is_terminal());
+    }
+
+    #[test]
+    fn test_invocation_context_roundtrip() {
+        #[derive(serde::Deserialize)]
+        struct Trigger {
+            record_id: String,
+        }
+
+        let ctx = InvocationContext::new("inv-1")
+            .with_org_id("00Dxx0000001gPLEAY")
+            .with_locale("fr-CA")
+            .with_payload(serde_json::json!({"record_id": "001xx000003DgAAAS"}));
+        let json = serde_json::to_value(&ctx).unwrap();
+        assert!(json.get("user_id").is_none());
+        let back: InvocationContext = serde_json::from_value(json).unwrap();
+        assert_eq!(back, ctx);
+        assert_eq!(
+            back.payload_as::<Trigger>().unwrap().record_id,
+            "001xx000003DgAAAS"
+        );
+
+        let bare: InvocationContext =
+            serde_json::from_value(serde_json::json!({"invocation_id": "inv-2"})).unwrap();
+        assert!(bare.payload.is_null());
+        assert!(bare.locale.is_none());
+    }
+
+    #[test]
+    fn test_capabilities_lookup() {
+        let caps = Capabilities {
+            abi_version: ABI_VERSION,
+            host_functions: vec![
+                host_fn_names::QUERY.to_string(),
+                host_fn_names::QUERY_MORE.to_string(),
+            ],
+            deprecated: vec![HostFunctionDeprecation {
+                name: host_fn_names::QUERY_MORE.to_string(),
+                since: "0.1.0".to_string(),
+                replacement: Some(host_fn_names::QUERY_CURSOR_NEXT.to_string()),
+                note: "Use query cursors".to_string(),
+            }],
+            ..Default::default()
+        };
+        assert!(caps.has(host_fn_names::QUERY));
+        assert!(!caps.has(host_fn_names::METADATA_DEPLOY));
+        assert!(caps.deprecation(host_fn_names::QUERY).is_none());
+        assert_eq!(
+            caps.deprecation(host_fn_names::QUERY_MORE)
+                .and_then(|d| d.replacement.as_deref()),
+            Some(host_fn_names::QUERY_CURSOR_NEXT)
+        );
+
+        let back: Capabilities =
+            serde_json::from_value(serde_json::to_value(&caps).unwrap()).unwrap();
+        assert_eq!(back, caps);
+    }
+
+    #[test]
+    fn test_trace_replay() {
+        use serde_json::json;
+
+        let mut trace = HostCallTrace::new("run", "inv-1");
+        for (request, response) in [
+            (
+                json!({"soql": "SELECT Id FROM Account"}),
+                json!({"ok": {"records": []}}),
+            ),
+            (
+                json!({"method": "GET", "headers": [["Authorization", "Bearer [REDACTED]"]]}),
+                json!({"ok": {"status": 200}}),
+            ),
+        ] {
+            trace.calls.push(HostCallRecord {
+                host_function: if request.get("soql").is_some() {
+                    host_fn_names::QUERY
+                } else {
+                    host_fn_names::HTTP_REQUEST
+                }
+                .to_string(),
+                request,
+                response,
+                trap: None,
+                duration_ms: 12,
+            });
+        }
+        let trace = HostCallTrace::from_json(&trace.to_json()).unwrap();
+        assert_eq!(trace.version, TRACE_FORMAT_VERSION);
+
+        let mut replay = TraceReplay::new(trace);
+        assert_eq!(replay.remaining(), 2);
+        let err = replay
+            .respond(
+                host_fn_names::QUERY,
+                &json!({"soql": "SELECT Id FROM Contact"}),
+            )
+            .unwrap_err();
+        assert_eq!(err.code, "REPLAY_MISMATCH");
+        assert_eq!(
+            replay
+                .respond(
+                    host_fn_names::HTTP_REQUEST,
+                    &json!({"method": "GET", "headers": [["Authorization", "Bearer {{secret:api}}"]]})
+                )
+                .unwrap(),
+            json!({"ok": {"status": 200}})
+        );
+        let err = replay
+            .respond(host_fn_names::HTTP_REQUEST, &json!({}))
+            .unwrap_err();
+        assert_eq!(err.code, "REPLAY_EXHAUSTED");
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[test]
+    fn test_apex_rest_request_defaults() {
+        let req: ApexRestRequest = serde_json::from_value(serde_json::json!({
+            "method": "GET",
+            "path": "Orders/v1?status=open"
+        }))
+        .unwrap();
+        assert!(req.body.is_none());
+        assert!(req.headers.is_empty());
+        let json = serde_json::to_value(&req).unwrap();
+        assert!(json.get("body").is_none());
+    }
+
+    #[test]
+    fn test_create_knowledge_draft_request_defaults() {
+        let req: CreateKnowledgeDraftRequest = serde_json::from_value(serde_json::json!({
+            "article_type": "Knowledge__kav",
+            "title": "Reset",
+            "url_name": "reset"
+        }))
+        .unwrap();
+        assert!(req.summary.is_none());
+        assert!(req.fields.is_empty());
+        let json = serde_json::to_value(&req).unwrap();
+        assert!(json.get("fields").is_none());
+
+        let req: PublishKnowledgeArticlesRequest =
+            serde_json::from_value(serde_json::json!({"version_ids": ["ka0xx0000000001AAA"]}))
+                .unwrap();
+        assert!(!req.new_version);
+    }
+
+    #[test]
+    fn test_http_request_defaults() {
+        let req: OutboundHttpRequest = serde_json::from_value(serde_json::json!({
+            "method": "GET",
+            "url": "https://api.example.com/v1/companies"
+        }))
+        .unwrap();
+        assert!(req.headers.is_empty());
+        assert!(req.body.is_none());
+
+        let req = OutboundHttpRequest {
+            method: "POST".to_string(),
+            url: "https://api.example.com/v1/enrich".to_string(),
+            headers: [(
+                "Authorization".to_string(),
+                "Bearer {{secret:example}}".to_string(),
+            )]
+            .into_iter()
+            .collect(),
+            body: Some("{}".to_string()),
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(
+            json["headers"]["Authorization"],
+            "Bearer {{secret:example}}"
+        );
+    }
+
+    #[test]
+    fn test_undelete_request() {
+        let req = UndeleteRequest {
+            ids: vec!["001xx000003Dg1".to_string()],
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["ids"][0], "001xx000003Dg1");
+    }
+
     // =========================================================================
     // REST API: Versions
     // =========================================================================
@@ -1950,23 +3740,31 @@ mod tests {
     fn test_bulk_create_ingest_job_request_roundtrip() {
         let req = BulkCreateIngestJobRequest {
             sobject: "Account".to_string(),
-            operation: "insert".to_string(),
+            operation: BulkOperation::Insert,
             external_id_field: None,
-            column_delimiter: "COMMA".to_string(),
-            line_ending: "LF".to_string(),
+            column_delimiter: ColumnDelimiter::Tab,
+            line_ending: LineEnding::Crlf,
         };
-        let json = serde_json::to_string(&req).unwrap();
-        let d: BulkCreateIngestJobRequest = serde_json::from_str(&json).unwrap();
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["operation"], "insert");
+        assert_eq!(json["column_delimiter"], "TAB");
+        assert_eq!(json["line_ending"], "CRLF");
+        let d: BulkCreateIngestJobRequest = serde_json::from_value(json).unwrap();
         assert_eq!(d.sobject, "Account");
-        assert_eq!(d.operation, "insert");
+        assert_eq!(d.operation, BulkOperation::Insert);
+        assert_eq!(d.column_delimiter, ColumnDelimiter::Tab);
     }
 
     #[test]
     fn test_bulk_create_ingest_job_defaults() {
-        let json = serde_json::json!({"sobject": "Account", "operation": "insert"});
+        let json = serde_json::json!({"sobject": "Account", "operation": "hardDelete"});
         let req: BulkCreateIngestJobRequest = serde_json::from_value(json).unwrap();
-        assert_eq!(req.column_delimiter, "COMMA");
-        assert_eq!(req.line_ending, "LF");
+        assert_eq!(req.operation, BulkOperation::HardDelete);
+        assert_eq!(req.column_delimiter, ColumnDelimiter::Comma);
+        assert_eq!(req.line_ending, LineEnding::Lf);
+
+        let json = serde_json::json!({"sobject": "Account", "operation": "merge"});
+        assert!(serde_json::from_value::<BulkCreateIngestJobRequest>(json).is_err());
     }
 
     #[test]
@@ -1981,11 +3779,14 @@ mod tests {
             created_date: Some("2024-01-15T10:30:00.000Z".to_string()),
             system_modstamp: None,
             error_message: None,
+            concurrency_mode: Some("Parallel".to_string()),
+            job_type: Some("V2Ingest".to_string()),
         };
         let json = serde_json::to_string(&resp).unwrap();
         let d: BulkJobResponse = serde_json::from_str(&json).unwrap();
         assert_eq!(d.id, "750xx");
         assert_eq!(d.number_records_processed, 100);
+        assert_eq!(d.job_type.as_deref(), Some("V2Ingest"));
     }
 
     #[test]
@@ -1996,6 +3797,25 @@ mod tests {
         let resp: BulkJobResponse = serde_json::from_value(json).unwrap();
         assert_eq!(resp.number_records_processed, 0);
         assert_eq!(resp.number_records_failed, 0);
+        assert_eq!(resp.job_type, None);
+    }
+
+    #[test]
+    fn test_bulk_get_all_ingest_jobs_request() {
+        let req: BulkGetAllIngestJobsRequest =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(req.job_type, None);
+        assert_eq!(serde_json::to_string(&req).unwrap(), "{}");
+
+        let req = BulkGetAllIngestJobsRequest {
+            is_pk_chunking_enabled: Some(false),
+            job_type: Some("Classic".to_string()),
+            concurrency_mode: None,
+        };
+        let json = serde_json::to_string(&req).unwrap();
+        let d: BulkGetAllIngestJobsRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(d.is_pk_chunking_enabled, Some(false));
+        assert_eq!(d.job_type.as_deref(), Some("Classic"));
     }
 
     #[test]
@@ -2054,6 +3874,8 @@ mod tests {
                 created_date: None,
                 system_modstamp: None,
                 error_message: None,
+                concurrency_mode: None,
+                job_type: None,
             }],
             done: true,
             next_records_url: None,
@@ -2064,6 +3886,21 @@ mod tests {
         assert_eq!(d.records.len(), 1);
     }
 
+    #[test]
+    fn test_bulk_create_query_job_defaults() {
+        let json = serde_json::json!({"soql": "SELECT Id FROM Account"});
+        let req: BulkCreateQueryJobRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(req.operation, QueryOperation::Query);
+        assert!(req.binds.is_empty());
+        assert!(!serde_json::to_string(&req).unwrap().contains("binds"));
+
+        let json = serde_json::json!({"soql": "SELECT Id FROM Account", "operation": "queryAll"});
+        let req: BulkCreateQueryJobRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(req.operation, QueryOperation::QueryAll);
+        let json = serde_json::json!({"soql": "SELECT Id FROM Account", "operation": "insert"});
+        assert!(serde_json::from_value::<BulkCreateQueryJobRequest>(json).is_err());
+    }
+
     #[test]
     fn test_bulk_query_results_request_roundtrip() {
         let req = BulkQueryResultsRequest {
@@ -2114,10 +3951,16 @@ mod tests {
     fn test_execute_anonymous_request_roundtrip() {
         let req = ExecuteAnonymousRequest {
             apex_code: "System.debug('hello');".to_string(),
+            capture_log: true,
         };
         let json = serde_json::to_string(&req).unwrap();
         let d: ExecuteAnonymousRequest = serde_json::from_str(&json).unwrap();
         assert!(d.apex_code.contains("System.debug"));
+        assert!(d.capture_log);
+
+        let d: ExecuteAnonymousRequest =
+            serde_json::from_str(r#"{"apex_code":"System.debug(1);"}"#).unwrap();
+        assert!(!d.capture_log);
     }
 
     #[test]
@@ -2130,6 +3973,7 @@ mod tests {
             exception_stack_trace: None,
             line: None,
             column: None,
+            debug_log: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         let d: ExecuteAnonymousResponse = serde_json::from_str(&json).unwrap();
@@ -2147,6 +3991,7 @@ mod tests {
             exception_stack_trace: None,
             line: Some(1),
             column: Some(5),
+            debug_log: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         let d: ExecuteAnonymousResponse = serde_json::from_str(&json).unwrap();
@@ -2165,6 +4010,7 @@ mod tests {
             exception_stack_trace: Some("AnonymousBlock: line 3".to_string()),
             line: None,
             column: None,
+            debug_log: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         let d: ExecuteAnonymousResponse = serde_json::from_str(&json).unwrap();
@@ -2195,6 +4041,62 @@ mod tests {
         assert_eq!(d.record["Body"], "public class Foo {}");
     }
 
+    #[test]
+    fn test_tooling_upsert_request_roundtrip() {
+        let req = ToolingUpsertRequest {
+            sobject: "DebugLevel".to_string(),
+            external_id_field: "DeveloperName".to_string(),
+            external_id_value: "Verbose".to_string(),
+            record: serde_json::json!({"ApexCode": "FINEST"}),
+        };
+        let json = serde_json::to_string(&req).unwrap();
+        let d: ToolingUpsertRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(d.external_id_field, "DeveloperName");
+        assert_eq!(d.record["ApexCode"], "FINEST");
+    }
+
+    #[test]
+    fn test_tooling_run_tests_request_defaults() {
+        let req: ToolingRunTestsRequest =
+            serde_json::from_str(r#"{"test_level":"RunLocalTests"}"#).unwrap();
+        assert!(req.class_names.is_empty());
+        assert_eq!(req.test_level.as_deref(), Some("RunLocalTests"));
+
+        let json = serde_json::to_string(&ToolingRunTestsRequest {
+            class_names: vec!["AccountServiceTest".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"class_names":["AccountServiceTest"]}"#);
+    }
+
+    #[test]
+    fn test_test_run_summary_roundtrip() {
+        let summary = TestRunSummary {
+            test_run_id: "707xx".to_string(),
+            complete: true,
+            success: false,
+            passed: 0,
+            failed: 1,
+            classes: vec![TestClassStatus {
+                class_name: Some("AccountServiceTest".to_string()),
+                status: "Completed".to_string(),
+                extended_status: None,
+            }],
+            results: vec![TestMethodResult {
+                class_name: Some("AccountServiceTest".to_string()),
+                method_name: Some("rejectsDuplicate".to_string()),
+                outcome: "Fail".to_string(),
+                run_time_ms: Some(80),
+                message: Some("System.AssertException".to_string()),
+                stack_trace: Some("Class.AccountServiceTest: line 12".to_string()),
+            }],
+        };
+        let json = serde_json::to_string(&summary).unwrap();
+        let d: TestRunSummary = serde_json::from_str(&json).unwrap();
+        assert_eq!(d, summary);
+    }
+
     #[test]
     fn test_tooling_delete_request_roundtrip() {
         let req = ToolingDeleteRequest {
@@ -2206,6 +4108,54 @@ mod tests {
         assert_eq!(d.id, "01pxx");
     }
 
+    #[test]
+    fn test_query_response_records_as() {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Contact {
+            #[serde(rename = "Id")]
+            id: String,
+            #[serde(rename = "Account")]
+            account: Account,
+        }
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Account {
+            #[serde(rename = "Name")]
+            name: String,
+        }
+
+        let page = QueryResponse {
+            total_size: 1,
+            done: true,
+            records: vec![serde_json::json!({
+                "attributes": {"type": "Contact", "url": "/x"},
+                "Id": "003xx",
+                "Account": {"attributes": {"type": "Account"}, "Name": "Acme"}
+            })],
+            next_records_url: None,
+            cursor: None,
+        };
+        let contacts: Vec<Contact> = page.records_as().unwrap();
+        assert_eq!(contacts[0].id, "003xx");
+        assert_eq!(contacts[0].account.name, "Acme");
+        // The original records keep their attributes.
+        assert!(page.records[0].get("attributes").is_some());
+    }
+
+    #[test]
+    fn test_async_apex_jobs_request_defaults() {
+        let d: AsyncApexJobsRequest =
+            serde_json::from_str(r#"{"statuses": ["Queued"], "older_than_minutes": 60}"#).unwrap();
+        assert_eq!(d.statuses, vec!["Queued"]);
+        assert!(d.job_types.is_empty());
+        assert_eq!(d.older_than_minutes, Some(60));
+        assert_eq!(
+            serde_json::to_string(&AsyncApexJobsRequest::default()).unwrap(),
+            "{}"
+        );
+    }
+
     // =========================================================================
     // Metadata API
     // =========================================================================
@@ -2219,11 +4169,13 @@ mod tests {
                 test_level: Some("RunLocalTests".to_string()),
                 run_tests: vec![],
                 rollback_on_error: true,
+                purge_on_delete: true,
             },
         };
         let json = serde_json::to_string(&req).unwrap();
         let d: MetadataDeployRequest = serde_json::from_str(&json).unwrap();
         assert!(d.options.check_only);
+        assert!(d.options.purge_on_delete);
     }
 
     #[test]
@@ -2234,6 +4186,7 @@ mod tests {
         assert!(opts.test_level.is_none());
         assert!(opts.run_tests.is_empty());
         assert!(opts.rollback_on_error);
+        assert!(!opts.purge_on_delete);
     }
 
     #[test]
@@ -2271,14 +4224,48 @@ mod tests {
             number_test_errors: 0,
             number_tests_completed: 10,
             number_tests_total: 10,
+            details: None,
         };
         let json = serde_json::to_string(&result).unwrap();
+        assert!(!json.contains("details"));
         let d: MetadataDeployResult = serde_json::from_str(&json).unwrap();
         assert!(d.done);
         assert!(d.success);
         assert_eq!(d.number_components_deployed, 5);
     }
 
+    #[test]
+    fn test_metadata_deploy_result_details_roundtrip() {
+        let json = serde_json::json!({
+            "id": "0Af1234",
+            "done": true,
+            "status": "Failed",
+            "success": false,
+            "details": {
+                "component_failures": [{
+                    "component_type": "ApexClass",
+                    "file_name": "classes/Invoices.cls",
+                    "line_number": 12,
+                    "column_number": 5,
+                    "problem": "Variable does not exist: total",
+                    "problem_type": "Error"
+                }],
+                "code_coverage_warnings": [{
+                    "name": "Invoices",
+                    "message": "Test coverage is 40%"
+                }]
+            }
+        });
+        let d: MetadataDeployResult = serde_json::from_value(json).unwrap();
+        let details = d.details.unwrap();
+        assert_eq!(details.component_failures[0].line_number, Some(12));
+        assert!(details.test_failures.is_empty());
+        assert_eq!(
+            details.code_coverage_warnings[0].message,
+            "Test coverage is 40%"
+        );
+    }
+
     #[test]
     fn test_metadata_retrieve_request_unpackaged() {
         let req = MetadataRetrieveRequest {
@@ -2362,10 +4349,41 @@ mod tests {
             success: true,
             zip_base64: Some("UEsDBBQ...".to_string()),
             error_message: None,
+            file_properties: vec![FileProperties {
+                full_name: "MyClass".to_string(),
+                file_name: "unpackaged/classes/MyClass.cls".to_string(),
+                component_type: "ApexClass".to_string(),
+                id: "01pxx".to_string(),
+                namespace_prefix: None,
+                manageable_state: Some("unmanaged".to_string()),
+                last_modified_date: None,
+                last_modified_by_name: None,
+            }],
+            messages: vec![RetrieveMessage {
+                file_name: "unpackaged/package.xml".to_string(),
+                problem: "Entity of type 'ApexClass' named 'Missing' cannot be found".to_string(),
+            }],
         };
         let json = serde_json::to_string(&result).unwrap();
         let d: MetadataRetrieveResult = serde_json::from_str(&json).unwrap();
         assert!(d.zip_base64.is_some());
+        assert_eq!(d.file_properties[0].component_type, "ApexClass");
+        assert_eq!(d.messages[0].file_name, "unpackaged/package.xml");
+    }
+
+    #[test]
+    fn test_metadata_retrieve_result_without_lists() {
+        let json = serde_json::json!({
+            "id": "09S1234",
+            "done": false,
+            "status": "InProgress",
+            "success": false
+        });
+        let d: MetadataRetrieveResult = serde_json::from_value(json).unwrap();
+        assert!(d.file_properties.is_empty());
+        assert!(d.messages.is_empty());
+        let back = serde_json::to_value(&d).unwrap();
+        assert!(back.get("file_properties").is_none());
     }
 
     #[test]
@@ -2435,6 +4453,7 @@ mod tests {
         let names = [
             QUERY,
             QUERY_MORE,
+            QUERY_CURSOR_NEXT,
             CREATE,
             GET,
             UPDATE,
@@ -2476,13 +4495,23 @@ mod tests {
             BULK_GET_JOB_RESULTS,
             BULK_DELETE_INGEST_JOB,
             BULK_GET_ALL_INGEST_JOBS,
+            BULK_CREATE_QUERY_JOB,
+            BULK_GET_QUERY_JOB,
             BULK_ABORT_QUERY_JOB,
             BULK_GET_QUERY_RESULTS,
             TOOLING_QUERY,
+            TOOLING_QUERY_MORE,
             TOOLING_EXECUTE_ANONYMOUS,
             TOOLING_GET,
             TOOLING_CREATE,
+            TOOLING_COMPOSITE,
+            TOOLING_UPDATE,
+            TOOLING_UPSERT,
             TOOLING_DELETE,
+            TOOLING_ASYNC_APEX_JOBS,
+            TOOLING_ABORT_JOB,
+            TOOLING_RUN_TESTS,
+            TOOLING_TEST_RUN_SUMMARY,
             METADATA_DEPLOY,
             METADATA_CHECK_DEPLOY_STATUS,
             METADATA_RETRIEVE,
@@ -2506,6 +4535,11 @@ mod tests {
             KNOWLEDGE_ARTICLES,
             DATA_CATEGORY_GROUPS,
             DATA_CATEGORIES,
+            KNOWLEDGE_ARTICLE,
+            CREATE_KNOWLEDGE_DRAFT,
+            PUBLISH_KNOWLEDGE_ARTICLES,
+            ARCHIVE_KNOWLEDGE_ARTICLES,
+            KNOWLEDGE_DRAFT_FROM_ONLINE,
             TABS,
             THEME,
             APP_MENU,
@@ -2513,6 +4547,7 @@ mod tests {
             RELEVANT_ITEMS,
             COMPACT_LAYOUTS_MULTI,
             PLATFORM_EVENT_SCHEMA,
+            PUBLISH_EVENT,
             LIGHTNING_TOGGLE_METRICS,
             LIGHTNING_USAGE,
             GET_USER_PASSWORD_STATUS,
@@ -2532,12 +4567,23 @@ mod tests {
             SEARCH_SCOPE_ORDER,
             SEARCH_RESULT_LAYOUTS,
             COMPOSITE_GRAPH,
+            GET_BY_EXTERNAL_ID,
+            DELETE_BY_EXTERNAL_ID,
+            EMPTY_RECYCLE_BIN,
+            UNDELETE,
+            CHANGES_SINCE,
+            HTTP_REQUEST,
+            APEX_REST,
+            SCHEMA_SNAPSHOT,
+            INVOCATION_CONTEXT,
+            OPERATION_STATUS,
+            CAPABILITIES,
         ];
         let mut unique = std::collections::HashSet::new();
         for name in &names {
             assert!(unique.insert(name), "duplicate host function name: {name}");
         }
-        assert_eq!(unique.len(), 98);
+        assert_eq!(unique.len(), 126);
     }
 
     #[test]
@@ -2546,6 +4592,7 @@ mod tests {
         let names = [
             QUERY,
             QUERY_MORE,
+            QUERY_CURSOR_NEXT,
             CREATE,
             GET,
             UPDATE,
@@ -2587,13 +4634,23 @@ mod tests {
             BULK_GET_JOB_RESULTS,
             BULK_DELETE_INGEST_JOB,
             BULK_GET_ALL_INGEST_JOBS,
+            BULK_CREATE_QUERY_JOB,
+            BULK_GET_QUERY_JOB,
             BULK_ABORT_QUERY_JOB,
             BULK_GET_QUERY_RESULTS,
             TOOLING_QUERY,
+            TOOLING_QUERY_MORE,
             TOOLING_EXECUTE_ANONYMOUS,
             TOOLING_GET,
             TOOLING_CREATE,
+            TOOLING_COMPOSITE,
+            TOOLING_UPDATE,
+            TOOLING_UPSERT,
             TOOLING_DELETE,
+            TOOLING_ASYNC_APEX_JOBS,
+            TOOLING_ABORT_JOB,
+            TOOLING_RUN_TESTS,
+            TOOLING_TEST_RUN_SUMMARY,
             METADATA_DEPLOY,
             METADATA_CHECK_DEPLOY_STATUS,
             METADATA_RETRIEVE,
@@ -2617,6 +4674,11 @@ mod tests {
             KNOWLEDGE_ARTICLES,
             DATA_CATEGORY_GROUPS,
             DATA_CATEGORIES,
+            KNOWLEDGE_ARTICLE,
+            CREATE_KNOWLEDGE_DRAFT,
+            PUBLISH_KNOWLEDGE_ARTICLES,
+            ARCHIVE_KNOWLEDGE_ARTICLES,
+            KNOWLEDGE_DRAFT_FROM_ONLINE,
             TABS,
             THEME,
             APP_MENU,
@@ -2624,6 +4686,7 @@ mod tests {
             RELEVANT_ITEMS,
             COMPACT_LAYOUTS_MULTI,
             PLATFORM_EVENT_SCHEMA,
+            PUBLISH_EVENT,
             LIGHTNING_TOGGLE_METRICS,
             LIGHTNING_USAGE,
             GET_USER_PASSWORD_STATUS,
@@ -2643,6 +4706,17 @@ mod tests {
             SEARCH_SCOPE_ORDER,
             SEARCH_RESULT_LAYOUTS,
             COMPOSITE_GRAPH,
+            GET_BY_EXTERNAL_ID,
+            DELETE_BY_EXTERNAL_ID,
+            EMPTY_RECYCLE_BIN,
+            UNDELETE,
+            CHANGES_SINCE,
+            HTTP_REQUEST,
+            APEX_REST,
+            SCHEMA_SNAPSHOT,
+            INVOCATION_CONTEXT,
+            OPERATION_STATUS,
+            CAPABILITIES,
         ];
         for name in &names {
             assert!(name.starts_with("sf_"), "{name} must start with sf_");
@@ -0,0 +1,168 @@
+//! Change Data Capture to webhook forwarder for the busbar-sf bridge.
+//!
+//! The host subscribes to a change event channel and invokes this plugin
+//! once per event, passing the event as the invocation payload. The
+//! plugin reshapes it into a compact JSON body and posts it to a webhook
+//! through `sf_http_request`. The webhook's token is a host secret
+//! referenced as `{{secret:NAME}}`, so it never enters the sandbox.
+//!
+//! Delivery failures are reported in the output rather than as errors, so
+//! the host can decide whether to retry or dead-letter the event.
+//!
+//! ## Building
+//!
+//! ```sh
+//! cargo build --manifest-path examples/cdc-webhook-forwarder/Cargo.toml \
+//!     --target wasm32-unknown-unknown --release
+//! ```
+//!
+//! ## Input
+//!
+//! ```json
+//! {
+//!   "webhook_url": "https://hooks.example.com/salesforce",
+//!   "secret": "webhook_token",
+//!   "objects": ["Account", "Opportunity"]
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use busbar_sf_guest_sdk::*;
+use extism_pdk::*;
+use serde::{Deserialize, Serialize};
+
+/// Input for the forward_change function.
+#[derive(Debug, Deserialize)]
+struct ForwardInput {
+    /// Where to post events.
+    webhook_url: String,
+    /// Host secret holding the webhook's bearer token.
+    #[serde(default)]
+    secret: Option<String>,
+    /// Objects to forward. Empty forwards every object.
+    #[serde(default)]
+    objects: Vec<String>,
+}
+
+/// The header Salesforce puts on every change event.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChangeEventHeader {
+    entity_name: String,
+    change_type: String,
+    record_ids: Vec<String>,
+    #[serde(default)]
+    changed_fields: Vec<String>,
+    commit_timestamp: i64,
+    #[serde(default)]
+    transaction_key: Option<String>,
+}
+
+/// A change event: the header plus the new field values.
+#[derive(Debug, Deserialize)]
+struct ChangeEvent {
+    #[serde(rename = "ChangeEventHeader")]
+    header: ChangeEventHeader,
+    #[serde(flatten)]
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Body posted to the webhook.
+#[derive(Debug, Serialize)]
+struct WebhookBody<'a> {
+    object: &'a str,
+    change: &'a str,
+    ids: &'a [String],
+    committed_at: i64,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Output from the forward_change function.
+#[derive(Debug, Default, Serialize)]
+struct ForwardOutput {
+    forwarded: bool,
+    /// Webhook response status, when the request was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    /// Bridge error code, when the request was refused or failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// Why the event was not forwarded, when it was filtered out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skipped: Option<String>,
+}
+
+/// Forward the change event in the invocation payload to a webhook.
+#[plugin_fn]
+pub fn forward_change(input: String) -> FnResult<Json<ForwardOutput>> {
+    let input: ForwardInput =
+        serde_json::from_str(&input).map_err(|e| Error::msg(format!("invalid input: {e}")))?;
+    let context = invocation_context()?;
+    let event: ChangeEvent = context
+        .payload_as()
+        .map_err(|e| Error::msg(format!("payload is not a change event: {e}")))?;
+    let header = &event.header;
+
+    if !input.objects.is_empty() && !input.objects.contains(&header.entity_name) {
+        return Ok(Json(ForwardOutput {
+            skipped: Some(format!("{} is not watched", header.entity_name)),
+            ..Default::default()
+        }));
+    }
+
+    // Updates carry only the changed fields; creates carry every field.
+    let fields = event
+        .fields
+        .into_iter()
+        .filter(|(name, _)| {
+            header.changed_fields.is_empty() || header.changed_fields.contains(name)
+        })
+        .collect();
+    let body = WebhookBody {
+        object: &header.entity_name,
+        change: &header.change_type,
+        ids: &header.record_ids,
+        committed_at: header.commit_timestamp,
+        fields,
+    };
+
+    let mut headers = BTreeMap::new();
+    headers.insert("Content-Type".to_string(), "application/json".to_string());
+    headers.insert(
+        "Idempotency-Key".to_string(),
+        header
+            .transaction_key
+            .clone()
+            .unwrap_or(context.invocation_id),
+    );
+    if let Some(secret) = &input.secret {
+        headers.insert(
+            "Authorization".to_string(),
+            format!("Bearer {{{{secret:{secret}}}}}"),
+        );
+    }
+
+    let request = OutboundHttpRequest {
+        method: "POST".to_string(),
+        url: input.webhook_url,
+        headers,
+        body: Some(serde_json::to_string(&body)?),
+    };
+    let output = match http_request(request) {
+        Ok(response) => ForwardOutput {
+            forwarded: (200..300).contains(&response.status),
+            status: Some(response.status),
+            ..Default::default()
+        },
+        Err(e) => {
+            let message = e.to_string();
+            let code = message.split_once(':').map_or(&*message, |(code, _)| code);
+            ForwardOutput {
+                error: Some(code.to_string()),
+                ..Default::default()
+            }
+        }
+    };
+    Ok(Json(output))
+}
@@ -0,0 +1,162 @@
+//! Duplicate finder plugin for the busbar-sf bridge.
+//!
+//! Scans an object for records that share a match field (for example
+//! `Contact.Email`) and reports each group of duplicates, keeping the
+//! oldest record. Values are compared after trimming and lowercasing.
+//!
+//! Records are read in match-field order, so a group is complete as soon
+//! as the value changes and only the current group is held in memory.
+//! When the host caps query memory, the rest of the result is streamed
+//! through `sf_query_cursor_next` instead of loaded in one go.
+//!
+//! ## Building
+//!
+//! ```sh
+//! cargo build --manifest-path examples/dedupe-plugin/Cargo.toml \
+//!     --target wasm32-unknown-unknown --release
+//! ```
+//!
+//! ## Input
+//!
+//! ```json
+//! {"sobject": "Contact", "match_field": "Email"}
+//! ```
+
+use busbar_sf_guest_sdk::*;
+use extism_pdk::*;
+use serde::{Deserialize, Serialize};
+
+/// Records read per `sf_query_cursor_next` call.
+const CURSOR_CHUNK: u32 = 200;
+
+/// Input for the find_duplicates function.
+#[derive(Debug, Deserialize)]
+struct FindDuplicatesInput {
+    /// Object to scan, e.g. `Contact`.
+    sobject: String,
+    /// Field whose value identifies a duplicate, e.g. `Email`.
+    match_field: String,
+}
+
+/// Records that share a match value.
+#[derive(Debug, Serialize)]
+struct DuplicateGroup {
+    /// The normalized match value.
+    key: String,
+    /// The oldest record, which the others duplicate.
+    keep: String,
+    /// The remaining records, oldest first.
+    duplicates: Vec<String>,
+}
+
+/// Output from the find_duplicates function.
+#[derive(Debug, Default, Serialize)]
+struct FindDuplicatesOutput {
+    /// Records read.
+    scanned: u64,
+    /// Whether the host held part of the result behind a cursor.
+    streamed: bool,
+    groups: Vec<DuplicateGroup>,
+}
+
+/// Groups consecutive records with the same match value.
+struct Scanner {
+    match_field: String,
+    current: Option<DuplicateGroup>,
+    output: FindDuplicatesOutput,
+}
+
+impl Scanner {
+    fn new(match_field: String) -> Self {
+        Self {
+            match_field,
+            current: None,
+            output: FindDuplicatesOutput::default(),
+        }
+    }
+
+    fn feed(&mut self, records: &[serde_json::Value]) {
+        for record in records {
+            self.output.scanned += 1;
+            let (Some(id), Some(value)) =
+                (record["Id"].as_str(), record[&self.match_field].as_str())
+            else {
+                continue;
+            };
+            let key = value.trim().to_lowercase();
+            match &mut self.current {
+                Some(group) if group.key == key => group.duplicates.push(id.to_string()),
+                _ => {
+                    self.flush();
+                    self.current = Some(DuplicateGroup {
+                        key,
+                        keep: id.to_string(),
+                        duplicates: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(group) = self.current.take() {
+            if !group.duplicates.is_empty() {
+                self.output.groups.push(group);
+            }
+        }
+    }
+
+    fn finish(mut self) -> FindDuplicatesOutput {
+        self.flush();
+        self.output
+    }
+}
+
+/// Whether `name` can be written into SOQL as an object or field name.
+fn is_api_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Find records of `sobject` that share `match_field`.
+#[plugin_fn]
+pub fn find_duplicates(input: String) -> FnResult<Json<FindDuplicatesOutput>> {
+    let input: FindDuplicatesInput =
+        serde_json::from_str(&input).map_err(|e| Error::msg(format!("invalid input: {e}")))?;
+    if !is_api_name(&input.sobject) || !is_api_name(&input.match_field) {
+        return Err(Error::msg("sobject and match_field must be API names").into());
+    }
+
+    let soql = format!(
+        "SELECT Id, {field} FROM {sobject} WHERE {field} != null ORDER BY {field}, CreatedDate, Id",
+        field = input.match_field,
+        sobject = input.sobject,
+    );
+
+    let mut scanner = Scanner::new(input.match_field);
+    let mut streamed = false;
+    let mut page = query(&soql)?;
+    loop {
+        scanner.feed(&page.records);
+        if let Some(cursor) = page.cursor.take() {
+            // The host holds everything that is left; read it in chunks.
+            streamed = true;
+            loop {
+                let chunk = query_cursor_next(&cursor, Some(CURSOR_CHUNK))?;
+                scanner.feed(&chunk.records);
+                if chunk.done {
+                    break;
+                }
+            }
+            break;
+        }
+        match page.next_records_url.take() {
+            Some(url) => page = query_more(&url)?,
+            None => break,
+        }
+    }
+
+    let mut output = scanner.finish();
+    output.streamed = streamed;
+    Ok(Json(output))
+}
@@ -0,0 +1,143 @@
+//! Deploy gatekeeper plugin for the busbar-sf bridge.
+//!
+//! Decides whether a metadata deployment (typically a check-only
+//! validation) may be promoted. A deployment is rejected when components
+//! failed, tests failed or did not run, or org-wide Apex coverage is below
+//! the required minimum.
+//!
+//! Coverage is read through the Tooling API. The plugin checks the host's
+//! capabilities first and rejects, rather than failing, when the host
+//! does not offer it.
+//!
+//! ## Building
+//!
+//! ```sh
+//! cargo build --manifest-path examples/deploy-gatekeeper/Cargo.toml \
+//!     --target wasm32-unknown-unknown --release
+//! ```
+//!
+//! ## Input
+//!
+//! ```json
+//! {"async_process_id": "0Af5g00000AbCdE", "min_coverage": 75.0}
+//! ```
+
+use busbar_sf_guest_sdk::*;
+use extism_pdk::*;
+use serde::{Deserialize, Serialize};
+
+/// Input for the gate_deploy function.
+#[derive(Debug, Deserialize)]
+struct GateInput {
+    /// ID returned when the deployment was started.
+    async_process_id: String,
+    /// Lowest org-wide Apex coverage to accept, in percent.
+    #[serde(default)]
+    min_coverage: Option<f64>,
+    /// Reject deployments that ran no tests.
+    #[serde(default = "default_true")]
+    require_tests: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// The gatekeeper's decision.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Verdict {
+    Approve,
+    Reject,
+    /// The deployment has not finished; ask again later.
+    Pending,
+}
+
+/// Output from the gate_deploy function.
+#[derive(Debug, Serialize)]
+struct GateOutput {
+    verdict: Verdict,
+    /// Deployment status as reported by the Metadata API.
+    deploy_status: String,
+    /// Why the deployment was rejected.
+    reasons: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    org_coverage: Option<f64>,
+}
+
+/// Org-wide Apex coverage, if the host offers the Tooling API.
+fn org_coverage() -> Result<Option<f64>, Error> {
+    if !capabilities()?.has(host_fn_names::TOOLING_QUERY) {
+        return Ok(None);
+    }
+    let result = tooling_query("SELECT PercentCovered FROM ApexOrgWideCoverage")?;
+    Ok(result
+        .records
+        .first()
+        .and_then(|r| r["PercentCovered"].as_f64()))
+}
+
+/// Decide whether the deployment `async_process_id` may be promoted.
+#[plugin_fn]
+pub fn gate_deploy(input: String) -> FnResult<Json<GateOutput>> {
+    let input: GateInput =
+        serde_json::from_str(&input).map_err(|e| Error::msg(format!("invalid input: {e}")))?;
+    let deploy = metadata_check_deploy_status(&input.async_process_id, true)?;
+
+    if !deploy.done {
+        return Ok(Json(GateOutput {
+            verdict: Verdict::Pending,
+            deploy_status: deploy.status,
+            reasons: Vec::new(),
+            org_coverage: None,
+        }));
+    }
+
+    let mut reasons = Vec::new();
+    if !deploy.success {
+        reasons.push(
+            deploy
+                .error_message
+                .clone()
+                .unwrap_or_else(|| "deployment did not succeed".to_string()),
+        );
+    }
+    if deploy.number_component_errors > 0 {
+        reasons.push(format!(
+            "{} of {} components failed",
+            deploy.number_component_errors, deploy.number_components_total
+        ));
+    }
+    if deploy.number_test_errors > 0 {
+        reasons.push(format!(
+            "{} of {} tests failed",
+            deploy.number_test_errors, deploy.number_tests_total
+        ));
+    }
+    if input.require_tests && deploy.number_tests_total == 0 {
+        reasons.push("no tests ran".to_string());
+    }
+
+    let mut coverage = None;
+    if let Some(min) = input.min_coverage {
+        coverage = org_coverage()?;
+        match coverage {
+            Some(percent) if percent < min => {
+                reasons.push(format!("org coverage {percent}% is below {min}%"));
+            }
+            Some(_) => {}
+            None => reasons.push("org coverage is not available".to_string()),
+        }
+    }
+
+    Ok(Json(GateOutput {
+        verdict: if reasons.is_empty() {
+            Verdict::Approve
+        } else {
+            Verdict::Reject
+        },
+        deploy_status: deploy.status,
+        reasons,
+        org_coverage: coverage,
+    }))
+}
@@ -95,6 +95,7 @@ pub fn create_account_with_contact(input: String) -> FnResult<Json<CompositeResp
                 body: Some(serde_json::json!({
                     "Name": account_name
                 })),
+                http_headers: Default::default(),
             },
             CompositeSubrequest {
                 method: "POST".to_string(),
@@ -104,6 +105,7 @@ pub fn create_account_with_contact(input: String) -> FnResult<Json<CompositeResp
                     "LastName": contact_name,
                     "AccountId": "@{NewAccount.id}"
                 })),
+                http_headers: Default::default(),
             },
         ],
     };
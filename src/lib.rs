@@ -20,6 +20,7 @@
 //! - **busbar-sf-tooling** - Tooling API: Apex operations, debug logs, code coverage
 //! - **busbar-sf-bulk** - Bulk API 2.0: Large-scale data operations
 //! - **busbar-sf-metadata** - Metadata API: Deploy and retrieve metadata
+//! - **busbar-sf-org-config** - Org Config: plan and apply remote sites, named credentials, custom metadata, and custom settings
 //!
 //! ## Quick Start
 //!
@@ -70,6 +71,9 @@ pub use busbar_sf_rest as rest;
 #[cfg(feature = "tooling")]
 pub use busbar_sf_tooling as tooling;
 
+#[cfg(feature = "org-config")]
+pub use busbar_sf_org_config as org_config;
+
 // Re-export commonly used types at the top level
 #[cfg(feature = "auth")]
 pub use busbar_sf_auth::{Credentials, SalesforceCredentials};
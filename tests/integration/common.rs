@@ -49,7 +49,7 @@ pub async fn ensure_test_accounts(client: &busbar_sf_rest::SalesforceRestClient)
                 .create("Account", &serde_json::json!({"Name": name}))
                 .await
                 .expect("Create test account should succeed");
-            ids.push(id);
+            ids.push(id.into());
         }
     }
 
@@ -3,6 +3,7 @@
 use super::common::{get_credentials, retry_on_propagation_lag};
 use busbar_sf_auth::Credentials;
 use busbar_sf_rest::{CompositeRequest, CompositeSubrequest, QueryBuilder, SalesforceRestClient};
+use busbar_sf_testing::TestOrgFactory;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -49,6 +50,7 @@ async fn test_rest_composite_api() {
                         chrono::Utc::now().timestamp_millis()
                     )
                 })),
+                http_headers: Default::default(),
             },
             CompositeSubrequest {
                 method: "GET".to_string(),
@@ -58,6 +60,7 @@ async fn test_rest_composite_api() {
                 ),
                 reference_id: "GetNewAccount".to_string(),
                 body: None,
+                http_headers: Default::default(),
             },
         ],
     };
@@ -113,7 +116,10 @@ async fn test_rest_batch_operations() {
 
     assert_eq!(create_results.len(), 3, "Should create 3 accounts");
 
-    let ids: Vec<String> = create_results.iter().filter_map(|r| r.id.clone()).collect();
+    let ids: Vec<String> = create_results
+        .iter()
+        .filter_map(|r| r.id.as_ref().map(ToString::to_string))
+        .collect();
     assert_eq!(ids.len(), 3, "Should have 3 account IDs");
 
     let id_refs: Vec<&str> = ids.iter().map(|s| s.as_str()).collect();
@@ -442,6 +448,7 @@ async fn test_rest_composite_error_subrequest() {
             url: format!("/services/data/v{}/sobjects/Nope__c", creds.api_version()),
             reference_id: "BadRequest".to_string(),
             body: None,
+            http_headers: Default::default(),
         }],
     };
 
@@ -756,6 +763,7 @@ async fn test_rest_composite_graph_api() {
                 body: Some(serde_json::json!({
                     "Name": format!("Graph Test Account {}", chrono::Utc::now().timestamp_millis())
                 })),
+                http_headers: Default::default(),
             }],
         }],
     };
@@ -1144,17 +1152,14 @@ async fn test_process_rules_trigger() {
         .expect("Failed to create REST client");
 
     // Name must start with "BusbarIntTest_ProcessRule" to match the deployed workflow rule
-    let test_name = format!(
-        "BusbarIntTest_ProcessRule_{}",
-        chrono::Utc::now().timestamp_millis()
-    );
-    let account_id = client
-        .create("Account", &serde_json::json!({"Name": test_name}))
+    let factory = TestOrgFactory::new(client.clone()).with_prefix("BusbarIntTest");
+    let account = factory
+        .create_named("Account", "Name", "ProcessRule", serde_json::json!({}))
         .await
         .expect("Account creation should succeed");
 
     let request = busbar_sf_rest::ProcessRuleRequest {
-        context_ids: vec![account_id.clone()],
+        context_ids: vec![account.id().clone()],
     };
 
     let result = client
@@ -1162,8 +1167,6 @@ async fn test_process_rules_trigger() {
         .await
         .expect("trigger_process_rules should succeed");
     assert!(result.success, "Process rule trigger should succeed");
-
-    let _ = client.delete("Account", &account_id).await;
 }
 
 #[tokio::test]
@@ -1172,8 +1175,13 @@ async fn test_process_rules_error_invalid_id() {
     let client = SalesforceRestClient::new(creds.instance_url(), creds.access_token())
         .expect("Failed to create REST client");
 
+    assert!("bad-id-not-valid"
+        .parse::<busbar_sf_rest::SalesforceId>()
+        .is_err());
+
+    // Well-formed, but no such record.
     let request = busbar_sf_rest::ProcessRuleRequest {
-        context_ids: vec!["bad-id-not-valid".to_string()],
+        context_ids: vec!["001000000000000AAA".parse().unwrap()],
     };
 
     let result = client.trigger_process_rules(&request).await;
@@ -1207,15 +1215,14 @@ async fn test_approvals_submit() {
     let client = SalesforceRestClient::new(creds.instance_url(), creds.access_token())
         .expect("Failed to create REST client");
 
-    let test_name = format!("Approval Test {}", chrono::Utc::now().timestamp_millis());
-    let account_id = client
-        .create("Account", &serde_json::json!({"Name": test_name}))
+    let account = TestOrgFactory::new(client.clone())
+        .create_named("Account", "Name", "Approval", serde_json::json!({}))
         .await
         .expect("Account creation should succeed");
 
     let request = busbar_sf_rest::ApprovalRequest {
         action_type: busbar_sf_rest::ApprovalActionType::Submit,
-        context_id: account_id.clone(),
+        context_id: account.id().clone(),
         context_actor_id: None,
         comments: Some("Integration test submission".to_string()),
         next_approver_ids: None,
@@ -1228,8 +1235,6 @@ async fn test_approvals_submit() {
         .await
         .expect("submit_approval should succeed");
     assert!(result.success, "Approval submission should succeed");
-
-    let _ = client.delete("Account", &account_id).await;
 }
 
 #[tokio::test]
@@ -1238,9 +1243,10 @@ async fn test_approvals_error_invalid_id() {
     let client = SalesforceRestClient::new(creds.instance_url(), creds.access_token())
         .expect("Failed to create REST client");
 
+    // Well-formed, but no such record.
     let request = busbar_sf_rest::ApprovalRequest {
         action_type: busbar_sf_rest::ApprovalActionType::Submit,
-        context_id: "bad-id-not-valid".to_string(),
+        context_id: "001000000000000AAA".parse().unwrap(),
         context_actor_id: None,
         comments: None,
         next_approver_ids: None,
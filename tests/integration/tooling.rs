@@ -131,6 +131,7 @@ async fn test_tooling_composite_api() {
                 ),
                 reference_id: "ApexClassQuery".to_string(),
                 body: None,
+                http_headers: Default::default(),
             },
             CompositeSubrequest {
                 method: "GET".to_string(),
@@ -140,6 +141,7 @@ async fn test_tooling_composite_api() {
                 ),
                 reference_id: "DebugLevelQuery".to_string(),
                 body: None,
+                http_headers: Default::default(),
             },
         ],
     };
@@ -178,6 +180,7 @@ async fn test_tooling_composite_batch() {
                 rich_input: None,
                 binary_part_name: None,
                 binary_part_name_alias: None,
+                http_headers: Default::default(),
             },
             CompositeBatchSubrequest {
                 method: "GET".to_string(),
@@ -188,6 +191,7 @@ async fn test_tooling_composite_batch() {
                 rich_input: None,
                 binary_part_name: None,
                 binary_part_name_alias: None,
+                http_headers: Default::default(),
             },
         ],
     };
@@ -193,12 +193,14 @@ pub fn test_composite(input: String) -> FnResult<Json<serde_json::Value>> {
                 url: format!("/services/data/{}/sobjects/Account", API_VERSION),
                 reference_id: "newAccount".to_string(),
                 body: Some(json!({"Name": account_name})),
+                http_headers: Default::default(),
             },
             CompositeSubrequest {
                 method: "DELETE".to_string(),
                 url: format!("/services/data/{}/sobjects/Account/@{{newAccount.id}}", API_VERSION),
                 reference_id: "deleteAccount".to_string(),
                 body: None,
+                http_headers: Default::default(),
             },
         ],
     };
@@ -236,6 +238,7 @@ pub fn test_composite_batch(input: String) -> FnResult<Json<serde_json::Value>>
             method: "POST".to_string(),
             url: format!("/services/data/{}/sobjects/Account", API_VERSION),
             rich_input: Some(json!({"Name": name})),
+            http_headers: Default::default(),
         });
     }
 
@@ -426,16 +429,13 @@ pub fn test_describe_sobject(input: String) -> FnResult<Json<serde_json::Value>>
     let sobject = req["sobject"].as_str().unwrap_or("Account");
 
     match describe_sobject(sobject) {
-        Ok(result) => {
-            let fields_count = result["fields"].as_array().map(|a| a.len()).unwrap_or(0);
-            Ok(Json(json!({
-                "success": true,
-                "data": {
-                    "name": result["name"],
-                    "fields_count": fields_count
-                }
-            })))
-        }
+        Ok(result) => Ok(Json(json!({
+            "success": true,
+            "data": {
+                "name": result.name,
+                "fields_count": result.fields.len()
+            }
+        }))),
         Err(e) => Ok(Json(json!({
             "success": false,
             "error": e.to_string()
@@ -743,11 +743,18 @@ pub fn test_versions(_input: String) -> FnResult<Json<serde_json::Value>> {
 pub fn test_bulk_ingest(input: String) -> FnResult<Json<serde_json::Value>> {
     let req: serde_json::Value = serde_json::from_str(&input)?;
     let sobject = req["sobject"].as_str().unwrap_or("Account");
-    let operation = req["operation"].as_str().unwrap_or("insert");
+    let operation =
+        serde_json::from_value(req["operation"].clone()).unwrap_or(BulkOperation::Insert);
     let csv_data = req["csv_data"].as_str().unwrap_or("");
 
     // 1. Create job
-    let job = match bulk_create_ingest_job(sobject, operation, None, "COMMA", "LF") {
+    let job = match bulk_create_ingest_job(
+        sobject,
+        operation,
+        None,
+        ColumnDelimiter::Comma,
+        LineEnding::Lf,
+    ) {
         Ok(j) => j,
         Err(e) => {
             return Ok(Json(json!({